@@ -0,0 +1,214 @@
+//! Agent health and tool activity for the dock indicator.
+//!
+//! Docks get an `AgentHealth` when they register and whenever it changes,
+//! plus a `ToolStarted` / `ToolFinished` pair around every tool call so they
+//! can show that the agent is busy and which sensors are in use. They also
+//! get an `AgentPaused` on registration and whenever the kill switch flips.
+//!
+//! Chat windows get the output of running tools as `ToolProgress`, and the
+//! same `ToolFinished`, so a long command shows up live in its tool card.
+//! With `[agent] show_plans` they also get an `AgentPlan` before the calls.
+//! Every chat message is acknowledged with a `MessageReceived` before the
+//! turn starts, so the sender can mark it delivered.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use aios_common::{
+    AgentHealth, ChatMessage, ClientType, IpcMessage, IpcPayload, ToolCall, ToolResult, telemetry,
+};
+use aios_mcp::executor::ProgressSender;
+use tokio::sync::{mpsc, RwLock};
+use uuid::Uuid;
+
+use crate::redact;
+use crate::state::{AgentState, ConfirmAnswer};
+
+/// Least time between two `ToolProgress` messages of one call; output
+/// coming in faster is sent together.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Health derived from the current provider and the outcome of its last
+/// request.
+pub fn health(state: &AgentState) -> AgentHealth {
+    let Some(provider) = &state.llm_provider else {
+        return AgentHealth::Echo;
+    };
+    let provider = provider.name().to_owned();
+    match &state.llm_error {
+        Some(message) => AgentHealth::ProviderError {
+            provider,
+            message: message.clone(),
+        },
+        None => AgentHealth::Ready { provider },
+    }
+}
+
+/// Wrap `health` for sending to a dock client.
+pub fn health_message(health: AgentHealth) -> IpcMessage {
+    IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::AgentHealth { health },
+    }
+}
+
+/// Record the outcome of an LLM request (`None` on success) and tell the
+/// docks if the health changed.
+pub async fn record_llm_result(state: &Arc<RwLock<AgentState>>, error: Option<String>) {
+    let mut guard = state.write().await;
+    guard.llm_error = error;
+    publish(guard).await;
+}
+
+/// Tell the docks about the health after the provider was replaced.
+pub async fn provider_changed(state: &Arc<RwLock<AgentState>>) {
+    let mut guard = state.write().await;
+    guard.llm_error = None;
+    publish(guard).await;
+}
+
+async fn publish(mut guard: tokio::sync::RwLockWriteGuard<'_, AgentState>) {
+    let health = health(&guard);
+    if guard.published_health.as_ref() == Some(&health) {
+        return;
+    }
+    tracing::info!(?health, "Agent health changed");
+    guard.published_health = Some(health.clone());
+    let guard = guard.downgrade();
+    guard
+        .broadcast(ClientType::Dock, &health_message(health))
+        .await;
+}
+
+/// Announce that `tool_call` is being handled, with the sensors it uses.
+pub async fn tool_started(state: &Arc<RwLock<AgentState>>, tool_call: &ToolCall) {
+    let guard = state.read().await;
+    let message = IpcMessage {
+        id: telemetry::message_id(),
+        payload: IpcPayload::ToolStarted {
+            call_id: tool_call.id,
+            tool: tool_call.name.clone(),
+            sensors: guard.tool_registry.sensors(&tool_call.name).to_vec(),
+        },
+    };
+    guard.broadcast(ClientType::Dock, &message).await;
+}
+
+/// Announce that `tool_call` is done, successfully or not.
+pub async fn tool_finished(
+    state: &Arc<RwLock<AgentState>>,
+    tool_call: &ToolCall,
+    result: &ToolResult,
+) {
+    let message = IpcMessage {
+        id: telemetry::message_id(),
+        payload: IpcPayload::ToolFinished {
+            call_id: tool_call.id,
+            tool: tool_call.name.clone(),
+            success: !result.is_error,
+        },
+    };
+    let guard = state.read().await;
+    guard.broadcast(ClientType::Dock, &message).await;
+    guard.broadcast(ClientType::Chat, &message).await;
+}
+
+/// A sender for the output of `tool_call` in `conversation_id`, which is
+/// passed on to chat windows with secrets masked until the call drops it.
+pub fn progress_sender(
+    state: &Arc<RwLock<AgentState>>,
+    conversation_id: Uuid,
+    tool_call: &ToolCall,
+) -> ProgressSender {
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let state = Arc::clone(state);
+    let (call_id, tool) = (tool_call.id, tool_call.name.clone());
+    tokio::spawn(async move {
+        while let Some(mut output) = rx.recv().await {
+            while let Ok(more) = rx.try_recv() {
+                output.push_str(&more);
+            }
+            redact::redact_string(&mut output);
+            let message = IpcMessage {
+                id: telemetry::message_id(),
+                payload: IpcPayload::ToolProgress {
+                    conversation_id,
+                    call_id,
+                    tool: tool.clone(),
+                    output,
+                },
+            };
+            state
+                .read()
+                .await
+                .broadcast(ClientType::Chat, &message)
+                .await;
+            tokio::time::sleep(PROGRESS_INTERVAL).await;
+        }
+    });
+    tx
+}
+
+/// Show the chat windows what the agent is about to do in a conversation.
+pub async fn plan(state: &Arc<RwLock<AgentState>>, conversation_id: Uuid, plan: &ChatMessage) {
+    let message = IpcMessage {
+        id: telemetry::message_id(),
+        payload: IpcPayload::AgentPlan {
+            conversation_id,
+            message: plan.clone(),
+        },
+    };
+    state
+        .read()
+        .await
+        .broadcast(ClientType::Chat, &message)
+        .await;
+}
+
+/// Tell the chat windows that a user message reached the agent.
+pub async fn received(state: &Arc<RwLock<AgentState>>, conversation_id: Uuid, message_id: Uuid) {
+    let message = IpcMessage {
+        id: telemetry::message_id(),
+        payload: IpcPayload::MessageReceived {
+            conversation_id,
+            message_id,
+        },
+    };
+    state
+        .read()
+        .await
+        .broadcast(ClientType::Chat, &message)
+        .await;
+}
+
+/// Wrap the pause state for sending to a dock client.
+pub fn paused_message(paused: bool) -> IpcMessage {
+    IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::AgentPaused { paused },
+    }
+}
+
+/// Pause or resume tool execution and tell the docks. Pausing rejects every
+/// confirmation still waiting for an answer; returns how many were rejected.
+pub async fn set_paused(state: &Arc<RwLock<AgentState>>, paused: bool) -> usize {
+    let mut guard = state.write().await;
+    let changed = guard.paused != paused;
+    guard.paused = paused;
+    let mut rejected = 0;
+    if paused {
+        for (_, pending) in guard.pending_confirms.drain() {
+            if pending.reply.send(ConfirmAnswer::REJECTED).is_ok() {
+                rejected += 1;
+            }
+        }
+    }
+    if changed {
+        tracing::warn!(paused, rejected, "Tool execution pause changed");
+        let guard = guard.downgrade();
+        guard
+            .broadcast(ClientType::Dock, &paused_message(paused))
+            .await;
+    }
+    rejected
+}
@@ -3,36 +3,118 @@
 //! Writes one JSON object per line (JSON Lines / NDJSON) to a configurable
 //! file path.  Every tool invocation -- whether approved, rejected, or
 //! timed-out -- is recorded so that the full action history can be
-//! reconstructed later for security review.
+//! reconstructed later for security review. With `audit_llm` set, LLM
+//! requests are recorded too, as [`AuditKind::Llm`] entries. Secrets are
+//! masked (see [`crate::redact`]) before anything is written.
+//!
+//! Entries go through a bounded queue to a background writer, which writes
+//! whatever has piled up in one go, so a burst of tool calls costs one
+//! write instead of one per entry. [`AuditLogger::flush`] waits for the
+//! queue to drain; the agent calls it before exiting.
+//!
+//! Lines are hash-chained (see `aios_common::audit::chain_entry`), so
+//! `aios_common::audit::verify` can tell later whether the file was edited.
+//! Clients query the history through [`AuditLogger::query`] instead of
+//! reading the file.
 
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-use aios_common::{AuditEntry, AuditResult, ToolCall, ToolResult as ToolExecResult};
+use aios_common::audit::{self, AuditIndex};
+use aios_common::{
+    AuditEntry, AuditFilter, AuditKind, AuditRange, AuditResult, ToolCall,
+    ToolResult as ToolExecResult, TrustLevel,
+};
 use chrono::Utc;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::llm::LlmProvider;
+use crate::llm::types::{LlmRequest, LlmResponse};
+use crate::redact;
+
+/// Most entries a single query returns.
+const MAX_QUERY_ENTRIES: usize = 1000;
+
+/// Entries waiting for the writer; logging waits while the queue is full.
+const QUEUE_CAPACITY: usize = 1024;
+
+/// Most entries the writer writes at once.
+const MAX_BATCH: usize = 256;
 
 /// Persistent, append-only audit logger backed by a JSON Lines file.
+/// Clones share the writer task.
+#[derive(Clone)]
 pub struct AuditLogger {
-    log_path: PathBuf,
+    /// Queue of the background writer.
+    queue: mpsc::Sender<WriterCommand>,
+    /// Time index for queries, brought up to date by each query.
+    index: Arc<std::sync::Mutex<AuditIndex>>,
+}
+
+enum WriterCommand {
+    Append(AuditEntry),
+    /// Reply once everything queued before is written.
+    Flush(oneshot::Sender<()>),
 }
 
 impl AuditLogger {
-    /// Create a new logger that appends entries to `log_path`.
+    /// Create a new logger that appends entries to `log_path`, and start
+    /// its writer task.
     ///
     /// The file (and its parent directories) are created lazily on the first
     /// write, so construction never fails.
     pub fn new(log_path: impl Into<PathBuf>) -> Self {
+        let log_path = log_path.into();
+        let (queue, commands) = mpsc::channel(QUEUE_CAPACITY);
+        let writer = AuditWriter {
+            log_path: log_path.clone(),
+            last: None,
+        };
+        tokio::spawn(writer.run(commands));
         Self {
-            log_path: log_path.into(),
+            queue,
+            index: Arc::new(std::sync::Mutex::new(AuditIndex::new(log_path))),
+        }
+    }
+
+    /// Wait until every entry logged so far is in the file.
+    pub async fn flush(&self) {
+        let (done, written) = oneshot::channel();
+        if self.queue.send(WriterCommand::Flush(done)).await.is_ok() {
+            let _ = written.await;
         }
     }
 
-    /// Record a tool execution that was **rejected** by the user or by a
-    /// missing Confirm client.
+    /// Logged entries in `range` matching `filter`, newest first, at most
+    /// `limit` (capped at [`MAX_QUERY_ENTRIES`]). The flag tells whether
+    /// more entries matched.
+    pub async fn query(
+        &self,
+        filter: AuditFilter,
+        range: AuditRange,
+        limit: usize,
+    ) -> anyhow::Result<(Vec<AuditEntry>, bool)> {
+        // Include entries still in the queue.
+        self.flush().await;
+        let index = Arc::clone(&self.index);
+        let limit = limit.min(MAX_QUERY_ENTRIES);
+        tokio::task::spawn_blocking(move || {
+            let mut index = index.lock().unwrap_or_else(|e| e.into_inner());
+            index.refresh()?;
+            Ok(index.query(&filter, range, limit)?)
+        })
+        .await?
+    }
+
+    /// Record a tool execution that was **rejected** by the user, by a
+    /// missing Confirm client, or because the tool is disabled.
     pub async fn log_rejected(&self, tool_call: &ToolCall) {
         let entry = AuditEntry {
             timestamp: Utc::now(),
+            kind: AuditKind::Tool,
             action: tool_call.name.clone(),
             arguments: tool_call.arguments.clone(),
             trust_level: tool_call.trust_level,
@@ -43,10 +125,26 @@ impl AuditLogger {
         self.append(&entry).await;
     }
 
+    /// Record a tool execution refused because the user paused the agent.
+    pub async fn log_paused(&self, tool_call: &ToolCall) {
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            kind: AuditKind::Tool,
+            action: tool_call.name.clone(),
+            arguments: tool_call.arguments.clone(),
+            trust_level: tool_call.trust_level,
+            user_approved: false,
+            result: AuditResult::Rejected,
+            details: Some("Tool execution paused".to_owned()),
+        };
+        self.append(&entry).await;
+    }
+
     /// Record a confirmation that **timed out**.
     pub async fn log_timeout(&self, tool_call: &ToolCall) {
         let entry = AuditEntry {
             timestamp: Utc::now(),
+            kind: AuditKind::Tool,
             action: tool_call.name.clone(),
             arguments: tool_call.arguments.clone(),
             trust_level: tool_call.trust_level,
@@ -61,6 +159,7 @@ impl AuditLogger {
     pub async fn log_rate_limited(&self, tool_call: &ToolCall) {
         let entry = AuditEntry {
             timestamp: Utc::now(),
+            kind: AuditKind::Tool,
             action: tool_call.name.clone(),
             arguments: tool_call.arguments.clone(),
             trust_level: tool_call.trust_level,
@@ -75,6 +174,7 @@ impl AuditLogger {
     pub async fn log_success(&self, tool_call: &ToolCall, result: &ToolExecResult) {
         let entry = AuditEntry {
             timestamp: Utc::now(),
+            kind: AuditKind::Tool,
             action: tool_call.name.clone(),
             arguments: tool_call.arguments.clone(),
             trust_level: tool_call.trust_level,
@@ -93,6 +193,7 @@ impl AuditLogger {
     pub async fn log_error(&self, tool_call: &ToolCall, error: &str) {
         let entry = AuditEntry {
             timestamp: Utc::now(),
+            kind: AuditKind::Tool,
             action: tool_call.name.clone(),
             arguments: tool_call.arguments.clone(),
             trust_level: tool_call.trust_level,
@@ -103,32 +204,142 @@ impl AuditLogger {
         self.append(&entry).await;
     }
 
+    /// Record an LLM request and its outcome. The prompt and the reply are
+    /// kept only as hashes.
+    pub async fn log_llm(
+        &self,
+        provider: &dyn LlmProvider,
+        request: &LlmRequest,
+        response: &anyhow::Result<LlmResponse>,
+        latency: Duration,
+    ) {
+        let prompt =
+            serde_json::to_string(&(&request.system_prompt, &request.messages)).unwrap_or_default();
+        let mut arguments = serde_json::json!({
+            "model": provider.model(),
+            "messages": request.messages.len(),
+            "tools": request.tools.len(),
+            "latency_ms": latency.as_millis() as u64,
+            "request_hash": audit::content_hash(&prompt),
+        });
+        let result = match response {
+            Ok(response) => {
+                let reply = serde_json::to_string(&response.message.content).unwrap_or_default();
+                arguments["input_tokens"] = response.usage.input_tokens.into();
+                arguments["output_tokens"] = response.usage.output_tokens.into();
+                arguments["response_hash"] = audit::content_hash(&reply).into();
+                AuditResult::Ok
+            }
+            Err(e) => AuditResult::Error(format!("{e:#}")),
+        };
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            kind: AuditKind::Llm,
+            action: provider.name().to_owned(),
+            arguments,
+            trust_level: TrustLevel::System,
+            user_approved: false,
+            result,
+            details: None,
+        };
+        self.append(&entry).await;
+    }
+
     // ------------------------------------------------------------------
     // Internal helpers
     // ------------------------------------------------------------------
 
-    /// Serialise `entry` to JSON and append it as a single line.
+    /// Mask secrets in `entry`, then serialise it to JSON and append it as
+    /// a single line.
     async fn append(&self, entry: &AuditEntry) {
-        if let Err(e) = self.try_append(entry).await {
-            tracing::error!("Failed to write audit log: {e:#}");
+        let mut entry = entry.clone();
+        redact::redact_value(&mut entry.arguments);
+        if let Some(details) = &mut entry.details {
+            redact::redact_string(details);
+        }
+        if let AuditResult::Error(message) = &mut entry.result {
+            redact::redact_string(message);
+        }
+        if self.queue.send(WriterCommand::Append(entry)).await.is_err() {
+            tracing::error!("Audit writer is gone; entry not logged");
         }
     }
+}
+
+/// Background task that appends queued entries to the log file.
+struct AuditWriter {
+    log_path: PathBuf,
+    /// Hash of the last line written and the length of the file after it.
+    /// The hash is read from the file instead on the first write, and when
+    /// the length no longer matches because the log was rotated.
+    last: Option<(String, u64)>,
+}
 
-    async fn try_append(&self, entry: &AuditEntry) -> anyhow::Result<()> {
+impl AuditWriter {
+    /// Write entries as they arrive, all that are queued at once, until the
+    /// logger is dropped.
+    async fn run(mut self, mut commands: mpsc::Receiver<WriterCommand>) {
+        let mut batch = Vec::with_capacity(MAX_BATCH);
+        while commands.recv_many(&mut batch, MAX_BATCH).await > 0 {
+            let mut entries = Vec::new();
+            let mut flushes = Vec::new();
+            for command in batch.drain(..) {
+                match command {
+                    WriterCommand::Append(entry) => entries.push(entry),
+                    WriterCommand::Flush(done) => flushes.push(done),
+                }
+            }
+            if !entries.is_empty()
+                && let Err(e) = self.write(&entries).await
+            {
+                tracing::error!(count = entries.len(), "Failed to write audit log: {e:#}");
+            }
+            for done in flushes {
+                let _ = done.send(());
+            }
+        }
+    }
+
+    async fn write(&mut self, entries: &[AuditEntry]) -> anyhow::Result<()> {
         if let Some(parent) = self.log_path.parent() {
             tokio::fs::create_dir_all(parent).await?;
         }
 
+        let cached = match (self.last.take(), tokio::fs::metadata(&self.log_path).await) {
+            (Some((hash, len)), Ok(metadata)) if metadata.len() == len => Some(hash),
+            _ => None,
+        };
+        let mut prev_hash = match cached {
+            Some(hash) => hash,
+            None => match tokio::fs::read_to_string(&self.log_path).await {
+                Ok(log) => audit::last_hash(&log),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    audit::GENESIS_HASH.to_owned()
+                }
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        let mut lines = String::new();
+        for entry in entries {
+            let (line, hash) = audit::chain_entry(entry, &prev_hash)?;
+            lines.push_str(&line);
+            lines.push('\n');
+            prev_hash = hash;
+        }
+
+        // The file is opened for each batch, so a rotated log is picked up.
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.log_path)
             .await?;
-
-        let json = serde_json::to_string(entry)?;
-        file.write_all(json.as_bytes()).await?;
-        file.write_all(b"\n").await?;
+        // If writing fails, `last` stays empty and the next batch reads the
+        // file again.
+        file.write_all(lines.as_bytes()).await?;
         file.flush().await?;
+        let len = file.metadata().await?.len();
+        self.last = Some((prev_hash, len));
         Ok(())
     }
 }
@@ -166,6 +377,171 @@ mod tests {
         assert!(result.ends_with("...[truncated]"));
     }
 
+    fn entry(action: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc::now(),
+            kind: AuditKind::Tool,
+            action: action.to_owned(),
+            arguments: serde_json::json!({ "path": "/tmp/x", "ratio": 0.1 }),
+            trust_level: aios_common::TrustLevel::User,
+            user_approved: true,
+            result: AuditResult::Ok,
+            details: Some("done".to_owned()),
+        }
+    }
+
+    /// A log of `actions`, chained from the start.
+    fn chained_log(actions: &[&str]) -> Vec<String> {
+        let mut prev_hash = audit::GENESIS_HASH.to_owned();
+        actions
+            .iter()
+            .map(|action| {
+                let (line, hash) = audit::chain_entry(&entry(action), &prev_hash).unwrap();
+                prev_hash = hash;
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chained_log_verifies() {
+        let log = chained_log(&["read_file", "write_file", "run_command"]).join("\n");
+        let verification = audit::verify(&log).unwrap();
+        assert_eq!(verification.chained, 3);
+        assert_eq!(verification.unchained, 0);
+        assert_eq!(verification.last_hash, audit::last_hash(&log));
+    }
+
+    #[test]
+    fn verify_detects_edited_line() {
+        let mut lines = chained_log(&["read_file", "write_file", "run_command"]);
+        lines[1] = lines[1].replace("write_file", "read_file");
+        assert_eq!(
+            audit::verify(&lines.join("\n")),
+            Err(audit::AuditVerifyError::Modified(2))
+        );
+    }
+
+    #[test]
+    fn verify_detects_removed_line() {
+        let mut lines = chained_log(&["read_file", "write_file", "run_command"]);
+        lines.remove(1);
+        assert_eq!(
+            audit::verify(&lines.join("\n")),
+            Err(audit::AuditVerifyError::Broken(2))
+        );
+    }
+
+    #[test]
+    fn verify_accepts_unchained_lines_before_the_chain() {
+        let old = serde_json::to_string(&entry("read_file")).unwrap();
+        let mut lines = vec![old.clone()];
+        lines.extend(chained_log(&["write_file"]));
+        let verification = audit::verify(&lines.join("\n")).unwrap();
+        assert_eq!(verification.unchained, 1);
+        assert_eq!(verification.chained, 1);
+
+        lines.push(old);
+        assert_eq!(
+            audit::verify(&lines.join("\n")),
+            Err(audit::AuditVerifyError::Unchained(3))
+        );
+    }
+
+    #[test]
+    fn entries_without_kind_are_tool_calls() {
+        let mut line = serde_json::to_value(entry("read_file")).unwrap();
+        line.as_object_mut().unwrap().remove("kind");
+        let parsed: AuditEntry = serde_json::from_value(line).unwrap();
+        assert_eq!(parsed.kind, AuditKind::Tool);
+    }
+
+    #[test]
+    fn index_queries_by_range_and_filter() {
+        let start = Utc::now() - chrono::Duration::hours(3);
+        let lines: Vec<String> = ["read_file", "run_command", "read_file"]
+            .iter()
+            .enumerate()
+            .map(|(hour, action)| {
+                let mut entry = entry(action);
+                entry.timestamp = start + chrono::Duration::hours(hour as i64);
+                serde_json::to_string(&entry).unwrap()
+            })
+            .collect();
+        let path = std::env::temp_dir().join(format!("aios-audit-{}.log", uuid::Uuid::new_v4()));
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let mut index = AuditIndex::new(&path);
+        index.refresh().unwrap();
+        let read_file = AuditFilter {
+            action: Some("read_file".to_owned()),
+            ..AuditFilter::default()
+        };
+        let (entries, truncated) = index.query(&read_file, AuditRange::default(), 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(!truncated);
+        assert!(entries[0].timestamp > entries[1].timestamp);
+
+        let range = AuditRange {
+            from: Some(start + chrono::Duration::minutes(30)),
+            to: Some(start + chrono::Duration::minutes(90)),
+        };
+        let (entries, _) = index.query(&AuditFilter::default(), range, 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "run_command");
+
+        let (entries, truncated) = index
+            .query(&AuditFilter::default(), AuditRange::default(), 2)
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(truncated);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn writer_chains_batched_entries() {
+        let path = std::env::temp_dir().join(format!("aios-audit-{}.log", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(&path);
+        for action in ["read_file", "write_file", "run_command"] {
+            logger.append(&entry(action)).await;
+        }
+        logger.flush().await;
+        logger.append(&entry("read_file")).await;
+        let (entries, _) = logger
+            .query(AuditFilter::default(), AuditRange::default(), 10)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 4);
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let verification = audit::verify(&log).unwrap();
+        assert_eq!(verification.chained, 4);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn writer_starts_a_new_chain_after_rotation() {
+        let path = std::env::temp_dir().join(format!("aios-audit-{}.log", uuid::Uuid::new_v4()));
+        let rotated = path.with_extension("log.1");
+        let logger = AuditLogger::new(&path);
+        for action in ["read_file", "write_file"] {
+            logger.append(&entry(action)).await;
+        }
+        logger.flush().await;
+        std::fs::rename(&path, &rotated).unwrap();
+        for action in ["run_command", "read_file"] {
+            logger.append(&entry(action)).await;
+        }
+        logger.flush().await;
+
+        for path in [&rotated, &path] {
+            let log = std::fs::read_to_string(path).unwrap();
+            let verification = audit::verify(&log).unwrap();
+            assert_eq!(verification.chained, 2);
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
     #[test]
     fn truncate_multibyte_safe() {
         // 3-byte UTF-8 chars
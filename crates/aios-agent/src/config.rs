@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
-use aios_common::AiosConfig;
-use anyhow::{Context, Result};
+use aios_common::{AiosConfig, McpTransport};
+use anyhow::{bail, Context, Result};
 
 /// Returns the default config path: `~/.config/aios/agent.toml`.
 pub fn config_path() -> PathBuf {
@@ -37,3 +37,131 @@ pub fn save_config(config: &AiosConfig) -> Result<()> {
     std::fs::write(&path, content)?;
     Ok(())
 }
+
+/// The `[section]` table of the config file, empty when the file has none.
+pub fn read_section(section: &str) -> Result<toml::Value> {
+    check_section(section)?;
+    let config = read_table()?;
+    Ok(config
+        .get(section)
+        .cloned()
+        .unwrap_or_else(|| toml::Value::Table(toml::map::Map::new())))
+}
+
+/// Replace `[section]` of the config file with `value`, if the result is a
+/// valid config. Everything else in the file is kept as written.
+pub fn write_section(section: &str, value: toml::Value) -> Result<()> {
+    let config = replace_section(read_table()?, section, value)?;
+    let content = toml::to_string_pretty(&config)?;
+    std::fs::write(config_path(), content)?;
+    Ok(())
+}
+
+/// The config file as a table. A missing file is an error: writing one
+/// from here would skip the chat's first-boot setup.
+fn read_table() -> Result<toml::map::Map<String, toml::Value>> {
+    let path = config_path();
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read config from {}", path.display()))?;
+    toml::from_str(&content)
+        .with_context(|| format!("failed to parse config from {}", path.display()))
+}
+
+/// `config` with `[section]` set to `value`, checked as a whole.
+fn replace_section(
+    mut config: toml::map::Map<String, toml::Value>,
+    section: &str,
+    value: toml::Value,
+) -> Result<toml::map::Map<String, toml::Value>> {
+    check_section(section)?;
+    if !value.is_table() {
+        bail!("[{section}] must be a table");
+    }
+    config.insert(section.to_owned(), value);
+    let parsed: AiosConfig = toml::Value::Table(config.clone())
+        .try_into()
+        .with_context(|| format!("invalid [{section}]"))?;
+    validate(&parsed)?;
+    Ok(config)
+}
+
+/// Only sections the agent knows can be read or written.
+fn check_section(section: &str) -> Result<()> {
+    let known = toml::Value::try_from(AiosConfig::default())?;
+    if known.get(section).is_none() {
+        bail!("unknown config section [{section}]");
+    }
+    Ok(())
+}
+
+/// Checks the types alone do not enforce.
+fn validate(config: &AiosConfig) -> Result<()> {
+    if config.agent.socket_path.trim().is_empty() {
+        bail!("agent.socket_path must not be empty");
+    }
+    if config.provider.model.trim().is_empty() {
+        bail!("provider.model must not be empty");
+    }
+    for (name, server) in &config.mcp_servers {
+        let valid_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_name {
+            bail!("MCP server name {name:?} may only use letters, digits, '_' and '-'");
+        }
+        let missing_target = match server.transport {
+            McpTransport::Stdio => server.command.trim().is_empty(),
+            McpTransport::Sse => server
+                .url
+                .as_deref()
+                .is_none_or(|url| url.trim().is_empty()),
+        };
+        if missing_target {
+            bail!("MCP server {name} has no command or URL");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_table() -> toml::map::Map<String, toml::Value> {
+        match toml::Value::try_from(AiosConfig::default()).unwrap() {
+            toml::Value::Table(table) => table,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn replace_section_keeps_other_sections() {
+        let mut agent = default_table()["agent"].clone();
+        agent["max_destructive_per_minute"] = toml::Value::Integer(7);
+
+        let config = replace_section(default_table(), "agent", agent).unwrap();
+        assert_eq!(
+            config["agent"]["max_destructive_per_minute"].as_integer(),
+            Some(7)
+        );
+        assert_eq!(config["provider"], default_table()["provider"]);
+    }
+
+    #[test]
+    fn replace_section_rejects_invalid_values() {
+        let mut agent = default_table()["agent"].clone();
+        agent["max_destructive_per_minute"] = toml::Value::String("many".to_owned());
+        assert!(replace_section(default_table(), "agent", agent).is_err());
+
+        let mut agent = default_table()["agent"].clone();
+        agent["socket_path"] = toml::Value::String(" ".to_owned());
+        assert!(replace_section(default_table(), "agent", agent).is_err());
+    }
+
+    #[test]
+    fn replace_section_rejects_unknown_sections() {
+        let value = toml::Value::Table(toml::map::Map::new());
+        assert!(replace_section(default_table(), "nonsense", value).is_err());
+    }
+}
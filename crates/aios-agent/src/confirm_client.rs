@@ -0,0 +1,88 @@
+//! Starting the confirmation dialog on demand.
+//!
+//! `aios-confirm` normally runs for the whole session. When a tool call
+//! needs approval and it is not connected, the agent runs `[agent]
+//! confirm_command` and waits a few seconds for the dialog to register
+//! before asking, instead of refusing the call.
+
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use aios_common::ClientType;
+use tokio::process::Command;
+use tokio::sync::RwLock;
+
+use crate::state::AgentState;
+
+/// Longest wait for a started dialog to register.
+const REGISTER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often to look for the registration.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Make sure a Confirm client is connected, starting one when none is.
+/// Returns whether one is connected now.
+pub async fn ensure_running(state: &Arc<RwLock<AgentState>>) -> bool {
+    let (command, launch) = {
+        let state_guard = state.read().await;
+        if state_guard.find_client(ClientType::Confirm).is_some() {
+            return true;
+        }
+        (
+            state_guard.confirm_command.clone(),
+            Arc::clone(&state_guard.confirm_launch),
+        )
+    };
+    let Some((program, args)) = command.split_first() else {
+        return false;
+    };
+
+    // One start at a time; calls waiting here find the started one.
+    let _launching = launch.lock().await;
+    if connected(state).await {
+        return true;
+    }
+    tracing::info!(?command, "No Confirm client connected; starting one");
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!("Cannot start the Confirm client {program}: {e}");
+            return false;
+        }
+    };
+
+    let waited = tokio::time::timeout(REGISTER_TIMEOUT, async {
+        loop {
+            tokio::select! {
+                status = child.wait() => {
+                    tracing::warn!(?status, "Confirm client exited before registering");
+                    return false;
+                }
+                () = tokio::time::sleep(POLL_INTERVAL) => {
+                    if connected(state).await {
+                        return true;
+                    }
+                }
+            }
+        }
+    })
+    .await;
+    let registered = waited.unwrap_or_else(|_| {
+        tracing::warn!("Confirm client did not register within {REGISTER_TIMEOUT:?}");
+        false
+    });
+    // Reap it when it exits; it outlives this call.
+    tokio::spawn(async move {
+        let _ = child.wait().await;
+    });
+    registered
+}
+
+async fn connected(state: &Arc<RwLock<AgentState>>) -> bool {
+    state.read().await.find_client(ClientType::Confirm).is_some()
+}
@@ -0,0 +1,410 @@
+//! Conversations of the agent, with a cap on how many stay in memory.
+//!
+//! A conversation leaves memory when it is the least recently used one
+//! beyond `[conversations] max_in_memory`, or has been idle for
+//! `evict_after_minutes`. Its messages and system prompt are written to
+//! `~/.local/share/aios/conversations/<id>.json`, readable by the user
+//! only, and read back the next time it is used, so clients never notice.
+//! Saved files stay until `max_saved` says otherwise. Its working directory
+//! and the tools approved for it do not survive eviction: approvals are
+//! asked for again, which errs on the safe side.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{DirBuilder, OpenOptions, Permissions};
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aios_common::{ChatMessage, ConversationConfig};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::state::{AgentState, Conversation};
+
+/// Conversations used this recently are never evicted, so a running turn
+/// keeps its history.
+const MIN_IDLE: Duration = Duration::from_secs(60);
+
+/// How often idle conversations are looked for.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What is written to disk for an evicted conversation.
+#[derive(Serialize, Deserialize)]
+struct Saved {
+    id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    system_prompt: Option<String>,
+    messages: Vec<ChatMessage>,
+}
+
+impl From<Saved> for Conversation {
+    fn from(saved: Saved) -> Self {
+        let mut conversation = Conversation::new(saved.id);
+        conversation.messages = saved.messages;
+        conversation.system_prompt = saved.system_prompt;
+        conversation
+    }
+}
+
+struct Resident {
+    conversation: Conversation,
+    used: Instant,
+}
+
+/// Conversations by id, in memory or saved to disk.
+pub struct Conversations {
+    resident: HashMap<Uuid, Resident>,
+    dir: PathBuf,
+    config: ConversationConfig,
+    min_idle: Duration,
+}
+
+impl Conversations {
+    /// An empty set saving evicted conversations in `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            resident: HashMap::new(),
+            dir,
+            config: ConversationConfig::default(),
+            min_idle: MIN_IDLE,
+        }
+    }
+
+    /// Apply new limits, evicting what they no longer allow.
+    pub fn set_limits(&mut self, config: ConversationConfig) {
+        self.config = config;
+        self.evict(None);
+    }
+
+    /// Evict conversations idle for `evict_after_minutes`, which otherwise
+    /// only happens when another one comes into memory.
+    pub fn sweep(&mut self) {
+        self.evict(None);
+    }
+
+    /// A conversation in memory. Readers that run outside a turn use
+    /// [`Self::messages`] to also see saved ones.
+    pub fn get(&self, id: &Uuid) -> Option<&Conversation> {
+        self.resident.get(id).map(|r| &r.conversation)
+    }
+
+    /// A conversation, read back from disk if it was evicted.
+    pub fn get_mut(&mut self, id: &Uuid) -> Option<&mut Conversation> {
+        if !self.resident.contains_key(id) {
+            let conversation = self.reload(id)?;
+            self.insert(conversation);
+        }
+        let resident = self.resident.get_mut(id)?;
+        resident.used = Instant::now();
+        Some(&mut resident.conversation)
+    }
+
+    /// A conversation, read back from disk or created empty.
+    pub fn get_or_create(&mut self, id: Uuid) -> &mut Conversation {
+        if !self.resident.contains_key(&id) {
+            let conversation = self.reload(&id).unwrap_or_else(|| Conversation::new(id));
+            self.insert(conversation);
+        }
+        let resident = self.resident.get_mut(&id).expect("inserted above");
+        resident.used = Instant::now();
+        &mut resident.conversation
+    }
+
+    /// The conversations in memory.
+    pub fn iter(&self) -> impl Iterator<Item = (&Uuid, &Conversation)> {
+        self.resident.iter().map(|(id, r)| (id, &r.conversation))
+    }
+
+    /// Messages of a conversation in memory or on disk, without bringing
+    /// it back into memory.
+    pub fn messages(&self, id: &Uuid) -> Option<Vec<ChatMessage>> {
+        match self.get(id) {
+            Some(conversation) => Some(conversation.messages.clone()),
+            None => read(&self.path(id)).map(|saved| saved.messages),
+        }
+    }
+
+    /// The conversations only on disk, to be read with
+    /// [`SavedList::read`] once the state lock is released.
+    pub fn saved(&self) -> SavedList {
+        SavedList {
+            dir: self.dir.clone(),
+            resident: self.resident.keys().copied().collect(),
+        }
+    }
+
+    fn path(&self, id: &Uuid) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    fn insert(&mut self, conversation: Conversation) {
+        let id = conversation.id;
+        self.resident.insert(
+            id,
+            Resident {
+                conversation,
+                used: Instant::now(),
+            },
+        );
+        self.evict(Some(id));
+    }
+
+    /// Take a saved conversation off the disk.
+    fn reload(&self, id: &Uuid) -> Option<Conversation> {
+        let path = self.path(id);
+        let saved = read(&path)?;
+        let _ = std::fs::remove_file(&path);
+        tracing::debug!(%id, "Conversation read back from disk");
+        Some(saved.into())
+    }
+
+    /// Save and drop conversations beyond the limits, except `keep`.
+    fn evict(&mut self, keep: Option<Uuid>) {
+        let now = Instant::now();
+        let mut candidates: Vec<(Instant, Uuid)> = self
+            .resident
+            .iter()
+            .filter(|(id, r)| Some(**id) != keep && now.duration_since(r.used) >= self.min_idle)
+            .map(|(id, r)| (r.used, *id))
+            .collect();
+        candidates.sort();
+
+        let max_idle = Duration::from_secs(self.config.evict_after_minutes * 60);
+        let mut excess = self.resident.len().saturating_sub(self.config.max_in_memory);
+        let mut evicted = false;
+        for (used, id) in candidates {
+            let idle = max_idle > Duration::ZERO && now.duration_since(used) >= max_idle;
+            if excess == 0 && !idle {
+                // Sorted oldest first: the rest are newer.
+                break;
+            }
+            excess = excess.saturating_sub(1);
+            let Some(resident) = self.resident.remove(&id) else {
+                continue;
+            };
+            self.save(resident.conversation);
+            evicted = true;
+        }
+        if evicted {
+            self.prune();
+        }
+    }
+
+    fn save(&self, conversation: Conversation) {
+        // Nothing to bring back.
+        if conversation.messages.is_empty() && conversation.system_prompt.is_none() {
+            return;
+        }
+        let path = self.path(&conversation.id);
+        let saved = Saved {
+            id: conversation.id,
+            system_prompt: conversation.system_prompt,
+            messages: conversation.messages,
+        };
+        // Chat history is private: the directory and files are the user's
+        // alone.
+        let result = DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&self.dir)
+            .and_then(|()| {
+                let json = serde_json::to_vec(&saved).map_err(std::io::Error::other)?;
+                let mut file = OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .mode(0o600)
+                    .open(&path)?;
+                // An older copy keeps its mode through the truncation.
+                file.set_permissions(Permissions::from_mode(0o600))?;
+                file.write_all(&json)
+            });
+        match result {
+            Ok(()) => tracing::debug!(id = %saved.id, "Conversation evicted to disk"),
+            Err(e) => tracing::warn!("Cannot save conversation to {}: {e}", path.display()),
+        }
+    }
+
+    /// Delete the least recently saved conversations beyond `max_saved`.
+    fn prune(&self) {
+        if self.config.max_saved == 0 {
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+            })
+            .collect();
+        files.sort();
+        let excess = files.len().saturating_sub(self.config.max_saved);
+        for (_, path) in &files[..excess] {
+            match std::fs::remove_file(path) {
+                Ok(()) => tracing::info!(
+                    max_saved = self.config.max_saved,
+                    "Saved conversation {} deleted",
+                    path.display()
+                ),
+                Err(e) => tracing::warn!("Cannot delete {}: {e}", path.display()),
+            }
+        }
+    }
+}
+
+/// The conversations saved to disk but not in memory, as of
+/// [`Conversations::saved`].
+pub struct SavedList {
+    dir: PathBuf,
+    resident: HashSet<Uuid>,
+}
+
+impl SavedList {
+    /// Ids and messages of the conversations. Blocking: it reads every
+    /// saved file.
+    pub fn read(&self) -> Vec<(Uuid, Vec<ChatMessage>)> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| read(&entry.ok()?.path()))
+            .filter(|saved| !self.resident.contains(&saved.id))
+            .map(|saved| (saved.id, saved.messages))
+            .collect()
+    }
+}
+
+/// Sweep the agent's conversations every [`SWEEP_INTERVAL`].
+pub async fn run(state: Arc<RwLock<AgentState>>) {
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        state.write().await.conversations.sweep();
+    }
+}
+
+/// Directory holding evicted conversations.
+pub fn conversations_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("aios")
+        .join("conversations")
+}
+
+fn read(path: &Path) -> Option<Saved> {
+    let content = std::fs::read(path).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use aios_common::{MessageContent, Role, TrustLevel};
+    use chrono::Utc;
+
+    use super::*;
+
+    fn store(max_in_memory: usize) -> Conversations {
+        let dir = std::env::temp_dir().join(format!("aios-conversations-{}", Uuid::new_v4()));
+        let mut store = Conversations::new(dir);
+        store.min_idle = Duration::ZERO;
+        store.set_limits(ConversationConfig {
+            max_in_memory,
+            evict_after_minutes: 0,
+            max_saved: 0,
+        });
+        store
+    }
+
+    fn say(store: &mut Conversations, id: Uuid, text: &str) {
+        store.get_or_create(id).messages.push(ChatMessage {
+            id: Uuid::new_v4(),
+            role: Role::User,
+            content: MessageContent::Text {
+                text: text.to_owned(),
+            },
+            trust_level: TrustLevel::User,
+            timestamp: Utc::now(),
+        });
+    }
+
+    #[test]
+    fn evicts_least_recently_used_and_reads_it_back() {
+        let mut store = store(2);
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        say(&mut store, a, "first");
+        say(&mut store, b, "second");
+        say(&mut store, a, "first again");
+        say(&mut store, c, "third");
+
+        assert!(store.get(&b).is_none());
+        assert_eq!(store.messages(&b).map(|m| m.len()), Some(1));
+        assert_eq!(store.saved().read().len(), 1);
+        let path = store.path(&b);
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // Using it again brings it back and pushes out the oldest.
+        assert_eq!(store.get_mut(&b).map(|c| c.messages.len()), Some(1));
+        assert!(store.get(&a).is_none());
+        assert!(store.get(&c).is_some());
+        assert_eq!(store.get_or_create(a).messages.len(), 2);
+
+        let _ = std::fs::remove_dir_all(&store.dir);
+    }
+
+    #[test]
+    fn saved_conversations_are_deleted_only_beyond_max_saved() {
+        let mut store = store(1);
+        for text in ["one", "two", "three", "four"] {
+            say(&mut store, Uuid::new_v4(), text);
+        }
+        assert_eq!(store.saved().read().len(), 3);
+
+        store.set_limits(ConversationConfig {
+            max_in_memory: 1,
+            evict_after_minutes: 0,
+            max_saved: 2,
+        });
+        say(&mut store, Uuid::new_v4(), "five");
+        assert_eq!(store.saved().read().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&store.dir);
+    }
+
+    #[test]
+    fn idle_conversations_are_evicted_without_new_ones() {
+        let mut store = store(4);
+        store.set_limits(ConversationConfig {
+            max_in_memory: 4,
+            evict_after_minutes: 1,
+            max_saved: 0,
+        });
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        say(&mut store, a, "first");
+        say(&mut store, b, "second");
+        store.sweep();
+        assert!(store.get(&a).is_some());
+
+        store.resident.get_mut(&a).unwrap().used -= Duration::from_secs(120);
+        store.sweep();
+        assert!(store.get(&a).is_none());
+        assert!(store.get(&b).is_some());
+        assert_eq!(store.messages(&a).map(|m| m.len()), Some(1));
+
+        let _ = std::fs::remove_dir_all(&store.dir);
+    }
+
+    #[test]
+    fn unknown_conversations_are_not_created_by_lookups() {
+        let mut store = store(2);
+        let id = Uuid::new_v4();
+        assert!(store.get_mut(&id).is_none());
+        assert!(store.messages(&id).is_none());
+        assert_eq!(store.iter().count(), 0);
+    }
+}
@@ -0,0 +1,199 @@
+//! Panics in tools and chat turns: caught, turned into an error for the
+//! caller, and written up as a crash report.
+//!
+//! Reports go to `~/.local/share/aios/crashes`, one text file per panic,
+//! with what was running, the request id, the panic message and location,
+//! a backtrace and the tail of the agent log. Only the newest
+//! [`MAX_REPORTS`] are kept.
+
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use aios_common::telemetry;
+use chrono::Utc;
+use futures::FutureExt as _;
+use tokio::sync::RwLock;
+
+use crate::logs::LogBuffer;
+use crate::redact;
+use crate::state::AgentState;
+
+/// Crash reports kept; older ones are deleted.
+const MAX_REPORTS: usize = 50;
+
+/// Log lines included in a report.
+const REPORT_LOG_LINES: usize = 100;
+
+thread_local! {
+    /// Location and backtrace of the last panic on this thread, recorded by
+    /// the hook for [`catch`] to pick up after unwinding.
+    static LAST_PANIC: RefCell<Option<(String, Backtrace)>> = const { RefCell::new(None) };
+}
+
+/// A caught panic.
+#[derive(Debug)]
+pub struct Panic {
+    pub message: String,
+    /// `file:line:column` of the panic, if the hook saw it.
+    pub location: Option<String>,
+    pub backtrace: Option<String>,
+}
+
+/// Record the location and backtrace of every panic for crash reports,
+/// then print it as before.
+pub fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        let location = info.location().map(ToString::to_string).unwrap_or_default();
+        let backtrace = Backtrace::force_capture();
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some((location, backtrace)));
+        default_hook(info);
+    }));
+}
+
+/// Run `f`, returning the panic instead of unwinding into the caller.
+pub async fn catch<F: Future>(f: F) -> Result<F::Output, Panic> {
+    LAST_PANIC.with(|last| last.borrow_mut().take());
+    AssertUnwindSafe(f).catch_unwind().await.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned());
+        let (location, backtrace) = LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .map(|(location, backtrace)| (Some(location), Some(backtrace.to_string())))
+            .unwrap_or_default();
+        Panic {
+            message,
+            location,
+            backtrace,
+        }
+    })
+}
+
+/// Log `panic` and write a crash report for it; `context` says what was
+/// running, e.g. the tool call.
+pub async fn report(state: &Arc<RwLock<AgentState>>, panic: &Panic, context: &str) {
+    let logs = state.read().await.logs.clone();
+    tracing::error!(
+        location = panic.location.as_deref().unwrap_or("unknown"),
+        "Panic in {context}: {}",
+        panic.message
+    );
+    let dir = crash_dir();
+    let path = dir.join(format!(
+        "crash-{}.txt",
+        Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&path, render(panic, context, &logs)));
+    match result {
+        Ok(()) => {
+            tracing::error!(path = %path.display(), "Crash report written");
+            prune(&dir);
+        }
+        Err(e) => tracing::error!("Cannot write crash report to {}: {e}", path.display()),
+    }
+}
+
+/// Directory holding the crash reports.
+pub fn crash_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("aios")
+        .join("crashes")
+}
+
+fn render(panic: &Panic, context: &str, logs: &LogBuffer) -> String {
+    let mut report = String::new();
+    let _ = writeln!(
+        report,
+        "aios-agent {} crash report",
+        env!("CARGO_PKG_VERSION")
+    );
+    let _ = writeln!(report, "time: {}", Utc::now().to_rfc3339());
+    if let Some(request_id) = telemetry::current() {
+        let _ = writeln!(report, "request_id: {request_id}");
+    }
+    let _ = writeln!(report, "context: {context}");
+    let _ = writeln!(report, "panic: {}", panic.message);
+    if let Some(location) = &panic.location {
+        let _ = writeln!(report, "location: {location}");
+    }
+    if let Some(backtrace) = &panic.backtrace {
+        let _ = write!(report, "\nbacktrace:\n{backtrace}\n");
+    }
+
+    let lines = logs.after(0);
+    let tail = &lines[lines.len().saturating_sub(REPORT_LOG_LINES)..];
+    report.push_str("\nlog:\n");
+    for line in tail {
+        report.push_str(&line.text);
+        report.push('\n');
+    }
+    // Arguments and log lines may hold tokens.
+    redact::redact_string(&mut report);
+    report
+}
+
+/// Delete all but the newest [`MAX_REPORTS`] reports; their names sort by
+/// time.
+fn prune(dir: &std::path::Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut reports: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("crash-"))
+        })
+        .collect();
+    reports.sort();
+    let excess = reports.len().saturating_sub(MAX_REPORTS);
+    for path in &reports[..excess] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn catch_returns_value_or_panic() {
+        assert_eq!(catch(async { 7 }).await.unwrap(), 7);
+
+        let panic = catch(async { Vec::<u8>::new()[0] }).await.unwrap_err();
+        assert!(panic.message.contains("index out of bounds"));
+
+        let panic = catch(async { panic!("tool {} broke", "x") })
+            .await
+            .unwrap_err();
+        assert_eq!(panic.message, "tool x broke");
+    }
+
+    #[test]
+    fn report_includes_context_and_redacted_log() {
+        let logs = LogBuffer::default();
+        logs.push("calling with api_key=sk-ant-REDACTED");
+        let panic = Panic {
+            message: "boom".to_owned(),
+            location: Some("src/tool.rs:1:1".to_owned()),
+            backtrace: None,
+        };
+
+        let report = render(&panic, "tool shell_exec", &logs);
+        assert!(report.contains("context: tool shell_exec"));
+        assert!(report.contains("panic: boom"));
+        assert!(report.contains("location: src/tool.rs:1:1"));
+        assert!(!report.contains("abcdefghijklmnopqrstuvwxyz0123"));
+    }
+}
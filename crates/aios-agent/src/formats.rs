@@ -0,0 +1,352 @@
+//! Fixed layouts for tool results the user reads as they are.
+//!
+//! Left to the LLM, the JSON of `system_info`, `wifi_list` and `gpu_info`
+//! comes back as a different table in every answer, with sizes in bytes now
+//! and then. Successful results of these tools are rendered here instead and
+//! shown below the answer, so the LLM only has to say what stands out. The
+//! templates: a card of host, load, memory and battery, tables of disks,
+//! processes and sensors, a table of networks and a table of GPUs.
+
+use aios_common::{ChargeState, ChatMessage, GpuInfo, MessageContent, SystemReport, ToolResult};
+use serde_json::Value;
+
+/// Tools whose results have a template.
+pub const TEMPLATED: [&str; 3] = ["system_info", "wifi_list", "gpu_info"];
+
+/// Markdown for the result of `tool`; `None` for failed calls, other tools
+/// and output that does not parse.
+pub fn render(tool: &str, result: &ToolResult) -> Option<String> {
+    if result.is_error {
+        return None;
+    }
+    match tool {
+        "system_info" => system_info(&serde_json::from_str(&result.output).ok()?),
+        "wifi_list" => wifi_list(&serde_json::from_str(&result.output).ok()?),
+        "gpu_info" => gpu_info(&serde_json::from_str(&result.output).ok()?),
+        _ => None,
+    }
+}
+
+/// Add `cards` below the text of `answer`.
+pub fn attach(answer: &mut ChatMessage, cards: &[String]) {
+    if cards.is_empty() {
+        return;
+    }
+    if let MessageContent::Text { text } = &mut answer.content {
+        for card in cards {
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            text.push_str(card);
+        }
+    }
+}
+
+fn system_info(report: &SystemReport) -> Option<String> {
+    let mut blocks = Vec::new();
+
+    let mut card = Vec::new();
+    if let Some(host) = &report.host {
+        let mut title = format!("**{}**", cell(&host.hostname));
+        if !host.os.is_empty() {
+            title.push_str(&format!(" · {}", cell(&host.os)));
+        }
+        if !host.kernel.is_empty() {
+            title.push_str(&format!(" · kernel {}", cell(&host.kernel)));
+        }
+        card.push(title);
+        card.push(format!("- CPU: {} × {}", cell(&host.cpu_model), host.cpu_count));
+        card.push(format!("- Uptime: {}", uptime(host.uptime_secs)));
+    }
+    if let Some(load) = &report.load {
+        card.push(format!(
+            "- Load: {:.2} / {:.2} / {:.2} ({} of {} tasks running)",
+            load.one, load.five, load.fifteen, load.running, load.tasks
+        ));
+    }
+    if let Some(memory) = &report.memory {
+        card.push(format!(
+            "- Memory: {} of {} used ({}%)",
+            size(memory.total.saturating_sub(memory.available)),
+            size(memory.total),
+            memory.used_percent()
+        ));
+        if memory.swap_total > 0 {
+            card.push(format!(
+                "- Swap: {} of {} used",
+                size(memory.swap_total.saturating_sub(memory.swap_free)),
+                size(memory.swap_total)
+            ));
+        }
+    }
+    if let Some(battery) = &report.battery {
+        let mut line = format!("- Battery: {}%", battery.percent);
+        if let Some(state) = charge_state(battery.state) {
+            line.push_str(&format!(", {state}"));
+        }
+        if let Some(minutes) = battery.minutes_to_empty {
+            line.push_str(&format!(", {} left", uptime(u64::from(minutes) * 60)));
+        }
+        card.push(line);
+    }
+    if !card.is_empty() {
+        blocks.push(card.join("\n"));
+    }
+
+    if let Some(disks) = report.disks.as_ref().filter(|d| !d.is_empty()) {
+        let rows = disks.iter().map(|d| {
+            vec![
+                cell(&d.mount),
+                size(d.used),
+                size(d.available),
+                size(d.total),
+                format!("{}%", percent(d.used, d.total)),
+            ]
+        });
+        blocks.push(table(&["Mount", "Used", "Free", "Size", "Use"], rows));
+    }
+    if let Some(processes) = report.processes.as_ref().filter(|p| !p.is_empty()) {
+        let rows = processes.iter().map(|p| {
+            vec![
+                p.pid.to_string(),
+                cell(&p.name),
+                format!("{:.1}%", p.cpu_percent),
+                size(p.memory),
+            ]
+        });
+        blocks.push(table(&["PID", "Process", "CPU", "Memory"], rows));
+    }
+    if let Some(thermal) = report.thermal.as_ref().filter(|t| !t.is_empty()) {
+        let rows = thermal
+            .iter()
+            .map(|zone| vec![cell(&zone.name), format!("{:.0} °C", zone.celsius)]);
+        blocks.push(table(&["Sensor", "Temperature"], rows));
+    }
+
+    (!blocks.is_empty()).then(|| blocks.join("\n\n"))
+}
+
+/// `wifi_list` output is an array of `{ssid, signal, security, connected}`.
+fn wifi_list(networks: &[Value]) -> Option<String> {
+    if networks.is_empty() {
+        return None;
+    }
+    let rows = networks.iter().map(|network| {
+        let ssid = network["ssid"].as_str().unwrap_or_default();
+        let name = match ssid {
+            "" => "*hidden*".to_owned(),
+            ssid if network["connected"].as_bool() == Some(true) => {
+                format!("**{}** (connected)", cell(ssid))
+            }
+            ssid => cell(ssid),
+        };
+        let security = match network["security"].as_str().unwrap_or_default() {
+            "" | "--" => "open".to_owned(),
+            security => cell(security),
+        };
+        let signal = network["signal"].as_u64().unwrap_or_default();
+        vec![name, format!("{signal}%"), security]
+    });
+    Some(table(&["Network", "Signal", "Security"], rows))
+}
+
+fn gpu_info(gpus: &[GpuInfo]) -> Option<String> {
+    if gpus.is_empty() {
+        return None;
+    }
+    let unknown = || "–".to_owned();
+    let rows = gpus.iter().map(|gpu| {
+        let memory = match (gpu.memory_used, gpu.memory_total) {
+            (Some(used), Some(total)) => format!(
+                "{} of {} ({}%)",
+                size(used),
+                size(total),
+                percent(used, total)
+            ),
+            _ => unknown(),
+        };
+        vec![
+            cell(&gpu.name),
+            gpu.utilization.map_or_else(unknown, |load| format!("{load}%")),
+            memory,
+            gpu.celsius.map_or_else(unknown, |c| format!("{c:.0} °C")),
+            gpu.watts.map_or_else(unknown, |w| format!("{w:.0} W")),
+        ]
+    });
+    Some(table(&["GPU", "Load", "Video memory", "Temperature", "Power"], rows))
+}
+
+/// A markdown table with `header` over `rows`.
+fn table(header: &[&str], rows: impl Iterator<Item = Vec<String>>) -> String {
+    let mut lines = vec![
+        format!("| {} |", header.join(" | ")),
+        format!("|{}", "---|".repeat(header.len())),
+    ];
+    lines.extend(rows.map(|row| format!("| {} |", row.join(" | "))));
+    lines.join("\n")
+}
+
+/// `value` safe to put in a table cell or a line.
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+fn charge_state(state: ChargeState) -> Option<&'static str> {
+    match state {
+        ChargeState::Unknown => None,
+        ChargeState::Charging => Some("charging"),
+        ChargeState::Discharging => Some("discharging"),
+        ChargeState::Full => Some("full"),
+        ChargeState::NotCharging => Some("plugged in, not charging"),
+    }
+}
+
+fn percent(part: u64, total: u64) -> u64 {
+    if total == 0 { 0 } else { part * 100 / total }
+}
+
+/// `bytes` for people: "4.7 GB", "310 MB".
+fn size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit >= 3 {
+        format!("{size:.1} {}", UNITS[unit])
+    } else {
+        format!("{size:.0} {}", UNITS[unit])
+    }
+}
+
+/// "3d 4h", "5h 12m" or "7m".
+fn uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aios_common::{Role, TrustLevel};
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn result(output: Value) -> ToolResult {
+        ToolResult {
+            call_id: Uuid::new_v4(),
+            output: output.to_string(),
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn system_info_sections_become_a_card_and_tables() {
+        let output = json!({
+            "host": {
+                "hostname": "laptop",
+                "kernel": "6.8.0-45-generic",
+                "os": "Debian GNU/Linux 13 (trixie)",
+                "cpu_model": "AMD Ryzen 7",
+                "cpu_count": 16,
+                "uptime_secs": 273_600
+            },
+            "memory": {
+                "total": 16_000_000_000u64,
+                "available": 4_000_000_000u64,
+                "swap_total": 0,
+                "swap_free": 0
+            },
+            "disks": [
+                { "mount": "/", "total": 500_000_000_000u64, "used": 125_000_000_000u64,
+                  "available": 375_000_000_000u64 }
+            ]
+        });
+        let markdown = render("system_info", &result(output)).unwrap();
+        assert_eq!(
+            markdown,
+            "**laptop** · Debian GNU/Linux 13 (trixie) · kernel 6.8.0-45-generic\n\
+             - CPU: AMD Ryzen 7 × 16\n\
+             - Uptime: 3d 4h\n\
+             - Memory: 12.0 GB of 16.0 GB used (75%)\n\
+             \n\
+             | Mount | Used | Free | Size | Use |\n\
+             |---|---|---|---|---|\n\
+             | / | 125.0 GB | 375.0 GB | 500.0 GB | 25% |"
+        );
+    }
+
+    #[test]
+    fn wifi_networks_become_a_table() {
+        let output = json!([
+            { "ssid": "Home|5G", "signal": 82, "security": "WPA2", "connected": true },
+            { "ssid": "Cafe", "signal": 40, "security": "--", "connected": false }
+        ]);
+        let markdown = render("wifi_list", &result(output)).unwrap();
+        assert_eq!(
+            markdown,
+            "| Network | Signal | Security |\n\
+             |---|---|---|\n\
+             | **Home\\|5G** (connected) | 82% | WPA2 |\n\
+             | Cafe | 40% | open |"
+        );
+    }
+
+    #[test]
+    fn gpus_become_a_table() {
+        let output = json!([
+            { "name": "NVIDIA GeForce RTX 3060", "utilization": 35,
+              "memory_used": 2_000_000_000u64, "memory_total": 12_000_000_000u64,
+              "celsius": 54.0, "watts": 28.4 },
+            { "name": "AMD GPU (card1)", "utilization": 3 }
+        ]);
+        let markdown = render("gpu_info", &result(output)).unwrap();
+        assert_eq!(
+            markdown,
+            "| GPU | Load | Video memory | Temperature | Power |\n\
+             |---|---|---|---|---|\n\
+             | NVIDIA GeForce RTX 3060 | 35% | 2.0 GB of 12.0 GB (16%) | 54 °C | 28 W |\n\
+             | AMD GPU (card1) | 3% | – | – | – |"
+        );
+    }
+
+    #[test]
+    fn failures_and_other_tools_are_left_to_the_llm() {
+        let mut failed = result(json!([]));
+        failed.is_error = true;
+        failed.output = "Failed to list networks: nmcli not found".to_owned();
+        assert!(render("wifi_list", &failed).is_none());
+        assert!(render("wifi_list", &result(json!([]))).is_none());
+        assert!(render("file_list", &result(json!({ "entries": [] }))).is_none());
+    }
+
+    #[test]
+    fn cards_go_below_the_answer() {
+        let mut answer = ChatMessage {
+            id: Uuid::new_v4(),
+            role: Role::Assistant,
+            content: MessageContent::Text {
+                text: "Your disk is a quarter full.".to_owned(),
+            },
+            trust_level: TrustLevel::System,
+            timestamp: Utc::now(),
+        };
+        attach(&mut answer, &["| a |\n|---|".to_owned()]);
+        let MessageContent::Text { text } = &answer.content else {
+            panic!("expected text");
+        };
+        assert_eq!(text, "Your disk is a quarter full.\n\n| a |\n|---|");
+    }
+}
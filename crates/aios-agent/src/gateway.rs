@@ -0,0 +1,329 @@
+//! Optional HTTP gateway, from `[gateway]`, so web dashboards and
+//! home-automation systems can talk to the agent without the IPC socket.
+//!
+//! Every request needs `Authorization: Bearer <token>`.
+//!
+//! - `POST /chat` with `{"message", "conversation_id"?}` runs a chat turn
+//!   and answers with server-sent events: `conversation` (the id, new when
+//!   none was given), then `message` (the reply and its usage) or `error`.
+//!   Closing the stream cancels the turn.
+//! - `GET /conversations` lists conversations; `GET /conversations/{id}`
+//!   returns the history and `DELETE /conversations/{id}` clears it.
+//! - `GET /confirmations` lists tool calls waiting for approval;
+//!   `POST /confirmations/{action_id}` with `{"approved", "remember"?}`
+//!   answers one; `remember` approves the tool for the rest of the
+//!   conversation when the request's `can_remember` allows it.
+//!   While the gateway runs, confirmations wait for an answer here even
+//!   when no Confirm client is connected.
+//! - `GET /mcp/sse` opens an MCP session of [`mcp_server`] over SSE; its
+//!   first event names where to post the session's messages,
+//!   `/mcp/messages?sessionId=`.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use aios_common::{
+    ChatMessage, GatewayConfig, IpcMessage, IpcPayload, MessageContent, Role, telemetry,
+};
+use anyhow::{Context, bail};
+use axum::extract::{Path, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio::sync::{RwLock, mpsc, watch};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::mcp_server;
+use crate::router;
+use crate::state::AgentState;
+use crate::tool_executor;
+
+/// Characters of the first user message shown in conversation lists.
+const PREVIEW_CHARS: usize = 80;
+
+#[derive(Clone)]
+struct Gateway {
+    state: Arc<RwLock<AgentState>>,
+    token: Arc<str>,
+}
+
+/// Serve the gateway on `config.listen` until the listener fails.
+pub async fn run(config: GatewayConfig, state: Arc<RwLock<AgentState>>) -> anyhow::Result<()> {
+    if config.token.is_empty() {
+        bail!("[gateway] is enabled but has no token");
+    }
+    let listener = TcpListener::bind(&config.listen)
+        .await
+        .with_context(|| format!("cannot listen on {}", config.listen))?;
+    tracing::info!(listen = %config.listen, "HTTP gateway listening");
+    state.write().await.remote_confirms = true;
+
+    let mcp = mcp_server::sse_router(Arc::clone(&state), listener.local_addr()?);
+    let gateway = Gateway {
+        state,
+        token: config.token.into(),
+    };
+    let app = Router::new()
+        .route("/chat", post(chat))
+        .route("/conversations", get(list_conversations))
+        .route(
+            "/conversations/{id}",
+            get(get_conversation).delete(clear_conversation),
+        )
+        .route("/confirmations", get(list_confirmations))
+        .route("/confirmations/{id}", post(answer_confirmation))
+        .nest_service("/mcp", mcp)
+        .layer(middleware::from_fn_with_state(gateway.clone(), authorize))
+        .with_state(gateway.clone());
+
+    let result = axum::serve(listener, app).await;
+    gateway.state.write().await.remote_confirms = false;
+    Ok(result?)
+}
+
+/// Reject requests without the configured bearer token.
+async fn authorize(State(gateway): State<Gateway>, request: Request, next: Next) -> Response {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        Some(token) if same_token(token.as_bytes(), gateway.token.as_bytes()) => {
+            next.run(request).await
+        }
+        _ => error(StatusCode::UNAUTHORIZED, "Missing or wrong bearer token"),
+    }
+}
+
+/// Compare without returning early, so response times do not reveal how
+/// much of a guess was right.
+fn same_token(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+// -- Chat --
+
+#[derive(Deserialize)]
+struct ChatBody {
+    message: String,
+    #[serde(default)]
+    conversation_id: Option<Uuid>,
+}
+
+/// Cancels the chat turn when the event stream is dropped, i.e. when the
+/// client disconnects before the reply.
+struct CancelOnDrop(watch::Sender<()>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.send_replace(());
+    }
+}
+
+async fn chat(
+    State(gateway): State<Gateway>,
+    Json(body): Json<ChatBody>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let conversation_id = body.conversation_id.unwrap_or_else(Uuid::new_v4);
+    tracing::info!(%conversation_id, "Chat request via gateway");
+
+    let (events, rx) = mpsc::unbounded_channel();
+    let (cancel_tx, cancel_rx) = watch::channel(());
+    let _ = events.send(
+        Event::default()
+            .event("conversation")
+            .data(conversation_id.to_string()),
+    );
+    let request_id = Uuid::new_v4();
+    let turn = async move {
+        let request = IpcMessage {
+            id: request_id,
+            payload: IpcPayload::ChatRequest {
+                message: body.message,
+                conversation_id,
+                message_id: None,
+                attachments: Vec::new(),
+            },
+        };
+        let response = router::route_message(request, Uuid::new_v4(), &gateway.state, &cancel_rx)
+            .await
+            .map(|response| response.payload);
+        let event = match response {
+            Some(IpcPayload::ChatResponse {
+                message,
+                usage,
+                reasoning,
+            }) => Event::default()
+                .event("message")
+                .json_data(json!({ "message": message, "usage": usage, "reasoning": reasoning }))
+                .unwrap_or_else(|e| Event::default().event("error").data(e.to_string())),
+            Some(IpcPayload::Error { message, .. }) => {
+                Event::default().event("error").data(message)
+            }
+            _ => Event::default().event("error").data("No reply"),
+        };
+        let _ = events.send(event);
+    };
+    tokio::spawn(
+        telemetry::scope(request_id, turn).instrument(telemetry::request_span(request_id)),
+    );
+
+    Sse::new(event_stream(rx, CancelOnDrop(cancel_tx))).keep_alive(KeepAlive::default())
+}
+
+/// The events sent to `rx`; `guard` is dropped with the stream.
+fn event_stream<G: Send + 'static>(
+    rx: mpsc::UnboundedReceiver<Event>,
+    guard: G,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    futures::stream::unfold((rx, guard), |(mut rx, guard)| async move {
+        let event = rx.recv().await?;
+        Some((Ok(event), (rx, guard)))
+    })
+}
+
+// -- Conversations --
+
+#[derive(Serialize)]
+struct ConversationSummary {
+    id: Uuid,
+    messages: usize,
+    /// Time of the last message.
+    updated: Option<DateTime<Utc>>,
+    /// Start of the first user message.
+    preview: Option<String>,
+}
+
+async fn list_conversations(State(gateway): State<Gateway>) -> Json<Vec<ConversationSummary>> {
+    let (mut conversations, saved) = {
+        let state = gateway.state.read().await;
+        let resident: Vec<ConversationSummary> = state
+            .conversations
+            .iter()
+            .map(|(id, conversation)| summarize(*id, &conversation.messages))
+            .collect();
+        (resident, state.conversations.saved())
+    };
+    // Conversations evicted from memory are listed from their saved copy,
+    // read without holding up the agent.
+    let saved = tokio::task::spawn_blocking(move || saved.read())
+        .await
+        .unwrap_or_default();
+    conversations.extend(saved.iter().map(|(id, messages)| summarize(*id, messages)));
+    conversations.sort_by_key(|c| std::cmp::Reverse(c.updated));
+    Json(conversations)
+}
+
+fn summarize(id: Uuid, messages: &[ChatMessage]) -> ConversationSummary {
+    ConversationSummary {
+        id,
+        messages: messages.len(),
+        updated: messages.last().map(|m| m.timestamp),
+        preview: messages.iter().find_map(preview),
+    }
+}
+
+fn preview(message: &ChatMessage) -> Option<String> {
+    match &message.content {
+        MessageContent::Text { text } if message.role == Role::User => {
+            Some(text.chars().take(PREVIEW_CHARS).collect())
+        }
+        _ => None,
+    }
+}
+
+async fn get_conversation(
+    State(gateway): State<Gateway>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ChatMessage>>, Response> {
+    let state = gateway.state.read().await;
+    state
+        .conversations
+        .messages(&id)
+        .map(Json)
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "No such conversation"))
+}
+
+async fn clear_conversation(State(gateway): State<Gateway>, Path(id): Path<Uuid>) -> StatusCode {
+    let request = IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::ClearConversation {
+            conversation_id: id,
+        },
+    };
+    // Clearing never waits on a turn, so a fresh cancel channel is enough.
+    let (_cancel_tx, cancel_rx) = watch::channel(());
+    router::route_message(request, Uuid::new_v4(), &gateway.state, &cancel_rx).await;
+    StatusCode::NO_CONTENT
+}
+
+// -- Confirmations --
+
+async fn list_confirmations(State(gateway): State<Gateway>) -> Json<Vec<Value>> {
+    let state = gateway.state.read().await;
+    let pending = state
+        .pending_confirms
+        .values()
+        .filter_map(|pending| match &pending.request {
+            IpcPayload::ConfirmRequest {
+                action_id,
+                action_type,
+                description,
+                command,
+                trust_level,
+                risk,
+                can_remember,
+            } => Some(json!({
+                "action_id": action_id,
+                "action_type": action_type,
+                "description": description,
+                "command": command,
+                "trust_level": trust_level,
+                "risk": risk,
+                "can_remember": can_remember,
+            })),
+            _ => None,
+        })
+        .collect();
+    Json(pending)
+}
+
+#[derive(Deserialize)]
+struct ConfirmBody {
+    approved: bool,
+    #[serde(default)]
+    remember: bool,
+}
+
+async fn answer_confirmation(
+    State(gateway): State<Gateway>,
+    Path(action_id): Path<Uuid>,
+    Json(body): Json<ConfirmBody>,
+) -> Response {
+    tracing::info!(%action_id, approved = body.approved, "Confirmation answered via gateway");
+    let answered =
+        tool_executor::answer_confirmation(&gateway.state, action_id, body.approved, body.remember)
+            .await;
+    if answered {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        error(
+            StatusCode::NOT_FOUND,
+            "No pending confirmation with this id",
+        )
+    }
+}
@@ -2,23 +2,44 @@ use std::num::NonZeroU16;
 use std::pin::Pin;
 
 use aios_common::{
-    ChatMessage, MessageContent, ProviderConfig, Role as AiosRole, TrustLevel,
+    ChatMessage, MessageContent, ModelInfo, ProviderConfig, Role as AiosRole, TokenUsage,
+    TrustLevel,
 };
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use futures::Stream;
 use misanthropic::prompt::message::{Content, Role as ClaudeRole};
 use misanthropic::{Client, Prompt};
+use serde::Deserialize;
 use uuid::Uuid;
 
 use super::types::{LlmRequest, LlmResponse, StreamDelta};
-use super::LlmProvider;
+use super::{LlmProvider, CATALOG_TIMEOUT};
 
 /// Claude provider backed by the `misanthropic` crate.
 pub struct ClaudeProvider {
     client: Client,
     model: String,
+    /// For the model list, which `misanthropic` does not cover.
+    http: reqwest::Client,
+    api_key: String,
+    api_base: String,
+}
+
+/// Response from `GET /v1/models`, newest models first.
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
 }
 
 impl ClaudeProvider {
@@ -27,9 +48,17 @@ impl ClaudeProvider {
         let client = Client::new(config.api_key.clone())
             .map_err(|e| anyhow::anyhow!("Invalid Anthropic API key: {e}"))?;
 
+        let api_base = match &config.base_url {
+            Some(url) if !url.is_empty() => url.trim_end_matches('/').to_owned(),
+            _ => "https://api.anthropic.com".to_owned(),
+        };
+
         Ok(Self {
             client,
             model: config.model.clone(),
+            http: reqwest::Client::new(),
+            api_key: config.api_key.clone(),
+            api_base,
         })
     }
 
@@ -105,9 +134,17 @@ impl LlmProvider for ClaudeProvider {
             timestamp: Utc::now(),
         };
 
+        let usage = TokenUsage {
+            input_tokens: u64::from(response.usage.input_tokens),
+            output_tokens: u64::from(response.usage.output_tokens),
+        };
+
         Ok(LlmResponse {
             message: chat_message,
             has_tool_calls,
+            usage,
+            plan: None,
+            reasoning: None,
         })
     }
 
@@ -127,18 +164,48 @@ impl LlmProvider for ClaudeProvider {
     fn name(&self) -> &str {
         "claude"
     }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let list: ModelList = self
+            .http
+            .get(format!("{}/v1/models?limit=1000", self.api_base))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .timeout(CATALOG_TIMEOUT)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .context("Claude model list request failed")?
+            .json()
+            .await
+            .context("Failed to parse Claude model list")?;
+
+        Ok(list
+            .data
+            .into_iter()
+            .map(|entry| ModelInfo {
+                display_name: entry.display_name,
+                created: entry.created_at,
+                ..ModelInfo::new(entry.id)
+            })
+            .collect())
+    }
 }
 
 /// Extract plain text from a `MessageContent` value.
 fn extract_text(content: &MessageContent) -> String {
     match content {
-        MessageContent::Text { text } => text.clone(),
+        MessageContent::Text { text } | MessageContent::Plan { text } => text.clone(),
         MessageContent::ToolUse { tool_calls } => {
             serde_json::to_string(tool_calls).unwrap_or_default()
         }
         MessageContent::ToolResult { results } => results
             .iter()
-            .map(|r| r.output.as_str())
+            .map(|r| r.llm_output())
             .collect::<Vec<_>>()
             .join("\n"),
     }
@@ -1,17 +1,25 @@
 pub mod claude;
 pub mod ollama;
 pub mod openai;
+pub mod overflow;
+pub mod react;
+pub mod reasoning;
 pub mod system_prompt;
 pub mod types;
 
 use std::pin::Pin;
+use std::time::Duration;
 
+use aios_common::ModelInfo;
 use anyhow::Result;
 use async_trait::async_trait;
 use futures::Stream;
 
 use self::types::{LlmRequest, LlmResponse, StreamDelta};
 
+/// Longest wait for a provider's model catalog.
+const CATALOG_TIMEOUT: Duration = Duration::from_secs(15);
+
 /// Trait abstracting an LLM provider.
 ///
 /// Implementors must be safe to share across threads (`Send + Sync`) since the
@@ -37,6 +45,12 @@ pub trait LlmProvider: Send + Sync {
 
     /// Provider name for logging and diagnostics.
     fn name(&self) -> &str;
+
+    /// Model requests are sent to, for usage reports.
+    fn model(&self) -> &str;
+
+    /// Models the provider offers, from its catalog.
+    async fn list_models(&self) -> Result<Vec<ModelInfo>>;
 }
 
 /// Factory function: create a boxed `LlmProvider` from the shared config.
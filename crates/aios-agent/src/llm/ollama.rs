@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::pin::Pin;
 
 use anyhow::{Context, Result};
@@ -5,15 +6,23 @@ use async_trait::async_trait;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 
-use aios_common::{ChatMessage, MessageContent, ProviderConfig, Role};
+use aios_common::{ChatMessage, MessageContent, ModelInfo, ProviderConfig, Role, TokenUsage};
 
+use super::{react, reasoning};
 use super::types::{LlmRequest, LlmResponse, StreamDelta};
-use super::LlmProvider;
+use super::{LlmProvider, CATALOG_TIMEOUT};
+
+/// Times a reply with a malformed tool call is sent back for a fix.
+const MAX_REPAIRS: usize = 1;
 
 /// Ollama provider — talks to a local Ollama instance via its HTTP API.
+/// Many local models lack function calling, so tools go through the
+/// text format of [`react`] instead.
 pub struct OllamaProvider {
     base_url: String,
     model: String,
+    /// Ask the model to think first (`reasoning` in `[provider]`).
+    think: bool,
     client: reqwest::Client,
 }
 
@@ -26,10 +35,13 @@ struct OllamaMessage {
 
 /// Request body for `POST /api/chat`.
 #[derive(Debug, Serialize)]
-struct OllamaChatRequest {
+struct OllamaChatRequest<'a> {
     model: String,
-    messages: Vec<OllamaMessage>,
+    messages: &'a [OllamaMessage],
     stream: bool,
+    /// Only sent when asked for: models without thinking reject it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     options: Option<OllamaOptions>,
 }
@@ -46,11 +58,44 @@ struct OllamaOptions {
 #[derive(Debug, Deserialize)]
 struct OllamaChatResponse {
     message: OllamaResponseMessage,
+    /// Prompt tokens evaluated.
+    #[serde(default)]
+    prompt_eval_count: u64,
+    /// Tokens generated.
+    #[serde(default)]
+    eval_count: u64,
 }
 
 #[derive(Debug, Deserialize)]
 struct OllamaResponseMessage {
     content: String,
+    /// Thinking of a model asked to `think`; others write it into
+    /// `content` between `<think>` tags.
+    #[serde(default)]
+    thinking: Option<String>,
+}
+
+/// Response from `GET /api/tags`: the models pulled to this Ollama.
+#[derive(Debug, Deserialize)]
+struct OllamaTags {
+    models: Vec<OllamaTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTag {
+    name: String,
+    #[serde(default)]
+    size: Option<u64>,
+    #[serde(default)]
+    modified_at: Option<String>,
+    #[serde(default)]
+    details: Option<OllamaTagDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagDetails {
+    #[serde(default)]
+    parameter_size: Option<String>,
 }
 
 impl OllamaProvider {
@@ -76,11 +121,13 @@ impl OllamaProvider {
         Ok(Self {
             base_url,
             model,
+            think: config.reasoning.is_some(),
             client,
         })
     }
 
-    /// Convert internal ChatMessage to Ollama API format.
+    /// Convert internal ChatMessage to Ollama API format. Tool calls and
+    /// results are written in the text format of [`react`].
     fn convert_messages(system_prompt: &str, messages: &[ChatMessage]) -> Vec<OllamaMessage> {
         let mut out = Vec::new();
 
@@ -92,6 +139,8 @@ impl OllamaProvider {
             });
         }
 
+        // Results only carry the call id; label them with the tool name.
+        let mut names = HashMap::new();
         for msg in messages {
             let role = match msg.role {
                 Role::User => "user",
@@ -101,13 +150,20 @@ impl OllamaProvider {
             };
 
             let content = match &msg.content {
-                MessageContent::Text { text } => text.clone(),
+                MessageContent::Text { text } | MessageContent::Plan { text } => text.clone(),
                 MessageContent::ToolUse { tool_calls } => {
-                    serde_json::to_string(tool_calls).unwrap_or_default()
-                }
-                MessageContent::ToolResult { results } => {
-                    serde_json::to_string(results).unwrap_or_default()
+                    for call in tool_calls {
+                        names.insert(call.id, call.name.clone());
+                    }
+                    tool_calls
+                        .iter()
+                        .map(react::render_call)
+                        .collect::<Vec<_>>()
+                        .join("\n")
                 }
+                // Attachments stay out of the prompt; only the text output is
+                // meaningful to the model.
+                MessageContent::ToolResult { results } => react::render_results(results, &names),
             };
 
             if !content.is_empty() {
@@ -120,17 +176,18 @@ impl OllamaProvider {
 
         out
     }
-}
-
-#[async_trait]
-impl LlmProvider for OllamaProvider {
-    async fn complete(&self, req: &LlmRequest) -> Result<LlmResponse> {
-        let messages = Self::convert_messages(&req.system_prompt, &req.messages);
 
+    /// One `POST /api/chat` round trip.
+    async fn chat(
+        &self,
+        req: &LlmRequest,
+        messages: &[OllamaMessage],
+    ) -> Result<OllamaChatResponse> {
         let body = OllamaChatRequest {
             model: self.model.clone(),
             messages,
             stream: false,
+            think: self.think.then_some(true),
             options: Some(OllamaOptions {
                 temperature: Some(req.temperature),
                 num_predict: if req.max_tokens > 0 {
@@ -159,24 +216,77 @@ impl LlmProvider for OllamaProvider {
             anyhow::bail!("Ollama returned {status}: {body_text}");
         }
 
-        let chat_resp: OllamaChatResponse = response
+        response
             .json()
             .await
-            .context("Failed to parse Ollama response")?;
+            .context("Failed to parse Ollama response")
+    }
+}
 
+#[async_trait]
+impl LlmProvider for OllamaProvider {
+    async fn complete(&self, req: &LlmRequest) -> Result<LlmResponse> {
+        // Tools are described in the prompt rather than sent natively.
+        let mut system_prompt = req.system_prompt.clone();
+        if !req.tools.is_empty() {
+            system_prompt.push_str(&react::render_tools(&req.tools));
+        }
+        let mut messages = Self::convert_messages(&system_prompt, &req.messages);
+
+        let mut usage = TokenUsage::default();
+        let mut repairs = 0;
+        let (content, plan, thinking) = loop {
+            let chat_resp = self.chat(req, &messages).await?;
+            usage += TokenUsage {
+                input_tokens: chat_resp.prompt_eval_count,
+                output_tokens: chat_resp.eval_count,
+            };
+            // Thinking may mention tools; only the answer can call them.
+            let (reply, thought) = reasoning::split(&chat_resp.message.content);
+            let thinking = chat_resp.message.thinking.as_deref();
+            let thinking = reasoning::join(thinking.into_iter().chain(thought.as_deref()));
+            match react::parse(&reply, &req.tools) {
+                Ok(calls) if calls.is_empty() => {
+                    break (MessageContent::Text { text: reply }, None, thinking);
+                }
+                Ok(calls) => {
+                    let plan = react::preamble(&reply).map(str::to_owned);
+                    break (MessageContent::ToolUse { tool_calls: calls }, plan, thinking);
+                }
+                Err(problem) if repairs < MAX_REPAIRS => {
+                    tracing::debug!(%problem, "Asking Ollama to fix a tool call");
+                    repairs += 1;
+                    messages.push(OllamaMessage {
+                        role: "assistant".to_owned(),
+                        content: reply,
+                    });
+                    messages.push(OllamaMessage {
+                        role: "user".to_owned(),
+                        content: react::correction(&problem),
+                    });
+                }
+                Err(problem) => {
+                    tracing::warn!(%problem, "Ollama wrote an unusable tool call");
+                    break (MessageContent::Text { text: reply }, None, thinking);
+                }
+            }
+        };
+
+        let has_tool_calls = matches!(content, MessageContent::ToolUse { .. });
         let message = ChatMessage {
             id: uuid::Uuid::new_v4(),
             role: Role::Assistant,
-            content: MessageContent::Text {
-                text: chat_resp.message.content,
-            },
+            content,
             trust_level: aios_common::TrustLevel::System,
             timestamp: chrono::Utc::now(),
         };
 
         Ok(LlmResponse {
             message,
-            has_tool_calls: false,
+            has_tool_calls,
+            usage,
+            plan,
+            reasoning: thinking,
         })
     }
 
@@ -194,4 +304,42 @@ impl LlmProvider for OllamaProvider {
     fn name(&self) -> &str {
         "ollama"
     }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let url = format!("{}/api/tags", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(CATALOG_TIMEOUT)
+            .send()
+            .await
+            .context("Failed to connect to Ollama — is it running?")?;
+        if !response.status().is_success() {
+            anyhow::bail!("Ollama returned {}", response.status());
+        }
+        let tags: OllamaTags = response
+            .json()
+            .await
+            .context("Failed to parse Ollama model list")?;
+
+        let mut models: Vec<ModelInfo> = tags
+            .models
+            .into_iter()
+            .map(|tag| ModelInfo {
+                size_bytes: tag.size,
+                parameters: tag.details.and_then(|d| d.parameter_size),
+                created: tag
+                    .modified_at
+                    .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+                    .map(|t| t.to_utc()),
+                ..ModelInfo::new(tag.name)
+            })
+            .collect();
+        models.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(models)
+    }
 }
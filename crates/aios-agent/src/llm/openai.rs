@@ -1,7 +1,8 @@
 use std::pin::Pin;
 
 use aios_common::{
-    ChatMessage, MessageContent, ProviderConfig, Role as AiosRole, TrustLevel,
+    ChatMessage, MessageContent, ModelInfo, ProviderConfig, ReasoningEffort, Role as AiosRole,
+    TokenUsage, TrustLevel,
 };
 use anyhow::{Context, Result};
 use async_openai::{
@@ -10,22 +11,44 @@ use async_openai::{
         ChatCompletionRequestAssistantMessage, ChatCompletionRequestMessage,
         ChatCompletionRequestSystemMessage, ChatCompletionRequestToolMessage,
         ChatCompletionRequestUserMessage, ChatCompletionTool, ChatCompletionTools,
-        CreateChatCompletionRequest, FunctionObject,
+        CreateChatCompletionRequest, FunctionObject, ReasoningEffort as OpenAiReasoningEffort,
     },
     Client,
 };
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use futures::Stream;
+use serde::Deserialize;
 use uuid::Uuid;
 
+use super::reasoning;
 use super::types::{LlmRequest, LlmResponse, StreamDelta};
-use super::LlmProvider;
+use super::{LlmProvider, CATALOG_TIMEOUT};
 
 /// OpenAI provider backed by the `async-openai` crate.
 pub struct OpenAiProvider {
     client: Client<OpenAIConfig>,
     model: String,
+    /// Effort asked of reasoning models (`reasoning` in `[provider]`).
+    reasoning: Option<ReasoningEffort>,
+    /// For the model list, which the client's feature set leaves out.
+    http: reqwest::Client,
+    api_key: String,
+    api_base: String,
+}
+
+/// Response from `GET /models`.
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+    /// Unix time.
+    #[serde(default)]
+    created: Option<i64>,
 }
 
 impl OpenAiProvider {
@@ -37,14 +60,29 @@ impl OpenAiProvider {
             openai_config = openai_config.with_api_base(base_url);
         }
 
+        let api_base = match &config.base_url {
+            Some(url) if !url.is_empty() => url.trim_end_matches('/').to_owned(),
+            _ => "https://api.openai.com/v1".to_owned(),
+        };
         let client = Client::with_config(openai_config);
 
         Ok(Self {
             client,
             model: config.model.clone(),
+            reasoning: config.reasoning,
+            http: reqwest::Client::new(),
+            api_key: config.api_key.clone(),
+            api_base,
         })
     }
 
+    /// Whether the model is one of OpenAI's reasoning models, which take a
+    /// reasoning effort but no temperature.
+    fn is_reasoning_model(&self) -> bool {
+        let model = self.model.as_str();
+        ["o1", "o3", "o4", "gpt-5"].iter().any(|prefix| model.starts_with(prefix))
+    }
+
     /// Convert our `ChatMessage` to async-openai's `ChatCompletionRequestMessage`.
     fn convert_message(msg: &ChatMessage) -> Option<ChatCompletionRequestMessage> {
         match msg.role {
@@ -118,12 +156,23 @@ impl LlmProvider for OpenAiProvider {
             Some(req.tools.iter().map(Self::convert_tool).collect())
         };
 
+        let reasoning_model = self.is_reasoning_model();
+        let reasoning_effort = self
+            .reasoning
+            .filter(|_| reasoning_model)
+            .map(|effort| match effort {
+                ReasoningEffort::Low => OpenAiReasoningEffort::Low,
+                ReasoningEffort::Medium => OpenAiReasoningEffort::Medium,
+                ReasoningEffort::High => OpenAiReasoningEffort::High,
+            });
+
         #[allow(deprecated)]
         let request = CreateChatCompletionRequest {
             model: self.model.clone(),
             messages,
             max_completion_tokens: Some(req.max_tokens),
-            temperature: Some(req.temperature),
+            temperature: (!reasoning_model).then_some(req.temperature),
+            reasoning_effort,
             tools,
             ..Default::default()
         };
@@ -135,6 +184,15 @@ impl LlmProvider for OpenAiProvider {
             .await
             .context("OpenAI chat completion request failed")?;
 
+        let usage = response
+            .usage
+            .as_ref()
+            .map(|u| TokenUsage {
+                input_tokens: u64::from(u.prompt_tokens),
+                output_tokens: u64::from(u.completion_tokens),
+            })
+            .unwrap_or_default();
+
         // Extract the first choice.
         let choice = response
             .choices
@@ -145,7 +203,10 @@ impl LlmProvider for OpenAiProvider {
         let response_msg = choice.message;
         let has_tool_calls = response_msg.tool_calls.is_some();
 
-        let content_text = response_msg.content.unwrap_or_default();
+        // OpenAI keeps its models' reasoning to itself; compatible servers
+        // running open reasoning models send it in the content.
+        let (content_text, thinking) =
+            reasoning::split(&response_msg.content.unwrap_or_default());
 
         let chat_message = ChatMessage {
             id: Uuid::new_v4(),
@@ -158,6 +219,9 @@ impl LlmProvider for OpenAiProvider {
         Ok(LlmResponse {
             message: chat_message,
             has_tool_calls,
+            usage,
+            plan: None,
+            reasoning: thinking,
         })
     }
 
@@ -179,12 +243,42 @@ impl LlmProvider for OpenAiProvider {
     fn name(&self) -> &str {
         "openai"
     }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let list: ModelList = self
+            .http
+            .get(format!("{}/models", self.api_base))
+            .bearer_auth(&self.api_key)
+            .timeout(CATALOG_TIMEOUT)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .context("OpenAI model list request failed")?
+            .json()
+            .await
+            .context("Failed to parse OpenAI model list")?;
+
+        let mut models: Vec<ModelInfo> = list
+            .data
+            .into_iter()
+            .map(|entry| ModelInfo {
+                created: entry.created.and_then(|t| DateTime::from_timestamp(t, 0)),
+                ..ModelInfo::new(entry.id)
+            })
+            .collect();
+        models.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(models)
+    }
 }
 
 /// Extract plain text from a `MessageContent` value.
 fn extract_text(content: &MessageContent) -> String {
     match content {
-        MessageContent::Text { text } => text.clone(),
+        MessageContent::Text { text } | MessageContent::Plan { text } => text.clone(),
         MessageContent::ToolUse { tool_calls } => {
             // Serialize tool calls as JSON for context.
             serde_json::to_string(tool_calls).unwrap_or_default()
@@ -193,7 +287,7 @@ fn extract_text(content: &MessageContent) -> String {
             // Concatenate tool outputs.
             results
                 .iter()
-                .map(|r| r.output.as_str())
+                .map(|r| r.llm_output())
                 .collect::<Vec<_>>()
                 .join("\n")
         }
@@ -0,0 +1,211 @@
+//! Smaller requests for when a conversation outgrows the model's context.
+//!
+//! Providers reject such a request with a 400 or 413 and a message about
+//! the context length. Instead of showing that to the user, the agent tries
+//! again with the history shrunk by the next [`STEPS`] entry: tool outputs
+//! cut shorter, then older turns dropped in favour of a short summary of
+//! what the user asked in them. Only the request shrinks; the conversation
+//! keeps every message.
+
+use aios_common::{ChatMessage, MessageContent, Role};
+
+/// How much of the history one attempt keeps.
+struct Step {
+    /// Longest tool output, in characters.
+    tool_output: usize,
+    /// Turns kept whole, counted from the newest; `None` keeps them all.
+    turns: Option<usize>,
+}
+
+/// Attempts after the first, gentlest first.
+const STEPS: [Step; 3] = [
+    Step {
+        tool_output: 4_000,
+        turns: None,
+    },
+    Step {
+        tool_output: 1_000,
+        turns: Some(4),
+    },
+    Step {
+        tool_output: 200,
+        turns: Some(1),
+    },
+];
+
+/// Longest line of the summary of an earlier request.
+const SUMMARY_LINE: usize = 120;
+
+/// Phrases providers use when a request does not fit the context.
+const MARKERS: [&str; 7] = [
+    "context_length_exceeded",
+    "context length",
+    "context window",
+    "prompt is too long",
+    "request_too_large",
+    "payload too large",
+    "too many tokens",
+];
+
+/// Whether `error` says the request was too large for the model.
+pub fn is_context_error(error: &anyhow::Error) -> bool {
+    let message = format!("{error:#}").to_lowercase();
+    MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+/// `messages` shrunk for the attempt after `attempt` failed (`0` for the
+/// first); `None` when there is nothing left to try.
+pub fn shrink(messages: &[ChatMessage], attempt: usize) -> Option<Vec<ChatMessage>> {
+    let step = STEPS.get(attempt)?;
+    let mut messages = messages.to_vec();
+    if let Some(turns) = step.turns {
+        drop_turns(&mut messages, turns);
+    }
+    for message in &mut messages {
+        if let MessageContent::ToolResult { results } = &mut message.content {
+            for result in results {
+                if let Some(cut) = clip(&result.output, step.tool_output) {
+                    result.output = format!("{cut}\n[output shortened to fit the context]");
+                }
+            }
+        }
+    }
+    Some(messages)
+}
+
+/// Keep the newest `turns` turns, each starting with a user's text, and
+/// tell the first kept one what the user asked before.
+fn drop_turns(messages: &mut Vec<ChatMessage>, turns: usize) {
+    let starts: Vec<usize> = messages
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.role == Role::User && matches!(m.content, MessageContent::Text { .. }))
+        .map(|(index, _)| index)
+        .collect();
+    let Some(&cut) = starts.len().checked_sub(turns.max(1)).and_then(|i| starts.get(i)) else {
+        return;
+    };
+    if cut == 0 {
+        return;
+    }
+    let asked: Vec<String> = messages
+        .drain(..cut)
+        .filter(|m| m.role == Role::User)
+        .filter_map(|m| match m.content {
+            MessageContent::Text { text } => {
+                let line = text.lines().find(|l| !l.trim().is_empty())?.trim().to_owned();
+                Some(match clip(&line, SUMMARY_LINE) {
+                    Some(cut) => format!("- {cut}…"),
+                    None => format!("- {line}"),
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    if let MessageContent::Text { text } = &mut messages[0].content {
+        *text = format!(
+            "[Earlier messages were left out to fit the model's context. The user had \
+             asked:\n{}]\n\n{text}",
+            asked.join("\n")
+        );
+    }
+}
+
+/// The first `max` characters of `text`, if it is longer.
+fn clip(text: &str, max: usize) -> Option<&str> {
+    text.char_indices().nth(max).map(|(end, _)| &text[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use aios_common::{ToolCall, ToolResult, TrustLevel};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn message(role: Role, content: MessageContent) -> ChatMessage {
+        ChatMessage {
+            id: Uuid::new_v4(),
+            role,
+            content,
+            trust_level: TrustLevel::User,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn text(role: Role, text: &str) -> ChatMessage {
+        message(role, MessageContent::Text { text: text.to_owned() })
+    }
+
+    fn history() -> Vec<ChatMessage> {
+        let call_id = Uuid::new_v4();
+        vec![
+            text(Role::User, "List my downloads"),
+            message(
+                Role::Assistant,
+                MessageContent::ToolUse {
+                    tool_calls: vec![ToolCall {
+                        id: call_id,
+                        name: "file_list".to_owned(),
+                        arguments: serde_json::json!({ "path": "~/Downloads" }),
+                        trust_level: TrustLevel::User,
+                    }],
+                },
+            ),
+            message(
+                Role::Tool,
+                MessageContent::ToolResult {
+                    results: vec![ToolResult {
+                        call_id,
+                        output: "x".repeat(10_000),
+                        is_error: false,
+                        error_kind: None,
+                        attachments: Vec::new(),
+                    }],
+                },
+            ),
+            text(Role::Assistant, "You have 300 files."),
+            text(Role::User, "Delete the oldest one"),
+        ]
+    }
+
+    fn tool_output(messages: &[ChatMessage]) -> Option<usize> {
+        messages.iter().find_map(|m| match &m.content {
+            MessageContent::ToolResult { results } => Some(results[0].output.chars().count()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn context_errors_are_recognized() {
+        let openai = anyhow::anyhow!(
+            "This model's maximum context length is 8192 tokens. However, your messages \
+             resulted in 9001 tokens."
+        );
+        let claude = anyhow::anyhow!("prompt is too long: 210000 tokens > 200000 maximum")
+            .context("Claude message request failed");
+        assert!(is_context_error(&openai));
+        assert!(is_context_error(&claude));
+        assert!(!is_context_error(&anyhow::anyhow!("invalid x-api-key")));
+    }
+
+    #[test]
+    fn each_step_keeps_less() {
+        let history = history();
+
+        let first = shrink(&history, 0).unwrap();
+        assert_eq!(first.len(), history.len());
+        assert!(tool_output(&first).unwrap() < 4_100);
+
+        let last = shrink(&history, 2).unwrap();
+        assert_eq!(last.len(), 1);
+        let MessageContent::Text { text } = &last[0].content else {
+            panic!("expected text");
+        };
+        assert!(text.contains("- List my downloads]"), "{text}");
+        assert!(text.ends_with("\n\nDelete the oldest one"));
+
+        assert!(shrink(&history, STEPS.len()).is_none());
+    }
+}
@@ -0,0 +1,260 @@
+//! Text-based tool calling for models without native function calling.
+//!
+//! The tool definitions are rendered into the system prompt, one compact
+//! line per tool, and the model is asked to answer with lines like
+//!
+//! ```text
+//! TOOL: file_read {"path": "/etc/hostname"}
+//! ```
+//!
+//! which [`parse`] turns back into [`ToolCall`]s after checking them
+//! against the definitions. Small models follow the format less reliably
+//! than they use native tools; a rejected reply comes with a description of
+//! what was wrong, for the provider to ask again.
+
+use std::collections::HashMap;
+
+use aios_common::{ToolCall, ToolDefinition, ToolResult, TrustLevel};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Marker that starts a tool call line.
+const MARKER: &str = "TOOL:";
+
+/// Instructions and one line per tool, appended to the system prompt.
+pub fn render_tools(tools: &[ToolDefinition]) -> String {
+    let mut out = String::from(
+        "\n\n## Tools\n\
+         To use a tool, reply with one line per call:\n\
+         TOOL: <name> {\"<parameter>\": <value>}\n\
+         Arguments are a JSON object; parameters marked * are required. The results \
+         come back in the next message. Without a tool call, reply normally.\n",
+    );
+    for tool in tools {
+        out.push_str(&format!("- {}({})", tool.name, parameters(&tool.parameters)));
+        let summary = summary(&tool.description);
+        if !summary.is_empty() {
+            out.push_str(": ");
+            out.push_str(summary);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// `path*: string, mode: read|write` from a JSON schema.
+fn parameters(schema: &Value) -> String {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else {
+        return String::new();
+    };
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let star = if required.contains(&name.as_str()) { "*" } else { "" };
+            let kind = match property.get("enum").and_then(|e| e.as_array()) {
+                Some(values) => values
+                    .iter()
+                    .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_owned))
+                    .collect::<Vec<_>>()
+                    .join("|"),
+                None => property
+                    .get("type")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("any")
+                    .to_owned(),
+            };
+            format!("{name}{star}: {kind}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// First sentence of a tool description.
+fn summary(description: &str) -> &str {
+    let line = description.lines().next().unwrap_or_default().trim();
+    match line.find(". ") {
+        Some(end) => &line[..end],
+        None => line.trim_end_matches('.'),
+    }
+}
+
+/// Tool calls in a model reply, in the order written; empty for a plain
+/// answer. `Err` describes the first malformed or unknown call.
+pub fn parse(reply: &str, tools: &[ToolDefinition]) -> Result<Vec<ToolCall>, String> {
+    let mut calls = Vec::new();
+    let mut rest = reply;
+    while let Some(start) = find_marker(rest) {
+        let after = rest[start + MARKER.len()..].trim_start_matches([' ', '\t']);
+        let name_end = after
+            .find(|c: char| c.is_whitespace() || c == '{' || c == '`')
+            .unwrap_or(after.len());
+        let name = &after[..name_end];
+        let mut tail = after[name_end..].trim_start_matches([' ', '\t']);
+
+        let arguments = if tail.starts_with('{') {
+            let mut values = serde_json::Deserializer::from_str(tail).into_iter::<Value>();
+            match values.next() {
+                Some(Ok(arguments)) => {
+                    tail = &tail[values.byte_offset()..];
+                    arguments
+                }
+                _ => return Err(format!("the arguments of '{name}' are not valid JSON")),
+            }
+        } else {
+            Value::Object(serde_json::Map::new())
+        };
+
+        check(name, &arguments, tools)?;
+        calls.push(ToolCall {
+            id: Uuid::new_v4(),
+            name: name.to_owned(),
+            arguments,
+            trust_level: TrustLevel::System,
+        });
+        rest = tail;
+    }
+    Ok(calls)
+}
+
+/// What a reply with tool calls says before the first of them, usually
+/// the model's plan; `None` if it starts with a call.
+pub fn preamble(reply: &str) -> Option<&str> {
+    let text = &reply[..find_marker(reply)?];
+    let text = text.trim_matches(|c: char| c.is_whitespace() || c == '`');
+    (!text.is_empty()).then_some(text)
+}
+
+/// Byte offset of the next marker that starts a line, allowing for the
+/// indentation and backticks of a code block.
+fn find_marker(text: &str) -> Option<usize> {
+    text.match_indices(MARKER).map(|(i, _)| i).find(|&i| {
+        let line_start = text[..i].rfind('\n').map_or(0, |n| n + 1);
+        text[line_start..i].trim_matches(|c: char| c.is_whitespace() || c == '`').is_empty()
+    })
+}
+
+/// Whether a call names a known tool with the arguments it requires.
+fn check(name: &str, arguments: &Value, tools: &[ToolDefinition]) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(format!("'{MARKER}' must be followed by a tool name"));
+    }
+    let Some(tool) = tools
+        .iter()
+        .find(|tool| tool.name == name || tool.aliases.iter().any(|alias| alias == name))
+    else {
+        return Err(format!("there is no tool named '{name}'"));
+    };
+    let Some(arguments) = arguments.as_object() else {
+        return Err(format!("the arguments of '{name}' must be a JSON object"));
+    };
+    let required = tool.parameters.get("required").and_then(|r| r.as_array());
+    let missing: Vec<&str> = required
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .filter(|parameter| !arguments.contains_key(*parameter))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("'{name}' requires {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+/// A past tool call as the model would have written it.
+pub fn render_call(call: &ToolCall) -> String {
+    format!("{MARKER} {} {}", call.name, call.arguments)
+}
+
+/// Results of earlier calls, labelled with the tool names from `names`
+/// (call id to name).
+pub fn render_results(results: &[ToolResult], names: &HashMap<Uuid, String>) -> String {
+    results
+        .iter()
+        .map(|result| {
+            let name = names.get(&result.call_id).map_or("tool", String::as_str);
+            format!("Result of {name}:\n{}", result.llm_output())
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Message asking the model to fix a rejected reply.
+pub fn correction(problem: &str) -> String {
+    format!(
+        "Your tool call could not be used: {problem}. Reply again, with each call on its \
+         own line as {MARKER} <name> {{JSON arguments}} and only the tools listed."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use aios_common::TrustRequirement;
+    use serde_json::json;
+
+    use super::*;
+
+    fn tools() -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: "file_read".to_owned(),
+            description: "Read the contents of a file. Large files are truncated.".to_owned(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" },
+                    "encoding": { "type": "string", "enum": ["utf8", "base64"] }
+                },
+                "required": ["path"]
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: vec!["read_file".to_owned()],
+        }]
+    }
+
+    #[test]
+    fn renders_one_line_per_tool() {
+        let rendered = render_tools(&tools());
+        assert!(rendered.contains(
+            "- file_read(encoding: utf8|base64, path*: string): Read the contents of a file\n"
+        ));
+    }
+
+    #[test]
+    fn parses_calls_in_a_reply() {
+        let reply = "I'll look.\n```\nTOOL: file_read {\"path\": \"/a\"}\n\
+                     TOOL: read_file {\n  \"path\": \"/b\"\n}\n```";
+        let calls = parse(reply, &tools()).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].name, "file_read");
+        assert_eq!(calls[0].arguments, json!({ "path": "/a" }));
+        assert_eq!(calls[1].name, "read_file");
+        assert_eq!(calls[1].arguments, json!({ "path": "/b" }));
+    }
+
+    #[test]
+    fn text_before_the_calls_is_the_preamble() {
+        let reply = "I'll look at /a first.\n```\nTOOL: file_read {\"path\": \"/a\"}\n```";
+        assert_eq!(preamble(reply), Some("I'll look at /a first."));
+        assert_eq!(preamble("TOOL: file_read {\"path\": \"/a\"}"), None);
+        assert_eq!(preamble("No tools needed."), None);
+    }
+
+    #[test]
+    fn plain_answers_have_no_calls() {
+        let reply = "Use the TOOL: syntax only when needed.";
+        assert!(parse(reply, &tools()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_calls() {
+        let tools = tools();
+        assert!(parse("TOOL: rm_rf {}", &tools).unwrap_err().contains("no tool"));
+        assert!(parse("TOOL: file_read {path: /a}", &tools).unwrap_err().contains("JSON"));
+        assert!(parse("TOOL: file_read", &tools).unwrap_err().contains("requires path"));
+        assert!(parse("TOOL: file_read [1]", &tools).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+//! Thinking of reasoning models, kept apart from their answers.
+//!
+//! Models like deepseek-r1 and qwq write their thinking between `<think>`
+//! tags ahead of the answer, through Ollama and OpenAI-compatible servers
+//! alike. [`split`] takes it out, so the answer reaches the user and the
+//! tool-call parser alone, and the chat shows the thinking only on request.
+//! It is never sent back to the model.
+
+const OPEN: &str = "<think>";
+const CLOSE: &str = "</think>";
+
+/// The answer in `text` and the thinking before it, if any.
+pub fn split(text: &str) -> (String, Option<String>) {
+    if !text.contains(OPEN) && !text.contains(CLOSE) {
+        return (text.to_owned(), None);
+    }
+    let mut answer = String::new();
+    let mut thoughts = Vec::new();
+    let mut rest = text;
+    // Some chat templates put the opening tag into the prompt, so the
+    // reply starts with the thinking itself.
+    if let Some(end) = rest.find(CLOSE)
+        && !rest[..end].contains(OPEN)
+    {
+        thoughts.push(&rest[..end]);
+        rest = &rest[end + CLOSE.len()..];
+    }
+    while let Some(start) = rest.find(OPEN) {
+        answer.push_str(&rest[..start]);
+        let after = &rest[start + OPEN.len()..];
+        match after.find(CLOSE) {
+            Some(end) => {
+                thoughts.push(&after[..end]);
+                rest = &after[end + CLOSE.len()..];
+            }
+            // Cut off while thinking.
+            None => {
+                thoughts.push(after);
+                rest = "";
+            }
+        }
+    }
+    answer.push_str(rest);
+    (answer.trim().to_owned(), join(thoughts))
+}
+
+/// Thinking from several places, e.g. a separate field and tags in the
+/// text; `None` if all are blank.
+pub fn join<'a>(thoughts: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let thoughts: Vec<&str> = thoughts
+        .into_iter()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect();
+    (!thoughts.is_empty()).then(|| thoughts.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thinking_is_taken_out_of_the_answer() {
+        let (answer, thinking) = split("<think>\nThe user wants 2+2.\n</think>\n\n4");
+        assert_eq!(answer, "4");
+        assert_eq!(thinking.as_deref(), Some("The user wants 2+2."));
+
+        // Opening tag in the prompt template.
+        let (answer, thinking) = split("Hmm, a greeting.</think>Hello!");
+        assert_eq!(answer, "Hello!");
+        assert_eq!(thinking.as_deref(), Some("Hmm, a greeting."));
+
+        // Cut off by the token limit.
+        let (answer, thinking) = split("<think>Let me count");
+        assert_eq!(answer, "");
+        assert_eq!(thinking.as_deref(), Some("Let me count"));
+    }
+
+    #[test]
+    fn answers_without_thinking_are_untouched() {
+        assert_eq!(split("  Just this.\n"), ("  Just this.\n".to_owned(), None));
+        assert_eq!(split("<think> </think>Hi"), ("Hi".to_owned(), None));
+    }
+}
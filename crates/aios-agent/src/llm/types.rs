@@ -1,4 +1,4 @@
-use aios_common::{ChatMessage, ToolDefinition};
+use aios_common::{ChatMessage, TokenUsage, ToolDefinition};
 
 /// Request to an LLM provider.
 #[derive(Debug, Clone)]
@@ -23,6 +23,14 @@ pub struct LlmResponse {
     /// Whether the response contains tool calls (used in later steps).
     #[allow(dead_code)]
     pub has_tool_calls: bool,
+    /// Tokens consumed by this request, as reported by the provider (zero
+    /// when it does not say).
+    pub usage: TokenUsage,
+    /// What the model said it is about to do, written before its tool
+    /// calls.
+    pub plan: Option<String>,
+    /// Thinking of a reasoning model, kept out of `message`.
+    pub reasoning: Option<String>,
 }
 
 /// A single chunk from a streaming response (used in later steps).
@@ -0,0 +1,78 @@
+//! The coarse location for the prompt's locale section.
+//!
+//! With `[location] in_prompt` on, the agent asks GeoClue every
+//! [`REFRESH`] and keeps the answer in [`AgentState::location`]; turned
+//! off, the location is dropped at the next check. Docks see each lookup
+//! like a call of the `location` tool, so the location badge shows while
+//! it runs.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use aios_common::geoclue::{self, Location};
+use aios_common::{ClientType, IpcMessage, IpcPayload, LocationConfig, Sensor, telemetry};
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::state::AgentState;
+
+/// Age at which the location is looked up again.
+const REFRESH: Duration = Duration::from_secs(60 * 60);
+
+/// How often the config is read, so turning the option on or off takes
+/// effect without a restart.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Keep [`AgentState::location`] in step with `[location] in_prompt`.
+pub async fn run(state: Arc<RwLock<AgentState>>) {
+    let mut located_at: Option<Instant> = None;
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        if !LocationConfig::load().in_prompt {
+            if located_at.take().is_some() {
+                state.write().await.location = None;
+            }
+            continue;
+        }
+        if located_at.is_some_and(|at| at.elapsed() < REFRESH) {
+            continue;
+        }
+        located_at = Some(Instant::now());
+        match lookup(&state).await {
+            Ok(location) => {
+                tracing::debug!("Location for the prompt updated");
+                state.write().await.location = Some(location);
+            }
+            Err(e) => tracing::warn!("Location for the prompt unavailable: {e}"),
+        }
+    }
+}
+
+/// Ask GeoClue with the dock's location badge showing.
+async fn lookup(state: &Arc<RwLock<AgentState>>) -> Result<Location, geoclue::GeoclueError> {
+    let call_id = Uuid::new_v4();
+    let started = IpcPayload::ToolStarted {
+        call_id,
+        tool: "location".to_owned(),
+        sensors: vec![Sensor::Location],
+    };
+    notify_docks(state, started).await;
+    let location = geoclue::locate().await;
+    let finished = IpcPayload::ToolFinished {
+        call_id,
+        tool: "location".to_owned(),
+        success: location.is_ok(),
+    };
+    notify_docks(state, finished).await;
+    location
+}
+
+async fn notify_docks(state: &Arc<RwLock<AgentState>>, payload: IpcPayload) {
+    let message = IpcMessage {
+        id: telemetry::message_id(),
+        payload,
+    };
+    state.read().await.broadcast(ClientType::Dock, &message).await;
+}
@@ -0,0 +1,129 @@
+//! The agent's recent log output, kept in memory so Settings can show a live
+//! tail over IPC without reading the journal.
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use aios_common::LogLine;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Number of lines kept; older ones are dropped.
+const CAPACITY: usize = 500;
+
+#[derive(Default)]
+struct Lines {
+    /// Number of the last line pushed; lines are numbered from 1.
+    last_seq: u64,
+    lines: VecDeque<LogLine>,
+}
+
+/// Ring buffer of log lines, shared between the tracing layer writing it and
+/// the router answering `LogsRequest`.
+#[derive(Clone, Default)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<Lines>>,
+}
+
+impl LogBuffer {
+    /// Append one line, dropping the oldest when full.
+    pub fn push(&self, text: &str) {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.last_seq += 1;
+        let seq = inner.last_seq;
+        if inner.lines.len() == CAPACITY {
+            inner.lines.pop_front();
+        }
+        inner.lines.push_back(LogLine {
+            seq,
+            text: text.to_owned(),
+        });
+    }
+
+    /// Lines numbered above `after`, oldest first.
+    pub fn after(&self, after: u64) -> Vec<LogLine> {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner
+            .lines
+            .iter()
+            .filter(|line| line.seq > after)
+            .cloned()
+            .collect()
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = EventWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        EventWriter {
+            buffer: self.clone(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// Collects the output of one tracing event and files its lines into the
+/// buffer when dropped.
+pub struct EventWriter {
+    buffer: LogBuffer,
+    pending: Vec<u8>,
+}
+
+impl io::Write for EventWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for EventWriter {
+    fn drop(&mut self) {
+        for line in String::from_utf8_lossy(&self.pending).lines() {
+            if !line.trim().is_empty() {
+                self.buffer.push(line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use super::*;
+
+    #[test]
+    fn writer_splits_event_into_lines() {
+        let buffer = LogBuffer::default();
+        {
+            let mut writer = buffer.make_writer();
+            writer.write_all(b"first\nsecond").unwrap();
+            writer.write_all(b" part\n\n").unwrap();
+        }
+
+        let lines = buffer.after(0);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].seq, 1);
+        assert_eq!(lines[1].text, "second part");
+        assert_eq!(buffer.after(1).len(), 1);
+        assert!(buffer.after(2).is_empty());
+    }
+
+    #[test]
+    fn buffer_drops_oldest_lines() {
+        let buffer = LogBuffer::default();
+        for i in 0..CAPACITY + 10 {
+            buffer.push(&format!("line {i}"));
+        }
+
+        let lines = buffer.after(0);
+        assert_eq!(lines.len(), CAPACITY);
+        assert_eq!(lines[0].seq, 11);
+        assert_eq!(lines.last().unwrap().text, format!("line {}", CAPACITY + 9));
+    }
+}
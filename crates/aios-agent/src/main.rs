@@ -1,29 +1,73 @@
+mod activity;
 mod audit;
 mod config;
+mod confirm_client;
+mod conversations;
+mod crash;
+mod formats;
+mod gateway;
 mod llm;
+mod location;
+mod logs;
+mod mcp_server;
+mod mcp_servers;
+mod notifications;
+mod otlp;
+mod redact;
+mod risk;
 mod router;
 mod server;
 mod state;
+mod system_status;
 mod tool_executor;
+mod tool_filter;
+mod tool_slots;
+mod trust;
+mod usage;
 
 use std::sync::Arc;
 
-use aios_common::IpcServer;
+use aios_common::{IpcServer, TelemetryConfig};
+use aios_voice::stt::WhisperStt;
 use anyhow::Result;
 use tokio::sync::RwLock;
+use tracing_subscriber::layer::SubscriberExt as _;
+use tracing_subscriber::util::SubscriberInitExt as _;
 
 use crate::audit::AuditLogger;
+use crate::logs::LogBuffer;
+use crate::tool_slots::ToolSlots;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(
+    // Everything logged goes to stderr (the journal) and to an in-memory
+    // tail for the Settings app, and spans to the OTLP collector if one is
+    // configured. Lines logged while handling a request carry its
+    // `request_id`.
+    let logs = LogBuffer::default();
+    let (otlp_layer, otlp_error) = match otlp::layer(&TelemetryConfig::load()) {
+        Ok(layer) => (layer, None),
+        Err(e) => (None, Some(e)),
+    };
+    tracing_subscriber::registry()
+        .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "aios_agent=info".into()),
+                .unwrap_or_else(|_| "aios_agent=info,aios_common=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(logs.clone()),
         )
+        .with(otlp_layer)
         .init();
 
     tracing::info!("aios-agent starting...");
+    crash::install_hook();
+    if let Some(e) = otlp_error {
+        tracing::error!("Cannot export traces: {e:#}");
+    }
 
     let config = config::load_config()?;
     tracing::info!(socket = %config.agent.socket_path, "Loaded configuration");
@@ -34,15 +78,12 @@ async fn main() -> Result<()> {
     // Create the LLM provider from config. If the API key is empty (and provider
     // is not Ollama, which doesn't need one), fall back to echo mode and warn.
     let needs_api_key = config.provider.provider_type != aios_common::ProviderType::Ollama;
-    let state = if needs_api_key && config.provider.api_key.is_empty() {
+    let mut agent_state = if needs_api_key && config.provider.api_key.is_empty() {
         tracing::warn!(
             "No API key configured for {:?} provider -- running in echo mode",
             config.provider.provider_type,
         );
-        Arc::new(RwLock::new(state::AgentState::new(
-            audit_logger,
-            max_destructive,
-        )))
+        state::AgentState::new(audit_logger, max_destructive)
     } else {
         match llm::create_provider(&config.provider) {
             Ok(provider) => {
@@ -50,27 +91,69 @@ async fn main() -> Result<()> {
                     provider = provider.name(),
                     "LLM provider initialized successfully",
                 );
-                Arc::new(RwLock::new(state::AgentState::with_provider(
-                    provider,
-                    audit_logger,
-                    max_destructive,
-                )))
+                state::AgentState::with_provider(provider, audit_logger, max_destructive)
             }
             Err(e) => {
                 tracing::error!("Failed to initialize LLM provider: {e:#}");
                 tracing::warn!("Falling back to echo mode");
-                Arc::new(RwLock::new(state::AgentState::new(
-                    audit_logger,
-                    max_destructive,
-                )))
+                state::AgentState::new(audit_logger, max_destructive)
             }
         }
     };
 
+    agent_state.stt = WhisperStt::new(&config.voice);
+    agent_state.tool_registry.set_policies(config.tools.clone());
+    agent_state.tool_selection = config.tool_selection.clone();
+    agent_state.conversations.set_limits(config.conversations);
+    agent_state.tool_slots = Arc::new(ToolSlots::new(config.agent.max_parallel_tools));
+    agent_state.system_prompt = config.agent.system_prompt.clone();
+    agent_state.prompt = config.prompt.clone();
+    agent_state.audit_llm = config.agent.audit_llm;
+    agent_state.show_plans = config.agent.show_plans;
+    agent_state.confirm_command = config.agent.confirm_command.clone();
+    agent_state.logs = logs;
+    agent_state.publish_tools();
+    let state = Arc::new(RwLock::new(agent_state));
+    mcp_servers::connect(&state, config.mcp_servers.clone()).await;
+
     let ipc_server = IpcServer::bind(&config.agent.socket_path)?;
     tracing::info!(path = %config.agent.socket_path, "IPC server bound");
 
-    server::run_server(ipc_server, state).await?;
+    tokio::spawn(system_status::run(Arc::clone(&state)));
+    tokio::spawn(location::run(Arc::clone(&state)));
+    tokio::spawn(conversations::run(Arc::clone(&state)));
+    if config.gateway.enabled {
+        let gateway = gateway::run(config.gateway.clone(), Arc::clone(&state));
+        tokio::spawn(async move {
+            if let Err(e) = gateway.await {
+                tracing::error!("HTTP gateway stopped: {e:#}");
+            }
+        });
+    }
+    tokio::select! {
+        result = server::run_server(ipc_server, Arc::clone(&state)) => result?,
+        () = shutdown_signal() => tracing::info!("aios-agent shutting down"),
+    }
+    state.read().await.audit_logger.flush().await;
+    otlp::shutdown();
 
     Ok(())
 }
+
+/// Resolves on SIGTERM (systemd stopping the service) or Ctrl+C.
+async fn shutdown_signal() {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Cannot listen for SIGTERM: {e}");
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
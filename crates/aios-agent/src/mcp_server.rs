@@ -0,0 +1,278 @@
+//! AIOS as an MCP server: editors and other assistants list and call the
+//! tools of the registry, through the same pipeline as the agent's own
+//! calls (confirmation, rate limit, pause, audit).
+//!
+//! The protocol is rmcp's: [`McpServer`] is its [`ServerHandler`], and rmcp
+//! negotiates the protocol version, runs requests concurrently and passes
+//! on cancellations. Messages arrive over IPC from `aios-cli mcp`, which
+//! bridges an MCP client's stdio ([`relay`]), or over rmcp's SSE transport
+//! on the gateway ([`sse_router`]). Each session has its own working
+//! directory and environment, and is told when the tools on offer change.
+//!
+//! Calls come from a program whose inputs AIOS cannot see, so they carry
+//! the web-content trust level: confirmations show the critical dialog.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use aios_common::{
+    AttachmentContent, IpcMessage, IpcPayload, ToolCall, ToolDefinition, ToolResult, TrustLevel,
+    telemetry,
+};
+use aios_mcp::executor::ExecContext;
+use axum::Router;
+use futures::StreamExt;
+use futures::channel::mpsc;
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, ClientJsonRpcMessage, Content, Implementation,
+    JsonObject, ListToolsResult, PaginatedRequestParam, ServerCapabilities, ServerInfo, Tool,
+};
+use rmcp::service::{Peer, RequestContext};
+use rmcp::transport::IntoTransport;
+use rmcp::transport::sse_server::{SseServer, SseServerConfig};
+use rmcp::{ErrorData, RoleServer, ServerHandler, ServiceExt};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::sync::{RwLock, watch};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::activity;
+use crate::state::AgentState;
+use crate::tool_executor;
+
+/// Messages from an `aios-cli mcp` client to its session, see [`relay`].
+pub type IpcSession = mpsc::UnboundedSender<ClientJsonRpcMessage>;
+
+/// One MCP session. The tools run in its own working directory and
+/// environment.
+#[derive(Clone)]
+struct McpServer {
+    state: Arc<RwLock<AgentState>>,
+    exec: Arc<std::sync::Mutex<ExecContext>>,
+}
+
+impl ServerHandler for McpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
+            server_info: Implementation {
+                name: "aios".to_owned(),
+                version: env!("CARGO_PKG_VERSION").to_owned(),
+                ..Implementation::default()
+            },
+            instructions: Some(
+                "Tools of the AIOS desktop. Destructive ones wait for the user to confirm on \
+                 the AIOS screen; a rejected call returns an error."
+                    .to_owned(),
+            ),
+            ..ServerInfo::default()
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        let mut definitions = self.state.read().await.tool_registry.definitions();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        let tools = definitions.into_iter().map(tool).collect();
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    /// A call the client cancelled before it started is not run; once
+    /// started it runs to completion, like the tool calls of a chat turn.
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if context.ct.is_cancelled() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "The call was cancelled",
+            )]));
+        }
+        let tool_call = ToolCall {
+            id: Uuid::new_v4(),
+            name: request.name.into_owned(),
+            arguments: Value::Object(request.arguments.unwrap_or_default()),
+            trust_level: TrustLevel::WebContent,
+        };
+        tracing::info!(tool = %tool_call.name, "Tool call from MCP client");
+
+        let request_id = Uuid::new_v4();
+        let result = telemetry::scope(request_id, self.execute(&tool_call))
+            .instrument(telemetry::request_span(request_id))
+            .await;
+        Ok(call_result(result))
+    }
+}
+
+impl McpServer {
+    async fn execute(&self, tool_call: &ToolCall) -> ToolResult {
+        // Copied out of the lock: a confirmation takes the write lock.
+        let (registry, audit_logger) = {
+            let state_guard = self.state.read().await;
+            (
+                state_guard.tool_registry.clone(),
+                state_guard.audit_logger.clone(),
+            )
+        };
+        activity::tool_started(&self.state, tool_call).await;
+        let result = tool_executor::execute_tool_call(
+            tool_call,
+            &registry,
+            &self.state,
+            &audit_logger,
+            Arc::clone(&self.exec),
+            None,
+            None,
+        )
+        .await;
+        activity::tool_finished(&self.state, tool_call, &result).await;
+        result
+    }
+}
+
+/// A registry tool as MCP describes it.
+fn tool(definition: ToolDefinition) -> Tool {
+    let schema = match definition.parameters {
+        Value::Object(schema) => schema,
+        // MCP requires an object schema.
+        _ => JsonObject::from_iter([("type".to_owned(), json!("object"))]),
+    };
+    Tool::new(definition.name, definition.description, schema)
+}
+
+/// A `ToolResult` as MCP content: the output as text, prefixed with the
+/// error kind like for our own LLM, images inline.
+fn call_result(result: ToolResult) -> CallToolResult {
+    let mut content = vec![Content::text(result.llm_output().into_owned())];
+    for attachment in result.attachments {
+        if let AttachmentContent::Image { data } = attachment.content {
+            content.push(Content::image(data, attachment.media_type));
+        }
+    }
+    if result.is_error {
+        CallToolResult::error(content)
+    } else {
+        CallToolResult::success(content)
+    }
+}
+
+/// Serve one MCP session over `transport` until the client goes away.
+async fn serve<T, E, A>(state: Arc<RwLock<AgentState>>, transport: T)
+where
+    T: IntoTransport<RoleServer, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let tools = state.read().await.offered_tools.subscribe();
+    let server = McpServer {
+        state,
+        exec: Arc::default(),
+    };
+    let service = match server.serve(transport).await {
+        Ok(service) => service,
+        Err(e) => {
+            tracing::warn!("MCP session failed to start: {e}");
+            return;
+        }
+    };
+    tracing::info!("MCP client connected");
+    let announce = tokio::spawn(announce_tool_changes(tools, service.peer().clone()));
+    let _ = service.waiting().await;
+    announce.abort();
+    tracing::info!("MCP client disconnected");
+}
+
+/// Tell the client to list the tools again whenever they change, see
+/// [`AgentState::publish_tools`].
+async fn announce_tool_changes(mut tools: watch::Receiver<Vec<String>>, peer: Peer<RoleServer>) {
+    while tools.changed().await.is_ok() {
+        if let Err(e) = peer.notify_tool_list_changed().await {
+            tracing::debug!("Tools change notification failed: {e}");
+        }
+    }
+}
+
+/// Pass `message` from the `aios-cli mcp` client `client_id` to its
+/// session, starting one with its first message. What the session sends
+/// goes back in `McpMessage`s.
+pub async fn relay(state: &Arc<RwLock<AgentState>>, client_id: Uuid, message: Value) {
+    let message = match ClientJsonRpcMessage::deserialize(&message) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            tracing::warn!(%client_id, "Unsupported MCP message: {e}");
+            // A request still gets an answer, e.g. for an unknown method.
+            if let Some(id) = message.get("id") {
+                let error = ErrorData::invalid_request(e.to_string(), None);
+                let msg = IpcMessage {
+                    id: Uuid::new_v4(),
+                    payload: IpcPayload::McpMessage {
+                        message: json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+                    },
+                };
+                state.read().await.send_to(client_id, &msg).await;
+            }
+            return;
+        }
+    };
+    let mut state_guard = state.write().await;
+    let session = state_guard
+        .mcp_sessions
+        .entry(client_id)
+        .or_insert_with(|| start_ipc_session(state, client_id));
+    // Fails only once the session has ended.
+    let _ = session.unbounded_send(message);
+}
+
+/// Serve the session of `client_id` on channels; it ends when the returned
+/// sender is dropped with the client.
+fn start_ipc_session(state: &Arc<RwLock<AgentState>>, client_id: Uuid) -> IpcSession {
+    let (session, from_client) = mpsc::unbounded();
+    let (to_client, mut outgoing) = mpsc::unbounded();
+    tokio::spawn(serve(Arc::clone(state), (to_client, from_client)));
+
+    let state = Arc::clone(state);
+    tokio::spawn(async move {
+        while let Some(message) = outgoing.next().await {
+            let message = match serde_json::to_value(&message) {
+                Ok(message) => message,
+                Err(e) => {
+                    tracing::error!(%client_id, "Cannot encode MCP message: {e}");
+                    continue;
+                }
+            };
+            let msg = IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::McpMessage { message },
+            };
+            state.read().await.send_to(client_id, &msg).await;
+        }
+    });
+    session
+}
+
+/// Routes of the SSE transport, for the gateway to nest under a path:
+/// `GET /sse` opens a session, whose first event says where to `POST`
+/// its messages. `bind` is the gateway's address.
+pub fn sse_router(state: Arc<RwLock<AgentState>>, bind: SocketAddr) -> Router {
+    let (mut sse, router) = SseServer::new(SseServerConfig {
+        bind,
+        sse_path: "/sse".to_owned(),
+        post_path: "/messages".to_owned(),
+        ct: Default::default(),
+        sse_keep_alive: None,
+    });
+    // Ends when the router is dropped with the gateway.
+    tokio::spawn(async move {
+        while let Some(transport) = sse.next_transport().await {
+            tokio::spawn(serve(Arc::clone(&state), transport));
+        }
+    });
+    router
+}
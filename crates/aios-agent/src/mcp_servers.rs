@@ -0,0 +1,37 @@
+//! External MCP servers from `[mcp_servers]`.
+//!
+//! Servers are started in the background so a slow or broken one does not
+//! hold up the agent; their tools show up in the registry once connected,
+//! and MCP clients of the agent are told the tool list changed.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use aios_common::McpServerConfig;
+use tokio::sync::RwLock;
+
+use crate::state::AgentState;
+
+/// (Re)connect the servers in `servers` unless they are the ones already
+/// running. Servers dropped from the config are stopped.
+pub async fn connect(state: &Arc<RwLock<AgentState>>, servers: BTreeMap<String, McpServerConfig>) {
+    {
+        let mut state_guard = state.write().await;
+        if state_guard.mcp_servers == servers {
+            return;
+        }
+        state_guard.mcp_servers = servers.clone();
+    }
+
+    let state = Arc::clone(state);
+    tokio::spawn(async move {
+        let tools = aios_mcp::external::connect_all(&servers).await;
+        let mut state_guard = state.write().await;
+        // A reload may have changed the servers while these were starting.
+        if state_guard.mcp_servers == servers {
+            tracing::info!(tools = tools.len(), "External MCP tools registered");
+            state_guard.tool_registry.set_external(tools);
+            state_guard.publish_tools();
+        }
+    });
+}
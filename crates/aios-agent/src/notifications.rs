@@ -0,0 +1,139 @@
+//! Notification center backing store.
+//!
+//! Things that happen while nobody is looking -- a scheduled task finishing,
+//! a download completing, a confirmation request that timed out -- are kept
+//! here and pushed to dock clients, which show them behind the bell in the
+//! tray.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use aios_common::{AgentNotification, ClientType, IpcMessage, IpcPayload, NotificationKind};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::state::AgentState;
+
+/// Notifications kept; older ones are dropped.
+const MAX_NOTIFICATIONS: usize = 50;
+
+/// Recent notifications, newest first.
+#[derive(Default)]
+pub struct NotificationLog {
+    entries: VecDeque<AgentNotification>,
+}
+
+impl NotificationLog {
+    /// Add `notification` at the front, dropping the oldest past the cap.
+    pub fn push(&mut self, notification: AgentNotification) {
+        self.entries.push_front(notification);
+        self.entries.truncate(MAX_NOTIFICATIONS);
+    }
+
+    /// Mark everything as read. Returns `false` if nothing changed.
+    pub fn mark_all_read(&mut self) -> bool {
+        let mut changed = false;
+        for entry in self.entries.iter_mut().filter(|n| !n.read) {
+            entry.read = true;
+            changed = true;
+        }
+        changed
+    }
+
+    /// Drop everything. Returns `false` if the log was already empty.
+    pub fn clear(&mut self) -> bool {
+        let changed = !self.entries.is_empty();
+        self.entries.clear();
+        changed
+    }
+
+    /// The log as sent to docks.
+    pub fn message(&self) -> IpcMessage {
+        IpcMessage {
+            id: Uuid::new_v4(),
+            payload: IpcPayload::Notifications {
+                notifications: self.entries.iter().cloned().collect(),
+            },
+        }
+    }
+}
+
+/// Record a notification and push the updated list to every dock.
+pub async fn post(
+    state: &Arc<RwLock<AgentState>>,
+    kind: NotificationKind,
+    title: impl Into<String>,
+    body: impl Into<String>,
+) {
+    let notification = AgentNotification::new(kind, title, body);
+    tracing::info!(?kind, title = %notification.title, "Posting notification");
+    update(state, |log| {
+        log.push(notification);
+        true
+    })
+    .await;
+}
+
+/// Mark all notifications as read (the dock panel was opened).
+pub async fn mark_read(state: &Arc<RwLock<AgentState>>) {
+    update(state, NotificationLog::mark_all_read).await;
+}
+
+/// Drop all notifications.
+pub async fn clear(state: &Arc<RwLock<AgentState>>) {
+    update(state, NotificationLog::clear).await;
+}
+
+/// Apply `change` and broadcast the log if it reports a change.
+async fn update(
+    state: &Arc<RwLock<AgentState>>,
+    change: impl FnOnce(&mut NotificationLog) -> bool,
+) {
+    let mut guard = state.write().await;
+    if !change(&mut guard.notifications) {
+        return;
+    }
+    let message = guard.notifications.message();
+    let guard = guard.downgrade();
+    guard.broadcast(ClientType::Dock, &message).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(title: &str) -> AgentNotification {
+        AgentNotification::new(NotificationKind::Info, title, "")
+    }
+
+    fn titles(log: &NotificationLog) -> Vec<&str> {
+        log.entries.iter().map(|n| n.title.as_str()).collect()
+    }
+
+    #[test]
+    fn keeps_newest_first_and_caps_length() {
+        let mut log = NotificationLog::default();
+        for i in 0..MAX_NOTIFICATIONS + 5 {
+            log.push(notification(&i.to_string()));
+        }
+        assert_eq!(log.entries.len(), MAX_NOTIFICATIONS);
+        let last = (MAX_NOTIFICATIONS + 4).to_string();
+        assert_eq!(titles(&log).first(), Some(&last.as_str()));
+        assert_eq!(titles(&log).last(), Some(&"5"));
+    }
+
+    #[test]
+    fn mark_read_and_clear_report_changes() {
+        let mut log = NotificationLog::default();
+        assert!(!log.mark_all_read());
+        assert!(!log.clear());
+
+        log.push(notification("a"));
+        assert!(log.mark_all_read());
+        assert!(log.entries.iter().all(|n| n.read));
+        assert!(!log.mark_all_read());
+
+        assert!(log.clear());
+        assert!(titles(&log).is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+//! Export of request spans to an OpenTelemetry collector.
+//!
+//! Only built with the `otlp` feature; without it an endpoint in
+//! `[telemetry]` is reported and otherwise ignored.
+
+use aios_common::TelemetryConfig;
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// A layer sending spans to `[telemetry] otlp_endpoint`, or `None` when no
+/// endpoint is set.
+#[cfg(feature = "otlp")]
+pub fn layer<S>(config: &TelemetryConfig) -> anyhow::Result<Option<Box<dyn Layer<S> + Send + Sync>>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    use opentelemetry::KeyValue;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig as _;
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::trace::TracerProvider;
+
+    let Some(endpoint) = &config.otlp_endpoint else {
+        return Ok(None);
+    };
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", "aios-agent")]))
+        .build();
+    let tracer = provider.tracer("aios-agent");
+    opentelemetry::global::set_tracer_provider(provider);
+    Ok(Some(Box::new(
+        tracing_opentelemetry::layer().with_tracer(tracer),
+    )))
+}
+
+#[cfg(not(feature = "otlp"))]
+pub fn layer<S>(config: &TelemetryConfig) -> anyhow::Result<Option<Box<dyn Layer<S> + Send + Sync>>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    if config.otlp_endpoint.is_some() {
+        anyhow::bail!("otlp_endpoint is set, but aios-agent was built without the `otlp` feature");
+    }
+    Ok(None)
+}
+
+/// Send the spans still queued before the agent exits.
+pub fn shutdown() {
+    #[cfg(feature = "otlp")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
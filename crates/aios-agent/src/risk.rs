@@ -0,0 +1,333 @@
+//! Risk summary generation for confirmation requests.
+//!
+//! Derives a [`RiskSummary`] from a tool's definition and the arguments the
+//! LLM supplied, so the Confirm dialog can say "Deletes 1 file under
+//! ~/Documents, not reversible" instead of dumping raw JSON.
+
+use std::path::Path;
+
+use aios_common::{RiskSummary, ToolDefinition, TrustRequirement};
+use serde_json::Value;
+
+/// Argument keys that carry filesystem paths.
+const PATH_KEYS: &[&str] = &["path", "paths", "working_dir", "output_path"];
+
+/// Shell programs that imply network access when they appear in a command.
+const NETWORK_COMMANDS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "rsync", "git", "apt", "pip", "npm", "cargo", "nc", "ping",
+];
+
+/// Build a [`RiskSummary`] for a pending tool call.
+pub fn summarize(definition: &ToolDefinition, args: &Value) -> RiskSummary {
+    let affected_paths = collect_paths(args);
+    let reversible = definition.trust_requirement != TrustRequirement::DoubleConfirm;
+    let network_access = uses_network(&definition.name, args);
+
+    let mut summary = describe_action(&definition.name, args, &affected_paths);
+    if network_access {
+        summary.push_str(", uses the network");
+    }
+    summary.push_str(if reversible { ", reversible" } else { ", not reversible" });
+
+    RiskSummary {
+        summary,
+        affected_paths,
+        reversible,
+        network_access,
+    }
+}
+
+/// Produce the leading clause of the summary, e.g. "Writes 1 file under ~/notes".
+fn describe_action(tool_name: &str, args: &Value, paths: &[String]) -> String {
+    let str_arg = |key: &str| args.get(key).and_then(Value::as_str).unwrap_or_default();
+
+    match tool_name {
+        "file_delete" if args.get("permanent").and_then(Value::as_bool) == Some(true) => {
+            format!("Permanently deletes {}", describe_paths(paths))
+        }
+        "file_delete" => format!("Moves {} to the trash", describe_paths(paths)),
+        "trash_restore" => format!("Restores {} from the trash", describe_paths(paths)),
+        "undo_last" => "Undoes the most recent file change".to_owned(),
+        "file_write" if str_arg("mode") == "append" => {
+            format!("Appends to {}", describe_paths(paths))
+        }
+        "file_write" => format!("Writes {}", describe_paths(paths)),
+        "shell_exec" => {
+            let program = str_arg("command").split_whitespace().next().unwrap_or("sh");
+            match common_parent(paths) {
+                Some(dir) => format!("Runs `{program}` in {dir}"),
+                None => format!("Runs `{program}` in a shell"),
+            }
+        }
+        "shell_session" => {
+            let session = args.get("name").and_then(Value::as_str).unwrap_or("main");
+            match str_arg("action") {
+                "open" => format!("Opens shell session \"{session}\""),
+                "send" => {
+                    let program = str_arg("input").split_whitespace().next().unwrap_or("input");
+                    format!("Types `{program}` into shell session \"{session}\"")
+                }
+                "close" => format!("Closes shell session \"{session}\""),
+                _ => format!("Reads shell session \"{session}\""),
+            }
+        }
+        "wifi_connect" => format!("Connects to Wi-Fi network \"{}\"", str_arg("ssid")),
+        "open_url" | "browser_navigate" => format!("Opens {}", str_arg("url")),
+        "location" => "Tells the assistant where this computer is, to about a kilometer".to_owned(),
+        "contacts_add" => format!("Adds \"{}\" to the contacts", str_arg("name")),
+        "brightness" => {
+            let value = args.get("value").map(Value::to_string).unwrap_or_default();
+            format!("Sets {tool_name} to {value}")
+        }
+        "volume" => {
+            let device = match (str_arg("device"), str_arg("kind")) {
+                ("", "input") => "the default input".to_owned(),
+                ("", _) => "the default output".to_owned(),
+                (device, _) => format!("\"{device}\""),
+            };
+            let value = match args.get("value") {
+                Some(Value::String(value)) => value.trim_end_matches('%').to_owned(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+            match (str_arg("mute"), value.chars().next()) {
+                ("on", _) => format!("Mutes {device}"),
+                ("off", _) => format!("Unmutes {device}"),
+                ("toggle", _) => format!("Toggles mute of {device}"),
+                (_, Some('+')) => format!("Raises volume of {device} by {}%", &value[1..]),
+                (_, Some('-')) => format!("Lowers volume of {device} by {}%", &value[1..]),
+                (_, Some(_)) => format!("Sets volume of {device} to {value}%"),
+                (_, None) => "Reads audio volume".to_owned(),
+            }
+        }
+        "home_assistant" => match str_arg("action") {
+            "call_service" => {
+                let entity_id = str_arg("entity_id");
+                let domain = match str_arg("domain") {
+                    "" => entity_id.split('.').next().unwrap_or_default(),
+                    domain => domain,
+                };
+                let service = format!("{domain}.{}", str_arg("service"));
+                match entity_id {
+                    "" => format!("Calls Home Assistant service {service}"),
+                    entity => format!("Calls Home Assistant service {service} on {entity}"),
+                }
+            }
+            _ => "Reads Home Assistant entity states".to_owned(),
+        },
+        "browser_type" => "Types text into the current web page".to_owned(),
+        "browser_click" => "Clicks an element on the current web page".to_owned(),
+        _ if paths.is_empty() => format!("Runs {tool_name}"),
+        _ => format!("Runs {tool_name} on {}", describe_paths(paths)),
+    }
+}
+
+/// "1 file under ~/Documents" / "3 files under /tmp" / "/etc/hosts".
+fn describe_paths(paths: &[String]) -> String {
+    match paths {
+        [] => "no files".to_owned(),
+        [single] => single.clone(),
+        many => {
+            let count = many.len();
+            match common_parent(many) {
+                Some(dir) => format!("{count} files under {dir}"),
+                None => format!("{count} files"),
+            }
+        }
+    }
+}
+
+/// Longest common directory of `paths`, with `$HOME` shortened to `~`.
+fn common_parent(paths: &[String]) -> Option<String> {
+    let first = Path::new(paths.first()?);
+    let mut prefix = if paths.len() == 1 && first.extension().is_none() {
+        first.to_path_buf()
+    } else {
+        first.parent()?.to_path_buf()
+    };
+
+    for p in &paths[1..] {
+        while !Path::new(p).starts_with(&prefix) {
+            if !prefix.pop() {
+                return None;
+            }
+        }
+    }
+
+    let prefix = prefix.to_string_lossy().into_owned();
+    if prefix.is_empty() {
+        return None;
+    }
+    Some(abbreviate_home(&prefix))
+}
+
+/// Replace a leading home directory with `~`.
+fn abbreviate_home(path: &str) -> String {
+    if let Some(home) = dirs::home_dir()
+        && let Ok(rest) = Path::new(path).strip_prefix(&home)
+    {
+        if rest.as_os_str().is_empty() {
+            return "~".to_owned();
+        }
+        return format!("~/{}", rest.display());
+    }
+    path.to_owned()
+}
+
+/// Collect every path-like string argument.
+fn collect_paths(args: &Value) -> Vec<String> {
+    let mut out = Vec::new();
+    for key in PATH_KEYS {
+        match args.get(*key) {
+            Some(Value::String(s)) if !s.is_empty() => out.push(s.clone()),
+            Some(Value::Array(items)) => out.extend(
+                items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_owned),
+            ),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Heuristic: does this call touch the network?
+fn uses_network(tool_name: &str, args: &Value) -> bool {
+    if tool_name.starts_with("browser_")
+        || tool_name.starts_with("wifi_")
+        || tool_name == "home_assistant"
+        // GeoClue sends nearby Wi-Fi networks to a geolocation service.
+        || tool_name == "location"
+    {
+        return true;
+    }
+    if args.get("url").is_some() {
+        return true;
+    }
+    let command_key = match tool_name {
+        "shell_exec" => Some("command"),
+        "shell_session" => Some("input"),
+        _ => None,
+    };
+    if let Some(key) = command_key {
+        let command = args.get(key).and_then(Value::as_str).unwrap_or_default();
+        return command
+            .split(|c: char| c.is_whitespace() || matches!(c, '|' | ';' | '&' | '(' | ')'))
+            .any(|word| NETWORK_COMMANDS.contains(&word));
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn definition(name: &str, trust_requirement: TrustRequirement) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_owned(),
+            description: String::new(),
+            parameters: json!({}),
+            trust_requirement,
+            aliases: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn permanent_delete_is_not_reversible() {
+        let def = definition("file_delete", TrustRequirement::DoubleConfirm);
+        let args = json!({ "path": "/srv/data/report.pdf", "permanent": true });
+        let risk = summarize(&def, &args);
+        assert_eq!(risk.affected_paths, vec!["/srv/data/report.pdf".to_owned()]);
+        assert!(!risk.reversible);
+        assert!(!risk.network_access);
+        assert_eq!(risk.summary, "Permanently deletes /srv/data/report.pdf, not reversible");
+    }
+
+    #[test]
+    fn trashing_is_reversible() {
+        let def = definition("file_delete", TrustRequirement::Confirm);
+        let risk = summarize(&def, &json!({ "path": "/srv/data/report.pdf" }));
+        assert!(risk.reversible);
+        assert_eq!(risk.summary, "Moves /srv/data/report.pdf to the trash, reversible");
+    }
+
+    #[test]
+    fn multiple_paths_share_parent() {
+        let paths = vec!["/srv/a/x.txt".to_owned(), "/srv/a/b/y.txt".to_owned()];
+        assert_eq!(describe_paths(&paths), "2 files under /srv/a");
+    }
+
+    #[test]
+    fn shell_network_detection() {
+        let def = definition("shell_exec", TrustRequirement::DoubleConfirm);
+        let risk = summarize(&def, &json!({ "command": "ls | curl -T - example.com" }));
+        assert!(risk.network_access);
+        let risk = summarize(&def, &json!({ "command": "ls -la" }));
+        assert!(!risk.network_access);
+    }
+
+    #[test]
+    fn shell_session_input() {
+        let def = definition("shell_session", TrustRequirement::DoubleConfirm);
+        let args = json!({ "action": "send", "name": "tests", "input": "pip install -e ." });
+        let risk = summarize(&def, &args);
+        assert!(risk.network_access);
+        assert_eq!(
+            risk.summary,
+            "Types `pip` into shell session \"tests\", uses the network, not reversible"
+        );
+    }
+
+    #[test]
+    fn volume_changes() {
+        let def = definition("volume", TrustRequirement::Confirm);
+        let risk = summarize(&def, &json!({ "mute": "on", "kind": "input" }));
+        assert_eq!(risk.summary, "Mutes the default input, reversible");
+        let risk = summarize(&def, &json!({ "value": "-10%", "device": "Headphones" }));
+        assert_eq!(risk.summary, "Lowers volume of \"Headphones\" by 10%, reversible");
+        let risk = summarize(&def, &json!({ "value": 40 }));
+        assert_eq!(risk.summary, "Sets volume of the default output to 40%, reversible");
+    }
+
+    #[test]
+    fn location_lookup() {
+        let def = definition("location", TrustRequirement::Confirm);
+        let risk = summarize(&def, &json!({}));
+        assert!(risk.network_access);
+        assert_eq!(
+            risk.summary,
+            "Tells the assistant where this computer is, to about a kilometer, uses the \
+             network, reversible"
+        );
+    }
+
+    #[test]
+    fn contact_addition() {
+        let def = definition("contacts_add", TrustRequirement::Confirm);
+        let risk = summarize(&def, &json!({"name": "Alice Liddell", "emails": ["a@example.com"]}));
+        assert!(!risk.network_access);
+        assert_eq!(risk.summary, "Adds \"Alice Liddell\" to the contacts, reversible");
+    }
+
+    #[test]
+    fn home_assistant_service_call() {
+        let def = definition("home_assistant", TrustRequirement::Confirm);
+        let args = json!({
+            "action": "call_service",
+            "domain": "light",
+            "service": "turn_on",
+            "entity_id": "light.kitchen",
+        });
+        let risk = summarize(&def, &args);
+        assert!(risk.network_access);
+        assert_eq!(
+            risk.summary,
+            "Calls Home Assistant service light.turn_on on light.kitchen, uses the network, \
+             reversible"
+        );
+    }
+}
@@ -1,16 +1,33 @@
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError};
+use std::time::Instant;
 
 use aios_common::{
-    ChatMessage, IpcMessage, IpcPayload, MessageContent, Role, ToolResult, TrustLevel,
+    Attachment, AttachmentContent, ChatMessage, IpcMessage, IpcPayload, MessageContent, Role,
+    ToolCall, ToolResult, TrustLevel,
 };
+use aios_voice::stt::{TranscriptionSession, WhisperStt};
+use base64::Engine as _;
 use chrono::Utc;
-use tokio::sync::RwLock;
+use serde_json::json;
+use tokio::sync::{RwLock, watch};
 use uuid::Uuid;
 
-use crate::llm::system_prompt::default_system_prompt;
-use crate::llm::types::LlmRequest;
-use crate::state::{AgentState, Conversation};
+use crate::activity;
+use crate::audit::AuditLogger;
+use crate::crash;
+use crate::formats;
+use crate::llm::types::{LlmRequest, LlmResponse};
+use crate::llm::{LlmProvider, overflow, reasoning};
+use crate::mcp_server;
+use crate::mcp_servers;
+use crate::notifications;
+use crate::state::{AgentState, Conversation, VoiceSession};
+use crate::system_status;
 use crate::tool_executor;
+use crate::tool_filter;
+use crate::tool_slots::ToolSlots;
+use crate::trust;
+use crate::usage::TurnUsage;
 
 /// Default maximum tokens for LLM responses.
 const DEFAULT_MAX_TOKENS: u32 = 4096;
@@ -24,10 +41,14 @@ const DEFAULT_TEMPERATURE: f32 = 0.7;
 const MAX_TOOL_ITERATIONS: u32 = 10;
 
 /// Route an incoming IPC message and optionally produce a response.
+///
+/// `cancel` changes when the client sends a `CancelRequest`; chat turns stop
+/// at the next safe point.
 pub async fn route_message(
     msg: IpcMessage,
-    _client_id: Uuid,
+    client_id: Uuid,
     state: &Arc<RwLock<AgentState>>,
+    cancel: &watch::Receiver<()>,
 ) -> Option<IpcMessage> {
     match msg.payload {
         IpcPayload::Register { client_type } => {
@@ -43,74 +64,282 @@ pub async fn route_message(
         IpcPayload::ChatRequest {
             message,
             conversation_id,
+            message_id,
+            attachments,
         } => {
-            tracing::info!(%conversation_id, "Chat request received");
+            tracing::info!(
+                %conversation_id,
+                attachments = attachments.len(),
+                "Chat request received"
+            );
+            let message_id = message_id.unwrap_or_else(Uuid::new_v4);
+            activity::received(state, conversation_id, message_id).await;
+            let message = inline_attachments(state, conversation_id, message, attachments).await;
+            Some(chat_turn(state, conversation_id, message_id, message, cancel).await)
+        }
 
-            // Store the user message in the conversation.
-            let user_msg = ChatMessage {
-                id: Uuid::new_v4(),
-                role: Role::User,
-                content: MessageContent::Text {
-                    text: message.clone(),
-                },
-                trust_level: TrustLevel::User,
-                timestamp: Utc::now(),
+        IpcPayload::EditMessage {
+            conversation_id,
+            message_id,
+            message,
+        } => {
+            tracing::info!(%conversation_id, %message_id, "Edit-and-resend received");
+            activity::received(state, conversation_id, message_id).await;
+            let found = {
+                let mut state_guard = state.write().await;
+                state_guard
+                    .conversations
+                    .get_mut(&conversation_id)
+                    .is_some_and(|c| c.truncate_from(message_id))
             };
+            if !found {
+                tracing::warn!(%message_id, "Edited message not found; appending instead");
+            }
+            Some(chat_turn(state, conversation_id, message_id, message, cancel).await)
+        }
 
-            {
+        IpcPayload::RegenerateResponse { conversation_id } => {
+            tracing::info!(%conversation_id, "Regenerate requested");
+            let last_user_text = {
                 let mut state_guard = state.write().await;
-                let conversation = state_guard
+                state_guard
                     .conversations
-                    .entry(conversation_id)
-                    .or_insert_with(|| Conversation {
-                        id: conversation_id,
-                        messages: Vec::new(),
-                    });
-                conversation.messages.push(user_msg);
-            }
+                    .get_mut(&conversation_id)
+                    .and_then(Conversation::rewind_to_last_user)
+            };
+            let Some(text) = last_user_text else {
+                return Some(IpcMessage {
+                    id: Uuid::new_v4(),
+                    payload: IpcPayload::Error {
+                        message: "Nothing to regenerate in this conversation".to_owned(),
+                        code: Some("nothing_to_regenerate".to_owned()),
+                    },
+                });
+            };
+            Some(complete_turn(state, conversation_id, &text, cancel).await)
+        }
 
-            // Run the agentic loop: LLM call -> tool execution -> repeat.
-            let assistant_msg = agentic_loop(state, conversation_id, &message).await;
+        IpcPayload::VoiceStart { session_id } => {
+            tracing::info!(%session_id, "Voice session started");
+            let session = VoiceSession {
+                client_id,
+                audio: TranscriptionSession::new(),
+            };
+            state
+                .write()
+                .await
+                .voice_sessions
+                .insert(session_id, session);
+            None
+        }
+
+        IpcPayload::VoiceAudio { session_id, pcm } => {
+            let bytes = match base64::engine::general_purpose::STANDARD.decode(pcm) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::warn!(%session_id, "Invalid voice audio chunk: {e}");
+                    return None;
+                }
+            };
+
+            // Append, and take a snapshot if a partial transcript is due.
+            let (snapshot, stt) = {
+                let mut state_guard = state.write().await;
+                let stt = state_guard.stt.clone();
+                let session = state_guard
+                    .voice_sessions
+                    .get_mut(&session_id)
+                    .filter(|session| session.client_id == client_id)?;
+                session.audio.push_pcm(&bytes);
+                if !session.audio.partial_due() {
+                    return None;
+                }
+                (session.audio.snapshot(), stt)
+            };
 
-            // Store the final assistant message.
-            {
+            // Whisper takes a while; the client's other requests go on
+            // meanwhile, and the transcript is sent when it is ready.
+            let state = Arc::clone(state);
+            tokio::spawn(async move {
+                let text = stt.transcribe(&snapshot).await;
+                if let Some(session) = state.write().await.voice_sessions.get_mut(&session_id) {
+                    session.audio.partial_done();
+                }
+                match text {
+                    Ok(text) => {
+                        let transcript = IpcMessage {
+                            id: Uuid::new_v4(),
+                            payload: IpcPayload::Transcript {
+                                session_id,
+                                text,
+                                is_final: false,
+                            },
+                        };
+                        state.read().await.send_to(client_id, &transcript).await;
+                    }
+                    Err(e) => tracing::warn!(%session_id, "Partial transcription failed: {e:#}"),
+                }
+            });
+            None
+        }
+
+        IpcPayload::VoiceStop { session_id } => {
+            let (session, stt) = {
                 let mut state_guard = state.write().await;
-                if let Some(conversation) = state_guard.conversations.get_mut(&conversation_id) {
-                    conversation.messages.push(assistant_msg.clone());
+                let owned = state_guard
+                    .voice_sessions
+                    .get(&session_id)
+                    .is_some_and(|session| session.client_id == client_id);
+                let session = owned.then(|| state_guard.voice_sessions.remove(&session_id));
+                (session.flatten(), state_guard.stt.clone())
+            };
+            let samples = session
+                .map(|session| session.audio.into_samples())
+                .unwrap_or_default();
+            tracing::info!(%session_id, samples = samples.len(), "Voice session stopped");
+
+            let payload = match stt.transcribe(&samples).await {
+                Ok(text) => IpcPayload::Transcript {
+                    session_id,
+                    text,
+                    is_final: true,
+                },
+                Err(e) => {
+                    tracing::error!(%session_id, "Transcription failed: {e:#}");
+                    IpcPayload::Error {
+                        message: format!("Speech recognition failed: {e}"),
+                        code: Some("stt_failed".to_owned()),
+                    }
                 }
+            };
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload,
+            })
+        }
+
+        IpcPayload::ClearConversation { conversation_id } => {
+            tracing::info!(%conversation_id, "Clearing conversation");
+            let mut state_guard = state.write().await;
+            if let Some(conversation) = state_guard.conversations.get_mut(&conversation_id) {
+                conversation.messages.clear();
+                conversation.exec = Arc::default();
+                conversation.grants = Arc::default();
             }
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::CommandResult {
+                    success: true,
+                    message: "Conversation cleared".to_owned(),
+                },
+            })
+        }
 
+        IpcPayload::SetSystemPrompt {
+            conversation_id,
+            prompt,
+        } => {
+            tracing::info!(%conversation_id, custom = prompt.is_some(), "Setting system prompt");
+            let message = if prompt.is_some() {
+                "System prompt updated for this conversation"
+            } else {
+                "System prompt reset to default"
+            };
+            let mut state_guard = state.write().await;
+            state_guard
+                .conversations
+                .get_or_create(conversation_id)
+                .system_prompt = prompt;
             Some(IpcMessage {
                 id: Uuid::new_v4(),
-                payload: IpcPayload::ChatResponse {
-                    message: assistant_msg,
+                payload: IpcPayload::CommandResult {
+                    success: true,
+                    message: message.to_owned(),
+                },
+            })
+        }
+
+        IpcPayload::SetModel { model } => {
+            tracing::info!(%model, "Model switch requested via IPC");
+            let result = set_model(state, &model).await;
+            if result.is_ok() {
+                activity::provider_changed(state).await;
+            }
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::ConfigReloaded {
+                    success: result.is_ok(),
+                    message: match &result {
+                        Ok(name) => format!("Switched to {model} ({name})"),
+                        Err(e) => format!("Model switch failed: {e}"),
+                    },
+                },
+            })
+        }
+
+        IpcPayload::ListModels => {
+            let guard = state.read().await;
+            let payload = match &guard.llm_provider {
+                Some(provider) => match provider.list_models().await {
+                    Ok(models) => IpcPayload::Models {
+                        provider: provider.name().to_owned(),
+                        current: provider.model().to_owned(),
+                        models,
+                    },
+                    Err(e) => IpcPayload::Error {
+                        message: format!("Cannot list models: {e:#}"),
+                        code: None,
+                    },
+                },
+                None => IpcPayload::Error {
+                    message: "No LLM provider is configured".to_owned(),
+                    code: None,
                 },
+            };
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload,
             })
         }
 
         IpcPayload::ConfirmResponse {
             action_id,
             approved,
+            remember,
             ..
         } => {
-            tracing::info!(%action_id, %approved, "Confirm response received");
-            let mut state_guard = state.write().await;
-            if let Some(sender) = state_guard.pending_confirms.remove(&action_id) {
-                if sender.send(approved).is_err() {
-                    tracing::warn!(
-                        %action_id,
-                        "Confirm response arrived but the waiting task was already gone"
-                    );
-                }
+            tracing::info!(%action_id, %approved, remember, "Confirm response received");
+            tool_executor::answer_confirmation(state, action_id, approved, remember).await;
+            None
+        }
+
+        IpcPayload::SetPaused { paused } => {
+            let rejected = activity::set_paused(state, paused).await;
+            let message = if paused {
+                format!("Tool execution paused; {rejected} pending confirmations rejected")
             } else {
-                tracing::warn!(%action_id, "No pending confirmation found for this action_id");
-            }
+                "Tool execution resumed".to_owned()
+            };
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::CommandResult {
+                    success: true,
+                    message,
+                },
+            })
+        }
+
+        IpcPayload::McpMessage { message } => {
+            mcp_server::relay(state, client_id, message).await;
             None
         }
 
         IpcPayload::ReloadConfig => {
             tracing::info!("Config reload requested via IPC");
             let result = reload_config(state).await;
+            if result.is_ok() {
+                activity::provider_changed(state).await;
+            }
             Some(IpcMessage {
                 id: Uuid::new_v4(),
                 payload: IpcPayload::ConfigReloaded {
@@ -123,6 +352,134 @@ pub async fn route_message(
             })
         }
 
+        IpcPayload::GetConfig { section } => {
+            let value = crate::config::read_section(&section)
+                .and_then(|value| Ok(serde_json::to_value(value)?));
+            let payload = match value {
+                Ok(value) => IpcPayload::ConfigSection { section, value },
+                Err(e) => IpcPayload::Error {
+                    message: format!("{e:#}"),
+                    code: None,
+                },
+            };
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload,
+            })
+        }
+
+        IpcPayload::SetConfig { section, value } => {
+            tracing::info!(%section, "Config change requested via IPC");
+            let result = set_config(state, &section, value).await;
+            if result.is_ok() {
+                activity::provider_changed(state).await;
+            }
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::ConfigReloaded {
+                    success: result.is_ok(),
+                    message: match &result {
+                        Ok(name) => format!("[{section}] saved, provider: {name}"),
+                        Err(e) => format!("{e:#}"),
+                    },
+                },
+            })
+        }
+
+        IpcPayload::RestartAgent => {
+            tracing::info!("Restart requested via IPC");
+            tokio::spawn(restart(Arc::clone(state)));
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::CommandResult {
+                    success: true,
+                    message: "Agent restarting".to_owned(),
+                },
+            })
+        }
+
+        IpcPayload::LogsRequest { after } => {
+            let lines = state.read().await.logs.after(after);
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::LogLines { lines },
+            })
+        }
+
+        IpcPayload::SetVolume { percent } => {
+            tracing::info!(percent, "Setting volume");
+            quick_control(state, system_status::set_volume(percent).await).await
+        }
+        IpcPayload::SetMuted { muted } => {
+            tracing::info!(muted, "Setting mute");
+            quick_control(state, system_status::set_muted(muted).await).await
+        }
+        IpcPayload::SetBrightness { percent } => {
+            tracing::info!(percent, "Setting brightness");
+            quick_control(state, system_status::set_brightness(percent).await).await
+        }
+
+        IpcPayload::MarkNotificationsRead => {
+            notifications::mark_read(state).await;
+            None
+        }
+        IpcPayload::ClearNotifications => {
+            tracing::info!("Clearing notifications");
+            notifications::clear(state).await;
+            None
+        }
+
+        IpcPayload::AuditQuery {
+            filter,
+            range,
+            limit,
+        } => {
+            let result = state
+                .read()
+                .await
+                .audit_logger
+                .query(filter, range, limit)
+                .await;
+            let payload = match result {
+                Ok((entries, truncated)) => IpcPayload::AuditEntries { entries, truncated },
+                Err(e) => {
+                    tracing::warn!("Audit query failed: {e:#}");
+                    IpcPayload::Error {
+                        message: format!("Audit query failed: {e}"),
+                        code: None,
+                    }
+                }
+            };
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload,
+            })
+        }
+
+        IpcPayload::CalendarRequest { date } => {
+            let events =
+                tokio::task::spawn_blocking(move || aios_common::calendar::events_on(date))
+                    .await
+                    .unwrap_or_default();
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::CalendarEvents { date, events },
+            })
+        }
+
+        IpcPayload::AboutRequest => {
+            let guard = state.read().await;
+            let provider = guard.llm_provider.as_ref();
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::AgentAbout {
+                    version: env!("CARGO_PKG_VERSION").to_owned(),
+                    provider: provider.map(|p| p.name().to_owned()),
+                    model: provider.map(|p| p.model().to_owned()),
+                },
+            })
+        }
+
         IpcPayload::Ping => Some(IpcMessage {
             id: Uuid::new_v4(),
             payload: IpcPayload::Pong,
@@ -135,6 +492,219 @@ pub async fn route_message(
     }
 }
 
+/// Finish a dock quick control: on success the new level reaches every dock
+/// through the status broadcast; a failure is reported to the sender.
+async fn quick_control(
+    state: &Arc<RwLock<AgentState>>,
+    result: Result<(), String>,
+) -> Option<IpcMessage> {
+    match result {
+        Ok(()) => {
+            system_status::refresh(state).await;
+            None
+        }
+        Err(message) => {
+            tracing::warn!("Quick control failed: {message}");
+            Some(IpcMessage {
+                id: Uuid::new_v4(),
+                payload: IpcPayload::Error {
+                    message,
+                    code: None,
+                },
+            })
+        }
+    }
+}
+
+// --------------------------------------------------------------------------
+// Chat turns
+// --------------------------------------------------------------------------
+
+/// Store a user message and run the agentic loop to produce a reply.
+async fn chat_turn(
+    state: &Arc<RwLock<AgentState>>,
+    conversation_id: Uuid,
+    message_id: Uuid,
+    message: String,
+    cancel: &watch::Receiver<()>,
+) -> IpcMessage {
+    let user_msg = ChatMessage {
+        id: message_id,
+        role: Role::User,
+        content: MessageContent::Text {
+            text: message.clone(),
+        },
+        trust_level: TrustLevel::User,
+        timestamp: Utc::now(),
+    };
+
+    {
+        let mut state_guard = state.write().await;
+        let conversation = state_guard.conversations.get_or_create(conversation_id);
+        conversation.messages.push(user_msg);
+    }
+
+    complete_turn(state, conversation_id, &message, cancel).await
+}
+
+/// Fold attachments into the user's message text.
+///
+/// Text files are appended as fenced context blocks. None of the providers
+/// accept image input yet, so images are recorded as a placeholder line
+/// telling the model an image was attached. Files referenced by path are
+/// read first; one that cannot be read leaves a note with the reason.
+async fn inline_attachments(
+    state: &Arc<RwLock<AgentState>>,
+    conversation_id: Uuid,
+    mut message: String,
+    attachments: Vec<Attachment>,
+) -> String {
+    for mut attachment in attachments {
+        if let AttachmentContent::Path { path } = &attachment.content {
+            match read_attached_file(state, conversation_id, path).await {
+                Ok(content) => attachment.content = content,
+                Err(error) => {
+                    message.push_str(&format!(
+                        "\n\n[Attached file `{}` could not be read: {error}]",
+                        attachment.name
+                    ));
+                    continue;
+                }
+            }
+        }
+        match &attachment.content {
+            AttachmentContent::Text { text } => {
+                message.push_str(&format!(
+                    "\n\nAttached file `{}`:\n```\n{}\n```",
+                    attachment.name, text
+                ));
+            }
+            AttachmentContent::Image { .. } => {
+                message.push_str(&format!(
+                    "\n\n[Image attached: {} ({}) -- image input is not supported by the current model]",
+                    attachment.name, attachment.media_type
+                ));
+            }
+            // Read above.
+            AttachmentContent::Path { .. } => {}
+        }
+    }
+    message
+}
+
+/// Read a file attached by path with the `file_read` tool, so the read is
+/// audited and resolved against the conversation's working directory like
+/// the model's own reads.
+async fn read_attached_file(
+    state: &Arc<RwLock<AgentState>>,
+    conversation_id: Uuid,
+    path: &str,
+) -> Result<AttachmentContent, String> {
+    let (registry, audit_logger, exec) = {
+        let state_guard = state.read().await;
+        let exec = state_guard
+            .conversations
+            .get(&conversation_id)
+            .map(|conv| Arc::clone(&conv.exec))
+            .unwrap_or_default();
+        (
+            state_guard.tool_registry.clone(),
+            state_guard.audit_logger.clone(),
+            exec,
+        )
+    };
+    let path = exec
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .resolve(path);
+    let tool_call = ToolCall {
+        id: Uuid::new_v4(),
+        name: "file_read".to_owned(),
+        arguments: json!({ "path": path }),
+        trust_level: TrustLevel::User,
+    };
+    tracing::info!(path = %path.display(), "Reading attached file");
+    let mut result = tool_executor::execute_tool_call(
+        &tool_call,
+        &registry,
+        state,
+        &audit_logger,
+        exec,
+        None,
+        None,
+    )
+    .await;
+    if result.is_error {
+        return Err(result.llm_output().into_owned());
+    }
+    match result.attachments.pop() {
+        Some(image) => Ok(image.content),
+        None => Ok(AttachmentContent::Text {
+            text: result.output,
+        }),
+    }
+}
+
+/// Run the agentic loop on the current history, store the final assistant
+/// message, and wrap it as a `ChatResponse`.
+async fn complete_turn(
+    state: &Arc<RwLock<AgentState>>,
+    conversation_id: Uuid,
+    raw_message: &str,
+    cancel: &watch::Receiver<()>,
+) -> IpcMessage {
+    // Run the agentic loop: LLM call -> tool execution -> repeat.
+    let mut usage = TurnUsage::default();
+    let mut thinking = Vec::new();
+    let mut cards = Vec::new();
+    let turn = agentic_loop(
+        state,
+        conversation_id,
+        raw_message,
+        cancel,
+        &mut usage,
+        &mut thinking,
+        &mut cards,
+    );
+    let mut assistant_msg = match crash::catch(turn).await {
+        Ok(message) => message,
+        Err(panic) => {
+            crash::report(state, &panic, "chat turn").await;
+            ChatMessage {
+                id: Uuid::new_v4(),
+                role: Role::Assistant,
+                content: MessageContent::Text {
+                    text: format!("Sorry, I crashed while answering: {}", panic.message),
+                },
+                trust_level: TrustLevel::System,
+                timestamp: Utc::now(),
+            }
+        }
+    };
+    formats::attach(&mut assistant_msg, &cards);
+
+    // Store the final assistant message.
+    let usage = {
+        let mut state_guard = state.write().await;
+        if let Some(conversation) = state_guard.conversations.get_mut(&conversation_id) {
+            conversation.messages.push(assistant_msg.clone());
+        }
+        state_guard
+            .llm_provider
+            .as_ref()
+            .and_then(|provider| usage.report(provider.name(), provider.model()))
+    };
+
+    IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::ChatResponse {
+            message: assistant_msg,
+            usage,
+            reasoning: reasoning::join(thinking.iter().map(String::as_str)),
+        },
+    }
+}
+
 // --------------------------------------------------------------------------
 // Agentic loop
 // --------------------------------------------------------------------------
@@ -142,10 +712,20 @@ pub async fn route_message(
 /// Run the agentic loop: call the LLM, execute any requested tools, feed the
 /// results back, and repeat until the LLM produces a text response or the
 /// iteration limit is reached.
+///
+/// A cancel aborts a pending LLM call or stops before the next one; tool
+/// calls already started run to completion so the history stays consistent.
+/// The thinking of reasoning models is collected in `thinking`, for the
+/// chat to show on request; it stays out of the history. Results with a
+/// template in [`formats`] are rendered into `cards`.
 async fn agentic_loop(
     state: &Arc<RwLock<AgentState>>,
     conversation_id: Uuid,
     raw_message: &str,
+    cancel: &watch::Receiver<()>,
+    usage: &mut TurnUsage,
+    thinking: &mut Vec<String>,
+    cards: &mut Vec<String>,
 ) -> ChatMessage {
     // Check if there is an LLM provider at all.
     let has_provider = {
@@ -158,11 +738,26 @@ async fn agentic_loop(
         return echo_response(raw_message);
     }
 
+    // Only cancels sent from now on apply to this turn.
+    let mut cancel = cancel.clone();
+    cancel.mark_unchanged();
+
     for iteration in 0..MAX_TOOL_ITERATIONS {
-        let llm_response = call_llm(state, conversation_id).await;
+        if cancel.has_changed().unwrap_or(false) {
+            return cancelled_response();
+        }
+        let llm_response = tokio::select! {
+            response = call_llm(state, conversation_id, usage) => response,
+            _ = cancel.changed() => return cancelled_response(),
+        };
 
-        let response_msg = match llm_response {
-            Ok(resp) => resp,
+        let llm_error = llm_response.as_ref().err().map(|e| format!("{e}"));
+        activity::record_llm_result(state, llm_error).await;
+        let (mut response_msg, plan) = match llm_response {
+            Ok(response) => {
+                thinking.extend(response.reasoning);
+                (response.message, response.plan)
+            }
             Err(e) => {
                 tracing::error!("LLM request failed: {e:#}");
                 return ChatMessage {
@@ -182,6 +777,22 @@ async fn agentic_loop(
             return response_msg;
         }
 
+        // The calls are only as trusted as the tool output the LLM has read.
+        let trust_level = {
+            let state_guard = state.read().await;
+            state_guard
+                .conversations
+                .get(&conversation_id)
+                .map_or(TrustLevel::User, |conv| {
+                    trust::conversation_trust(&conv.messages)
+                })
+        };
+        if let MessageContent::ToolUse { tool_calls } = &mut response_msg.content {
+            for tc in tool_calls {
+                tc.trust_level = trust_level;
+            }
+        }
+
         // The LLM returned tool calls -- execute them.
         let tool_calls = match &response_msg.content {
             MessageContent::ToolUse { tool_calls } => tool_calls.clone(),
@@ -194,37 +805,74 @@ async fn agentic_loop(
             "LLM requested tool calls"
         );
 
-        // Store the assistant tool-use message in the conversation.
+        // Store the plan, if shown, and the assistant tool-use message in
+        // the conversation.
+        let show_plans = state.read().await.show_plans;
+        let plan = plan.filter(|_| show_plans).map(|text| ChatMessage {
+            id: Uuid::new_v4(),
+            role: Role::Assistant,
+            content: MessageContent::Plan { text },
+            trust_level: TrustLevel::System,
+            timestamp: Utc::now(),
+        });
+        if let Some(plan) = &plan {
+            activity::plan(state, conversation_id, plan).await;
+        }
         {
             let mut state_guard = state.write().await;
             if let Some(conv) = state_guard.conversations.get_mut(&conversation_id) {
+                conv.messages.extend(plan);
                 conv.messages.push(response_msg);
             }
         }
 
         // Execute each tool call and collect results.
         let mut results: Vec<ToolResult> = Vec::with_capacity(tool_calls.len());
+        let mut results_trust = TrustLevel::System;
         for tc in &tool_calls {
-            // We need to read registry and audit_logger from state for each call.
-            // To avoid holding the lock across an async tool execution, we clone
-            // the registry reference pattern -- but ToolRegistry is not Clone.
-            // Instead, we pass the full state Arc and let execute_tool_call
-            // acquire the lock internally.
-            let result = {
+            activity::tool_started(state, tc).await;
+            // The registry and logger are copied out of the lock: a tool that
+            // needs confirmation takes the write lock while it waits.
+            let (registry, audit_logger, exec, grants) = {
                 let state_guard = state.read().await;
-                let registry = &state_guard.tool_registry;
-                let audit_logger = &state_guard.audit_logger;
-                tool_executor::execute_tool_call(tc, registry, state, audit_logger).await
+                let conversation = state_guard.conversations.get(&conversation_id);
+                let exec = conversation
+                    .map(|conv| Arc::clone(&conv.exec))
+                    .unwrap_or_default();
+                let grants = conversation
+                    .map(|conv| Arc::clone(&conv.grants))
+                    .unwrap_or_default();
+                (
+                    state_guard.tool_registry.clone(),
+                    state_guard.audit_logger.clone(),
+                    exec,
+                    grants,
+                )
             };
+            results_trust = results_trust.least_trusted(registry.output_trust(&tc.name));
+            let progress = activity::progress_sender(state, conversation_id, tc);
+            let result = tool_executor::execute_tool_call(
+                tc,
+                &registry,
+                state,
+                &audit_logger,
+                exec,
+                Some(grants),
+                Some(progress),
+            )
+            .await;
+            activity::tool_finished(state, tc, &result).await;
+            cards.extend(formats::render(&tc.name, &result));
             results.push(result);
         }
 
-        // Build a tool-result message and push it into the conversation.
+        // Build a tool-result message, as trusted as its least trusted tool,
+        // and push it into the conversation.
         let tool_result_msg = ChatMessage {
             id: Uuid::new_v4(),
             role: Role::Tool,
             content: MessageContent::ToolResult { results },
-            trust_level: TrustLevel::System,
+            trust_level: results_trust,
             timestamp: Utc::now(),
         };
 
@@ -240,75 +888,145 @@ async fn agentic_loop(
 
     // Iteration limit reached.  Force a text response.
     tracing::warn!("Agentic loop reached {MAX_TOOL_ITERATIONS} iterations, forcing text response");
-    force_text_response(state, conversation_id).await
+    force_text_response(state, conversation_id, usage, thinking).await
+}
+
+/// Reply stored and returned when the user stops a turn.
+fn cancelled_response() -> ChatMessage {
+    tracing::info!("Chat turn cancelled");
+    ChatMessage {
+        id: Uuid::new_v4(),
+        role: Role::Assistant,
+        content: MessageContent::Text {
+            text: "(Reply cancelled.)".to_owned(),
+        },
+        trust_level: TrustLevel::System,
+        timestamp: Utc::now(),
+    }
 }
 
 /// Call the LLM with the current conversation history and tool definitions.
+#[tracing::instrument(name = "llm", skip_all)]
 async fn call_llm(
     state: &Arc<RwLock<AgentState>>,
     conversation_id: Uuid,
-) -> anyhow::Result<ChatMessage> {
-    let (history, tool_defs) = {
+    usage: &mut TurnUsage,
+) -> anyhow::Result<LlmResponse> {
+    let (history, system_prompt, tool_defs) = {
         let state_guard = state.read().await;
-        let history = state_guard
-            .conversations
-            .get(&conversation_id)
-            .map(|c| c.messages.clone())
-            .unwrap_or_default();
-        let tool_defs = state_guard.tool_registry.definitions();
-        (history, tool_defs)
+        let conversation = state_guard.conversations.get(&conversation_id);
+        let history = conversation.map(|c| c.messages.clone()).unwrap_or_default();
+        // Chosen from the user's own words, before untrusted text is wrapped.
+        let tool_defs = tool_filter::select(
+            state_guard.tool_registry.definitions(),
+            &history,
+            &state_guard.tool_selection,
+        );
+        let system_prompt = conversation
+            .and_then(|c| c.system_prompt.clone())
+            .or_else(|| state_guard.system_prompt.clone())
+            .unwrap_or_else(|| state_guard.built_prompt(&tool_defs));
+        (history, system_prompt, tool_defs)
     };
 
     let llm_request = LlmRequest {
-        messages: history,
+        messages: Vec::new(),
         tools: tool_defs,
-        system_prompt: default_system_prompt(),
+        system_prompt,
         max_tokens: DEFAULT_MAX_TOKENS,
         temperature: DEFAULT_TEMPERATURE,
     };
 
-    let state_guard = state.read().await;
-    let provider = state_guard
-        .llm_provider
-        .as_ref()
-        .expect("LLM provider must exist when agentic_loop runs");
-    let response = provider.complete(&llm_request).await?;
-    Ok(response.message)
+    let (provider, audit) =
+        llm_handles(&*state.read().await).expect("LLM provider must exist when agentic_loop runs");
+    let response = complete(provider.as_ref(), audit.as_ref(), llm_request, &history).await?;
+    usage.record(response.usage);
+    Ok(response)
+}
+
+/// The provider, and the audit log if LLM calls are audited, taken out of
+/// the state so requests are sent without holding its lock.
+fn llm_handles(state: &AgentState) -> Option<(Arc<dyn LlmProvider>, Option<AuditLogger>)> {
+    let provider = Arc::clone(state.llm_provider.as_ref()?);
+    let audit = state.audit_llm.then(|| state.audit_logger.clone());
+    Some((provider, audit))
+}
+
+/// Send `request` with `history` as its messages. While the provider says
+/// they exceed the model's context, try again with less of them.
+async fn complete(
+    provider: &dyn LlmProvider,
+    audit: Option<&AuditLogger>,
+    mut request: LlmRequest,
+    history: &[ChatMessage],
+) -> anyhow::Result<LlmResponse> {
+    request.messages = history.to_vec();
+    trust::wrap_untrusted(&mut request.messages);
+    let mut attempt = 0;
+    loop {
+        let started = Instant::now();
+        let response = provider.complete(&request).await;
+        if let Some(audit) = audit {
+            audit
+                .log_llm(provider, &request, &response, started.elapsed())
+                .await;
+        }
+        let error = match response {
+            Err(e) if overflow::is_context_error(&e) => e,
+            response => return response,
+        };
+        let Some(mut messages) = overflow::shrink(history, attempt) else {
+            return Err(error.context(
+                "The conversation is too long for the model, even shortened; start a new one \
+                 or choose a model with a larger context",
+            ));
+        };
+        tracing::warn!("Request exceeds the model's context, retrying shorter: {error:#}");
+        trust::wrap_untrusted(&mut messages);
+        request.messages = messages;
+        attempt += 1;
+    }
 }
 
 /// Ask the LLM one more time but without tools, forcing a text answer.
 async fn force_text_response(
     state: &Arc<RwLock<AgentState>>,
     conversation_id: Uuid,
+    usage: &mut TurnUsage,
+    thinking: &mut Vec<String>,
 ) -> ChatMessage {
-    let history = {
+    let (history, system_prompt) = {
         let state_guard = state.read().await;
-        state_guard
-            .conversations
-            .get(&conversation_id)
-            .map(|c| c.messages.clone())
-            .unwrap_or_default()
+        let conversation = state_guard.conversations.get(&conversation_id);
+        let history = conversation.map(|c| c.messages.clone()).unwrap_or_default();
+        (
+            history,
+            conversation
+                .and_then(|c| c.system_prompt.clone())
+                .or_else(|| state_guard.system_prompt.clone())
+                .unwrap_or_else(|| state_guard.built_prompt(&[])),
+        )
     };
 
     let llm_request = LlmRequest {
-        messages: history,
+        messages: Vec::new(),
         tools: Vec::new(), // No tools -> LLM must respond with text.
-        system_prompt: default_system_prompt(),
+        system_prompt,
         max_tokens: DEFAULT_MAX_TOKENS,
         temperature: DEFAULT_TEMPERATURE,
     };
 
-    let result = {
-        let state_guard = state.read().await;
-        if let Some(provider) = &state_guard.llm_provider {
-            provider.complete(&llm_request).await
-        } else {
-            return echo_response("(iteration limit reached)");
-        }
+    let Some((provider, audit)) = llm_handles(&*state.read().await) else {
+        return echo_response("(iteration limit reached)");
     };
+    let result = complete(provider.as_ref(), audit.as_ref(), llm_request, &history).await;
 
     match result {
-        Ok(response) => response.message,
+        Ok(response) => {
+            usage.record(response.usage);
+            thinking.extend(response.reasoning);
+            response.message
+        }
         Err(e) => {
             tracing::error!("Force-text LLM call failed: {e:#}");
             ChatMessage {
@@ -355,7 +1073,73 @@ async fn reload_config(state: &Arc<RwLock<AgentState>>) -> anyhow::Result<String
 
     {
         let mut state_guard = state.write().await;
-        state_guard.llm_provider = new_provider;
+        state_guard.llm_provider = new_provider.map(Arc::from);
+        state_guard.stt = WhisperStt::new(&config.voice);
+        state_guard.tool_registry.set_policies(config.tools);
+        state_guard.tool_selection = config.tool_selection;
+        state_guard.conversations.set_limits(config.conversations);
+        if state_guard.tool_slots.limit() != config.agent.max_parallel_tools.max(1) {
+            state_guard.tool_slots = Arc::new(ToolSlots::new(config.agent.max_parallel_tools));
+        }
+        state_guard.system_prompt = config.agent.system_prompt;
+        state_guard.prompt = config.prompt;
+        state_guard.audit_llm = config.agent.audit_llm;
+        state_guard.show_plans = config.agent.show_plans;
+        state_guard.confirm_command = config.agent.confirm_command;
+        state_guard
+            .rate_limiter
+            .set_limit(config.agent.max_destructive_per_minute);
+        // Tools toggled in the permissions tab.
+        state_guard.publish_tools();
+    }
+    mcp_servers::connect(state, config.mcp_servers).await;
+
+    Ok(provider_name)
+}
+
+/// Validate and write `[section]`, then apply the new config.
+async fn set_config(
+    state: &Arc<RwLock<AgentState>>,
+    section: &str,
+    value: serde_json::Value,
+) -> anyhow::Result<String> {
+    let value = toml::Value::try_from(value)
+        .map_err(|e| anyhow::anyhow!("[{section}] cannot be written as TOML: {e}"))?;
+    crate::config::write_section(section, value)?;
+    reload_config(state).await
+}
+
+/// Replace the process with a fresh copy of the agent binary, which rereads
+/// the config and rebinds the socket. Waits briefly so the reply to the
+/// requester goes out first, and writes out queued audit entries.
+async fn restart(state: Arc<RwLock<AgentState>>) {
+    use std::os::unix::process::CommandExt as _;
+
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    state.read().await.audit_logger.flush().await;
+    let error = match std::env::current_exe() {
+        Ok(exe) => std::process::Command::new(exe)
+            .args(std::env::args_os().skip(1))
+            .exec(),
+        Err(e) => e,
+    };
+    tracing::error!("Restart failed: {error}");
+}
+
+/// Recreate the LLM provider from the on-disk config with `model` swapped in.
+///
+/// The change lasts until the next config reload; the config file itself is
+/// left untouched.
+async fn set_model(state: &Arc<RwLock<AgentState>>, model: &str) -> anyhow::Result<String> {
+    let mut config = crate::config::load_config()?;
+    config.provider.model = model.to_owned();
+
+    let provider = crate::llm::create_provider(&config.provider)?;
+    let provider_name = provider.name().to_owned();
+
+    {
+        let mut state_guard = state.write().await;
+        state_guard.llm_provider = Some(provider.into());
     }
 
     Ok(provider_name)
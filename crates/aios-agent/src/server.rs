@@ -1,10 +1,14 @@
 use std::sync::Arc;
 
-use aios_common::{AiosError, IpcMessage, IpcPayload, IpcServer};
-use tokio::sync::{Mutex, RwLock};
+use aios_common::{AiosError, ClientType, IpcMessage, IpcPayload, IpcServer, telemetry};
+use tokio::sync::{Mutex, RwLock, mpsc, watch};
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::activity;
+use crate::crash;
 use crate::router;
+use crate::system_status;
 use crate::state::{AgentState, ConnectedClient};
 
 /// Run the IPC server loop: accept connections and spawn per-client handlers.
@@ -75,38 +79,101 @@ async fn handle_client(
                 payload: IpcPayload::RegisterAck { success: true },
             };
             client.writer.lock().await.send(&ack).await?;
+            if client_type == ClientType::Dock
+                && let Some(status) = state_guard.system_status.clone()
+            {
+                let status = system_status::status_message(status);
+                client.writer.lock().await.send(&status).await?;
+            }
+            if client_type == ClientType::Dock {
+                let health = activity::health_message(activity::health(&state_guard));
+                client.writer.lock().await.send(&health).await?;
+                let paused = activity::paused_message(state_guard.paused);
+                client.writer.lock().await.send(&paused).await?;
+                let notifications = state_guard.notifications.message();
+                client.writer.lock().await.send(&notifications).await?;
+            }
         }
     }
 
-    // Main message loop.
-    loop {
-        match reader.recv().await {
-            Ok(msg) => {
-                if let Some(response) = router::route_message(msg, client_id, &state).await {
-                    let state_guard = state.read().await;
-                    if let Some(client) = state_guard.clients.get(&client_id)
-                        && let Err(e) = client.writer.lock().await.send(&response).await
-                    {
-                        tracing::error!(%client_id, "Failed to send response: {e}");
+    // Requests are read on their own task so that a `CancelRequest` reaches
+    // us while a chat turn is still running; everything else is routed in
+    // order below.
+    let (cancel_tx, cancel_rx) = watch::channel(());
+    let (request_tx, mut requests) = mpsc::unbounded_channel();
+    let read_task = tokio::spawn(async move {
+        loop {
+            match reader.recv().await {
+                Ok(IpcMessage {
+                    payload: IpcPayload::CancelRequest { conversation_id },
+                    ..
+                }) => {
+                    tracing::info!(%client_id, %conversation_id, "Cancel requested");
+                    cancel_tx.send_replace(());
+                }
+                Ok(msg) => {
+                    if request_tx.send(msg).is_err() {
                         break;
                     }
                 }
+                Err(AiosError::ConnectionClosed) => {
+                    tracing::info!(%client_id, "Client disconnected");
+                    break;
+                }
+                Err(e) => {
+                    tracing::error!(%client_id, "Read error: {e}");
+                    break;
+                }
             }
-            Err(AiosError::ConnectionClosed) => {
-                tracing::info!(%client_id, "Client disconnected");
-                break;
+        }
+    });
+
+    // Main message loop.
+    while let Some(msg) = requests.recv().await {
+        // Everything done for this message, down to the tools and their
+        // confirmations, is logged and sent under its id.
+        let request_id = msg.id;
+        let handled = async {
+            // A panic answers this request with an error rather than ending
+            // the client's connection.
+            match crash::catch(router::route_message(msg, client_id, &state, &cancel_rx)).await {
+                Ok(response) => response,
+                Err(panic) => {
+                    crash::report(&state, &panic, "request").await;
+                    Some(IpcMessage {
+                        id: request_id,
+                        payload: IpcPayload::Error {
+                            message: format!("The agent crashed handling this: {}", panic.message),
+                            code: Some("crashed".to_owned()),
+                        },
+                    })
+                }
             }
-            Err(e) => {
-                tracing::error!(%client_id, "Read error: {e}");
+        };
+        let routed = telemetry::scope(request_id, handled)
+            .instrument(telemetry::request_span(request_id))
+            .await;
+        if let Some(mut response) = routed {
+            response.id = request_id;
+            let state_guard = state.read().await;
+            if let Some(client) = state_guard.clients.get(&client_id)
+                && let Err(e) = client.writer.lock().await.send(&response).await
+            {
+                tracing::error!(%client_id, "Failed to send response: {e}");
                 break;
             }
         }
     }
+    read_task.abort();
 
     // Cleanup: remove client from shared state.
     {
         let mut state_guard = state.write().await;
         state_guard.clients.remove(&client_id);
+        state_guard.mcp_sessions.remove(&client_id);
+        state_guard
+            .voice_sessions
+            .retain(|_, session| session.client_id != client_id);
     }
 
     Ok(())
@@ -1,27 +1,121 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::Instant;
 
+use aios_common::geoclue::Location;
 use aios_common::ipc::IpcWriter;
-use aios_common::{ChatMessage, ClientType};
+use aios_common::locale;
+use aios_common::{
+    AgentHealth, ChatMessage, ClientType, IpcMessage, IpcPayload, McpServerConfig, MessageContent,
+    PromptConfig, Role, SystemStatus, ToolDefinition, ToolSelectionConfig,
+};
+use aios_mcp::executor::ExecContext;
 use aios_mcp::registry::ToolRegistry;
-use tokio::sync::{oneshot, Mutex};
+use aios_voice::stt::{TranscriptionSession, WhisperStt};
+use chrono::Local;
+use tokio::sync::{oneshot, watch, Mutex};
 use uuid::Uuid;
 
 use crate::audit::AuditLogger;
+use crate::conversations::{Conversations, conversations_dir};
 use crate::llm::LlmProvider;
+use crate::llm::system_prompt::{OsFacts, PromptBuilder};
+use crate::logs::LogBuffer;
+use crate::mcp_server::IpcSession;
+use crate::notifications::NotificationLog;
+use crate::tool_slots::ToolSlots;
 
 /// A registered client with its IPC writer half.
 pub struct ConnectedClient {
-    #[allow(dead_code)]
     pub client_type: ClientType,
     pub writer: Mutex<IpcWriter>,
 }
 
+/// A tool call waiting for the user's decision.
+pub struct PendingConfirm {
+    /// The `ConfirmRequest` sent to the Confirm client, kept for the
+    /// gateway's confirmation list.
+    pub request: IpcPayload,
+    /// Resolves the waiting `execute_tool_call` future.
+    pub reply: oneshot::Sender<ConfirmAnswer>,
+}
+
+/// The user's answer to a confirmation request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmAnswer {
+    pub approved: bool,
+    /// Also approve later calls of the tool in the same conversation.
+    pub remember: bool,
+}
+
+impl ConfirmAnswer {
+    pub const REJECTED: Self = Self {
+        approved: false,
+        remember: false,
+    };
+}
+
+/// Tools the user approved for the rest of one conversation, by name.
+pub type Grants = Arc<std::sync::Mutex<HashSet<String>>>;
+
 /// A conversation with accumulated message history.
 pub struct Conversation {
-    #[allow(dead_code)]
     pub id: Uuid,
     pub messages: Vec<ChatMessage>,
+    /// Per-conversation system prompt override set via `/system`.
+    pub system_prompt: Option<String>,
+    /// Working directory and environment of the conversation's tool calls.
+    pub exec: Arc<std::sync::Mutex<ExecContext>>,
+    /// Tools that run without confirmation for the rest of the
+    /// conversation; gone with it.
+    pub grants: Grants,
+}
+
+impl Conversation {
+    /// Create an empty conversation.
+    pub fn new(id: Uuid) -> Self {
+        Self {
+            id,
+            messages: Vec::new(),
+            system_prompt: None,
+            exec: Arc::default(),
+            grants: Arc::default(),
+        }
+    }
+}
+
+impl Conversation {
+    /// Remove the message with `message_id` and everything after it.
+    ///
+    /// Returns `false` (leaving history untouched) if no such message exists.
+    pub fn truncate_from(&mut self, message_id: Uuid) -> bool {
+        match self.messages.iter().position(|m| m.id == message_id) {
+            Some(index) => {
+                self.messages.truncate(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop every message after the last user message and return that
+    /// message's text, or `None` if the conversation has no user text.
+    pub fn rewind_to_last_user(&mut self) -> Option<String> {
+        let index = self.messages.iter().rposition(|m| {
+            m.role == Role::User && matches!(m.content, MessageContent::Text { .. })
+        })?;
+        self.messages.truncate(index + 1);
+        match &self.messages[index].content {
+            MessageContent::Text { text } => Some(text.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// A voice input session and the client recording it.
+pub struct VoiceSession {
+    pub client_id: Uuid,
+    pub audio: TranscriptionSession,
 }
 
 /// Sliding-window rate limiter for destructive tool actions.
@@ -75,25 +169,82 @@ impl RateLimiter {
         self.window.push_back(now);
         true
     }
+
+    /// Change the per-minute cap, keeping the actions already recorded.
+    pub fn set_limit(&mut self, max_per_minute: u32) {
+        self.max_per_minute = max_per_minute;
+    }
 }
 
 /// Central mutable state of the agent process.
 pub struct AgentState {
     pub clients: HashMap<Uuid, ConnectedClient>,
-    pub conversations: HashMap<Uuid, Conversation>,
+    pub conversations: Conversations,
     /// The active LLM provider. `None` when no valid API key is configured,
-    /// in which case the agent falls back to echo mode.
-    pub llm_provider: Option<Box<dyn LlmProvider>>,
+    /// in which case the agent falls back to echo mode. Shared so requests
+    /// can be sent without holding the state lock.
+    pub llm_provider: Option<Arc<dyn LlmProvider>>,
+    /// Error of the last failed LLM request; cleared by the next success or
+    /// a provider change.
+    pub llm_error: Option<String>,
+    /// Health last broadcast to docks, to only send changes.
+    pub published_health: Option<AgentHealth>,
     /// Registry of all available MCP tools.
     pub tool_registry: ToolRegistry,
-    /// Pending confirmation requests awaiting a `ConfirmResponse`.
-    /// Maps `action_id` to a one-shot sender that resolves the waiting
-    /// `execute_tool_call` future.
-    pub pending_confirms: HashMap<Uuid, oneshot::Sender<bool>>,
+    /// Which of the tools each LLM request carries.
+    pub tool_selection: ToolSelectionConfig,
+    /// Slots for tool calls running at once; replaced when the limit
+    /// changes, letting calls already running finish.
+    pub tool_slots: Arc<ToolSlots>,
+    /// Names of the tools on offer as last published by
+    /// [`AgentState::publish_tools`]; MCP sessions watch it to tell their
+    /// clients.
+    pub offered_tools: watch::Sender<Vec<String>>,
+    /// External MCP servers last connected, from `[mcp_servers]`.
+    pub mcp_servers: BTreeMap<String, McpServerConfig>,
+    /// Pending confirmation requests awaiting a `ConfirmResponse`, keyed
+    /// by `action_id`.
+    pub pending_confirms: HashMap<Uuid, PendingConfirm>,
+    /// The HTTP gateway is running, so confirmations can be answered there
+    /// when no Confirm client is connected.
+    pub remote_confirms: bool,
+    /// Starts the Confirm client when none is connected (`confirm_command`
+    /// in `[agent]`); empty never starts one.
+    pub confirm_command: Vec<String>,
+    /// Held while a Confirm client is being started.
+    pub confirm_launch: Arc<Mutex<()>>,
+    /// Tool execution is paused by the user; every tool call is refused.
+    pub paused: bool,
     /// Rate limiter for destructive tool actions.
     pub rate_limiter: RateLimiter,
     /// Audit logger shared across all tool executions.
     pub audit_logger: AuditLogger,
+    /// Also audit LLM requests (`audit_llm` in `[agent]`).
+    pub audit_llm: bool,
+    /// Ask for and show plans before tool calls (`show_plans` in
+    /// `[agent]`).
+    pub show_plans: bool,
+    /// Speech-to-text backend for voice input.
+    pub stt: WhisperStt,
+    /// Active voice input sessions keyed by `session_id`.
+    pub voice_sessions: HashMap<Uuid, VoiceSession>,
+    /// MCP sessions of clients connected over IPC, keyed by client id.
+    pub mcp_sessions: HashMap<Uuid, IpcSession>,
+    /// Latest system status; sent to docks as they register.
+    pub system_status: Option<SystemStatus>,
+    /// Coarse location for the prompt; only kept with `[location]
+    /// in_prompt` on.
+    pub location: Option<Location>,
+    /// Notification center contents; sent to docks as they register.
+    pub notifications: NotificationLog,
+    /// System prompt from `[agent]`; the built-in one when unset.
+    pub system_prompt: Option<String>,
+    /// Sections of the built-in system prompt.
+    pub prompt: PromptConfig,
+    /// This machine, for the built-in system prompt.
+    pub os_facts: OsFacts,
+    /// Recent log output, served to Settings.
+    pub logs: LogBuffer,
 }
 
 impl AgentState {
@@ -101,12 +252,34 @@ impl AgentState {
     pub fn new(audit_logger: AuditLogger, max_destructive_per_minute: u32) -> Self {
         Self {
             clients: HashMap::new(),
-            conversations: HashMap::new(),
+            conversations: Conversations::new(conversations_dir()),
+            tool_slots: Arc::default(),
+            prompt: PromptConfig::default(),
+            os_facts: OsFacts::gather(),
             llm_provider: None,
+            llm_error: None,
+            published_health: None,
             tool_registry: ToolRegistry::with_defaults(),
+            tool_selection: ToolSelectionConfig::default(),
+            offered_tools: watch::Sender::new(Vec::new()),
+            mcp_servers: BTreeMap::new(),
             pending_confirms: HashMap::new(),
+            remote_confirms: false,
+            confirm_command: Vec::new(),
+            confirm_launch: Arc::default(),
+            paused: false,
             rate_limiter: RateLimiter::new(max_destructive_per_minute),
             audit_logger,
+            audit_llm: false,
+            show_plans: false,
+            stt: WhisperStt::new(&aios_common::VoiceConfig::default()),
+            voice_sessions: HashMap::new(),
+            mcp_sessions: HashMap::new(),
+            system_status: None,
+            location: None,
+            notifications: NotificationLog::default(),
+            system_prompt: None,
+            logs: LogBuffer::default(),
         }
     }
 
@@ -118,19 +291,95 @@ impl AgentState {
     ) -> Self {
         Self {
             clients: HashMap::new(),
-            conversations: HashMap::new(),
-            llm_provider: Some(provider),
+            conversations: Conversations::new(conversations_dir()),
+            tool_slots: Arc::default(),
+            prompt: PromptConfig::default(),
+            os_facts: OsFacts::gather(),
+            llm_provider: Some(provider.into()),
+            llm_error: None,
+            published_health: None,
             tool_registry: ToolRegistry::with_defaults(),
+            tool_selection: ToolSelectionConfig::default(),
+            offered_tools: watch::Sender::new(Vec::new()),
+            mcp_servers: BTreeMap::new(),
             pending_confirms: HashMap::new(),
+            remote_confirms: false,
+            confirm_command: Vec::new(),
+            confirm_launch: Arc::default(),
+            paused: false,
             rate_limiter: RateLimiter::new(max_destructive_per_minute),
             audit_logger,
+            audit_llm: false,
+            show_plans: false,
+            stt: WhisperStt::new(&aios_common::VoiceConfig::default()),
+            voice_sessions: HashMap::new(),
+            mcp_sessions: HashMap::new(),
+            system_status: None,
+            location: None,
+            notifications: NotificationLog::default(),
+            system_prompt: None,
+            logs: LogBuffer::default(),
         }
     }
 
+    /// The built-in system prompt for a request offering `tools`.
+    pub fn built_prompt(&self, tools: &[ToolDefinition]) -> String {
+        PromptBuilder::new(&self.prompt)
+            .os(&self.os_facts)
+            .tools(tools)
+            .plans(self.show_plans)
+            .time(Local::now().fixed_offset())
+            .timezone(locale::timezone())
+            .locale(locale::locale())
+            .location(self.location.as_ref())
+            .build()
+    }
+
     /// Find the first connected client matching a given type.
     pub fn find_client(&self, client_type: ClientType) -> Option<&ConnectedClient> {
         self.clients.values().find(|c| c.client_type == client_type)
     }
+
+    /// Tell MCP sessions when the tools on offer changed since the last
+    /// call: after the registry or the tool policies change.
+    pub fn publish_tools(&self) {
+        let tools = self.tool_registry.enabled_names();
+        let changed = self.offered_tools.send_if_modified(|offered| {
+            if *offered == tools {
+                return false;
+            }
+            offered.clone_from(&tools);
+            true
+        });
+        if !changed {
+            return;
+        }
+        tracing::info!(tools = tools.len(), "Tools on offer changed");
+    }
+
+    /// Send `msg` to the client `client_id`, if it is still connected.
+    /// Failures are logged, as for [`Self::broadcast`].
+    pub async fn send_to(&self, client_id: Uuid, msg: &IpcMessage) {
+        let Some(client) = self.clients.get(&client_id) else {
+            return;
+        };
+        if let Err(e) = client.writer.lock().await.send(msg).await {
+            tracing::warn!(%client_id, "Send failed: {e}");
+        }
+    }
+
+    /// Send `msg` to every connected client of `client_type`. Failures are
+    /// logged; the client's own handler notices the broken connection.
+    pub async fn broadcast(&self, client_type: ClientType, msg: &IpcMessage) {
+        for (client_id, client) in &self.clients {
+            if client.client_type != client_type {
+                continue;
+            }
+            if let Err(e) = client.writer.lock().await.send(msg).await {
+                tracing::warn!(%client_id, "Broadcast failed: {e}");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -147,9 +396,52 @@ mod tests {
         assert!(!rl.check_and_record());
     }
 
+    #[test]
+    fn rate_limiter_set_limit_keeps_window() {
+        let mut rl = RateLimiter::new(2);
+        assert!(rl.check_and_record());
+        rl.set_limit(1);
+        assert!(!rl.check_and_record());
+        rl.set_limit(3);
+        assert!(rl.check_and_record());
+    }
+
     #[test]
     fn rate_limiter_zero_limit_rejects_all() {
         let mut rl = RateLimiter::new(0);
         assert!(!rl.check_and_record());
     }
+
+    fn text_message(role: Role, text: &str) -> ChatMessage {
+        ChatMessage {
+            id: Uuid::new_v4(),
+            role,
+            content: MessageContent::Text {
+                text: text.to_owned(),
+            },
+            trust_level: aios_common::TrustLevel::User,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn conversation_truncate_and_rewind() {
+        let first = text_message(Role::User, "first");
+        let edited = text_message(Role::User, "second");
+        let edited_id = edited.id;
+        let mut conv = Conversation::new(Uuid::new_v4());
+        conv.messages = vec![
+            first,
+            text_message(Role::Assistant, "reply"),
+            edited,
+            text_message(Role::Assistant, "reply 2"),
+        ];
+
+        assert_eq!(conv.rewind_to_last_user().as_deref(), Some("second"));
+        assert_eq!(conv.messages.len(), 3);
+
+        assert!(conv.truncate_from(edited_id));
+        assert_eq!(conv.messages.len(), 2);
+        assert!(!conv.truncate_from(Uuid::new_v4()));
+    }
 }
@@ -0,0 +1,275 @@
+//! System status for the dock tray.
+//!
+//! The agent polls network, volume, battery, brightness and, with `[ui]
+//! gpu_meter` on, the GPU and pushes a `SystemStatus` to dock clients when
+//! something changes, so the dock itself never has to spawn processes or
+//! touch sysfs. Power supply changes reported by `upower --monitor` trigger
+//! an immediate refresh, and the dock's quick controls are applied here as
+//! well.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use aios_common::{gpu, wpctl};
+use aios_common::{
+    BatteryStatus, ChargeState, ClientType, GpuInfo, IpcMessage, IpcPayload, NetworkStatus,
+    SystemStatus, UiConfig, VolumeStatus,
+};
+use tokio::io::AsyncBufReadExt;
+use tokio::sync::{RwLock, mpsc};
+use uuid::Uuid;
+
+use crate::state::AgentState;
+
+/// How often the sources are sampled.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sample the system every [`POLL_INTERVAL`], and on power supply events,
+/// and broadcast changes.
+pub async fn run(state: Arc<RwLock<AgentState>>) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    let mut power_events = watch_power_supply();
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            Some(()) = power_events.recv() => tracing::debug!("Power supply changed"),
+        }
+        refresh(&state).await;
+    }
+}
+
+/// Sample the system now and broadcast the status if it changed.
+pub async fn refresh(state: &Arc<RwLock<AgentState>>) {
+    let status = gather().await;
+
+    let mut guard = state.write().await;
+    if guard.system_status.as_ref() == Some(&status) {
+        return;
+    }
+    tracing::debug!(?status, "System status changed");
+    guard.system_status = Some(status.clone());
+    let guard = guard.downgrade();
+    guard.broadcast(ClientType::Dock, &status_message(status)).await;
+}
+
+/// Set the default sink volume.
+pub async fn set_volume(percent: u8) -> Result<(), String> {
+    run_control(
+        "wpctl",
+        &["set-volume", "@DEFAULT_AUDIO_SINK@", &format!("{percent}%")],
+    )
+    .await
+}
+
+/// Mute or unmute the default sink.
+pub async fn set_muted(muted: bool) -> Result<(), String> {
+    let muted = if muted { "1" } else { "0" };
+    run_control("wpctl", &["set-mute", "@DEFAULT_AUDIO_SINK@", muted]).await
+}
+
+/// Set the backlight; `brightnessctl` goes through logind, so no write
+/// access to sysfs is needed.
+pub async fn set_brightness(percent: u8) -> Result<(), String> {
+    // Never switch the panel off entirely from a slider.
+    let percent = percent.max(1);
+    run_control("brightnessctl", &["--quiet", "set", &format!("{percent}%")]).await
+}
+
+async fn run_control(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("{program}: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Wrap `status` for sending to a dock client.
+pub fn status_message(status: SystemStatus) -> IpcMessage {
+    IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::SystemStatus { status },
+    }
+}
+
+async fn gather() -> SystemStatus {
+    let (network, volume, battery, brightness, gpu) =
+        tokio::join!(network(), volume(), battery(), brightness(), gpu());
+    SystemStatus {
+        network,
+        volume,
+        battery,
+        brightness,
+        gpu,
+    }
+}
+
+/// Interface state from `/sys/class/net`, preferring a wired connection.
+async fn network() -> NetworkStatus {
+    let mut status = NetworkStatus::default();
+    let Ok(mut entries) = tokio::fs::read_dir("/sys/class/net").await else {
+        return status;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if entry.file_name() == "lo" {
+            continue;
+        }
+        let up = tokio::fs::read_to_string(path.join("operstate"))
+            .await
+            .is_ok_and(|state| state.trim() == "up");
+        if !up {
+            continue;
+        }
+        let wireless = tokio::fs::try_exists(path.join("wireless"))
+            .await
+            .unwrap_or(false);
+        status.wireless = if status.connected {
+            status.wireless && wireless
+        } else {
+            wireless
+        };
+        status.connected = true;
+    }
+    status
+}
+
+/// The busiest GPU while the dock shows the meter; read on every poll so
+/// turning it on in settings takes effect without a restart.
+async fn gpu() -> Option<GpuInfo> {
+    if !UiConfig::load().gpu_meter {
+        return None;
+    }
+    gpu::busiest().await
+}
+
+/// Default sink volume via `wpctl`.
+async fn volume() -> Option<VolumeStatus> {
+    wpctl::volume(wpctl::DEFAULT_SINK).await.ok()
+}
+
+/// Signals a power supply change (plugged in, charge level, ...) for every
+/// line `upower --monitor` prints. Never fires if upower is missing; the
+/// battery is then only polled.
+fn watch_power_supply() -> mpsc::Receiver<()> {
+    let (tx, rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        let child = tokio::process::Command::new("upower")
+            .arg("--monitor")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                tracing::info!("upower unavailable, polling the battery: {e}");
+                return;
+            }
+        };
+        let Some(stdout) = child.stdout.take() else {
+            return;
+        };
+        let mut lines = tokio::io::BufReader::new(stdout).lines();
+        while let Ok(Some(_)) = lines.next_line().await {
+            // A full channel means a refresh is already pending.
+            let _ = tx.try_send(());
+        }
+        tracing::warn!("upower --monitor exited, polling the battery");
+    });
+    rx
+}
+
+/// Charge, state and runtime of the first battery in
+/// `/sys/class/power_supply`.
+async fn battery() -> Option<BatteryStatus> {
+    let mut entries = tokio::fs::read_dir("/sys/class/power_supply").await.ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if read_trimmed(&path.join("type")).await.as_deref() != Some("Battery") {
+            continue;
+        }
+        let percent = read_trimmed(&path.join("capacity")).await?.parse().ok()?;
+        let state = read_trimmed(&path.join("status"))
+            .await
+            .map_or(ChargeState::Unknown, |status| ChargeState::from_sysfs(&status));
+        let minutes_to_empty = if state == ChargeState::Discharging {
+            minutes_to_empty(&path).await
+        } else {
+            None
+        };
+        return Some(BatteryStatus {
+            percent,
+            state,
+            minutes_to_empty,
+        });
+    }
+    None
+}
+
+/// Remaining runtime from the energy (µWh / µW) or charge (µAh / µA)
+/// attributes, whichever pair the battery exposes.
+async fn minutes_to_empty(battery: &Path) -> Option<u32> {
+    for (remaining, rate) in [("energy_now", "power_now"), ("charge_now", "current_now")] {
+        let remaining = read_trimmed(&battery.join(remaining)).await;
+        let rate = read_trimmed(&battery.join(rate)).await;
+        if let (Some(remaining), Some(rate)) = (remaining, rate) {
+            return runtime_minutes(remaining.parse().ok()?, rate.parse().ok()?);
+        }
+    }
+    None
+}
+
+/// Minutes until `remaining` is used up at `rate` per hour.
+fn runtime_minutes(remaining: u64, rate: u64) -> Option<u32> {
+    if rate == 0 {
+        return None;
+    }
+    u32::try_from(remaining * 60 / rate).ok()
+}
+
+/// Level of the first backlight in `/sys/class/backlight`.
+async fn brightness() -> Option<u8> {
+    let mut entries = tokio::fs::read_dir("/sys/class/backlight").await.ok()?;
+    let entry = entries.next_entry().await.ok()??;
+    let path = entry.path();
+    let current: u64 = read_trimmed(&path.join("brightness")).await?.parse().ok()?;
+    let max: u64 = read_trimmed(&path.join("max_brightness")).await?.parse().ok()?;
+    if max == 0 {
+        return None;
+    }
+    u8::try_from((current * 100 + max / 2) / max).ok()
+}
+
+async fn read_trimmed(path: &Path) -> Option<String> {
+    let content = tokio::fs::read_to_string(path).await.ok()?;
+    Some(content.trim().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_battery_runtime() {
+        // 30 Wh left at 10 W.
+        assert_eq!(runtime_minutes(30_000_000, 10_000_000), Some(180));
+        assert_eq!(runtime_minutes(30_000_000, 0), None);
+    }
+
+    #[test]
+    fn maps_sysfs_charge_state() {
+        assert_eq!(ChargeState::from_sysfs("Not charging"), ChargeState::NotCharging);
+        assert_eq!(ChargeState::from_sysfs("Discharging"), ChargeState::Discharging);
+        assert_eq!(ChargeState::from_sysfs("Bogus"), ChargeState::Unknown);
+    }
+}
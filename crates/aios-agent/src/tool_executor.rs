@@ -3,37 +3,62 @@
 //! This module bridges the LLM tool-call mechanism with the MCP tool registry.
 //! When the LLM returns a `ToolUse` message the router delegates here to:
 //!
-//! 1. Look up the tool in the [`ToolRegistry`].
-//! 2. Check whether user confirmation is required ([`TrustRequirement`]).
+//! 1. Look up the tool in the [`ToolRegistry`], by its current name or a
+//!    deprecated alias, and refuse disabled ones, or every tool while the
+//!    user has paused execution.
+//! 2. Check whether user confirmation is required ([`TrustRequirement`],
+//!    after the user's `[tools]` overrides).
 //! 3. Enforce rate limits for destructive actions.
-//! 4. Send a `ConfirmRequest` to the connected Confirm client and wait.
-//! 5. Execute the tool and return a [`ToolResult`].
+//! 4. Send a `ConfirmRequest` to the connected Confirm client, starting
+//!    one if none is connected, and wait.
+//! 5. Execute the tool, once fewer than `[agent] max_parallel_tools`
+//!    calls run, and return a [`ToolResult`], with secrets in its output
+//!    masked before the LLM sees it.
 //! 6. Log every step to the audit trail.
 
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError};
 use std::time::Duration;
 
 use aios_common::{
-    ClientType, IpcMessage, IpcPayload, ToolCall, ToolResult, TrustRequirement,
+    ClientType, IpcMessage, IpcPayload, NotificationKind, ToolCall, ToolDefinition,
+    ToolErrorKind, ToolResult, TrustRequirement, telemetry,
 };
-use aios_mcp::executor::ToolContext;
+use aios_mcp::executor::{ExecContext, ProgressSender, ToolContext, ToolError};
 use aios_mcp::registry::ToolRegistry;
 use tokio::sync::{oneshot, RwLock};
 use uuid::Uuid;
 
 use crate::audit::AuditLogger;
-use crate::state::AgentState;
+use crate::confirm_client;
+use crate::crash;
+use crate::notifications;
+use crate::redact;
+use crate::risk;
+use crate::state::{AgentState, ConfirmAnswer, Grants, PendingConfirm};
 
 /// Timeout for waiting on user confirmation via the Confirm client.
 const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Execute a single tool call through the full pipeline:
 /// lookup -> rate limit -> confirm -> execute -> audit.
+///
+/// `exec` is the working directory and environment of the conversation the
+/// call belongs to, `grants` the tools the user approved for the rest of it;
+/// calls from outside a conversation have no grants. Output the tool reports
+/// while it runs goes to `progress`.
+#[tracing::instrument(
+    name = "tool",
+    skip_all,
+    fields(tool = %tool_call.name, call_id = %tool_call.id)
+)]
 pub async fn execute_tool_call(
     tool_call: &ToolCall,
     registry: &ToolRegistry,
     state: &Arc<RwLock<AgentState>>,
     audit_logger: &AuditLogger,
+    exec: Arc<std::sync::Mutex<ExecContext>>,
+    grants: Option<Grants>,
+    progress: Option<ProgressSender>,
 ) -> ToolResult {
     // 1. Look up the tool.
     let Some(tool) = registry.get(&tool_call.name) else {
@@ -42,10 +67,49 @@ pub async fn execute_tool_call(
             call_id: tool_call.id,
             output: format!("Unknown tool: {}", tool_call.name),
             is_error: true,
+            error_kind: Some(ToolErrorKind::UnknownTool),
+            attachments: Vec::new(),
         };
     };
 
-    let trust_req = tool.trust_requirement();
+    // A call by a deprecated alias is confirmed, granted and audited under
+    // the tool's current name.
+    let renamed;
+    let tool_call = match registry.canonical_name(&tool_call.name) {
+        Some(name) if name != tool_call.name => {
+            renamed = ToolCall {
+                name: name.to_owned(),
+                ..tool_call.clone()
+            };
+            &renamed
+        }
+        _ => tool_call,
+    };
+
+    if !registry.is_enabled(&tool_call.name) {
+        tracing::warn!(tool = %tool_call.name, "Disabled tool requested");
+        audit_logger.log_rejected(tool_call).await;
+        return ToolResult {
+            call_id: tool_call.id,
+            output: format!("Tool {} is disabled in settings", tool_call.name),
+            is_error: true,
+            error_kind: Some(ToolErrorKind::Rejected),
+            attachments: Vec::new(),
+        };
+    }
+
+    if state.read().await.paused {
+        return refuse_paused(tool_call, audit_logger).await;
+    }
+
+    // The user may have raised or lowered the tool's own level; some calls
+    // need more than that.
+    let trust_req = registry
+        .trust_requirement(&tool_call.name)
+        .unwrap_or_else(|| tool.trust_requirement());
+    let trust_req = tool
+        .call_trust(&tool_call.arguments)
+        .map_or(trust_req, |call| call.max(trust_req));
 
     // 2. Rate-limit destructive actions.
     if trust_req == TrustRequirement::DoubleConfirm {
@@ -61,16 +125,42 @@ pub async fn execute_tool_call(
                 output: "Rate limit exceeded for destructive actions. Please wait before retrying."
                     .to_owned(),
                 is_error: true,
+                error_kind: Some(ToolErrorKind::RateLimited),
+                attachments: Vec::new(),
             };
         }
     }
 
-    // 3. Request user confirmation if the trust requirement demands it.
-    if trust_req != TrustRequirement::None {
-        let definition = tool.definition();
-        match request_confirmation(state, tool_call, &definition.description).await {
-            ConfirmOutcome::Approved => {
+    // 3. Request user confirmation if the trust requirement demands it. A
+    //    plain confirmation can be granted for the rest of the conversation;
+    //    destructive actions and calls led by untrusted content always ask.
+    let grants = grants.filter(|_| {
+        trust_req == TrustRequirement::Confirm && !tool_call.trust_level.is_untrusted()
+    });
+    let granted = grants.as_ref().is_some_and(|grants| {
+        grants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&tool_call.name)
+    });
+    if granted {
+        tracing::info!(tool = %tool_call.name, "Action approved earlier in this conversation");
+    } else if trust_req != TrustRequirement::None {
+        let mut definition = tool.definition();
+        definition.trust_requirement = trust_req;
+        match request_confirmation(state, tool_call, &definition, grants.is_some()).await {
+            ConfirmOutcome::Approved { remember } => {
                 tracing::info!(tool = %tool_call.name, "Action approved by user");
+                if remember && let Some(grants) = &grants {
+                    tracing::info!(
+                        tool = %tool_call.name,
+                        "Tool approved for the rest of the conversation"
+                    );
+                    grants
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .insert(tool_call.name.clone());
+                }
             }
             ConfirmOutcome::Rejected => {
                 tracing::info!(tool = %tool_call.name, "Action rejected by user");
@@ -79,25 +169,33 @@ pub async fn execute_tool_call(
                     call_id: tool_call.id,
                     output: "Action rejected by user".to_owned(),
                     is_error: true,
+                    error_kind: Some(ToolErrorKind::Rejected),
+                    attachments: Vec::new(),
                 };
             }
             ConfirmOutcome::Timeout => {
                 tracing::warn!(tool = %tool_call.name, "Confirmation timed out");
                 audit_logger.log_timeout(tool_call).await;
+                notify_confirm_missed(state, tool_call, "Nobody answered within 60s").await;
                 return ToolResult {
                     call_id: tool_call.id,
                     output: "Confirmation timed out (60s)".to_owned(),
                     is_error: true,
+                    error_kind: Some(ToolErrorKind::Timeout),
+                    attachments: Vec::new(),
                 };
             }
             ConfirmOutcome::NoClient => {
                 tracing::warn!(tool = %tool_call.name, "No confirm client connected");
                 audit_logger.log_rejected(tool_call).await;
+                notify_confirm_missed(state, tool_call, "No confirmation dialog was running").await;
                 return ToolResult {
                     call_id: tool_call.id,
                     output: "No confirmation client connected. Cannot execute this action."
                         .to_owned(),
                     is_error: true,
+                    error_kind: Some(ToolErrorKind::Unavailable),
+                    attachments: Vec::new(),
                 };
             }
             ConfirmOutcome::SendFailed => {
@@ -107,41 +205,95 @@ pub async fn execute_tool_call(
                     call_id: tool_call.id,
                     output: "Internal error: failed to contact confirmation client".to_owned(),
                     is_error: true,
+                    error_kind: Some(ToolErrorKind::Failed),
+                    attachments: Vec::new(),
                 };
             }
         }
     }
 
-    // 4. Execute the tool.
+    // 4. Execute the tool once a slot is free. Calls sharing `exec` belong
+    //    to the same conversation or MCP session.
+    let slots = Arc::clone(&state.read().await.tool_slots);
+    let _slot = slots.acquire(Arc::as_ptr(&exec) as usize).await;
+    // The user may have paused while the call waited for its slot.
+    if state.read().await.paused {
+        return refuse_paused(tool_call, audit_logger).await;
+    }
     let ctx = ToolContext {
         call_id: tool_call.id,
+        exec,
+        progress,
     };
 
-    let result = match tool.execute(tool_call.arguments.clone(), &ctx).await {
+    // A panicking tool fails its call instead of the whole chat turn.
+    let execution = match crash::catch(tool.execute(tool_call.arguments.clone(), &ctx)).await {
+        Ok(execution) => execution,
+        Err(panic) => {
+            let context = format!("tool {} with {}", tool_call.name, tool_call.arguments);
+            crash::report(state, &panic, &context).await;
+            Err(anyhow::anyhow!("the tool crashed: {}", panic.message))
+        }
+    };
+    let mut result = match execution {
         Ok(r) => r,
         Err(e) => {
-            let error_msg = format!("Execution error: {e:#}");
+            let mut error_msg = format!("Execution error: {e:#}");
+            redact::redact_string(&mut error_msg);
             audit_logger.log_error(tool_call, &error_msg).await;
             return ToolResult {
                 call_id: tool_call.id,
                 output: error_msg,
                 is_error: true,
+                error_kind: Some(ToolError::kind_of(&e)),
+                attachments: Vec::new(),
             };
         }
     };
 
-    // 5. Audit the result.
+    // 5. Mask secrets before the output goes back to the LLM, then audit.
+    redact::redact_string(&mut result.output);
     audit_logger.log_success(tool_call, &result).await;
     result
 }
 
+/// Refuse `tool_call` because the user paused tool execution.
+async fn refuse_paused(tool_call: &ToolCall, audit_logger: &AuditLogger) -> ToolResult {
+    tracing::warn!(tool = %tool_call.name, "Tool requested while paused");
+    audit_logger.log_paused(tool_call).await;
+    ToolResult {
+        call_id: tool_call.id,
+        output: "Tool execution is paused by the user. Do not retry; tell the user \
+                 the action was not performed."
+            .to_owned(),
+        is_error: true,
+        error_kind: Some(ToolErrorKind::Rejected),
+        attachments: Vec::new(),
+    }
+}
+
 // --------------------------------------------------------------------------
 // Confirmation flow
 // --------------------------------------------------------------------------
 
+/// Leave a note in the notification center that `tool_call` was skipped
+/// for lack of an answer, so the user can retry it later.
+async fn notify_confirm_missed(
+    state: &Arc<RwLock<AgentState>>,
+    tool_call: &ToolCall,
+    reason: &str,
+) {
+    let kind = NotificationKind::ConfirmMissed;
+    notifications::post(state, kind, &tool_call.name, reason).await;
+}
+
 /// Possible outcomes of a confirmation request.
 enum ConfirmOutcome {
-    Approved,
+    /// `remember`: the user also approved later calls of the tool in this
+    /// conversation.
+    Approved {
+        remember: bool,
+    },
     Rejected,
     Timeout,
     NoClient,
@@ -150,33 +302,57 @@ enum ConfirmOutcome {
 
 /// Send a `ConfirmRequest` to the connected Confirm client and wait for the
 /// user's decision.  Returns the outcome.
+///
+/// With the HTTP gateway running the request is also listed there, and is
+/// waited on even when no Confirm client is connected.
+///
+/// `can_remember` offers the user to approve the tool for the rest of the
+/// conversation.
 async fn request_confirmation(
     state: &Arc<RwLock<AgentState>>,
     tool_call: &ToolCall,
-    description: &str,
+    definition: &ToolDefinition,
+    can_remember: bool,
 ) -> ConfirmOutcome {
     let action_id = Uuid::new_v4();
     let (tx, rx) = oneshot::channel();
 
-    // Register the pending confirmation before sending the IPC message so
-    // that a fast response cannot arrive before the entry exists.
-    {
-        let mut state_guard = state.write().await;
-        state_guard.pending_confirms.insert(action_id, tx);
-    }
-
-    // Build the IPC message.
+    // Build the IPC message; it carries the id of the request the call
+    // belongs to.
     let confirm_msg = IpcMessage {
-        id: Uuid::new_v4(),
+        id: telemetry::message_id(),
         payload: IpcPayload::ConfirmRequest {
             action_id,
             action_type: tool_call.name.clone(),
-            description: description.to_owned(),
+            description: definition.description.clone(),
             command: serde_json::to_string_pretty(&tool_call.arguments).unwrap_or_default(),
             trust_level: tool_call.trust_level,
+            risk: Some(risk::summarize(definition, &tool_call.arguments)),
+            can_remember,
         },
     };
 
+    // Start the dialog if it is not running; without it the gateway may
+    // still answer.
+    confirm_client::ensure_running(state).await;
+
+    // Register the pending confirmation before sending the IPC message so
+    // that a fast response cannot arrive before the entry exists.
+    // A pause that came in since the check above must not be missed.
+    {
+        let mut state_guard = state.write().await;
+        if state_guard.paused {
+            return ConfirmOutcome::Rejected;
+        }
+        state_guard.pending_confirms.insert(
+            action_id,
+            PendingConfirm {
+                request: confirm_msg.payload.clone(),
+                reply: tx,
+            },
+        );
+    }
+
     // Find the Confirm client and send.
     let send_ok = {
         let state_guard = state.read().await;
@@ -185,9 +361,12 @@ async fn request_confirmation(
                 Ok(()) => true,
                 Err(e) => {
                     tracing::error!("Failed to send confirm request via IPC: {e}");
-                    false
+                    state_guard.remote_confirms
                 }
             }
+        } else if state_guard.remote_confirms {
+            tracing::info!(%action_id, "No Confirm client; waiting for the gateway");
+            true
         } else {
             // Clean up the pending entry since nobody will answer.
             drop(state_guard);
@@ -205,8 +384,13 @@ async fn request_confirmation(
 
     // Wait for the response with a timeout.
     match tokio::time::timeout(CONFIRM_TIMEOUT, rx).await {
-        Ok(Ok(true)) => ConfirmOutcome::Approved,
-        Ok(Ok(false)) => ConfirmOutcome::Rejected,
+        Ok(Ok(ConfirmAnswer {
+            approved: true,
+            remember,
+        })) => ConfirmOutcome::Approved {
+            remember: remember && can_remember,
+        },
+        Ok(Ok(_)) => ConfirmOutcome::Rejected,
         Ok(Err(_)) => {
             // Channel dropped -- the confirm client disconnected.
             tracing::warn!("Confirm channel dropped before response");
@@ -220,3 +404,27 @@ async fn request_confirmation(
         }
     }
 }
+
+/// Resolve the pending confirmation `action_id`, from a `ConfirmResponse`
+/// or the gateway. Returns `false` when nothing was waiting for it.
+pub async fn answer_confirmation(
+    state: &Arc<RwLock<AgentState>>,
+    action_id: Uuid,
+    approved: bool,
+    remember: bool,
+) -> bool {
+    let mut state_guard = state.write().await;
+    let Some(pending) = state_guard.pending_confirms.remove(&action_id) else {
+        tracing::warn!(%action_id, "No pending confirmation found for this action_id");
+        return false;
+    };
+    let answer = ConfirmAnswer { approved, remember };
+    if pending.reply.send(answer).is_err() {
+        tracing::warn!(
+            %action_id,
+            "Confirm response arrived but the waiting task was already gone"
+        );
+        return false;
+    }
+    true
+}
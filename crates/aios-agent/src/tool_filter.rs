@@ -0,0 +1,231 @@
+//! Per-request selection of the tool definitions sent to the LLM.
+//!
+//! Every definition costs prompt tokens, and small local models pick the
+//! wrong tool more often the more they see. With `[tool_selection]` on,
+//! a request carries the tools whose names and descriptions share words
+//! with the conversation's latest user messages, ranked by how many, plus
+//! the `always_include` ones and every tool the conversation already used.
+//! When nothing matches (a vague follow-up, another language) all tools are
+//! sent rather than guessing.
+
+use std::collections::HashSet;
+
+use aios_common::{ChatMessage, MessageContent, Role, ToolDefinition, ToolSelectionConfig};
+
+/// User messages, newest first, whose words select tools; the one before
+/// the latest gives a follow-up like "and the microphone?" its context.
+const USER_MESSAGES: usize = 2;
+
+/// Words too common to say anything about a tool.
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "this", "that", "these", "those", "you", "your",
+    "are", "was", "can", "could", "would", "please", "what", "which", "when", "where", "how",
+    "all", "any", "some", "not", "but", "have", "has", "get", "set", "use", "using", "make", "let",
+    "its", "it's", "then", "than", "there", "here", "about", "default", "optional", "given",
+];
+
+/// The definitions among `tools` worth sending with the next request of a
+/// conversation with `history`.
+pub fn select(
+    tools: Vec<ToolDefinition>,
+    history: &[ChatMessage],
+    config: &ToolSelectionConfig,
+) -> Vec<ToolDefinition> {
+    if !config.enabled || tools.len() <= config.max_tools {
+        return tools;
+    }
+
+    let query: HashSet<String> = history
+        .iter()
+        .rev()
+        .filter(|message| message.role == Role::User)
+        .filter_map(|message| match &message.content {
+            MessageContent::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .take(USER_MESSAGES)
+        .flat_map(words)
+        .collect();
+    let used: HashSet<&str> = history
+        .iter()
+        .filter_map(|message| match &message.content {
+            MessageContent::ToolUse { tool_calls } => Some(tool_calls),
+            _ => None,
+        })
+        .flatten()
+        .map(|call| call.name.as_str())
+        .collect();
+
+    let mut ranked: Vec<(usize, &str)> = tools
+        .iter()
+        .filter(|tool| !is_pinned(tool, config, &used))
+        .map(|tool| (score(tool, &query), tool.name.as_str()))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    if ranked.is_empty() {
+        return tools;
+    }
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+    let picked: HashSet<String> = ranked
+        .into_iter()
+        .take(config.max_tools)
+        .map(|(_, name)| name.to_owned())
+        .collect();
+
+    let selected: Vec<ToolDefinition> = tools
+        .into_iter()
+        .filter(|tool| picked.contains(&tool.name) || is_pinned(tool, config, &used))
+        .collect();
+    tracing::debug!(tools = selected.len(), "Selected tools for the request");
+    selected
+}
+
+/// Sent regardless of the words: configured, or used before.
+fn is_pinned(tool: &ToolDefinition, config: &ToolSelectionConfig, used: &HashSet<&str>) -> bool {
+    used.contains(tool.name.as_str()) || config.always_include.contains(&tool.name)
+}
+
+/// How many of the `query` words the tool's name, description and
+/// parameters mention; words of the name count twice.
+fn score(tool: &ToolDefinition, query: &HashSet<String>) -> usize {
+    let name: Vec<String> = words(&tool.name).collect();
+    let mut text = tool.description.clone();
+    if let Some(properties) = tool.parameters.get("properties").and_then(|p| p.as_object()) {
+        for (parameter, schema) in properties {
+            text.push(' ');
+            text.push_str(parameter);
+            if let Some(description) = schema.get("description").and_then(|d| d.as_str()) {
+                text.push(' ');
+                text.push_str(description);
+            }
+        }
+    }
+    let described: Vec<String> = words(&text).collect();
+
+    query
+        .iter()
+        .map(|word| {
+            if name.iter().any(|w| same_word(w, word)) {
+                2
+            } else if described.iter().any(|w| same_word(w, word)) {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Lowercase words of `text` worth matching.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+}
+
+/// Equal, or the same stem: "files" and "file", "brighter" and
+/// "brightness".
+fn same_word(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    let shorter = a.chars().count().min(b.chars().count());
+    if shorter < 4 {
+        return false;
+    }
+    let common = a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count();
+    common == shorter || common >= 5
+}
+
+#[cfg(test)]
+mod tests {
+    use aios_common::{ToolCall, TrustLevel, TrustRequirement};
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn tool(name: &str, description: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            parameters: json!({ "type": "object", "properties": {} }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn message(role: Role, content: MessageContent) -> ChatMessage {
+        ChatMessage {
+            id: Uuid::new_v4(),
+            role,
+            content,
+            trust_level: TrustLevel::User,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn user(text: &str) -> ChatMessage {
+        message(Role::User, MessageContent::Text { text: text.to_owned() })
+    }
+
+    fn tools() -> Vec<ToolDefinition> {
+        vec![
+            tool("volume", "Get or set audio volume and mute of speakers and microphones"),
+            tool("brightness", "Get or set display brightness"),
+            tool("wifi_connect", "Connect to a Wi-Fi network by SSID"),
+            tool("file_read", "Read the contents of a file"),
+            tool("open_url", "Open a web page in the browser"),
+        ]
+    }
+
+    fn config(max_tools: usize) -> ToolSelectionConfig {
+        ToolSelectionConfig {
+            enabled: true,
+            max_tools,
+            always_include: vec!["file_read".to_owned()],
+        }
+    }
+
+    fn names(tools: &[ToolDefinition]) -> Vec<&str> {
+        tools.iter().map(|tool| tool.name.as_str()).collect()
+    }
+
+    #[test]
+    fn picks_matching_tools_and_pinned_ones() {
+        let history = [user("Please mute my microphone")];
+        let selected = select(tools(), &history, &config(2));
+        assert_eq!(names(&selected), ["volume", "file_read"]);
+    }
+
+    #[test]
+    fn keeps_tools_used_in_the_conversation() {
+        let call = ToolCall {
+            id: Uuid::new_v4(),
+            name: "wifi_connect".to_owned(),
+            arguments: json!({}),
+            trust_level: TrustLevel::User,
+        };
+        let history = [
+            user("join the cafe network"),
+            message(Role::Assistant, MessageContent::ToolUse { tool_calls: vec![call] }),
+            user("make the screen brighter"),
+        ];
+        let selected = select(tools(), &history, &config(1));
+        assert_eq!(names(&selected), ["brightness", "wifi_connect", "file_read"]);
+    }
+
+    #[test]
+    fn sends_everything_without_a_match() {
+        let history = [user("сделай потише")];
+        assert_eq!(select(tools(), &history, &config(2)).len(), 5);
+    }
+
+    #[test]
+    fn small_tool_sets_are_not_filtered() {
+        let history = [user("mute")];
+        assert_eq!(select(tools(), &history, &config(5)).len(), 5);
+    }
+}
@@ -0,0 +1,131 @@
+//! Limit on tool calls running at once.
+//!
+//! Calls beyond `[agent] max_parallel_tools` wait their turn in arrival
+//! order. One conversation or MCP session may hold at most half of the
+//! slots, so a burst of its calls leaves room for everybody else; its
+//! surplus calls queue behind each other before they join the shared
+//! queue.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use aios_common::AiosConfig;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Who a call runs for; calls of one conversation or session share it.
+pub type Owner = usize;
+
+/// Slots for running tool calls.
+pub struct ToolSlots {
+    limit: usize,
+    shared: Arc<Semaphore>,
+    /// Slots one owner may hold at once.
+    per_owner: usize,
+    owners: Arc<Mutex<HashMap<Owner, Arc<Semaphore>>>>,
+}
+
+/// A slot held while a call runs; freed on drop.
+pub struct ToolSlot {
+    _shared: OwnedSemaphorePermit,
+    _owned: OwnedSemaphorePermit,
+    owner: Owner,
+    owners: Arc<Mutex<HashMap<Owner, Arc<Semaphore>>>>,
+}
+
+impl ToolSlots {
+    /// Room for `limit` calls at once; `0` is taken as `1`.
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            limit,
+            shared: Arc::new(Semaphore::new(limit)),
+            per_owner: limit.div_ceil(2),
+            owners: Arc::default(),
+        }
+    }
+
+    /// Calls that may run at once.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Wait for a slot for a call of `owner`.
+    pub async fn acquire(&self, owner: Owner) -> ToolSlot {
+        let own = Arc::clone(
+            self.owners
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(owner)
+                .or_insert_with(|| Arc::new(Semaphore::new(self.per_owner))),
+        );
+        if own.available_permits() == 0 || self.shared.available_permits() == 0 {
+            tracing::debug!("Tool call queued until another one finishes");
+        }
+        // Neither semaphore is ever closed.
+        let owned = own.acquire_owned().await.expect("semaphore closed");
+        let shared = Arc::clone(&self.shared)
+            .acquire_owned()
+            .await
+            .expect("semaphore closed");
+        ToolSlot {
+            _shared: shared,
+            _owned: owned,
+            owner,
+            owners: Arc::clone(&self.owners),
+        }
+    }
+}
+
+impl Default for ToolSlots {
+    fn default() -> Self {
+        Self::new(AiosConfig::default().agent.max_parallel_tools)
+    }
+}
+
+impl Drop for ToolSlot {
+    fn drop(&mut self) {
+        let mut owners = self.owners.lock().unwrap_or_else(PoisonError::into_inner);
+        // Forget owners with nothing running or queued; this slot's own
+        // permit and reference are still counted.
+        if let Some(own) = owners.get(&self.owner)
+            && Arc::strong_count(own) == 2
+        {
+            owners.remove(&self.owner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    async fn blocked(slots: &ToolSlots, owner: Owner) -> bool {
+        tokio::time::timeout(Duration::from_millis(20), slots.acquire(owner))
+            .await
+            .is_err()
+    }
+
+    #[tokio::test]
+    async fn one_owner_leaves_room_for_others() {
+        let slots = ToolSlots::new(4);
+        let _a1 = slots.acquire(1).await;
+        let _a2 = slots.acquire(1).await;
+        assert!(blocked(&slots, 1).await);
+
+        let _b1 = slots.acquire(2).await;
+        let _b2 = slots.acquire(2).await;
+        assert!(blocked(&slots, 3).await);
+    }
+
+    #[tokio::test]
+    async fn finished_calls_free_their_slot() {
+        let slots = ToolSlots::new(1);
+        let first = slots.acquire(1).await;
+        assert!(blocked(&slots, 2).await);
+        drop(first);
+        let _second = slots.acquire(2).await;
+        assert!(slots.owners.lock().unwrap().keys().eq([&2]));
+    }
+}
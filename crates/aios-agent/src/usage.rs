@@ -0,0 +1,102 @@
+//! Cost estimates for LLM token usage.
+//!
+//! Prices are list prices in USD per million tokens, matched by model name
+//! prefix. They go stale; the chat labels the result as an estimate.
+
+use aios_common::{TokenUsage, UsageReport};
+
+/// `(provider, model prefix, input price, output price)`, most specific
+/// prefix first.
+const PRICES: &[(&str, &str, f64, f64)] = &[
+    ("claude", "claude-opus-4", 15.0, 75.0),
+    ("claude", "claude-sonnet-4", 3.0, 15.0),
+    ("claude", "claude-3-7-sonnet", 3.0, 15.0),
+    ("claude", "claude-3-5-sonnet", 3.0, 15.0),
+    ("claude", "claude-3-5-haiku", 0.8, 4.0),
+    ("claude", "claude-3-opus", 15.0, 75.0),
+    ("claude", "claude-3-haiku", 0.25, 1.25),
+    ("openai", "gpt-4o-mini", 0.15, 0.6),
+    ("openai", "gpt-4o", 2.5, 10.0),
+    ("openai", "gpt-4.1-nano", 0.1, 0.4),
+    ("openai", "gpt-4.1-mini", 0.4, 1.6),
+    ("openai", "gpt-4.1", 2.0, 8.0),
+    ("openai", "o4-mini", 1.1, 4.4),
+    ("openai", "o3-mini", 1.1, 4.4),
+    ("openai", "o3", 2.0, 8.0),
+];
+
+/// Tokens used across the LLM calls of one chat turn.
+#[derive(Debug, Default)]
+pub struct TurnUsage {
+    tokens: TokenUsage,
+    llm_calls: u32,
+}
+
+impl TurnUsage {
+    /// Add the usage of one LLM response.
+    pub fn record(&mut self, tokens: TokenUsage) {
+        self.tokens += tokens;
+        self.llm_calls += 1;
+    }
+
+    /// Usage report for the chat, or `None` if no LLM was called (echo
+    /// mode).
+    pub fn report(&self, provider: &str, model: &str) -> Option<UsageReport> {
+        if self.llm_calls == 0 {
+            return None;
+        }
+        Some(UsageReport {
+            provider: provider.to_owned(),
+            model: model.to_owned(),
+            tokens: self.tokens,
+            llm_calls: self.llm_calls,
+            cost_usd: estimate_cost(provider, model, self.tokens),
+        })
+    }
+}
+
+/// Estimated cost in USD of `tokens` on `provider`/`model`.
+///
+/// Returns `None` for local providers (Ollama) and unknown models.
+pub fn estimate_cost(provider: &str, model: &str, tokens: TokenUsage) -> Option<f64> {
+    let (_, _, input, output) = PRICES
+        .iter()
+        .find(|(p, prefix, _, _)| *p == provider && model.starts_with(prefix))?;
+    let cost = tokens.input_tokens as f64 * input + tokens.output_tokens as f64 * output;
+    Some(cost / 1_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input_tokens: u64, output_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens,
+            output_tokens,
+        }
+    }
+
+    fn assert_cost(cost: Option<f64>, expected: f64) {
+        let cost = cost.expect("model should be priced");
+        assert!((cost - expected).abs() < 1e-9, "{cost} != {expected}");
+    }
+
+    #[test]
+    fn prices_known_models() {
+        let cost = estimate_cost("claude", "claude-sonnet-4-20250514", tokens(1_000_000, 100_000));
+        assert_cost(cost, 4.5);
+    }
+
+    #[test]
+    fn most_specific_prefix_wins() {
+        assert_cost(estimate_cost("openai", "gpt-4o-mini", tokens(1_000_000, 0)), 0.15);
+        assert_cost(estimate_cost("openai", "gpt-4o", tokens(1_000_000, 0)), 2.5);
+    }
+
+    #[test]
+    fn local_and_unknown_models_are_unpriced() {
+        assert_eq!(estimate_cost("ollama", "llama3", tokens(500, 500)), None);
+        assert_eq!(estimate_cost("openai", "some-future-model", tokens(500, 500)), None);
+    }
+}
@@ -1,34 +1,90 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
+use base64::Engine as _;
 use chrono::Utc;
-use iced::widget::markdown;
-use iced::{Element, Subscription, Task};
+use iced::widget::{self, image, markdown, scrollable, text_editor};
+use iced::{keyboard, Element, Subscription, Task};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use aios_common::hardware::Memory;
 use aios_common::ipc::IpcWriter;
 use aios_common::{
-    AiosConfig, ChatMessage, IpcMessage, IpcPayload, MessageContent, ProviderConfig, ProviderType,
+    AiosConfig, Attachment, ChatMessage, IpcMessage, IpcPayload, MacroConfig, MessageContent,
+    ModelInfo, ProviderConfig, ProviderType, UiConfig, config_path, t,
 };
+use serde::{Deserialize, Serialize};
 
+use crate::attachments;
+use crate::background::{self, Activation};
+use crate::commands::{self, SlashCommand};
 use crate::ipc_client::{self, IpcEvent};
-use crate::state::{ConnectionStatus, DisplayMessage, ToolStatus};
-use crate::views::{chat_view, oobe};
+use crate::oobe_progress::{self, OobeProgress};
+use crate::state::{
+    ConnectionStatus, ConversationUsage, Delivery, DisplayMessage, MessageRole, ToolStatus,
+    VoiceStatus,
+};
+use crate::views::{chat_view, oobe, system_card};
+use crate::voice::{self, RecorderEvent};
 
-/// Root application state for the AIOS Chat UI.
+/// State of one chat window: a conversation and its agent connection.
 pub struct AiosChat {
     messages: Vec<DisplayMessage>,
-    input_text: String,
+    /// Multi-line message input.
+    input: text_editor::Content,
     connection_status: ConnectionStatus,
+    /// Why the last connection attempt failed; cleared once connected.
+    connection_error: Option<String>,
+    /// When the IPC worker will next try to reconnect.
+    retry_at: Option<Instant>,
     /// Shared writer handle for sending messages to the agent.
     writer: Option<Arc<Mutex<IpcWriter>>>,
+    /// Messages written while offline, sent in order once connected.
+    outbox: Vec<OutboxEntry>,
+    /// An outbox flush is in flight; new messages queue behind it.
+    flushing: bool,
+    /// Messages written to the agent that it has not confirmed yet, kept
+    /// so a lost one can be sent again.
+    unconfirmed: Vec<OutboxEntry>,
     /// Sent with every `ChatRequest`.
     conversation_id: Uuid,
+    /// A request was sent and the agent has not replied yet; Escape cancels
+    /// it.
+    awaiting_reply: bool,
+    /// Tokens and cost spent in this conversation, shown in the header.
+    usage: ConversationUsage,
     /// Accumulator for the current streaming assistant response.
     streaming_message: Option<StreamingMessage>,
+    /// Id of the user message being edited; the next send replaces it.
+    editing: Option<Uuid>,
+    /// Files dropped into the window, sent with the next message.
+    attachments: Vec<Attachment>,
+    /// In-chat search query. `None` when the search bar is closed.
+    search_query: Option<String>,
+    /// Whether the message list follows new content. Cleared when the user
+    /// scrolls away from the bottom.
+    stick_to_bottom: bool,
+    /// New content arrived while the user was scrolled up.
+    has_unseen_messages: bool,
+    /// Active voice input session, if any.
+    voice: Option<VoiceSession>,
+    /// Image shown full size over the chat, if any.
+    image_preview: Option<image::Handle>,
+    /// Widget ids of the message list, search field and input field, unique
+    /// per window so scroll and focus operations only reach this one.
+    message_list_id: widget::Id,
+    search_input_id: widget::Id,
+    input_id: widget::Id,
     /// OOBE wizard state. `None` means normal chat mode.
     oobe_state: Option<OobeState>,
+    /// Macros from `[macros]`, run as slash commands.
+    macros: BTreeMap<String, MacroConfig>,
+    /// The window was closed to the background (sway scratchpad); replies
+    /// are announced with desktop notifications.
+    hidden: bool,
 }
 
 /// State for the OOBE (first boot) setup wizard.
@@ -51,10 +107,50 @@ pub struct OobeState {
     pub available_models: Vec<String>,
     /// Custom model name typed by user.
     pub custom_model_input: String,
+    /// RAM and VRAM of this machine, once probed, to recommend a model
+    /// size.
+    pub memory: Option<Memory>,
+}
+
+impl OobeState {
+    /// A wizard starting at the welcome screen.
+    fn new() -> Self {
+        Self {
+            step: OobeStep::Welcome,
+            selected_provider: None,
+            api_key_input: String::new(),
+            ollama_model: None,
+            ollama_status: None,
+            pulling: false,
+            pull_progress: 0.0,
+            available_models: Vec::new(),
+            custom_model_input: String::new(),
+            memory: None,
+        }
+    }
+
+    /// A wizard reopened at the step saved by an earlier run.
+    fn resume(progress: OobeProgress) -> Self {
+        Self {
+            step: progress.step,
+            selected_provider: progress.selected_provider,
+            ollama_model: progress.ollama_model,
+            ..Self::new()
+        }
+    }
+
+    fn progress(&self) -> OobeProgress {
+        OobeProgress {
+            step: self.step,
+            selected_provider: self.selected_provider,
+            ollama_model: self.ollama_model.clone(),
+        }
+    }
 }
 
 /// Steps in the OOBE setup wizard.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum OobeStep {
     /// Welcome / greeting screen.
     Welcome,
@@ -70,6 +166,24 @@ pub enum OobeStep {
     Complete,
 }
 
+/// A voice input session: recording, then waiting for the final transcript.
+struct VoiceSession {
+    id: Uuid,
+    /// Input text present when recording started; transcripts are appended.
+    base_text: String,
+    /// `false` once the user stopped recording and the final transcript is
+    /// pending.
+    recording: bool,
+}
+
+/// A message waiting in the outbox: the display message it belongs to and
+/// the payload to send.
+#[derive(Debug, Clone)]
+pub struct OutboxEntry {
+    message_id: Uuid,
+    payload: IpcPayload,
+}
+
 /// Tracks an in-progress streaming response from the agent.
 struct StreamingMessage {
     id: Uuid,
@@ -81,9 +195,9 @@ struct StreamingMessage {
 #[derive(Debug, Clone)]
 #[allow(clippy::enum_variant_names)]
 pub enum Message {
-    /// The user typed in the input field.
-    InputChanged(String),
-    /// The user pressed Enter or clicked Send.
+    /// The user edited the input field.
+    InputAction(text_editor::Action),
+    /// The user pressed Enter / Ctrl+Enter or clicked Send.
     SendMessage,
     /// A clickable link inside a rendered markdown block was clicked.
     OpenUrl(markdown::Uri),
@@ -91,6 +205,96 @@ pub enum Message {
     Ipc(IpcEvent),
     /// Async IPC send completed (Ok) or failed (Err reason).
     SendCompleted(Result<(), String>),
+    /// An outbox flush finished. `unsent` holds the entries that could not
+    /// be delivered, starting with the one that failed.
+    OutboxFlushed {
+        sent: Vec<OutboxEntry>,
+        unsent: Vec<OutboxEntry>,
+        error: Option<String>,
+    },
+    /// The user clicked "Reconnect now" in the connection banner.
+    ReconnectNow,
+    /// One-second tick that refreshes the reconnect countdown.
+    ConnectionTick,
+
+    // -- Message actions --
+
+    /// Copy the text of a message to the clipboard.
+    CopyMessage(Uuid),
+    /// Load a previous user message into the input for editing.
+    EditMessage(Uuid),
+    /// Send a message again that never reached the agent.
+    RetryMessage(Uuid),
+    /// Up arrow in an empty input: edit the last user message.
+    EditLastMessage,
+    /// Abandon the current edit.
+    CancelEdit,
+    /// Ask the agent to regenerate its last reply.
+    RegenerateLast,
+    /// Unfold or fold the reasoning behind a reply.
+    ToggleReasoning(Uuid),
+    /// Ctrl+N: start a new conversation.
+    NewConversation,
+    /// Escape: close the topmost overlay, or stop the reply in progress.
+    Escape,
+
+    // -- Attachments --
+
+    /// A file was dropped onto the chat window.
+    FileDropped(PathBuf),
+    /// A dropped file finished loading (or was refused).
+    AttachmentLoaded(Result<Attachment, String>),
+    /// User removed a pending attachment chip.
+    RemoveAttachment(usize),
+
+    // -- Slash commands --
+
+    /// Replace the input with the given command name (autocompletion).
+    CompleteCommand(String),
+    /// Tab pressed: complete the first suggested command, or move the
+    /// focus to the next field when there is none.
+    AutocompleteCommand,
+    /// `/export` finished writing the transcript.
+    ExportFinished(Result<PathBuf, String>),
+    /// The config file changed: reload the macros.
+    ConfigChanged,
+
+    // -- Voice input --
+
+    /// Mic button: start recording, or stop and transcribe.
+    ToggleVoice,
+    /// Audio (or a failure) from the microphone recorder.
+    VoiceCaptured(RecorderEvent),
+
+    // -- Scrolling --
+
+    /// The message list was scrolled (by the user or programmatically).
+    ChatScrolled(scrollable::Viewport),
+    /// User clicked the "New messages" button.
+    JumpToLatest,
+
+    // -- Search --
+
+    /// Ctrl+F: open the search bar (or refocus it).
+    OpenSearch,
+    /// User typed into the search field.
+    SearchChanged(String),
+    /// The close button: hide the search bar.
+    CloseSearch,
+
+    // -- Keyboard focus --
+
+    /// Shift+Tab: focus the previous field.
+    FocusPrevious,
+    /// Ctrl+L: focus the message field.
+    FocusInput,
+
+    // -- Image preview --
+
+    /// A tool card thumbnail was clicked: show the image full size.
+    OpenImage(image::Handle),
+    /// Click on the preview or Escape: close it.
+    CloseImage,
 
     // -- OOBE wizard messages --
 
@@ -106,6 +310,8 @@ pub enum Message {
     OobeOllamaChecked { installed: bool },
     /// Available models list fetched from Ollama library.
     OobeOllamaModelsLoaded(Vec<String>),
+    /// RAM and VRAM probed for the model recommendation.
+    OobeHardwareProbed(Option<Memory>),
     /// User typed into custom model input.
     OobeOllamaCustomModelChanged(String),
     /// User selected an Ollama model to pull.
@@ -125,74 +331,308 @@ pub enum Message {
     /// Agent config reload via IPC completed.
     OobeAgentReloaded(bool, String),
 
-    /// User clicked the close (X) button.
+    /// User clicked the close (X) button or the compositor asked to close.
+    /// Pop-out windows close; the main window goes to the background.
     CloseWindow,
+    /// Open a new window with a conversation of its own (Ctrl+Shift+N).
+    /// Handled by [`ChatWindows`](crate::windows::ChatWindows).
+    PopOut,
+    /// Another `aios-chat` invocation asked this instance to show itself.
+    Activated(Activation),
 }
 
 impl AiosChat {
     /// Bootstrap the application state. Returns `(state, initial_command)`.
     ///
     /// If no configuration file exists at `~/.config/aios/agent.toml`, the
-    /// application starts in OOBE (first-boot) mode.
+    /// application starts in OOBE (first-boot) mode. An interrupted wizard
+    /// resumes at its saved step, and `--setup` re-runs it deliberately.
     pub fn new() -> (Self, Task<Message>) {
-        let oobe_state = if config_path().exists() {
-            None
-        } else {
-            Some(OobeState {
-                step: OobeStep::Welcome,
-                selected_provider: None,
-                api_key_input: String::new(),
-                ollama_model: None,
-                ollama_status: None,
-                pulling: false,
-                pull_progress: 0.0,
-                available_models: Vec::new(),
-                custom_model_input: String::new(),
-            })
+        let rerun = std::env::args().any(|arg| arg == "--setup");
+        let oobe_state = match oobe_progress::load() {
+            Some(progress) => {
+                tracing::info!(step = ?progress.step, "Resuming setup wizard");
+                Some(OobeState::resume(progress))
+            }
+            None if rerun || !config_path().exists() => Some(OobeState::new()),
+            None => None,
+        };
+        // The Ollama steps depend on a service check; redo it on resume.
+        let resume_task = match &oobe_state {
+            Some(oobe)
+                if matches!(oobe.step, OobeStep::OllamaSetup | OobeStep::OllamaModelSelect) =>
+            {
+                Task::done(Message::OobeSelectProvider(ProviderType::Ollama))
+            }
+            _ => Task::none(),
         };
 
-        let state = Self {
+        // The IPC worker subscription handles connection automatically.
+        (Self::with_oobe(oobe_state), resume_task)
+    }
+
+    /// A chat for a pop-out window: a fresh conversation, never the wizard.
+    pub fn popout() -> Self {
+        Self::with_oobe(None)
+    }
+
+    fn with_oobe(oobe_state: Option<OobeState>) -> Self {
+        Self {
             messages: Vec::new(),
-            input_text: String::new(),
+            input: text_editor::Content::new(),
             connection_status: ConnectionStatus::Connecting,
+            connection_error: None,
+            retry_at: None,
             writer: None,
+            outbox: Vec::new(),
+            flushing: false,
+            unconfirmed: Vec::new(),
             conversation_id: Uuid::new_v4(),
+            awaiting_reply: false,
+            usage: ConversationUsage::default(),
             streaming_message: None,
+            editing: None,
+            attachments: Vec::new(),
+            search_query: None,
+            stick_to_bottom: true,
+            has_unseen_messages: false,
+            voice: None,
+            image_preview: None,
+            message_list_id: widget::Id::unique(),
+            search_input_id: widget::Id::unique(),
+            input_id: widget::Id::unique(),
             oobe_state,
-        };
-        // The IPC worker subscription handles connection automatically.
-        (state, Task::none())
+            macros: MacroConfig::load_all(),
+            hidden: false,
+        }
     }
 
     /// Process an incoming UI message and return a command.
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             // -- Normal chat messages --
-            Message::InputChanged(value) => {
-                self.input_text = value;
+            Message::InputAction(action) => {
+                self.input.perform(action);
             }
             Message::SendMessage => {
-                return self.handle_send();
+                let send = self.handle_send();
+                return Task::batch([send, self.jump_to_latest()]);
             }
             Message::OpenUrl(url) => {
                 tracing::info!("Opening URL: {url}");
             }
+            Message::OutboxFlushed {
+                sent,
+                unsent,
+                error,
+            } => {
+                return self.handle_outbox_flushed(sent, unsent, error);
+            }
+            Message::ReconnectNow => {
+                ipc_client::reconnect_now();
+                self.connection_status = ConnectionStatus::Connecting;
+                self.retry_at = None;
+            }
+            Message::ConnectionTick => {}
             Message::Ipc(event) => {
-                return self.handle_ipc_event(event);
+                let before = self.content_revision();
+                let task = self.handle_ipc_event(event);
+                if self.content_revision() != before {
+                    return Task::batch([task, self.follow_new_content()]);
+                }
+                return task;
+            }
+            Message::ChatScrolled(viewport) => {
+                // Within a few pixels of the end counts as "at the bottom".
+                let bounds = viewport.bounds();
+                let content = viewport.content_bounds();
+                let remaining = content.height - (viewport.absolute_offset().y + bounds.height);
+                self.stick_to_bottom = remaining <= 8.0;
+                if self.stick_to_bottom {
+                    self.has_unseen_messages = false;
+                }
+            }
+            Message::JumpToLatest => {
+                return self.jump_to_latest();
             }
             Message::SendCompleted(result) => {
                 if let Err(reason) = result {
                     tracing::error!("Failed to send message: {reason}");
                     self.messages.push(DisplayMessage::assistant(
                         Uuid::new_v4(),
-                        format!("*Send error:* {reason}"),
+                        t!("chat-send-error", reason = reason),
                         Utc::now(),
                     ));
                 }
             }
 
+            Message::CopyMessage(id) => {
+                if let Some(msg) = self.messages.iter().find(|m| m.id == id) {
+                    return iced::clipboard::write(msg.text.clone());
+                }
+            }
+            Message::ToggleReasoning(id) => {
+                if let Some(msg) = self.messages.iter_mut().find(|m| m.id == id) {
+                    msg.reasoning_shown = !msg.reasoning_shown;
+                }
+            }
+            Message::EditMessage(id) => {
+                if let Some(msg) = self
+                    .messages
+                    .iter()
+                    .find(|m| m.id == id && m.role == MessageRole::User)
+                {
+                    let text = msg.text.clone();
+                    self.set_input(&text);
+                    self.editing = Some(id);
+                }
+            }
+            Message::RetryMessage(id) => {
+                let Some(index) = self.unconfirmed.iter().position(|e| e.message_id == id) else {
+                    return Task::none();
+                };
+                let entry = self.unconfirmed.remove(index);
+                self.set_delivery(id, Delivery::Sending);
+                self.outbox.push(entry);
+                self.awaiting_reply = true;
+                return self.flush_outbox();
+            }
+            Message::EditLastMessage => {
+                if let Some(id) = self
+                    .messages
+                    .iter()
+                    .rev()
+                    .find(|m| m.role == MessageRole::User)
+                    .map(|m| m.id)
+                {
+                    return self.update(Message::EditMessage(id));
+                }
+            }
+            Message::CancelEdit => {
+                self.editing = None;
+                self.set_input("");
+            }
+            Message::RegenerateLast => {
+                return self.handle_regenerate();
+            }
+            Message::NewConversation => {
+                if self.oobe_state.is_none() {
+                    self.start_new_conversation();
+                }
+            }
+            Message::Escape => {
+                return self.handle_escape();
+            }
+            Message::FileDropped(path) => {
+                if self.oobe_state.is_none() {
+                    return Task::perform(attachments::load(path), Message::AttachmentLoaded);
+                }
+            }
+            Message::AttachmentLoaded(result) => {
+                // All of them go in one request, which must fit an IPC frame.
+                let result = result.and_then(|attachment| {
+                    let total = attachments::encoded_len(&self.attachments)
+                        + attachments::encoded_len(std::slice::from_ref(&attachment));
+                    if total > attachments::MAX_TOTAL_BYTES {
+                        return Err(t!(
+                            "chat-attach-over-budget",
+                            name = attachment.name.as_str(),
+                            limit = attachments::mib(attachments::MAX_TOTAL_BYTES)
+                        ));
+                    }
+                    Ok(attachment)
+                });
+                match result {
+                    Ok(attachment) => self.attachments.push(attachment),
+                    Err(reason) => {
+                        tracing::warn!("Attachment refused: {reason}");
+                        self.messages.push(DisplayMessage::assistant(
+                            Uuid::new_v4(),
+                            t!("chat-attach-error", reason = reason),
+                            Utc::now(),
+                        ));
+                    }
+                }
+            }
+            Message::RemoveAttachment(index) => {
+                if index < self.attachments.len() {
+                    self.attachments.remove(index);
+                }
+            }
+            Message::ToggleVoice => {
+                return self.toggle_voice();
+            }
+            Message::VoiceCaptured(event) => {
+                return self.handle_voice_captured(event);
+            }
+            Message::CompleteCommand(name) => {
+                self.set_input(&format!("/{name} "));
+            }
+            Message::AutocompleteCommand => {
+                match self.command_suggestions().first() {
+                    Some(first) => self.set_input(&format!("/{} ", first.name)),
+                    None => return widget::operation::focus_next(),
+                }
+            }
+            Message::ConfigChanged => {
+                self.macros = MacroConfig::load_all();
+            }
+            Message::ExportFinished(result) => {
+                let text = match result {
+                    Ok(path) => t!("chat-exported", path = path.display().to_string()),
+                    Err(reason) => t!("chat-export-failed", reason = reason),
+                };
+                self.messages
+                    .push(DisplayMessage::assistant(Uuid::new_v4(), text, Utc::now()));
+            }
+            Message::OpenSearch => {
+                if self.oobe_state.is_none() {
+                    self.search_query.get_or_insert_with(String::new);
+                    return iced::widget::operation::focus(self.search_input_id.clone());
+                }
+            }
+            Message::SearchChanged(query) => {
+                self.search_query = Some(query);
+            }
+            Message::CloseSearch => {
+                self.search_query = None;
+                return widget::operation::focus(self.input_id.clone());
+            }
+            Message::FocusPrevious => {
+                return widget::operation::focus_previous();
+            }
+            Message::FocusInput => {
+                return widget::operation::focus(self.input_id.clone());
+            }
+            Message::OpenImage(handle) => {
+                self.image_preview = Some(handle);
+            }
+            Message::CloseImage => {
+                self.image_preview = None;
+            }
+
+            // Opening windows is up to `ChatWindows`; it never forwards this.
+            Message::PopOut => {}
             Message::CloseWindow => {
-                return iced::exit();
+                if !UiConfig::load().close_to_background {
+                    return iced::exit();
+                }
+                tracing::info!("Closing to background");
+                self.hidden = true;
+                return Task::future(background::hide_window()).discard();
+            }
+            Message::Activated(activation) => {
+                match activation {
+                    Activation::Setup if self.oobe_state.is_none() => self.rerun_setup(),
+                    Activation::Ask(question) if self.oobe_state.is_none() => {
+                        self.set_input(&question);
+                    }
+                    _ => {}
+                }
+                if std::mem::take(&mut self.hidden) {
+                    return Task::future(background::show_window()).discard();
+                }
+                return Task::future(background::focus_window()).discard();
             }
 
             // -- OOBE wizard messages --
@@ -200,13 +640,15 @@ impl AiosChat {
                 if let Some(oobe) = &mut self.oobe_state {
                     oobe.step = OobeStep::SelectProvider;
                 }
+                self.save_oobe_progress();
             }
             Message::OobeSelectProvider(provider) => {
                 if let Some(oobe) = &mut self.oobe_state {
                     oobe.selected_provider = Some(provider);
                     if provider == ProviderType::Ollama {
                         oobe.step = OobeStep::OllamaSetup;
-                        oobe.ollama_status = Some("Starting Ollama service...".to_owned());
+                        oobe.ollama_status = Some(t!("oobe-ollama-starting"));
+                        self.save_oobe_progress();
                         return Task::perform(
                             async {
                                 // Ollama is pre-installed in the ISO. Just check it exists and start the service.
@@ -229,6 +671,7 @@ impl AiosChat {
                     }
                     oobe.step = OobeStep::EnterApiKey;
                 }
+                self.save_oobe_progress();
             }
             Message::OobeApiKeyChanged(value) => {
                 if let Some(oobe) = &mut self.oobe_state {
@@ -242,17 +685,23 @@ impl AiosChat {
                 if let Some(oobe) = &mut self.oobe_state {
                     if installed {
                         oobe.step = OobeStep::OllamaModelSelect;
-                        oobe.ollama_status = Some("Loading available models...".to_owned());
-                        // Fetch available models from Ollama library
-                        return Task::perform(
-                            async {
-                                fetch_ollama_models().await
-                            },
-                            Message::OobeOllamaModelsLoaded,
-                        );
+                        oobe.ollama_status = Some(t!("oobe-ollama-loading-models"));
+                        self.save_oobe_progress();
+                        // Fetch available models from Ollama library, and
+                        // size up the machine to recommend one.
+                        return Task::batch([
+                            Task::perform(
+                                async {
+                                    fetch_ollama_models().await
+                                },
+                                Message::OobeOllamaModelsLoaded,
+                            ),
+                            Task::perform(probe_memory(), Message::OobeHardwareProbed),
+                        ]);
                     } else {
-                        oobe.ollama_status = Some("Ollama not found. You can install it from Settings.".to_owned());
+                        oobe.ollama_status = Some(t!("oobe-ollama-not-found"));
                         oobe.step = OobeStep::OllamaModelSelect;
+                        self.save_oobe_progress();
                     }
                 }
             }
@@ -262,6 +711,11 @@ impl AiosChat {
                     oobe.ollama_status = None;
                 }
             }
+            Message::OobeHardwareProbed(memory) => {
+                if let Some(oobe) = &mut self.oobe_state {
+                    oobe.memory = memory;
+                }
+            }
             Message::OobeOllamaCustomModelChanged(value) => {
                 if let Some(oobe) = &mut self.oobe_state {
                     oobe.custom_model_input = value;
@@ -270,9 +724,10 @@ impl AiosChat {
             Message::OobeOllamaSelectModel(model) => {
                 if let Some(oobe) = &mut self.oobe_state {
                     oobe.ollama_model = Some(model.clone());
-                    oobe.ollama_status = Some(format!("Pulling {model}..."));
+                    oobe.ollama_status = Some(t!("oobe-ollama-pulling", model = model.clone()));
                     oobe.pulling = true;
                     oobe.pull_progress = 0.0;
+                    self.save_oobe_progress();
                     return Task::perform(
                         async move {
                             let output = tokio::task::spawn_blocking(move || {
@@ -304,10 +759,10 @@ impl AiosChat {
                     oobe.pull_progress = 0.0;
                     match result {
                         Ok(()) => {
-                            oobe.ollama_status = Some("Model ready!".to_owned());
+                            oobe.ollama_status = Some(t!("oobe-ollama-model-ready"));
                         }
                         Err(e) => {
-                            oobe.ollama_status = Some(format!("Pull failed: {e}. You can try again from Settings."));
+                            oobe.ollama_status = Some(t!("oobe-ollama-pull-failed", error = e));
                         }
                     }
                     return self.save_oobe_config();
@@ -325,16 +780,25 @@ impl AiosChat {
                         _ => {}
                     }
                 }
+                self.save_oobe_progress();
             }
             Message::OobeSkip => {
+                if config_path().exists() {
+                    // Re-run on a configured system: keep the current
+                    // settings and go back to the chat.
+                    oobe_progress::clear();
+                    self.oobe_state = None;
+                    return Task::none();
+                }
                 // Save a default config with an empty API key (echo mode).
                 return self.save_default_config();
             }
             Message::OobeComplete => {
+                oobe_progress::clear();
                 self.oobe_state = None;
                 self.messages.push(DisplayMessage::assistant(
                     Uuid::new_v4(),
-                    "Привет! Чем могу помочь?".to_owned(),
+                    t!("chat-welcome"),
                     Utc::now(),
                 ));
             }
@@ -344,6 +808,7 @@ impl AiosChat {
                         if let Some(oobe) = &mut self.oobe_state {
                             oobe.step = OobeStep::Complete;
                         }
+                        self.save_oobe_progress();
                         // Hot-reload agent config via IPC instead of restarting
                         return Task::perform(
                             async { notify_agent_reload().await },
@@ -367,9 +832,62 @@ impl AiosChat {
         Task::none()
     }
 
-    /// Declarative subscription: runs the IPC background worker when alive.
+    /// Map a window event to a message for the chat shown in that window.
+    ///
+    /// Used by [`ChatWindows`](crate::windows::ChatWindows), which routes
+    /// the result by window id.
+    pub fn window_event(event: iced::Event) -> Option<Message> {
+        match event {
+            iced::Event::Window(iced::window::Event::FileDropped(path)) => {
+                Some(Message::FileDropped(path))
+            }
+            iced::Event::Window(iced::window::Event::CloseRequested) => Some(Message::CloseWindow),
+            iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                match key.as_ref() {
+                    keyboard::Key::Character("f") if modifiers.command() => {
+                        Some(Message::OpenSearch)
+                    }
+                    keyboard::Key::Character("n" | "N")
+                        if modifiers.command() && modifiers.shift() =>
+                    {
+                        Some(Message::PopOut)
+                    }
+                    keyboard::Key::Character("n") if modifiers.command() => {
+                        Some(Message::NewConversation)
+                    }
+                    keyboard::Key::Character("l") if modifiers.command() => {
+                        Some(Message::FocusInput)
+                    }
+                    keyboard::Key::Named(keyboard::key::Named::Escape) => Some(Message::Escape),
+                    keyboard::Key::Named(keyboard::key::Named::Tab) if modifiers.shift() => {
+                        Some(Message::FocusPrevious)
+                    }
+                    keyboard::Key::Named(keyboard::key::Named::Tab) => {
+                        Some(Message::AutocompleteCommand)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Per-window subscriptions: this chat's own IPC connection, plus the
+    /// reconnect countdown, voice recorder and pull animation when active.
     pub fn subscription(&self) -> Subscription<Message> {
-        let ipc = Subscription::run(ipc_client::ipc_worker).map(Message::Ipc);
+        let mut ipc = Subscription::run(ipc_client::ipc_worker).map(Message::Ipc);
+
+        if self.retry_at.is_some() {
+            let countdown = iced::time::every(std::time::Duration::from_secs(1))
+                .map(|_| Message::ConnectionTick);
+            ipc = Subscription::batch([ipc, countdown]);
+        }
+
+        if let Some(session) = self.voice.as_ref().filter(|v| v.recording) {
+            let recorder =
+                Subscription::run_with(session.id, voice::recorder).map(Message::VoiceCaptured);
+            ipc = Subscription::batch([ipc, recorder]);
+        }
 
         // Animate progress bar while pulling a model
         let is_pulling = self
@@ -398,21 +916,126 @@ impl AiosChat {
 
     // -- Accessors used by views --
 
-    pub fn messages(&self) -> &[DisplayMessage] {
-        &self.messages
-    }
-
-    pub fn input_text(&self) -> &str {
-        &self.input_text
+    pub fn input(&self) -> &text_editor::Content {
+        &self.input
     }
 
     pub fn connection_status(&self) -> ConnectionStatus {
         self.connection_status
     }
 
+    /// Failure reason and seconds until the next retry, while the agent is
+    /// unreachable. The countdown is `None` while an attempt is running.
+    pub fn connection_problem(&self) -> Option<(&str, Option<u64>)> {
+        if self.connection_status == ConnectionStatus::Connected {
+            return None;
+        }
+        let reason = self.connection_error.as_deref()?;
+        let retry_in = self.retry_at.map(|at| {
+            at.saturating_duration_since(Instant::now())
+                .as_secs_f32()
+                .ceil() as u64
+        });
+        Some((reason, retry_in))
+    }
+
+    /// Tokens and cost spent in the current conversation.
+    pub fn conversation_usage(&self) -> ConversationUsage {
+        self.usage
+    }
+
+    /// The image opened from a tool card, if any.
+    pub fn image_preview(&self) -> Option<&image::Handle> {
+        self.image_preview.as_ref()
+    }
+
+    /// The active search query, if the search bar is open.
+    pub fn search_query(&self) -> Option<&str> {
+        self.search_query.as_deref()
+    }
+
+    pub fn message_list_id(&self) -> &widget::Id {
+        &self.message_list_id
+    }
+
+    pub fn search_input_id(&self) -> &widget::Id {
+        &self.search_input_id
+    }
+
+    pub fn input_id(&self) -> &widget::Id {
+        &self.input_id
+    }
+
+    /// The first user message, used to title pop-out windows.
+    pub fn topic(&self) -> Option<&str> {
+        self.messages
+            .iter()
+            .find(|m| m.role == MessageRole::User)
+            .map(|m| m.text.as_str())
+    }
+
+    /// Messages to display: all of them, or only those matching the active
+    /// search query.
+    pub fn visible_messages(&self) -> Vec<&DisplayMessage> {
+        match self.search_query.as_deref().map(str::trim) {
+            Some(query) if !query.is_empty() => {
+                let query = query.to_lowercase();
+                self.messages.iter().filter(|m| m.matches(&query)).collect()
+            }
+            _ => self.messages.iter().collect(),
+        }
+    }
+
+    pub fn attachments(&self) -> &[Attachment] {
+        &self.attachments
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// Id of the assistant reply that can be regenerated: the latest
+    /// assistant message, provided no user message follows it.
+    pub fn regeneratable_message_id(&self) -> Option<Uuid> {
+        if self.writer.is_none() {
+            return None;
+        }
+        self.messages
+            .iter()
+            .rev()
+            .take_while(|m| m.role != MessageRole::User)
+            .find(|m| m.role == MessageRole::Assistant)
+            .map(|m| m.id)
+    }
+
+    /// Whether to show the floating "New messages" button.
+    pub fn has_unseen_messages(&self) -> bool {
+        self.has_unseen_messages
+    }
+
+    /// Slash commands and macros matching the partially typed command in
+    /// the input.
+    pub fn command_suggestions(&self) -> Vec<commands::Suggestion> {
+        commands::suggestions(&self.input.text(), &self.macros)
+    }
+
+    /// State of the voice input button.
+    pub fn voice_status(&self) -> VoiceStatus {
+        match &self.voice {
+            None => VoiceStatus::Idle,
+            Some(v) if v.recording => VoiceStatus::Recording,
+            Some(_) => VoiceStatus::Transcribing,
+        }
+    }
+
+    pub fn can_record(&self) -> bool {
+        self.writer.is_some()
+    }
+
     pub fn can_send(&self) -> bool {
-        !self.input_text.trim().is_empty()
-            && self.connection_status == ConnectionStatus::Connected
+        // Messages written offline are queued; slash commands are validated
+        // on send.
+        !self.input.text().trim().is_empty()
     }
 
     /// Returns the OOBE state if the wizard is active.
@@ -426,61 +1049,465 @@ impl AiosChat {
     /// Handle `Message::SendMessage`: validate, enqueue user message, and
     /// fire an async IPC send.
     fn handle_send(&mut self) -> Task<Message> {
-        let text = self.input_text.trim().to_owned();
+        let text = self.input.text().trim().to_owned();
         if text.is_empty() {
             return Task::none();
         }
 
-        let Some(writer) = self.writer.clone() else {
-            // Not connected -- do nothing (button should be disabled).
-            tracing::warn!("SendMessage while disconnected; ignoring");
-            return Task::none();
+        if let Some(command) = commands::parse(&text, &self.macros) {
+            self.set_input("");
+            return match command {
+                Ok(command) => self.handle_command(command),
+                Err(reason) => {
+                    self.messages.push(DisplayMessage::assistant(
+                        Uuid::new_v4(),
+                        format!("*{reason}*"),
+                        Utc::now(),
+                    ));
+                    Task::none()
+                }
+            };
+        }
+
+        let conversation_id = self.conversation_id;
+        let editing = self.editing.take();
+        let message_id = editing.unwrap_or_else(Uuid::new_v4);
+        let payload = if editing.is_some() {
+            // Drop the edited message and everything after it locally; the
+            // agent truncates its history the same way.
+            if let Some(index) = self.messages.iter().position(|m| m.id == message_id) {
+                self.messages.truncate(index);
+            }
+            self.streaming_message = None;
+            IpcPayload::EditMessage {
+                conversation_id,
+                message_id,
+                message: text.clone(),
+            }
+        } else {
+            IpcPayload::ChatRequest {
+                message: text.clone(),
+                conversation_id,
+                message_id: Some(message_id),
+                attachments: std::mem::take(&mut self.attachments),
+            }
         };
 
         // Add the user message to the display list.
-        self.messages
-            .push(DisplayMessage::user(Uuid::new_v4(), text.clone(), Utc::now()));
+        let mut display = DisplayMessage::user(message_id, text, Utc::now());
+        if let IpcPayload::ChatRequest { attachments, .. } = &payload {
+            display.attachment_names = attachments.iter().map(|a| a.name.clone()).collect();
+        }
+        display.delivery = Some(Delivery::Sending);
+        self.messages.push(display);
 
         // Clear input.
-        self.input_text.clear();
+        self.set_input("");
+        self.awaiting_reply = true;
+
+        // Everything goes through the outbox, so messages written while
+        // offline or during a flush keep their order.
+        if self.writer.is_none() {
+            tracing::info!(%message_id, "Queueing message until connected");
+        }
+        self.outbox.push(OutboxEntry {
+            message_id,
+            payload,
+        });
+        self.flush_outbox()
+    }
+
+    fn set_delivery(&mut self, message_id: Uuid, delivery: Delivery) {
+        if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
+            msg.delivery = Some(delivery);
+        }
+    }
+
+    /// The agent has `message_id`: it needs no resending.
+    fn confirm(&mut self, message_id: Uuid) {
+        self.unconfirmed.retain(|e| e.message_id != message_id);
+        self.set_delivery(message_id, Delivery::Received);
+    }
+
+    /// Send queued messages in order, if connected and no flush is running.
+    fn flush_outbox(&mut self) -> Task<Message> {
+        let Some(writer) = self.writer.clone() else {
+            return Task::none();
+        };
+        if self.flushing || self.outbox.is_empty() {
+            return Task::none();
+        }
+        self.flushing = true;
+        let entries = std::mem::take(&mut self.outbox);
+        tracing::info!(count = entries.len(), "Flushing outbox");
+        Task::perform(send_outbox(writer, entries), |(sent, unsent, error)| {
+            Message::OutboxFlushed {
+                sent,
+                unsent,
+                error,
+            }
+        })
+    }
 
-        // Build IPC message.
+    /// Mark written messages as sent until the agent confirms them, and
+    /// requeue the rest.
+    fn handle_outbox_flushed(
+        &mut self,
+        sent: Vec<OutboxEntry>,
+        mut unsent: Vec<OutboxEntry>,
+        error: Option<String>,
+    ) -> Task<Message> {
+        self.flushing = false;
+        for entry in sent {
+            // The receipt can come in before the flush reports back.
+            let received = self
+                .messages
+                .iter()
+                .any(|m| m.id == entry.message_id && m.delivery == Some(Delivery::Received));
+            if !received {
+                self.set_delivery(entry.message_id, Delivery::Sent);
+                self.unconfirmed.push(entry);
+            }
+        }
+        // Anything queued during the flush goes after the failed entries.
+        unsent.append(&mut self.outbox);
+        self.outbox = unsent;
+
+        match error {
+            Some(reason) => {
+                // The connection dropped mid-flush; the worker will emit
+                // `Disconnected` and we flush again on reconnect.
+                tracing::warn!("Outbox flush failed: {reason}");
+                Task::none()
+            }
+            None => self.flush_outbox(),
+        }
+    }
+
+    /// Cheap fingerprint of the message list, used to detect new content
+    /// (appended messages or a growing stream).
+    fn content_revision(&self) -> (usize, usize) {
+        let streamed = self.streaming_message.as_ref().map_or(0, |s| s.text.len());
+        (self.messages.len(), streamed)
+    }
+
+    /// Keep the view pinned to the bottom if the user is following along,
+    /// otherwise flag that there is something new below.
+    fn follow_new_content(&mut self) -> Task<Message> {
+        if self.stick_to_bottom {
+            iced::widget::operation::snap_to_end(self.message_list_id.clone())
+        } else {
+            self.has_unseen_messages = true;
+            Task::none()
+        }
+    }
+
+    /// Scroll to the newest message and resume following.
+    fn jump_to_latest(&mut self) -> Task<Message> {
+        self.stick_to_bottom = true;
+        self.has_unseen_messages = false;
+        iced::widget::operation::snap_to_end(self.message_list_id.clone())
+    }
+
+    /// Execute a parsed slash command.
+    ///
+    /// `/new`, `/export`, `/setup` and `/quit` are handled locally, and macros
+    /// are sent as chat messages; the others map to IPC payloads and need a
+    /// connection.
+    fn handle_command(&mut self, command: SlashCommand) -> Task<Message> {
         let conversation_id = self.conversation_id;
-        let ipc_msg = IpcMessage {
-            id: Uuid::new_v4(),
-            payload: IpcPayload::ChatRequest {
-                message: text,
+        let payload = match command {
+            SlashCommand::New => {
+                self.start_new_conversation();
+                return Task::none();
+            }
+            SlashCommand::Export => {
+                let markdown = self.transcript_markdown();
+                return Task::perform(export_transcript(markdown), Message::ExportFinished);
+            }
+            SlashCommand::Setup => {
+                self.rerun_setup();
+                return Task::none();
+            }
+            SlashCommand::Quit => return iced::exit(),
+            SlashCommand::Macro { name, details } => {
+                let instructions = self
+                    .macros
+                    .get(&name)
+                    .and_then(|config| config.instructions(&name, &details));
+                let Some(instructions) = instructions else {
+                    self.messages.push(DisplayMessage::assistant(
+                        Uuid::new_v4(),
+                        format!("*{}*", t!("command-macro-empty", name = name)),
+                        Utc::now(),
+                    ));
+                    return Task::none();
+                };
+                // Sent like a typed message, so the steps show in the chat
+                // and the message can be edited and sent again.
+                self.set_input(&instructions);
+                return self.handle_send();
+            }
+            SlashCommand::Clear => {
+                self.messages.clear();
+                self.unconfirmed.clear();
+                self.usage = ConversationUsage::default();
+                self.streaming_message = None;
+                self.editing = None;
+                IpcPayload::ClearConversation { conversation_id }
+            }
+            SlashCommand::Model(model) => IpcPayload::SetModel { model },
+            SlashCommand::Models => IpcPayload::ListModels,
+            SlashCommand::Pause => IpcPayload::SetPaused { paused: true },
+            SlashCommand::Resume => IpcPayload::SetPaused { paused: false },
+            SlashCommand::System(prompt) => IpcPayload::SetSystemPrompt {
                 conversation_id,
+                prompt,
             },
         };
 
-        // Fire and forget via async task.
-        Task::perform(
-            async move {
-                let mut w = writer.lock().await;
-                w.send(&ipc_msg)
-                    .await
-                    .map_err(|e| format!("{e}"))
+        let Some(writer) = self.writer.clone() else {
+            self.messages.push(DisplayMessage::assistant(
+                Uuid::new_v4(),
+                t!("chat-not-connected"),
+                Utc::now(),
+            ));
+            return Task::none();
+        };
+
+        send_payload(writer, payload)
+    }
+
+    /// Escape closes the image preview, then the search bar, then the
+    /// current edit; with nothing open it stops the reply in progress.
+    fn handle_escape(&mut self) -> Task<Message> {
+        if self.image_preview.take().is_some() {
+            return Task::none();
+        }
+        if self.search_query.take().is_some() {
+            return widget::operation::focus(self.input_id.clone());
+        }
+        if self.editing.is_some() {
+            return self.update(Message::CancelEdit);
+        }
+        if !self.awaiting_reply {
+            return Task::none();
+        }
+        self.finalize_streaming();
+        let Some(writer) = self.writer.clone() else {
+            return Task::none();
+        };
+        send_payload(
+            writer,
+            IpcPayload::CancelRequest {
+                conversation_id: self.conversation_id,
             },
-            Message::SendCompleted,
         )
     }
 
+    /// Replace the input contents and put the cursor at the end.
+    fn set_input(&mut self, text: &str) {
+        self.input = text_editor::Content::with_text(text);
+        self.input
+            .perform(text_editor::Action::Move(text_editor::Motion::DocumentEnd));
+    }
+
+    /// Switch to a fresh conversation id and clear the local view.
+    fn start_new_conversation(&mut self) {
+        self.conversation_id = Uuid::new_v4();
+        self.awaiting_reply = false;
+        self.usage = ConversationUsage::default();
+        self.messages.clear();
+        self.unconfirmed.clear();
+        self.streaming_message = None;
+        self.editing = None;
+        self.attachments.clear();
+    }
+
+    /// Render the visible conversation as a Markdown transcript.
+    fn transcript_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", t!("export-title"));
+        for msg in &self.messages {
+            let heading = match msg.role {
+                MessageRole::User => t!("export-role-user"),
+                MessageRole::Assistant => t!("export-role-assistant"),
+                MessageRole::Plan => t!("export-role-plan"),
+                MessageRole::ToolCall | MessageRole::ToolResult => {
+                    t!("export-role-tool", name = msg.tool_name.as_deref().unwrap_or("tool"))
+                }
+            };
+            let body = msg.tool_args.as_deref().unwrap_or(&msg.text);
+            out.push_str(&format!(
+                "## {heading} ({})\n\n{body}\n\n",
+                msg.timestamp.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        out
+    }
+
+    /// Handle `Message::RegenerateLast`: drop the reply shown after the last
+    /// user message and ask the agent for a new one.
+    fn handle_regenerate(&mut self) -> Task<Message> {
+        let Some(writer) = self.writer.clone() else {
+            return Task::none();
+        };
+        let Some(last_user) = self
+            .messages
+            .iter()
+            .rposition(|m| m.role == MessageRole::User)
+        else {
+            return Task::none();
+        };
+
+        self.messages.truncate(last_user + 1);
+        self.streaming_message = None;
+        self.awaiting_reply = true;
+
+        send_payload(
+            writer,
+            IpcPayload::RegenerateResponse {
+                conversation_id: self.conversation_id,
+            },
+        )
+    }
+
+    /// Handle `Message::ToggleVoice`.
+    ///
+    /// The first press opens a voice session on the agent and starts the
+    /// recorder subscription; the second stops recording and asks for the
+    /// final transcript.
+    fn toggle_voice(&mut self) -> Task<Message> {
+        let Some(writer) = self.writer.clone() else {
+            return Task::none();
+        };
+
+        if let Some(session) = &mut self.voice {
+            if !session.recording {
+                // Already waiting for the final transcript.
+                return Task::none();
+            }
+            session.recording = false;
+            return send_payload(
+                writer,
+                IpcPayload::VoiceStop {
+                    session_id: session.id,
+                },
+            );
+        }
+
+        let id = Uuid::new_v4();
+        let mut base_text = self.input.text();
+        if !base_text.is_empty() && !base_text.ends_with(' ') {
+            base_text.push(' ');
+        }
+        self.voice = Some(VoiceSession {
+            id,
+            base_text,
+            recording: true,
+        });
+        send_payload(writer, IpcPayload::VoiceStart { session_id: id })
+    }
+
+    /// Forward recorded audio to the agent, or end the session on failure.
+    fn handle_voice_captured(&mut self, event: RecorderEvent) -> Task<Message> {
+        let Some(session) = self.voice.as_mut().filter(|v| v.recording) else {
+            return Task::none();
+        };
+        let Some(writer) = self.writer.clone() else {
+            return Task::none();
+        };
+
+        match event {
+            RecorderEvent::Chunk(bytes) => send_payload(
+                writer,
+                IpcPayload::VoiceAudio {
+                    session_id: session.id,
+                    pcm: base64::engine::general_purpose::STANDARD.encode(bytes),
+                },
+            ),
+            RecorderEvent::Failed(reason) => {
+                // Transcribe whatever was captured before the failure.
+                session.recording = false;
+                let session_id = session.id;
+                self.messages.push(DisplayMessage::assistant(
+                    Uuid::new_v4(),
+                    t!("chat-microphone-error", reason = reason),
+                    Utc::now(),
+                ));
+                send_payload(writer, IpcPayload::VoiceStop { session_id })
+            }
+        }
+    }
+
     /// Handle an event coming from the IPC background subscription.
     fn handle_ipc_event(&mut self, event: IpcEvent) -> Task<Message> {
         match event {
+            IpcEvent::Connecting => {
+                self.connection_status = ConnectionStatus::Connecting;
+                self.retry_at = None;
+            }
             IpcEvent::Connected(writer) => {
                 tracing::info!("IPC connected");
                 self.connection_status = ConnectionStatus::Connected;
+                self.connection_error = None;
+                self.retry_at = None;
                 self.writer = Some(writer);
+                return self.flush_outbox();
             }
-            IpcEvent::Disconnected(reason) => {
+            IpcEvent::Disconnected { reason, retry_in } => {
                 tracing::warn!("IPC disconnected: {reason}");
+                // Messages the agent never confirmed may not have reached
+                // it; the user decides whether to send them again.
+                let lost: Vec<Uuid> = self.unconfirmed.iter().map(|e| e.message_id).collect();
+                for id in lost {
+                    self.set_delivery(id, Delivery::Failed(reason.clone()));
+                }
                 self.connection_status = ConnectionStatus::Disconnected;
+                self.connection_error = Some(reason);
+                self.retry_at = Some(Instant::now() + retry_in);
                 self.writer = None;
+                self.voice = None;
+                // Whatever was in flight is lost; queued messages still
+                // expect a reply once sent.
+                self.awaiting_reply = !self.outbox.is_empty();
+            }
+            IpcEvent::Received {
+                conversation_id,
+                message_id,
+            } => {
+                if conversation_id == self.conversation_id {
+                    self.confirm(message_id);
+                }
             }
-            IpcEvent::ChatResponse(chat_msg) => {
+            IpcEvent::ChatResponse(chat_msg, usage, reasoning) => {
+                if matches!(chat_msg.content, MessageContent::Text { .. }) {
+                    self.awaiting_reply = false;
+                }
+                // Agents without receipts confirm a message by answering.
+                let answered: Vec<Uuid> = self
+                    .unconfirmed
+                    .iter()
+                    .map(|e| e.message_id)
+                    .filter(|id| {
+                        self.messages
+                            .iter()
+                            .any(|m| m.id == *id && m.delivery == Some(Delivery::Sent))
+                    })
+                    .collect();
+                for id in answered {
+                    self.confirm(id);
+                }
                 self.append_chat_response(&chat_msg);
+                if let Some(report) = &usage {
+                    self.usage.add(report);
+                }
+                if let Some(msg) = self.messages.iter_mut().rev().find(|m| m.id == chat_msg.id) {
+                    msg.usage = usage;
+                    msg.reasoning = reasoning;
+                }
+                if let MessageContent::Text { text } = &chat_msg.content {
+                    return self.notify_if_hidden(text);
+                }
             }
             IpcEvent::StreamChunk {
                 request_id,
@@ -489,11 +1516,101 @@ impl AiosChat {
             } => {
                 self.handle_stream_chunk(request_id, &delta, done);
             }
+            IpcEvent::Notice { success, message } => {
+                let notify = self.notify_if_hidden(&message);
+                let text = if success {
+                    format!("*{message}*")
+                } else {
+                    t!("chat-notice-error", message = message)
+                };
+                self.messages.push(DisplayMessage::assistant(
+                    Uuid::new_v4(),
+                    text,
+                    Utc::now(),
+                ));
+                return notify;
+            }
+            IpcEvent::ToolProgress {
+                conversation_id,
+                call_id,
+                tool,
+                output,
+            } => {
+                if conversation_id != self.conversation_id {
+                    return Task::none();
+                }
+                // The first output opens a card for the call.
+                let card = match self.messages.iter().rposition(|m| {
+                    m.id == call_id && m.role == MessageRole::ToolCall
+                }) {
+                    Some(index) => &mut self.messages[index],
+                    None => {
+                        let mut card =
+                            DisplayMessage::tool_call(call_id, tool, String::new(), Utc::now());
+                        card.tool_args = None;
+                        self.messages.push(card);
+                        self.messages.last_mut().expect("just pushed")
+                    }
+                };
+                card.append_tool_output(&output);
+            }
+            IpcEvent::ToolFinished { call_id, success } => {
+                if let Some(card) = self
+                    .messages
+                    .iter_mut()
+                    .rev()
+                    .find(|m| m.id == call_id && m.role == MessageRole::ToolCall)
+                {
+                    card.set_tool_status(if success {
+                        ToolStatus::Completed
+                    } else {
+                        ToolStatus::Failed
+                    });
+                }
+            }
+            IpcEvent::Models {
+                provider,
+                current,
+                models,
+            } => {
+                self.messages.push(DisplayMessage::assistant(
+                    Uuid::new_v4(),
+                    models_markdown(&provider, &current, &models),
+                    Utc::now(),
+                ));
+            }
+            IpcEvent::Plan {
+                conversation_id,
+                message,
+            } => {
+                if conversation_id == self.conversation_id {
+                    self.append_chat_response(&message);
+                }
+            }
+            IpcEvent::Transcript {
+                session_id,
+                text,
+                is_final,
+            } => {
+                if let Some(session) = self.voice.as_ref().filter(|v| v.id == session_id) {
+                    let text = format!("{}{}", session.base_text, text.trim());
+                    self.set_input(&text);
+                    if is_final {
+                        self.voice = None;
+                    }
+                }
+            }
             IpcEvent::AgentError { message } => {
                 tracing::error!("Agent error: {message}");
+                self.awaiting_reply = false;
+                // A failed final transcription is reported as an error; stop
+                // waiting for it.
+                if self.voice.as_ref().is_some_and(|v| !v.recording) {
+                    self.voice = None;
+                }
                 self.messages.push(DisplayMessage::assistant(
                     Uuid::new_v4(),
-                    format!("*Agent error:* {message}"),
+                    t!("chat-agent-error", message = message),
                     Utc::now(),
                 ));
             }
@@ -501,10 +1618,30 @@ impl AiosChat {
         Task::none()
     }
 
+    /// Replies longer than this are cut short in notifications.
+    const NOTIFY_PREVIEW_CHARS: usize = 200;
+
+    /// While closed to the background, announce `text` with a desktop
+    /// notification whose "Open" action brings the window back.
+    fn notify_if_hidden(&self, text: &str) -> Task<Message> {
+        if !self.hidden {
+            return Task::none();
+        }
+        let preview: String = text.chars().take(Self::NOTIFY_PREVIEW_CHARS).collect();
+        Task::future(background::notify_reply(preview)).then(|open| {
+            if open {
+                Task::done(Message::Activated(Activation::Show))
+            } else {
+                Task::none()
+            }
+        })
+    }
+
     /// Append a complete `ChatResponse` as one or more `DisplayMessage`s.
     ///
-    /// Text content becomes a single assistant message. Tool use and tool
-    /// result payloads are expanded into individual tool cards.
+    /// Text content becomes a single assistant message, and a plan a muted
+    /// note. Tool use and tool result payloads are expanded into individual
+    /// tool cards.
     fn append_chat_response(&mut self, chat_msg: &ChatMessage) {
         match &chat_msg.content {
             MessageContent::Text { text } => {
@@ -514,6 +1651,13 @@ impl AiosChat {
                     chat_msg.timestamp,
                 ));
             }
+            MessageContent::Plan { text } => {
+                self.messages.push(DisplayMessage::plan(
+                    chat_msg.id,
+                    text.clone(),
+                    chat_msg.timestamp,
+                ));
+            }
             MessageContent::ToolUse { tool_calls } => {
                 for tc in tool_calls {
                     let args_pretty = serde_json::to_string_pretty(&tc.arguments)
@@ -553,13 +1697,18 @@ impl AiosChat {
                         call_msg.set_tool_status(new_status);
                     }
 
-                    self.messages.push(DisplayMessage::tool_result(
+                    let mut display = DisplayMessage::tool_result(
                         tr.call_id,
                         tool_name,
                         tr.output.clone(),
                         tr.is_error,
                         chat_msg.timestamp,
-                    ));
+                    );
+                    display.images = attachments::image_handles(&tr.attachments);
+                    if display.tool_name.as_deref() == Some("system_info") && !tr.is_error {
+                        display.system_report = serde_json::from_str(&tr.output).ok();
+                    }
+                    self.messages.push(display);
                 }
             }
         }
@@ -611,6 +1760,20 @@ impl AiosChat {
 
     // -- OOBE config persistence --
 
+    /// Record the wizard position so an interrupted setup can resume.
+    fn save_oobe_progress(&self) {
+        if let Some(oobe) = &self.oobe_state {
+            oobe_progress::save(&oobe.progress());
+        }
+    }
+
+    /// `/setup`: reopen the wizard from the start.
+    fn rerun_setup(&mut self) {
+        tracing::info!("Re-running setup wizard");
+        self.oobe_state = Some(OobeState::new());
+        self.save_oobe_progress();
+    }
+
     /// Build an `AiosConfig` from current OOBE selections and save it.
     fn save_oobe_config(&self) -> Task<Message> {
         let Some(oobe) = &self.oobe_state else {
@@ -635,6 +1798,7 @@ impl AiosChat {
                 api_key,
                 model,
                 base_url,
+                reasoning: None,
             },
             ..AiosConfig::default()
         };
@@ -649,20 +1813,82 @@ impl AiosChat {
     }
 }
 
-/// Returns the canonical config file path: `~/.config/aios/agent.toml`.
-fn config_path() -> PathBuf {
-    dirs::config_dir()
-        .unwrap_or_else(|| PathBuf::from(".config"))
-        .join("aios")
-        .join("agent.toml")
+/// Send `payload` to the agent over `writer` as a fire-and-forget task.
+fn send_payload(writer: Arc<Mutex<IpcWriter>>, payload: IpcPayload) -> Task<Message> {
+    let ipc_msg = IpcMessage {
+        id: Uuid::new_v4(),
+        payload,
+    };
+    Task::perform(
+        async move {
+            let mut w = writer.lock().await;
+            w.send(&ipc_msg).await.map_err(|e| format!("{e}"))?;
+            log_chat_request(&ipc_msg);
+            Ok(())
+        },
+        Message::SendCompleted,
+    )
+}
+
+/// Log the id of a sent chat turn; the agent logs the turn, and its reply
+/// comes back, under the same `request_id`.
+fn log_chat_request(ipc_msg: &IpcMessage) {
+    if let IpcPayload::ChatRequest {
+        conversation_id, ..
+    }
+    | IpcPayload::EditMessage {
+        conversation_id, ..
+    } = &ipc_msg.payload
+    {
+        tracing::info!(request_id = %ipc_msg.id, %conversation_id, "Chat request sent");
+    }
+}
+
+/// Send `entries` one after another over `writer`, stopping at the first
+/// failure. Returns the entries that were sent, those that were not, and
+/// the failure reason.
+async fn send_outbox(
+    writer: Arc<Mutex<IpcWriter>>,
+    entries: Vec<OutboxEntry>,
+) -> (Vec<OutboxEntry>, Vec<OutboxEntry>, Option<String>) {
+    let mut sent = Vec::with_capacity(entries.len());
+    let mut w = writer.lock().await;
+    let mut entries = entries.into_iter();
+    while let Some(entry) = entries.next() {
+        let ipc_msg = IpcMessage {
+            id: Uuid::new_v4(),
+            payload: entry.payload.clone(),
+        };
+        if let Err(e) = w.send(&ipc_msg).await {
+            let unsent = std::iter::once(entry).chain(entries).collect();
+            return (sent, unsent, Some(e.to_string()));
+        }
+        log_chat_request(&ipc_msg);
+        sent.push(entry);
+    }
+    (sent, Vec::new(), None)
 }
 
 /// Serialize `config` as TOML and write it to [`config_path()`].
 ///
-/// Creates the parent directory if it does not exist.
-async fn write_config(config: AiosConfig) -> Result<(), String> {
+/// Creates the parent directory if it does not exist. When re-running the
+/// wizard on a configured system only the provider section is replaced, so
+/// agent, voice and UI settings survive.
+async fn write_config(mut config: AiosConfig) -> Result<(), String> {
     let path = config_path();
 
+    if let Ok(existing) = tokio::fs::read_to_string(&path).await {
+        match toml::from_str::<AiosConfig>(&existing) {
+            Ok(existing) => {
+                config = AiosConfig {
+                    provider: config.provider,
+                    ..existing
+                };
+            }
+            Err(e) => tracing::warn!("Replacing unreadable config {}: {e}", path.display()),
+        }
+    }
+
     if let Some(parent) = path.parent() {
         tokio::fs::create_dir_all(parent)
             .await
@@ -680,6 +1906,61 @@ async fn write_config(config: AiosConfig) -> Result<(), String> {
     Ok(())
 }
 
+/// The `/model` reply: the provider's models, the current one marked.
+fn models_markdown(provider: &str, current: &str, models: &[ModelInfo]) -> String {
+    if models.is_empty() {
+        return t!("chat-models-empty", provider = provider);
+    }
+    let mut text = format!("{}
+
+", t!("chat-models", provider = provider));
+    for model in models {
+        let mut details: Vec<String> = model.parameters.iter().cloned().collect();
+        details.extend(model.size_bytes.map(system_card::format_size));
+        if model.id == current {
+            details.push(t!("chat-models-current"));
+        }
+        text.push_str(&format!("- `{}`", model.id));
+        if let Some(name) = model.display_name.as_deref().filter(|n| *n != model.id) {
+            text.push_str(&format!(" {name}"));
+        }
+        if !details.is_empty() {
+            text.push_str(&format!(" ({})", details.join(", ")));
+        }
+        text.push('\n');
+    }
+    text.push('\n');
+    text.push_str(&t!("chat-models-hint"));
+    text
+}
+
+/// Write a Markdown transcript to `~/Documents` (or the home directory).
+async fn export_transcript(markdown: String) -> Result<PathBuf, String> {
+    let dir = dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "no home directory".to_owned())?;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+
+    let path = dir.join(format!(
+        "aios-chat-{}.md",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    tokio::fs::write(&path, markdown)
+        .await
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+/// RAM and VRAM of this machine; probing runs `nvidia-smi`.
+async fn probe_memory() -> Option<Memory> {
+    tokio::task::spawn_blocking(Memory::probe)
+        .await
+        .ok()
+        .flatten()
+}
+
 /// Get available Ollama models: locally installed + offline models from Ollama API.
 ///
 /// Strategy:
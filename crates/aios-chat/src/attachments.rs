@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use aios_common::ipc::LengthPrefixedCodec;
+use aios_common::{Attachment, AttachmentContent};
+use base64::Engine as _;
+use iced::widget::image;
+
+/// Largest text file accepted as an attachment (256 KiB).
+const MAX_TEXT_BYTES: u64 = 256 * 1024;
+
+/// Largest image accepted as an attachment (8 MiB). Base64 inflates this by
+/// a third, which keeps the request well under the 16 MiB IPC frame limit.
+const MAX_IMAGE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Most the attachments of one message may take together once encoded
+/// (12 MiB), leaving a quarter of the IPC frame for the rest of the request.
+pub const MAX_TOTAL_BYTES: usize = LengthPrefixedCodec::MAX_MESSAGE_SIZE as usize / 4 * 3;
+
+/// What `attachments` add to a request: their JSON, base64 and escapes
+/// included.
+pub fn encoded_len(attachments: &[Attachment]) -> usize {
+    attachments
+        .iter()
+        .map(|attachment| serde_json::to_vec(attachment).map_or(0, |json| json.len()))
+        .sum()
+}
+
+/// `bytes` in MiB with one decimal, e.g. `4.2`.
+pub fn mib(bytes: usize) -> String {
+    format!("{:.1}", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Read a dropped file and turn it into an [`Attachment`].
+///
+/// Images are detected by extension and base64-encoded; everything else must
+/// be valid UTF-8 text.
+pub async fn load(path: impl AsRef<Path>) -> Result<Attachment, String> {
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| format!("{name}: {e}"))?;
+    if !metadata.is_file() {
+        return Err(format!("{name}: not a regular file"));
+    }
+
+    if let Some(media_type) = image_media_type(path) {
+        if metadata.len() > MAX_IMAGE_BYTES {
+            return Err(format!("{name}: image is larger than 8 MiB"));
+        }
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| format!("{name}: {e}"))?;
+        return Ok(Attachment {
+            name,
+            media_type: media_type.to_owned(),
+            content: AttachmentContent::Image {
+                data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            },
+        });
+    }
+
+    if metadata.len() > MAX_TEXT_BYTES {
+        return Err(format!("{name}: text file is larger than 256 KiB"));
+    }
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("{name}: {e}"))?;
+    let text = String::from_utf8(bytes).map_err(|_| format!("{name}: not a text file"))?;
+
+    Ok(Attachment {
+        name,
+        media_type: "text/plain".to_owned(),
+        content: AttachmentContent::Text { text },
+    })
+}
+
+/// Decode the image attachments of a tool result into displayable handles.
+///
+/// Attachments that are not images or fail to decode are skipped.
+pub fn image_handles(attachments: &[Attachment]) -> Vec<image::Handle> {
+    attachments
+        .iter()
+        .filter_map(|attachment| match &attachment.content {
+            AttachmentContent::Image { data } => {
+                match base64::engine::general_purpose::STANDARD.decode(data) {
+                    Ok(bytes) => Some(image::Handle::from_bytes(bytes)),
+                    Err(e) => {
+                        tracing::warn!(name = %attachment.name, "Invalid image attachment: {e}");
+                        None
+                    }
+                }
+            }
+            AttachmentContent::Text { .. } | AttachmentContent::Path { .. } => None,
+        })
+        .collect()
+}
+
+/// Map an image file extension to its MIME type.
+fn image_media_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
@@ -0,0 +1,185 @@
+//! Background mode: single instance, hide/show and reply notifications.
+//!
+//! Closing the chat window only hides it (sway scratchpad), so the IPC
+//! subscription stays connected. Running `aios-chat` again -- the Super+Enter
+//! binding, a dock click -- finds the running instance through a Unix socket
+//! in the runtime directory, asks it to show itself, and exits.
+//! `aios-chat --ask <question>` (the dock's quick-ask field) additionally
+//! pre-fills the input with the question.
+
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use aios_common::t;
+use futures::SinkExt;
+use futures::channel::mpsc;
+use tokio::io::AsyncBufReadExt;
+
+/// sway criteria matching the main chat window. The title is a regex, and
+/// pop-out windows ("AIOS Chat — ...") must not match.
+const WINDOW_CRITERIA: &str = "[title=\"^AIOS Chat$\"]";
+
+/// Request passed from a new `aios-chat` invocation to the running one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Activation {
+    /// Bring the window back.
+    Show,
+    /// Bring the window back and open the setup wizard (`--setup`).
+    Setup,
+    /// Bring the window back with the question in the input (`--ask`).
+    Ask(String),
+}
+
+impl Activation {
+    /// The activation requested on the command line.
+    pub fn from_args() -> Self {
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--setup" => return Self::Setup,
+                "--ask" => return Self::Ask(args.next().unwrap_or_default()),
+                _ => {}
+            }
+        }
+        Self::Show
+    }
+
+    /// One line on the activation socket.
+    fn to_line(&self) -> String {
+        match self {
+            Self::Show => "show".to_owned(),
+            Self::Setup => "setup".to_owned(),
+            // The protocol is line based.
+            Self::Ask(question) => format!("ask {}", question.replace('\n', " ")),
+        }
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "show" => Some(Self::Show),
+            "setup" => Some(Self::Setup),
+            line => line
+                .strip_prefix("ask ")
+                .map(|question| Self::Ask(question.to_owned())),
+        }
+    }
+}
+
+fn socket_path() -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("aios-chat.sock")
+}
+
+/// Hand `activation` to an already running instance.
+///
+/// Returns `false` if none is listening, in which case the caller starts
+/// normally.
+pub fn forward_to_running(activation: Activation) -> bool {
+    let Ok(mut stream) = UnixStream::connect(socket_path()) else {
+        return false;
+    };
+    match writeln!(stream, "{}", activation.to_line()) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Running instance did not accept activation: {e}");
+            false
+        }
+    }
+}
+
+/// Accept activations from later `aios-chat` invocations.
+pub fn listen() -> impl futures::Stream<Item = Activation> {
+    iced::stream::channel(4, async move |mut output: mpsc::Sender<Activation>| {
+        let path = socket_path();
+        // A socket left over from a crashed instance; `forward_to_running`
+        // already failed to connect to it.
+        let _ = std::fs::remove_file(&path);
+        let listener = match tokio::net::UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!("Cannot listen on {}: {e}", path.display());
+                return;
+            }
+        };
+
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let mut line = String::new();
+            let mut reader = tokio::io::BufReader::new(stream);
+            if reader.read_line(&mut line).await.is_err() {
+                continue;
+            }
+            let Some(activation) = Activation::parse(&line) else {
+                tracing::warn!(request = line.trim(), "Unknown activation request");
+                continue;
+            };
+            if output.send(activation).await.is_err() {
+                return;
+            }
+        }
+    })
+}
+
+/// Move the chat window to the sway scratchpad.
+pub async fn hide_window() {
+    swaymsg(&format!("{WINDOW_CRITERIA} move scratchpad")).await;
+}
+
+/// Bring the chat window back from the scratchpad, tiled and focused.
+pub async fn show_window() {
+    swaymsg(&format!(
+        "{WINDOW_CRITERIA} scratchpad show, floating disable, focus"
+    ))
+    .await;
+}
+
+/// Focus the chat window when it is already visible.
+pub async fn focus_window() {
+    swaymsg(&format!("{WINDOW_CRITERIA} focus")).await;
+}
+
+async fn swaymsg(command: &str) {
+    let result = tokio::process::Command::new("swaymsg")
+        .arg(command)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => tracing::warn!(%status, command, "swaymsg failed"),
+        Err(e) => tracing::warn!("Failed to run swaymsg: {e}"),
+    }
+}
+
+/// Show a notification for a reply that arrived while hidden. Returns
+/// `true` if the user clicked "Open".
+pub async fn notify_reply(preview: String) -> bool {
+    let output = tokio::process::Command::new("notify-send")
+        .arg("--app-name=AIOS Chat")
+        .arg("--wait")
+        .arg(format!("--action=open={}", t!("notify-chat-open")))
+        .arg(t!("notify-chat-reply"))
+        .arg(preview)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await;
+
+    match output {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).trim() == "open",
+        Ok(o) => {
+            tracing::warn!(status = %o.status, "notify-send exited with failure");
+            false
+        }
+        Err(e) => {
+            tracing::warn!("Failed to run notify-send: {e}");
+            false
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use aios_common::{MacroConfig, t};
+
+/// A slash command typed into the chat input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlashCommand {
+    /// `/model <name>` -- switch the agent's model for this session.
+    Model(String),
+    /// `/model` -- list the models the agent's provider offers.
+    Models,
+    /// `/new` -- start a fresh conversation.
+    New,
+    /// `/clear` -- clear the current conversation on both sides.
+    Clear,
+    /// `/system [prompt]` -- set (or, with no text, reset) the system prompt.
+    System(Option<String>),
+    /// `/export` -- save the conversation as Markdown.
+    Export,
+    /// `/pause` -- refuse all tool calls until `/resume`.
+    Pause,
+    /// `/resume` -- allow tool calls again after `/pause`.
+    Resume,
+    /// `/setup` -- re-run the first-boot setup wizard.
+    Setup,
+    /// `/quit` -- exit instead of closing to the background.
+    Quit,
+    /// `/<macro> [details]` -- run a macro from `[macros]`; built-in
+    /// commands win over a macro of the same name.
+    Macro { name: String, details: String },
+}
+
+/// An entry of the autocompletion list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub name: String,
+    pub usage: String,
+    pub help: String,
+}
+
+/// Static description of a slash command for the autocompletion list.
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub usage: &'static str,
+    /// Fluent message id of the one-line description.
+    pub help: &'static str,
+}
+
+/// All supported slash commands, in the order they are suggested.
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "model",
+        usage: "/model [name]",
+        help: "command-model-help",
+    },
+    CommandSpec {
+        name: "new",
+        usage: "/new",
+        help: "command-new-help",
+    },
+    CommandSpec {
+        name: "clear",
+        usage: "/clear",
+        help: "command-clear-help",
+    },
+    CommandSpec {
+        name: "system",
+        usage: "/system <prompt>",
+        help: "command-system-help",
+    },
+    CommandSpec {
+        name: "export",
+        usage: "/export",
+        help: "command-export-help",
+    },
+    CommandSpec {
+        name: "pause",
+        usage: "/pause",
+        help: "command-pause-help",
+    },
+    CommandSpec {
+        name: "resume",
+        usage: "/resume",
+        help: "command-resume-help",
+    },
+    CommandSpec {
+        name: "setup",
+        usage: "/setup",
+        help: "command-setup-help",
+    },
+    CommandSpec {
+        name: "quit",
+        usage: "/quit",
+        help: "command-quit-help",
+    },
+];
+
+/// Parse `input` as a slash command, or a run of one of `macros`.
+///
+/// Returns `None` if the input does not start with `/`, and `Some(Err)` for
+/// unknown commands or missing arguments.
+pub fn parse(
+    input: &str,
+    macros: &BTreeMap<String, MacroConfig>,
+) -> Option<Result<SlashCommand, String>> {
+    let rest = input.trim().strip_prefix('/')?;
+    let (name, arg) = match rest.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name, arg.trim()),
+        None => (rest, ""),
+    };
+
+    let command = match name {
+        "model" if arg.is_empty() => Ok(SlashCommand::Models),
+        "model" => Ok(SlashCommand::Model(arg.to_owned())),
+        "new" => Ok(SlashCommand::New),
+        "clear" => Ok(SlashCommand::Clear),
+        "system" if arg.is_empty() => Ok(SlashCommand::System(None)),
+        "system" => Ok(SlashCommand::System(Some(arg.to_owned()))),
+        "export" => Ok(SlashCommand::Export),
+        "pause" => Ok(SlashCommand::Pause),
+        "resume" => Ok(SlashCommand::Resume),
+        "setup" => Ok(SlashCommand::Setup),
+        "quit" => Ok(SlashCommand::Quit),
+        other if macros.contains_key(other) => Ok(SlashCommand::Macro {
+            name: other.to_owned(),
+            details: arg.to_owned(),
+        }),
+        other => Err(t!("command-unknown", name = other)),
+    };
+    Some(command)
+}
+
+/// Commands and `macros` whose name starts with the partially typed
+/// command in `input`, built-in commands first.
+///
+/// Suggestions are only offered while the user is still typing the command
+/// name (before the first space).
+pub fn suggestions(input: &str, macros: &BTreeMap<String, MacroConfig>) -> Vec<Suggestion> {
+    let Some(prefix) = input.strip_prefix('/') else {
+        return Vec::new();
+    };
+    if prefix.contains(char::is_whitespace) {
+        return Vec::new();
+    }
+    let commands = COMMANDS
+        .iter()
+        .filter(|c| c.name.starts_with(prefix))
+        .map(|c| Suggestion {
+            name: c.name.to_owned(),
+            usage: c.usage.to_owned(),
+            help: t!(c.help),
+        });
+    let macros = macros
+        .iter()
+        .filter(|(name, _)| name.starts_with(prefix))
+        .filter(|(name, _)| !COMMANDS.iter().any(|c| c.name == name.as_str()))
+        .map(|(name, config)| Suggestion {
+            name: name.clone(),
+            usage: format!("/{name}"),
+            help: if config.description.is_empty() {
+                t!("command-macro-help")
+            } else {
+                config.description.clone()
+            },
+        });
+    commands.chain(macros).collect()
+}
@@ -1,10 +1,21 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use aios_common::ipc::IpcWriter;
-use aios_common::{ChatMessage, IpcPayload};
+use aios_common::{ChatMessage, IpcPayload, ModelInfo, UsageReport};
 use futures::channel::mpsc;
 use futures::SinkExt;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
+
+/// Delay before the first reconnect attempt; doubles with every consecutive
+/// failure up to [`MAX_RETRY_DELAY`].
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Upper bound for the reconnect backoff.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Wakes the worker out of its retry delay; see [`reconnect_now`].
+static RECONNECT: Notify = Notify::const_new();
 
 /// Socket path resolution: `AIOS_SOCKET` env var or platform default.
 pub fn socket_path() -> String {
@@ -20,12 +31,25 @@ pub fn socket_path() -> String {
 /// Events produced by the IPC background worker and forwarded to the app.
 #[derive(Clone)]
 pub enum IpcEvent {
+    /// A connection attempt is starting.
+    Connecting,
     /// Connection established; carries a shared writer handle.
     Connected(Arc<Mutex<IpcWriter>>),
-    /// Connection attempt failed or lost; carries a human-readable reason.
-    Disconnected(String),
-    /// A complete chat response was received from the agent.
-    ChatResponse(ChatMessage),
+    /// Connection attempt failed or lost.
+    Disconnected {
+        /// Human-readable failure reason.
+        reason: String,
+        /// Delay until the next automatic attempt.
+        retry_in: Duration,
+    },
+    /// The agent confirmed it has a message sent from this window.
+    Received {
+        conversation_id: uuid::Uuid,
+        message_id: uuid::Uuid,
+    },
+    /// A complete chat response was received from the agent, with its token
+    /// usage when an LLM produced it and the thinking of a reasoning model.
+    ChatResponse(ChatMessage, Option<UsageReport>, Option<String>),
     /// A streaming chunk was received.
     StreamChunk {
         request_id: uuid::Uuid,
@@ -34,16 +58,60 @@ pub enum IpcEvent {
     },
     /// The agent reported an error.
     AgentError { message: String },
+    /// The agent acknowledged a command (config reload, slash command, ...).
+    Notice { success: bool, message: String },
+    /// A partial or final transcript for a voice input session.
+    Transcript {
+        session_id: uuid::Uuid,
+        text: String,
+        is_final: bool,
+    },
+    /// Output of a tool call that is still running.
+    ToolProgress {
+        conversation_id: uuid::Uuid,
+        call_id: uuid::Uuid,
+        tool: String,
+        output: String,
+    },
+    /// A tool call finished, was rejected, or failed.
+    ToolFinished { call_id: uuid::Uuid, success: bool },
+    /// Models the agent's provider offers, for `/model`.
+    Models {
+        provider: String,
+        current: String,
+        models: Vec<ModelInfo>,
+    },
+    /// What the agent is about to do before its next tool calls.
+    Plan {
+        conversation_id: uuid::Uuid,
+        message: ChatMessage,
+    },
 }
 
 impl std::fmt::Debug for IpcEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Connecting => f.write_str("Connecting"),
             Self::Connected(_) => f.debug_tuple("Connected").field(&"<IpcWriter>").finish(),
-            Self::Disconnected(reason) => {
-                f.debug_tuple("Disconnected").field(reason).finish()
-            }
-            Self::ChatResponse(msg) => f.debug_tuple("ChatResponse").field(msg).finish(),
+            Self::Disconnected { reason, retry_in } => f
+                .debug_struct("Disconnected")
+                .field("reason", reason)
+                .field("retry_in", retry_in)
+                .finish(),
+            Self::Received {
+                conversation_id,
+                message_id,
+            } => f
+                .debug_struct("Received")
+                .field("conversation_id", conversation_id)
+                .field("message_id", message_id)
+                .finish(),
+            Self::ChatResponse(msg, usage, reasoning) => f
+                .debug_tuple("ChatResponse")
+                .field(msg)
+                .field(usage)
+                .field(reasoning)
+                .finish(),
             Self::StreamChunk {
                 request_id,
                 delta,
@@ -57,6 +125,56 @@ impl std::fmt::Debug for IpcEvent {
             Self::AgentError { message } => {
                 f.debug_struct("AgentError").field("message", message).finish()
             }
+            Self::Notice { success, message } => f
+                .debug_struct("Notice")
+                .field("success", success)
+                .field("message", message)
+                .finish(),
+            Self::Transcript {
+                session_id,
+                text,
+                is_final,
+            } => f
+                .debug_struct("Transcript")
+                .field("session_id", session_id)
+                .field("text", text)
+                .field("is_final", is_final)
+                .finish(),
+            Self::ToolProgress {
+                conversation_id,
+                call_id,
+                tool,
+                output,
+            } => f
+                .debug_struct("ToolProgress")
+                .field("conversation_id", conversation_id)
+                .field("call_id", call_id)
+                .field("tool", tool)
+                .field("output", output)
+                .finish(),
+            Self::ToolFinished { call_id, success } => f
+                .debug_struct("ToolFinished")
+                .field("call_id", call_id)
+                .field("success", success)
+                .finish(),
+            Self::Models {
+                provider,
+                current,
+                models,
+            } => f
+                .debug_struct("Models")
+                .field("provider", provider)
+                .field("current", current)
+                .field("models", &models.len())
+                .finish(),
+            Self::Plan {
+                conversation_id,
+                message,
+            } => f
+                .debug_struct("Plan")
+                .field("conversation_id", conversation_id)
+                .field("message", message)
+                .finish(),
         }
     }
 }
@@ -67,26 +185,62 @@ impl std::fmt::Debug for IpcEvent {
 /// 2. Sends `Register { client_type: Chat }`.
 /// 3. Waits for `RegisterAck`.
 /// 4. Enters a read loop, forwarding agent messages as `IpcEvent`s.
-/// 5. On any error, emits `Disconnected`, waits 2 seconds, and retries.
+/// 5. On any error, emits `Disconnected` and retries with exponential
+///    backoff (2 s up to 30 s), or immediately after [`reconnect_now`].
 ///
-/// This function is designed to be used with `Subscription::run`.
+/// This function is designed to be used with `Subscription::run`; each chat
+/// window keys its own copy so conversations stream independently.
 pub fn ipc_worker() -> impl futures::Stream<Item = IpcEvent> {
     iced::stream::channel(64, async move |mut output: mpsc::Sender<IpcEvent>| {
+        let mut failures: u32 = 0;
         loop {
-            if let Err(reason) = run_ipc_session(&mut output).await {
-                let _ = output
-                    .send(IpcEvent::Disconnected(reason.clone()))
-                    .await;
-                tracing::warn!("IPC session ended: {reason}. Reconnecting in 2 s...");
+            let _ = output.send(IpcEvent::Connecting).await;
+            let delay = match run_ipc_session(&mut output, &mut failures).await {
+                Ok(()) => INITIAL_RETRY_DELAY,
+                Err(reason) => {
+                    failures += 1;
+                    let delay = retry_delay(failures);
+                    tracing::warn!(
+                        "IPC session ended: {reason}. Reconnecting in {} s...",
+                        delay.as_secs()
+                    );
+                    let _ = output
+                        .send(IpcEvent::Disconnected {
+                            reason,
+                            retry_in: delay,
+                        })
+                        .await;
+                    delay
+                }
+            };
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {}
+                () = RECONNECT.notified() => tracing::info!("Manual reconnect requested"),
             }
-            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
         }
     })
 }
 
+/// Skip the remaining retry delay and reconnect right away. Every window
+/// runs its own worker, and all of them are woken.
+pub fn reconnect_now() {
+    RECONNECT.notify_waiters();
+}
+
+/// Backoff after `failures` consecutive failed sessions (at least 1).
+fn retry_delay(failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(5);
+    INITIAL_RETRY_DELAY
+        .saturating_mul(1 << exponent)
+        .min(MAX_RETRY_DELAY)
+}
+
 /// A single connect-register-read session. Returns `Err(reason)` when the
-/// session must be retried.
-async fn run_ipc_session(output: &mut mpsc::Sender<IpcEvent>) -> Result<(), String> {
+/// session must be retried. Resets `failures` once registered.
+async fn run_ipc_session(
+    output: &mut mpsc::Sender<IpcEvent>,
+    failures: &mut u32,
+) -> Result<(), String> {
     use aios_common::{ClientType, IpcClient, IpcMessage};
 
     let path = socket_path();
@@ -123,6 +277,7 @@ async fn run_ipc_session(output: &mut mpsc::Sender<IpcEvent>) -> Result<(), Stri
     match ack.payload {
         IpcPayload::RegisterAck { success: true } => {
             tracing::info!("Registered with agent successfully");
+            *failures = 0;
         }
         IpcPayload::RegisterAck { success: false } => {
             return Err("agent rejected registration".to_owned());
@@ -146,7 +301,21 @@ async fn run_ipc_session(output: &mut mpsc::Sender<IpcEvent>) -> Result<(), Stri
             .map_err(|e| format!("read error: {e}"))?;
 
         let event = match msg.payload {
-            IpcPayload::ChatResponse { message } => IpcEvent::ChatResponse(message),
+            IpcPayload::ChatResponse {
+                message,
+                usage,
+                reasoning,
+            } => {
+                tracing::info!(request_id = %msg.id, "Chat response received");
+                IpcEvent::ChatResponse(message, usage, reasoning)
+            }
+            IpcPayload::MessageReceived {
+                conversation_id,
+                message_id,
+            } => IpcEvent::Received {
+                conversation_id,
+                message_id,
+            },
             IpcPayload::StreamChunk {
                 request_id,
                 delta,
@@ -157,6 +326,49 @@ async fn run_ipc_session(output: &mut mpsc::Sender<IpcEvent>) -> Result<(), Stri
                 done,
             },
             IpcPayload::Error { message, .. } => IpcEvent::AgentError { message },
+            IpcPayload::ConfigReloaded { success, message }
+            | IpcPayload::CommandResult { success, message } => {
+                IpcEvent::Notice { success, message }
+            }
+            IpcPayload::Transcript {
+                session_id,
+                text,
+                is_final,
+            } => IpcEvent::Transcript {
+                session_id,
+                text,
+                is_final,
+            },
+            IpcPayload::ToolProgress {
+                conversation_id,
+                call_id,
+                tool,
+                output,
+            } => IpcEvent::ToolProgress {
+                conversation_id,
+                call_id,
+                tool,
+                output,
+            },
+            IpcPayload::ToolFinished {
+                call_id, success, ..
+            } => IpcEvent::ToolFinished { call_id, success },
+            IpcPayload::Models {
+                provider,
+                current,
+                models,
+            } => IpcEvent::Models {
+                provider,
+                current,
+                models,
+            },
+            IpcPayload::AgentPlan {
+                conversation_id,
+                message,
+            } => IpcEvent::Plan {
+                conversation_id,
+                message,
+            },
             IpcPayload::Ping => {
                 // Respond with Pong.
                 let pong = IpcMessage {
@@ -180,5 +392,3 @@ async fn run_ipc_session(output: &mut mpsc::Sender<IpcEvent>) -> Result<(), Stri
         }
     }
 }
-
-
@@ -1,10 +1,17 @@
 mod app;
+mod attachments;
+mod background;
+mod commands;
 mod ipc_client;
+mod oobe_progress;
 mod state;
 mod theme;
 mod views;
+mod voice;
+mod windows;
 
-use app::AiosChat;
+use background::Activation;
+use windows::ChatWindows;
 
 fn main() -> iced::Result {
     tracing_subscriber::fmt()
@@ -14,14 +21,21 @@ fn main() -> iced::Result {
         )
         .init();
 
+    // A second launch (hotkey, dock) only brings the running window back.
+    if background::forward_to_running(Activation::from_args()) {
+        tracing::info!("aios-chat is already running; activated it");
+        return Ok(());
+    }
+
     tracing::info!("aios-chat starting...");
+    aios_common::i18n::init_from_config();
+    aios_theme::init_from_config();
 
-    iced::application(AiosChat::new, AiosChat::update, AiosChat::view)
-        .subscription(AiosChat::subscription)
-        .title("AIOS Chat")
-        .theme(iced::Theme::TokyoNight)
-        .window_size((800.0, 600.0))
-        .centered()
+    iced::daemon(ChatWindows::new, ChatWindows::update, ChatWindows::view)
+        .subscription(ChatWindows::subscription)
+        .title(ChatWindows::title)
+        .theme(|_state: &ChatWindows, _window| aios_theme::iced_theme())
+        .scale_factor(|_state: &ChatWindows, _window| aios_theme::scale_factor())
         .antialiasing(true)
         .run()
 }
@@ -0,0 +1,67 @@
+//! Persisted OOBE wizard progress.
+//!
+//! The wizard records its position in `~/.config/aios/oobe-progress.toml`
+//! after every step, so a chat window that crashes (or is closed) mid-setup
+//! reopens at the same step. The file is removed once the user reaches the
+//! chat. API keys are never written here.
+
+use std::path::PathBuf;
+
+use aios_common::ProviderType;
+use serde::{Deserialize, Serialize};
+
+use crate::app::OobeStep;
+
+/// Where the wizard left off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OobeProgress {
+    pub step: OobeStep,
+    #[serde(default)]
+    pub selected_provider: Option<ProviderType>,
+    #[serde(default)]
+    pub ollama_model: Option<String>,
+}
+
+fn progress_path() -> PathBuf {
+    aios_common::config_path().with_file_name("oobe-progress.toml")
+}
+
+/// Progress saved by an unfinished wizard, if any.
+pub fn load() -> Option<OobeProgress> {
+    let path = progress_path();
+    let contents = std::fs::read_to_string(&path).ok()?;
+    match toml::from_str(&contents) {
+        Ok(progress) => Some(progress),
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Record the current wizard position. Failures are logged; at worst the
+/// wizard restarts from an earlier step.
+pub fn save(progress: &OobeProgress) {
+    let path = progress_path();
+    let result = toml::to_string(progress)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::write(&path, contents).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        tracing::warn!("Failed to save OOBE progress to {}: {e}", path.display());
+    }
+}
+
+/// Forget saved progress after the wizard finished.
+pub fn clear() {
+    let path = progress_path();
+    match std::fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!("Failed to remove {}: {e}", path.display()),
+    }
+}
@@ -1,5 +1,6 @@
+use aios_common::{SystemReport, TokenUsage, UsageReport, t};
 use chrono::{DateTime, Utc};
-use iced::widget::markdown;
+use iced::widget::{image, markdown};
 use uuid::Uuid;
 
 /// Maximum characters to display for tool result output before truncation.
@@ -26,6 +27,22 @@ pub struct DisplayMessage {
     pub tool_is_error: Option<bool>,
     /// Current status of a tool interaction card.
     pub tool_status: Option<ToolStatus>,
+    /// Names of files attached to a user message.
+    pub attachment_names: Vec<String>,
+    /// How far a user message got on its way to the agent.
+    pub delivery: Option<Delivery>,
+    /// Images returned by a tool (screenshots, camera captures), shown as
+    /// thumbnails in the tool card.
+    pub images: Vec<image::Handle>,
+    /// Parsed `system_info` result, shown as a summary card instead of the
+    /// raw JSON.
+    pub system_report: Option<Box<SystemReport>>,
+    /// Tokens and cost of an assistant reply.
+    pub usage: Option<UsageReport>,
+    /// Thinking of a reasoning model behind an assistant reply.
+    pub reasoning: Option<String>,
+    /// The reasoning is unfolded under the reply.
+    pub reasoning_shown: bool,
 }
 
 impl DisplayMessage {
@@ -41,6 +58,13 @@ impl DisplayMessage {
             tool_args: None,
             tool_is_error: None,
             tool_status: None,
+            attachment_names: Vec::new(),
+            delivery: None,
+            images: Vec::new(),
+            system_report: None,
+            usage: None,
+            reasoning: None,
+            reasoning_shown: false,
         }
     }
 
@@ -57,6 +81,35 @@ impl DisplayMessage {
             tool_args: None,
             tool_is_error: None,
             tool_status: None,
+            attachment_names: Vec::new(),
+            delivery: None,
+            images: Vec::new(),
+            system_report: None,
+            usage: None,
+            reasoning: None,
+            reasoning_shown: false,
+        }
+    }
+
+    /// Creates a plan note, shown muted between the tool cards.
+    pub fn plan(id: Uuid, text: String, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            role: MessageRole::Plan,
+            text,
+            timestamp,
+            markdown_content: None,
+            tool_name: None,
+            tool_args: None,
+            tool_is_error: None,
+            tool_status: None,
+            attachment_names: Vec::new(),
+            delivery: None,
+            images: Vec::new(),
+            system_report: None,
+            usage: None,
+            reasoning: None,
+            reasoning_shown: false,
         }
     }
 
@@ -77,6 +130,13 @@ impl DisplayMessage {
             tool_args: Some(args_json),
             tool_is_error: None,
             tool_status: Some(ToolStatus::Pending),
+            attachment_names: Vec::new(),
+            delivery: None,
+            images: Vec::new(),
+            system_report: None,
+            usage: None,
+            reasoning: None,
+            reasoning_shown: false,
         }
     }
 
@@ -106,6 +166,13 @@ impl DisplayMessage {
             tool_args: None,
             tool_is_error: Some(is_error),
             tool_status: Some(status),
+            attachment_names: Vec::new(),
+            delivery: None,
+            images: Vec::new(),
+            system_report: None,
+            usage: None,
+            reasoning: None,
+            reasoning_shown: false,
         }
     }
 
@@ -118,6 +185,33 @@ impl DisplayMessage {
         }
     }
 
+    /// Case-insensitive match of `query` (already lowercased) against the
+    /// message text, tool name, and tool arguments.
+    pub fn matches(&self, query: &str) -> bool {
+        self.text.to_lowercase().contains(query)
+            || self
+                .tool_name
+                .as_deref()
+                .is_some_and(|n| n.to_lowercase().contains(query))
+            || self
+                .tool_args
+                .as_deref()
+                .is_some_and(|a| a.to_lowercase().contains(query))
+    }
+
+    /// Add output of a running tool to its card, keeping the last
+    /// [`TOOL_OUTPUT_MAX_LEN`] bytes.
+    pub fn append_tool_output(&mut self, output: &str) {
+        self.text.push_str(output);
+        if self.text.len() > TOOL_OUTPUT_MAX_LEN {
+            let mut start = self.text.len() - TOOL_OUTPUT_MAX_LEN;
+            while !self.text.is_char_boundary(start) {
+                start += 1;
+            }
+            self.text.drain(..start);
+        }
+    }
+
     /// Mark a tool call card as having received its result.
     pub fn set_tool_status(&mut self, status: ToolStatus) {
         self.tool_status = Some(status);
@@ -136,6 +230,48 @@ fn truncate_output(output: &str) -> String {
     }
 }
 
+/// Running token and cost totals for the current conversation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConversationUsage {
+    pub tokens: TokenUsage,
+    /// Sum of the priced replies, in USD.
+    pub cost_usd: f64,
+    /// At least one reply had a cost estimate (paid provider).
+    pub priced: bool,
+}
+
+impl ConversationUsage {
+    pub fn add(&mut self, report: &UsageReport) {
+        self.tokens += report.tokens;
+        if let Some(cost) = report.cost_usd {
+            self.cost_usd += cost;
+            self.priced = true;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.total() == 0
+    }
+}
+
+/// Compact token count: `950`, `12.3k`, `1.2M`.
+pub fn format_tokens(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+/// Dollar amount with enough precision for sub-cent costs.
+pub fn format_cost(usd: f64) -> String {
+    if usd < 0.01 {
+        format!("${usd:.4}")
+    } else {
+        format!("${usd:.2}")
+    }
+}
+
 /// The author role of a displayed message.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MessageRole {
@@ -145,6 +281,22 @@ pub enum MessageRole {
     ToolCall,
     /// A tool execution result returned to the agent.
     ToolResult,
+    /// What the agent is about to do before its tool calls.
+    Plan,
+}
+
+/// How far a user message got on its way to the agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Delivery {
+    /// Waiting in the outbox for a connection, or being written.
+    Sending,
+    /// Written to the agent's socket; no receipt yet.
+    Sent,
+    /// The agent confirmed it has the message.
+    Received,
+    /// The connection dropped before the agent confirmed it; the user can
+    /// send it again.
+    Failed(String),
 }
 
 /// Lifecycle status of a tool interaction card.
@@ -160,6 +312,17 @@ pub enum ToolStatus {
     Rejected,
 }
 
+/// State of the voice input button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceStatus {
+    /// Not recording.
+    Idle,
+    /// Capturing microphone audio; partial transcripts update the input.
+    Recording,
+    /// Recording stopped; waiting for the final transcript.
+    Transcribing,
+}
+
 /// Current connection status to the AIOS agent backend.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionStatus {
@@ -170,11 +333,11 @@ pub enum ConnectionStatus {
 
 impl ConnectionStatus {
     /// Returns a human-readable label for the status indicator.
-    pub fn label(self) -> &'static str {
+    pub fn label(self) -> String {
         match self {
-            Self::Disconnected => "Disconnected",
-            Self::Connecting => "Connecting...",
-            Self::Connected => "Connected",
+            Self::Disconnected => t!("status-disconnected"),
+            Self::Connecting => t!("status-connecting"),
+            Self::Connected => t!("status-connected"),
         }
     }
 }
@@ -1,44 +1,85 @@
-use iced::widget::{button, container, scrollable, text_input};
+use iced::widget::{button, container, scrollable, text_editor, text_input};
 use iced::{Background, Border, Color, Shadow, Vector};
 
-/// Dark theme color palette for AIOS Chat.
+/// Color palette for AIOS Chat, read from the shared [`aios_theme`] palette
+/// so scheme and accent changes apply without a restart.
 pub struct AiosColors;
 
 impl AiosColors {
-    pub const BG_PRIMARY: Color = Color::from_rgb(0.10, 0.11, 0.15);
-    pub const BG_SECONDARY: Color = Color::from_rgb(0.13, 0.14, 0.18);
-    pub const BG_INPUT: Color = Color::from_rgb(0.16, 0.17, 0.22);
-    pub const TEXT_PRIMARY: Color = Color::from_rgb(0.87, 0.89, 0.93);
-    pub const TEXT_SECONDARY: Color = Color::from_rgb(0.55, 0.58, 0.65);
-    pub const ACCENT: Color = Color::from_rgb(0.47, 0.56, 1.0);
-    pub const USER_BUBBLE: Color = Color::from_rgb(0.20, 0.25, 0.45);
-    pub const ASSISTANT_BUBBLE: Color = Color::from_rgb(0.15, 0.16, 0.20);
+    pub fn bg_primary() -> Color {
+        aios_theme::palette().bg_primary
+    }
+    pub fn bg_secondary() -> Color {
+        aios_theme::palette().bg_secondary
+    }
+    pub fn bg_input() -> Color {
+        aios_theme::palette().bg_input
+    }
+    pub fn text_primary() -> Color {
+        aios_theme::palette().text_primary
+    }
+    pub fn text_secondary() -> Color {
+        aios_theme::palette().text_secondary
+    }
+    pub fn accent() -> Color {
+        aios_theme::palette().accent
+    }
+    pub fn user_bubble() -> Color {
+        aios_theme::palette().user_bubble
+    }
+    pub fn assistant_bubble() -> Color {
+        aios_theme::palette().assistant_bubble
+    }
+    pub fn danger() -> Color {
+        aios_theme::palette().danger
+    }
 
     // -- OOBE wizard colors --
 
     /// Background for OOBE provider selection cards.
-    pub const OOBE_CARD_BG: Color = Color::from_rgb(0.16, 0.17, 0.22);
+    pub fn oobe_card_bg() -> Color {
+        aios_theme::palette().bg_input
+    }
     /// Border for OOBE provider selection cards (idle).
-    pub const OOBE_CARD_BORDER: Color = Color::from_rgb(0.25, 0.27, 0.35);
+    pub fn oobe_card_border() -> Color {
+        aios_theme::palette().border
+    }
     /// Success indicator color.
-    pub const SUCCESS: Color = Color::from_rgb(0.30, 0.78, 0.48);
+    pub fn success() -> Color {
+        aios_theme::palette().success
+    }
 
     // -- Tool card colors --
 
-    /// Background for pending tool calls (dark amber tint).
-    pub const TOOL_PENDING_BG: Color = Color::from_rgb(0.22, 0.20, 0.12);
+    /// Background for pending tool calls (amber tint).
+    pub fn tool_pending_bg() -> Color {
+        let palette = aios_theme::palette();
+        palette.tint(palette.warning)
+    }
     /// Border for pending tool calls (amber).
-    pub const TOOL_PENDING_BORDER: Color = Color::from_rgb(0.75, 0.65, 0.20);
+    pub fn tool_pending_border() -> Color {
+        aios_theme::palette().warning
+    }
 
-    /// Background for completed tool results (dark green tint).
-    pub const TOOL_COMPLETED_BG: Color = Color::from_rgb(0.12, 0.20, 0.14);
+    /// Background for completed tool results (green tint).
+    pub fn tool_completed_bg() -> Color {
+        let palette = aios_theme::palette();
+        palette.tint(palette.success)
+    }
     /// Border for completed tool results (green).
-    pub const TOOL_COMPLETED_BORDER: Color = Color::from_rgb(0.30, 0.70, 0.40);
+    pub fn tool_completed_border() -> Color {
+        aios_theme::palette().success
+    }
 
-    /// Background for failed/rejected tool results (dark red tint).
-    pub const TOOL_FAILED_BG: Color = Color::from_rgb(0.22, 0.12, 0.12);
+    /// Background for failed/rejected tool results (red tint).
+    pub fn tool_failed_bg() -> Color {
+        let palette = aios_theme::palette();
+        palette.tint(palette.danger)
+    }
     /// Border for failed/rejected tool results (red).
-    pub const TOOL_FAILED_BORDER: Color = Color::from_rgb(0.80, 0.30, 0.30);
+    pub fn tool_failed_border() -> Color {
+        aios_theme::palette().danger
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -48,8 +89,8 @@ impl AiosColors {
 /// Primary background for the root container.
 pub fn container_primary(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::BG_PRIMARY)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::bg_primary())),
+        text_color: Some(AiosColors::text_primary()),
         ..container::Style::default()
     }
 }
@@ -57,8 +98,44 @@ pub fn container_primary(_theme: &iced::Theme) -> container::Style {
 /// Secondary background for the header and input bar areas.
 pub fn container_secondary(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::BG_SECONDARY)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::bg_secondary())),
+        text_color: Some(AiosColors::text_primary()),
+        ..container::Style::default()
+    }
+}
+
+/// Banner shown while the agent is unreachable (red tint, red border).
+pub fn container_banner_error(_theme: &iced::Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(AiosColors::tool_failed_bg())),
+        text_color: Some(AiosColors::text_primary()),
+        border: Border {
+            color: AiosColors::danger(),
+            width: 1.0,
+            radius: 0.0.into(),
+        },
+        ..container::Style::default()
+    }
+}
+
+/// Dimmed backdrop behind an enlarged image.
+pub fn container_image_overlay(_theme: &iced::Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.85))),
+        ..container::Style::default()
+    }
+}
+
+/// Popover with per-message details (token usage).
+pub fn container_tooltip(_theme: &iced::Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(AiosColors::bg_input())),
+        text_color: Some(AiosColors::text_primary()),
+        border: Border {
+            color: aios_theme::palette().border,
+            width: 1.0,
+            radius: 6.0.into(),
+        },
         ..container::Style::default()
     }
 }
@@ -66,8 +143,8 @@ pub fn container_secondary(_theme: &iced::Theme) -> container::Style {
 /// User message bubble background.
 pub fn container_user_bubble(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::USER_BUBBLE)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::user_bubble())),
+        text_color: Some(AiosColors::text_primary()),
         border: Border {
             radius: 12.0.into(),
             ..Border::default()
@@ -76,11 +153,25 @@ pub fn container_user_bubble(_theme: &iced::Theme) -> container::Style {
     }
 }
 
+/// Chip showing a pending attachment above the input field.
+pub fn container_attachment_chip(_theme: &iced::Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(AiosColors::bg_input())),
+        text_color: Some(AiosColors::text_primary()),
+        border: Border {
+            color: AiosColors::oobe_card_border(),
+            width: 1.0,
+            radius: 10.0.into(),
+        },
+        ..container::Style::default()
+    }
+}
+
 /// Assistant message bubble background.
 pub fn container_assistant_bubble(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::ASSISTANT_BUBBLE)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::assistant_bubble())),
+        text_color: Some(AiosColors::text_primary()),
         border: Border {
             radius: 12.0.into(),
             ..Border::default()
@@ -89,43 +180,43 @@ pub fn container_assistant_bubble(_theme: &iced::Theme) -> container::Style {
     }
 }
 
-/// Tool card in `Pending` state (amber border, dark amber background).
+/// Tool card in `Pending` state (amber border, amber-tinted background).
 pub fn container_tool_pending(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::TOOL_PENDING_BG)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::tool_pending_bg())),
+        text_color: Some(AiosColors::text_primary()),
         border: Border {
             radius: 8.0.into(),
             width: 1.5,
-            color: AiosColors::TOOL_PENDING_BORDER,
+            color: AiosColors::tool_pending_border(),
         },
         ..container::Style::default()
     }
 }
 
-/// Tool card in `Completed` state (green border, dark green background).
+/// Tool card in `Completed` state (green border, green-tinted background).
 pub fn container_tool_completed(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::TOOL_COMPLETED_BG)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::tool_completed_bg())),
+        text_color: Some(AiosColors::text_primary()),
         border: Border {
             radius: 8.0.into(),
             width: 1.5,
-            color: AiosColors::TOOL_COMPLETED_BORDER,
+            color: AiosColors::tool_completed_border(),
         },
         ..container::Style::default()
     }
 }
 
-/// Tool card in `Failed` or `Rejected` state (red border, dark red background).
+/// Tool card in `Failed` or `Rejected` state (red border, red-tinted background).
 pub fn container_tool_failed(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::TOOL_FAILED_BG)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::tool_failed_bg())),
+        text_color: Some(AiosColors::text_primary()),
         border: Border {
             radius: 8.0.into(),
             width: 1.5,
-            color: AiosColors::TOOL_FAILED_BORDER,
+            color: AiosColors::tool_failed_border(),
         },
         ..container::Style::default()
     }
@@ -138,12 +229,12 @@ pub fn container_tool_failed(_theme: &iced::Theme) -> container::Style {
 /// Container style for an OOBE provider card (unselected).
 pub fn container_oobe_card(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::OOBE_CARD_BG)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::oobe_card_bg())),
+        text_color: Some(AiosColors::text_primary()),
         border: Border {
             radius: 10.0.into(),
             width: 1.5,
-            color: AiosColors::OOBE_CARD_BORDER,
+            color: AiosColors::oobe_card_border(),
         },
         ..container::Style::default()
     }
@@ -152,8 +243,8 @@ pub fn container_oobe_card(_theme: &iced::Theme) -> container::Style {
 /// Centered OOBE content area container.
 pub fn container_oobe_content(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(AiosColors::BG_PRIMARY)),
-        text_color: Some(AiosColors::TEXT_PRIMARY),
+        background: Some(Background::Color(AiosColors::bg_primary())),
+        text_color: Some(AiosColors::text_primary()),
         ..container::Style::default()
     }
 }
@@ -162,11 +253,11 @@ pub fn container_oobe_content(_theme: &iced::Theme) -> container::Style {
 pub fn oobe_secondary_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
         background: Some(Background::Color(Color::TRANSPARENT)),
-        text_color: AiosColors::TEXT_SECONDARY,
+        text_color: AiosColors::text_secondary(),
         border: Border {
             radius: 8.0.into(),
             width: 1.0,
-            color: AiosColors::OOBE_CARD_BORDER,
+            color: AiosColors::oobe_card_border(),
         },
         ..button::Style::default()
     };
@@ -174,20 +265,20 @@ pub fn oobe_secondary_button(_theme: &iced::Theme, status: button::Status) -> bu
     match status {
         button::Status::Active => base,
         button::Status::Hovered => button::Style {
-            text_color: AiosColors::TEXT_PRIMARY,
+            text_color: AiosColors::text_primary(),
             border: Border {
-                color: AiosColors::TEXT_PRIMARY,
+                color: AiosColors::text_primary(),
                 ..base.border
             },
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.05))),
-            text_color: AiosColors::TEXT_PRIMARY,
+            background: Some(Background::Color(aios_theme::palette().overlay(0.05))),
+            text_color: AiosColors::text_primary(),
             ..base
         },
         button::Status::Disabled => button::Style {
-            text_color: Color::from_rgba(0.55, 0.58, 0.65, 0.4),
+            text_color: aios_theme::with_alpha(AiosColors::text_secondary(), 0.4),
             ..base
         },
     }
@@ -197,7 +288,7 @@ pub fn oobe_secondary_button(_theme: &iced::Theme, status: button::Status) -> bu
 pub fn oobe_card_button(_theme: &iced::Theme, _status: button::Status) -> button::Style {
     button::Style {
         background: None,
-        text_color: AiosColors::TEXT_PRIMARY,
+        text_color: AiosColors::text_primary(),
         border: Border::default(),
         shadow: Shadow::default(),
         snap: true,
@@ -211,30 +302,64 @@ pub fn oobe_card_button(_theme: &iced::Theme, _status: button::Status) -> button
 /// Custom style for the message input field.
 pub fn input_style(_theme: &iced::Theme, status: text_input::Status) -> text_input::Style {
     let base = text_input::Style {
-        background: Background::Color(AiosColors::BG_INPUT),
+        background: Background::Color(AiosColors::bg_input()),
         border: Border {
             radius: 8.0.into(),
             width: 1.0,
-            color: Color::from_rgba(1.0, 1.0, 1.0, 0.08),
+            color: aios_theme::palette().overlay(0.08),
         },
-        icon: AiosColors::TEXT_SECONDARY,
-        placeholder: AiosColors::TEXT_SECONDARY,
-        value: AiosColors::TEXT_PRIMARY,
-        selection: AiosColors::ACCENT,
+        icon: AiosColors::text_secondary(),
+        placeholder: AiosColors::text_secondary(),
+        value: AiosColors::text_primary(),
+        selection: AiosColors::accent(),
     };
 
     match status {
         text_input::Status::Active | text_input::Status::Disabled => base,
         text_input::Status::Hovered => text_input::Style {
             border: Border {
-                color: Color::from_rgba(1.0, 1.0, 1.0, 0.15),
+                color: aios_theme::palette().overlay(0.15),
                 ..base.border
             },
             ..base
         },
         text_input::Status::Focused { .. } => text_input::Style {
             border: Border {
-                color: AiosColors::ACCENT,
+                color: AiosColors::accent(),
+                width: 1.5,
+                ..base.border
+            },
+            ..base
+        },
+    }
+}
+
+/// Multi-line variant of [`input_style`] for the message editor.
+pub fn editor_style(_theme: &iced::Theme, status: text_editor::Status) -> text_editor::Style {
+    let base = text_editor::Style {
+        background: Background::Color(AiosColors::bg_input()),
+        border: Border {
+            radius: 8.0.into(),
+            width: 1.0,
+            color: aios_theme::palette().overlay(0.08),
+        },
+        placeholder: AiosColors::text_secondary(),
+        value: AiosColors::text_primary(),
+        selection: AiosColors::accent(),
+    };
+
+    match status {
+        text_editor::Status::Active | text_editor::Status::Disabled => base,
+        text_editor::Status::Hovered => text_editor::Style {
+            border: Border {
+                color: aios_theme::palette().overlay(0.15),
+                ..base.border
+            },
+            ..base
+        },
+        text_editor::Status::Focused { .. } => text_editor::Style {
+            border: Border {
+                color: AiosColors::accent(),
                 width: 1.5,
                 ..base.border
             },
@@ -250,7 +375,7 @@ pub fn input_style(_theme: &iced::Theme, status: text_input::Status) -> text_inp
 /// Send button style.
 pub fn send_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(AiosColors::ACCENT)),
+        background: Some(Background::Color(AiosColors::accent())),
         text_color: Color::WHITE,
         border: Border {
             radius: 8.0.into(),
@@ -262,15 +387,15 @@ pub fn send_button(_theme: &iced::Theme, status: button::Status) -> button::Styl
     match status {
         button::Status::Active => base,
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.55, 0.64, 1.0))),
+            background: Some(Background::Color(aios_theme::palette().accent_hover())),
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.38, 0.47, 0.90))),
+            background: Some(Background::Color(aios_theme::palette().accent_pressed())),
             ..base
         },
         button::Status::Disabled => button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.47, 0.56, 1.0, 0.4))),
+            background: Some(Background::Color(aios_theme::with_alpha(AiosColors::accent(), 0.4))),
             text_color: Color::from_rgba(1.0, 1.0, 1.0, 0.4),
             ..base
         },
@@ -281,7 +406,7 @@ pub fn send_button(_theme: &iced::Theme, status: button::Status) -> button::Styl
 pub fn close_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
         background: Some(Background::Color(Color::TRANSPARENT)),
-        text_color: AiosColors::TEXT_SECONDARY,
+        text_color: AiosColors::text_secondary(),
         border: Border {
             radius: 4.0.into(),
             ..Border::default()
@@ -292,12 +417,12 @@ pub fn close_button(_theme: &iced::Theme, status: button::Status) -> button::Sty
     match status {
         button::Status::Active => base,
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.85, 0.30, 0.30, 0.3))),
-            text_color: Color::from_rgb(0.95, 0.40, 0.40),
+            background: Some(Background::Color(aios_theme::with_alpha(AiosColors::danger(), 0.3))),
+            text_color: AiosColors::danger(),
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.85, 0.30, 0.30, 0.5))),
+            background: Some(Background::Color(aios_theme::with_alpha(AiosColors::danger(), 0.5))),
             text_color: Color::WHITE,
             ..base
         },
@@ -305,11 +430,37 @@ pub fn close_button(_theme: &iced::Theme, status: button::Status) -> button::Sty
     }
 }
 
+/// Small transparent button used for hover actions on chat bubbles.
+pub fn message_action_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
+    let base = button::Style {
+        background: Some(Background::Color(aios_theme::with_alpha(AiosColors::bg_primary(), 0.85))),
+        text_color: AiosColors::text_secondary(),
+        border: Border {
+            radius: 4.0.into(),
+            ..Border::default()
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        button::Status::Active | button::Status::Disabled => base,
+        button::Status::Hovered => button::Style {
+            text_color: AiosColors::text_primary(),
+            ..base
+        },
+        button::Status::Pressed => button::Style {
+            background: Some(Background::Color(AiosColors::accent())),
+            text_color: Color::WHITE,
+            ..base
+        },
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Scrollable style
 // ---------------------------------------------------------------------------
 
-/// Dark scrollable style matching the primary background.
+/// Scrollable style matching the primary background.
 pub fn scrollable_dark(_theme: &iced::Theme, status: scrollable::Status) -> scrollable::Style {
     let scroller_border = Border {
         radius: 4.0.into(),
@@ -320,24 +471,24 @@ pub fn scrollable_dark(_theme: &iced::Theme, status: scrollable::Status) -> scro
         background: None,
         border: Border::default(),
         scroller: scrollable::Scroller {
-            background: Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.15)),
+            background: Background::Color(aios_theme::palette().overlay(0.15)),
             border: scroller_border,
         },
     };
 
     let auto_scroll = scrollable::AutoScroll {
-        background: Background::Color(AiosColors::BG_SECONDARY),
+        background: Background::Color(AiosColors::bg_secondary()),
         border: Border {
             radius: 8.0.into(),
             width: 1.0,
-            color: Color::from_rgba(1.0, 1.0, 1.0, 0.1),
+            color: aios_theme::palette().overlay(0.1),
         },
         shadow: Shadow {
             color: Color::from_rgba(0.0, 0.0, 0.0, 0.5),
             offset: Vector::ZERO,
             blur_radius: 2.0,
         },
-        icon: AiosColors::TEXT_SECONDARY,
+        icon: AiosColors::text_secondary(),
     };
 
     match status {
@@ -355,7 +506,7 @@ pub fn scrollable_dark(_theme: &iced::Theme, status: scrollable::Status) -> scro
         } => {
             let hovered_rail = scrollable::Rail {
                 scroller: scrollable::Scroller {
-                    background: Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.30)),
+                    background: Background::Color(aios_theme::palette().overlay(0.30)),
                     ..rail.scroller
                 },
                 ..rail
@@ -384,7 +535,7 @@ pub fn scrollable_dark(_theme: &iced::Theme, status: scrollable::Status) -> scro
         } => {
             let dragged_rail = scrollable::Rail {
                 scroller: scrollable::Scroller {
-                    background: Background::Color(AiosColors::ACCENT),
+                    background: Background::Color(AiosColors::accent()),
                     ..rail.scroller
                 },
                 ..rail
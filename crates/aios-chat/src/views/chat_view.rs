@@ -1,51 +1,115 @@
-use iced::widget::{button, column, container, row, scrollable, text, Space};
-use iced::{Element, Length};
+use iced::widget::{
+    button, column, container, image, mouse_area, row, scrollable, stack, text, tooltip, Space,
+};
+use iced::{ContentFit, Element, Length};
+
+use aios_common::t;
 
 use crate::app::{AiosChat, Message};
-use crate::state::ConnectionStatus;
+use crate::state::{self, ConnectionStatus, ConversationUsage};
 use crate::theme::{self, AiosColors};
-use crate::views::{input_bar, message_bubble};
+use crate::views::{connection_banner, input_bar, message_bubble, search_bar};
 
 /// Renders the full chat layout: header, scrollable message list, and input bar.
 pub fn view(state: &AiosChat) -> Element<'_, Message> {
-    let header = header_row(state.connection_status());
+    let header = header_row(state.connection_status(), state.conversation_usage());
     let messages = message_list(state);
-    let input = input_bar::view(state.input_text(), state.can_send());
+    let input = input_bar::view(
+        state.input(),
+        state.input_id(),
+        state.can_send(),
+        state.is_editing(),
+        state.attachments(),
+        &state.command_suggestions(),
+        state.voice_status(),
+        state.can_record(),
+    );
 
-    let content = column![header, messages, input];
+    let mut content = column![header];
+    if let Some((reason, retry_in)) = state.connection_problem() {
+        content = content.push(connection_banner::view(reason, retry_in));
+    }
+    if let Some(query) = state.search_query() {
+        content = content.push(search_bar::view(
+            query,
+            state.visible_messages().len(),
+            state.search_input_id(),
+        ));
+    }
+    let content = content.push(messages).push(input);
 
-    container(content)
+    let chat = container(content)
         .width(Length::Fill)
         .height(Length::Fill)
-        .style(theme::container_primary)
-        .into()
+        .style(theme::container_primary);
+
+    match state.image_preview() {
+        Some(handle) => stack![chat, image_preview(handle)].into(),
+        None => chat.into(),
+    }
 }
 
-/// The top header bar with the application title and connection status.
-fn header_row(status: ConnectionStatus) -> Element<'static, Message> {
-    let title = text("AIOS Chat").size(18).color(AiosColors::TEXT_PRIMARY);
+/// Full-window overlay showing an image from a tool card. Clicking anywhere
+/// (or pressing Escape) closes it.
+fn image_preview(handle: &image::Handle) -> Element<'_, Message> {
+    let enlarged = image(handle.clone())
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .content_fit(ContentFit::Contain);
+
+    mouse_area(
+        container(enlarged)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(24)
+            .style(theme::container_image_overlay),
+    )
+    .on_press(Message::CloseImage)
+    .into()
+}
+
+/// The top header bar with the application title, token usage of the
+/// conversation and connection status.
+fn header_row(status: ConnectionStatus, usage: ConversationUsage) -> Element<'static, Message> {
+    let title = text("AIOS Chat").size(18).color(AiosColors::text_primary());
 
     let status_color = match status {
-        ConnectionStatus::Connected => AiosColors::ACCENT,
-        ConnectionStatus::Connecting => AiosColors::TEXT_SECONDARY,
-        ConnectionStatus::Disconnected => iced::Color::from_rgb(0.85, 0.30, 0.30),
+        ConnectionStatus::Connected => AiosColors::accent(),
+        ConnectionStatus::Connecting => AiosColors::text_secondary(),
+        ConnectionStatus::Disconnected => AiosColors::danger(),
     };
 
     let status_label = text(status.label()).size(12).color(status_color);
 
-    let close_btn = button(text("X").size(14).color(AiosColors::TEXT_SECONDARY))
-        .on_press(Message::CloseWindow)
-        .padding([4, 10])
-        .style(theme::close_button);
+    let pop_out_btn = tooltip(
+        button(text(t!("chat-new-window")).size(12))
+            .on_press(Message::PopOut)
+            .padding([4, 10])
+            .style(theme::message_action_button),
+        container(text(t!("chat-new-window-hint")).size(12))
+            .padding(8)
+            .style(theme::container_tooltip),
+        tooltip::Position::Bottom,
+    );
 
-    let bar = row![
-        title,
-        Space::new().width(Length::Fill),
-        status_label,
-        close_btn
-    ]
-    .spacing(8)
-    .align_y(iced::Alignment::Center);
+    let close_btn = tooltip(
+        button(text("X").size(14).color(AiosColors::text_secondary()))
+            .on_press(Message::CloseWindow)
+            .padding([4, 10])
+            .style(theme::close_button),
+        container(text(t!("chat-close")).size(12))
+            .padding(8)
+            .style(theme::container_tooltip),
+        tooltip::Position::Bottom,
+    );
+
+    let mut bar = row![title, Space::new().width(Length::Fill)]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+    if !usage.is_empty() {
+        bar = bar.push(usage_summary(usage));
+    }
+    let bar = bar.push(status_label).push(pop_out_btn).push(close_btn);
 
     container(bar)
         .width(Length::Fill)
@@ -56,26 +120,79 @@ fn header_row(status: ConnectionStatus) -> Element<'static, Message> {
 
 /// The scrollable list of chat messages.
 fn message_list(state: &AiosChat) -> Element<'_, Message> {
-    let messages = state.messages();
+    let messages = state.visible_messages();
 
-    let content: Element<'_, Message> = if messages.is_empty() {
+    let content: Element<'_, Message> = if messages.is_empty() && state.search_query().is_some() {
         container(
-            text("No messages yet. Start a conversation!")
+            text(t!("chat-no-search-results"))
                 .size(14)
-                .color(AiosColors::TEXT_SECONDARY),
+                .color(AiosColors::text_secondary()),
+        )
+        .center(Length::Fill)
+        .into()
+    } else if messages.is_empty() {
+        container(
+            text(t!("chat-empty"))
+                .size(14)
+                .color(AiosColors::text_secondary()),
         )
         .center(Length::Fill)
         .into()
     } else {
-        let mut col = column![].spacing(8).padding([8, 12]);
+        let regenerate_id = state.regeneratable_message_id();
+        let metrics = aios_theme::metrics();
+        let mut col = column![]
+            .spacing(metrics.space(8.0))
+            .padding([metrics.space(8.0), 12.0]);
         for msg in messages {
-            col = col.push(message_bubble::view(msg));
+            col = col.push(message_bubble::view(msg, Some(msg.id) == regenerate_id));
         }
         col.into()
     };
 
-    scrollable(container(content).width(Length::Fill))
+    let list = scrollable(container(content).width(Length::Fill))
+        .id(state.message_list_id().clone())
+        .on_scroll(Message::ChatScrolled)
         .height(Length::Fill)
-        .style(theme::scrollable_dark)
-        .into()
+        .style(theme::scrollable_dark);
+
+    if !state.has_unseen_messages() {
+        return list.into();
+    }
+
+    // Floating "jump to latest" button over the bottom of the list.
+    let jump_btn = button(text(format!("\u{2193} {}", t!("chat-new-messages"))).size(13))
+        .on_press(Message::JumpToLatest)
+        .padding([6, 14])
+        .style(theme::send_button);
+
+    stack![
+        list,
+        container(jump_btn)
+            .align_bottom(Length::Fill)
+            .center_x(Length::Fill)
+            .padding(12),
+    ]
+    .into()
+}
+
+/// "12.3k tokens · ~$0.04" with an input/output breakdown on hover.
+fn usage_summary(usage: ConversationUsage) -> Element<'static, Message> {
+    let mut label = t!("usage-tokens", count = state::format_tokens(usage.tokens.total()));
+    if usage.priced {
+        label = format!("{label} · {}", t!("usage-cost", cost = state::format_cost(usage.cost_usd)));
+    }
+
+    let details = column![
+        text(t!("usage-detail-input", count = state::format_tokens(usage.tokens.input_tokens))).size(12),
+        text(t!("usage-detail-output", count = state::format_tokens(usage.tokens.output_tokens))).size(12),
+    ]
+    .spacing(2);
+
+    tooltip(
+        text(label).size(12).color(AiosColors::text_secondary()),
+        container(details).padding(8).style(theme::container_tooltip),
+        tooltip::Position::Bottom,
+    )
+    .into()
 }
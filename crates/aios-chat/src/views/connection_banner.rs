@@ -0,0 +1,43 @@
+use iced::widget::{button, container, row, text, Space};
+use iced::{Element, Length};
+
+use aios_common::t;
+
+use crate::app::Message;
+use crate::theme::{self, AiosColors};
+
+/// Renders the banner shown under the header while the agent is unreachable.
+///
+/// Shows the failure reason and the reconnect countdown; `retry_in` is
+/// `None` while an attempt is already running, which hides the button.
+pub fn view(reason: &str, retry_in: Option<u64>) -> Element<'_, Message> {
+    let status = match retry_in {
+        Some(seconds) => t!("banner-retry-in", seconds = seconds),
+        None => t!("banner-reconnecting"),
+    };
+
+    let mut bar = row![
+        text(t!("banner-disconnected", reason = reason))
+            .size(13)
+            .color(AiosColors::text_primary()),
+        Space::new().width(Length::Fill),
+        text(status).size(12).color(AiosColors::text_secondary()),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    if retry_in.is_some() {
+        bar = bar.push(
+            button(text(t!("banner-reconnect")).size(12))
+                .on_press(Message::ReconnectNow)
+                .padding([4, 10])
+                .style(theme::send_button),
+        );
+    }
+
+    container(bar)
+        .width(Length::Fill)
+        .padding([6, 12])
+        .style(theme::container_banner_error)
+        .into()
+}
@@ -1,19 +1,48 @@
-use iced::widget::{button, container, row, text, text_input};
+use aios_common::{Attachment, AttachmentContent, t};
+use iced::keyboard::{self, key::Named};
+use iced::widget::text_editor::{Binding, KeyPress};
+use iced::widget::{button, column, container, row, text, text_editor, tooltip, Id, Space};
 use iced::{Element, Length};
 
 use crate::app::Message;
-use crate::theme;
+use crate::attachments;
+use crate::commands::Suggestion;
+use crate::state::VoiceStatus;
+use crate::theme::{self, AiosColors};
 
-/// Renders the bottom input bar with a text field and a send button.
-pub fn view<'a>(input_text: &str, can_send: bool) -> Element<'a, Message> {
-    let input = text_input("Type a message...", input_text)
-        .on_input(Message::InputChanged)
-        .on_submit(Message::SendMessage)
+/// Renders the bottom input bar with a multi-line text field, a microphone
+/// button and a send button.
+///
+/// Enter or Ctrl+Enter sends, Shift+Enter inserts a newline, and Up in an
+/// empty field edits the last user message. Tab is left to the window, which
+/// completes a command or moves the focus; `id` lets Ctrl+L focus the field.
+///
+/// While `editing` is set, a banner above the field offers to cancel the edit.
+/// Pending attachments are shown as removable chips above the field, with
+/// how much of the per-message limit they take.
+pub fn view<'a>(
+    input: &'a text_editor::Content,
+    id: &Id,
+    can_send: bool,
+    editing: bool,
+    attachments: &'a [Attachment],
+    suggestions: &[Suggestion],
+    voice: VoiceStatus,
+    can_record: bool,
+) -> Element<'a, Message> {
+    let is_empty = input.text().trim().is_empty();
+    let input = text_editor(input)
+        .id(id.clone())
+        .placeholder(t!("input-placeholder"))
+        .on_action(Message::InputAction)
+        .key_binding(move |key_press| key_binding(key_press, is_empty))
+        .height(Length::Shrink)
+        .max_height(160)
         .padding(10)
         .size(14)
-        .style(theme::input_style);
+        .style(theme::editor_style);
 
-    let send_btn = button(text("Send").size(14))
+    let send_btn = button(text(t!("input-send")).size(14))
         .on_press_maybe(if can_send {
             Some(Message::SendMessage)
         } else {
@@ -22,13 +51,133 @@ pub fn view<'a>(input_text: &str, can_send: bool) -> Element<'a, Message> {
         .padding([8, 16])
         .style(theme::send_button);
 
-    let bar = row![input, send_btn]
+    let (mic_label, mic_enabled) = match voice {
+        VoiceStatus::Idle => (t!("input-mic"), can_record),
+        VoiceStatus::Recording => (t!("input-mic-stop"), true),
+        VoiceStatus::Transcribing => ("...".to_owned(), false),
+    };
+    let mic_btn = button(text(mic_label).size(14))
+        .on_press_maybe(mic_enabled.then_some(Message::ToggleVoice))
+        .padding([8, 12])
+        .style(if voice == VoiceStatus::Recording {
+            theme::send_button
+        } else {
+            theme::message_action_button
+        });
+
+    let bar = row![input, mic_btn, send_btn]
         .spacing(8)
         .align_y(iced::Alignment::Center);
 
-    container(bar)
+    let mut content = column![].spacing(6);
+    if !suggestions.is_empty() {
+        content = content.push(command_suggestions(suggestions));
+    }
+    if editing {
+        let cancel_btn = button(text(t!("input-cancel")).size(12))
+            .on_press(Message::CancelEdit)
+            .padding([2, 8])
+            .style(theme::close_button);
+        content = content.push(
+            row![
+                text(t!("input-editing-banner"))
+                    .size(12)
+                    .color(AiosColors::text_secondary()),
+                Space::new().width(Length::Fill),
+                cancel_btn,
+            ]
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    if !attachments.is_empty() {
+        content = content.push(attachment_chips(attachments));
+    }
+    content = content.push(bar);
+
+    container(content)
         .width(Length::Fill)
-        .padding(12)
+        .padding(aios_theme::metrics().space(12.0))
         .style(theme::container_secondary)
         .into()
 }
+
+/// Send on Enter unless Shift is held; edit the last message on Up when the
+/// field is empty; ignore Tab. Everything else keeps the editor defaults.
+fn key_binding(key_press: KeyPress, is_empty: bool) -> Option<Binding<Message>> {
+    if !matches!(key_press.status, text_editor::Status::Focused { .. }) {
+        return None;
+    }
+    match key_press.key.as_ref() {
+        keyboard::Key::Named(Named::Enter) if key_press.modifiers.shift() => Some(Binding::Enter),
+        keyboard::Key::Named(Named::Enter) => Some(Binding::Custom(Message::SendMessage)),
+        keyboard::Key::Named(Named::ArrowUp) if is_empty => {
+            Some(Binding::Custom(Message::EditLastMessage))
+        }
+        keyboard::Key::Named(Named::Tab) => None,
+        _ => Binding::from_key_press(key_press),
+    }
+}
+
+/// A row of chips, one per pending attachment, each with a remove button,
+/// then their total size against [`attachments::MAX_TOTAL_BYTES`].
+fn attachment_chips(attachments: &[Attachment]) -> Element<'_, Message> {
+    let mut chips = row![].spacing(6);
+    for (index, attachment) in attachments.iter().enumerate() {
+        let kind = match attachment.content {
+            AttachmentContent::Image { .. } => "[img]",
+            AttachmentContent::Text { .. } | AttachmentContent::Path { .. } => "[txt]",
+        };
+        let remove_btn = tooltip(
+            button(text("x").size(11))
+                .on_press(Message::RemoveAttachment(index))
+                .padding([0, 4])
+                .style(theme::close_button),
+            container(text(t!("input-remove-attachment", name = attachment.name.as_str())).size(12))
+                .padding(8)
+                .style(theme::container_tooltip),
+            tooltip::Position::Top,
+        );
+        let chip = row![
+            text(kind).size(11).color(AiosColors::text_secondary()),
+            text(&attachment.name).size(12),
+            remove_btn,
+        ]
+        .spacing(4)
+        .align_y(iced::Alignment::Center);
+        chips = chips.push(
+            container(chip)
+                .padding([2, 6])
+                .style(theme::container_attachment_chip),
+        );
+    }
+    let size = t!(
+        "input-attachments-size",
+        used = attachments::mib(attachments::encoded_len(attachments)),
+        limit = attachments::mib(attachments::MAX_TOTAL_BYTES)
+    );
+    chips = chips.push(text(size).size(11).color(AiosColors::text_secondary()));
+    chips.wrap().into()
+}
+
+/// Autocompletion list for a partially typed slash command.
+fn command_suggestions<'a>(suggestions: &[Suggestion]) -> Element<'a, Message> {
+    let mut list = column![].spacing(2);
+    for suggestion in suggestions {
+        let entry = row![
+            text(suggestion.usage.clone()).size(13),
+            Space::new().width(Length::Fill),
+            text(suggestion.help.clone())
+                .size(12)
+                .color(AiosColors::text_secondary()),
+        ]
+        .align_y(iced::Alignment::Center);
+        list = list.push(
+            button(entry)
+                .on_press(Message::CompleteCommand(suggestion.name.clone()))
+                .width(Length::Fill)
+                .padding([4, 8])
+                .style(theme::message_action_button),
+        );
+    }
+    list.into()
+}
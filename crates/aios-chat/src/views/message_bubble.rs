@@ -1,8 +1,11 @@
-use iced::widget::{column, container, markdown, row, text, Space};
-use iced::{Element, Length, Theme};
+use iced::widget::{button, column, container, hover, markdown, row, text, tooltip, Space};
+use iced::{font, Element, Font, Length, Theme};
+
+use aios_common::{UsageReport, t};
+use uuid::Uuid;
 
 use crate::app::Message;
-use crate::state::{DisplayMessage, MessageRole};
+use crate::state::{self, Delivery, DisplayMessage, MessageRole};
 use crate::theme::{self, AiosColors};
 use crate::views::tool_card;
 
@@ -11,42 +14,75 @@ use crate::views::tool_card;
 /// - User messages are right-aligned with `USER_BUBBLE` background, plain text.
 /// - Assistant messages are left-aligned with `ASSISTANT_BUBBLE` background, markdown rendered.
 /// - Tool call / result messages are rendered as distinct cards via [`tool_card::view`].
-pub fn view(msg: &DisplayMessage) -> Element<'_, Message> {
+/// - Plans are a muted, italic line without a bubble.
+///
+/// Hovering a user or assistant bubble reveals message actions: copy for
+/// both, edit for user messages, and regenerate when `can_regenerate` is set
+/// (the latest assistant reply). Assistant replies show their token count
+/// next to the timestamp, with a usage popover on hover, and the thinking
+/// of a reasoning model folded above the text. User messages say whether
+/// they reached the agent, with a retry button when they did not.
+pub fn view(msg: &DisplayMessage, can_regenerate: bool) -> Element<'_, Message> {
     match msg.role {
         MessageRole::ToolCall | MessageRole::ToolResult => {
             return tool_card::view(msg);
         }
+        MessageRole::Plan => return plan(msg),
         MessageRole::User | MessageRole::Assistant => {}
     }
 
-    let timestamp_label = msg.timestamp.format("%H:%M").to_string();
+    let time = msg.timestamp.format("%H:%M").to_string();
+    let timestamp_label = match &msg.delivery {
+        None => time,
+        Some(Delivery::Sending) => t!("bubble-sending", time = time),
+        Some(Delivery::Sent) => t!("bubble-sent", time = time),
+        Some(Delivery::Received) => t!("bubble-received", time = time),
+        Some(Delivery::Failed(_)) => t!("bubble-failed", time = time),
+    };
 
     let content_element: Element<'_, Message> = match msg.role {
         MessageRole::User => text(&msg.text).size(14).into(),
         MessageRole::Assistant => render_assistant_markdown(msg),
         // Handled above with early return.
-        MessageRole::ToolCall | MessageRole::ToolResult => unreachable!(),
+        MessageRole::ToolCall | MessageRole::ToolResult | MessageRole::Plan => unreachable!(),
     };
 
-    let body = column![
-        content_element,
-        text(timestamp_label)
-            .size(10)
-            .color(AiosColors::TEXT_SECONDARY),
-    ]
-    .spacing(4);
+    let mut body = column![].spacing(4);
+    if let Some(reasoning) = &msg.reasoning {
+        body = body.push(reasoning_view(msg, reasoning));
+    }
+    body = body.push(content_element);
+    if !msg.attachment_names.is_empty() {
+        body = body.push(
+            text(t!("bubble-attached", names = msg.attachment_names.join(", ")))
+                .size(11)
+                .color(AiosColors::text_secondary()),
+        );
+    }
+    let timestamp = text(timestamp_label)
+        .size(10)
+        .color(AiosColors::text_secondary());
+    let mut body = match &msg.usage {
+        Some(usage) => body.push(row![timestamp, usage_badge(usage)].spacing(6)),
+        None => body.push(timestamp),
+    };
+    if let Some(Delivery::Failed(reason)) = &msg.delivery {
+        body = body.push(failed_view(msg.id, reason));
+    }
 
     let bubble_style: fn(&Theme) -> container::Style = match msg.role {
         MessageRole::User => theme::container_user_bubble,
         MessageRole::Assistant => theme::container_assistant_bubble,
-        MessageRole::ToolCall | MessageRole::ToolResult => unreachable!(),
+        MessageRole::ToolCall | MessageRole::ToolResult | MessageRole::Plan => unreachable!(),
     };
 
     let bubble = container(body)
-        .padding(10)
+        .padding(aios_theme::metrics().space(10.0))
         .max_width(520)
         .style(bubble_style);
 
+    let bubble = hover(bubble, action_bar(msg, can_regenerate));
+
     match msg.role {
         MessageRole::User => row![Space::new().width(Length::Fill), bubble]
             .spacing(8)
@@ -54,10 +90,68 @@ pub fn view(msg: &DisplayMessage) -> Element<'_, Message> {
         MessageRole::Assistant => row![bubble, Space::new().width(Length::Fill)]
             .spacing(8)
             .into(),
-        MessageRole::ToolCall | MessageRole::ToolResult => unreachable!(),
+        MessageRole::ToolCall | MessageRole::ToolResult | MessageRole::Plan => unreachable!(),
     }
 }
 
+/// Why a message did not reach the agent, and a button to send it again.
+fn failed_view(id: Uuid, reason: &str) -> Element<'_, Message> {
+    row![
+        text(reason)
+            .size(11)
+            .color(AiosColors::danger())
+            .width(Length::Fill),
+        button(text(t!("bubble-retry")).size(11))
+            .on_press(Message::RetryMessage(id))
+            .padding([2, 6])
+            .style(theme::message_action_button),
+    ]
+    .spacing(6)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// What the agent is about to do, set apart from its replies.
+fn plan(msg: &DisplayMessage) -> Element<'_, Message> {
+    let italic = Font {
+        style: font::Style::Italic,
+        ..Font::DEFAULT
+    };
+    container(
+        text(&msg.text)
+            .size(13)
+            .font(italic)
+            .color(AiosColors::text_secondary()),
+    )
+    .padding([2, 10])
+    .max_width(520)
+    .into()
+}
+
+/// Thinking behind a reply, unfolded on request.
+fn reasoning_view<'a>(msg: &'a DisplayMessage, reasoning: &'a str) -> Element<'a, Message> {
+    let label = if msg.reasoning_shown {
+        t!("bubble-reasoning-hide")
+    } else {
+        t!("bubble-reasoning-show")
+    };
+    let toggle = button(text(label).size(11))
+        .on_press(Message::ToggleReasoning(msg.id))
+        .padding([2, 6])
+        .style(theme::message_action_button);
+    if !msg.reasoning_shown {
+        return toggle.into();
+    }
+    column![
+        toggle,
+        text(reasoning)
+            .size(12)
+            .color(AiosColors::text_secondary()),
+    ]
+    .spacing(4)
+    .into()
+}
+
 /// Renders assistant message content as markdown.
 ///
 /// If the message has pre-parsed markdown content, renders it with the Iced markdown widget.
@@ -67,7 +161,7 @@ fn render_assistant_markdown(msg: &DisplayMessage) -> Element<'_, Message> {
         Some(content) => {
             let settings = markdown::Settings::with_text_size(
                 14,
-                markdown::Style::from_palette(Theme::TokyoNight.palette()),
+                markdown::Style::from_palette(aios_theme::iced_theme().palette()),
             );
 
             markdown::view(content.items(), settings)
@@ -76,3 +170,54 @@ fn render_assistant_markdown(msg: &DisplayMessage) -> Element<'_, Message> {
         None => text(&msg.text).size(14).into(),
     }
 }
+
+/// Token count of a reply; hovering shows model, token split and cost.
+fn usage_badge(usage: &UsageReport) -> Element<'_, Message> {
+    let secondary = |label: String| text(label).size(12).color(AiosColors::text_secondary());
+
+    let cost = match usage.cost_usd {
+        Some(cost) => t!("usage-detail-cost", cost = state::format_cost(cost)),
+        None => t!("usage-detail-free"),
+    };
+    let details = column![
+        text(t!("usage-detail-model", model = usage.model.as_str())).size(12),
+        secondary(t!("usage-detail-input", count = state::format_tokens(usage.tokens.input_tokens))),
+        secondary(t!("usage-detail-output", count = state::format_tokens(usage.tokens.output_tokens))),
+        secondary(t!("usage-detail-calls", count = usage.llm_calls.to_string())),
+        secondary(cost),
+    ]
+    .spacing(2);
+
+    tooltip(
+        text(t!("usage-tokens", count = state::format_tokens(usage.tokens.total())))
+            .size(10)
+            .color(AiosColors::text_secondary()),
+        container(details).padding(8).style(theme::container_tooltip),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Hover overlay with the actions available for this message.
+fn action_bar(msg: &DisplayMessage, can_regenerate: bool) -> Element<'_, Message> {
+    let action = |label: String, message: Message| {
+        button(text(label).size(11))
+            .on_press(message)
+            .padding([2, 6])
+            .style(theme::message_action_button)
+    };
+
+    let mut actions = row![action(t!("bubble-copy"), Message::CopyMessage(msg.id))].spacing(4);
+    if msg.role == MessageRole::User {
+        actions = actions.push(action(t!("bubble-edit"), Message::EditMessage(msg.id)));
+    }
+    if msg.role == MessageRole::Assistant && can_regenerate {
+        actions = actions.push(action(t!("bubble-regenerate"), Message::RegenerateLast));
+    }
+
+    container(actions)
+        .width(Length::Fill)
+        .align_right(Length::Fill)
+        .padding(4)
+        .into()
+}
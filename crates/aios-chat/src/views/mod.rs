@@ -1,5 +1,8 @@
 pub mod chat_view;
+pub mod connection_banner;
 pub mod input_bar;
 pub mod message_bubble;
 pub mod oobe;
+pub mod search_bar;
+pub mod system_card;
 pub mod tool_card;
@@ -1,7 +1,7 @@
 use iced::widget::{button, column, container, progress_bar, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length};
 
-use aios_common::ProviderType;
+use aios_common::{ProviderType, t};
 
 use crate::app::{Message, OobeState, OobeStep};
 use crate::theme::{self, AiosColors};
@@ -30,27 +30,25 @@ pub fn view(state: &OobeState) -> Element<'_, Message> {
 fn welcome_view() -> Element<'static, Message> {
     let title = text("AIOS")
         .size(36)
-        .color(AiosColors::ACCENT);
+        .color(AiosColors::accent());
 
-    let greeting = text("Привет! Я AIOS -- твой ИИ-ассистент.")
+    let greeting = text(t!("oobe-greeting"))
         .size(16)
-        .color(AiosColors::TEXT_PRIMARY);
+        .color(AiosColors::text_primary());
 
-    let description = text(
-        "Давай настроим систему.\nДля работы мне нужен доступ к языковой модели (LLM).",
-    )
+    let description = text(t!("oobe-intro"))
     .size(14)
-    .color(AiosColors::TEXT_SECONDARY);
+    .color(AiosColors::text_secondary());
 
     let start_btn = button(
-        text("Начать настройку").size(15),
+        text(t!("oobe-start-setup")).size(15),
     )
     .on_press(Message::OobeNext)
     .padding([10, 24])
     .style(theme::send_button);
 
     let skip_btn = button(
-        text("Пропустить").size(13),
+        text(t!("oobe-skip")).size(13),
     )
     .on_press(Message::OobeSkip)
     .padding([8, 20])
@@ -79,9 +77,9 @@ fn welcome_view() -> Element<'static, Message> {
 
 /// Provider selection step.
 fn select_provider_view() -> Element<'static, Message> {
-    let heading = text("Выбери провайдера LLM:")
+    let heading = text(t!("oobe-choose-provider"))
         .size(20)
-        .color(AiosColors::TEXT_PRIMARY);
+        .color(AiosColors::text_primary());
 
     let claude_card = provider_card(
         "Claude (Anthropic)",
@@ -96,8 +94,8 @@ fn select_provider_view() -> Element<'static, Message> {
     );
 
     let ollama_card = provider_card(
-        "Ollama (локальный)",
-        "Без API-ключа, работает локально",
+        &t!("oobe-ollama-name"),
+        &t!("oobe-ollama-subtitle"),
         ProviderType::Ollama,
     );
 
@@ -128,11 +126,11 @@ fn provider_card(
 ) -> Element<'static, Message> {
     let label = text(name.to_owned())
         .size(16)
-        .color(AiosColors::TEXT_PRIMARY);
+        .color(AiosColors::text_primary());
 
     let sub = text(subtitle.to_owned())
         .size(12)
-        .color(AiosColors::TEXT_SECONDARY);
+        .color(AiosColors::text_secondary());
 
     let inner = column![label, sub].spacing(4).padding(14);
 
@@ -150,40 +148,40 @@ fn provider_card(
 /// API key input step.
 fn enter_api_key_view(state: &OobeState) -> Element<'_, Message> {
     let provider_name = match state.selected_provider {
-        Some(ProviderType::Claude) => "Claude",
-        Some(ProviderType::OpenAi) => "OpenAI",
-        _ => "провайдера",
+        Some(ProviderType::Claude) => "Claude".to_owned(),
+        Some(ProviderType::OpenAi) => "OpenAI".to_owned(),
+        _ => t!("oobe-provider-generic"),
     };
 
-    let heading = text(format!("Введи API-ключ для {provider_name}:"))
+    let heading = text(t!("oobe-enter-api-key", provider = provider_name))
         .size(20)
-        .color(AiosColors::TEXT_PRIMARY);
+        .color(AiosColors::text_primary());
 
     let placeholder = match state.selected_provider {
-        Some(ProviderType::Claude) => "sk-ant-...",
-        Some(ProviderType::OpenAi) => "sk-...",
-        _ => "API key",
+        Some(ProviderType::Claude) => "sk-ant-...".to_owned(),
+        Some(ProviderType::OpenAi) => "sk-...".to_owned(),
+        _ => t!("oobe-api-key-placeholder"),
     };
 
-    let input = text_input(placeholder, &state.api_key_input)
+    let input = text_input(&placeholder, &state.api_key_input)
         .on_input(Message::OobeApiKeyChanged)
         .on_submit(Message::OobeSubmitApiKey)
         .padding(10)
         .size(14)
         .style(theme::input_style);
 
-    let hint = text("Ключ хранится локально в ~/.config/aios/agent.toml")
+    let hint = text(t!("oobe-api-key-hint"))
         .size(12)
-        .color(AiosColors::TEXT_SECONDARY);
+        .color(AiosColors::text_secondary());
 
     let can_submit = !state.api_key_input.trim().is_empty();
 
-    let back_btn = button(text("Назад").size(14))
+    let back_btn = button(text(t!("oobe-back")).size(14))
         .on_press(Message::OobeBack)
         .padding([8, 20])
         .style(theme::oobe_secondary_button);
 
-    let save_btn = button(text("Сохранить").size(14))
+    let save_btn = button(text(t!("oobe-save")).size(14))
         .on_press_maybe(if can_submit {
             Some(Message::OobeSubmitApiKey)
         } else {
@@ -215,18 +213,18 @@ fn enter_api_key_view(state: &OobeState) -> Element<'_, Message> {
 
 /// Ollama setup step -- shows installation check status.
 fn ollama_setup_view(state: &OobeState) -> Element<'_, Message> {
-    let heading = text("Ollama Setup")
+    let heading = text(t!("oobe-ollama-setup"))
         .size(22)
-        .color(AiosColors::ACCENT);
+        .color(AiosColors::accent());
 
     let status_msg = state
         .ollama_status
-        .as_deref()
-        .unwrap_or("Checking Ollama installation...");
+        .clone()
+        .unwrap_or_else(|| t!("oobe-ollama-checking"));
 
-    let status = text(status_msg.to_owned())
+    let status = text(status_msg)
         .size(14)
-        .color(AiosColors::TEXT_SECONDARY);
+        .color(AiosColors::text_secondary());
 
     let content = column![
         heading,
@@ -244,10 +242,34 @@ fn ollama_setup_view(state: &OobeState) -> Element<'_, Message> {
 }
 
 /// Ollama model selection step -- shows fetched models and custom input.
+/// A model to pick, with a warning if it likely won't run well here.
+fn model_card(state: &OobeState, model: String) -> Element<'_, Message> {
+    let mut label = column![
+        text(model.clone())
+            .size(15)
+            .color(AiosColors::text_primary())
+    ]
+    .spacing(4);
+    if let Some(warning) = state.memory.and_then(|memory| memory.warning(&model, None)) {
+        label = label.push(text(warning).size(12).color(AiosColors::danger()));
+    }
+    let inner = container(label)
+        .width(Length::Fill)
+        .padding(14)
+        .style(theme::container_oobe_card);
+    let mut btn = button(inner)
+        .width(Length::Fill)
+        .style(theme::oobe_card_button);
+    if !state.pulling {
+        btn = btn.on_press(Message::OobeOllamaSelectModel(model));
+    }
+    btn.into()
+}
+
 fn ollama_model_select_view(state: &OobeState) -> Element<'_, Message> {
-    let heading = text("Выбери модель")
+    let heading = text(t!("oobe-choose-model"))
         .size(22)
-        .color(AiosColors::ACCENT);
+        .color(AiosColors::accent());
 
     let mut content = column![heading, Space::new().height(24)]
         .align_x(Alignment::Center)
@@ -257,7 +279,7 @@ fn ollama_model_select_view(state: &OobeState) -> Element<'_, Message> {
     if let Some(status_msg) = &state.ollama_status {
         let status = text(status_msg.clone())
             .size(13)
-            .color(AiosColors::TEXT_SECONDARY);
+            .color(AiosColors::text_secondary());
         content = content.push(status);
 
         if state.pulling {
@@ -270,46 +292,52 @@ fn ollama_model_select_view(state: &OobeState) -> Element<'_, Message> {
         content = content.push(Space::new().height(8));
     }
 
+    // The model sized for this machine comes first.
+    let recommended = state.memory.map(|memory| memory.recommended_model());
+    if let (Some(memory), Some(model)) = (&state.memory, recommended) {
+        content = content.push(
+            text(t!(
+                "hardware-recommended",
+                memory = memory.describe(),
+                model = model
+            ))
+            .size(13)
+            .color(AiosColors::text_secondary()),
+        );
+        content = content.push(model_card(state, model.to_owned()));
+        content = content.push(Space::new().height(12));
+    }
+
     // Show fetched models as cards
     if !state.available_models.is_empty() {
-        let subtitle = text("Популярные модели:")
+        let subtitle = text(t!("oobe-popular-models"))
             .size(14)
-            .color(AiosColors::TEXT_SECONDARY);
+            .color(AiosColors::text_secondary());
         content = content.push(subtitle);
 
         for model in &state.available_models {
-            let label = text(model.clone())
-                .size(15)
-                .color(AiosColors::TEXT_PRIMARY);
-            let inner = container(label)
-                .width(Length::Fill)
-                .padding(14)
-                .style(theme::container_oobe_card);
-            let mut btn = button(inner)
-                .width(Length::Fill)
-                .style(theme::oobe_card_button);
-            if !state.pulling {
-                btn = btn.on_press(Message::OobeOllamaSelectModel(model.clone()));
+            if Some(model.as_str()) == recommended {
+                continue;
             }
-            content = content.push(btn);
+            content = content.push(model_card(state, model.clone()));
         }
 
         content = content.push(Space::new().height(12));
     }
 
     // Custom model input
-    let custom_label = text("Или введи имя модели:")
+    let custom_label = text(t!("oobe-custom-model"))
         .size(14)
-        .color(AiosColors::TEXT_SECONDARY);
+        .color(AiosColors::text_secondary());
 
-    let custom_input = text_input("например: codellama:7b", &state.custom_model_input)
+    let custom_input = text_input(&t!("oobe-custom-model-placeholder"), &state.custom_model_input)
         .on_input(Message::OobeOllamaCustomModelChanged)
         .padding(10)
         .size(14)
         .style(theme::input_style);
 
     let can_pull_custom = !state.custom_model_input.trim().is_empty();
-    let pull_btn = button(text("Pull").size(14))
+    let pull_btn = button(text(t!("oobe-pull")).size(14))
         .on_press_maybe(if can_pull_custom {
             Some(Message::OobeOllamaSelectModel(state.custom_model_input.trim().to_owned()))
         } else {
@@ -323,9 +351,15 @@ fn ollama_model_select_view(state: &OobeState) -> Element<'_, Message> {
 
     content = content.push(custom_label);
     content = content.push(custom_row);
+    if let Some(warning) = state
+        .memory
+        .and_then(|memory| memory.warning(state.custom_model_input.trim(), None))
+    {
+        content = content.push(text(warning).size(12).color(AiosColors::danger()));
+    }
 
     // Back button
-    let back_btn = button(text("Назад").size(14))
+    let back_btn = button(text(t!("oobe-back")).size(14))
         .on_press(Message::OobeBack)
         .padding([8, 20])
         .style(theme::oobe_secondary_button);
@@ -342,15 +376,15 @@ fn ollama_model_select_view(state: &OobeState) -> Element<'_, Message> {
 
 /// Completion step -- shows the chosen provider and a start button.
 fn complete_view(state: &OobeState) -> Element<'static, Message> {
-    let checkmark = text("Настройка завершена!")
+    let checkmark = text(t!("oobe-complete"))
         .size(22)
-        .color(AiosColors::SUCCESS);
+        .color(AiosColors::success());
 
     let provider_label = match state.selected_provider {
-        Some(ProviderType::Claude) => "Claude",
-        Some(ProviderType::OpenAi) => "OpenAI",
-        Some(ProviderType::Ollama) => "Ollama",
-        None => "по умолчанию",
+        Some(ProviderType::Claude) => "Claude".to_owned(),
+        Some(ProviderType::OpenAi) => "OpenAI".to_owned(),
+        Some(ProviderType::Ollama) => "Ollama".to_owned(),
+        None => t!("oobe-provider-default"),
     };
 
     let ollama_model_name = state.ollama_model.clone().unwrap_or_else(|| "llama3".to_owned());
@@ -362,23 +396,23 @@ fn complete_view(state: &OobeState) -> Element<'static, Message> {
     };
 
     let info = column![
-        text(format!("Провайдер: {provider_label}")).size(14).color(AiosColors::TEXT_PRIMARY),
-        text(format!("Модель: {model_label}")).size(14).color(AiosColors::TEXT_SECONDARY),
+        text(t!("oobe-summary-provider", provider = provider_label)).size(14).color(AiosColors::text_primary()),
+        text(t!("oobe-summary-model", model = model_label)).size(14).color(AiosColors::text_secondary()),
     ]
     .spacing(4);
 
-    let suggestions_header = text("Попробуй попросить меня что-нибудь:")
+    let suggestions_header = text(t!("oobe-try-asking"))
         .size(14)
-        .color(AiosColors::TEXT_PRIMARY);
+        .color(AiosColors::text_primary());
 
     let suggestions = column![
-        text("  - \"Открой google.com\"").size(13).color(AiosColors::TEXT_SECONDARY),
-        text("  - \"Покажи содержимое /home\"").size(13).color(AiosColors::TEXT_SECONDARY),
-        text("  - \"Какая сейчас погода?\"").size(13).color(AiosColors::TEXT_SECONDARY),
+        text(format!("  - \"{}\"", t!("oobe-example-browse"))).size(13).color(AiosColors::text_secondary()),
+        text(format!("  - \"{}\"", t!("oobe-example-files"))).size(13).color(AiosColors::text_secondary()),
+        text(format!("  - \"{}\"", t!("oobe-example-weather"))).size(13).color(AiosColors::text_secondary()),
     ]
     .spacing(2);
 
-    let start_btn = button(text("Начать общение").size(15))
+    let start_btn = button(text(t!("oobe-start-chatting")).size(15))
         .on_press(Message::OobeComplete)
         .padding([10, 24])
         .style(theme::send_button);
@@ -0,0 +1,51 @@
+use iced::widget::{button, container, row, text, text_input, tooltip, Id};
+use iced::{Element, Length};
+
+use aios_common::t;
+
+use crate::app::Message;
+use crate::theme::{self, AiosColors};
+
+/// Renders the search bar shown under the header while search is active.
+///
+/// Displays the query field, the number of matching messages, and a close
+/// button (Escape also closes). `id` lets Ctrl+F focus the field.
+pub fn view<'a>(query: &'a str, match_count: usize, id: &Id) -> Element<'a, Message> {
+    let input = text_input(&t!("search-placeholder"), query)
+        .id(id.clone())
+        .on_input(Message::SearchChanged)
+        .padding(8)
+        .size(13)
+        .style(theme::input_style);
+
+    let count_label = if query.trim().is_empty() {
+        String::new()
+    } else {
+        t!("search-match-count", count = match_count)
+    };
+
+    let close_btn = tooltip(
+        button(text("X").size(12).color(AiosColors::text_secondary()))
+            .on_press(Message::CloseSearch)
+            .padding([4, 8])
+            .style(theme::close_button),
+        container(text(t!("search-close")).size(12))
+            .padding(8)
+            .style(theme::container_tooltip),
+        tooltip::Position::Bottom,
+    );
+
+    let bar = row![
+        input,
+        text(count_label).size(12).color(AiosColors::text_secondary()),
+        close_btn,
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    container(bar)
+        .width(Length::Fill)
+        .padding([6, 12])
+        .style(theme::container_secondary)
+        .into()
+}
@@ -0,0 +1,107 @@
+use iced::widget::{column, text};
+use iced::Element;
+
+use aios_common::{SystemReport, t};
+
+use crate::app::Message;
+use crate::theme::AiosColors;
+
+/// Renders a `system_info` result as a summary instead of raw JSON: host,
+/// uptime and load, memory, disks, battery, busiest processes and
+/// temperatures, each only if the report has it.
+pub fn view(report: &SystemReport) -> Element<'_, Message> {
+    let mut col = column![].spacing(2);
+
+    if let Some(host) = &report.host {
+        let mut title = host.hostname.clone();
+        for part in [&host.os, &host.kernel] {
+            if !part.is_empty() {
+                title = format!("{title} · {part}");
+            }
+        }
+        col = col.push(text(title).size(13).color(AiosColors::text_primary()));
+        col = col.push(line(format!(
+            "{} · {}",
+            host.cpu_model,
+            t!("system-card-uptime", duration = format_uptime(host.uptime_secs)),
+        )));
+    }
+    if let Some(load) = &report.load {
+        let averages = format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen);
+        col = col.push(line(t!("system-card-load", load = averages)));
+    }
+    if let Some(memory) = &report.memory {
+        col = col.push(line(t!(
+            "system-card-memory",
+            used = format_size(memory.total.saturating_sub(memory.available)),
+            total = format_size(memory.total),
+            percent = memory.used_percent().to_string(),
+        )));
+    }
+    for disk in report.disks.iter().flatten() {
+        col = col.push(line(t!(
+            "system-card-disk",
+            mount = disk.mount.as_str(),
+            free = format_size(disk.available),
+            total = format_size(disk.total),
+        )));
+    }
+    if let Some(battery) = &report.battery {
+        col = col.push(line(t!("system-card-battery", percent = battery.percent.to_string())));
+    }
+    if let Some(processes) = report.processes.as_ref().filter(|p| !p.is_empty()) {
+        col = col.push(heading(t!("system-card-processes")));
+        for process in processes {
+            col = col.push(line(t!(
+                "system-card-process",
+                name = process.name.as_str(),
+                cpu = format!("{:.1}", process.cpu_percent),
+                memory = format_size(process.memory),
+            )));
+        }
+    }
+    if let Some(zones) = report.thermal.as_ref().filter(|z| !z.is_empty()) {
+        col = col.push(heading(t!("system-card-thermal")));
+        for zone in zones {
+            col = col.push(line(format!("{}: {:.0} °C", zone.name, zone.celsius)));
+        }
+    }
+
+    col.into()
+}
+
+fn heading<'a>(label: String) -> Element<'a, Message> {
+    text(label).size(12).color(AiosColors::text_primary()).into()
+}
+
+fn line<'a>(label: String) -> Element<'a, Message> {
+    text(label).size(12).color(AiosColors::text_secondary()).into()
+}
+
+/// `bytes` for people: "4.7 GB", "310 MB".
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit >= 3 {
+        format!("{size:.1} {}", UNITS[unit])
+    } else {
+        format!("{size:.0} {}", UNITS[unit])
+    }
+}
+
+/// "3d 4h", "5h 12m" or "7m".
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
@@ -1,12 +1,18 @@
-use iced::widget::{column, container, row, text, Space};
-use iced::{Element, Length, Theme};
+use iced::widget::{button, column, container, image, row, text, Space};
+use iced::{ContentFit, Element, Length, Theme};
+
+use aios_common::t;
 
 use crate::app::Message;
 use crate::state::{DisplayMessage, ToolStatus};
 use crate::theme::{self, AiosColors};
+use crate::views::system_card;
 
 /// Renders a tool call or tool result as a visually distinct card.
 ///
+/// Images returned by the tool are shown as thumbnails; clicking one opens
+/// it full size.
+///
 /// Cards are color-coded by status:
 /// - **Pending**: amber border and background
 /// - **Completed**: green border and background
@@ -22,7 +28,7 @@ pub fn view(msg: &DisplayMessage) -> Element<'_, Message> {
         text(icon).size(14),
         text(tool_name)
             .size(14)
-            .color(AiosColors::TEXT_PRIMARY),
+            .color(AiosColors::text_primary()),
     ]
     .spacing(6)
     .align_y(iced::Alignment::Center);
@@ -33,7 +39,7 @@ pub fn view(msg: &DisplayMessage) -> Element<'_, Message> {
     // Timestamp
     let timestamp_label = msg.timestamp.format("%H:%M").to_string();
 
-    let card_content = column![header, body, text(timestamp_label).size(10).color(AiosColors::TEXT_SECONDARY)]
+    let card_content = column![header, body, text(timestamp_label).size(10).color(AiosColors::text_secondary())]
         .spacing(4);
 
     let style: fn(&Theme) -> container::Style = match status {
@@ -43,7 +49,7 @@ pub fn view(msg: &DisplayMessage) -> Element<'_, Message> {
     };
 
     let card = container(card_content)
-        .padding(10)
+        .padding(aios_theme::metrics().space(10.0))
         .max_width(520)
         .style(style);
 
@@ -54,12 +60,12 @@ pub fn view(msg: &DisplayMessage) -> Element<'_, Message> {
 }
 
 /// Returns (icon, status_label) for the given tool status.
-fn status_decoration(status: ToolStatus) -> (&'static str, &'static str) {
+fn status_decoration(status: ToolStatus) -> (&'static str, String) {
     match status {
-        ToolStatus::Pending => ("[~]", "Pending..."),
-        ToolStatus::Completed => ("[ok]", "Completed"),
-        ToolStatus::Failed => ("[err]", "Failed"),
-        ToolStatus::Rejected => ("[x]", "Rejected"),
+        ToolStatus::Pending => ("[~]", t!("tool-status-pending")),
+        ToolStatus::Completed => ("[ok]", t!("tool-status-completed")),
+        ToolStatus::Failed => ("[err]", t!("tool-status-failed")),
+        ToolStatus::Rejected => ("[x]", t!("tool-status-rejected")),
     }
 }
 
@@ -67,51 +73,85 @@ fn status_decoration(status: ToolStatus) -> (&'static str, &'static str) {
 fn build_body<'a>(
     msg: &'a DisplayMessage,
     status: ToolStatus,
-    status_label: &'a str,
+    status_label: String,
 ) -> Element<'a, Message> {
     match status {
         ToolStatus::Pending => {
-            // Show pretty-printed arguments and a pending indicator.
+            // Show pretty-printed arguments, live output and a pending
+            // indicator.
             let mut col = column![].spacing(2);
             if let Some(args) = &msg.tool_args {
                 col = col.push(
                     text(args)
                         .size(12)
-                        .color(AiosColors::TEXT_SECONDARY),
+                        .color(AiosColors::text_secondary()),
+                );
+            }
+            // Output of a tool still running, e.g. a build.
+            if !msg.text.is_empty() {
+                col = col.push(
+                    text(&msg.text)
+                        .size(12)
+                        .font(iced::Font::MONOSPACE)
+                        .color(AiosColors::text_primary()),
                 );
             }
             col = col.push(
                 text(status_label)
                     .size(11)
-                    .color(AiosColors::TOOL_PENDING_BORDER),
+                    .color(AiosColors::tool_pending_border()),
             );
             col.into()
         }
         ToolStatus::Completed => {
-            // Show (possibly truncated) output.
+            // Show a summary card, or the (possibly truncated) output.
             let mut col = column![].spacing(2);
-            if !msg.text.is_empty() {
+            if let Some(report) = &msg.system_report {
+                col = col.push(system_card::view(report));
+            } else if !msg.text.is_empty() {
                 col = col.push(
                     text(&msg.text)
                         .size(12)
-                        .color(AiosColors::TEXT_SECONDARY),
+                        .color(AiosColors::text_secondary()),
                 );
             }
+            if !msg.images.is_empty() {
+                col = col.push(thumbnails(&msg.images));
+            }
             col.into()
         }
         ToolStatus::Failed | ToolStatus::Rejected => {
             // Show error output.
             let label = if status == ToolStatus::Rejected {
-                "Action rejected by user"
+                t!("tool-rejected-by-user")
             } else if msg.text.is_empty() {
-                "Tool execution failed"
+                t!("tool-execution-failed")
             } else {
-                &msg.text
+                msg.text.clone()
             };
             text(label)
                 .size(12)
-                .color(AiosColors::TOOL_FAILED_BORDER)
+                .color(AiosColors::tool_failed_border())
                 .into()
         }
     }
 }
+
+/// Row of clickable image thumbnails.
+fn thumbnails(images: &[image::Handle]) -> Element<'_, Message> {
+    let mut thumbs = row![].spacing(6);
+    for handle in images {
+        thumbs = thumbs.push(
+            button(
+                image(handle.clone())
+                    .width(160)
+                    .height(120)
+                    .content_fit(ContentFit::Contain),
+            )
+            .on_press(Message::OpenImage(handle.clone()))
+            .padding(0)
+            .style(theme::oobe_card_button),
+        );
+    }
+    thumbs.wrap().into()
+}
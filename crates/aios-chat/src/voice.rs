@@ -0,0 +1,71 @@
+//! Microphone capture for voice input.
+//!
+//! Audio is recorded with `pw-record` as 16 kHz mono signed 16-bit PCM and
+//! forwarded to the agent in short chunks; transcription happens agent-side.
+
+use std::process::Stdio;
+
+use futures::channel::mpsc;
+use futures::SinkExt;
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+
+/// Bytes per chunk: 0.25 s of 16 kHz mono s16 audio.
+const CHUNK_BYTES: usize = 16_000 / 4 * 2;
+
+/// Events produced by the recorder stream.
+#[derive(Debug, Clone)]
+pub enum RecorderEvent {
+    /// A chunk of raw little-endian PCM samples.
+    Chunk(Vec<u8>),
+    /// The recorder could not be started or stopped unexpectedly.
+    Failed(String),
+}
+
+/// Record from the default PipeWire source until the stream is dropped.
+///
+/// Designed for `Subscription::run_with(session_id, recorder)`: the
+/// subscription is removed when recording stops, which drops the stream and
+/// kills `pw-record`.
+pub fn recorder(_session_id: &Uuid) -> impl futures::Stream<Item = RecorderEvent> {
+    iced::stream::channel(16, async move |mut output: mpsc::Sender<RecorderEvent>| {
+        if let Err(reason) = record(&mut output).await {
+            tracing::warn!("Voice recording failed: {reason}");
+            let _ = output.send(RecorderEvent::Failed(reason)).await;
+        }
+    })
+}
+
+async fn record(output: &mut mpsc::Sender<RecorderEvent>) -> Result<(), String> {
+    let mut child = tokio::process::Command::new("pw-record")
+        .args(["--rate=16000", "--channels=1", "--format=s16", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("cannot start pw-record: {e}"))?;
+
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "pw-record has no stdout".to_owned())?;
+
+    let mut buf = vec![0u8; CHUNK_BYTES];
+    loop {
+        let mut filled = 0;
+        while filled < CHUNK_BYTES {
+            let n = stdout
+                .read(&mut buf[filled..])
+                .await
+                .map_err(|e| format!("read error: {e}"))?;
+            if n == 0 {
+                return Err("microphone stream ended".to_owned());
+            }
+            filled += n;
+        }
+        if output.send(RecorderEvent::Chunk(buf.clone())).await.is_err() {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Window management: the main chat window plus conversations popped out
+//! into windows of their own.
+//!
+//! Every window holds an independent [`AiosChat`] with its own agent
+//! connection, so one conversation can stream a long task while another is
+//! used for something else.
+
+use std::collections::BTreeMap;
+
+use iced::widget::Space;
+use iced::{window, Element, Size, Subscription, Task};
+
+use aios_common::t;
+
+use crate::app::{self, AiosChat};
+use crate::background::{self, Activation};
+
+/// Pop-out titles quote at most this much of the first message.
+const TOPIC_TITLE_CHARS: usize = 40;
+
+/// Root state of the chat daemon.
+pub struct ChatWindows {
+    /// The window opened at startup. Closing it goes to the background (or
+    /// exits); closing any other window only closes that window.
+    main: window::Id,
+    windows: BTreeMap<window::Id, AiosChat>,
+}
+
+/// Top-level messages.
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// A message for the chat in the given window.
+    Chat(window::Id, app::Message),
+    /// Another `aios-chat` invocation asked this instance to show itself.
+    Activated(Activation),
+    /// The config changed: redraw with the new color scheme and language,
+    /// and reload the macros.
+    AppearanceChanged,
+}
+
+impl ChatWindows {
+    /// Open the main window and start its chat.
+    pub fn new() -> (Self, Task<Message>) {
+        let (main, opened) = window::open(window::Settings {
+            size: Size::new(800.0, 600.0),
+            position: window::Position::Centered,
+            exit_on_close_request: false,
+            ..window::Settings::default()
+        });
+        let (chat, task) = AiosChat::new();
+
+        let state = Self {
+            main,
+            windows: BTreeMap::from([(main, chat)]),
+        };
+        let task = task.map(move |message| Message::Chat(main, message));
+        // `--ask` on a cold start: pre-fill the question like a running
+        // instance would.
+        let ask = match Activation::from_args() {
+            ask @ Activation::Ask(_) => Task::done(Message::Activated(ask)),
+            _ => Task::none(),
+        };
+        (state, Task::batch([opened.discard(), task, ask]))
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Chat(_, app::Message::PopOut) => self.pop_out(),
+            Message::Chat(id, app::Message::CloseWindow) if id != self.main => {
+                self.windows.remove(&id);
+                window::close(id)
+            }
+            Message::Chat(id, message) => self.update_chat(id, message),
+            Message::Activated(activation) => {
+                self.update_chat(self.main, app::Message::Activated(activation))
+            }
+            Message::AppearanceChanged => {
+                let ids: Vec<window::Id> = self.windows.keys().copied().collect();
+                let reloads: Vec<Task<Message>> = ids
+                    .into_iter()
+                    .map(|id| self.update_chat(id, app::Message::ConfigChanged))
+                    .collect();
+                Task::batch(reloads)
+            }
+        }
+    }
+
+    pub fn view(&self, id: window::Id) -> Element<'_, Message> {
+        match self.windows.get(&id) {
+            Some(chat) => chat.view().map(move |message| Message::Chat(id, message)),
+            // The window is being closed.
+            None => Space::new().into(),
+        }
+    }
+
+    /// "AIOS Chat" for the main window, which sway matches by title; pop-outs
+    /// are named after their conversation.
+    pub fn title(&self, id: window::Id) -> String {
+        if id == self.main {
+            return "AIOS Chat".to_owned();
+        }
+        match self.windows.get(&id).and_then(AiosChat::topic) {
+            Some(topic) => {
+                let topic: String = topic.chars().take(TOPIC_TITLE_CHARS).collect();
+                t!("chat-popout-title", topic = topic.trim())
+            }
+            None => t!("chat-popout-title-new"),
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let chats = self.windows.iter().map(|(&id, chat)| {
+            chat.subscription().with(id).map(|(id, message)| Message::Chat(id, message))
+        });
+        let events = iced::event::listen_with(|event, _status, id| {
+            AiosChat::window_event(event).map(|message| Message::Chat(id, message))
+        });
+        let appearance = aios_theme::subscription().map(|()| Message::AppearanceChanged);
+        let activations = Subscription::run(background::listen).map(Message::Activated);
+
+        Subscription::batch(chats.chain([events, appearance, activations]))
+    }
+
+    /// Open a window with a new conversation next to the current ones.
+    fn pop_out(&mut self) -> Task<Message> {
+        let (id, opened) = window::open(window::Settings {
+            size: Size::new(640.0, 560.0),
+            exit_on_close_request: false,
+            ..window::Settings::default()
+        });
+        tracing::info!(?id, "Opening a pop-out chat window");
+        self.windows.insert(id, AiosChat::popout());
+        opened.discard()
+    }
+
+    fn update_chat(&mut self, id: window::Id, message: app::Message) -> Task<Message> {
+        let Some(chat) = self.windows.get_mut(&id) else {
+            return Task::none();
+        };
+        chat.update(message).map(move |message| Message::Chat(id, message))
+    }
+}
@@ -0,0 +1,97 @@
+//! `aios-cli ask`: send one prompt and print the reply to stdout.
+
+use std::io::Write;
+
+use aios_common::{Attachment, ClientType, IpcPayload, MessageContent, UsageReport, t};
+use anyhow::{Context, Result, bail};
+use uuid::Uuid;
+
+use crate::{confirm, ipc};
+
+/// Send `prompt` with `attachments` in `conversation_id` and print the
+/// reply. With `confirm`, confirmation requests raised while it runs are
+/// answered in the terminal.
+pub async fn run(
+    prompt: String,
+    attachments: Vec<Attachment>,
+    conversation_id: Uuid,
+    confirm: bool,
+) -> Result<()> {
+    let confirmer = confirm.then(|| tokio::spawn(confirm::run()));
+    let result = ask(prompt, attachments, conversation_id).await;
+    if let Some(confirmer) = confirmer {
+        confirmer.abort();
+    }
+    result
+}
+
+async fn ask(prompt: String, attachments: Vec<Attachment>, conversation_id: Uuid) -> Result<()> {
+    let conn = ipc::connect(ClientType::Cli).await?;
+    let (mut reader, mut writer) = conn.into_split();
+    let request = IpcPayload::ChatRequest {
+        message: prompt,
+        conversation_id,
+        message_id: None,
+        attachments,
+    };
+    writer
+        .send(&ipc::message(request))
+        .await
+        .context("failed to send the prompt")?;
+
+    let mut stdout = std::io::stdout();
+    let mut streamed = false;
+    let mut cancelled = false;
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        let msg = tokio::select! {
+            msg = reader.recv() => msg.context("connection to the agent lost")?,
+            _ = &mut ctrl_c, if !cancelled => {
+                // The agent answers the interrupted request with a short
+                // reply, so keep reading until it arrives.
+                cancelled = true;
+                let cancel = IpcPayload::CancelRequest { conversation_id };
+                writer.send(&ipc::message(cancel)).await?;
+                continue;
+            }
+        };
+        match msg.payload {
+            IpcPayload::StreamChunk { delta, done, .. } => {
+                streamed = true;
+                print!("{delta}");
+                stdout.flush()?;
+                if done {
+                    println!();
+                }
+            }
+            IpcPayload::ChatResponse { message, usage, .. } => {
+                if !streamed && let MessageContent::Text { text } = message.content {
+                    println!("{text}");
+                }
+                if let Some(usage) = usage {
+                    print_usage(&usage);
+                }
+                return Ok(());
+            }
+            IpcPayload::Error { message, .. } => bail!("{message}"),
+            IpcPayload::Ping => writer.send(&ipc::message(IpcPayload::Pong)).await?,
+            _ => {}
+        }
+    }
+}
+
+/// Model, tokens and cost go to stderr so stdout holds only the reply.
+fn print_usage(usage: &UsageReport) {
+    let mut line = format!(
+        "{} · {}",
+        usage.model,
+        t!("usage-tokens", count = usage.tokens.total())
+    );
+    if let Some(cost) = usage.cost_usd {
+        let cost = format!("${cost:.4}");
+        line = format!("{line} · {}", t!("usage-cost", cost = cost));
+    }
+    eprintln!("{line}");
+}
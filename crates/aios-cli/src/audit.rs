@@ -0,0 +1,124 @@
+//! `aios-cli audit`: print recent audit log entries, optionally following
+//! new ones as the agent writes them.
+
+use std::time::Duration;
+
+use aios_common::{
+    AuditEntry, AuditFilter, AuditRange, AuditResult, ClientType, IpcConnection, IpcPayload,
+};
+use anyhow::{Result, bail};
+use chrono::{DateTime, Local, Utc};
+
+use crate::ipc;
+
+/// How often `--follow` asks for new entries.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Entries fetched per poll while following.
+const FOLLOW_LIMIT: usize = 500;
+
+pub struct AuditArgs {
+    pub filter: AuditFilter,
+    pub limit: usize,
+    pub follow: bool,
+    pub json: bool,
+}
+
+pub async fn run(args: AuditArgs) -> Result<()> {
+    let mut conn = ipc::connect(ClientType::Cli).await?;
+
+    let entries = query(&mut conn, &args.filter, AuditRange::default(), args.limit).await?;
+    // Printed oldest first, like `tail`.
+    for entry in entries.iter().rev() {
+        print_entry(entry, args.json)?;
+    }
+    if !args.follow {
+        return Ok(());
+    }
+
+    // The range is inclusive, so entries at the last printed timestamp come
+    // back on the next poll; skip the ones already printed.
+    let mut last = entries.first().map(|entry| entry.timestamp);
+    let mut seen: Vec<AuditEntry> = entries
+        .into_iter()
+        .take_while(|entry| Some(entry.timestamp) == last)
+        .collect();
+    loop {
+        tokio::time::sleep(FOLLOW_INTERVAL).await;
+        let range = AuditRange {
+            from: last,
+            to: None,
+        };
+        let entries = query(&mut conn, &args.filter, range, FOLLOW_LIMIT).await?;
+        let new: Vec<AuditEntry> = entries
+            .into_iter()
+            .filter(|entry| !seen.iter().any(|old| same_entry(old, entry)))
+            .collect();
+        let Some(newest) = new.first().map(|entry| entry.timestamp) else {
+            continue;
+        };
+        for entry in new.iter().rev() {
+            print_entry(entry, args.json)?;
+        }
+        if Some(newest) != last {
+            seen.clear();
+            last = Some(newest);
+        }
+        seen.extend(new.into_iter().filter(|entry| entry.timestamp == newest));
+    }
+}
+
+async fn query(
+    conn: &mut IpcConnection,
+    filter: &AuditFilter,
+    range: AuditRange,
+    limit: usize,
+) -> Result<Vec<AuditEntry>> {
+    let request = IpcPayload::AuditQuery {
+        filter: filter.clone(),
+        range,
+        limit,
+    };
+    match ipc::request(conn, request).await? {
+        IpcPayload::AuditEntries { entries, .. } => Ok(entries),
+        IpcPayload::Error { message, .. } => bail!("{message}"),
+        other => bail!("unexpected response: {other:?}"),
+    }
+}
+
+fn same_entry(a: &AuditEntry, b: &AuditEntry) -> bool {
+    a.timestamp == b.timestamp && a.action == b.action && a.arguments == b.arguments
+}
+
+/// One line per entry: local time, action, outcome and arguments, or the
+/// entry as JSON.
+fn print_entry(entry: &AuditEntry, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(entry)?);
+        return Ok(());
+    }
+    let outcome = match &entry.result {
+        AuditResult::Ok => "ok".to_string(),
+        AuditResult::Error(e) => format!("error: {e}"),
+        AuditResult::Rejected => "rejected".to_string(),
+        AuditResult::Timeout => "timeout".to_string(),
+    };
+    let mut line = format!(
+        "{} {} [{outcome}] {}",
+        local_time(entry.timestamp),
+        entry.action,
+        entry.arguments
+    );
+    if let Some(details) = &entry.details {
+        line = format!("{line} -- {details}");
+    }
+    println!("{line}");
+    Ok(())
+}
+
+fn local_time(timestamp: DateTime<Utc>) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
@@ -0,0 +1,167 @@
+//! `aios-cli confirm`: answer the agent's confirmation requests in the
+//! terminal, for sessions without the confirmation dialog (SSH).
+//!
+//! Requests are printed to stderr and answers read from the controlling
+//! terminal, so stdin and stdout stay free for scripts. Actions that follow
+//! from untrusted content must be confirmed by typing the tool name, like
+//! the keyword in the critical dialog. When the agent offers it, answering
+//! `a` approves the tool for the rest of the conversation.
+
+use aios_common::{ClientType, IpcPayload, RiskSummary, TrustLevel, t};
+use anyhow::{Context, Result};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader, Lines};
+
+use crate::ipc;
+
+/// Where answers are read from.
+type Answers = Lines<BufReader<Box<dyn AsyncRead + Unpin + Send>>>;
+
+/// A confirmation request as printed to the user.
+struct Request {
+    action_type: String,
+    description: String,
+    command: String,
+    trust_level: TrustLevel,
+    risk: Option<RiskSummary>,
+    can_remember: bool,
+}
+
+/// The user's decision on one request.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    Reject,
+    Approve,
+    /// Approve this and later calls of the tool in the conversation.
+    ApproveForConversation,
+}
+
+/// Register as the Confirm client and answer requests until the connection
+/// drops or the terminal closes.
+pub async fn run() -> Result<()> {
+    let mut conn = ipc::connect(ClientType::Confirm).await?;
+    let mut answers = answers().await;
+    eprintln!("{}", t!("confirm-waiting"));
+
+    loop {
+        let msg = conn.recv().await.context("connection to the agent lost")?;
+        match msg.payload {
+            IpcPayload::ConfirmRequest {
+                action_id,
+                action_type,
+                description,
+                command,
+                trust_level,
+                risk,
+                can_remember,
+            } => {
+                let request = Request {
+                    action_type,
+                    description,
+                    command,
+                    trust_level,
+                    risk,
+                    can_remember,
+                };
+                print_request(&request);
+                let answer = ask(&mut answers, &request).await?;
+                let response = IpcPayload::ConfirmResponse {
+                    action_id,
+                    approved: answer != Answer::Reject,
+                    reason: None,
+                    remember: answer == Answer::ApproveForConversation,
+                };
+                conn.send(&ipc::message(response)).await?;
+                match answer {
+                    Answer::Reject => eprintln!("{}", t!("cli-rejected")),
+                    Answer::Approve => eprintln!("{}", t!("cli-approved")),
+                    Answer::ApproveForConversation => {
+                        eprintln!("{}", t!("cli-approved-conversation"));
+                    }
+                }
+            }
+            IpcPayload::Ping => conn.send(&ipc::message(IpcPayload::Pong)).await?,
+            _ => {}
+        }
+    }
+}
+
+/// The controlling terminal, or stdin when there is none.
+async fn answers() -> Answers {
+    let input: Box<dyn AsyncRead + Unpin + Send> = match tokio::fs::File::open("/dev/tty").await {
+        Ok(tty) => Box::new(tty),
+        Err(_) => Box::new(tokio::io::stdin()),
+    };
+    BufReader::new(input).lines()
+}
+
+fn print_request(request: &Request) {
+    let title = if request.trust_level.is_untrusted() {
+        t!("confirm-critical-title")
+    } else {
+        t!("confirm-title")
+    };
+    eprintln!("\n== {title} ==");
+    eprintln!("{} {}", t!("confirm-type"), request.action_type);
+    eprintln!(
+        "{} {}",
+        t!("confirm-source"),
+        trust_label(request.trust_level)
+    );
+    eprintln!("{}", request.description);
+    eprintln!("{}", t!("confirm-command"));
+    for line in request.command.lines() {
+        eprintln!("    {line}");
+    }
+    if let Some(risk) = &request.risk {
+        eprintln!("{}", risk.summary);
+        if !risk.reversible {
+            eprintln!("{}", t!("confirm-irreversible"));
+        }
+    }
+    match request.trust_level {
+        TrustLevel::WebContent => eprintln!("{}", t!("confirm-web-content-warning")),
+        TrustLevel::Memory => eprintln!("{}", t!("confirm-memory-warning")),
+        TrustLevel::User | TrustLevel::System => {}
+    }
+}
+
+fn trust_label(trust_level: TrustLevel) -> String {
+    match trust_level {
+        TrustLevel::User => t!("trust-user"),
+        TrustLevel::System => t!("trust-system"),
+        TrustLevel::WebContent => t!("trust-web-content"),
+        TrustLevel::Memory => t!("trust-memory"),
+    }
+}
+
+/// Read the user's decision. Anything but an explicit yes rejects, and so
+/// does a closed terminal.
+async fn ask(answers: &mut Answers, request: &Request) -> Result<Answer> {
+    let untrusted = request.trust_level.is_untrusted();
+    if untrusted {
+        let keyword = request.action_type.as_str();
+        eprintln!("{}", t!("confirm-type-keyword", keyword = keyword));
+    } else if request.can_remember {
+        eprint!("{} ", t!("cli-confirm-prompt-remember"));
+    } else {
+        eprint!("{} ", t!("cli-confirm-prompt"));
+    }
+    let Some(answer) = answers.next_line().await? else {
+        return Ok(Answer::Reject);
+    };
+    let answer = answer.trim();
+    if untrusted {
+        return Ok(if answer == request.action_type {
+            Answer::Approve
+        } else {
+            Answer::Reject
+        });
+    }
+    Ok(match answer.to_lowercase().as_str() {
+        "y" | "yes" | "д" | "да" => Answer::Approve,
+        "a" | "always" | "в" | "всегда" if request.can_remember => {
+            Answer::ApproveForConversation
+        }
+        _ => Answer::Reject,
+    })
+}
@@ -0,0 +1,52 @@
+//! Connection to the agent's IPC socket.
+
+use aios_common::{AgentConfig, ClientType, IpcClient, IpcConnection, IpcMessage, IpcPayload};
+use anyhow::{Context, Result, bail};
+use uuid::Uuid;
+
+/// Socket path resolution: `AIOS_SOCKET` env var or `socket_path` from
+/// `[agent]`.
+pub fn socket_path() -> String {
+    std::env::var("AIOS_SOCKET").unwrap_or_else(|_| AgentConfig::load().socket_path)
+}
+
+/// Connect to the agent and register as `client_type`.
+pub async fn connect(client_type: ClientType) -> Result<IpcConnection> {
+    let path = socket_path();
+    let mut conn = IpcClient::connect(&path)
+        .await
+        .with_context(|| format!("cannot connect to the agent at {path}"))?;
+    conn.send(&message(IpcPayload::Register { client_type }))
+        .await
+        .context("failed to register")?;
+    match conn.recv().await.context("registration failed")?.payload {
+        IpcPayload::RegisterAck { success: true } => Ok(conn),
+        other => bail!("unexpected registration response: {other:?}"),
+    }
+}
+
+/// `payload` in an envelope with a fresh id.
+pub fn message(payload: IpcPayload) -> IpcMessage {
+    IpcMessage {
+        id: Uuid::new_v4(),
+        payload,
+    }
+}
+
+/// Send `payload` and wait for the reply, answering pings meanwhile.
+pub async fn request(conn: &mut IpcConnection, payload: IpcPayload) -> Result<IpcPayload> {
+    conn.send(&message(payload))
+        .await
+        .context("failed to send request")?;
+    loop {
+        match conn
+            .recv()
+            .await
+            .context("no response from the agent")?
+            .payload
+        {
+            IpcPayload::Ping => conn.send(&message(IpcPayload::Pong)).await?,
+            reply => return Ok(reply),
+        }
+    }
+}
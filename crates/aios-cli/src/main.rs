@@ -0,0 +1,224 @@
+mod ask;
+mod audit;
+mod confirm;
+mod ipc;
+mod mcp;
+
+use std::io::Read;
+
+use aios_common::{
+    Attachment, AttachmentContent, AuditFilter, AuditOutcome, ClientType, IpcPayload, MacroConfig,
+};
+use anyhow::{Context, Result, bail};
+use uuid::Uuid;
+
+use audit::AuditArgs;
+
+const USAGE: &str = "\
+Usage: aios-cli <command> [options]
+
+Commands:
+  ask [--conversation <id>] [--confirm] [--file <path>]... <prompt>...
+                     Send a prompt and print the reply; `-` reads it from stdin.
+                     --confirm answers confirmation requests in the terminal.
+                     --file has the agent read a file and attach it.
+  run [--confirm] <macro> [details]...
+                     Run a macro from the [macros] config section and print the
+                     reply; details are added to its steps.
+  macros             List the macros.
+  confirm            Answer confirmation requests in the terminal.
+  audit [--follow] [--limit <n>] [--errors] [--action <tool>] [--json]
+                     Print recent audit log entries, oldest first.
+  mcp                Serve AIOS's tools to an MCP client on stdin/stdout.
+  pause              Pause all tool execution.
+  resume             Resume tool execution.
+
+The agent socket is taken from AIOS_SOCKET or the [agent] config section.";
+
+/// Entries `audit` prints without `--limit`.
+const DEFAULT_AUDIT_LIMIT: usize = 20;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "aios_cli=warn".into()),
+        )
+        .init();
+    aios_common::i18n::init_from_config();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(args).await {
+        eprintln!("aios-cli: {e:#}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: Vec<String>) -> Result<()> {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+    match command.as_str() {
+        "ask" => ask(rest).await,
+        "run" => run_macro(rest).await,
+        "macros" => {
+            list_macros();
+            Ok(())
+        }
+        "confirm" => confirm::run().await,
+        "audit" => audit::run(audit_args(rest)?).await,
+        "mcp" => mcp::run().await,
+        "pause" => set_paused(true).await,
+        "resume" => set_paused(false).await,
+        "help" | "-h" | "--help" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        other => bail!("unknown command `{other}`\n\n{USAGE}"),
+    }
+}
+
+async fn ask(args: &[String]) -> Result<()> {
+    let mut conversation_id = None;
+    let mut confirm = false;
+    let mut files = Vec::new();
+    let mut words = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--conversation" => {
+                let id = args.next().context("--conversation needs an id")?;
+                conversation_id = Some(Uuid::parse_str(id).context("invalid conversation id")?);
+            }
+            "--confirm" => confirm = true,
+            "--file" => {
+                let path = args.next().context("--file needs a path")?;
+                files.push(file_attachment(path)?);
+            }
+            _ => words.push(arg.as_str()),
+        }
+    }
+
+    let prompt = if words == ["-"] {
+        let mut prompt = String::new();
+        std::io::stdin()
+            .read_to_string(&mut prompt)
+            .context("failed to read the prompt from stdin")?;
+        prompt
+    } else {
+        words.join(" ")
+    };
+    if prompt.trim().is_empty() {
+        bail!("no prompt given\n\n{USAGE}");
+    }
+
+    // Without --conversation every run starts a fresh conversation; print
+    // its id so follow-up prompts can continue it.
+    let conversation_id = match conversation_id {
+        Some(id) => id,
+        None => {
+            let id = Uuid::new_v4();
+            eprintln!("conversation: {id}");
+            id
+        }
+    };
+    ask::run(prompt, files, conversation_id, confirm).await
+}
+
+async fn run_macro(args: &[String]) -> Result<()> {
+    let mut confirm = false;
+    let mut words = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--confirm" => confirm = true,
+            _ => words.push(arg.as_str()),
+        }
+    }
+    let Some((name, details)) = words.split_first() else {
+        bail!("no macro given\n\n{USAGE}");
+    };
+    let macros = MacroConfig::load_all();
+    let config = macros
+        .get(*name)
+        .with_context(|| format!("no macro `{name}` in the [macros] config section"))?;
+    let prompt = config
+        .instructions(name, &details.join(" "))
+        .with_context(|| format!("macro `{name}` has no steps"))?;
+
+    let conversation_id = Uuid::new_v4();
+    eprintln!("conversation: {conversation_id}");
+    ask::run(prompt, Vec::new(), conversation_id, confirm).await
+}
+
+fn list_macros() {
+    let macros = MacroConfig::load_all();
+    if macros.is_empty() {
+        eprintln!("No macros; add them as [macros.<name>] sections to the config.");
+    }
+    for (name, config) in macros {
+        println!("{name}\t{}", config.description);
+    }
+}
+
+/// A file for the agent to read. The path is made absolute here, since the
+/// agent resolves relative paths against its own working directory.
+fn file_attachment(path: &str) -> Result<Attachment> {
+    let path = std::path::absolute(path).with_context(|| format!("invalid path {path}"))?;
+    let name = path
+        .file_name()
+        .map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+    Ok(Attachment {
+        name,
+        media_type: "text/plain".to_owned(),
+        content: AttachmentContent::Path {
+            path: path.display().to_string(),
+        },
+    })
+}
+
+fn audit_args(args: &[String]) -> Result<AuditArgs> {
+    let mut parsed = AuditArgs {
+        filter: AuditFilter::default(),
+        limit: DEFAULT_AUDIT_LIMIT,
+        follow: false,
+        json: false,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--follow" | "-f" => parsed.follow = true,
+            "--json" => parsed.json = true,
+            "--errors" => parsed.filter.outcome = Some(AuditOutcome::Error),
+            "--limit" | "-n" => {
+                let limit = args.next().context("--limit needs a number")?;
+                parsed.limit = limit.parse().context("invalid --limit")?;
+            }
+            "--action" => {
+                let action = args.next().context("--action needs a tool name")?;
+                parsed.filter.action = Some(action.clone());
+            }
+            other => bail!("unknown audit option `{other}`\n\n{USAGE}"),
+        }
+    }
+    Ok(parsed)
+}
+
+async fn set_paused(paused: bool) -> Result<()> {
+    let mut conn = ipc::connect(ClientType::Cli).await?;
+    match ipc::request(&mut conn, IpcPayload::SetPaused { paused }).await? {
+        IpcPayload::CommandResult {
+            success: true,
+            message,
+        } => {
+            println!("{message}");
+            Ok(())
+        }
+        IpcPayload::CommandResult { message, .. } | IpcPayload::Error { message, .. } => {
+            bail!("{message}")
+        }
+        other => bail!("unexpected response: {other:?}"),
+    }
+}
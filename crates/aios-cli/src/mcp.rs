@@ -0,0 +1,56 @@
+//! `aios-cli mcp`: an MCP server on stdio, for editors and assistants that
+//! start their servers as a command. Messages are relayed both ways
+//! between the client and the agent's MCP server, which runs the tools.
+
+use aios_common::{ClientType, IpcPayload};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Stdout};
+
+use crate::ipc;
+
+pub async fn run() -> Result<()> {
+    let conn = ipc::connect(ClientType::Cli).await?;
+    let (mut reader, mut writer) = conn.into_split();
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                // The MCP client closed stdin: it is done with us.
+                let Some(line) = line? else {
+                    return Ok(());
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let message: Value = match serde_json::from_str(&line) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::warn!("Ignoring malformed MCP message: {e}");
+                        continue;
+                    }
+                };
+                let payload = IpcPayload::McpMessage { message };
+                writer.send(&ipc::message(payload)).await?;
+            }
+            msg = reader.recv() => {
+                match msg.context("connection to the agent lost")?.payload {
+                    IpcPayload::McpMessage { message } => write(&mut stdout, &message).await?,
+                    IpcPayload::Ping => writer.send(&ipc::message(IpcPayload::Pong)).await?,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Write one JSON-RPC message as a line.
+async fn write(stdout: &mut Stdout, message: &Value) -> Result<()> {
+    let mut line = message.to_string();
+    line.push('\n');
+    stdout.write_all(line.as_bytes()).await?;
+    stdout.flush().await?;
+    Ok(())
+}
@@ -0,0 +1,499 @@
+# AIOS UI strings -- English (fallback for missing keys).
+
+## OOBE
+oobe-greeting = Hi! I'm AIOS -- your AI assistant.
+oobe-intro =
+    Let's set up the system.
+    I need access to a language model (LLM) to work.
+oobe-start-setup = Start setup
+oobe-skip = Skip
+oobe-choose-provider = Choose an LLM provider:
+oobe-ollama-name = Ollama (local)
+oobe-ollama-subtitle = No API key, runs locally
+oobe-provider-generic = the provider
+oobe-enter-api-key = Enter the API key for { $provider }:
+oobe-api-key-placeholder = API key
+oobe-api-key-hint = The key is stored locally in ~/.config/aios/agent.toml
+oobe-back = Back
+oobe-save = Save
+oobe-ollama-setup = Ollama Setup
+oobe-ollama-checking = Checking Ollama installation...
+oobe-choose-model = Choose a model
+oobe-popular-models = Popular models:
+oobe-custom-model = Or enter a model name:
+oobe-custom-model-placeholder = e.g. codellama:7b
+oobe-pull = Pull
+oobe-complete = Setup complete!
+oobe-provider-default = default
+oobe-summary-provider = Provider: { $provider }
+oobe-summary-model = Model: { $model }
+oobe-try-asking = Try asking me something:
+oobe-example-browse = Open google.com
+oobe-example-files = Show the contents of /home
+oobe-example-weather = What's the weather like?
+oobe-start-chatting = Start chatting
+oobe-ollama-starting = Starting Ollama service...
+oobe-ollama-loading-models = Loading available models...
+oobe-ollama-not-found = Ollama not found. You can install it from Settings.
+oobe-ollama-pulling = Pulling { $model }...
+oobe-ollama-model-ready = Model ready!
+oobe-ollama-pull-failed = Pull failed: { $error }. You can try again from Settings.
+
+## Model sizing
+hardware-memory = { $ram } RAM
+hardware-memory-gpu = { $ram } RAM, { $vram } GPU memory
+hardware-recommended = Recommended for this computer ({ $memory }): { $model }
+hardware-fit-tight = Needs about { $size } of memory: may be slow on this computer.
+hardware-fit-too-large = Needs about { $size } of memory: likely too large for this computer.
+
+## Chat
+chat-welcome = Hi! How can I help?
+chat-send-error = *Send error:* { $reason }
+chat-attach-error = *Cannot attach* { $reason }
+chat-attach-over-budget = { $name }: the attachments of one message can take { $limit } MiB at most
+chat-exported = *Conversation exported to* `{ $path }`
+chat-export-failed = *Export failed:* { $reason }
+chat-not-connected = *Not connected to the agent.*
+chat-microphone-error = *Microphone error:* { $reason }
+chat-notice-error = *Error: { $message }*
+chat-models = Models of { $provider }:
+chat-models-current = current
+chat-models-empty = *{ $provider } offers no models.*
+chat-models-hint = Switch with `/model <name>`
+chat-agent-error = *Agent error:* { $message }
+chat-no-search-results = No messages match your search.
+chat-empty = No messages yet. Start a conversation!
+chat-new-messages = New messages
+chat-new-window = New window
+chat-new-window-hint = Open another conversation side by side (Ctrl+Shift+N)
+chat-close = Close window
+chat-popout-title = AIOS Chat — { $topic }
+chat-popout-title-new = AIOS Chat — new conversation
+status-disconnected = Disconnected
+status-connecting = Connecting...
+status-connected = Connected
+banner-disconnected = Agent unavailable: { $reason }
+banner-retry-in = Retrying in { $seconds } s
+banner-reconnecting = Reconnecting...
+banner-reconnect = Reconnect now
+input-placeholder = Type a message or / for commands (Shift+Enter for a new line)
+input-send = Send
+input-mic = Mic
+input-mic-stop = Stop
+input-cancel = Cancel
+input-editing-banner = Editing message -- sending will replace it and everything after it
+input-remove-attachment = Remove { $name }
+input-attachments-size = { $used } of { $limit } MiB
+bubble-copy = Copy
+bubble-edit = Edit
+bubble-regenerate = Regenerate
+bubble-reasoning-show = Show reasoning
+bubble-reasoning-hide = Hide reasoning
+bubble-attached = Attached: { $names }
+bubble-sending = { $time } · sending…
+bubble-sent = { $time } · sent
+bubble-received = { $time } · delivered
+bubble-failed = { $time } · not delivered
+bubble-retry = Retry
+search-placeholder = Search messages...
+search-close = Close search (Esc)
+search-match-count =
+    { $count ->
+        [one] { $count } match
+       *[other] { $count } matches
+    }
+tool-status-pending = Pending...
+tool-status-completed = Completed
+tool-status-failed = Failed
+tool-status-rejected = Rejected
+tool-rejected-by-user = Action rejected by user
+tool-execution-failed = Tool execution failed
+system-card-uptime = up { $duration }
+system-card-load = Load: { $load }
+system-card-memory = Memory: { $used } of { $total } ({ $percent }%)
+system-card-disk = { $mount }: { $free } free of { $total }
+system-card-battery = Battery: { $percent }%
+system-card-processes = Busiest processes
+system-card-process = { $name }: { $cpu }% CPU, { $memory }
+system-card-thermal = Temperatures
+command-usage = Usage: { $usage }
+command-unknown = Unknown command: /{ $name }
+command-model-help = Switch the model for this session, or list the models
+command-new-help = Start a new conversation
+command-clear-help = Clear the current conversation
+command-system-help = Set the system prompt (empty resets)
+command-export-help = Save the conversation as Markdown
+command-pause-help = Pause all tool execution (emergency stop)
+command-resume-help = Resume tool execution
+command-setup-help = Re-run the setup wizard
+command-quit-help = Quit instead of running in the background
+command-macro-help = Run your macro
+command-macro-empty = Macro /{ $name } has no steps. Add them under [macros.{ $name }] in the config.
+notify-chat-reply = AIOS replied
+notify-chat-open = Open chat
+
+## Token usage
+usage-tokens = { $count } tokens
+usage-cost = ~{ $cost }
+usage-detail-model = Model: { $model }
+usage-detail-input = Input: { $count } tokens
+usage-detail-output = Output: { $count } tokens
+usage-detail-calls = LLM calls: { $count }
+usage-detail-cost = Estimated cost: { $cost }
+usage-detail-free = No cost estimate (local model or unknown price)
+export-title = AIOS conversation
+export-role-user = User
+export-role-assistant = Assistant
+export-role-plan = Plan
+export-role-tool = Tool: { $name }
+
+## Confirm
+confirm-title = Confirm action
+confirm-critical-title = DANGEROUS ACTION
+confirm-type = Type:
+confirm-source = Source:
+confirm-command = Command:
+confirm-cancel = Cancel
+confirm-allow = Allow
+confirm-allow-conversation = Allow for this conversation
+confirm-confirm = Confirm
+confirm-close = Cancel (Esc)
+confirm-keys-hint = Enter to allow, Esc to cancel
+confirm-keys-hint-remember = Enter to allow, Shift+Enter to allow for this conversation, Esc to cancel
+confirm-critical-keys-hint = Type { $keyword }, then press Enter to confirm; Esc cancels
+confirm-web-content-warning = WebContent source -- exercise extreme caution!
+confirm-memory-warning = Recalled from memory -- it may contain instructions from a web page
+confirm-irreversible = This action is irreversible!
+confirm-type-keyword = Type "{ $keyword }" to confirm:
+confirm-waiting = Waiting for requests...
+confirm-quit = Quit AIOS Confirm
+confirm-simulate-normal = Simulate Normal
+confirm-simulate-critical = Simulate Critical
+risk-reversible = Reversible
+risk-not-reversible = Not reversible
+risk-network = Network access
+risk-no-network = No network
+risk-more-paths = +{ $count } more
+trust-user = User
+trust-system = System
+trust-web-content = WebContent
+trust-memory = Memory
+notify-title = AIOS wants to run { $action }
+notify-open = Open
+notify-deny = Deny
+cli-confirm-prompt = Allow? [y/N]
+cli-confirm-prompt-remember = Allow? [y/N, a = for this conversation]
+cli-approved = Approved
+cli-approved-conversation = Approved for this conversation
+cli-rejected = Rejected
+
+## Dock
+tray-wifi = WiFi
+tray-ethernet = LAN
+tray-offline = Offline
+tray-volume = Vol { $percent }%
+tray-volume-muted = Muted
+tray-brightness = Bri { $percent }%
+tray-gpu = GPU { $percent }%
+tray-gpu-memory = GPU { $percent }% · { $used }/{ $total } GB
+tray-mute = Mute
+tray-unmute = Unmute
+tray-battery = Bat { $percent }%
+tray-battery-charging = Bat { $percent }% ↑
+tray-battery-remaining = Bat { $percent }% · { $time }
+tray-notifications = Alerts
+tray-notifications-unread = Alerts { $count }
+notifications-title = Notifications
+notifications-clear = Clear all
+notifications-empty = Nothing new.
+notification-task-result = Scheduled task
+notification-download = Download finished
+notification-confirm-missed = Missed confirmation
+notification-info = Agent
+agent-status-offline = Agent is not running
+agent-status-ready = Agent ready ({ $provider })
+agent-status-echo = Echo mode: no LLM provider configured
+agent-status-error = { $provider } error: { $message }
+agent-status-running = Running { $tool }…
+agent-status-paused = Tool execution paused
+agent-pause-tools = Pause all tool execution
+agent-resume-tools = Resume tool execution
+privacy-camera = CAM
+privacy-microphone = MIC
+privacy-screen = SCREEN
+privacy-location = LOC
+privacy-in-use = In use by { $tools }
+quick-ask-placeholder = Ask AIOS…
+quick-ask-waiting = Thinking…
+quick-ask-open-in-chat = Open in chat
+quick-ask-close = Close
+quick-ask-no-answer = The agent did not answer with text.
+quick-ask-failed = Could not send the question: { $reason }
+quick-ask-disconnected = The agent disconnected before answering.
+calendar-months = January February March April May June July August September October November December
+calendar-weekdays = Mo Tu We Th Fr Sa Su
+calendar-today = Today
+calendar-loading = Loading events…
+calendar-unavailable = Events are unavailable while the agent is offline.
+calendar-no-events = No events.
+calendar-all-day = All day
+calendar-untitled = (no title)
+
+## Settings
+settings-title = AIOS Settings
+settings-close = Close settings
+settings-move-up = Move up
+settings-move-down = Move down
+settings-tab-network = Network
+settings-tab-display = Display
+settings-tab-audio = Audio
+settings-tab-power = Power
+settings-tab-input = Input
+settings-tab-ai = AI Provider
+settings-tab-agent = Agent
+settings-tab-activity = Activity
+settings-tab-language = Language
+settings-tab-appearance = Appearance
+settings-tab-dock = Dock
+settings-tab-startup = Startup
+settings-tab-permissions = Permissions
+settings-tab-mcp = MCP Servers
+settings-tab-about = About
+settings-refresh = Refresh
+settings-save = Save
+settings-network-scan = Scan
+settings-network-empty = No networks found. Click Scan to search.
+settings-network-scanning = Scanning...
+settings-network-password = Password...
+settings-network-identity = Username...
+settings-network-identity-optional = Username (802.1X)...
+settings-network-connect = Connect
+settings-network-disconnect = Disconnect
+settings-network-hidden = Hidden network
+settings-network-hidden-ssid = Network name (SSID)...
+settings-network-join = Join
+settings-network-saved = Saved connections
+settings-network-saved-empty = No saved connections.
+settings-network-active = active
+settings-network-forget = Forget
+settings-network-kind-wifi = Wi-Fi
+settings-network-kind-ethernet = Ethernet
+settings-network-kind-vpn = VPN
+settings-display-loading = Loading displays...
+settings-display-empty = No displays found. Click Refresh.
+settings-display-output = { $name }: { $mode } (scale { $scale })
+settings-audio-loading = Loading audio devices...
+settings-audio-empty = No audio devices found. Is PipeWire running?
+settings-audio-outputs = Outputs
+settings-audio-inputs = Inputs
+settings-audio-none = None
+settings-audio-default = Default
+settings-audio-make-default = Make default
+settings-audio-mute = Mute
+settings-audio-unmute = Unmute
+settings-power-screen-off = Turn off screens after
+settings-power-suspend = Suspend after
+settings-power-never = Never
+settings-power-minutes = { $minutes } min
+settings-power-lid = When the lid is closed
+settings-power-lid-suspend = Suspend
+settings-power-lid-screen-off = Turn off screens
+settings-power-lid-nothing = Do nothing
+settings-power-profile = Performance profile
+settings-power-no-profiles = Profiles are unavailable: power-profiles-daemon is not running.
+settings-power-performance = Performance
+settings-power-balanced = Balanced
+settings-power-saver = Power saver
+settings-power-saved = Saved and applied.
+settings-input-layouts = Keyboard layouts
+settings-input-remove = Remove
+settings-input-add-layout = Add a layout...
+settings-input-switch = Switch layouts with
+settings-input-super-space = Super+Space
+settings-input-repeat = Key repeat
+settings-input-repeat-delay = Delay: { $ms } ms
+settings-input-repeat-rate = Rate: { $rate } per second
+settings-input-touchpad = Touchpad
+settings-input-tap-to-click = Tap to click
+settings-input-natural-scroll = Natural scrolling
+settings-input-saved = Saved and applied.
+settings-ollama-running = Running
+settings-ollama-stopped = Stopped
+settings-ollama-start = Start
+settings-ollama-stop = Stop
+settings-ollama-installed = Installed Models
+settings-ollama-none-installed = No models installed.
+settings-ollama-remove = Remove
+settings-ollama-pull = Pull Model
+settings-ollama-none-available = No models available. Press Refresh.
+settings-ollama-oversized = Models in red likely need more memory than this computer has.
+settings-ollama-starting = Starting Ollama...
+settings-ollama-stopping = Stopping Ollama...
+settings-ollama-pulling = Pulling { $model }...
+settings-ollama-removing = Removing { $model }...
+settings-ollama-cancel = Cancel
+settings-ollama-disk-total = { $size } on disk
+settings-ollama-modified = updated { $date }
+settings-ollama-loaded = in memory
+settings-ai-provider = Provider
+settings-ai-api-key = API Key
+settings-ai-model = Model
+settings-ai-model-pick = Choose a model
+settings-ai-model-manual = Or enter manually:
+settings-ai-model-placeholder = model name
+settings-ai-base-url = Base URL (optional)
+settings-ai-saved = Saved & applied!
+settings-ai-setup-hint = Walk through provider and model setup again in the chat window.
+settings-ai-rerun-setup = Re-run setup wizard
+settings-ai-setup-failed = Could not start the setup wizard: { $error }
+settings-agent-socket = IPC socket
+settings-agent-audit-log = Audit log
+settings-agent-max-destructive = Destructive actions per minute
+settings-agent-max-parallel-tools = Tools running at once
+settings-agent-restart-hint = Socket and audit log changes take effect after a restart.
+settings-agent-invalid-limit = Enter the limit as a whole number.
+settings-agent-prompt = System prompt
+settings-agent-prompt-placeholder = Leave empty to use the built-in prompt.
+settings-agent-save = Save
+settings-agent-reload = Reload config
+settings-agent-restart = Restart agent
+settings-agent-saved = Saved!
+settings-agent-logs = Log
+settings-agent-logs-empty = No log output yet.
+settings-agent-logs-filter = Filter, e.g. by request_id
+settings-agent-disconnected = Agent not reachable: { $reason }
+settings-agent-audit-verify = Verify log integrity
+settings-agent-audit-empty = Nothing logged yet.
+settings-agent-audit-intact =
+    { $count ->
+        [one] Intact: { $count } entry verified.
+       *[other] Intact: { $count } entries verified.
+    }
+settings-agent-audit-intact-partly = Intact: { $count } entries verified; { $unchained } older entries predate hash chaining and cannot be checked.
+settings-agent-audit-malformed = Line { $line } is not a valid audit entry.
+settings-agent-audit-unchained = Line { $line } has no hash although earlier lines do.
+settings-agent-audit-modified = Line { $line } was modified.
+settings-agent-audit-broken = The chain breaks at line { $line }: an entry before it was inserted or removed.
+settings-agent-audit-llm = Audit LLM requests
+settings-agent-audit-llm-hint = Also log the provider, model, token counts and latency of each request. Prompts and replies are kept only as hashes.
+settings-agent-show-plans = Show plans in the chat
+settings-agent-show-plans-hint = Before it uses tools, the agent says what it is about to do and why.
+settings-activity-hint = Actions the assistant performed, newest first, from its audit log.
+settings-activity-loading = Loading...
+settings-activity-today = Today
+settings-activity-yesterday = Yesterday
+settings-activity-week = Last 7 days
+settings-activity-all = All
+settings-activity-action = Tool, e.g. shell_exec
+settings-activity-search = Search arguments...
+settings-activity-errors-only = Errors only
+settings-activity-empty = No actions.
+settings-activity-truncated = Only the newest { $count } actions are shown.
+settings-activity-ok = Done
+settings-activity-error = Failed
+settings-activity-rejected = Rejected
+settings-activity-timeout = Timed out
+settings-activity-llm = LLM request ({ $provider })
+settings-language-hint = Language of the AIOS apps.
+settings-language-auto = Automatic ({ $detected })
+settings-language-saved = Saved!
+settings-ui-no-config = Finish the first-boot setup in AIOS Chat before changing these settings.
+settings-appearance-scheme = Color scheme
+settings-appearance-dark = Dark
+settings-appearance-light = Light
+settings-appearance-accent = Accent color
+settings-appearance-accent-default = Default
+settings-appearance-accent-custom = Custom color (#rrggbb), press Enter to apply
+settings-appearance-invalid-accent = Enter the color as #rrggbb.
+settings-appearance-font-scale = Text size
+settings-appearance-density = Density
+settings-appearance-comfortable = Comfortable
+settings-appearance-compact = Compact
+settings-appearance-saved = Saved!
+settings-appearance-wallpaper = Wallpaper
+settings-appearance-wallpaper-none = Plain color
+settings-appearance-wallpaper-custom = Custom image (file path), press Enter to apply
+settings-appearance-wallpaper-missing = No image at that path.
+settings-appearance-dock-position = Dock position
+settings-appearance-dock-bottom = Bottom
+settings-appearance-dock-top = Top
+settings-dock-pinned = Pinned apps
+settings-dock-none-pinned = No apps pinned.
+settings-dock-unpin = Unpin
+settings-dock-add = Add an app
+settings-dock-filter = Search installed apps
+settings-dock-pin = Pin
+settings-dock-gpu-meter = GPU meter
+settings-dock-gpu-meter-hint = Show GPU load and video memory next to the volume. The agent reads the GPU every few seconds while it is on.
+settings-dock-saved = Saved! The dock updates in a few seconds.
+settings-startup-services = AIOS services
+settings-startup-services-hint = Services turned off here no longer start at login. The chat needs aios-session and starts it with it.
+settings-startup-at-login = At login
+settings-startup-start = Start
+settings-startup-stop = Stop
+settings-startup-state-active = Running
+settings-startup-state-inactive = Stopped
+settings-startup-state-failed = Failed
+settings-startup-state-starting = Starting
+settings-startup-state-restarting = Restarting
+settings-startup-components = Components
+settings-startup-components-hint = aios-session starts these in order and starts them again when they crash. A component stopped here stays stopped until you start it or log in again.
+settings-startup-session-down = aios-session is not running.
+settings-startup-restarts = { $count ->
+        [one] Restarted once
+       *[other] Restarted { $count } times
+    }, last exit: { $exit }
+settings-startup-apps = Applications
+settings-startup-apps-none = No applications start at login.
+settings-startup-remove = Remove
+settings-startup-add = Add an application
+settings-permissions-hint = Turn tools off or change how they are confirmed. The agent applies changes right away.
+settings-permissions-default = Default ({ $level })
+settings-permissions-trust-none = No confirmation
+settings-permissions-trust-confirm = Confirm
+settings-permissions-trust-double = Confirm twice
+settings-permissions-saved = Saved!
+settings-mcp-hint = Tools of these servers are offered to the agent next to the built-in ones, as server__tool.
+settings-mcp-none = No servers added.
+settings-mcp-test = Test
+settings-mcp-remove = Remove
+settings-mcp-testing = Starting the server...
+settings-mcp-tools =
+    { $count ->
+        [one] { $count } tool
+       *[other] { $count } tools
+    }
+settings-mcp-add = Add a server
+settings-mcp-name = Name (letters, digits, _ and -)
+settings-mcp-command = Command
+settings-mcp-args = Arguments, separated by spaces
+settings-mcp-sse-unsupported = SSE servers are saved but not connected yet; only stdio is supported.
+settings-mcp-add-button = Add
+settings-mcp-invalid-name = Use only letters, digits, _ and - in the name.
+settings-mcp-duplicate-name = A server with this name already exists.
+settings-mcp-missing-target = Enter the command or URL of the server.
+settings-mcp-saved = Saved! The agent reconnects its servers.
+settings-about-title = About AIOS
+settings-about-version = AIOS { $version }
+settings-about-build = Build { $id }, { $date }
+settings-about-dev-build = Development build: no /etc/aios-release.
+settings-about-components = Components
+settings-about-not-installed = not installed
+settings-about-agent = Agent
+settings-about-agent-running = Running { $version }
+settings-about-agent-stopped = Not running
+settings-about-agent-echo = No AI provider configured (echo mode)
+settings-about-agent-provider = { $provider }, model { $model }
+settings-about-updates = Updates
+settings-about-updates-hint = AIOS components update with a new ISO; system packages update through apt.
+settings-about-check = Check for updates
+settings-about-checking = Checking...
+settings-about-up-to-date = The system is up to date.
+settings-about-updates-available =
+    { $count ->
+        [one] { $count } package can be updated
+       *[other] { $count } packages can be updated
+    }
+settings-about-install = Install updates
+settings-about-installing = Installing...
+settings-about-installed = Updates installed. Restart to use the new versions.
@@ -0,0 +1,509 @@
+# AIOS UI strings -- Russian.
+
+## OOBE
+oobe-greeting = Привет! Я AIOS -- твой ИИ-ассистент.
+oobe-intro =
+    Давай настроим систему.
+    Для работы мне нужен доступ к языковой модели (LLM).
+oobe-start-setup = Начать настройку
+oobe-skip = Пропустить
+oobe-choose-provider = Выбери провайдера LLM:
+oobe-ollama-name = Ollama (локальный)
+oobe-ollama-subtitle = Без API-ключа, работает локально
+oobe-provider-generic = провайдера
+oobe-enter-api-key = Введи API-ключ для { $provider }:
+oobe-api-key-placeholder = API-ключ
+oobe-api-key-hint = Ключ хранится локально в ~/.config/aios/agent.toml
+oobe-back = Назад
+oobe-save = Сохранить
+oobe-ollama-setup = Настройка Ollama
+oobe-ollama-checking = Проверяю установку Ollama...
+oobe-choose-model = Выбери модель
+oobe-popular-models = Популярные модели:
+oobe-custom-model = Или введи имя модели:
+oobe-custom-model-placeholder = например: codellama:7b
+oobe-pull = Скачать
+oobe-complete = Настройка завершена!
+oobe-provider-default = по умолчанию
+oobe-summary-provider = Провайдер: { $provider }
+oobe-summary-model = Модель: { $model }
+oobe-try-asking = Попробуй попросить меня что-нибудь:
+oobe-example-browse = Открой google.com
+oobe-example-files = Покажи содержимое /home
+oobe-example-weather = Какая сейчас погода?
+oobe-start-chatting = Начать общение
+oobe-ollama-starting = Запускаю службу Ollama...
+oobe-ollama-loading-models = Загружаю список моделей...
+oobe-ollama-not-found = Ollama не найдена. Её можно установить в Настройках.
+oobe-ollama-pulling = Скачиваю { $model }...
+oobe-ollama-model-ready = Модель готова!
+oobe-ollama-pull-failed = Не удалось скачать: { $error }. Можно повторить в Настройках.
+
+## Model sizing
+hardware-memory = ОЗУ: { $ram }
+hardware-memory-gpu = ОЗУ: { $ram }, видеопамять: { $vram }
+hardware-recommended = Рекомендуется для этого компьютера ({ $memory }): { $model }
+hardware-fit-tight = Нужно около { $size } памяти: на этом компьютере может работать медленно.
+hardware-fit-too-large = Нужно около { $size } памяти: скорее всего, не поместится на этом компьютере.
+
+## Chat
+chat-welcome = Привет! Чем могу помочь?
+chat-send-error = *Ошибка отправки:* { $reason }
+chat-attach-error = *Не удалось прикрепить* { $reason }
+chat-attach-over-budget = { $name }: вложения одного сообщения занимают не больше { $limit } МБ
+chat-exported = *Беседа сохранена в* `{ $path }`
+chat-export-failed = *Не удалось экспортировать:* { $reason }
+chat-not-connected = *Нет подключения к агенту.*
+chat-microphone-error = *Ошибка микрофона:* { $reason }
+chat-notice-error = *Ошибка: { $message }*
+chat-models = Модели { $provider }:
+chat-models-current = текущая
+chat-models-empty = *{ $provider } не предлагает ни одной модели.*
+chat-models-hint = Переключиться: `/model <имя>`
+chat-agent-error = *Ошибка агента:* { $message }
+chat-no-search-results = Сообщений по запросу не найдено.
+chat-empty = Сообщений пока нет. Начни разговор!
+chat-new-messages = Новые сообщения
+chat-new-window = Новое окно
+chat-new-window-hint = Открыть ещё одну беседу рядом (Ctrl+Shift+N)
+chat-close = Закрыть окно
+chat-popout-title = AIOS Chat — { $topic }
+chat-popout-title-new = AIOS Chat — новая беседа
+status-disconnected = Отключено
+status-connecting = Подключение...
+status-connected = Подключено
+banner-disconnected = Агент недоступен: { $reason }
+banner-retry-in = Повтор через { $seconds } с
+banner-reconnecting = Переподключение...
+banner-reconnect = Переподключиться
+input-placeholder = Напиши сообщение или / для команд (Shift+Enter — новая строка)
+input-send = Отправить
+input-mic = Микрофон
+input-mic-stop = Стоп
+input-cancel = Отмена
+input-editing-banner = Редактирование -- отправка заменит это сообщение и всё после него
+input-remove-attachment = Убрать { $name }
+input-attachments-size = { $used } из { $limit } МБ
+bubble-copy = Копировать
+bubble-edit = Изменить
+bubble-regenerate = Перегенерировать
+bubble-reasoning-show = Показать рассуждения
+bubble-reasoning-hide = Скрыть рассуждения
+bubble-attached = Вложения: { $names }
+bubble-sending = { $time } · отправляется…
+bubble-sent = { $time } · отправлено
+bubble-received = { $time } · доставлено
+bubble-failed = { $time } · не доставлено
+bubble-retry = Повторить
+search-placeholder = Поиск по сообщениям...
+search-close = Закрыть поиск (Esc)
+search-match-count =
+    { $count ->
+        [one] { $count } совпадение
+        [few] { $count } совпадения
+       *[many] { $count } совпадений
+    }
+tool-status-pending = Выполняется...
+tool-status-completed = Готово
+tool-status-failed = Ошибка
+tool-status-rejected = Отклонено
+tool-rejected-by-user = Действие отклонено пользователем
+tool-execution-failed = Не удалось выполнить инструмент
+system-card-uptime = работает { $duration }
+system-card-load = Нагрузка: { $load }
+system-card-memory = Память: { $used } из { $total } ({ $percent }%)
+system-card-disk = { $mount }: свободно { $free } из { $total }
+system-card-battery = Батарея: { $percent }%
+system-card-processes = Самые активные процессы
+system-card-process = { $name }: { $cpu }% ЦП, { $memory }
+system-card-thermal = Температура
+command-usage = Использование: { $usage }
+command-unknown = Неизвестная команда: /{ $name }
+command-model-help = Сменить модель для этой сессии или показать список моделей
+command-new-help = Начать новую беседу
+command-clear-help = Очистить текущую беседу
+command-system-help = Задать системный промпт (пусто -- сброс)
+command-export-help = Сохранить беседу в Markdown
+command-pause-help = Приостановить выполнение всех инструментов (экстренная остановка)
+command-resume-help = Возобновить выполнение инструментов
+command-setup-help = Заново запустить мастер настройки
+command-quit-help = Выйти вместо работы в фоне
+command-macro-help = Запустить ваш макрос
+command-macro-empty = У макроса /{ $name } нет шагов. Добавьте их в [macros.{ $name }] в конфигурации.
+notify-chat-reply = Ответ AIOS
+notify-chat-open = Открыть чат
+
+## Token usage
+usage-tokens = Токенов: { $count }
+usage-cost = ~{ $cost }
+usage-detail-model = Модель: { $model }
+usage-detail-input = Вход: { $count } токенов
+usage-detail-output = Выход: { $count } токенов
+usage-detail-calls = Запросов к LLM: { $count }
+usage-detail-cost = Оценка стоимости: { $cost }
+usage-detail-free = Без оценки стоимости (локальная модель или неизвестная цена)
+export-title = Беседа AIOS
+export-role-user = Пользователь
+export-role-assistant = Ассистент
+export-role-plan = План
+export-role-tool = Инструмент: { $name }
+
+## Confirm
+confirm-title = Подтверждение действия
+confirm-critical-title = ОПАСНОЕ ДЕЙСТВИЕ
+confirm-type = Тип:
+confirm-source = Источник:
+confirm-command = Команда:
+confirm-cancel = Отмена
+confirm-allow = Разрешить
+confirm-allow-conversation = Разрешать в этой беседе
+confirm-confirm = Подтвердить
+confirm-close = Отмена (Esc)
+confirm-keys-hint = Enter — разрешить, Esc — отменить
+confirm-keys-hint-remember = Enter — разрешить, Shift+Enter — разрешать в этой беседе, Esc — отменить
+confirm-critical-keys-hint = Введи { $keyword } и нажми Enter для подтверждения; Esc — отмена
+confirm-web-content-warning = Источник -- веб-контент. Будь предельно осторожен!
+confirm-memory-warning = Извлечено из памяти -- может содержать инструкции с веб-страницы
+confirm-irreversible = Это действие необратимо!
+confirm-type-keyword = Введи "{ $keyword }" для подтверждения:
+confirm-waiting = Ожидание запросов...
+confirm-quit = Закрыть AIOS Confirm
+confirm-simulate-normal = Тест: обычный
+confirm-simulate-critical = Тест: критический
+risk-reversible = Обратимо
+risk-not-reversible = Необратимо
+risk-network = Доступ к сети
+risk-no-network = Без сети
+risk-more-paths = ещё { $count }
+trust-user = Пользователь
+trust-system = Система
+trust-web-content = Веб-контент
+trust-memory = Память
+notify-title = AIOS хочет выполнить { $action }
+notify-open = Открыть
+notify-deny = Запретить
+cli-confirm-prompt = Разрешить? [y/N]
+cli-confirm-prompt-remember = Разрешить? [y/N, a = до конца беседы]
+cli-approved = Разрешено
+cli-approved-conversation = Разрешено до конца беседы
+cli-rejected = Отклонено
+
+## Dock
+tray-wifi = Wi-Fi
+tray-ethernet = Сеть
+tray-offline = Нет сети
+tray-volume = Звук { $percent }%
+tray-volume-muted = Без звука
+tray-brightness = Ярк { $percent }%
+tray-gpu = ГП { $percent }%
+tray-gpu-memory = ГП { $percent }% · { $used }/{ $total } ГБ
+tray-mute = Выкл. звук
+tray-unmute = Вкл. звук
+tray-battery = Бат { $percent }%
+tray-battery-charging = Бат { $percent }% ↑
+tray-battery-remaining = Бат { $percent }% · { $time }
+tray-notifications = Увед.
+tray-notifications-unread = Увед. { $count }
+notifications-title = Уведомления
+notifications-clear = Очистить
+notifications-empty = Ничего нового.
+notification-task-result = Задача по расписанию
+notification-download = Загрузка завершена
+notification-confirm-missed = Пропущенное подтверждение
+notification-info = Агент
+agent-status-offline = Агент не запущен
+agent-status-ready = Агент готов ({ $provider })
+agent-status-echo = Режим эха: LLM-провайдер не настроен
+agent-status-error = Ошибка { $provider }: { $message }
+agent-status-running = Выполняется { $tool }…
+agent-status-paused = Выполнение инструментов приостановлено
+agent-pause-tools = Приостановить выполнение инструментов
+agent-resume-tools = Возобновить выполнение инструментов
+privacy-camera = КАМ
+privacy-microphone = МИК
+privacy-screen = ЭКРАН
+privacy-location = ГЕО
+privacy-in-use = Используется: { $tools }
+quick-ask-placeholder = Спросить AIOS…
+quick-ask-waiting = Думаю…
+quick-ask-open-in-chat = Открыть в чате
+quick-ask-close = Закрыть
+quick-ask-no-answer = Агент не ответил текстом.
+quick-ask-failed = Не удалось отправить вопрос: { $reason }
+quick-ask-disconnected = Агент отключился, не ответив.
+calendar-months = Январь Февраль Март Апрель Май Июнь Июль Август Сентябрь Октябрь Ноябрь Декабрь
+calendar-weekdays = Пн Вт Ср Чт Пт Сб Вс
+calendar-today = Сегодня
+calendar-loading = Загрузка событий…
+calendar-unavailable = События недоступны, пока агент не запущен.
+calendar-no-events = Событий нет.
+calendar-all-day = Весь день
+calendar-untitled = (без названия)
+
+## Settings
+settings-title = Настройки AIOS
+settings-close = Закрыть настройки
+settings-move-up = Выше
+settings-move-down = Ниже
+settings-tab-network = Сеть
+settings-tab-display = Экран
+settings-tab-audio = Звук
+settings-tab-power = Питание
+settings-tab-input = Ввод
+settings-tab-ai = ИИ-провайдер
+settings-tab-agent = Агент
+settings-tab-activity = Действия
+settings-tab-language = Язык
+settings-tab-appearance = Оформление
+settings-tab-dock = Панель
+settings-tab-startup = Автозапуск
+settings-tab-permissions = Разрешения
+settings-tab-mcp = MCP-серверы
+settings-tab-about = О системе
+settings-refresh = Обновить
+settings-save = Сохранить
+settings-network-scan = Поиск
+settings-network-empty = Сети не найдены. Нажми «Поиск».
+settings-network-scanning = Поиск...
+settings-network-password = Пароль...
+settings-network-identity = Имя пользователя...
+settings-network-identity-optional = Имя пользователя (802.1X)...
+settings-network-connect = Подключить
+settings-network-disconnect = Отключить
+settings-network-hidden = Скрытая сеть
+settings-network-hidden-ssid = Имя сети (SSID)...
+settings-network-join = Подключиться
+settings-network-saved = Сохранённые подключения
+settings-network-saved-empty = Нет сохранённых подключений.
+settings-network-active = активно
+settings-network-forget = Забыть
+settings-network-kind-wifi = Wi-Fi
+settings-network-kind-ethernet = Ethernet
+settings-network-kind-vpn = VPN
+settings-display-loading = Загрузка дисплеев...
+settings-display-empty = Дисплеи не найдены. Нажми «Обновить».
+settings-display-output = { $name }: { $mode } (масштаб { $scale })
+settings-audio-loading = Загрузка аудиоустройств...
+settings-audio-empty = Аудиоустройства не найдены. Запущен ли PipeWire?
+settings-audio-outputs = Выходы
+settings-audio-inputs = Входы
+settings-audio-none = Нет
+settings-audio-default = По умолчанию
+settings-audio-make-default = Сделать основным
+settings-audio-mute = Выключить звук
+settings-audio-unmute = Включить звук
+settings-power-screen-off = Выключать экраны через
+settings-power-suspend = Переходить в сон через
+settings-power-never = Никогда
+settings-power-minutes = { $minutes } мин
+settings-power-lid = При закрытии крышки
+settings-power-lid-suspend = Переходить в сон
+settings-power-lid-screen-off = Выключать экраны
+settings-power-lid-nothing = Ничего не делать
+settings-power-profile = Профиль производительности
+settings-power-no-profiles = Профили недоступны: power-profiles-daemon не запущен.
+settings-power-performance = Производительность
+settings-power-balanced = Сбалансированный
+settings-power-saver = Энергосбережение
+settings-power-saved = Сохранено и применено.
+settings-input-layouts = Раскладки клавиатуры
+settings-input-remove = Удалить
+settings-input-add-layout = Добавить раскладку...
+settings-input-switch = Переключать раскладку
+settings-input-super-space = Super+Пробел
+settings-input-repeat = Повтор клавиш
+settings-input-repeat-delay = Задержка: { $ms } мс
+settings-input-repeat-rate = Скорость: { $rate } в секунду
+settings-input-touchpad = Тачпад
+settings-input-tap-to-click = Нажатие касанием
+settings-input-natural-scroll = Естественная прокрутка
+settings-input-saved = Сохранено и применено.
+settings-ollama-running = Работает
+settings-ollama-stopped = Остановлена
+settings-ollama-start = Запустить
+settings-ollama-stop = Остановить
+settings-ollama-installed = Установленные модели
+settings-ollama-none-installed = Модели не установлены.
+settings-ollama-remove = Удалить
+settings-ollama-pull = Скачать модель
+settings-ollama-none-available = Нет доступных моделей. Нажми «Обновить».
+settings-ollama-oversized = Моделям, выделенным красным, скорее всего, не хватит памяти этого компьютера.
+settings-ollama-starting = Запуск Ollama...
+settings-ollama-stopping = Остановка Ollama...
+settings-ollama-pulling = Скачиваю { $model }...
+settings-ollama-removing = Удаляю { $model }...
+settings-ollama-cancel = Отмена
+settings-ollama-disk-total = { $size } на диске
+settings-ollama-modified = обновлена { $date }
+settings-ollama-loaded = в памяти
+settings-ai-provider = Провайдер
+settings-ai-api-key = API-ключ
+settings-ai-model = Модель
+settings-ai-model-pick = Выбери модель
+settings-ai-model-manual = Или введи вручную:
+settings-ai-model-placeholder = имя модели
+settings-ai-base-url = Базовый URL (необязательно)
+settings-ai-saved = Сохранено и применено!
+settings-ai-setup-hint = Заново выбрать провайдера и модель в окне чата.
+settings-ai-rerun-setup = Запустить мастер настройки
+settings-ai-setup-failed = Не удалось запустить мастер настройки: { $error }
+settings-agent-socket = IPC-сокет
+settings-agent-audit-log = Журнал аудита
+settings-agent-max-destructive = Опасных действий в минуту
+settings-agent-max-parallel-tools = Инструментов одновременно
+settings-agent-restart-hint = Изменения сокета и журнала аудита вступят в силу после перезапуска.
+settings-agent-invalid-limit = Введите лимит целым числом.
+settings-agent-prompt = Системный промпт
+settings-agent-prompt-placeholder = Оставьте пустым, чтобы использовать встроенный промпт.
+settings-agent-save = Сохранить
+settings-agent-reload = Перечитать конфигурацию
+settings-agent-restart = Перезапустить агента
+settings-agent-saved = Сохранено!
+settings-agent-logs = Журнал
+settings-agent-logs-empty = Журнал пока пуст.
+settings-agent-logs-filter = Фильтр, например по request_id
+settings-agent-disconnected = Агент недоступен: { $reason }
+settings-agent-audit-verify = Проверить целостность
+settings-agent-audit-empty = Журнал пока пуст.
+settings-agent-audit-intact =
+    { $count ->
+        [one] Не изменён: проверена { $count } запись.
+        [few] Не изменён: проверены { $count } записи.
+       *[other] Не изменён: проверено { $count } записей.
+    }
+settings-agent-audit-intact-partly = Не изменён: проверено записей: { $count }; { $unchained } старых записей сделаны до появления цепочки хешей и не проверяются.
+settings-agent-audit-malformed = Строка { $line } не является записью аудита.
+settings-agent-audit-unchained = У строки { $line } нет хеша, хотя у предыдущих он есть.
+settings-agent-audit-modified = Строка { $line } изменена.
+settings-agent-audit-broken = Цепочка прерывается на строке { $line }: перед ней вставлена или удалена запись.
+settings-agent-audit-llm = Записывать запросы к LLM
+settings-agent-audit-llm-hint = Сохранять в журнал также провайдера, модель, число токенов и задержку каждого запроса. Запросы и ответы сохраняются только в виде хешей.
+settings-agent-show-plans = Показывать планы в чате
+settings-agent-show-plans-hint = Перед вызовом инструментов агент сообщает, что собирается сделать и зачем.
+settings-activity-hint = Действия ассистента из журнала аудита, сначала новые.
+settings-activity-loading = Загрузка...
+settings-activity-today = Сегодня
+settings-activity-yesterday = Вчера
+settings-activity-week = 7 дней
+settings-activity-all = Все
+settings-activity-action = Инструмент, например shell_exec
+settings-activity-search = Поиск по аргументам...
+settings-activity-errors-only = Только ошибки
+settings-activity-empty = Действий нет.
+settings-activity-truncated =
+    { $count ->
+        [one] Показано только последнее { $count } действие.
+        [few] Показаны только последние { $count } действия.
+       *[other] Показаны только последние { $count } действий.
+    }
+settings-activity-ok = Выполнено
+settings-activity-error = Ошибка
+settings-activity-rejected = Отклонено
+settings-activity-timeout = Тайм-аут
+settings-activity-llm = Запрос к LLM ({ $provider })
+settings-language-hint = Язык приложений AIOS.
+settings-language-auto = Автоматически ({ $detected })
+settings-language-saved = Сохранено!
+settings-ui-no-config = Сначала заверши первоначальную настройку в AIOS Chat.
+settings-appearance-scheme = Цветовая схема
+settings-appearance-dark = Тёмная
+settings-appearance-light = Светлая
+settings-appearance-accent = Акцентный цвет
+settings-appearance-accent-default = По умолчанию
+settings-appearance-accent-custom = Свой цвет (#rrggbb), нажми Enter для применения
+settings-appearance-invalid-accent = Введи цвет в формате #rrggbb.
+settings-appearance-font-scale = Размер текста
+settings-appearance-density = Плотность
+settings-appearance-comfortable = Свободно
+settings-appearance-compact = Компактно
+settings-appearance-saved = Сохранено!
+settings-appearance-wallpaper = Обои
+settings-appearance-wallpaper-none = Сплошной цвет
+settings-appearance-wallpaper-custom = Своё изображение (путь к файлу), нажми Enter для применения
+settings-appearance-wallpaper-missing = По этому пути нет изображения.
+settings-appearance-dock-position = Положение панели
+settings-appearance-dock-bottom = Снизу
+settings-appearance-dock-top = Сверху
+settings-dock-pinned = Закреплённые приложения
+settings-dock-none-pinned = Нет закреплённых приложений.
+settings-dock-unpin = Открепить
+settings-dock-add = Добавить приложение
+settings-dock-filter = Поиск установленных приложений
+settings-dock-pin = Закрепить
+settings-dock-gpu-meter = Загрузка видеокарты
+settings-dock-gpu-meter-hint = Показывать загрузку видеокарты и видеопамяти рядом с громкостью. Пока включено, агент опрашивает видеокарту каждые несколько секунд.
+settings-dock-saved = Сохранено! Панель обновится через несколько секунд.
+settings-startup-services = Службы AIOS
+settings-startup-services-hint = Отключённые здесь службы больше не запускаются при входе. Чат нуждается в aios-session и запускает её вместе с собой.
+settings-startup-at-login = При входе
+settings-startup-start = Запустить
+settings-startup-stop = Остановить
+settings-startup-state-active = Работает
+settings-startup-state-inactive = Остановлена
+settings-startup-state-failed = Сбой
+settings-startup-state-starting = Запускается
+settings-startup-state-restarting = Перезапускается
+settings-startup-components = Компоненты
+settings-startup-components-hint = aios-session запускает их по порядку и перезапускает после сбоя. Остановленный здесь компонент не запустится, пока вы не запустите его или не войдёте снова.
+settings-startup-session-down = aios-session не запущена.
+settings-startup-restarts = { $count ->
+        [one] Перезапущен { $count } раз
+        [few] Перезапущен { $count } раза
+       *[many] Перезапущен { $count } раз
+    }, последний выход: { $exit }
+settings-startup-apps = Приложения
+settings-startup-apps-none = Нет приложений, запускаемых при входе.
+settings-startup-remove = Удалить
+settings-startup-add = Добавить приложение
+settings-permissions-hint = Отключай инструменты или меняй, как они подтверждаются. Агент применяет изменения сразу.
+settings-permissions-default = По умолчанию ({ $level })
+settings-permissions-trust-none = Без подтверждения
+settings-permissions-trust-confirm = Подтверждать
+settings-permissions-trust-double = Подтверждать дважды
+settings-permissions-saved = Сохранено!
+settings-mcp-hint = Инструменты этих серверов доступны агенту наряду со встроенными, как сервер__инструмент.
+settings-mcp-none = Серверы не добавлены.
+settings-mcp-test = Проверить
+settings-mcp-remove = Удалить
+settings-mcp-testing = Запуск сервера...
+settings-mcp-tools =
+    { $count ->
+        [one] { $count } инструмент
+        [few] { $count } инструмента
+       *[many] { $count } инструментов
+    }
+settings-mcp-add = Добавить сервер
+settings-mcp-name = Имя (буквы, цифры, _ и -)
+settings-mcp-command = Команда
+settings-mcp-args = Аргументы через пробел
+settings-mcp-sse-unsupported = SSE-серверы сохраняются, но пока не подключаются; поддерживается только stdio.
+settings-mcp-add-button = Добавить
+settings-mcp-invalid-name = Используйте в имени только буквы, цифры, _ и -.
+settings-mcp-duplicate-name = Сервер с таким именем уже есть.
+settings-mcp-missing-target = Укажите команду или URL сервера.
+settings-mcp-saved = Сохранено! Агент переподключит серверы.
+settings-about-title = Об AIOS
+settings-about-version = AIOS { $version }
+settings-about-build = Сборка { $id }, { $date }
+settings-about-dev-build = Сборка для разработки: нет /etc/aios-release.
+settings-about-components = Компоненты
+settings-about-not-installed = не установлен
+settings-about-agent = Агент
+settings-about-agent-running = Работает, версия { $version }
+settings-about-agent-stopped = Не запущен
+settings-about-agent-echo = AI-провайдер не настроен (режим эха)
+settings-about-agent-provider = { $provider }, модель { $model }
+settings-about-updates = Обновления
+settings-about-updates-hint = Компоненты AIOS обновляются с новым ISO, системные пакеты — через apt.
+settings-about-check = Проверить обновления
+settings-about-checking = Проверка...
+settings-about-up-to-date = Система обновлена.
+settings-about-updates-available =
+    { $count ->
+        [one] Можно обновить { $count } пакет
+        [few] Можно обновить { $count } пакета
+       *[other] Можно обновить { $count } пакетов
+    }
+settings-about-install = Установить обновления
+settings-about-installing = Установка...
+settings-about-installed = Обновления установлены. Перезагрузитесь, чтобы использовать новые версии.
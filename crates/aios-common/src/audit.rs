@@ -1,5 +1,10 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::PathBuf;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::types::trust::TrustLevel;
 
@@ -7,6 +12,9 @@ use crate::types::trust::TrustLevel;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEntry {
     pub timestamp: DateTime<Utc>,
+    /// Entries written before kinds existed are tool calls.
+    #[serde(default)]
+    pub kind: AuditKind,
     pub action: String,
     pub arguments: serde_json::Value,
     pub trust_level: TrustLevel,
@@ -15,6 +23,19 @@ pub struct AuditEntry {
     pub details: Option<String>,
 }
 
+/// What an [`AuditEntry`] records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    /// A tool call: `action` is the tool, `arguments` its arguments.
+    #[default]
+    Tool,
+    /// An LLM request: `action` is the provider, `arguments` the model,
+    /// token counts, latency and hashes of the content (see
+    /// [`content_hash`]).
+    Llm,
+}
+
 /// Outcome of an audited action.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -24,3 +45,332 @@ pub enum AuditResult {
     Rejected,
     Timeout,
 }
+
+impl AuditResult {
+    pub fn outcome(&self) -> AuditOutcome {
+        match self {
+            Self::Ok => AuditOutcome::Ok,
+            Self::Error(_) => AuditOutcome::Error,
+            Self::Rejected => AuditOutcome::Rejected,
+            Self::Timeout => AuditOutcome::Timeout,
+        }
+    }
+}
+
+/// [`AuditResult`] without the error message, for filtering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Ok,
+    Error,
+    Rejected,
+    Timeout,
+}
+
+// -- Queries --
+
+/// Which entries an audit query returns; fields left empty match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditFilter {
+    #[serde(default)]
+    pub kind: Option<AuditKind>,
+    /// Tool name, exact.
+    #[serde(default)]
+    pub action: Option<String>,
+    #[serde(default)]
+    pub outcome: Option<AuditOutcome>,
+    /// Text in the arguments or details, case-insensitive.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        if let Some(kind) = self.kind
+            && entry.kind != kind
+        {
+            return false;
+        }
+        if let Some(action) = &self.action
+            && entry.action != *action
+        {
+            return false;
+        }
+        if let Some(outcome) = self.outcome
+            && entry.result.outcome() != outcome
+        {
+            return false;
+        }
+        if let Some(text) = &self.text {
+            let text = text.to_lowercase();
+            let in_arguments = entry.arguments.to_string().to_lowercase().contains(&text);
+            let in_details = entry
+                .details
+                .as_ref()
+                .is_some_and(|details| details.to_lowercase().contains(&text));
+            if !in_arguments && !in_details {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Time range of an audit query: `from` inclusive, `to` exclusive; an open
+/// end is unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRange {
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Line offsets of an audit log by time, so range queries read only the
+/// lines in range.
+///
+/// The log is append-only, so [`AuditIndex::refresh`] only indexes what was
+/// added since the last call; a file that shrank is indexed again.
+#[derive(Debug)]
+pub struct AuditIndex {
+    path: PathBuf,
+    /// Bytes indexed so far; always the end of a complete line.
+    indexed: u64,
+    /// Timestamp and offset of each line, in file order.
+    lines: Vec<(DateTime<Utc>, u64)>,
+}
+
+impl AuditIndex {
+    /// An empty index of the log at `path`; call [`AuditIndex::refresh`]
+    /// before querying.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            indexed: 0,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Index the lines appended since the last refresh. A missing file is an
+    /// empty log.
+    pub fn refresh(&mut self) -> io::Result<()> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.indexed = 0;
+                self.lines.clear();
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        let len = file.metadata()?.len();
+        if len < self.indexed {
+            self.indexed = 0;
+            self.lines.clear();
+        }
+        if len == self.indexed {
+            return Ok(());
+        }
+
+        file.seek(SeekFrom::Start(self.indexed))?;
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            // A line still being written is indexed next time.
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            if let Some(timestamp) = line_timestamp(&line) {
+                self.lines.push((timestamp, self.indexed));
+            }
+            self.indexed += read as u64;
+        }
+        Ok(())
+    }
+
+    /// Entries in `range` that match `filter`, newest first, at most
+    /// `limit`. The second value tells whether more entries matched.
+    pub fn query(
+        &self,
+        filter: &AuditFilter,
+        range: AuditRange,
+        limit: usize,
+    ) -> io::Result<(Vec<AuditEntry>, bool)> {
+        // Entries are appended as they happen, so the index is in time order.
+        let start = range.from.map_or(0, |from| {
+            self.lines.partition_point(|(time, _)| *time < from)
+        });
+        let end = range.to.map_or(self.lines.len(), |to| {
+            self.lines.partition_point(|(time, _)| *time < to)
+        });
+
+        let mut entries = Vec::new();
+        if start >= end {
+            return Ok((entries, false));
+        }
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut line = String::new();
+        for (_, offset) in self.lines[start..end].iter().rev() {
+            reader.seek(SeekFrom::Start(*offset))?;
+            line.clear();
+            reader.read_line(&mut line)?;
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+                continue;
+            };
+            if !filter.matches(&entry) {
+                continue;
+            }
+            if entries.len() == limit {
+                return Ok((entries, true));
+            }
+            entries.push(entry);
+        }
+        Ok((entries, false))
+    }
+}
+
+/// The `timestamp` of a log line, without parsing the whole entry.
+fn line_timestamp(line: &str) -> Option<DateTime<Utc>> {
+    #[derive(Deserialize)]
+    struct Timestamp {
+        timestamp: DateTime<Utc>,
+    }
+    serde_json::from_str::<Timestamp>(line)
+        .ok()
+        .map(|line| line.timestamp)
+}
+
+// -- Hash chain --
+//
+// Each log line is the entry's JSON object plus `prev_hash`, the `hash` of
+// the line before it, and `hash`, the SHA-256 of the object without `hash`.
+// Editing, inserting or removing a line breaks the chain from there on.
+// Lines cut from the end leave a valid chain; compare the last hash with a
+// copy kept elsewhere to catch that.
+
+/// `prev_hash` of the first chained line.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Serialize `entry` as a log line chained to `prev_hash`. Returns the line
+/// (without newline) and its hash.
+pub fn chain_entry(entry: &AuditEntry, prev_hash: &str) -> serde_json::Result<(String, String)> {
+    let mut line = serde_json::to_value(entry)?;
+    line["prev_hash"] = prev_hash.into();
+    let hash = hash_line(&line);
+    line["hash"] = hash.as_str().into();
+    Ok((line.to_string(), hash))
+}
+
+/// SHA-256 of the line's JSON without `hash`, in hex. Object keys serialize
+/// sorted, so a parsed line hashes the same bytes as when it was written.
+fn hash_line(line: &serde_json::Value) -> String {
+    let mut line = line.clone();
+    if let Some(object) = line.as_object_mut() {
+        object.remove("hash");
+    }
+    sha256_hex(line.to_string().as_bytes())
+}
+
+/// Digits of [`content_hash`]; enough to tell contents apart.
+const CONTENT_HASH_LEN: usize = 16;
+
+/// Short SHA-256 of `content` in hex, to record that content was sent
+/// without keeping the content itself.
+pub fn content_hash(content: &str) -> String {
+    let mut hash = sha256_hex(content.as_bytes());
+    hash.truncate(CONTENT_HASH_LEN);
+    hash
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Hash to chain the next line to, given the log so far: the last line's
+/// hash, or [`GENESIS_HASH`] if it has none.
+pub fn last_hash(log: &str) -> String {
+    log.lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .and_then(|line| line["hash"].as_str().map(str::to_owned))
+        .unwrap_or_else(|| GENESIS_HASH.to_owned())
+}
+
+/// A log whose chain is intact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditVerification {
+    /// Lines covered by the chain.
+    pub chained: usize,
+    /// Lines written before chaining was introduced, at the start of the
+    /// log; they cannot be checked.
+    pub unchained: usize,
+    /// Hash of the last line.
+    pub last_hash: String,
+}
+
+/// Why a log failed verification. Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AuditVerifyError {
+    #[error("line {0} is not a valid audit entry")]
+    Malformed(usize),
+    #[error("line {0} has no hash although earlier lines do")]
+    Unchained(usize),
+    #[error("line {0} was modified")]
+    Modified(usize),
+    #[error("the chain breaks at line {0}: a line was inserted or removed before it")]
+    Broken(usize),
+}
+
+impl AuditVerifyError {
+    pub fn line(&self) -> usize {
+        match self {
+            Self::Malformed(line)
+            | Self::Unchained(line)
+            | Self::Modified(line)
+            | Self::Broken(line) => *line,
+        }
+    }
+}
+
+/// Check the hash chain of a whole log (the file's content).
+pub fn verify(log: &str) -> Result<AuditVerification, AuditVerifyError> {
+    let mut verification = AuditVerification {
+        chained: 0,
+        unchained: 0,
+        last_hash: GENESIS_HASH.to_owned(),
+    };
+    for (index, line) in log.lines().enumerate() {
+        let number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: serde_json::Value =
+            serde_json::from_str(line).map_err(|_| AuditVerifyError::Malformed(number))?;
+        if serde_json::from_value::<AuditEntry>(value.clone()).is_err() {
+            return Err(AuditVerifyError::Malformed(number));
+        }
+        let (Some(prev_hash), Some(hash)) = (value["prev_hash"].as_str(), value["hash"].as_str())
+        else {
+            if verification.chained > 0 {
+                return Err(AuditVerifyError::Unchained(number));
+            }
+            verification.unchained += 1;
+            continue;
+        };
+        if hash_line(&value) != hash {
+            return Err(AuditVerifyError::Modified(number));
+        }
+        if prev_hash != verification.last_hash {
+            return Err(AuditVerifyError::Broken(number));
+        }
+        verification.chained += 1;
+        verification.last_hash = hash.to_owned();
+    }
+    Ok(verification)
+}
@@ -0,0 +1,159 @@
+//! Local iCalendar files.
+//!
+//! Events are read from the `.ics` files in `~/.local/share/aios/calendar`,
+//! where a sync tool or the user drops exported calendars. Only single
+//! `VEVENT`s are understood: recurrence rules are ignored, and times with a
+//! `TZID` are taken as local time.
+
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+use crate::types::calendar::CalendarEvent;
+
+/// Directory holding the calendar files.
+pub fn calendar_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("aios")
+        .join("calendar")
+}
+
+/// Events touching any of the `days` days starting at `from`, all-day
+/// events first, then by start time.
+pub fn events_between(from: NaiveDate, days: u32) -> Vec<CalendarEvent> {
+    let mut events: Vec<CalendarEvent> = load()
+        .into_iter()
+        .filter(|event| {
+            from.iter_days()
+                .take(days as usize)
+                .any(|d| event.occurs_on(d))
+        })
+        .collect();
+    events.sort_by_key(|event| (!event.all_day, event.start));
+    events
+}
+
+/// Events touching `date`.
+pub fn events_on(date: NaiveDate) -> Vec<CalendarEvent> {
+    events_between(date, 1)
+}
+
+/// Every event in every calendar file.
+fn load() -> Vec<CalendarEvent> {
+    let Ok(files) = std::fs::read_dir(calendar_dir()) else {
+        return Vec::new();
+    };
+    files
+        .flatten()
+        .map(|file| file.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ics"))
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .flat_map(|content| parse(&content))
+        .collect()
+}
+
+/// Parse the `VEVENT`s of an iCalendar document.
+pub fn parse(content: &str) -> Vec<CalendarEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<PartialEvent> = None;
+
+    for line in unfold(content) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // `DTSTART;VALUE=DATE:20261016` -> name `DTSTART`, params `VALUE=DATE`.
+        let (name, params) = name.split_once(';').unwrap_or((name, ""));
+        match (name.to_ascii_uppercase().as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                current = Some(PartialEvent::default());
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(event) = current.take().and_then(PartialEvent::finish) {
+                    events.push(event);
+                }
+            }
+            ("SUMMARY", Some(event)) => event.title = Some(unescape(value)),
+            ("LOCATION", Some(event)) if !value.is_empty() => {
+                event.location = Some(unescape(value));
+            }
+            ("DTSTART", Some(event)) => event.start = parse_time(params, value),
+            ("DTEND", Some(event)) => event.end = parse_time(params, value),
+            _ => {}
+        }
+    }
+    events
+}
+
+#[derive(Default)]
+struct PartialEvent {
+    title: Option<String>,
+    start: Option<(NaiveDateTime, bool)>,
+    end: Option<(NaiveDateTime, bool)>,
+    location: Option<String>,
+}
+
+impl PartialEvent {
+    fn finish(self) -> Option<CalendarEvent> {
+        let (start, all_day) = self.start?;
+        Some(CalendarEvent {
+            title: self.title.unwrap_or_default(),
+            start,
+            end: self.end.map(|(end, _)| end),
+            all_day,
+            location: self.location,
+        })
+    }
+}
+
+/// A `DTSTART`/`DTEND` value in local time, and whether it is date-only.
+fn parse_time(params: &str, value: &str) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+    let date_only = params
+        .split(';')
+        .any(|param| param.eq_ignore_ascii_case("VALUE=DATE"))
+        || value.len() == 8;
+    if date_only {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_time(NaiveTime::MIN), true));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        let local = Utc.from_utc_datetime(&time).with_timezone(&Local);
+        return Some((local.naive_local(), false));
+    }
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Some((time, false))
+}
+
+/// Join folded content lines (continuations start with a space or tab).
+/// vCard folds its lines the same way.
+pub(crate) fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines().map(|line| line.trim_end_matches('\r')) {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_owned()),
+        }
+    }
+    lines
+}
+
+/// Undo iCalendar (and vCard) text escaping (`\,`, `\;`, `\n`, `\\`).
+pub(crate) fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
@@ -0,0 +1,260 @@
+//! Local vCard address book.
+//!
+//! Contacts are the `.vcf` files in the `[contacts]` directory and its
+//! subdirectories, the layout vdirsyncer and khard share; Evolution and
+//! other address books get there through vdirsyncer. A file may hold
+//! several `VCARD`s. New contacts are written as vCard 3.0, one file each.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::calendar::{unescape, unfold};
+use crate::types::config::ContactsConfig;
+use crate::types::contact::Contact;
+
+/// Contacts matching `query` (see [`Contact::matches`]), by name; all of
+/// them for an empty query.
+pub fn search(query: &str) -> Vec<Contact> {
+    let query = query.trim();
+    let mut contacts: Vec<Contact> = load(&ContactsConfig::load().dir())
+        .into_iter()
+        .filter(|contact| query.is_empty() || contact.matches(query))
+        .collect();
+    contacts.sort_by_cached_key(|contact| contact.name.to_lowercase());
+    contacts
+}
+
+/// Save `contact` as a new file in the address book and return its path.
+/// Fails with [`io::ErrorKind::AlreadyExists`] when a contact has one of
+/// its email addresses already.
+pub fn add(contact: &Contact) -> io::Result<PathBuf> {
+    let dir = ContactsConfig::load().dir();
+    let same_email = |other: &Contact| {
+        other
+            .emails
+            .iter()
+            .any(|email| contact.emails.iter().any(|new| new.eq_ignore_ascii_case(email)))
+    };
+    if let Some(existing) = load(&dir).into_iter().find(same_email) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already has this email address", existing.name),
+        ));
+    }
+
+    std::fs::create_dir_all(&dir)?;
+    let uid = Uuid::new_v4();
+    let path = dir.join(format!("{uid}.vcf"));
+    let mut file = std::fs::File::create_new(&path)?;
+    file.write_all(to_vcard(contact, &uid.to_string()).as_bytes())?;
+    Ok(path)
+}
+
+/// Every contact in `dir` and its subdirectories.
+fn load(dir: &Path) -> Vec<Contact> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut contacts = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            contacts.extend(load(&path));
+        } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("vcf"))
+            && let Ok(content) = std::fs::read_to_string(&path)
+        {
+            contacts.extend(parse(&content));
+        }
+    }
+    contacts
+}
+
+/// Parse the `VCARD`s of a vCard 3.0 or 4.0 document.
+pub fn parse(content: &str) -> Vec<Contact> {
+    let mut contacts = Vec::new();
+    let mut current: Option<(Contact, Option<String>)> = None;
+
+    for line in unfold(content) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // `item1.EMAIL;TYPE=work:a@example.com` -> name `EMAIL`.
+        let name = name.split(';').next().unwrap_or_default();
+        let name = name.rsplit('.').next().unwrap_or(name);
+        match (name.to_ascii_uppercase().as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VCARD") => {
+                current = Some((Contact::default(), None));
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VCARD") => {
+                if let Some((mut contact, structured)) = current.take() {
+                    if contact.name.is_empty() {
+                        contact.name = structured.unwrap_or_default();
+                    }
+                    if !contact.name.is_empty() {
+                        contacts.push(contact);
+                    }
+                }
+            }
+            ("FN", Some((contact, _))) => contact.name = unescape(value).trim().to_owned(),
+            ("N", Some((_, structured))) => *structured = structured_name(value),
+            ("EMAIL", Some((contact, _))) if !value.trim().is_empty() => {
+                contact.emails.push(unescape(value).trim().to_owned());
+            }
+            ("TEL", Some((contact, _))) if !value.trim().is_empty() => {
+                let phone = unescape(value);
+                // vCard 4.0 writes numbers as `tel:` URIs.
+                let phone = phone.trim().trim_start_matches("tel:");
+                contact.phones.push(phone.to_owned());
+            }
+            ("NICKNAME", Some((contact, _))) if !value.is_empty() => {
+                contact.nickname = Some(unescape(value));
+            }
+            ("ORG", Some((contact, _))) => {
+                // `Company;Department`; the company is enough.
+                let company = components(value).swap_remove(0);
+                if !company.is_empty() {
+                    contact.organization = Some(company);
+                }
+            }
+            ("NOTE", Some((contact, _))) if !value.is_empty() => {
+                contact.note = Some(unescape(value));
+            }
+            _ => {}
+        }
+    }
+    contacts
+}
+
+/// "Given Family" from `N:Family;Given;Additional;Prefix;Suffix`, for cards
+/// without `FN`.
+fn structured_name(value: &str) -> Option<String> {
+    let mut parts = components(value).into_iter();
+    let family = parts.next().unwrap_or_default();
+    let given = parts.next().unwrap_or_default();
+    let name = format!("{given} {family}").trim().to_owned();
+    (!name.is_empty()).then_some(name)
+}
+
+/// The unescaped parts of a structured value like `N` or `ORG`, split at
+/// the semicolons that are not escaped; at least one.
+fn components(value: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        let part = parts.last_mut().expect("starts with one part");
+        match c {
+            ';' => parts.push(String::new()),
+            '\\' => {
+                part.push(c);
+                part.extend(chars.next());
+            }
+            c => part.push(c),
+        }
+    }
+    parts.iter().map(|part| unescape(part)).collect()
+}
+
+/// `contact` as a vCard 3.0 with the id `uid`.
+fn to_vcard(contact: &Contact, uid: &str) -> String {
+    // `N` is required in 3.0; the last word is taken as the family name.
+    let (given, family) = contact
+        .name
+        .trim()
+        .rsplit_once(' ')
+        .unwrap_or(("", contact.name.trim()));
+    let mut lines = vec![
+        "BEGIN:VCARD".to_owned(),
+        "VERSION:3.0".to_owned(),
+        format!("UID:{uid}"),
+        format!("FN:{}", escape(&contact.name)),
+        format!("N:{};{};;;", escape(family), escape(given)),
+    ];
+    lines.extend(contact.emails.iter().map(|email| format!("EMAIL:{}", escape(email))));
+    lines.extend(contact.phones.iter().map(|phone| format!("TEL:{}", escape(phone))));
+    if let Some(nickname) = &contact.nickname {
+        lines.push(format!("NICKNAME:{}", escape(nickname)));
+    }
+    if let Some(organization) = &contact.organization {
+        lines.push(format!("ORG:{}", escape(organization)));
+    }
+    if let Some(note) = &contact.note {
+        lines.push(format!("NOTE:{}", escape(note)));
+    }
+    lines.push("END:VCARD".to_owned());
+    // vCard lines end in CRLF.
+    lines.join("\r\n") + "\r\n"
+}
+
+/// vCard text escaping, the reverse of [`unescape`].
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cards_with_groups_folding_and_escapes() {
+        let content = "BEGIN:VCARD\r\n\
+                       VERSION:4.0\r\n\
+                       FN:Alice Liddell\r\n\
+                       item1.EMAIL;TYPE=work:alice@example.com\r\n\
+                       EMAIL:alice.l\r\n \
+                       iddell@example.org\r\n\
+                       TEL;VALUE=uri:tel:+1-555-0100\r\n\
+                       ORG:Wonderland\\, Ltd;Tea parties\r\n\
+                       END:VCARD\r\n\
+                       BEGIN:VCARD\r\n\
+                       VERSION:3.0\r\n\
+                       N:Hatter;Mad;;;\r\n\
+                       END:VCARD\r\n";
+        let contacts = parse(content);
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[0].name, "Alice Liddell");
+        assert_eq!(
+            contacts[0].emails,
+            ["alice@example.com", "alice.liddell@example.org"]
+        );
+        assert_eq!(contacts[0].phones, ["+1-555-0100"]);
+        assert_eq!(contacts[0].organization.as_deref(), Some("Wonderland, Ltd"));
+        assert_eq!(contacts[1].name, "Mad Hatter");
+    }
+
+    #[test]
+    fn written_cards_read_back() {
+        let contact = Contact {
+            name: "Bob O'Neil".to_owned(),
+            emails: vec!["bob@example.com".to_owned()],
+            phones: vec!["+44 20 7946 0000".to_owned()],
+            nickname: None,
+            organization: Some("Acme; Inc".to_owned()),
+            note: Some("Met at the conference,\nlikes tea".to_owned()),
+        };
+        let vcard = to_vcard(&contact, "b0b");
+        assert!(vcard.contains("\r\nN:O'Neil;Bob;;;\r\n"));
+        assert_eq!(parse(&vcard), [contact]);
+    }
+
+    #[test]
+    fn search_matches_words_and_phone_digits() {
+        let alice = Contact {
+            name: "Alice Liddell".to_owned(),
+            emails: vec!["alice@example.com".to_owned()],
+            phones: vec!["+1 (555) 0100".to_owned()],
+            ..Contact::default()
+        };
+        assert!(alice.matches("alice"));
+        assert!(alice.matches("liddell ALICE"));
+        assert!(alice.matches("example.com"));
+        assert!(alice.matches("555-0100"));
+        assert!(!alice.matches("alice smith"));
+        assert!(!alice.matches(""));
+    }
+}
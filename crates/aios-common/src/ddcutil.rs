@@ -0,0 +1,224 @@
+//! External monitors over DDC/CI through `ddcutil`, for desktops without a
+//! backlight in `/sys/class/backlight`.
+//!
+//! Monitors are addressed by their I2C bus, which stays the same while the
+//! cable does. Brightness is VCP feature `0x10`. DDC is slow, a detection
+//! takes a second or two, so every command runs with a timeout.
+
+use std::fmt;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::process::Command;
+
+use crate::types::tool::ToolErrorKind;
+
+/// Time a command gets; monitors that do not answer take long to give up.
+const CMD_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// VCP feature code of brightness (luminance).
+const BRIGHTNESS: &str = "10";
+
+/// A monitor that answers DDC/CI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Monitor {
+    /// `/dev/i2c-N` bus number.
+    pub bus: u32,
+    /// Model name, e.g. `DELL U2719D`.
+    pub model: String,
+    pub manufacturer: String,
+    pub serial: String,
+    /// DRM connector, e.g. `card1-DP-1`.
+    pub connector: Option<String>,
+}
+
+/// Current and maximum value of a VCP feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VcpValue {
+    pub current: u16,
+    pub max: u16,
+}
+
+impl VcpValue {
+    /// The current value in percent of the maximum.
+    pub fn percent(self) -> u8 {
+        if self.max == 0 {
+            return 0;
+        }
+        let (current, max) = (u32::from(self.current), u32::from(self.max));
+        u8::try_from(((current * 100 + max / 2) / max).min(100)).unwrap_or(100)
+    }
+}
+
+/// Why a ddcutil command failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DdcError {
+    /// ddcutil is not installed.
+    NotInstalled,
+    /// ddcutil ran and reported an error, e.g. no access to `/dev/i2c-*`.
+    Failed(String),
+    TimedOut,
+}
+
+impl fmt::Display for DdcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInstalled => f.write_str("ddcutil is not installed"),
+            Self::Failed(message) => f.write_str(message),
+            Self::TimedOut => f.write_str("ddcutil timed out"),
+        }
+    }
+}
+
+impl std::error::Error for DdcError {}
+
+impl DdcError {
+    /// How a tool reports this failure.
+    pub fn tool_kind(&self) -> ToolErrorKind {
+        match self {
+            Self::NotInstalled => ToolErrorKind::Unavailable,
+            Self::Failed(_) => ToolErrorKind::Failed,
+            Self::TimedOut => ToolErrorKind::Timeout,
+        }
+    }
+}
+
+/// Monitors that answer DDC/CI; laptop panels and monitors with DDC
+/// turned off in their menu are left out.
+pub async fn monitors() -> Result<Vec<Monitor>, DdcError> {
+    run(&["detect", "--terse"]).await.map(|out| parse_detect(&out))
+}
+
+/// Brightness of the monitor on `bus`.
+pub async fn brightness(bus: u32) -> Result<VcpValue, DdcError> {
+    let bus = bus.to_string();
+    let output = run(&["--bus", &bus, "--terse", "getvcp", BRIGHTNESS]).await?;
+    parse_getvcp(&output)
+        .ok_or_else(|| DdcError::Failed(format!("unexpected ddcutil output: {output}")))
+}
+
+/// Set the brightness of the monitor on `bus` to `percent` of its range.
+pub async fn set_brightness(bus: u32, percent: u8) -> Result<(), DdcError> {
+    let max = brightness(bus).await?.max;
+    let value = u32::from(max) * u32::from(percent.min(100)) / 100;
+    let bus = bus.to_string();
+    run(&["--bus", &bus, "setvcp", BRIGHTNESS, &value.to_string()]).await.map(drop)
+}
+
+/// Run ddcutil; stdout on success, otherwise stderr (or stdout) as the error.
+async fn run(args: &[&str]) -> Result<String, DdcError> {
+    let output = Command::new("ddcutil")
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(CMD_TIMEOUT, output).await {
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+            if output.status.success() {
+                return Ok(stdout);
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            Err(DdcError::Failed(if stderr.is_empty() { stdout } else { stderr }))
+        }
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => Err(DdcError::NotInstalled),
+        Ok(Err(e)) => Err(DdcError::Failed(format!("failed to run ddcutil: {e}"))),
+        Err(_) => Err(DdcError::TimedOut),
+    }
+}
+
+/// Parse `ddcutil detect --terse`: a `Display N` block per usable monitor,
+/// `Invalid display` blocks for the rest.
+pub fn parse_detect(output: &str) -> Vec<Monitor> {
+    let mut monitors = Vec::new();
+    let mut current: Option<Monitor> = None;
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with("Display ") || line.starts_with("Invalid display") {
+            monitors.extend(current.take().filter(|m| m.bus != u32::MAX));
+            if line.starts_with("Display ") {
+                current = Some(Monitor {
+                    bus: u32::MAX,
+                    model: String::new(),
+                    manufacturer: String::new(),
+                    serial: String::new(),
+                    connector: None,
+                });
+            }
+            continue;
+        }
+        let (Some(monitor), Some((key, value))) = (current.as_mut(), line.split_once(':')) else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "I2C bus" => {
+                if let Some(bus) = value.rsplit('-').next().and_then(|n| n.parse().ok()) {
+                    monitor.bus = bus;
+                }
+            }
+            "DRM connector" => monitor.connector = Some(value.to_owned()),
+            // MFG:model:serial
+            "Monitor" => {
+                let mut fields = value.splitn(3, ':');
+                monitor.manufacturer = fields.next().unwrap_or_default().to_owned();
+                monitor.model = fields.next().unwrap_or_default().to_owned();
+                monitor.serial = fields.next().unwrap_or_default().to_owned();
+            }
+            _ => {}
+        }
+    }
+    monitors.extend(current.filter(|m| m.bus != u32::MAX));
+    monitors
+}
+
+/// Parse `ddcutil --terse getvcp` of a continuous feature: `VCP 10 C 50 100`.
+pub fn parse_getvcp(output: &str) -> Option<VcpValue> {
+    let line = output.lines().find(|line| line.starts_with("VCP "))?;
+    match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["VCP", _, "C", current, max] => Some(VcpValue {
+            current: current.parse().ok()?,
+            max: max.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_skips_invalid_displays() {
+        let output = "Display 1\n\
+                      \x20  I2C bus:             /dev/i2c-6\n\
+                      \x20  DRM connector:       card1-DP-1\n\
+                      \x20  Monitor:             DEL:DELL U2719D:7Y1ZF23\n\
+                      \n\
+                      Invalid display\n\
+                      \x20  I2C bus:             /dev/i2c-4\n\
+                      \x20  DRM connector:       card1-eDP-1\n\
+                      \x20  Monitor:             BOE::\n\
+                      \n\
+                      Display 2\n\
+                      \x20  I2C bus:             /dev/i2c-7\n\
+                      \x20  Monitor:             GSM:LG HDR 4K:\n";
+        let monitors = parse_detect(output);
+        assert_eq!(monitors.len(), 2);
+        assert_eq!(monitors[0].bus, 6);
+        assert_eq!(monitors[0].model, "DELL U2719D");
+        assert_eq!(monitors[0].connector.as_deref(), Some("card1-DP-1"));
+        assert_eq!(monitors[1].bus, 7);
+        assert_eq!(monitors[1].manufacturer, "GSM");
+        assert_eq!(monitors[1].connector, None);
+    }
+
+    #[test]
+    fn getvcp_continuous_value() {
+        let value = parse_getvcp("VCP 10 C 30 75\n").unwrap();
+        assert_eq!(value, VcpValue { current: 30, max: 75 });
+        assert_eq!(value.percent(), 40);
+        assert_eq!(parse_getvcp("VCP 10 ERR"), None);
+    }
+}
@@ -0,0 +1,226 @@
+//! Freedesktop desktop entries and icon lookup.
+//!
+//! The dock launches its pinned apps from `.desktop` files and shows their
+//! icons; the settings app lists the installed entries to pin from. Only
+//! what those two need is implemented: the `[Desktop Entry]` group, `Exec`
+//! field codes, and icons from the hicolor/Adwaita themes and `pixmaps`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Icon themes searched in order.
+const ICON_THEMES: &[&str] = &["hicolor", "Adwaita"];
+
+/// Icon sizes searched in order; the dock shows icons at about 28 px.
+const ICON_SIZES: &[&str] = &["scalable", "48x48", "64x64", "32x32", "128x128", "256x256"];
+
+/// An installed application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopEntry {
+    /// File name without `.desktop`, e.g. `firefox-esr`.
+    pub id: String,
+    pub name: String,
+    /// The `Exec` line as written in the file.
+    pub exec: String,
+    /// Icon name or absolute path.
+    pub icon: Option<String>,
+    /// Must run inside a terminal emulator.
+    pub terminal: bool,
+}
+
+impl DesktopEntry {
+    /// Parse the `[Desktop Entry]` group of a desktop file.
+    ///
+    /// Returns `None` for entries that are not applications or that ask to
+    /// be hidden from menus (`NoDisplay`, `Hidden`).
+    pub fn parse(id: &str, content: &str) -> Option<Self> {
+        let mut in_group = false;
+        let mut name = None;
+        let mut exec = None;
+        let mut icon = None;
+        let mut terminal = false;
+
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_group = line == "[Desktop Entry]";
+                continue;
+            }
+            if !in_group || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "Type" if value != "Application" => return None,
+                "NoDisplay" | "Hidden" if value == "true" => return None,
+                "Name" => name = Some(value.to_owned()),
+                "Exec" => exec = Some(value.to_owned()),
+                "Icon" if !value.is_empty() => icon = Some(value.to_owned()),
+                "Terminal" => terminal = value == "true",
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            id: id.to_owned(),
+            name: name?,
+            exec: exec?,
+            icon,
+            terminal,
+        })
+    }
+
+    /// The `Exec` line split into arguments, with field codes (`%f`, `%U`,
+    /// ...) removed since the dock never passes files or URLs.
+    pub fn command(&self) -> Vec<String> {
+        split_exec(&self.exec)
+            .into_iter()
+            .filter(|arg| !is_field_code(arg))
+            .map(|arg| arg.replace("%%", "%"))
+            .collect()
+    }
+}
+
+/// `applications` directories in XDG precedence order: the user's data
+/// directory first, then `$XDG_DATA_DIRS`.
+pub fn application_dirs() -> Vec<PathBuf> {
+    data_dirs()
+        .into_iter()
+        .map(|dir| dir.join("applications"))
+        .collect()
+}
+
+/// Look up the entry with file name `id` (with or without `.desktop`).
+pub fn find(id: &str) -> Option<DesktopEntry> {
+    let id = id.strip_suffix(".desktop").unwrap_or(id);
+    application_dirs().into_iter().find_map(|dir| {
+        let content = std::fs::read_to_string(dir.join(format!("{id}.desktop"))).ok()?;
+        DesktopEntry::parse(id, &content)
+    })
+}
+
+/// All visible installed applications, sorted by name. An entry in the
+/// user's directory shadows a system one with the same id.
+pub fn all() -> Vec<DesktopEntry> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for dir in application_dirs() {
+        let Ok(files) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            let Some(id) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".desktop"))
+            else {
+                continue;
+            };
+            // Hidden entries shadow system ones too, so mark them seen first.
+            if !seen.insert(id.to_owned()) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if let Some(entry) = DesktopEntry::parse(id, &content) {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+    entries
+}
+
+/// Resolve an `Icon` value to an SVG or PNG file.
+pub fn icon_path(icon: &str) -> Option<PathBuf> {
+    let path = Path::new(icon);
+    if path.is_absolute() {
+        return path.exists().then(|| path.to_path_buf());
+    }
+
+    let data_dirs = data_dirs();
+    for theme in ICON_THEMES {
+        for size in ICON_SIZES {
+            for category in ["apps", "legacy"] {
+                for dir in &data_dirs {
+                    let base = dir.join("icons").join(theme).join(size).join(category);
+                    if let Some(found) = with_extension(&base, icon) {
+                        return Some(found);
+                    }
+                }
+            }
+        }
+    }
+    data_dirs
+        .iter()
+        .find_map(|dir| with_extension(&dir.join("pixmaps"), icon))
+}
+
+/// `dir/name.svg` or `dir/name.png`, whichever exists.
+fn with_extension(dir: &Path, name: &str) -> Option<PathBuf> {
+    ["svg", "png"]
+        .iter()
+        .map(|ext| dir.join(format!("{name}.{ext}")))
+        .find(|path| path.exists())
+}
+
+/// `$XDG_DATA_HOME` followed by `$XDG_DATA_DIRS` (with the spec defaults).
+fn data_dirs() -> Vec<PathBuf> {
+    let system = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_owned());
+    dirs::data_dir()
+        .into_iter()
+        .chain(system.split(':').map(PathBuf::from))
+        .collect()
+}
+
+/// Split an `Exec` value into arguments, honouring double quotes and
+/// backslash escapes inside them.
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quoted = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_arg = true;
+            }
+            '\\' if quoted => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+fn is_field_code(arg: &str) -> bool {
+    matches!(
+        arg,
+        "%f" | "%F" | "%u" | "%U" | "%d" | "%D" | "%n" | "%N" | "%i" | "%c" | "%k" | "%v" | "%m"
+    )
+}
@@ -0,0 +1,228 @@
+//! Coarse location from GeoClue, shared by the `location` tool and the
+//! agent's prompt.
+//!
+//! GeoClue is asked through its `where-am-i` demo, which most distributions
+//! ship and allow in `geoclue.conf`, at city accuracy: GeoClue then answers
+//! from Wi-Fi or IP geolocation and never turns on GPS. The fix is rounded
+//! further by [`Location::coarse`] before it leaves this module.
+
+use std::fmt;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+use crate::types::tool::ToolErrorKind;
+
+/// Where distributions install the demo: Debian and Fedora, then Arch.
+const WHERE_AM_I: [&str; 2] = [
+    "/usr/libexec/geoclue-2.0/demos/where-am-i",
+    "/usr/lib/geoclue-2.0/demos/where-am-i",
+];
+
+/// `GCLUE_ACCURACY_LEVEL_CITY`.
+const CITY_ACCURACY: &str = "4";
+
+/// Time GeoClue gets for a fix; the first one can take a Wi-Fi scan.
+const LOCATE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Decimal places kept of latitude and longitude: about a kilometer.
+const COARSE_DECIMALS: i32 = 2;
+
+/// Accuracy a coarse location claims at best, in meters.
+const COARSE_ACCURACY: f64 = 1000.0;
+
+/// Where the machine is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Radius the true position is likely within, in meters.
+    pub accuracy: f64,
+    /// Source of the fix as GeoClue describes it, e.g. `WiFi-based
+    /// location`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Location {
+    /// The location rounded to [`COARSE_DECIMALS`], with the accuracy
+    /// widened to match.
+    pub fn coarse(&self) -> Self {
+        let scale = 10f64.powi(COARSE_DECIMALS);
+        let round = |degrees: f64| (degrees * scale).round() / scale;
+        Self {
+            latitude: round(self.latitude),
+            longitude: round(self.longitude),
+            accuracy: self.accuracy.max(COARSE_ACCURACY).round(),
+            description: self.description.clone(),
+        }
+    }
+}
+
+/// Why no location could be had.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeoclueError {
+    /// GeoClue or its `where-am-i` demo is not installed.
+    NotInstalled,
+    /// GeoClue refused or found no position; its message if it gave one.
+    Failed(String),
+    TimedOut,
+}
+
+impl fmt::Display for GeoclueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInstalled => f.write_str("GeoClue (its where-am-i demo) is not installed"),
+            Self::Failed(message) if message.is_empty() => f.write_str("GeoClue found no location"),
+            Self::Failed(message) => write!(f, "GeoClue found no location: {message}"),
+            Self::TimedOut => f.write_str("GeoClue found no location in time"),
+        }
+    }
+}
+
+impl std::error::Error for GeoclueError {}
+
+impl GeoclueError {
+    /// How a tool reports this failure.
+    pub fn tool_kind(&self) -> ToolErrorKind {
+        match self {
+            Self::NotInstalled => ToolErrorKind::Unavailable,
+            Self::Failed(_) => ToolErrorKind::Failed,
+            Self::TimedOut => ToolErrorKind::Timeout,
+        }
+    }
+}
+
+/// Ask GeoClue for a city-level fix, already made [`Location::coarse`].
+pub async fn locate() -> Result<Location, GeoclueError> {
+    let program = WHERE_AM_I
+        .iter()
+        .find(|path| Path::new(path).exists())
+        .ok_or(GeoclueError::NotInstalled)?;
+    let mut child = Command::new(program)
+        .args(["-a", CITY_ACCURACY, "-t", &LOCATE_TIMEOUT.as_secs().to_string()])
+        // `%f` would print decimal commas in some locales.
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| GeoclueError::Failed(format!("failed to run where-am-i: {e}")))?;
+
+    // The demo keeps running to print updates; the first fix is enough.
+    let stdout = child.stdout.take().ok_or(GeoclueError::Failed(String::new()))?;
+    let first_fix = async {
+        let mut lines = BufReader::new(stdout).lines();
+        let mut output = String::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let done = line.starts_with("Timestamp:");
+            output.push_str(&line);
+            output.push('\n');
+            if done {
+                break;
+            }
+        }
+        output
+    };
+    let output = tokio::time::timeout(LOCATE_TIMEOUT + Duration::from_secs(5), first_fix)
+        .await
+        .map_err(|_| GeoclueError::TimedOut)?;
+    if let Some(location) = parse_where_am_i(&output) {
+        return Ok(location.coarse());
+    }
+
+    // Without a fix the demo has exited, or will not print one now.
+    let _ = child.start_kill();
+    let stderr = match child.wait_with_output().await {
+        Ok(rest) => String::from_utf8_lossy(&rest.stderr).trim().to_owned(),
+        Err(_) => String::new(),
+    };
+    Err(GeoclueError::Failed(stderr))
+}
+
+/// The first fix `where-am-i` prints:
+///
+/// ```text
+/// New location:
+/// Latitude:    52.520008°
+/// Longitude:   13.404954°
+/// Accuracy:    25000.000000 meters
+/// Description: WiFi-based location
+/// Timestamp:   Fri 16 Oct 2026 10:12:44 (1792145564 seconds since the Epoch)
+/// ```
+///
+/// Altitude, speed and heading lines may come before the description.
+fn parse_where_am_i(output: &str) -> Option<Location> {
+    let block = output.split_once("New location:")?.1;
+    let field = |name: &str| {
+        block
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+            .map(str::trim)
+    };
+    let number = |name: &str| -> Option<f64> {
+        let value = field(name)?;
+        let end = value
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '.')))
+            .unwrap_or(value.len());
+        value[..end].parse().ok()
+    };
+    Some(Location {
+        latitude: number("Latitude")?,
+        longitude: number("Longitude")?,
+        accuracy: number("Accuracy")?,
+        description: field("Description")
+            .filter(|d| !d.is_empty())
+            .map(str::to_owned),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "Client object: /org/freedesktop/GeoClue2/Client/1\n\
+                          \n\
+                          New location:\n\
+                          Latitude:    52.520008°\n\
+                          Longitude:   -13.404954°\n\
+                          Accuracy:    25000.000000 meters\n\
+                          Description: WiFi-based location\n\
+                          Timestamp:   Fri 16 Oct 2026 10:12:44\n";
+
+    #[test]
+    fn parses_the_first_fix() {
+        let location = parse_where_am_i(OUTPUT).unwrap();
+        assert_eq!(location.latitude, 52.520008);
+        assert_eq!(location.longitude, -13.404954);
+        assert_eq!(location.accuracy, 25000.0);
+        assert_eq!(location.description.as_deref(), Some("WiFi-based location"));
+    }
+
+    #[test]
+    fn no_fix_no_location() {
+        assert_eq!(
+            parse_where_am_i("Client object: /org/freedesktop/GeoClue2/Client/1\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn coarse_locations_are_rounded_to_a_kilometer() {
+        let precise = Location {
+            latitude: 52.520008,
+            longitude: -13.404954,
+            accuracy: 12.5,
+            description: None,
+        };
+        let coarse = precise.coarse();
+        assert_eq!(coarse.latitude, 52.52);
+        assert_eq!(coarse.longitude, -13.4);
+        assert_eq!(coarse.accuracy, 1000.0);
+    }
+}
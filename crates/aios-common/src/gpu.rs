@@ -0,0 +1,170 @@
+//! GPU load and video memory, shared by the `gpu_info` tool and the dock's
+//! GPU meter.
+//!
+//! NVIDIA cards are read through `nvidia-smi`, AMD cards through the amdgpu
+//! attributes under `/sys/class/drm/card*/device`. Other GPUs (Intel, the
+//! nouveau driver) report no load and are not listed.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::process::Command;
+
+use crate::types::system::GpuInfo;
+
+/// Time `nvidia-smi` gets; it can hang while the driver wakes a card.
+const CMD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Fields asked from `nvidia-smi`, in the order [`parse_nvidia_smi`] reads
+/// them.
+const NVIDIA_QUERY: &str =
+    "--query-gpu=name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw";
+
+/// PCI vendor id of AMD.
+const AMD_VENDOR: &str = "0x1002";
+
+/// Every NVIDIA and AMD GPU found; empty without one.
+pub async fn gpus() -> Vec<GpuInfo> {
+    let (mut gpus, amd) = tokio::join!(nvidia(), amd());
+    gpus.extend(amd);
+    gpus
+}
+
+/// The GPU with the highest [`GpuInfo::load`].
+pub async fn busiest() -> Option<GpuInfo> {
+    gpus().await.into_iter().max_by_key(GpuInfo::load)
+}
+
+async fn nvidia() -> Vec<GpuInfo> {
+    let output = Command::new("nvidia-smi")
+        .args([NVIDIA_QUERY, "--format=csv,noheader,nounits"])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(CMD_TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => {
+            parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Lines of `nvidia-smi --format=csv,noheader,nounits`, e.g.
+/// `NVIDIA GeForce RTX 3060, 35, 2048, 12288, 54, 28.50`. Memory is in
+/// MiB; fields the card does not support read `[N/A]`.
+fn parse_nvidia_smi(output: &str) -> Vec<GpuInfo> {
+    const MIB: u64 = 1024 * 1024;
+    output
+        .lines()
+        .filter_map(|line| {
+            // The name may contain commas; the numbers never do.
+            let mut fields: Vec<&str> = line.rsplitn(6, ',').map(str::trim).collect();
+            if fields.len() != 6 {
+                return None;
+            }
+            fields.reverse();
+            Some(GpuInfo {
+                name: fields[0].to_owned(),
+                utilization: fields[1].parse().ok(),
+                memory_used: fields[2].parse::<u64>().ok().map(|mib| mib * MIB),
+                memory_total: fields[3].parse::<u64>().ok().map(|mib| mib * MIB),
+                celsius: fields[4].parse().ok(),
+                watts: fields[5].parse().ok(),
+            })
+        })
+        .collect()
+}
+
+async fn amd() -> Vec<GpuInfo> {
+    let mut gpus = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir("/sys/class/drm").await else {
+        return gpus;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let card = entry.file_name().to_string_lossy().into_owned();
+        // `card0-DP-1` and the like are connectors of `card0`.
+        if !card.starts_with("card") || card.contains('-') {
+            continue;
+        }
+        let device = entry.path().join("device");
+        if read_trimmed(&device.join("vendor")).await.as_deref() != Some(AMD_VENDOR) {
+            continue;
+        }
+        gpus.push(amd_card(&device, &card).await);
+    }
+    gpus
+}
+
+async fn amd_card(device: &Path, card: &str) -> GpuInfo {
+    let hwmon = first_hwmon(device).await;
+    let sensor = |name: &str| hwmon.as_ref().map(|hwmon| hwmon.join(name));
+    let watts = match read_number(sensor("power1_average")).await {
+        Some(microwatts) => Some(microwatts),
+        None => read_number(sensor("power1_input")).await,
+    };
+    GpuInfo {
+        name: read_trimmed(&device.join("product_name"))
+            .await
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("AMD GPU ({card})")),
+        utilization: read_number(Some(device.join("gpu_busy_percent")))
+            .await
+            .and_then(|percent| u8::try_from(percent).ok()),
+        memory_used: read_number(Some(device.join("mem_info_vram_used"))).await,
+        memory_total: read_number(Some(device.join("mem_info_vram_total"))).await,
+        celsius: read_number(sensor("temp1_input"))
+            .await
+            .map(|millidegrees| millidegrees as f32 / 1000.0),
+        watts: watts.map(|microwatts| microwatts as f32 / 1_000_000.0),
+    }
+}
+
+/// `device/hwmon/hwmonN`, where amdgpu puts its sensors.
+async fn first_hwmon(device: &Path) -> Option<PathBuf> {
+    let mut entries = tokio::fs::read_dir(device.join("hwmon")).await.ok()?;
+    let entry = entries.next_entry().await.ok()??;
+    Some(entry.path())
+}
+
+async fn read_number(path: Option<PathBuf>) -> Option<u64> {
+    read_trimmed(&path?).await?.parse().ok()
+}
+
+async fn read_trimmed(path: &Path) -> Option<String> {
+    let content = tokio::fs::read_to_string(path).await.ok()?;
+    Some(content.trim().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nvidia_smi_lines() {
+        let output = "NVIDIA GeForce RTX 3060, 35, 2048, 12288, 54, 28.50\n\
+                      Tesla T4, [N/A], 0, 15360, 40, [N/A]\n";
+        let gpus = parse_nvidia_smi(output);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].name, "NVIDIA GeForce RTX 3060");
+        assert_eq!(gpus[0].utilization, Some(35));
+        assert_eq!(gpus[0].memory_used, Some(2048 * 1024 * 1024));
+        assert_eq!(gpus[0].memory_percent(), Some(16));
+        assert_eq!(gpus[0].watts, Some(28.5));
+        assert_eq!(gpus[1].utilization, None);
+        assert_eq!(gpus[1].watts, None);
+        assert_eq!(gpus[1].load(), 0);
+    }
+
+    #[test]
+    fn keeps_commas_in_names() {
+        let gpus = parse_nvidia_smi("Quadro RTX 4000, Mobile, 5, 100, 8192, 50, 10\n");
+        assert_eq!(gpus[0].name, "Quadro RTX 4000, Mobile");
+        assert_eq!(gpus[0].utilization, Some(5));
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        assert!(parse_nvidia_smi("No devices were found\n").is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+//! Memory available for local models, and which Ollama model fits in it.
+//!
+//! The setup wizard and the settings app recommend a model size for the
+//! machine (3B, 8B or 14B parameters) and warn when a chosen model likely
+//! won't fit. Sizes are estimates for Ollama's default 4-bit quantization.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::t;
+
+/// Models recommended by size, largest first, with their parameter count in
+/// billions.
+const RECOMMENDED: &[(&str, f64)] = &[
+    ("qwen2.5:14b", 14.0),
+    ("llama3.1:8b", 8.0),
+    ("llama3.2:3b", 3.0),
+];
+
+/// Parameter counts (billions) of the default tag of common models, for
+/// names without a size tag.
+const DEFAULT_SIZES: &[(&str, f64)] = &[
+    ("llama3.2", 3.0),
+    ("llama3.1", 8.0),
+    ("llama3", 8.0),
+    ("mistral", 7.0),
+    ("qwen2.5", 7.0),
+    ("qwen3", 8.0),
+    ("gemma2", 9.0),
+    ("gemma3", 4.0),
+    ("phi4-mini", 3.8),
+    ("phi4", 14.0),
+    ("deepseek-r1", 8.0),
+    ("codellama", 7.0),
+];
+
+/// Bytes per parameter of a 4-bit quantized model, scales included.
+const BYTES_PER_PARAMETER: f64 = 0.6;
+
+/// Memory for the context (KV cache) and runtime on top of the weights.
+const RUNTIME_OVERHEAD: u64 = 1_000_000_000;
+
+/// RAM left to the desktop and other programs.
+const SYSTEM_RESERVE: u64 = 2_000_000_000;
+
+/// Share of the usable memory a model may take before it counts as tight.
+const COMFORTABLE_SHARE: f64 = 0.8;
+
+/// Memory of this machine, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Memory {
+    pub ram: u64,
+    /// Memory of the largest GPU, if one was found. Apple silicon shares
+    /// RAM with the GPU and reports none.
+    pub vram: Option<u64>,
+}
+
+/// How well a model fits in [`Memory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    Fits,
+    /// Fits, but leaves little memory for anything else; expect it to be
+    /// slow or to push other programs out.
+    Tight,
+    /// Likely won't load, or only swapping.
+    TooLarge,
+}
+
+impl Memory {
+    /// Probe RAM and VRAM, or `None` if the RAM size is unknown. Runs
+    /// `nvidia-smi` or `sysctl`, so call it off the UI thread.
+    pub fn probe() -> Option<Self> {
+        Some(Self {
+            ram: total_ram()?,
+            vram: total_vram(),
+        })
+    }
+
+    /// Memory a model may use: the GPU's if it is larger than what RAM can
+    /// spare, since Ollama then runs the model on the GPU.
+    pub fn usable(&self) -> u64 {
+        let ram = self.ram.saturating_sub(SYSTEM_RESERVE);
+        self.vram.unwrap_or(0).max(ram)
+    }
+
+    /// How a model needing `required` bytes fits.
+    pub fn fit(&self, required: u64) -> Fit {
+        let usable = self.usable();
+        if (required as f64) <= usable as f64 * COMFORTABLE_SHARE {
+            Fit::Fits
+        } else if required <= usable {
+            Fit::Tight
+        } else {
+            Fit::TooLarge
+        }
+    }
+
+    /// The largest of the recommended models that fits comfortably; the
+    /// smallest one on machines too small for any.
+    pub fn recommended_model(&self) -> &'static str {
+        RECOMMENDED
+            .iter()
+            .find(|(_, parameters)| self.fit(required_memory(*parameters)) == Fit::Fits)
+            .or(RECOMMENDED.last())
+            .map(|(name, _)| *name)
+            .unwrap_or_default()
+    }
+
+    /// "15.6 GB RAM, 8.0 GB GPU memory".
+    pub fn describe(&self) -> String {
+        match self.vram {
+            Some(vram) => t!(
+                "hardware-memory-gpu",
+                ram = format_gb(self.ram),
+                vram = format_gb(vram)
+            ),
+            None => t!("hardware-memory", ram = format_gb(self.ram)),
+        }
+    }
+
+    /// Why `model` may not run well here, or `None` if it fits or its size
+    /// is unknown.
+    pub fn warning(&self, model: &str, size_on_disk: Option<u64>) -> Option<String> {
+        let required = estimate_model(model, size_on_disk)?;
+        let size = format_gb(required);
+        match self.fit(required) {
+            Fit::Fits => None,
+            Fit::Tight => Some(t!("hardware-fit-tight", size = size)),
+            Fit::TooLarge => Some(t!("hardware-fit-too-large", size = size)),
+        }
+    }
+}
+
+/// Bytes a model with `parameters` billion parameters needs to run.
+pub fn required_memory(parameters: f64) -> u64 {
+    (parameters * 1e9 * BYTES_PER_PARAMETER) as u64 + RUNTIME_OVERHEAD
+}
+
+/// Bytes `model` needs to run, from its size on disk when known, else from
+/// the parameter count in its name (`llama3.1:8b`) or the default tag.
+pub fn estimate_model(model: &str, size_on_disk: Option<u64>) -> Option<u64> {
+    if let Some(size) = size_on_disk.filter(|&size| size > 0) {
+        return Some(size + RUNTIME_OVERHEAD);
+    }
+    parameter_count(model).map(required_memory)
+}
+
+/// Parameter count in billions from an Ollama model name: the size tag
+/// (`qwen2.5:14b`, `mixtral:8x7b`, `qwen2.5:0.5b-instruct`, `gemma3:270m`)
+/// or, without one, the default tag of a known model.
+pub fn parameter_count(model: &str) -> Option<f64> {
+    let (base, tag) = model.split_once(':').unwrap_or((model, ""));
+    let base = base.rsplit('/').next().unwrap_or(base);
+    tag.split(['-', '_'])
+        .find_map(parse_parameter_size)
+        .or_else(|| {
+            DEFAULT_SIZES
+                .iter()
+                .find(|(name, _)| *name == base)
+                .map(|(_, parameters)| *parameters)
+        })
+}
+
+/// `8b`, `8.0B`, `8x7b`, `270m` as billions of parameters; also reads the
+/// `parameter_size` Ollama reports for installed models.
+pub fn parse_parameter_size(size: &str) -> Option<f64> {
+    let size = size.trim().to_ascii_lowercase();
+    let (number, scale) = if let Some(number) = size.strip_suffix('b') {
+        (number, 1.0)
+    } else if let Some(number) = size.strip_suffix('m') {
+        (number, 0.001)
+    } else {
+        return None;
+    };
+    let count = match number.split_once('x') {
+        Some((experts, each)) => experts.parse::<f64>().ok()? * each.parse::<f64>().ok()?,
+        None => number.parse::<f64>().ok()?,
+    };
+    Some(count * scale)
+}
+
+/// `bytes` in gigabytes for people: "15.6 GB".
+pub fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / 1e9)
+}
+
+fn total_ram() -> Option<u64> {
+    if cfg!(target_os = "macos") {
+        return command_output("sysctl", &["-n", "hw.memsize"])?
+            .trim()
+            .parse()
+            .ok();
+    }
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let kib: u64 = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// VRAM of the largest NVIDIA or AMD GPU.
+fn total_vram() -> Option<u64> {
+    let nvidia = command_output(
+        "nvidia-smi",
+        &["--query-gpu=memory.total", "--format=csv,noheader,nounits"],
+    )
+    .into_iter()
+    .flat_map(|output| {
+        output
+            .lines()
+            .filter_map(|mib| mib.trim().parse::<u64>().ok())
+            .map(|mib| mib * 1024 * 1024)
+            .collect::<Vec<_>>()
+    });
+    let amd = std::fs::read_dir("/sys/class/drm")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path().join("device/mem_info_vram_total");
+            read_number(&path)
+        });
+    nvidia.chain(amd).max()
+}
+
+fn read_number(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
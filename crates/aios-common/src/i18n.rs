@@ -0,0 +1,156 @@
+//! UI localization shared by the desktop apps.
+//!
+//! Translations are Fluent (`.ftl`) files compiled into the binary from
+//! `crates/aios-common/locales/`. Each app calls [`init_from_config`] once at
+//! startup and then looks strings up with the [`t!`](crate::t) macro:
+//!
+//! ```ignore
+//! text(aios_common::t!("input-send"));
+//! text(aios_common::t!("oobe-enter-api-key", provider = "Claude"));
+//! ```
+//!
+//! Missing keys fall back to English, then to the key itself, so an
+//! untranslated string never panics or renders empty.
+
+use std::sync::{OnceLock, RwLock};
+
+use fluent_bundle::FluentResource;
+use fluent_bundle::concurrent::FluentBundle;
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+use crate::UiConfig;
+
+/// Language used when nothing else matches, and for missing keys.
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Languages with a bundled translation: `(code, native name)`.
+pub const LANGUAGES: &[(&str, &str)] = &[("en", "English"), ("ru", "Русский")];
+
+/// Bundled Fluent sources, in the same order as [`LANGUAGES`].
+const SOURCES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("ru", include_str!("../locales/ru.ftl")),
+];
+
+static BUNDLES: OnceLock<Vec<(&'static str, FluentBundle<FluentResource>)>> = OnceLock::new();
+static CURRENT: RwLock<&'static str> = RwLock::new(FALLBACK_LANGUAGE);
+
+/// Select the UI language. `auto` (or an unknown code) follows the system
+/// locale; codes without a translation fall back to English.
+pub fn set_language(language: &str) {
+    let requested = if language.is_empty() || language == "auto" {
+        detect_locale()
+    } else {
+        language.to_owned()
+    };
+    let resolved = resolve(&requested);
+    tracing::info!(requested = %requested, language = resolved, "UI language selected");
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = resolved;
+}
+
+/// The language code currently in use.
+pub fn current_language() -> &'static str {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Read `ui.language` from `~/.config/aios/agent.toml` and apply it.
+///
+/// A missing or unreadable config means `auto`.
+pub fn init_from_config() {
+    set_language(&UiConfig::load().language);
+}
+
+/// Language from the POSIX locale variables (`LC_ALL`, `LC_MESSAGES`,
+/// `LANG`), e.g. `ru_RU.UTF-8` becomes `ru`.
+pub fn detect_locale() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .and_then(|value| {
+            let tag = value.split(['.', '@']).next()?.replace('_', "-");
+            let id: LanguageIdentifier = tag.parse().ok()?;
+            Some(id.language.as_str().to_owned())
+        })
+        .unwrap_or_else(|| FALLBACK_LANGUAGE.to_owned())
+}
+
+/// Translate `key` in the current language.
+///
+/// Prefer the [`t!`](crate::t) macro, which builds `args` for you.
+pub fn tr(key: &str, args: Option<&FluentArgs<'_>>) -> String {
+    let bundles = bundles();
+    let current = current_language();
+    [current, FALLBACK_LANGUAGE]
+        .iter()
+        .find_map(|lang| {
+            let (_, bundle) = bundles.iter().find(|(code, _)| code == lang)?;
+            let pattern = bundle.get_message(key)?.value()?;
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!(key, lang, ?errors, "Fluent formatting errors");
+            }
+            Some(text.into_owned())
+        })
+        .unwrap_or_else(|| {
+            tracing::warn!(key, "Missing translation");
+            key.to_owned()
+        })
+}
+
+/// Look up a localized UI string.
+///
+/// `t!("key")` or `t!("key", name = value, ...)`; values may be anything
+/// convertible into a Fluent value (strings and numbers).
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key, None)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $( args.set(stringify!($name), $value); )+
+        $crate::i18n::tr($key, Some(&args))
+    }};
+}
+
+/// Map a requested language code (`ru`, `ru-RU`, `pt_BR`) onto a bundled
+/// one, falling back to English.
+pub fn resolve(requested: &str) -> &'static str {
+    let primary = requested
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == primary)
+        .unwrap_or(FALLBACK_LANGUAGE)
+}
+
+fn bundles() -> &'static [(&'static str, FluentBundle<FluentResource>)] {
+    BUNDLES.get_or_init(|| {
+        SOURCES
+            .iter()
+            .map(|(code, source)| {
+                let resource = FluentResource::try_new((*source).to_owned())
+                    .unwrap_or_else(|(resource, errors)| {
+                        tracing::error!(lang = code, ?errors, "Invalid Fluent resource");
+                        resource
+                    });
+                let langid: LanguageIdentifier = code.parse().unwrap_or_default();
+                let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+                // Unicode isolation marks render as boxes in iced.
+                bundle.set_use_isolating(false);
+                if let Err(errors) = bundle.add_resource(resource) {
+                    tracing::error!(lang = code, ?errors, "Duplicate Fluent messages");
+                }
+                (*code, bundle)
+            })
+            .collect()
+    })
+}
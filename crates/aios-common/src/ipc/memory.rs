@@ -0,0 +1,521 @@
+//! In-memory IPC transport for tests and third-party client development.
+//!
+//! [`duplex`] returns two connected ends that speak the same
+//! [`LengthPrefixedCodec`] framing as the Unix socket transport, so a client
+//! or a fake agent can be exercised without binding a socket.
+
+use tokio::io::{AsyncWriteExt, DuplexStream};
+
+use crate::error::AiosError;
+
+use super::protocol::{IpcMessage, LengthPrefixedCodec};
+
+/// Buffer size of each direction of a [`duplex`] pair; large enough for a
+/// maximum-size frame so a single `send` never waits for the peer.
+const BUFFER_SIZE: usize = LengthPrefixedCodec::MAX_MESSAGE_SIZE as usize + 4;
+
+/// Create a connected pair of in-memory IPC endpoints.
+pub fn duplex() -> (MemoryConnection, MemoryConnection) {
+    let (a, b) = tokio::io::duplex(BUFFER_SIZE);
+    (MemoryConnection { stream: a }, MemoryConnection { stream: b })
+}
+
+/// One end of an in-memory IPC connection, see [`duplex`].
+pub struct MemoryConnection {
+    stream: DuplexStream,
+}
+
+impl MemoryConnection {
+    /// Send an IPC message to the other end.
+    ///
+    /// # Errors
+    ///
+    /// Returns encoding or I/O errors.
+    pub async fn send(&mut self, msg: &IpcMessage) -> Result<(), AiosError> {
+        LengthPrefixedCodec::write(&mut self.stream, msg).await
+    }
+
+    /// Receive the next IPC message from the other end.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AiosError::ConnectionClosed`] on EOF, or decoding/I/O errors.
+    pub async fn recv(&mut self) -> Result<IpcMessage, AiosError> {
+        LengthPrefixedCodec::decode(&mut self.stream).await
+    }
+
+    /// Write raw bytes, bypassing the codec, to feed the other end
+    /// malformed or oversized frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors.
+    pub async fn send_raw(&mut self, bytes: &[u8]) -> Result<(), AiosError> {
+        self.stream.write_all(bytes).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+
+    /// Close the write direction; the other end then sees
+    /// [`AiosError::ConnectionClosed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors.
+    pub async fn shutdown(&mut self) -> Result<(), AiosError> {
+        self.stream.shutdown().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::audit::{AuditFilter, AuditRange};
+    use crate::ipc::{ClientType, IpcPayload};
+    use crate::types::message::{Attachment, AttachmentContent, ChatMessage, MessageContent, Role};
+    use crate::types::model::ModelInfo;
+    use crate::types::risk::RiskSummary;
+    use crate::types::session::{ComponentState, ComponentStatus};
+    use crate::types::system::{AgentHealth, LogLine, NetworkStatus, SystemStatus};
+    use crate::types::tool::Sensor;
+    use crate::types::trust::TrustLevel;
+
+    /// Position of `payload`'s variant in [`IpcPayload`]. The match has no
+    /// wildcard, so a new variant does not compile until it is listed here
+    /// and given a sample in [`samples`].
+    fn variant_index(payload: &IpcPayload) -> usize {
+        match payload {
+            IpcPayload::ChatRequest { .. } => 0,
+            IpcPayload::EditMessage { .. } => 1,
+            IpcPayload::MessageReceived { .. } => 2,
+            IpcPayload::RegenerateResponse { .. } => 3,
+            IpcPayload::CancelRequest { .. } => 4,
+            IpcPayload::ClearConversation { .. } => 5,
+            IpcPayload::SetSystemPrompt { .. } => 6,
+            IpcPayload::ChatResponse { .. } => 7,
+            IpcPayload::StreamChunk { .. } => 8,
+            IpcPayload::VoiceStart { .. } => 9,
+            IpcPayload::VoiceAudio { .. } => 10,
+            IpcPayload::VoiceStop { .. } => 11,
+            IpcPayload::Transcript { .. } => 12,
+            IpcPayload::ConfirmRequest { .. } => 13,
+            IpcPayload::ConfirmResponse { .. } => 14,
+            IpcPayload::Register { .. } => 15,
+            IpcPayload::RegisterAck { .. } => 16,
+            IpcPayload::ReloadConfig => 17,
+            IpcPayload::ConfigReloaded { .. } => 18,
+            IpcPayload::SetModel { .. } => 19,
+            IpcPayload::ListModels => 20,
+            IpcPayload::Models { .. } => 21,
+            IpcPayload::CommandResult { .. } => 22,
+            IpcPayload::GetConfig { .. } => 23,
+            IpcPayload::ConfigSection { .. } => 24,
+            IpcPayload::SetConfig { .. } => 25,
+            IpcPayload::AboutRequest => 26,
+            IpcPayload::AgentAbout { .. } => 27,
+            IpcPayload::RestartAgent => 28,
+            IpcPayload::LogsRequest { .. } => 29,
+            IpcPayload::LogLines { .. } => 30,
+            IpcPayload::SystemInfo { .. } => 31,
+            IpcPayload::SystemStatus { .. } => 32,
+            IpcPayload::SetVolume { .. } => 33,
+            IpcPayload::SetMuted { .. } => 34,
+            IpcPayload::SetBrightness { .. } => 35,
+            IpcPayload::AgentHealth { .. } => 36,
+            IpcPayload::ToolStarted { .. } => 37,
+            IpcPayload::ToolFinished { .. } => 38,
+            IpcPayload::ToolProgress { .. } => 39,
+            IpcPayload::AgentPlan { .. } => 40,
+            IpcPayload::SetPaused { .. } => 41,
+            IpcPayload::AgentPaused { .. } => 42,
+            IpcPayload::Notifications { .. } => 43,
+            IpcPayload::MarkNotificationsRead => 44,
+            IpcPayload::ClearNotifications => 45,
+            IpcPayload::AuditQuery { .. } => 46,
+            IpcPayload::AuditEntries { .. } => 47,
+            IpcPayload::CalendarRequest { .. } => 48,
+            IpcPayload::CalendarEvents { .. } => 49,
+            IpcPayload::McpMessage { .. } => 50,
+            IpcPayload::SessionStatusRequest => 51,
+            IpcPayload::SessionStatus { .. } => 52,
+            IpcPayload::SetComponentRunning { .. } => 53,
+            IpcPayload::Error { .. } => 54,
+            IpcPayload::Ping => 55,
+            IpcPayload::Pong => 56,
+        }
+    }
+
+    const VARIANT_COUNT: usize = 57;
+
+    /// One payload of every variant, with awkward strings where there are
+    /// strings.
+    fn samples() -> Vec<IpcPayload> {
+        let id = Uuid::new_v4();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        vec![
+            IpcPayload::ChatRequest {
+                message: "привет \u{0} \"quoted\" \n\t 🦀".into(),
+                conversation_id: id,
+                message_id: Some(Uuid::new_v4()),
+                attachments: vec![Attachment {
+                    name: "notes.txt".into(),
+                    media_type: "text/plain".into(),
+                    content: AttachmentContent::Text { text: "x".repeat(1000) },
+                }],
+            },
+            IpcPayload::EditMessage {
+                conversation_id: id,
+                message_id: id,
+                message: String::new(),
+            },
+            IpcPayload::MessageReceived {
+                conversation_id: id,
+                message_id: id,
+            },
+            IpcPayload::RegenerateResponse { conversation_id: id },
+            IpcPayload::CancelRequest { conversation_id: id },
+            IpcPayload::ClearConversation { conversation_id: id },
+            IpcPayload::SetSystemPrompt {
+                conversation_id: id,
+                prompt: None,
+            },
+            IpcPayload::ChatResponse {
+                message: ChatMessage {
+                    id,
+                    role: Role::Assistant,
+                    content: MessageContent::Text { text: "done".into() },
+                    trust_level: TrustLevel::System,
+                    timestamp: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+                },
+                usage: None,
+                reasoning: Some("<think>\"2+2\"</think>".into()),
+            },
+            IpcPayload::StreamChunk {
+                request_id: id,
+                delta: "\u{feff}".into(),
+                done: true,
+            },
+            IpcPayload::VoiceStart { session_id: id },
+            IpcPayload::VoiceAudio {
+                session_id: id,
+                pcm: "AAAA".into(),
+            },
+            IpcPayload::VoiceStop { session_id: id },
+            IpcPayload::Transcript {
+                session_id: id,
+                text: "hello".into(),
+                is_final: false,
+            },
+            IpcPayload::ConfirmRequest {
+                action_id: id,
+                action_type: "file_delete".into(),
+                description: "Delete a file".into(),
+                command: "{}".into(),
+                trust_level: TrustLevel::WebContent,
+                risk: Some(RiskSummary {
+                    summary: "Deletes /tmp/x".into(),
+                    affected_paths: vec!["/tmp/x".into()],
+                    reversible: false,
+                    network_access: false,
+                }),
+                can_remember: true,
+            },
+            IpcPayload::ConfirmResponse {
+                action_id: id,
+                approved: true,
+                reason: Some("ok".into()),
+                remember: true,
+            },
+            IpcPayload::Register {
+                client_type: ClientType::Cli,
+            },
+            IpcPayload::RegisterAck { success: true },
+            IpcPayload::ReloadConfig,
+            IpcPayload::ConfigReloaded {
+                success: false,
+                message: "bad toml".into(),
+            },
+            IpcPayload::SetModel {
+                model: "llama3.2:3b".into(),
+            },
+            IpcPayload::ListModels,
+            IpcPayload::Models {
+                provider: "ollama".into(),
+                current: "llama3.2:3b".into(),
+                models: vec![
+                    ModelInfo::new("qwen3:8b"),
+                    ModelInfo {
+                        display_name: Some("Llama \"3.2\"".into()),
+                        size_bytes: Some(2_019_393_189),
+                        parameters: Some("3.2B".into()),
+                        created: Some(Utc.timestamp_opt(1_700_000_000, 0).unwrap()),
+                        ..ModelInfo::new("llama3.2:3b")
+                    },
+                ],
+            },
+            IpcPayload::CommandResult {
+                success: true,
+                message: String::new(),
+            },
+            IpcPayload::GetConfig {
+                section: "agent".into(),
+            },
+            IpcPayload::ConfigSection {
+                section: "ui".into(),
+                value: json!({ "nested": [1, 2.5, null, { "k": "v" }] }),
+            },
+            IpcPayload::SetConfig {
+                section: "ui".into(),
+                value: json!(null),
+            },
+            IpcPayload::AboutRequest,
+            IpcPayload::AgentAbout {
+                version: "0.1.0".into(),
+                provider: Some("ollama".into()),
+                model: None,
+            },
+            IpcPayload::RestartAgent,
+            IpcPayload::LogsRequest { after: u64::MAX },
+            IpcPayload::LogLines {
+                lines: vec![LogLine {
+                    seq: 1,
+                    text: "started".into(),
+                }],
+            },
+            IpcPayload::SystemInfo { info: json!({}) },
+            IpcPayload::SystemStatus {
+                status: SystemStatus {
+                    network: NetworkStatus {
+                        connected: true,
+                        wireless: true,
+                    },
+                    volume: None,
+                    battery: None,
+                    brightness: Some(100),
+                    gpu: None,
+                },
+            },
+            IpcPayload::SetVolume { percent: 0 },
+            IpcPayload::SetMuted { muted: true },
+            IpcPayload::SetBrightness { percent: 255 },
+            IpcPayload::AgentHealth {
+                health: AgentHealth::ProviderError {
+                    provider: "claude".into(),
+                    message: "401".into(),
+                },
+            },
+            IpcPayload::ToolStarted {
+                call_id: id,
+                tool: "camera_snapshot".into(),
+                sensors: vec![Sensor::Camera],
+            },
+            IpcPayload::ToolFinished {
+                call_id: id,
+                tool: "camera_snapshot".into(),
+                success: false,
+            },
+            IpcPayload::ToolProgress {
+                conversation_id: id,
+                call_id: id,
+                tool: "shell_exec".into(),
+                output: "\u{1b}[1mCompiling\u{1b}[0m aios \r\n".into(),
+            },
+            IpcPayload::AgentPlan {
+                conversation_id: id,
+                message: ChatMessage {
+                    id,
+                    role: Role::Assistant,
+                    content: MessageContent::Plan {
+                        text: "Read \"Cargo.toml\" first, then build.".into(),
+                    },
+                    trust_level: TrustLevel::System,
+                    timestamp: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+                },
+            },
+            IpcPayload::SetPaused { paused: true },
+            IpcPayload::AgentPaused { paused: false },
+            IpcPayload::Notifications {
+                notifications: Vec::new(),
+            },
+            IpcPayload::MarkNotificationsRead,
+            IpcPayload::ClearNotifications,
+            IpcPayload::AuditQuery {
+                filter: AuditFilter {
+                    text: Some("rm".into()),
+                    ..AuditFilter::default()
+                },
+                range: AuditRange::default(),
+                limit: 50,
+            },
+            IpcPayload::AuditEntries {
+                entries: Vec::new(),
+                truncated: true,
+            },
+            IpcPayload::CalendarRequest { date },
+            IpcPayload::CalendarEvents {
+                date,
+                events: Vec::new(),
+            },
+            IpcPayload::McpMessage {
+                message: json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }),
+            },
+            IpcPayload::SessionStatusRequest,
+            IpcPayload::SessionStatus {
+                components: vec![ComponentStatus {
+                    name: "aios-dock".into(),
+                    state: ComponentState::Restarting,
+                    pid: None,
+                    restarts: 3,
+                    last_exit: Some("signal: 11 (SIGSEGV) \"core\"".into()),
+                    since: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+                }],
+            },
+            IpcPayload::SetComponentRunning {
+                name: "aios-confirm".into(),
+                running: false,
+            },
+            IpcPayload::Error {
+                message: "boom".into(),
+                code: Some("E42".into()),
+            },
+            IpcPayload::Ping,
+            IpcPayload::Pong,
+        ]
+    }
+
+    fn message(payload: IpcPayload) -> IpcMessage {
+        IpcMessage {
+            id: Uuid::new_v4(),
+            payload,
+        }
+    }
+
+    /// xorshift64, so the generated inputs are reproducible without a
+    /// dependency.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn string(&mut self, max_len: usize) -> String {
+            const ALPHABET: &[char] = &[
+                'a', 'Z', '0', ' ', '"', '\\', '\n', '\0', 'ж', '🦀', '\u{7f}',
+            ];
+            (0..self.below(max_len + 1))
+                .map(|_| ALPHABET[self.below(ALPHABET.len())])
+                .collect()
+        }
+    }
+
+    #[test]
+    fn samples_cover_every_variant() {
+        let covered: BTreeSet<usize> = samples().iter().map(variant_index).collect();
+        assert_eq!(covered.len(), VARIANT_COUNT);
+    }
+
+    #[tokio::test]
+    async fn every_variant_round_trips() {
+        let (mut a, mut b) = duplex();
+        for payload in samples() {
+            let sent = message(payload);
+            a.send(&sent).await.unwrap();
+            let received = b.recv().await.unwrap();
+            assert_eq!(received.id, sent.id);
+            assert_eq!(
+                serde_json::to_value(&received).unwrap(),
+                serde_json::to_value(&sent).unwrap(),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn random_strings_round_trip() {
+        let (mut a, mut b) = duplex();
+        let mut rng = Rng(0x5eed_cafe);
+        for _ in 0..200 {
+            let sent = message(IpcPayload::Error {
+                message: rng.string(64),
+                code: Some(rng.string(8)),
+            });
+            a.send(&sent).await.unwrap();
+            let received = b.recv().await.unwrap();
+            assert_eq!(
+                serde_json::to_value(&received).unwrap(),
+                serde_json::to_value(&sent).unwrap(),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn garbage_frames_are_rejected() {
+        let mut rng = Rng(0xdead_beef);
+        for _ in 0..200 {
+            let (mut a, mut b) = duplex();
+            let len = rng.below(64);
+            let body: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+            let mut frame = (len as u32).to_be_bytes().to_vec();
+            frame.extend_from_slice(&body);
+            a.send_raw(&frame).await.unwrap();
+            // Random bytes never form a JSON object with a known `type`.
+            assert!(matches!(b.recv().await, Err(AiosError::Json(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_type_is_rejected() {
+        let (mut a, mut b) = duplex();
+        let body = br#"{"id":"00000000-0000-0000-0000-000000000000","type":"no_such_payload"}"#;
+        let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(body);
+        a.send_raw(&frame).await.unwrap();
+        assert!(matches!(b.recv().await, Err(AiosError::Json(_))));
+    }
+
+    #[tokio::test]
+    async fn oversized_frame_is_rejected_before_reading_the_body() {
+        let (mut a, mut b) = duplex();
+        let len = LengthPrefixedCodec::MAX_MESSAGE_SIZE + 1;
+        a.send_raw(&len.to_be_bytes()).await.unwrap();
+        assert!(matches!(b.recv().await, Err(AiosError::Protocol(_))));
+    }
+
+    #[test]
+    fn oversized_message_is_not_encoded() {
+        let too_big = message(IpcPayload::SystemInfo {
+            info: json!("x".repeat(LengthPrefixedCodec::MAX_MESSAGE_SIZE as usize)),
+        });
+        assert!(matches!(
+            LengthPrefixedCodec::encode(&too_big),
+            Err(AiosError::Protocol(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn truncated_frame_and_eof() {
+        let (mut a, mut b) = duplex();
+        a.send_raw(&[0, 0]).await.unwrap();
+        a.shutdown().await.unwrap();
+        assert!(matches!(b.recv().await, Err(AiosError::ConnectionClosed)));
+
+        let (mut a, mut b) = duplex();
+        a.send_raw(&[0, 0, 0, 10, b'{']).await.unwrap();
+        a.shutdown().await.unwrap();
+        assert!(matches!(b.recv().await, Err(AiosError::Io(_))));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod memory;
 pub mod protocol;
 pub mod transport;
 
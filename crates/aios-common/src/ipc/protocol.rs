@@ -1,12 +1,26 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use uuid::Uuid;
 
+use crate::audit::{AuditEntry, AuditFilter, AuditRange};
 use crate::error::AiosError;
-use crate::types::message::ChatMessage;
+use crate::types::calendar::CalendarEvent;
+use crate::types::message::{Attachment, ChatMessage};
+use crate::types::model::ModelInfo;
+use crate::types::notification::AgentNotification;
+use crate::types::risk::RiskSummary;
+use crate::types::session::ComponentStatus;
+use crate::types::system::{AgentHealth, LogLine, SystemStatus};
+use crate::types::tool::Sensor;
 use crate::types::trust::TrustLevel;
+use crate::types::usage::UsageReport;
 
-/// IPC message envelope with a unique identifier and typed payload.
+/// IPC message envelope with an identifier and typed payload.
+///
+/// Requests get a fresh `id`; messages sent while handling one (its reply,
+/// confirmation requests, tool activity) carry the request's `id`, see
+/// [`crate::telemetry`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IpcMessage {
     pub id: Uuid,
@@ -22,9 +36,56 @@ pub enum IpcPayload {
     ChatRequest {
         message: String,
         conversation_id: Uuid,
+        /// Client-chosen id for the stored user message, so later
+        /// `EditMessage` requests can refer back to it.
+        #[serde(default)]
+        message_id: Option<Uuid>,
+        /// Files dropped into the chat window alongside the message, or
+        /// referenced by path for the agent to read.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        attachments: Vec<Attachment>,
+    },
+    /// Replace a previous user message: the conversation is truncated at
+    /// `message_id` (inclusive) and `message` is sent in its place.
+    EditMessage {
+        conversation_id: Uuid,
+        message_id: Uuid,
+        message: String,
+    },
+    /// Receipt for a `ChatRequest` or `EditMessage`, sent to chat windows
+    /// as soon as the agent has it, before any reply.
+    MessageReceived {
+        conversation_id: Uuid,
+        message_id: Uuid,
+    },
+    /// Drop everything after the last user message and ask the LLM again.
+    RegenerateResponse {
+        conversation_id: Uuid,
+    },
+    /// Stop the reply currently being generated for the sending client.
+    /// The agent answers the interrupted request with a short "cancelled"
+    /// `ChatResponse`; it is a no-op when nothing is running.
+    CancelRequest {
+        conversation_id: Uuid,
+    },
+    /// Forget the agent-side history of a conversation.
+    ClearConversation {
+        conversation_id: Uuid,
+    },
+    /// Override the system prompt for a single conversation. `None` restores
+    /// the default prompt.
+    SetSystemPrompt {
+        conversation_id: Uuid,
+        prompt: Option<String>,
     },
     ChatResponse {
         message: ChatMessage,
+        /// Tokens and estimated cost of this reply; absent in echo mode.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        usage: Option<UsageReport>,
+        /// What a reasoning model thought on the way to this reply.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reasoning: Option<String>,
     },
     StreamChunk {
         request_id: Uuid,
@@ -32,6 +93,27 @@ pub enum IpcPayload {
         done: bool,
     },
 
+    // -- Voice --
+    /// Begin a speech-to-text session.
+    VoiceStart {
+        session_id: Uuid,
+    },
+    /// A chunk of 16 kHz mono signed 16-bit little-endian PCM, base64-encoded.
+    VoiceAudio {
+        session_id: Uuid,
+        pcm: String,
+    },
+    /// End of audio; the agent answers with a final `Transcript`.
+    VoiceStop {
+        session_id: Uuid,
+    },
+    /// Partial (`is_final: false`) or final transcription of a voice session.
+    Transcript {
+        session_id: Uuid,
+        text: String,
+        is_final: bool,
+    },
+
     // -- Tool confirmation --
     ConfirmRequest {
         action_id: Uuid,
@@ -39,11 +121,22 @@ pub enum IpcPayload {
         description: String,
         command: String,
         trust_level: TrustLevel,
+        /// Structured risk assessment; absent from older agents.
+        #[serde(default)]
+        risk: Option<RiskSummary>,
+        /// The user may approve this tool for the rest of the
+        /// conversation, see `ConfirmResponse::remember`.
+        #[serde(default)]
+        can_remember: bool,
     },
     ConfirmResponse {
         action_id: Uuid,
         approved: bool,
         reason: Option<String>,
+        /// Run further calls of the tool in the same conversation without
+        /// asking; only honored when the request offered it.
+        #[serde(default)]
+        remember: bool,
     },
 
     // -- Client registration --
@@ -62,11 +155,196 @@ pub enum IpcPayload {
         success: bool,
         message: String,
     },
+    /// Switch the active provider to a different model for this session
+    /// (not persisted). Answered with `ConfigReloaded`.
+    SetModel {
+        model: String,
+    },
+    /// Ask for the models the active provider offers; answered with
+    /// `Models` or an `Error`.
+    ListModels,
+    /// Answer to `ListModels`.
+    Models {
+        /// Provider name, e.g. `ollama`.
+        provider: String,
+        /// Model requests go to now.
+        current: String,
+        models: Vec<ModelInfo>,
+    },
+    /// Generic acknowledgement for commands without a dedicated response.
+    CommandResult {
+        success: bool,
+        message: String,
+    },
+    /// Ask for one config section (`agent`, `ui`, ...) as written in the
+    /// config file; answered with `ConfigSection` or an `Error`.
+    GetConfig {
+        section: String,
+    },
+    /// Answer to `GetConfig`.
+    ConfigSection {
+        section: String,
+        value: serde_json::Value,
+    },
+    /// Replace one config section. The agent validates the whole config,
+    /// writes it and applies it live; answered with `ConfigReloaded`.
+    SetConfig {
+        section: String,
+        value: serde_json::Value,
+    },
+    /// Ask for the agent's version and LLM backend; answered with
+    /// `AgentAbout`.
+    AboutRequest,
+    /// Answer to `AboutRequest`. `provider` and `model` are `None` when no
+    /// provider is configured (echo mode).
+    AgentAbout {
+        version: String,
+        provider: Option<String>,
+        model: Option<String>,
+    },
+    /// Restart the agent process. Answered with `CommandResult` just before
+    /// the connection drops.
+    RestartAgent,
+    /// Ask for log lines numbered above `after` (0 for everything kept);
+    /// answered with `LogLines`.
+    LogsRequest {
+        after: u64,
+    },
+    /// Recent agent log lines, oldest first.
+    LogLines {
+        lines: Vec<LogLine>,
+    },
 
     // -- System --
     SystemInfo {
         info: serde_json::Value,
     },
+    /// Network, volume, battery and keyboard layout, pushed to dock clients
+    /// on registration and whenever something changes.
+    SystemStatus {
+        status: SystemStatus,
+    },
+    /// Dock quick controls. The agent applies the change and broadcasts the
+    /// resulting `SystemStatus`, or answers with an `Error`.
+    SetVolume {
+        percent: u8,
+    },
+    SetMuted {
+        muted: bool,
+    },
+    SetBrightness {
+        percent: u8,
+    },
+
+    // -- Agent activity --
+    /// LLM backend health, pushed to dock clients on registration and
+    /// whenever it changes.
+    AgentHealth {
+        health: AgentHealth,
+    },
+    /// The agent started handling a tool call; this includes waiting for
+    /// confirmation.
+    ToolStarted {
+        call_id: Uuid,
+        tool: String,
+        /// Devices the tool uses, for the dock's privacy indicators.
+        #[serde(default)]
+        sensors: Vec<Sensor>,
+    },
+    /// A tool call finished, was rejected, or failed.
+    ToolFinished {
+        call_id: Uuid,
+        tool: String,
+        success: bool,
+    },
+    /// Output of a tool call still running, such as the lines a build has
+    /// printed so far, for the chat window showing the conversation.
+    ToolProgress {
+        conversation_id: Uuid,
+        call_id: Uuid,
+        tool: String,
+        /// Output since the previous progress message, secrets masked.
+        output: String,
+    },
+    /// What the agent is about to do before its next tool calls, for the
+    /// chat window showing the conversation. Sent with `[agent] show_plans`.
+    AgentPlan {
+        conversation_id: Uuid,
+        /// A [`MessageContent::Plan`](crate::MessageContent::Plan) message.
+        message: ChatMessage,
+    },
+    /// Pause or resume tool execution. While paused every tool call is
+    /// refused and pending confirmations are rejected. Answered with
+    /// `CommandResult`.
+    SetPaused {
+        paused: bool,
+    },
+    /// Whether tool execution is paused, pushed to dock clients on
+    /// registration and whenever it changes.
+    AgentPaused {
+        paused: bool,
+    },
+
+    // -- Notifications --
+    /// Recent agent notifications, newest first. Pushed to dock clients on
+    /// registration and whenever the list changes.
+    Notifications {
+        notifications: Vec<AgentNotification>,
+    },
+    /// The notification panel was opened; mark everything as read.
+    MarkNotificationsRead,
+    /// Drop all notifications.
+    ClearNotifications,
+
+    // -- Audit --
+    /// Ask for audit log entries in `range` matching `filter`, newest first,
+    /// at most `limit`; answered with `AuditEntries` or an `Error`.
+    AuditQuery {
+        #[serde(default)]
+        filter: AuditFilter,
+        #[serde(default)]
+        range: AuditRange,
+        limit: usize,
+    },
+    AuditEntries {
+        entries: Vec<AuditEntry>,
+        /// More entries matched than `limit`.
+        truncated: bool,
+    },
+
+    // -- Calendar --
+    /// Ask for the events on `date`; answered with `CalendarEvents`.
+    CalendarRequest {
+        date: NaiveDate,
+    },
+    /// Events touching `date` from the local calendar files.
+    CalendarEvents {
+        date: NaiveDate,
+        events: Vec<CalendarEvent>,
+    },
+
+    // -- MCP server --
+    /// One MCP JSON-RPC message between an editor or assistant that uses
+    /// AIOS's tools (`aios-cli mcp`) and the agent's MCP server, in either
+    /// direction: requests, their responses and notifications.
+    McpMessage {
+        message: serde_json::Value,
+    },
+
+    // -- Session --
+    /// Ask `aios-session` for its components; answered with
+    /// `SessionStatus`.
+    SessionStatusRequest,
+    SessionStatus {
+        components: Vec<ComponentStatus>,
+    },
+    /// Start or stop the component `name` of `aios-session`; answered with
+    /// `CommandResult`. A stopped component stays stopped until started.
+    SetComponentRunning {
+        name: String,
+        running: bool,
+    },
+
     Error {
         message: String,
         code: Option<String>,
@@ -83,6 +361,10 @@ pub enum ClientType {
     Dock,
     Confirm,
     Settings,
+    /// `aios-cli`, for prompts and audit queries from a terminal.
+    Cli,
+    /// `aios-session`, checking that the agent is up.
+    Session,
 }
 
 /// Length-prefixed JSON codec for IPC messages.
@@ -95,7 +377,7 @@ pub struct LengthPrefixedCodec;
 
 impl LengthPrefixedCodec {
     /// Maximum allowed message size (16 MiB).
-    const MAX_MESSAGE_SIZE: u32 = 16 * 1024 * 1024;
+    pub const MAX_MESSAGE_SIZE: u32 = 16 * 1024 * 1024;
 
     /// Encode an [`IpcMessage`] into a length-prefixed byte buffer.
     ///
@@ -1,12 +1,45 @@
 pub mod audit;
+pub mod calendar;
+pub mod contacts;
+pub mod ddcutil;
+pub mod desktop;
 pub mod error;
+pub mod geoclue;
+pub mod gpu;
+pub mod hardware;
+pub mod i18n;
 pub mod ipc;
+pub mod locale;
+pub mod nmcli;
+pub mod telemetry;
 pub mod types;
+pub mod wpctl;
 
-pub use audit::{AuditEntry, AuditResult};
+pub use audit::{AuditEntry, AuditFilter, AuditKind, AuditOutcome, AuditRange, AuditResult};
 pub use error::AiosError;
 pub use ipc::{ClientType, IpcClient, IpcConnection, IpcMessage, IpcPayload, IpcServer};
-pub use types::config::{AgentConfig, AiosConfig, ProviderConfig, ProviderType};
-pub use types::message::{ChatMessage, MessageContent, Role};
-pub use types::tool::{ToolCall, ToolDefinition, ToolResult, TrustRequirement};
+pub use types::calendar::CalendarEvent;
+pub use types::contact::Contact;
+pub use types::config::{
+    AgentConfig, AiosConfig, BrowserConfig, BrowserProfile, ColorScheme, ContactsConfig,
+    ConversationConfig, Density, DockPosition, GatewayConfig, HomeAssistantConfig, InputConfig,
+    LayoutSwitch, LidAction, LocationConfig, MacroConfig, McpServerConfig, McpTransport,
+    PowerConfig, PromptConfig, ProviderConfig, ProviderType, ReasoningEffort, SandboxConfig,
+    SandboxOverride, SandboxProfile, SessionConfig, TelemetryConfig, ToolPolicy,
+    ToolSelectionConfig, UiConfig, VoiceConfig, config_path,
+};
+pub use types::message::{Attachment, AttachmentContent, ChatMessage, MessageContent, Role};
+pub use types::model::ModelInfo;
+pub use types::notification::{AgentNotification, NotificationKind};
+pub use types::risk::RiskSummary;
+pub use types::session::{ComponentState, ComponentStatus};
+pub use types::system::{
+    AgentHealth, BatteryStatus, ChargeState, DiskInfo, GpuInfo, HostInfo, LoadInfo, LogLine,
+    MemoryInfo, NetworkStatus, ProcessInfo, SystemReport, SystemSection, SystemStatus,
+    ThermalZone, VolumeStatus,
+};
+pub use types::tool::{
+    Sensor, ToolCall, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement,
+};
 pub use types::trust::TrustLevel;
+pub use types::usage::{TokenUsage, UsageReport};
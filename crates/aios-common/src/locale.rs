@@ -0,0 +1,67 @@
+//! The user's locale and timezone, for the agent's prompt and the
+//! `datetime` tool.
+//!
+//! chrono knows the local UTC offset but not the zone's name, which is
+//! read from `TZ`, `/etc/timezone` or the `/etc/localtime` symlink like
+//! the C library does.
+
+use std::path::Path;
+
+/// The locale of dates and numbers, e.g. `ru_RU.UTF-8`, from the POSIX
+/// variables; `None` for the `C` locale.
+pub fn locale() -> Option<String> {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .filter(|value| value != "C" && value != "POSIX")
+}
+
+/// Name of the local timezone, e.g. `Europe/Berlin`.
+pub fn timezone() -> Option<String> {
+    if let Ok(tz) = std::env::var("TZ") {
+        // `:Europe/Berlin` names a zone file, like `Europe/Berlin`.
+        let tz = tz.trim_start_matches(':').trim();
+        if !tz.is_empty() {
+            return Some(zone_from_path(tz).unwrap_or(tz).to_owned());
+        }
+    }
+    if let Ok(content) = std::fs::read_to_string("/etc/timezone") {
+        let name = content.trim();
+        if !name.is_empty() {
+            return Some(name.to_owned());
+        }
+    }
+    let target = std::fs::read_link("/etc/localtime").ok()?;
+    zone_from_path(target.to_str()?).map(str::to_owned)
+}
+
+/// `Europe/Berlin` from `/usr/share/zoneinfo/Europe/Berlin`.
+fn zone_from_path(path: &str) -> Option<&str> {
+    let (_, zone) = path.split_once("zoneinfo/")?;
+    // Some distributions link into `zoneinfo/posix/` or `zoneinfo/right/`.
+    let zone = zone
+        .strip_prefix("posix/")
+        .or_else(|| zone.strip_prefix("right/"))
+        .unwrap_or(zone);
+    (!zone.is_empty() && Path::new(zone).is_relative()).then_some(zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zone_names_from_zoneinfo_paths() {
+        assert_eq!(
+            zone_from_path("/usr/share/zoneinfo/Europe/Berlin"),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            zone_from_path("../usr/share/zoneinfo/posix/America/New_York"),
+            Some("America/New_York")
+        );
+        assert_eq!(zone_from_path("/usr/share/zoneinfo/UTC"), Some("UTC"));
+        assert_eq!(zone_from_path("/etc/localtime"), None);
+    }
+}
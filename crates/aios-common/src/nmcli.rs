@@ -0,0 +1,452 @@
+//! NetworkManager through `nmcli`, shared by the Wi-Fi tools and the
+//! settings Network tab.
+//!
+//! Listings use terse output (`nmcli -t`), parsed by [`split_terse`].
+//! Commands run as async processes with a timeout and are killed when the
+//! future is dropped.
+
+use std::fmt;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::process::Command;
+
+use crate::types::tool::ToolErrorKind;
+
+/// Time a command gets unless it needs longer.
+const CMD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Joining a network waits for authentication and DHCP.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// A network from a Wi-Fi scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    /// Signal strength in percent.
+    pub signal: u8,
+    /// Security as nmcli prints it, e.g. `WPA2` or `WPA2 802.1X`; `--` or
+    /// empty for open networks.
+    pub security: String,
+    /// This machine is connected to it.
+    pub connected: bool,
+}
+
+impl WifiNetwork {
+    /// The network asks for a password or credentials.
+    pub fn is_secured(&self) -> bool {
+        !self.security.is_empty() && self.security != "--"
+    }
+
+    /// The network uses 802.1X (WPA-Enterprise) and needs an identity.
+    pub fn is_enterprise(&self) -> bool {
+        self.security.contains("802.1X")
+    }
+}
+
+/// A saved connection profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SavedConnection {
+    pub name: String,
+    pub uuid: String,
+    /// nmcli connection type, e.g. `802-11-wireless`.
+    pub kind: String,
+    pub active: bool,
+}
+
+/// State of a network device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeviceStatus {
+    pub device: String,
+    /// Device type, e.g. `wifi` or `ethernet`.
+    pub kind: String,
+    /// e.g. `connected`, `disconnected` or `unavailable`.
+    pub state: String,
+    /// Name of the active connection profile, if any.
+    pub connection: Option<String>,
+}
+
+/// Whether listing networks waits for a fresh scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rescan {
+    /// Only networks NetworkManager already knows about.
+    No,
+    /// Rescan if the last scan is stale.
+    Auto,
+    /// Always rescan.
+    Yes,
+}
+
+impl Rescan {
+    fn as_arg(self) -> &'static str {
+        match self {
+            Self::No => "no",
+            Self::Auto => "auto",
+            Self::Yes => "yes",
+        }
+    }
+}
+
+/// What to join and how.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectRequest {
+    pub ssid: String,
+    /// Pre-shared key, or the 802.1X password; empty for open networks.
+    pub password: String,
+    /// The network does not broadcast its SSID.
+    pub hidden: bool,
+    /// Credentials of a WPA-Enterprise network.
+    pub enterprise: Option<Enterprise>,
+}
+
+/// 802.1X settings for [`ConnectRequest`].
+#[derive(Debug, Clone)]
+pub struct Enterprise {
+    pub identity: String,
+    /// EAP method, e.g. `peap` or `ttls`.
+    pub eap: String,
+    /// Inner authentication, e.g. `mschapv2`.
+    pub phase2: String,
+}
+
+impl Enterprise {
+    /// PEAP with MSCHAPv2, what most campus and office networks use.
+    pub fn peap(identity: impl Into<String>) -> Self {
+        Self {
+            identity: identity.into(),
+            eap: "peap".to_owned(),
+            phase2: "mschapv2".to_owned(),
+        }
+    }
+}
+
+/// Why an nmcli command failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NmcliError {
+    /// nmcli is not installed.
+    NotInstalled,
+    /// nmcli ran and reported an error.
+    Failed(String),
+    TimedOut,
+}
+
+impl fmt::Display for NmcliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInstalled => f.write_str("nmcli is not installed"),
+            Self::Failed(message) => f.write_str(message),
+            Self::TimedOut => f.write_str("nmcli timed out"),
+        }
+    }
+}
+
+impl std::error::Error for NmcliError {}
+
+impl NmcliError {
+    /// How a tool reports this failure.
+    pub fn tool_kind(&self) -> ToolErrorKind {
+        match self {
+            Self::NotInstalled => ToolErrorKind::Unavailable,
+            Self::Failed(_) => ToolErrorKind::Failed,
+            Self::TimedOut => ToolErrorKind::Timeout,
+        }
+    }
+}
+
+/// Why joining a network failed, read from nmcli's message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectFailure {
+    /// The password is wrong, malformed, or was needed and not given.
+    WrongPassword,
+    /// No access point with the SSID is in range.
+    NotFound,
+    /// The network was joined but handed out no IP address.
+    NoAddress,
+    /// Anything else; nmcli's message tells.
+    Other,
+}
+
+impl ConnectFailure {
+    /// Classify the message of a failed `connect` or `connect_saved`.
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        if message.contains("secrets were required")
+            || message.contains("psk: property is invalid")
+            || message.contains("wep-key0: property is invalid")
+            || message.contains("802-1x.password")
+        {
+            Self::WrongPassword
+        } else if message.contains("no network with ssid")
+            || message.contains("network could not be found")
+        {
+            Self::NotFound
+        } else if message.contains("ip configuration could not be reserved") {
+            Self::NoAddress
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Visible Wi-Fi networks, strongest first, one entry per SSID.
+pub async fn wifi_list(rescan: Rescan) -> Result<Vec<WifiNetwork>, NmcliError> {
+    let fields = "SSID,SIGNAL,SECURITY,IN-USE";
+    let args = ["-t", "-f", fields, "dev", "wifi", "list", "--rescan", rescan.as_arg()];
+    run(&args, CMD_TIMEOUT).await.map(|out| parse_wifi_list(&out))
+}
+
+/// Join a network; returns nmcli's message. WPA-Enterprise networks get a
+/// connection profile of their own, since `nmcli dev wifi connect` cannot
+/// take 802.1X settings.
+pub async fn connect(request: &ConnectRequest) -> Result<String, NmcliError> {
+    let ssid = request.ssid.as_str();
+    let hidden = if request.hidden { "yes" } else { "no" };
+    let Some(enterprise) = &request.enterprise else {
+        let mut args = vec!["dev", "wifi", "connect", ssid];
+        if !request.password.is_empty() {
+            args.extend(["password", request.password.as_str()]);
+        }
+        if request.hidden {
+            args.extend(["hidden", "yes"]);
+        }
+        return run(&args, CONNECT_TIMEOUT).await;
+    };
+
+    let add = [
+        "connection", "add", "type", "wifi", "con-name", ssid, "ssid", ssid,
+        "802-11-wireless.hidden", hidden,
+        "wifi-sec.key-mgmt", "wpa-eap",
+        "802-1x.eap", enterprise.eap.as_str(),
+        "802-1x.phase2-auth", enterprise.phase2.as_str(),
+        "802-1x.identity", enterprise.identity.as_str(),
+        "802-1x.password", request.password.as_str(),
+    ];
+    run(&add, CMD_TIMEOUT).await?;
+    run(&["connection", "up", "id", ssid], CONNECT_TIMEOUT).await
+}
+
+/// Bring up a saved profile by name or UUID.
+pub async fn connect_saved(profile: &str) -> Result<String, NmcliError> {
+    run(&["connection", "up", profile], CONNECT_TIMEOUT).await
+}
+
+/// The saved Wi-Fi profile for `ssid`: one named after it, or else one whose
+/// SSID setting matches, since a profile can be renamed.
+pub async fn saved_wifi_profile(ssid: &str) -> Result<Option<SavedConnection>, NmcliError> {
+    let wifi: Vec<SavedConnection> = saved_connections()
+        .await?
+        .into_iter()
+        .filter(|connection| connection.kind == "802-11-wireless")
+        .collect();
+    if let Some(connection) = wifi.iter().find(|connection| connection.name == ssid) {
+        return Ok(Some(connection.clone()));
+    }
+    for connection in wifi {
+        let args = ["-g", "802-11-wireless.ssid", "connection", "show", "uuid", &connection.uuid];
+        // `-g` escapes like terse output.
+        let output = run(&args, CMD_TIMEOUT).await?;
+        if split_terse(&output).first().is_some_and(|value| value == ssid) {
+            return Ok(Some(connection));
+        }
+    }
+    Ok(None)
+}
+
+/// Disconnect `device`, e.g. `wlan0`.
+pub async fn disconnect(device: &str) -> Result<String, NmcliError> {
+    run(&["dev", "disconnect", device], CMD_TIMEOUT).await
+}
+
+/// Saved connection profiles.
+pub async fn saved_connections() -> Result<Vec<SavedConnection>, NmcliError> {
+    let args = ["-t", "-f", "NAME,UUID,TYPE,ACTIVE", "connection", "show"];
+    run(&args, CMD_TIMEOUT).await.map(|out| parse_connections(&out))
+}
+
+/// Delete a saved profile, forgetting its password.
+pub async fn forget(uuid: &str) -> Result<String, NmcliError> {
+    run(&["connection", "delete", "uuid", uuid], CMD_TIMEOUT).await
+}
+
+/// State of every network device.
+pub async fn device_status() -> Result<Vec<DeviceStatus>, NmcliError> {
+    let args = ["-t", "-f", "DEVICE,TYPE,STATE,CONNECTION", "dev", "status"];
+    run(&args, CMD_TIMEOUT).await.map(|out| parse_device_status(&out))
+}
+
+/// The first Wi-Fi device, for disconnecting.
+pub async fn wifi_device() -> Option<String> {
+    device_status()
+        .await
+        .ok()?
+        .into_iter()
+        .find(|device| device.kind == "wifi")
+        .map(|device| device.device)
+}
+
+/// Run nmcli; stdout on success, otherwise stderr (or stdout) as the error.
+async fn run(args: &[&str], timeout: Duration) -> Result<String, NmcliError> {
+    let output = Command::new("nmcli")
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(timeout, output).await {
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+            if output.status.success() {
+                return Ok(stdout);
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            Err(NmcliError::Failed(if stderr.is_empty() { stdout } else { stderr }))
+        }
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => Err(NmcliError::NotInstalled),
+        Ok(Err(e)) => Err(NmcliError::Failed(format!("failed to run nmcli: {e}"))),
+        Err(_) => Err(NmcliError::TimedOut),
+    }
+}
+
+/// Split a line of `nmcli -t` output into fields; `:` and `\` inside a
+/// field are escaped with a backslash.
+pub fn split_terse(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => field.extend(chars.next()),
+            ':' => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Parse `SSID,SIGNAL,SECURITY,IN-USE` lines. Networks without an SSID
+/// (hidden ones) are skipped, and each SSID is kept once, strongest first.
+pub fn parse_wifi_list(output: &str) -> Vec<WifiNetwork> {
+    let mut networks: Vec<WifiNetwork> = output
+        .lines()
+        .filter_map(|line| match split_terse(line).as_slice() {
+            [ssid, signal, security, in_use, ..] if !ssid.trim().is_empty() => {
+                Some(WifiNetwork {
+                    ssid: ssid.trim().to_owned(),
+                    signal: signal.trim().parse().unwrap_or(0),
+                    security: security.trim().to_owned(),
+                    connected: in_use.trim() == "*",
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    // The connected entry wins among equally strong duplicates.
+    networks.sort_by(|a, b| b.signal.cmp(&a.signal).then(b.connected.cmp(&a.connected)));
+    let mut seen = std::collections::HashSet::new();
+    networks.retain(|network| seen.insert(network.ssid.clone()));
+    networks
+}
+
+/// Parse `NAME,UUID,TYPE,ACTIVE` lines.
+pub fn parse_connections(output: &str) -> Vec<SavedConnection> {
+    output
+        .lines()
+        .filter_map(|line| match split_terse(line).as_slice() {
+            [name, uuid, kind, active] => Some(SavedConnection {
+                name: name.clone(),
+                uuid: uuid.clone(),
+                kind: kind.clone(),
+                active: active == "yes",
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse `DEVICE,TYPE,STATE,CONNECTION` lines, leaving out loopback.
+pub fn parse_device_status(output: &str) -> Vec<DeviceStatus> {
+    output
+        .lines()
+        .filter_map(|line| match split_terse(line).as_slice() {
+            [device, kind, state, connection] if kind != "loopback" => Some(DeviceStatus {
+                device: device.clone(),
+                kind: kind.clone(),
+                state: state.clone(),
+                connection: (!connection.is_empty() && connection != "--")
+                    .then(|| connection.clone()),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terse_fields_unescape_colons_and_backslashes() {
+        assert_eq!(
+            split_terse(r"Cafe\: Free:70:WPA2:"),
+            vec!["Cafe: Free", "70", "WPA2", ""]
+        );
+        assert_eq!(split_terse(r"a\\b:c"), vec![r"a\b", "c"]);
+    }
+
+    #[test]
+    fn wifi_list_dedups_by_ssid_and_skips_hidden() {
+        let output = "Home:40:WPA2:\n\
+                      :90:WPA2:\n\
+                      Home:80:WPA2:*\n\
+                      Campus:60:WPA2 802.1X:\n\
+                      Open:20:--:";
+        let networks = parse_wifi_list(output);
+        let ssids: Vec<_> = networks.iter().map(|n| n.ssid.as_str()).collect();
+        assert_eq!(ssids, ["Home", "Campus", "Open"]);
+        assert!(networks[0].connected);
+        assert_eq!(networks[0].signal, 80);
+        assert!(networks[1].is_enterprise());
+        assert!(!networks[2].is_secured());
+    }
+
+    #[test]
+    fn classifies_connect_failures() {
+        let failure = |message| ConnectFailure::from_message(message);
+        assert_eq!(
+            failure(
+                "Error: Connection activation failed: (7) Secrets were required, but not \
+                 provided."
+            ),
+            ConnectFailure::WrongPassword
+        );
+        assert_eq!(
+            failure("Error: No network with SSID 'Cafe' found."),
+            ConnectFailure::NotFound
+        );
+        assert_eq!(
+            failure(
+                "Error: Connection activation failed: IP configuration could not be reserved \
+                 (no available address, timeout, etc.)."
+            ),
+            ConnectFailure::NoAddress
+        );
+        assert_eq!(failure("Error: Device not managed."), ConnectFailure::Other);
+    }
+
+    #[test]
+    fn connections_and_devices() {
+        let saved =
+            parse_connections("Home:0b1c-4d:802-11-wireless:yes\nwired:77aa:802-3-ethernet:no");
+        assert_eq!(saved.len(), 2);
+        assert!(saved[0].active);
+        assert_eq!(saved[1].kind, "802-3-ethernet");
+
+        let devices = parse_device_status(
+            "wlan0:wifi:connected:Home\neth0:ethernet:unavailable:--\nlo:loopback:unmanaged:",
+        );
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].connection.as_deref(), Some("Home"));
+        assert_eq!(devices[1].connection, None);
+    }
+}
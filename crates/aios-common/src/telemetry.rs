@@ -0,0 +1,35 @@
+//! Request ids that follow a request across processes.
+//!
+//! The id of the IPC message that starts a request, such as a
+//! `ChatRequest`, is its request id. Messages sent while handling it (the
+//! reply, confirmation requests, tool activity) reuse that id, and each
+//! process logs its work inside a `request` span carrying it, so the logs
+//! of chat, agent and confirm can be joined on `request_id`.
+
+use std::future::Future;
+
+use uuid::Uuid;
+
+tokio::task_local! {
+    static REQUEST_ID: Uuid;
+}
+
+/// Run `f` as part of the request `request_id`.
+pub async fn scope<F: Future>(request_id: Uuid, f: F) -> F::Output {
+    REQUEST_ID.scope(request_id, f).await
+}
+
+/// The request the current task works on, if any.
+pub fn current() -> Option<Uuid> {
+    REQUEST_ID.try_with(|id| *id).ok()
+}
+
+/// Id for a message sent now: the current request's, or a fresh one.
+pub fn message_id() -> Uuid {
+    current().unwrap_or_else(Uuid::new_v4)
+}
+
+/// Span for the work on request `request_id`.
+pub fn request_span(request_id: Uuid) -> tracing::Span {
+    tracing::info_span!("request", %request_id)
+}
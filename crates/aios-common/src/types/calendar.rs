@@ -0,0 +1,30 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// An event from the user's calendar, in local time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    pub title: String,
+    pub start: NaiveDateTime,
+    /// Exclusive end; `None` when the event has no duration.
+    #[serde(default)]
+    pub end: Option<NaiveDateTime>,
+    /// Date-only event; `start` and `end` are at midnight.
+    #[serde(default)]
+    pub all_day: bool,
+    #[serde(default)]
+    pub location: Option<String>,
+}
+
+impl CalendarEvent {
+    /// Whether any part of the event falls on `date`.
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        let start = self.start.date();
+        let last = match self.end {
+            // An event ending at midnight does not reach into that day.
+            Some(end) if end > self.start => (end - chrono::Duration::seconds(1)).date(),
+            _ => start,
+        };
+        start <= date && date <= last
+    }
+}
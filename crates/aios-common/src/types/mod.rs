@@ -1,4 +1,12 @@
+pub mod calendar;
 pub mod config;
+pub mod contact;
 pub mod message;
+pub mod model;
+pub mod notification;
+pub mod risk;
+pub mod session;
+pub mod system;
 pub mod tool;
 pub mod trust;
+pub mod usage;
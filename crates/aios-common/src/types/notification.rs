@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Something the agent wants the user to know about after the fact, kept in
+/// the dock's notification center.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentNotification {
+    pub id: Uuid,
+    pub kind: NotificationKind,
+    /// What the notification is about: a task name, a file name, a tool.
+    pub title: String,
+    /// Result or details; may be empty.
+    #[serde(default)]
+    pub body: String,
+    pub timestamp: DateTime<Utc>,
+    /// Set once the notification panel has been opened.
+    #[serde(default)]
+    pub read: bool,
+}
+
+impl AgentNotification {
+    /// A new unread notification stamped with the current time.
+    pub fn new(kind: NotificationKind, title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            title: title.into(),
+            body: body.into(),
+            timestamp: Utc::now(),
+            read: false,
+        }
+    }
+}
+
+/// Source of an [`AgentNotification`]; clients use it for the heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A scheduled task finished.
+    TaskResult,
+    /// A download finished.
+    Download,
+    /// A tool needed confirmation but nobody answered.
+    ConfirmMissed,
+    /// Anything else.
+    #[serde(other)]
+    Info,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Structured, human-readable assessment of what a tool call will do.
+///
+/// Generated by the agent from the tool definition and call arguments and
+/// attached to `ConfirmRequest` so the dialog can show a plain-language
+/// summary instead of raw JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskSummary {
+    /// One-line description, e.g. "Deletes 3 files under ~/Documents, not reversible".
+    pub summary: String,
+    /// Filesystem paths the action reads from or writes to.
+    #[serde(default)]
+    pub affected_paths: Vec<String>,
+    /// Whether the effect can be undone without external backups.
+    pub reversible: bool,
+    /// Whether the action may reach the network.
+    pub network_access: bool,
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One component `aios-session` runs, as its `SessionStatus` reports it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentStatus {
+    /// Program name, e.g. `aios-agent`.
+    pub name: String,
+    pub state: ComponentState,
+    /// Process id while it runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// Times it was started again after exiting on its own.
+    pub restarts: u32,
+    /// How it last exited, e.g. `exit status: 101`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_exit: Option<String>,
+    /// When it entered `state`.
+    pub since: DateTime<Utc>,
+}
+
+/// What a supervised component is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    /// Started, not yet ready; only the agent has a readiness check.
+    Starting,
+    Running,
+    /// Exited on its own; started again after a backoff.
+    Restarting,
+    /// Stopped on request, or not started yet.
+    Stopped,
+}
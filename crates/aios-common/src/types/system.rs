@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+
+/// Snapshot of the desktop state shown in the dock tray, gathered by the
+/// agent and pushed to dock clients whenever it changes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemStatus {
+    pub network: NetworkStatus,
+    /// Default audio output; `None` if PipeWire is not reachable.
+    #[serde(default)]
+    pub volume: Option<VolumeStatus>,
+    /// `None` on machines without a battery.
+    #[serde(default)]
+    pub battery: Option<BatteryStatus>,
+    /// Display backlight in percent; `None` without a controllable
+    /// backlight (desktops).
+    #[serde(default)]
+    pub brightness: Option<u8>,
+    /// The busiest GPU; only sampled while `[ui] gpu_meter` is on.
+    #[serde(default)]
+    pub gpu: Option<GpuInfo>,
+}
+
+/// Connectivity of the non-loopback interfaces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkStatus {
+    /// At least one interface is up.
+    pub connected: bool,
+    /// The interface that is up is a wireless one.
+    pub wireless: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeStatus {
+    /// Volume in percent; may exceed 100 when boosted.
+    pub percent: u8,
+    pub muted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatteryStatus {
+    /// Charge in percent.
+    pub percent: u8,
+    #[serde(default)]
+    pub state: ChargeState,
+    /// Estimated minutes until empty; only known while discharging.
+    #[serde(default)]
+    pub minutes_to_empty: Option<u32>,
+}
+
+impl BatteryStatus {
+    /// Running on battery with little charge left.
+    pub fn is_low(&self) -> bool {
+        self.state == ChargeState::Discharging && self.percent <= 20
+    }
+}
+
+/// Battery charging state as reported by the kernel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChargeState {
+    #[default]
+    Unknown,
+    Charging,
+    Discharging,
+    Full,
+    /// Plugged in but held below full (charge thresholds).
+    NotCharging,
+}
+
+impl ChargeState {
+    /// Map the sysfs `status` attribute of a power supply.
+    pub fn from_sysfs(status: &str) -> Self {
+        match status {
+            "Charging" => Self::Charging,
+            "Discharging" => Self::Discharging,
+            "Full" => Self::Full,
+            "Not charging" => Self::NotCharging,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Whether the agent can answer with an LLM, shown as a dot in the dock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum AgentHealth {
+    /// A provider is configured and its last request succeeded.
+    Ready { provider: String },
+    /// No provider (missing API key); replies are echoed back.
+    Echo,
+    /// The last LLM request failed.
+    ProviderError { provider: String, message: String },
+}
+
+/// A line of the agent's own log output, numbered so clients can ask for
+/// what they have not seen yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLine {
+    pub seq: u64,
+    pub text: String,
+}
+
+/// Document returned by the `system_info` tool. Sections that were not
+/// asked for, or could not be read, are left out of the JSON.
+///
+/// Sizes are bytes, durations seconds, temperatures degrees Celsius.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<HostInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<LoadInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryInfo>,
+    /// Mounted filesystems, pseudo filesystems left out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disks: Option<Vec<DiskInfo>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub battery: Option<BatteryStatus>,
+    /// Busiest processes by CPU, busiest first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processes: Option<Vec<ProcessInfo>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thermal: Option<Vec<ThermalZone>>,
+}
+
+/// Parts of a [`SystemReport`] the caller can ask for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemSection {
+    Host,
+    Load,
+    Memory,
+    Disks,
+    Battery,
+    Processes,
+    Thermal,
+}
+
+impl SystemSection {
+    pub const ALL: [Self; 7] = [
+        Self::Host,
+        Self::Load,
+        Self::Memory,
+        Self::Disks,
+        Self::Battery,
+        Self::Processes,
+        Self::Thermal,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Host => "host",
+            Self::Load => "load",
+            Self::Memory => "memory",
+            Self::Disks => "disks",
+            Self::Battery => "battery",
+            Self::Processes => "processes",
+            Self::Thermal => "thermal",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|section| section.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub hostname: String,
+    /// Kernel release, e.g. `6.8.0-45-generic`.
+    pub kernel: String,
+    /// `PRETTY_NAME` from os-release.
+    pub os: String,
+    pub cpu_model: String,
+    pub cpu_count: u32,
+    pub uptime_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadInfo {
+    /// Load averages over 1, 5 and 15 minutes.
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+    pub running: u32,
+    /// All processes and threads.
+    pub tasks: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    pub total: u64,
+    pub available: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+}
+
+impl MemoryInfo {
+    /// Share of RAM in use, in percent.
+    pub fn used_percent(&self) -> u8 {
+        if self.total == 0 {
+            return 0;
+        }
+        let used = self.total.saturating_sub(self.available);
+        (used * 100 / self.total) as u8
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub mount: String,
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    /// CPU usage averaged over the process lifetime, as `ps` reports it;
+    /// may exceed 100 on several cores.
+    pub cpu_percent: f32,
+    /// Resident memory.
+    pub memory: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThermalZone {
+    /// Sensor name, e.g. `x86_pkg_temp` or `acpitz`.
+    pub name: String,
+    pub celsius: f32,
+}
+
+/// Load of a graphics card, from `nvidia-smi` or the amdgpu sysfs
+/// attributes. Values the driver does not report are left out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GpuInfo {
+    /// Model name, e.g. `NVIDIA GeForce RTX 3060`.
+    pub name: String,
+    /// Time the GPU was busy recently, in percent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utilization: Option<u8>,
+    /// Video memory in use, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_used: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_total: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub celsius: Option<f32>,
+    /// Power draw in watts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watts: Option<f32>,
+}
+
+impl GpuInfo {
+    /// Share of video memory in use, in percent.
+    pub fn memory_percent(&self) -> Option<u8> {
+        let (used, total) = (self.memory_used?, self.memory_total?);
+        if total == 0 {
+            return None;
+        }
+        Some((used.min(total) * 100 / total) as u8)
+    }
+
+    /// The larger of [`Self::utilization`] and [`Self::memory_percent`],
+    /// for comparing GPUs and coloring a meter.
+    pub fn load(&self) -> u8 {
+        self.utilization
+            .unwrap_or(0)
+            .max(self.memory_percent().unwrap_or(0))
+    }
+}
@@ -1,6 +1,10 @@
+use std::borrow::Cow;
+use std::io;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::message::Attachment;
 use super::trust::TrustLevel;
 
 /// A request to invoke a specific tool with arguments.
@@ -18,11 +22,100 @@ pub struct ToolResult {
     pub call_id: Uuid,
     pub output: String,
     pub is_error: bool,
+    /// Why the call failed; `None` on success and from older agents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<ToolErrorKind>,
+    /// Images or files produced by the tool (screenshots, camera captures).
+    /// Shown inline in the chat; only `output` is passed to the LLM.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+}
+
+impl ToolResult {
+    /// `output` as shown to the LLM: a failure is prefixed with its kind,
+    /// e.g. `[error: not_found]`, so the model can tell a missing file from
+    /// a denied one and correct the call.
+    pub fn llm_output(&self) -> Cow<'_, str> {
+        match self.error_kind {
+            Some(kind) => Cow::Owned(format!("[error: {}] {}", kind.as_str(), self.output)),
+            None => Cow::Borrowed(&self.output),
+        }
+    }
 }
 
-/// Required confirmation level for tool execution.
+/// Cause of a failed tool call.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+pub enum ToolErrorKind {
+    /// Missing or malformed arguments; fix the call before retrying.
+    InvalidArguments,
+    /// No tool with the requested name.
+    UnknownTool,
+    /// The file, device, network or entity does not exist.
+    NotFound,
+    /// The target exists already.
+    AlreadyExists,
+    /// The OS or a remote service refused access.
+    PermissionDenied,
+    /// The operation or confirmation took too long.
+    Timeout,
+    /// The user rejected the call, disabled the tool or paused the agent;
+    /// do not retry.
+    Rejected,
+    /// Too many destructive actions in a short time.
+    RateLimited,
+    /// A program, device or service the tool needs is missing or not
+    /// configured.
+    Unavailable,
+    /// A network request failed.
+    Network,
+    /// Any other failure.
+    Failed,
+}
+
+impl ToolErrorKind {
+    /// The `snake_case` name, as serialized.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidArguments => "invalid_arguments",
+            Self::UnknownTool => "unknown_tool",
+            Self::NotFound => "not_found",
+            Self::AlreadyExists => "already_exists",
+            Self::PermissionDenied => "permission_denied",
+            Self::Timeout => "timeout",
+            Self::Rejected => "rejected",
+            Self::RateLimited => "rate_limited",
+            Self::Unavailable => "unavailable",
+            Self::Network => "network",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Classify an I/O error.
+    pub fn from_io(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Self::NotFound,
+            io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => {
+                Self::PermissionDenied
+            }
+            io::ErrorKind::AlreadyExists => Self::AlreadyExists,
+            io::ErrorKind::TimedOut => Self::Timeout,
+            io::ErrorKind::InvalidInput => Self::InvalidArguments,
+            io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::HostUnreachable
+            | io::ErrorKind::NetworkUnreachable => Self::Network,
+            _ => Self::Failed,
+        }
+    }
+}
+
+/// Required confirmation level for tool execution, ordered from least to
+/// most strict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TrustRequirement {
     /// Read-only operation, no confirmation needed.
     None,
@@ -32,6 +125,19 @@ pub enum TrustRequirement {
     DoubleConfirm,
 }
 
+/// A privacy-sensitive device a tool may use while it runs. The dock shows
+/// an indicator for each one in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sensor {
+    Camera,
+    Microphone,
+    /// Capturing the screen or a window.
+    Screen,
+    /// Looking up where the machine is.
+    Location,
+}
+
 /// Declares a tool that the agent can invoke.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolDefinition {
@@ -40,4 +146,8 @@ pub struct ToolDefinition {
     /// JSON Schema describing the tool's parameters.
     pub parameters: serde_json::Value,
     pub trust_requirement: TrustRequirement,
+    /// Deprecated former names the tool still answers to; filled in by the
+    /// registry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
 }
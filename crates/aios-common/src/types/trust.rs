@@ -16,3 +16,32 @@ pub enum TrustLevel {
     /// Data retrieved from RAG memory.
     Memory,
 }
+
+impl TrustLevel {
+    /// Whether data at this level may carry instructions the user never
+    /// gave, so it must be treated as data and its effects confirmed.
+    #[must_use]
+    pub fn is_untrusted(self) -> bool {
+        matches!(self, Self::WebContent | Self::Memory)
+    }
+
+    /// The less trusted of `self` and `other`. Memory ranks below system
+    /// data since it may have been recalled from a web page.
+    #[must_use]
+    pub fn least_trusted(self, other: Self) -> Self {
+        if other.rank() < self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            Self::WebContent => 0,
+            Self::Memory => 1,
+            Self::System => 2,
+            Self::User => 3,
+        }
+    }
+}
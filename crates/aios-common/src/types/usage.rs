@@ -0,0 +1,41 @@
+use std::ops::AddAssign;
+
+use serde::{Deserialize, Serialize};
+
+/// Token counts reported by an LLM provider.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Prompt tokens (history, system prompt, tool definitions).
+    pub input_tokens: u64,
+    /// Generated tokens.
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
+}
+
+/// Usage behind one agent reply, summed over every LLM call of the turn
+/// (tool round-trips included).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// Provider name, e.g. `claude`.
+    pub provider: String,
+    pub model: String,
+    pub tokens: TokenUsage,
+    /// Number of LLM requests made for the reply.
+    pub llm_calls: u32,
+    /// Estimated cost in USD. `None` for local providers and models without
+    /// a known price.
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+}
@@ -0,0 +1,309 @@
+//! PipeWire audio through `wpctl`, shared by the volume tool, the dock
+//! status and the settings Audio tab.
+//!
+//! Devices are addressed by their PipeWire object id or by the
+//! [`DEFAULT_SINK`] / [`DEFAULT_SOURCE`] aliases. Commands run with a
+//! timeout and are killed when the future is dropped.
+
+use std::fmt;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::process::Command;
+
+use crate::types::system::VolumeStatus;
+use crate::types::tool::ToolErrorKind;
+
+/// Time a command gets; wpctl answers at once unless PipeWire hangs.
+const CMD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default output device.
+pub const DEFAULT_SINK: &str = "@DEFAULT_AUDIO_SINK@";
+
+/// The default input device (microphone).
+pub const DEFAULT_SOURCE: &str = "@DEFAULT_AUDIO_SOURCE@";
+
+/// Audio sink or source parsed from `wpctl status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AudioDevice {
+    /// PipeWire object id.
+    pub id: u32,
+    pub name: String,
+    /// Volume in percent; may exceed 100.
+    pub volume: u8,
+    pub muted: bool,
+    pub default: bool,
+}
+
+/// A volume change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeChange {
+    /// Set to this percentage.
+    Set(u8),
+    /// Raise by this many points, up to 100%.
+    Raise(u8),
+    /// Lower by this many points.
+    Lower(u8),
+}
+
+impl VolumeChange {
+    /// Parse `50`, `50%`, `+10%` or `-10`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let value = value.strip_suffix('%').unwrap_or(value).trim_end();
+        let percent = |digits: &str| digits.trim().parse::<u8>().ok();
+        if let Some(step) = value.strip_prefix('+') {
+            percent(step).map(Self::Raise)
+        } else if let Some(step) = value.strip_prefix('-') {
+            percent(step).map(Self::Lower)
+        } else {
+            percent(value).map(Self::Set)
+        }
+    }
+}
+
+/// Why a wpctl command failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WpctlError {
+    /// wpctl is not installed.
+    NotInstalled,
+    /// wpctl ran and reported an error, e.g. an unknown object id.
+    Failed(String),
+    TimedOut,
+}
+
+impl fmt::Display for WpctlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInstalled => f.write_str("wpctl is not installed"),
+            Self::Failed(message) => f.write_str(message),
+            Self::TimedOut => f.write_str("wpctl timed out"),
+        }
+    }
+}
+
+impl std::error::Error for WpctlError {}
+
+impl WpctlError {
+    /// How a tool reports this failure.
+    pub fn tool_kind(&self) -> ToolErrorKind {
+        match self {
+            Self::NotInstalled => ToolErrorKind::Unavailable,
+            Self::Failed(_) => ToolErrorKind::Failed,
+            Self::TimedOut => ToolErrorKind::Timeout,
+        }
+    }
+}
+
+/// Sinks and sources, in that order.
+pub async fn devices() -> Result<(Vec<AudioDevice>, Vec<AudioDevice>), WpctlError> {
+    run(&["status"]).await.map(|out| parse_status(&out))
+}
+
+/// Volume of `target`, an object id or a default alias.
+pub async fn volume(target: &str) -> Result<VolumeStatus, WpctlError> {
+    let output = run(&["get-volume", target]).await?;
+    parse_volume(&output)
+        .ok_or_else(|| WpctlError::Failed(format!("unexpected wpctl output: {output}")))
+}
+
+/// Change the volume of `target`.
+pub async fn set_volume(target: &str, change: VolumeChange) -> Result<(), WpctlError> {
+    match change {
+        VolumeChange::Set(percent) => run(&["set-volume", target, &format!("{percent}%")]).await,
+        // A relative raise stops at 100% rather than boosting.
+        VolumeChange::Raise(step) => {
+            run(&["set-volume", "--limit", "1.0", target, &format!("{step}%+")]).await
+        }
+        VolumeChange::Lower(step) => run(&["set-volume", target, &format!("{step}%-")]).await,
+    }
+    .map(drop)
+}
+
+/// Mute or unmute `target`; `None` toggles.
+pub async fn set_muted(target: &str, muted: Option<bool>) -> Result<(), WpctlError> {
+    let state = match muted {
+        Some(true) => "1",
+        Some(false) => "0",
+        None => "toggle",
+    };
+    run(&["set-mute", target, state]).await.map(drop)
+}
+
+/// Run wpctl; stdout on success, otherwise stderr (or stdout) as the error.
+async fn run(args: &[&str]) -> Result<String, WpctlError> {
+    let output = Command::new("wpctl")
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(CMD_TIMEOUT, output).await {
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+            if output.status.success() {
+                return Ok(stdout);
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            Err(WpctlError::Failed(if stderr.is_empty() { stdout } else { stderr }))
+        }
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => Err(WpctlError::NotInstalled),
+        Ok(Err(e)) => Err(WpctlError::Failed(format!("failed to run wpctl: {e}"))),
+        Err(_) => Err(WpctlError::TimedOut),
+    }
+}
+
+/// Sinks and sources from the "Audio" section of `wpctl status`:
+///
+/// ```text
+/// Audio
+///  ├─ Sinks:
+///  │  *   46. Built-in Audio Analog Stereo        [vol: 0.40]
+///  │      52. HDMI Output                         [vol: 1.00 MUTED]
+/// ```
+pub fn parse_status(output: &str) -> (Vec<AudioDevice>, Vec<AudioDevice>) {
+    let mut sinks = Vec::new();
+    let mut sources = Vec::new();
+    let mut in_audio = false;
+    let mut section = None;
+
+    for line in output.lines() {
+        // Top-level headings ("Audio", "Video", "Settings") start at column 0.
+        if !line.starts_with(' ') && !line.trim().is_empty() {
+            in_audio = line.trim() == "Audio";
+            section = None;
+            continue;
+        }
+        if !in_audio {
+            continue;
+        }
+        let body = line.trim_start_matches(|c: char| c.is_whitespace() || "│├└─".contains(c));
+        if let Some(heading) = body.strip_suffix(':') {
+            section = match heading {
+                "Sinks" => Some(&mut sinks),
+                "Sources" => Some(&mut sources),
+                _ => None,
+            };
+            continue;
+        }
+        let Some(devices) = section.as_mut() else {
+            continue;
+        };
+        if let Some(device) = parse_device(body) {
+            devices.push(device);
+        }
+    }
+    (sinks, sources)
+}
+
+/// One device line: `*   46. Name   [vol: 0.40 MUTED]`.
+fn parse_device(line: &str) -> Option<AudioDevice> {
+    let (default, line) = match line.strip_prefix('*') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, line),
+    };
+    let (id, rest) = line.split_once('.')?;
+    let id = id.trim().parse().ok()?;
+    let (name, volume) = match rest.rsplit_once("[vol:") {
+        Some((name, volume)) => (name, volume.trim_end_matches(']')),
+        None => (rest, ""),
+    };
+    let mut volume_parts = volume.split_whitespace();
+    let volume = volume_parts
+        .next()
+        .and_then(|v| v.parse::<f32>().ok())
+        .map_or(0, |v| (v * 100.0).round().clamp(0.0, 255.0) as u8);
+    let muted = volume_parts.any(|part| part == "MUTED");
+
+    Some(AudioDevice {
+        id,
+        name: name.trim().to_owned(),
+        volume,
+        muted,
+        default,
+    })
+}
+
+/// Parse `wpctl get-volume`: `Volume: 0.45` / `Volume: 0.45 [MUTED]`.
+pub fn parse_volume(output: &str) -> Option<VolumeStatus> {
+    let rest = output.trim().strip_prefix("Volume:")?.trim();
+    let (level, flags) = rest.split_once(' ').unwrap_or((rest, ""));
+    let level: f32 = level.parse().ok()?;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 0..=255
+    let percent = (level * 100.0).round().clamp(0.0, 255.0) as u8;
+    Some(VolumeStatus {
+        percent,
+        muted: flags.contains("[MUTED]"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wpctl_volume() {
+        assert_eq!(
+            parse_volume("Volume: 0.45\n"),
+            Some(VolumeStatus {
+                percent: 45,
+                muted: false
+            })
+        );
+        assert_eq!(
+            parse_volume("Volume: 1.20 [MUTED]\n"),
+            Some(VolumeStatus {
+                percent: 120,
+                muted: true
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unexpected_wpctl_output() {
+        assert_eq!(parse_volume(""), None);
+        assert_eq!(parse_volume("Volume: loud"), None);
+    }
+
+    #[test]
+    fn status_lists_sinks_and_sources() {
+        let output = "PipeWire 'pipewire-0' [1.0.5]\n\
+                      \x20└─ Clients:\n\
+                      \x20      33. WirePlumber\n\
+                      \n\
+                      Audio\n\
+                      \x20├─ Devices:\n\
+                      \x20│      42. Built-in Audio\n\
+                      \x20│\n\
+                      \x20├─ Sinks:\n\
+                      \x20│  *   46. Built-in Audio Analog Stereo  [vol: 0.40]\n\
+                      \x20│      52. WH-1000XM4                    [vol: 1.00 MUTED]\n\
+                      \x20│\n\
+                      \x20├─ Sources:\n\
+                      \x20│  *   47. Built-in Audio Analog Stereo  [vol: 0.85]\n\
+                      \x20│\n\
+                      \x20└─ Streams:\n\
+                      \x20       60. Firefox\n\
+                      \n\
+                      Video\n\
+                      \x20├─ Sinks:\n\
+                      \x20│      70. Camera\n";
+        let (sinks, sources) = parse_status(output);
+        assert_eq!(sinks.len(), 2);
+        assert_eq!(sinks[0].id, 46);
+        assert!(sinks[0].default);
+        assert_eq!(sinks[1].name, "WH-1000XM4");
+        assert_eq!(sinks[1].volume, 100);
+        assert!(sinks[1].muted);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].volume, 85);
+    }
+
+    #[test]
+    fn parses_volume_changes() {
+        assert_eq!(VolumeChange::parse("50"), Some(VolumeChange::Set(50)));
+        assert_eq!(VolumeChange::parse("+10%"), Some(VolumeChange::Raise(10)));
+        assert_eq!(VolumeChange::parse(" -5 %"), Some(VolumeChange::Lower(5)));
+        assert_eq!(VolumeChange::parse("loud"), None);
+    }
+}
@@ -1,12 +1,28 @@
-use aios_common::TrustLevel;
-use iced::{Element, Task as IcedTask};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aios_common::ipc::IpcWriter;
+use aios_common::{RiskSummary, TrustLevel};
+use iced::keyboard::{self, key::Named};
+use iced::{event, Element, Subscription, Task as IcedTask};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use crate::ipc_client::{self, IpcEvent};
+use crate::notify::{self, NotificationAction};
 use crate::views::{confirm_dialog, critical_dialog, waiting_view};
 
+/// Keys are ignored this long after a request appears, so that typing
+/// meant for another window does not answer it.
+const KEY_DELAY: Duration = Duration::from_millis(800);
+
 /// Root application state for the AIOS Confirm dialog.
 pub struct AiosConfirm {
     state: ConfirmState,
+    /// Shared writer handle for sending `ConfirmResponse`s to the agent.
+    writer: Option<Arc<Mutex<IpcWriter>>>,
+    /// When the request on screen appeared.
+    shown_at: Option<Instant>,
 }
 
 /// The current state of the confirmation dialog.
@@ -21,6 +37,9 @@ enum ConfirmState {
         description: String,
         command: String,
         trust_level: TrustLevel,
+        risk: Option<RiskSummary>,
+        /// Offer to approve the tool for the rest of the conversation.
+        can_remember: bool,
     },
 
     /// Showing a critical (destructive) confirmation dialog that requires
@@ -31,6 +50,7 @@ enum ConfirmState {
         description: String,
         command: String,
         trust_level: TrustLevel,
+        risk: Option<RiskSummary>,
         confirm_input: String,
     },
 }
@@ -44,15 +64,32 @@ pub enum Message {
 
     // -- Dialog interactions --
     Approve,
+    /// Approve, and run the tool without asking for the rest of the
+    /// conversation.
+    ApproveForConversation,
     Reject,
     ConfirmInputChanged(String),
+    /// A key no widget handled: Enter approves, Shift+Enter approves for
+    /// the conversation and Escape rejects.
+    KeyPressed(keyboard::Key, keyboard::Modifiers),
+
+    // -- IPC --
+    /// An IPC lifecycle event from the background worker.
+    Ipc(IpcEvent),
+    /// Async `ConfirmResponse` send completed (Ok) or failed (Err reason).
+    ResponseSent(Result<(), String>),
 
-    // -- Post-response (will be used when IPC is wired up) --
-    #[allow(dead_code)]
-    ResponseSent,
+    // -- Notification fallback --
+    /// Focus probe finished for the given request.
+    FocusChecked { action_id: Uuid, blocked: bool },
+    /// The user acted on the notification for the given request.
+    NotificationActed(Uuid, NotificationAction),
 
     /// User clicked the close (X) button.
     CloseWindow,
+
+    /// Color scheme or language changed in the config; redraw.
+    AppearanceChanged,
 }
 
 // ---------------------------------------------------------------------------
@@ -60,7 +97,6 @@ pub enum Message {
 // ---------------------------------------------------------------------------
 
 /// Action type keywords that indicate a destructive / dangerous operation.
-#[allow(dead_code)]
 const CRITICAL_KEYWORDS: &[&str] = &[
     "delete", "remove", "drop", "exec", "shell", "format",
 ];
@@ -69,11 +105,11 @@ const CRITICAL_KEYWORDS: &[&str] = &[
 ///
 /// A request is considered critical if:
 /// - The `action_type` contains any of the [`CRITICAL_KEYWORDS`], **or**
-/// - The `trust_level` is [`TrustLevel::WebContent`] (any action from
-///   web content is inherently untrusted).
+/// - The `trust_level` is untrusted: the action follows from web content
+///   or a memory recall, which may carry injected instructions.
 #[allow(dead_code)]
 fn is_critical(action_type: &str, trust_level: &TrustLevel) -> bool {
-    if *trust_level == TrustLevel::WebContent {
+    if trust_level.is_untrusted() {
         return true;
     }
     let lower = action_type.to_lowercase();
@@ -89,6 +125,8 @@ impl AiosConfirm {
     pub fn new() -> (Self, IcedTask<Message>) {
         let app = Self {
             state: ConfirmState::Waiting,
+            writer: None,
+            shown_at: None,
         };
         (app, IcedTask::none())
     }
@@ -105,7 +143,15 @@ impl AiosConfirm {
                     description: "Write file /home/user/notes.txt".into(),
                     command: "echo \"hello\" > notes.txt".into(),
                     trust_level: TrustLevel::User,
+                    risk: Some(RiskSummary {
+                        summary: "Writes /home/user/notes.txt, reversible".into(),
+                        affected_paths: vec!["/home/user/notes.txt".into()],
+                        reversible: true,
+                        network_access: false,
+                    }),
+                    can_remember: true,
                 };
+                self.shown_at = Some(Instant::now());
             }
 
             Message::SimulateCriticalRequest => {
@@ -117,11 +163,20 @@ impl AiosConfirm {
                     description: "Delete file /home/user/important.doc".into(),
                     command: "rm /home/user/important.doc".into(),
                     trust_level: TrustLevel::WebContent,
+                    risk: Some(RiskSummary {
+                        summary: "Deletes /home/user/important.doc, not reversible".into(),
+                        affected_paths: vec!["/home/user/important.doc".into()],
+                        reversible: false,
+                        network_access: false,
+                    }),
                     confirm_input: String::new(),
                 };
+                self.shown_at = Some(Instant::now());
+                return iced::widget::operation::focus(critical_dialog::input_id());
             }
 
-            Message::Approve => {
+            Message::Approve | Message::ApproveForConversation => {
+                let remember = matches!(message, Message::ApproveForConversation);
                 let (action_id, action_type) = match &self.state {
                     ConfirmState::Normal { action_id, action_type, .. } => {
                         (*action_id, action_type.clone())
@@ -134,9 +189,11 @@ impl AiosConfirm {
                 tracing::info!(
                     action_id = %action_id,
                     action_type = %action_type,
+                    remember,
                     "action APPROVED by user",
                 );
                 self.state = ConfirmState::Waiting;
+                return self.respond(action_id, true, remember, None);
             }
 
             Message::Reject => {
@@ -155,6 +212,12 @@ impl AiosConfirm {
                     "action REJECTED by user",
                 );
                 self.state = ConfirmState::Waiting;
+                return self.respond(
+                    action_id,
+                    false,
+                    false,
+                    Some("Rejected by user".to_owned()),
+                );
             }
 
             Message::ConfirmInputChanged(value) => {
@@ -163,18 +226,92 @@ impl AiosConfirm {
                 }
             }
 
-            Message::ResponseSent => {
-                self.state = ConfirmState::Waiting;
+            Message::KeyPressed(key, modifiers) => {
+                if self.shown_at.is_none_or(|shown| shown.elapsed() < KEY_DELAY) {
+                    return IcedTask::none();
+                }
+                if let Some(message) = self.shortcut(&key, modifiers) {
+                    return self.update(message);
+                }
+            }
+
+            Message::Ipc(event) => {
+                return self.handle_ipc_event(event);
+            }
+
+            Message::ResponseSent(result) => {
+                if let Err(reason) = result {
+                    tracing::error!("Failed to send confirm response: {reason}");
+                }
+            }
+
+            Message::FocusChecked { action_id, blocked } => {
+                if !blocked || self.current_action_id() != Some(action_id) {
+                    return IcedTask::none();
+                }
+                let (action_type, description, risk, critical) = match &self.state {
+                    ConfirmState::Normal { action_type, description, risk, .. } => {
+                        (action_type.clone(), description.clone(), risk, false)
+                    }
+                    ConfirmState::Critical { action_type, description, risk, .. } => {
+                        (action_type.clone(), description.clone(), risk, true)
+                    }
+                    ConfirmState::Waiting => return IcedTask::none(),
+                };
+                // Prefer the plain-language risk summary over the tool description.
+                let description = risk.as_ref().map_or(description, |r| r.summary.clone());
+                tracing::info!(%action_id, "dialog cannot take focus, sending notification");
+                return IcedTask::perform(
+                    async move {
+                        notify::show_confirm_notification(&action_type, &description, critical)
+                            .await
+                    },
+                    move |action| Message::NotificationActed(action_id, action),
+                );
+            }
+
+            Message::NotificationActed(action_id, action) => {
+                // The dialog may already have answered this request.
+                if self.current_action_id() != Some(action_id) {
+                    return IcedTask::none();
+                }
+                match action {
+                    NotificationAction::Approve => {
+                        // Critical requests never offer "Allow" in the
+                        // notification, but guard against it anyway.
+                        if matches!(self.state, ConfirmState::Critical { .. }) {
+                            return IcedTask::none();
+                        }
+                        return self.update(Message::Approve);
+                    }
+                    NotificationAction::Reject => return self.update(Message::Reject),
+                    NotificationAction::Open => {
+                        return IcedTask::future(notify::focus_dialog()).discard();
+                    }
+                    NotificationAction::Dismissed => {}
+                }
             }
 
             Message::CloseWindow => {
                 return iced::exit();
             }
+
+            Message::AppearanceChanged => {}
         }
 
         IcedTask::none()
     }
 
+    /// Declarative subscription: runs the IPC background worker, watches
+    /// the config for appearance changes and listens for shortcuts.
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch([
+            Subscription::run(ipc_client::ipc_worker).map(Message::Ipc),
+            aios_theme::subscription().map(|()| Message::AppearanceChanged),
+            event::listen_with(key_event),
+        ])
+    }
+
     /// Produces the view tree for the current state.
     pub fn view(&self) -> Element<'_, Message> {
         match &self.state {
@@ -185,14 +322,24 @@ impl AiosConfirm {
                 description,
                 command,
                 trust_level,
+                risk,
+                can_remember,
                 ..
-            } => confirm_dialog::view(action_type, description, command, trust_level),
+            } => confirm_dialog::view(
+                action_type,
+                description,
+                command,
+                trust_level,
+                risk.as_ref(),
+                *can_remember,
+            ),
 
             ConfirmState::Critical {
                 action_type,
                 description,
                 command,
                 trust_level,
+                risk,
                 confirm_input,
                 ..
             } => critical_dialog::view(
@@ -200,17 +347,140 @@ impl AiosConfirm {
                 description,
                 command,
                 trust_level,
+                risk.as_ref(),
                 confirm_input,
             ),
         }
     }
 }
 
-/// Determines whether a request should show the critical dialog.
-///
-/// Will be used when IPC is wired up to route incoming `ConfirmRequest`
-/// messages to the appropriate dialog variant.
-#[allow(dead_code)]
-pub fn request_is_critical(action_type: &str, trust_level: &TrustLevel) -> bool {
-    is_critical(action_type, trust_level)
+/// Key presses for [`AiosConfirm::shortcut`]. Enter counts only when no
+/// widget used it; Escape also when the keyword field did, which only
+/// unfocuses it.
+fn key_event(
+    event: iced::Event,
+    status: event::Status,
+    _window: iced::window::Id,
+) -> Option<Message> {
+    let iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = event else {
+        return None;
+    };
+    let escape = key == keyboard::Key::Named(Named::Escape);
+    (escape || status == event::Status::Ignored).then_some(Message::KeyPressed(key, modifiers))
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+impl AiosConfirm {
+    /// Returns the `action_id` of the request currently on screen.
+    fn current_action_id(&self) -> Option<Uuid> {
+        match &self.state {
+            ConfirmState::Normal { action_id, .. } | ConfirmState::Critical { action_id, .. } => {
+                Some(*action_id)
+            }
+            ConfirmState::Waiting => None,
+        }
+    }
+
+    /// The answer a key gives to the request on screen. The critical dialog
+    /// only takes Escape here; Enter confirms it from the keyword field.
+    fn shortcut(&self, key: &keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+        match (key.as_ref(), &self.state) {
+            (
+                keyboard::Key::Named(Named::Escape),
+                ConfirmState::Normal { .. } | ConfirmState::Critical { .. },
+            ) => Some(Message::Reject),
+            (keyboard::Key::Named(Named::Enter), ConfirmState::Normal { can_remember, .. }) => {
+                if !modifiers.shift() {
+                    Some(Message::Approve)
+                } else if *can_remember {
+                    Some(Message::ApproveForConversation)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Handle an event coming from the IPC background subscription.
+    fn handle_ipc_event(&mut self, event: IpcEvent) -> IcedTask<Message> {
+        match event {
+            IpcEvent::Connected(writer) => {
+                tracing::info!("IPC connected");
+                self.writer = Some(writer);
+            }
+            IpcEvent::Disconnected(reason) => {
+                tracing::warn!("IPC disconnected: {reason}");
+                self.writer = None;
+                // The agent drops pending confirmations with the connection.
+                self.state = ConfirmState::Waiting;
+            }
+            IpcEvent::ConfirmRequest {
+                action_id,
+                action_type,
+                description,
+                command,
+                trust_level,
+                risk,
+                can_remember,
+            } => {
+                tracing::info!(%action_id, %action_type, "confirmation requested");
+                let critical = is_critical(&action_type, &trust_level);
+                self.state = if critical {
+                    ConfirmState::Critical {
+                        action_id,
+                        action_type,
+                        description,
+                        command,
+                        trust_level,
+                        risk,
+                        confirm_input: String::new(),
+                    }
+                } else {
+                    ConfirmState::Normal {
+                        action_id,
+                        action_type,
+                        description,
+                        command,
+                        trust_level,
+                        risk,
+                        can_remember,
+                    }
+                };
+                self.shown_at = Some(Instant::now());
+                let focus_check = IcedTask::perform(notify::focus_blocked(), move |blocked| {
+                    Message::FocusChecked { action_id, blocked }
+                });
+                if critical {
+                    return IcedTask::batch([
+                        focus_check,
+                        iced::widget::operation::focus(critical_dialog::input_id()),
+                    ]);
+                }
+                return focus_check;
+            }
+        }
+        IcedTask::none()
+    }
+
+    /// Send a `ConfirmResponse` to the agent, if connected.
+    fn respond(
+        &self,
+        action_id: Uuid,
+        approved: bool,
+        remember: bool,
+        reason: Option<String>,
+    ) -> IcedTask<Message> {
+        let Some(writer) = self.writer.clone() else {
+            tracing::warn!(%action_id, "not connected to agent; response dropped");
+            return IcedTask::none();
+        };
+        IcedTask::perform(
+            ipc_client::send_response(writer, action_id, approved, remember, reason),
+            Message::ResponseSent,
+        )
+    }
 }
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+use aios_common::ipc::IpcWriter;
+use aios_common::{IpcPayload, RiskSummary, TrustLevel};
+use futures::channel::mpsc;
+use futures::SinkExt;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Request each pending confirmation belongs to, by action id; the
+/// response carries the same request id so the agent's and our logs join.
+static REQUEST_IDS: LazyLock<std::sync::Mutex<HashMap<Uuid, Uuid>>> =
+    LazyLock::new(Default::default);
+
+/// Socket path resolution: `AIOS_SOCKET` env var or platform default.
+pub fn socket_path() -> String {
+    std::env::var("AIOS_SOCKET").unwrap_or_else(|_| {
+        if cfg!(target_os = "macos") {
+            "/tmp/aios-agent.sock".to_owned()
+        } else {
+            format!("/run/user/{}/aios-agent.sock", 1000)
+        }
+    })
+}
+
+/// Events produced by the IPC background worker and forwarded to the app.
+#[derive(Clone)]
+pub enum IpcEvent {
+    /// Connection established; carries a shared writer handle.
+    Connected(Arc<Mutex<IpcWriter>>),
+    /// Connection attempt failed or lost; carries a human-readable reason.
+    Disconnected(String),
+    /// The agent asked the user to approve a tool action.
+    ConfirmRequest {
+        action_id: Uuid,
+        action_type: String,
+        description: String,
+        command: String,
+        trust_level: TrustLevel,
+        risk: Option<RiskSummary>,
+        /// The user may approve the tool for the rest of the conversation.
+        can_remember: bool,
+    },
+}
+
+impl std::fmt::Debug for IpcEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connected(_) => f.debug_tuple("Connected").field(&"<IpcWriter>").finish(),
+            Self::Disconnected(reason) => {
+                f.debug_tuple("Disconnected").field(reason).finish()
+            }
+            Self::ConfirmRequest {
+                action_id,
+                action_type,
+                ..
+            } => f
+                .debug_struct("ConfirmRequest")
+                .field("action_id", action_id)
+                .field("action_type", action_type)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Creates a long-lived `Stream<Item = IpcEvent>` that registers with the
+/// agent as the Confirm client and forwards incoming `ConfirmRequest`s.
+///
+/// On any error, emits `Disconnected`, waits 2 seconds, and retries.
+pub fn ipc_worker() -> impl futures::Stream<Item = IpcEvent> {
+    iced::stream::channel(16, async move |mut output: mpsc::Sender<IpcEvent>| {
+        loop {
+            if let Err(reason) = run_ipc_session(&mut output).await {
+                let _ = output
+                    .send(IpcEvent::Disconnected(reason.clone()))
+                    .await;
+                tracing::warn!("IPC session ended: {reason}. Reconnecting in 2 s...");
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        }
+    })
+}
+
+/// A single connect-register-read session. Returns `Err(reason)` when the
+/// session must be retried.
+async fn run_ipc_session(output: &mut mpsc::Sender<IpcEvent>) -> Result<(), String> {
+    use aios_common::{ClientType, IpcClient, IpcMessage};
+
+    let path = socket_path();
+    tracing::info!("Connecting to agent at {path}...");
+
+    let conn = IpcClient::connect(&path)
+        .await
+        .map_err(|e| format!("connect failed: {e}"))?;
+
+    let (mut reader, writer) = conn.into_split();
+
+    let register_msg = IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::Register {
+            client_type: ClientType::Confirm,
+        },
+    };
+
+    let writer = Arc::new(Mutex::new(writer));
+    writer
+        .lock()
+        .await
+        .send(&register_msg)
+        .await
+        .map_err(|e| format!("register send failed: {e}"))?;
+
+    let ack = reader
+        .recv()
+        .await
+        .map_err(|e| format!("register ack recv failed: {e}"))?;
+
+    if !matches!(ack.payload, IpcPayload::RegisterAck { success: true }) {
+        return Err(format!("unexpected registration response: {:?}", ack.payload));
+    }
+
+    tracing::info!("Registered with agent as confirm client");
+    let _ = output.send(IpcEvent::Connected(Arc::clone(&writer))).await;
+
+    loop {
+        let msg = reader
+            .recv()
+            .await
+            .map_err(|e| format!("read error: {e}"))?;
+
+        let event = match msg.payload {
+            IpcPayload::ConfirmRequest {
+                action_id,
+                action_type,
+                description,
+                command,
+                trust_level,
+                risk,
+                can_remember,
+            } => {
+                tracing::info!(
+                    request_id = %msg.id,
+                    %action_id,
+                    action = %action_type,
+                    "Confirmation requested",
+                );
+                REQUEST_IDS
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(action_id, msg.id);
+                IpcEvent::ConfirmRequest {
+                    action_id,
+                    action_type,
+                    description,
+                    command,
+                    trust_level,
+                    risk,
+                    can_remember,
+                }
+            }
+            IpcPayload::Ping => {
+                let pong = IpcMessage {
+                    id: Uuid::new_v4(),
+                    payload: IpcPayload::Pong,
+                };
+                let _ = writer.lock().await.send(&pong).await;
+                continue;
+            }
+            other => {
+                tracing::debug!("Ignoring unexpected IPC payload: {other:?}");
+                continue;
+            }
+        };
+
+        if output.send(event).await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Send a `ConfirmResponse` for `action_id` over the shared writer;
+/// `remember` approves the tool for the rest of the conversation.
+pub async fn send_response(
+    writer: Arc<Mutex<IpcWriter>>,
+    action_id: Uuid,
+    approved: bool,
+    remember: bool,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let request_id = REQUEST_IDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&action_id)
+        .unwrap_or_else(Uuid::new_v4);
+    tracing::info!(%request_id, %action_id, approved, remember, "Sending confirmation response");
+    let msg = aios_common::IpcMessage {
+        id: request_id,
+        payload: IpcPayload::ConfirmResponse {
+            action_id,
+            approved,
+            reason,
+            remember,
+        },
+    };
+    writer
+        .lock()
+        .await
+        .send(&msg)
+        .await
+        .map_err(|e| format!("{e}"))
+}
@@ -1,4 +1,6 @@
 mod app;
+mod ipc_client;
+mod notify;
 mod theme;
 mod views;
 
@@ -15,16 +17,19 @@ fn main() -> Result<(), iced::Error> {
         .init();
 
     tracing::info!("aios-confirm starting...");
+    aios_common::i18n::init_from_config();
+    aios_theme::init_from_config();
 
     iced::application(AiosConfirm::new, AiosConfirm::update, AiosConfirm::view)
+        .subscription(AiosConfirm::subscription)
         .title("AIOS Confirm")
         .window_size((500.0, 400.0))
         .centered()
         .resizable(false)
-        .theme(Theme::Dark)
+        .theme(|_state: &AiosConfirm| aios_theme::iced_theme())
         .style(|_state: &AiosConfirm, _theme: &Theme| iced::theme::Style {
-            background_color: ConfirmTheme::BG,
-            text_color: ConfirmTheme::TEXT,
+            background_color: ConfirmTheme::bg(),
+            text_color: ConfirmTheme::text(),
         })
         .run()
 }
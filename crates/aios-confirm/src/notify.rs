@@ -0,0 +1,141 @@
+//! Freedesktop notification fallback for confirmation requests.
+//!
+//! When the confirm window cannot take focus -- the session is locked or a
+//! fullscreen window owns the output -- the request is mirrored into the
+//! notification center via `notify-send`, with action buttons that map back
+//! to a `ConfirmResponse`.
+
+use std::process::Stdio;
+
+use aios_common::t;
+
+/// The action the user picked on a confirmation notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationAction {
+    /// "Allow" was clicked.
+    Approve,
+    /// "Deny" was clicked.
+    Reject,
+    /// "Open" was clicked; the user wants to review in the dialog.
+    Open,
+    /// The notification was closed or expired without an action.
+    Dismissed,
+}
+
+/// Returns `true` if the confirm window is unlikely to be able to take focus.
+///
+/// Checks the logind `LockedHint` of the current session and whether the
+/// focused sway container is fullscreen. Any probe that fails is treated as
+/// "focus available" so that the dialog stays the primary path.
+pub async fn focus_blocked() -> bool {
+    session_locked().await || fullscreen_focused().await
+}
+
+/// Show a notification for a pending confirmation and wait for the user's
+/// choice.
+///
+/// Critical requests only offer "Open" and "Deny": approving a destructive
+/// action still requires typing the confirmation keyword in the dialog.
+pub async fn show_confirm_notification(
+    action_type: &str,
+    description: &str,
+    critical: bool,
+) -> NotificationAction {
+    let (urgency, primary) = if critical {
+        ("critical", format!("--action=open={}", t!("notify-open")))
+    } else {
+        ("normal", format!("--action=approve={}", t!("confirm-allow")))
+    };
+
+    let output = tokio::process::Command::new("notify-send")
+        .arg("--app-name=AIOS Confirm")
+        .arg(format!("--urgency={urgency}"))
+        .arg("--wait")
+        .arg(primary)
+        .arg(format!("--action=reject={}", t!("notify-deny")))
+        .arg(t!("notify-title", action = action_type))
+        .arg(description)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await;
+
+    match output {
+        Ok(o) if o.status.success() => parse_action(&String::from_utf8_lossy(&o.stdout)),
+        Ok(o) => {
+            tracing::warn!(status = %o.status, "notify-send exited with failure");
+            NotificationAction::Dismissed
+        }
+        Err(e) => {
+            tracing::warn!("Failed to run notify-send: {e}");
+            NotificationAction::Dismissed
+        }
+    }
+}
+
+/// Ask sway to focus the confirm window (used after "Open").
+pub async fn focus_dialog() {
+    let _ = tokio::process::Command::new("swaymsg")
+        .arg("[title=\"AIOS Confirm\"] focus")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+}
+
+/// Map the action key printed by `notify-send --wait` to a
+/// [`NotificationAction`].
+fn parse_action(stdout: &str) -> NotificationAction {
+    match stdout.trim() {
+        "approve" => NotificationAction::Approve,
+        "reject" => NotificationAction::Reject,
+        "open" => NotificationAction::Open,
+        _ => NotificationAction::Dismissed,
+    }
+}
+
+/// Query logind for the current session's `LockedHint`.
+async fn session_locked() -> bool {
+    let output = tokio::process::Command::new("loginctl")
+        .args(["show-session", "self", "--property=LockedHint", "--value"])
+        .output()
+        .await;
+
+    matches!(output, Ok(o) if o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "yes")
+}
+
+/// Walk the sway tree and report whether the focused node is fullscreen.
+async fn fullscreen_focused() -> bool {
+    let Ok(output) = tokio::process::Command::new("swaymsg")
+        .args(["-t", "get_tree", "--raw"])
+        .output()
+        .await
+    else {
+        return false;
+    };
+
+    if !output.status.success() {
+        return false;
+    }
+
+    serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .map(|tree| focused_is_fullscreen(&tree))
+        .unwrap_or(false)
+}
+
+fn focused_is_fullscreen(node: &serde_json::Value) -> bool {
+    let focused = node.get("focused").and_then(serde_json::Value::as_bool) == Some(true);
+    let fullscreen = node
+        .get("fullscreen_mode")
+        .and_then(serde_json::Value::as_u64)
+        .is_some_and(|m| m > 0);
+    if focused {
+        return fullscreen;
+    }
+
+    ["nodes", "floating_nodes"]
+        .iter()
+        .filter_map(|key| node.get(*key).and_then(serde_json::Value::as_array))
+        .flatten()
+        .any(focused_is_fullscreen)
+}
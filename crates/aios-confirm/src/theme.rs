@@ -1,49 +1,76 @@
-use aios_common::TrustLevel;
+use aios_common::{TrustLevel, t};
 use iced::widget::{button, container, text_input};
 use iced::{Background, Border, Color};
 
-/// Visual constants for the AIOS Confirm dialog.
+/// Colors for the AIOS Confirm dialog.
 ///
-/// Dark theme palette designed for high-contrast confirmation dialogs
-/// that demand user attention and convey trust level through color.
+/// High-contrast palette, derived from the shared [`aios_theme`] palette,
+/// for confirmation dialogs that demand user attention and convey trust
+/// level through color.
 pub struct ConfirmTheme;
 
 impl ConfirmTheme {
     // -- Base palette --
 
-    pub const BG: Color = Color::from_rgb(0.12, 0.13, 0.17);
-    pub const TEXT: Color = Color::from_rgb(0.87, 0.89, 0.93);
-    pub const TEXT_MUTED: Color = Color::from_rgb(0.55, 0.57, 0.63);
-    pub const WARNING: Color = Color::from_rgb(1.0, 0.76, 0.03);
-    pub const DANGER: Color = Color::from_rgb(0.91, 0.30, 0.24);
-    pub const APPROVE: Color = Color::from_rgb(0.47, 0.56, 1.0);
-    pub const CANCEL: Color = Color::from_rgb(0.35, 0.36, 0.42);
-    pub const COMMAND_BG: Color = Color::from_rgb(0.08, 0.08, 0.12);
+    pub fn bg() -> Color {
+        aios_theme::palette().bg_secondary
+    }
+    pub fn text() -> Color {
+        aios_theme::palette().text_primary
+    }
+    pub fn text_muted() -> Color {
+        aios_theme::palette().text_secondary
+    }
+    pub fn warning() -> Color {
+        aios_theme::palette().warning
+    }
+    pub fn danger() -> Color {
+        aios_theme::palette().danger
+    }
+    pub fn approve() -> Color {
+        aios_theme::palette().accent
+    }
+    pub fn cancel() -> Color {
+        let palette = aios_theme::palette();
+        aios_theme::mix(palette.bg_primary, palette.text_secondary, 0.5)
+    }
+    pub fn command_bg() -> Color {
+        aios_theme::palette().bg_code
+    }
 
     // -- Trust level colors --
 
-    pub const TRUST_USER: Color = Color::from_rgb(0.30, 0.69, 0.31);
-    pub const TRUST_SYSTEM: Color = Color::from_rgb(0.26, 0.54, 0.90);
-    pub const TRUST_WEB: Color = Color::from_rgb(0.91, 0.30, 0.24);
-    pub const TRUST_MEMORY: Color = Color::from_rgb(1.0, 0.76, 0.03);
+    pub fn trust_user() -> Color {
+        aios_theme::palette().success
+    }
+    /// System trust keeps a fixed blue so it never blends into a custom accent.
+    pub fn trust_system() -> Color {
+        Color::from_rgb(0.26, 0.54, 0.90)
+    }
+    pub fn trust_web() -> Color {
+        aios_theme::palette().danger
+    }
+    pub fn trust_memory() -> Color {
+        aios_theme::palette().warning
+    }
 
     /// Returns the color associated with the given trust level.
     pub fn trust_color(trust: &TrustLevel) -> Color {
         match trust {
-            TrustLevel::User => Self::TRUST_USER,
-            TrustLevel::System => Self::TRUST_SYSTEM,
-            TrustLevel::WebContent => Self::TRUST_WEB,
-            TrustLevel::Memory => Self::TRUST_MEMORY,
+            TrustLevel::User => Self::trust_user(),
+            TrustLevel::System => Self::trust_system(),
+            TrustLevel::WebContent => Self::trust_web(),
+            TrustLevel::Memory => Self::trust_memory(),
         }
     }
 
     /// Returns a human-readable label for the trust level.
-    pub fn trust_label(trust: &TrustLevel) -> &'static str {
+    pub fn trust_label(trust: &TrustLevel) -> String {
         match trust {
-            TrustLevel::User => "User",
-            TrustLevel::System => "System",
-            TrustLevel::WebContent => "WebContent",
-            TrustLevel::Memory => "Memory",
+            TrustLevel::User => t!("trust-user"),
+            TrustLevel::System => t!("trust-system"),
+            TrustLevel::WebContent => t!("trust-web-content"),
+            TrustLevel::Memory => t!("trust-memory"),
         }
     }
 }
@@ -52,20 +79,20 @@ impl ConfirmTheme {
 // Container styles
 // ---------------------------------------------------------------------------
 
-/// Dark background container for the main window area.
+/// Background container for the main window area.
 pub fn dark_container(_theme: &iced::Theme) -> container::Style {
     container::Style::default()
-        .background(Background::Color(ConfirmTheme::BG))
-        .color(ConfirmTheme::TEXT)
+        .background(Background::Color(ConfirmTheme::bg()))
+        .color(ConfirmTheme::text())
 }
 
 /// Container styled as a code block for displaying commands.
 pub fn command_container(_theme: &iced::Theme) -> container::Style {
     container::Style::default()
-        .background(Background::Color(ConfirmTheme::COMMAND_BG))
-        .color(ConfirmTheme::TEXT)
+        .background(Background::Color(ConfirmTheme::command_bg()))
+        .color(ConfirmTheme::text())
         .border(Border {
-            color: ConfirmTheme::CANCEL,
+            color: ConfirmTheme::cancel(),
             width: 1.0,
             radius: 4.0.into(),
         })
@@ -93,10 +120,22 @@ pub fn danger_container(_theme: &iced::Theme) -> container::Style {
     container::Style::default()
         .background(Background::Color(Color {
             a: 0.10,
-            ..ConfirmTheme::DANGER
+            ..ConfirmTheme::danger()
         }))
         .border(Border {
-            color: ConfirmTheme::DANGER,
+            color: ConfirmTheme::danger(),
+            width: 1.0,
+            radius: 4.0.into(),
+        })
+}
+
+/// Tooltip naming an icon-only button.
+pub fn tooltip_container(_theme: &iced::Theme) -> container::Style {
+    container::Style::default()
+        .background(Background::Color(ConfirmTheme::command_bg()))
+        .color(ConfirmTheme::text())
+        .border(Border {
+            color: ConfirmTheme::cancel(),
             width: 1.0,
             radius: 4.0.into(),
         })
@@ -109,7 +148,7 @@ pub fn danger_container(_theme: &iced::Theme) -> container::Style {
 /// Approve / allow button style (accent blue).
 pub fn approve_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(ConfirmTheme::APPROVE)),
+        background: Some(Background::Color(ConfirmTheme::approve())),
         text_color: Color::WHITE,
         border: Border {
             color: Color::TRANSPARENT,
@@ -122,11 +161,11 @@ pub fn approve_button(_theme: &iced::Theme, status: button::Status) -> button::S
 
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(lighten(ConfirmTheme::APPROVE, 0.15))),
+            background: Some(Background::Color(lighten(ConfirmTheme::approve(), 0.15))),
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(darken(ConfirmTheme::APPROVE, 0.10))),
+            background: Some(Background::Color(darken(ConfirmTheme::approve(), 0.10))),
             ..base
         },
         _ => base,
@@ -136,8 +175,8 @@ pub fn approve_button(_theme: &iced::Theme, status: button::Status) -> button::S
 /// Cancel / reject button style (gray).
 pub fn cancel_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(ConfirmTheme::CANCEL)),
-        text_color: ConfirmTheme::TEXT,
+        background: Some(Background::Color(ConfirmTheme::cancel())),
+        text_color: ConfirmTheme::text(),
         border: Border {
             color: Color::TRANSPARENT,
             width: 0.0,
@@ -149,11 +188,11 @@ pub fn cancel_button(_theme: &iced::Theme, status: button::Status) -> button::St
 
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(lighten(ConfirmTheme::CANCEL, 0.10))),
+            background: Some(Background::Color(lighten(ConfirmTheme::cancel(), 0.10))),
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(darken(ConfirmTheme::CANCEL, 0.10))),
+            background: Some(Background::Color(darken(ConfirmTheme::cancel(), 0.10))),
             ..base
         },
         _ => base,
@@ -163,7 +202,7 @@ pub fn cancel_button(_theme: &iced::Theme, status: button::Status) -> button::St
 /// Danger / confirm-destructive button style (red, used in critical dialog).
 pub fn danger_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(ConfirmTheme::DANGER)),
+        background: Some(Background::Color(ConfirmTheme::danger())),
         text_color: Color::WHITE,
         border: Border {
             color: Color::TRANSPARENT,
@@ -176,11 +215,11 @@ pub fn danger_button(_theme: &iced::Theme, status: button::Status) -> button::St
 
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(lighten(ConfirmTheme::DANGER, 0.15))),
+            background: Some(Background::Color(lighten(ConfirmTheme::danger(), 0.15))),
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(darken(ConfirmTheme::DANGER, 0.10))),
+            background: Some(Background::Color(darken(ConfirmTheme::danger(), 0.10))),
             ..base
         },
         _ => base,
@@ -190,8 +229,8 @@ pub fn danger_button(_theme: &iced::Theme, status: button::Status) -> button::St
 /// Disabled button style (dimmed, non-interactive appearance).
 pub fn disabled_button(_theme: &iced::Theme, _status: button::Status) -> button::Style {
     button::Style {
-        background: Some(Background::Color(Color::from_rgb(0.20, 0.21, 0.25))),
-        text_color: ConfirmTheme::TEXT_MUTED,
+        background: Some(Background::Color(aios_theme::palette().bg_input)),
+        text_color: ConfirmTheme::text_muted(),
         border: Border {
             color: Color::TRANSPARENT,
             width: 0.0,
@@ -206,9 +245,9 @@ pub fn disabled_button(_theme: &iced::Theme, _status: button::Status) -> button:
 pub fn simulate_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
         background: Some(Background::Color(Color::TRANSPARENT)),
-        text_color: ConfirmTheme::TEXT_MUTED,
+        text_color: ConfirmTheme::text_muted(),
         border: Border {
-            color: ConfirmTheme::CANCEL,
+            color: ConfirmTheme::cancel(),
             width: 1.0,
             radius: 6.0.into(),
         },
@@ -218,8 +257,8 @@ pub fn simulate_button(_theme: &iced::Theme, status: button::Status) -> button::
 
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.05))),
-            text_color: ConfirmTheme::TEXT,
+            background: Some(Background::Color(aios_theme::palette().overlay(0.05))),
+            text_color: ConfirmTheme::text(),
             ..base
         },
         _ => base,
@@ -233,16 +272,16 @@ pub fn simulate_button(_theme: &iced::Theme, status: button::Status) -> button::
 /// Style for the "type DELETE" confirmation input.
 pub fn confirm_input(_theme: &iced::Theme, _status: text_input::Status) -> text_input::Style {
     text_input::Style {
-        background: Background::Color(ConfirmTheme::COMMAND_BG),
+        background: Background::Color(ConfirmTheme::command_bg()),
         border: Border {
-            color: ConfirmTheme::DANGER,
+            color: ConfirmTheme::danger(),
             width: 1.0,
             radius: 4.0.into(),
         },
-        icon: ConfirmTheme::TEXT_MUTED,
-        placeholder: ConfirmTheme::TEXT_MUTED,
-        value: ConfirmTheme::TEXT,
-        selection: ConfirmTheme::APPROVE,
+        icon: ConfirmTheme::text_muted(),
+        placeholder: ConfirmTheme::text_muted(),
+        value: ConfirmTheme::text(),
+        selection: ConfirmTheme::approve(),
     }
 }
 
@@ -1,25 +1,31 @@
-use aios_common::TrustLevel;
+use aios_common::{RiskSummary, TrustLevel, t};
 use iced::widget::{button, column, container, row, text, Space};
 use iced::{Element, Fill};
 
 use crate::app::Message;
 use crate::theme::{self, ConfirmTheme};
+use crate::views::{named, risk_summary};
 
 /// Renders the standard (non-destructive) confirmation dialog.
 ///
-/// Displays the action type, description, command, and trust level
-/// with color-coded indicators. Offers "Cancel" and "Allow" buttons.
+/// Displays the action type, description, risk summary (when the agent
+/// provided one), command, and trust level with color-coded indicators. Offers "Cancel" and "Allow" buttons,
+/// and "Allow for this conversation" when `can_remember` is set. Enter,
+/// Shift+Enter and Escape do the same from the keyboard; a line under the
+/// buttons says so.
 pub fn view<'a>(
     action_type: &'a str,
     description: &'a str,
     command: &'a str,
     trust_level: &'a TrustLevel,
+    risk: Option<&'a RiskSummary>,
+    can_remember: bool,
 ) -> Element<'a, Message> {
-    let header = text("Confirm action")
+    let header = text(t!("confirm-title"))
         .size(20)
-        .color(ConfirmTheme::WARNING);
+        .color(ConfirmTheme::warning());
 
-    let close_btn = button(text("X").size(14).color(ConfirmTheme::TEXT_MUTED))
+    let close_btn = button(text("X").size(14).color(ConfirmTheme::text_muted()))
         .on_press(Message::Reject)
         .padding([4, 10])
         .style(theme::cancel_button);
@@ -27,23 +33,23 @@ pub fn view<'a>(
     let top_row = row![
         header,
         Space::new().width(Fill),
-        close_btn,
+        named(close_btn, t!("confirm-close")),
     ]
     .align_y(iced::Alignment::Center);
 
     let type_row = row![
-        text("Type: ").size(13).color(ConfirmTheme::TEXT_MUTED),
-        text(action_type).size(13).color(ConfirmTheme::TEXT),
+        text(format!("{} ", t!("confirm-type"))).size(13).color(ConfirmTheme::text_muted()),
+        text(action_type).size(13).color(ConfirmTheme::text()),
     ];
 
     let desc_label = text(description)
         .size(14)
-        .color(ConfirmTheme::TEXT);
+        .color(ConfirmTheme::text());
 
     let command_block = container(
         text(command)
             .size(13)
-            .color(ConfirmTheme::TEXT),
+            .color(ConfirmTheme::text()),
     )
     .padding(12)
     .width(Fill)
@@ -54,47 +60,75 @@ pub fn view<'a>(
 
     let trust_row = container(
         row![
-            text("Source: ").size(13).color(ConfirmTheme::TEXT_MUTED),
+            text(format!("{} ", t!("confirm-source"))).size(13).color(ConfirmTheme::text_muted()),
             text(trust_label).size(13).color(trust_color),
         ],
     )
     .padding(8)
     .style(theme::trust_badge_container(trust_level));
 
-    let cancel_btn = button(text("Cancel").size(14))
+    let cancel_btn = button(text(t!("confirm-cancel")).size(14))
         .style(theme::cancel_button)
         .on_press(Message::Reject)
         .padding([10, 24]);
 
-    let approve_btn = button(text("Allow").size(14))
+    let approve_btn = button(text(t!("confirm-allow")).size(14))
         .style(theme::approve_button)
         .on_press(Message::Approve)
         .padding([10, 24]);
 
-    let buttons = row![
+    let mut buttons = row![
         cancel_btn,
         Space::new().width(Fill),
-        approve_btn,
     ]
+    .spacing(8)
     .width(Fill);
 
-    let content = column![
+    if can_remember {
+        buttons = buttons.push(
+            button(text(t!("confirm-allow-conversation")).size(14))
+                .style(theme::cancel_button)
+                .on_press(Message::ApproveForConversation)
+                .padding([10, 24]),
+        );
+    }
+    let buttons = buttons.push(approve_btn);
+
+    let keys_hint = text(if can_remember {
+        t!("confirm-keys-hint-remember")
+    } else {
+        t!("confirm-keys-hint")
+    })
+    .size(12)
+    .color(ConfirmTheme::text_muted());
+
+    let mut content = column![
         top_row,
         Space::new().height(12),
         type_row,
         Space::new().height(8),
         desc_label,
-        Space::new().height(12),
-        text("Command:").size(12).color(ConfirmTheme::TEXT_MUTED),
-        Space::new().height(4),
-        command_block,
-        Space::new().height(12),
-        trust_row,
-        Space::new().height(20),
-        buttons,
     ]
     .width(Fill);
 
+    if let Some(risk) = risk {
+        content = content
+            .push(Space::new().height(12))
+            .push(risk_summary::view(risk));
+    }
+
+    content = content
+        .push(Space::new().height(12))
+        .push(text(t!("confirm-command")).size(12).color(ConfirmTheme::text_muted()))
+        .push(Space::new().height(4))
+        .push(command_block)
+        .push(Space::new().height(12))
+        .push(trust_row)
+        .push(Space::new().height(20))
+        .push(buttons)
+        .push(Space::new().height(8))
+        .push(keys_hint);
+
     container(content)
         .padding(24)
         .width(Fill)
@@ -1,41 +1,50 @@
-use aios_common::TrustLevel;
-use iced::widget::{button, column, container, row, text, text_input, Space};
+use aios_common::{RiskSummary, TrustLevel, t};
+use iced::widget::{button, column, container, row, text, text_input, Id, Space};
 use iced::{Color, Element, Fill};
 
 use crate::app::Message;
 use crate::theme::{self, ConfirmTheme};
+use crate::views::risk_summary;
 
 /// The exact string the user must type to confirm a destructive action.
 const CONFIRM_KEYWORD: &str = "DELETE";
 
+/// The keyword field, focused when the dialog opens so the keyword can be
+/// typed right away.
+pub fn input_id() -> Id {
+    Id::new("confirm-keyword")
+}
+
 /// Renders the critical (destructive) confirmation dialog.
 ///
-/// Requires the user to type "DELETE" before the confirm button becomes active.
+/// Requires the user to type "DELETE" before the confirm button becomes active;
+/// Enter in the field then confirms and Escape cancels.
 /// Uses red/danger theming to clearly signal the irreversible nature of the action.
 pub fn view<'a>(
     action_type: &'a str,
     description: &'a str,
     command: &'a str,
     trust_level: &'a TrustLevel,
+    risk: Option<&'a RiskSummary>,
     confirm_input: &'a str,
 ) -> Element<'a, Message> {
-    let header = text("DANGEROUS ACTION")
+    let header = text(t!("confirm-critical-title"))
         .size(20)
-        .color(ConfirmTheme::DANGER);
+        .color(ConfirmTheme::danger());
 
     let type_row = row![
-        text("Type: ").size(13).color(ConfirmTheme::TEXT_MUTED),
-        text(action_type).size(13).color(ConfirmTheme::DANGER),
+        text(format!("{} ", t!("confirm-type"))).size(13).color(ConfirmTheme::text_muted()),
+        text(action_type).size(13).color(ConfirmTheme::danger()),
     ];
 
     let desc_label = text(description)
         .size(14)
-        .color(ConfirmTheme::TEXT);
+        .color(ConfirmTheme::text());
 
     let command_block = container(
         text(command)
             .size(13)
-            .color(ConfirmTheme::TEXT),
+            .color(ConfirmTheme::text()),
     )
     .padding(12)
     .width(Fill)
@@ -46,64 +55,62 @@ pub fn view<'a>(
 
     let trust_row = container(
         row![
-            text("Source: ").size(13).color(ConfirmTheme::TEXT_MUTED),
+            text(format!("{} ", t!("confirm-source"))).size(13).color(ConfirmTheme::text_muted()),
             text(trust_label).size(13).color(trust_color),
         ],
     )
     .padding(8)
     .style(theme::trust_badge_container(trust_level));
 
-    // WebContent gets an extra prominent warning.
-    let web_warning: Option<Element<'_, Message>> =
-        if *trust_level == TrustLevel::WebContent {
-            Some(
-                container(
-                    text("WebContent source -- exercise extreme caution!")
-                        .size(13)
-                        .color(Color::WHITE),
-                )
-                .padding(8)
-                .width(Fill)
-                .style(theme::danger_container)
-                .into(),
-            )
-        } else {
-            None
-        };
+    // Untrusted sources get an extra prominent warning.
+    let warning = match trust_level {
+        TrustLevel::WebContent => Some(t!("confirm-web-content-warning")),
+        TrustLevel::Memory => Some(t!("confirm-memory-warning")),
+        TrustLevel::User | TrustLevel::System => None,
+    };
+    let web_warning: Option<Element<'_, Message>> = warning.map(|warning| {
+        container(text(warning).size(13).color(Color::WHITE))
+            .padding(8)
+            .width(Fill)
+            .style(theme::danger_container)
+            .into()
+    });
 
     let irreversible_warning = container(
-        text("This action is irreversible!")
+        text(t!("confirm-irreversible"))
             .size(13)
-            .color(ConfirmTheme::DANGER),
+            .color(ConfirmTheme::danger()),
     )
     .padding(8)
     .width(Fill)
     .style(theme::danger_container);
 
-    let input_label = text(format!("Type \"{CONFIRM_KEYWORD}\" to confirm:"))
+    let input_label = text(t!("confirm-type-keyword", keyword = CONFIRM_KEYWORD))
         .size(13)
-        .color(ConfirmTheme::TEXT_MUTED);
+        .color(ConfirmTheme::text_muted());
+
+    let confirmed = confirm_input == CONFIRM_KEYWORD;
 
     let input_field = text_input("", confirm_input)
+        .id(input_id())
         .on_input(Message::ConfirmInputChanged)
+        .on_submit_maybe(confirmed.then_some(Message::Approve))
         .padding(10)
         .size(14)
         .style(theme::confirm_input);
 
-    let confirmed = confirm_input == CONFIRM_KEYWORD;
-
-    let cancel_btn = button(text("Cancel").size(14))
+    let cancel_btn = button(text(t!("confirm-cancel")).size(14))
         .style(theme::cancel_button)
         .on_press(Message::Reject)
         .padding([10, 24]);
 
     let confirm_btn = if confirmed {
-        button(text("Confirm").size(14))
+        button(text(t!("confirm-confirm")).size(14))
             .style(theme::danger_button)
             .on_press(Message::Approve)
             .padding([10, 24])
     } else {
-        button(text("Confirm").size(14))
+        button(text(t!("confirm-confirm")).size(14))
             .style(theme::disabled_button)
             .padding([10, 24])
     };
@@ -115,21 +122,33 @@ pub fn view<'a>(
     ]
     .width(Fill);
 
+    let keys_hint = text(t!("confirm-critical-keys-hint", keyword = CONFIRM_KEYWORD))
+        .size(12)
+        .color(ConfirmTheme::text_muted());
+
     let mut content = column![
         header,
         Space::new().height(12),
         type_row,
         Space::new().height(8),
         desc_label,
-        Space::new().height(12),
-        text("Command:").size(12).color(ConfirmTheme::TEXT_MUTED),
-        Space::new().height(4),
-        command_block,
-        Space::new().height(12),
-        trust_row,
     ]
     .width(Fill);
 
+    if let Some(risk) = risk {
+        content = content
+            .push(Space::new().height(12))
+            .push(risk_summary::view(risk));
+    }
+
+    content = content
+        .push(Space::new().height(12))
+        .push(text(t!("confirm-command")).size(12).color(ConfirmTheme::text_muted()))
+        .push(Space::new().height(4))
+        .push(command_block)
+        .push(Space::new().height(12))
+        .push(trust_row);
+
     if let Some(warning) = web_warning {
         content = content
             .push(Space::new().height(8))
@@ -144,7 +163,9 @@ pub fn view<'a>(
         .push(Space::new().height(4))
         .push(input_field)
         .push(Space::new().height(16))
-        .push(buttons);
+        .push(buttons)
+        .push(Space::new().height(8))
+        .push(keys_hint);
 
     container(content)
         .padding(24)
@@ -1,3 +1,23 @@
 pub mod confirm_dialog;
 pub mod critical_dialog;
+pub mod risk_summary;
 pub mod waiting_view;
+
+use iced::widget::{container, text, tooltip};
+use iced::Element;
+
+use crate::app::Message;
+use crate::theme;
+
+/// `button` with a tooltip saying what it does, for buttons that only show
+/// a symbol.
+pub fn named<'a>(button: impl Into<Element<'a, Message>>, name: String) -> Element<'a, Message> {
+    tooltip(
+        button,
+        container(text(name).size(12))
+            .padding(6)
+            .style(theme::tooltip_container),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
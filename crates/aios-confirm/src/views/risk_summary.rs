@@ -0,0 +1,57 @@
+use aios_common::{RiskSummary, t};
+use iced::widget::{column, container, row, text};
+use iced::{Element, Fill};
+
+use crate::app::Message;
+use crate::theme::{self, ConfirmTheme};
+
+/// Maximum number of affected paths listed before collapsing to "+N more".
+const MAX_LISTED_PATHS: usize = 4;
+
+/// Renders the agent-generated risk summary: a one-line description, the
+/// reversibility / network flags, and the affected paths.
+pub fn view(risk: &RiskSummary) -> Element<'_, Message> {
+    let summary_color = if risk.reversible {
+        ConfirmTheme::text()
+    } else {
+        ConfirmTheme::danger()
+    };
+
+    let reversible_label = if risk.reversible {
+        text(t!("risk-reversible")).size(12).color(ConfirmTheme::trust_user())
+    } else {
+        text(t!("risk-not-reversible")).size(12).color(ConfirmTheme::danger())
+    };
+
+    let network_label = if risk.network_access {
+        text(t!("risk-network")).size(12).color(ConfirmTheme::warning())
+    } else {
+        text(t!("risk-no-network")).size(12).color(ConfirmTheme::text_muted())
+    };
+
+    let mut content = column![
+        text(&risk.summary).size(14).color(summary_color),
+        row![reversible_label, network_label].spacing(16),
+    ]
+    .spacing(6);
+
+    for path in risk.affected_paths.iter().take(MAX_LISTED_PATHS) {
+        content = content.push(text(path).size(12).color(ConfirmTheme::text_muted()));
+    }
+    if risk.affected_paths.len() > MAX_LISTED_PATHS {
+        content = content.push(
+            text(t!(
+                "risk-more-paths",
+                count = risk.affected_paths.len() - MAX_LISTED_PATHS
+            ))
+            .size(12)
+            .color(ConfirmTheme::text_muted()),
+        );
+    }
+
+    container(content)
+        .padding(12)
+        .width(Fill)
+        .style(theme::command_container)
+        .into()
+}
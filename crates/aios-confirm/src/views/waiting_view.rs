@@ -1,41 +1,43 @@
+use aios_common::t;
 use iced::widget::{button, column, container, row, text, Space};
 use iced::{Element, Fill, Length};
 
 use crate::app::Message;
 use crate::theme::{self, ConfirmTheme};
+use crate::views::named;
 
 /// Renders the idle waiting screen displayed when no confirmation request is active.
 ///
 /// Includes debug/simulate buttons for testing the UI without a live IPC connection.
 pub fn view() -> Element<'static, Message> {
     // Close button in top-right
-    let close_btn = button(text("X").size(14).color(ConfirmTheme::TEXT_MUTED))
+    let close_btn = button(text("X").size(14).color(ConfirmTheme::text_muted()))
         .on_press(Message::CloseWindow)
         .padding([4, 10])
         .style(theme::simulate_button);
 
     let header = row![
         Space::new().width(Length::Fill),
-        close_btn,
+        named(close_btn, t!("confirm-quit")),
     ];
 
     let title = text("AIOS Confirm")
         .size(24)
-        .color(ConfirmTheme::TEXT);
+        .color(ConfirmTheme::text());
 
-    let subtitle = text("Waiting for requests...")
+    let subtitle = text(t!("confirm-waiting"))
         .size(14)
-        .color(ConfirmTheme::TEXT_MUTED);
+        .color(ConfirmTheme::text_muted());
 
     let simulate_normal = button(
-        text("Simulate Normal").size(13),
+        text(t!("confirm-simulate-normal")).size(13),
     )
     .style(theme::simulate_button)
     .on_press(Message::SimulateNormalRequest)
     .padding([8, 16]);
 
     let simulate_critical = button(
-        text("Simulate Critical").size(13),
+        text(t!("confirm-simulate-critical")).size(13),
     )
     .style(theme::simulate_button)
     .on_press(Message::SimulateCriticalRequest)
@@ -1,40 +1,175 @@
 //! Core application state, messages, and logic for the AIOS Dock.
 
+use std::sync::Arc;
+
+use aios_common::ipc::IpcWriter;
+use aios_common::{
+    AgentHealth, AgentNotification, CalendarEvent, ChatMessage, DockPosition, IpcPayload,
+    MacroConfig, MessageContent, Sensor, SystemStatus, UiConfig, t,
+};
+use chrono::{Datelike, Months, NaiveDate};
 use iced::{Element, Task};
+use iced_layershell::reexport::Anchor;
+use iced_layershell::to_layer_message;
+use tokio::sync::Mutex;
+use uuid::Uuid;
 
+use crate::ipc_client::{self, IpcEvent};
 use crate::launcher;
+use crate::pinned::{self, PinnedApp};
+use crate::sway;
 use crate::views::dock_bar;
 
-/// Identifies a launchable application in the dock.
+/// Height of the panels that open next to the bar; the layer surface grows
+/// away from its screen edge by this much while one is open.
+pub(crate) const PANEL_HEIGHT: u32 = 320;
+
+/// A panel shown above the bar (below it when the dock is at the top).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Notifications,
+    QuickAsk,
+    Calendar,
+}
+
+/// The month shown in the calendar panel and the day whose agenda is listed.
+pub(crate) struct CalendarView {
+    /// First day of the shown month.
+    pub(crate) month: NaiveDate,
+    pub(crate) selected: NaiveDate,
+    /// Events on `selected`; `None` until the agent answers.
+    pub(crate) events: Option<Vec<CalendarEvent>>,
+}
+
+/// A tool call the agent is handling right now.
+pub(crate) struct RunningTool {
+    call_id: Uuid,
+    pub(crate) tool: String,
+    /// Camera, microphone or screen in use by the call.
+    pub(crate) sensors: Vec<Sensor>,
+}
+
+/// A one-shot question asked from the dock's quick-ask field.
+pub(crate) struct QuickAsk {
+    pub(crate) question: String,
+    /// Fresh for every question; the agent forgets it after replying.
+    conversation_id: Uuid,
+    /// `None` while waiting for the agent.
+    pub(crate) reply: Option<String>,
+}
+
+/// A tray item with a slider popover.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AppId {
-    Chat,
-    Browser,
-    Terminal,
-    Settings,
+pub enum QuickControl {
+    Volume,
+    Brightness,
 }
 
-/// All messages the dock UI can produce.
+/// All messages the dock UI can produce. `to_layer_message` adds the
+/// layer-shell requests (anchor, size, ...) understood by the runtime.
+#[to_layer_message]
 #[derive(Debug, Clone)]
 pub enum Message {
-    /// Periodic tick -- refreshes clock and system status.
+    /// Periodic tick -- refreshes the clock.
     Tick,
-    /// User clicked an app icon to launch it.
-    LaunchApp(AppId),
+    /// Advances the activity spinner while tools are running.
+    SpinnerTick,
+    /// System status pushed by the agent, or the connection dropping.
+    Ipc(IpcEvent),
+    /// User clicked a pinned app; carries its desktop entry id.
+    LaunchApp(String),
+    /// Workspaces or windows changed in sway.
+    Sway(sway::Snapshot),
+    /// User clicked a workspace button.
+    FocusWorkspace(String),
+    /// User clicked a running app; carries its sway container id.
+    FocusWindow(i64),
+    /// Open the popover of a tray item, or close it if it is open.
+    TogglePopover(QuickControl),
+    /// The popover slider moved; applied on release.
+    LevelChanged(u8),
+    /// The popover slider was released.
+    LevelReleased,
+    /// The mute button in the volume popover was clicked.
+    ToggleMute,
+    /// The layout indicator was clicked: switch to the next layout.
+    CycleLayout,
+    /// The layout indicator was right-clicked: open or close the layout menu.
+    ToggleLayoutMenu,
+    /// A layout in the layout menu was chosen; carries its index.
+    SelectLayout(usize),
+    /// The pause button was clicked: pause or resume tool execution.
+    TogglePause,
+    /// A request reached the agent (or failed to).
+    ControlSent(Result<(), String>),
+    /// The bell was clicked: open or close the notification panel.
+    ToggleNotifications,
+    /// "Clear all" in the notification panel.
+    ClearNotifications,
+    /// The quick-ask field was edited.
+    AskInputChanged(String),
+    /// Enter in the quick-ask field.
+    AskSubmitted,
+    /// A macro button was clicked; carries the macro's name.
+    RunMacro(String),
+    /// The question reached the agent (or failed to).
+    AskSent(Result<(), String>),
+    /// "Open in chat": continue the question in the chat window.
+    OpenAskInChat,
+    /// Close the open panel.
+    ClosePanel,
+    /// The clock was clicked: open or close the calendar panel.
+    ToggleCalendar,
+    /// Show another month in the calendar; carries its first day.
+    ShowMonth(NaiveDate),
+    /// A day in the calendar was clicked; list its events.
+    SelectDay(NaiveDate),
+    /// The config file changed: redraw with the new palette and language,
+    /// and reload the pinned apps.
+    AppearanceChanged,
 }
 
 /// Root application state for the dock panel.
 pub struct DockApp {
     /// Current clock string, e.g. "15:30".
     pub(crate) clock: String,
-    /// Whether Wi-Fi is connected (hardcoded for MVP).
-    pub(crate) wifi_connected: bool,
-    /// Battery percentage, if available (`None` on desktop).
-    pub(crate) battery_percent: Option<u8>,
-    /// Volume percentage (hardcoded for MVP).
-    pub(crate) volume_percent: u8,
-    /// Current keyboard layout, e.g. "EN" or "RU".
-    pub(crate) kbd_layout: String,
+    /// Launcher buttons from `ui.pinned_apps`.
+    pub(crate) pinned: Vec<PinnedApp>,
+    /// Screen edge from `ui.dock_position`.
+    pub(crate) position: DockPosition,
+    /// Network, volume, battery and keyboard layout from the agent. `None`
+    /// until the first update and while the agent is unreachable.
+    pub(crate) status: Option<SystemStatus>,
+    /// Shared writer for quick-control requests to the agent.
+    writer: Option<Arc<Mutex<IpcWriter>>>,
+    /// Open slider popover, if any.
+    pub(crate) popover: Option<QuickControl>,
+    /// Slider position while dragging, before it is sent on release.
+    pub(crate) pending_level: Option<u8>,
+    /// The keyboard layout menu is open.
+    pub(crate) layout_menu: bool,
+    /// Workspaces and open windows, for the taskbar.
+    pub(crate) sway: sway::Snapshot,
+    /// LLM backend health; `None` while the agent is unreachable.
+    pub(crate) health: Option<AgentHealth>,
+    /// Tool calls the agent is handling right now, oldest first.
+    pub(crate) running_tools: Vec<RunningTool>,
+    /// Tool execution is paused by the kill switch.
+    pub(crate) paused: bool,
+    /// Current frame of the activity spinner.
+    pub(crate) spinner_frame: usize,
+    /// Recent agent notifications, newest first.
+    pub(crate) notifications: Vec<AgentNotification>,
+    /// Panel open next to the bar, if any.
+    pub(crate) panel: Option<Panel>,
+    /// Contents of the quick-ask field.
+    pub(crate) ask_input: String,
+    /// The last quick question and its answer.
+    pub(crate) ask: Option<QuickAsk>,
+    /// Macros from `[macros]` with `dock = true`, by name.
+    pub(crate) macros: Vec<(String, MacroConfig)>,
+    /// State of the calendar panel while it is open.
+    pub(crate) calendar: Option<CalendarView>,
 }
 
 impl DockApp {
@@ -42,45 +177,241 @@ impl DockApp {
     pub fn new() -> (Self, Task<Message>) {
         let state = Self {
             clock: current_time(),
-            wifi_connected: true,
-            battery_percent: None,
-            volume_percent: 50,
-            kbd_layout: current_kbd_layout(),
+            pinned: pinned::load(),
+            position: UiConfig::load().dock_position,
+            status: None,
+            writer: None,
+            popover: None,
+            pending_level: None,
+            layout_menu: false,
+            sway: sway::Snapshot::default(),
+            health: None,
+            running_tools: Vec::new(),
+            paused: false,
+            spinner_frame: 0,
+            notifications: Vec::new(),
+            panel: None,
+            ask_input: String::new(),
+            ask: None,
+            macros: dock_macros(),
+            calendar: None,
         };
 
-        // On Wayland, clients cannot set their own window position.
-        // We spawn a background thread that retries swaymsg IPC until
-        // the window is found and positioned at the bottom.
-        std::thread::spawn(|| {
-            for attempt in 1..=5 {
-                std::thread::sleep(std::time::Duration::from_millis(600 * attempt));
-                if position_dock_via_sway() {
-                    tracing::info!("Dock positioned successfully on attempt {attempt}");
-                    return;
-                }
-                tracing::warn!("Dock positioning attempt {attempt} failed, retrying...");
-            }
-            tracing::error!("Failed to position dock after 5 attempts");
-        });
-
         (state, Task::none())
     }
 
+    /// Layer-shell namespace; lets compositor rules match the dock surface.
+    pub fn namespace(&self) -> String {
+        "aios-dock".to_owned()
+    }
+
     /// Process an incoming message and return a follow-up task.
     #[allow(clippy::needless_pass_by_value)] // iced requires owned Message
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::Tick => {
                 self.clock = current_time();
-                self.kbd_layout = current_kbd_layout();
-                // WiFi, battery, volume -- hardcoded until IPC to aios-agent is wired.
-            }
-            Message::LaunchApp(app) => match app {
-                AppId::Chat => launcher::launch_chat(),
-                AppId::Browser => launcher::launch_browser(),
-                AppId::Terminal => launcher::launch_terminal(),
-                AppId::Settings => launcher::launch_settings(),
-            },
+            }
+            Message::Ipc(IpcEvent::Connected(writer)) => {
+                self.writer = Some(writer);
+                // Retry an agenda request that was lost with the connection.
+                if let Some(calendar) = self.calendar.as_ref().filter(|c| c.events.is_none()) {
+                    let date = calendar.selected;
+                    return self.send(IpcPayload::CalendarRequest { date });
+                }
+            }
+            Message::Ipc(IpcEvent::Status(status)) => {
+                self.status = Some(status);
+            }
+            Message::SpinnerTick => {
+                self.spinner_frame = self.spinner_frame.wrapping_add(1);
+            }
+            Message::Ipc(IpcEvent::Health(health)) => {
+                self.health = Some(health);
+            }
+            Message::Ipc(IpcEvent::ToolStarted(call_id, tool, sensors)) => {
+                self.running_tools.push(RunningTool {
+                    call_id,
+                    tool,
+                    sensors,
+                });
+            }
+            Message::Ipc(IpcEvent::ToolFinished(call_id)) => {
+                self.running_tools
+                    .retain(|running| running.call_id != call_id);
+            }
+            Message::Ipc(IpcEvent::Paused(paused)) => {
+                self.paused = paused;
+            }
+            Message::Ipc(IpcEvent::Notifications(notifications)) => {
+                self.notifications = notifications;
+                // Anything arriving while the panel is open has been seen.
+                if self.panel == Some(Panel::Notifications) && self.unread_notifications() > 0 {
+                    return self.send(IpcPayload::MarkNotificationsRead);
+                }
+            }
+            Message::Ipc(IpcEvent::Disconnected(_)) => {
+                self.status = None;
+                self.writer = None;
+                self.popover = None;
+                self.pending_level = None;
+                self.health = None;
+                self.running_tools.clear();
+                self.paused = false;
+                self.notifications.clear();
+                if let Some(ask) = self.ask.as_mut().filter(|ask| ask.reply.is_none()) {
+                    ask.reply = Some(t!("quick-ask-disconnected"));
+                }
+                if self.panel == Some(Panel::Notifications) {
+                    return self.set_panel(None);
+                }
+            }
+            Message::Ipc(IpcEvent::CalendarEvents(date, events)) => {
+                if let Some(calendar) = self.calendar.as_mut().filter(|c| c.selected == date) {
+                    calendar.events = Some(events);
+                }
+            }
+            Message::Ipc(IpcEvent::Reply(message)) => {
+                let Some(ask) = self.ask.as_mut().filter(|ask| ask.reply.is_none()) else {
+                    return Task::none();
+                };
+                ask.reply = Some(reply_text(&message));
+                let conversation_id = ask.conversation_id;
+                return self.send(IpcPayload::ClearConversation { conversation_id });
+            }
+            Message::Sway(snapshot) => {
+                self.sway = snapshot;
+            }
+            Message::CycleLayout => {
+                self.layout_menu = false;
+                return Task::future(sway::next_layout()).discard();
+            }
+            Message::ToggleLayoutMenu => {
+                self.layout_menu = !self.layout_menu;
+            }
+            Message::SelectLayout(index) => {
+                self.layout_menu = false;
+                return Task::future(sway::switch_layout(index)).discard();
+            }
+            Message::FocusWorkspace(name) => {
+                return Task::future(sway::focus_workspace(name)).discard();
+            }
+            Message::FocusWindow(id) => {
+                return Task::future(sway::focus_window(id)).discard();
+            }
+            Message::TogglePopover(control) => {
+                self.popover = (self.popover != Some(control)).then_some(control);
+                self.pending_level = None;
+            }
+            Message::LevelChanged(level) => {
+                self.pending_level = Some(level);
+            }
+            Message::LevelReleased => {
+                let (Some(control), Some(percent)) = (self.popover, self.pending_level.take())
+                else {
+                    return Task::none();
+                };
+                let payload = match control {
+                    QuickControl::Volume => IpcPayload::SetVolume { percent },
+                    QuickControl::Brightness => IpcPayload::SetBrightness { percent },
+                };
+                return self.send(payload);
+            }
+            Message::ToggleMute => {
+                if let Some(volume) = self.status.as_ref().and_then(|s| s.volume) {
+                    return self.send(IpcPayload::SetMuted {
+                        muted: !volume.muted,
+                    });
+                }
+            }
+            Message::TogglePause => {
+                return self.send(IpcPayload::SetPaused {
+                    paused: !self.paused,
+                });
+            }
+            Message::ToggleNotifications => {
+                let open = self.panel != Some(Panel::Notifications);
+                let resize = self.set_panel(open.then_some(Panel::Notifications));
+                if open && self.unread_notifications() > 0 {
+                    return Task::batch([resize, self.send(IpcPayload::MarkNotificationsRead)]);
+                }
+                return resize;
+            }
+            Message::ClearNotifications => {
+                return self.send(IpcPayload::ClearNotifications);
+            }
+            Message::AskInputChanged(value) => {
+                self.ask_input = value;
+            }
+            Message::AskSubmitted => return self.ask_question(),
+            Message::RunMacro(name) => return self.run_macro(&name),
+            Message::AskSent(Ok(())) => {}
+            Message::AskSent(Err(reason)) => {
+                if let Some(ask) = &mut self.ask {
+                    ask.reply = Some(t!("quick-ask-failed", reason = reason));
+                }
+            }
+            Message::OpenAskInChat => {
+                if let Some(ask) = self.ask.take() {
+                    launcher::ask_in_chat(&ask.question);
+                }
+                return self.set_panel(None);
+            }
+            Message::ClosePanel => {
+                if self.panel == Some(Panel::QuickAsk) {
+                    self.ask = None;
+                }
+                return self.set_panel(None);
+            }
+            Message::ToggleCalendar => {
+                if self.panel == Some(Panel::Calendar) {
+                    return self.set_panel(None);
+                }
+                let today = chrono::Local::now().date_naive();
+                self.calendar = Some(CalendarView {
+                    month: first_of_month(today),
+                    selected: today,
+                    events: None,
+                });
+                return Task::batch([
+                    self.set_panel(Some(Panel::Calendar)),
+                    self.send(IpcPayload::CalendarRequest { date: today }),
+                ]);
+            }
+            Message::ShowMonth(month) => {
+                if let Some(calendar) = &mut self.calendar {
+                    calendar.month = month;
+                }
+            }
+            Message::SelectDay(date) => {
+                let Some(calendar) = &mut self.calendar else {
+                    return Task::none();
+                };
+                calendar.selected = date;
+                calendar.month = first_of_month(date);
+                calendar.events = None;
+                return self.send(IpcPayload::CalendarRequest { date });
+            }
+            Message::ControlSent(Ok(())) => {}
+            Message::ControlSent(Err(reason)) => {
+                tracing::warn!("Agent request failed: {reason}");
+            }
+            Message::LaunchApp(id) => {
+                if let Some(app) = self.pinned.iter().find(|app| app.entry.id == id) {
+                    launcher::launch(&app.entry);
+                }
+            }
+            Message::AppearanceChanged => {
+                self.pinned = pinned::load();
+                self.macros = dock_macros();
+                let position = UiConfig::load().dock_position;
+                if position != self.position {
+                    self.position = position;
+                    return Task::done(Message::AnchorChange(anchor(position)));
+                }
+            }
+            // Layer-shell requests are handled by the runtime.
+            _ => {}
         }
         Task::none()
     }
@@ -89,44 +420,181 @@ impl DockApp {
     pub fn view(&self) -> Element<'_, Message> {
         dock_bar::view(self)
     }
-}
 
-/// Returns the current local time formatted as `HH:MM`.
-fn current_time() -> String {
-    chrono::Local::now().format("%H:%M").to_string()
-}
+    /// Current level of `control`: the slider position while dragging,
+    /// otherwise the agent's last report.
+    pub(crate) fn level(&self, control: QuickControl) -> Option<u8> {
+        if self.popover == Some(control) && self.pending_level.is_some() {
+            return self.pending_level;
+        }
+        let status = self.status.as_ref()?;
+        match control {
+            QuickControl::Volume => status.volume.map(|v| v.percent),
+            QuickControl::Brightness => status.brightness,
+        }
+    }
 
-/// Query sway for the active keyboard layout via `swaymsg -t get_inputs`.
-///
-/// Returns a short label like "EN" or "RU".
-fn current_kbd_layout() -> String {
-    let output = std::process::Command::new("swaymsg")
-        .args(["-t", "get_inputs", "-r"])
-        .output()
-        .ok();
-
-    if let Some(out) = output {
-        if let Ok(inputs) = serde_json::from_slice::<Vec<serde_json::Value>>(&out.stdout) {
-            // Find first keyboard input with xkb_active_layout_name
-            for input in &inputs {
-                if input.get("type").and_then(|v| v.as_str()) != Some("keyboard") {
-                    continue;
-                }
-                if let Some(layout) = input
-                    .get("xkb_active_layout_name")
-                    .and_then(|v| v.as_str())
-                {
-                    return layout_to_short(layout);
+    /// Notifications not seen in the panel yet.
+    pub(crate) fn unread_notifications(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.read).count()
+    }
+
+    /// Sensors in use by running tools, each with the tools using it.
+    pub(crate) fn sensors_in_use(&self) -> Vec<(Sensor, Vec<&str>)> {
+        let mut in_use: Vec<(Sensor, Vec<&str>)> = Vec::new();
+        for running in &self.running_tools {
+            for sensor in &running.sensors {
+                match in_use.iter_mut().find(|(s, _)| s == sensor) {
+                    Some((_, tools)) => tools.push(&running.tool),
+                    None => in_use.push((*sensor, vec![&running.tool])),
                 }
             }
         }
+        in_use
+    }
+
+    /// Whether the agent connection is up.
+    pub(crate) fn connected(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Whether a quick question is waiting for its answer.
+    pub(crate) fn ask_pending(&self) -> bool {
+        self.ask.as_ref().is_some_and(|ask| ask.reply.is_none())
+    }
+
+    /// Send the quick-ask field as a one-shot question.
+    fn ask_question(&mut self) -> Task<Message> {
+        let question = self.ask_input.trim().to_owned();
+        if question.is_empty() || self.ask_pending() {
+            return Task::none();
+        }
+        if !self.connected() {
+            tracing::warn!("Not connected to the agent; cannot ask");
+            return Task::none();
+        }
+        self.ask_input.clear();
+        self.ask(question.clone(), question)
+    }
+
+    /// Send the steps of macro `name` as a one-shot question. The panel and
+    /// "Open in chat" show it as `/name`, which the chat runs the same way.
+    fn run_macro(&mut self, name: &str) -> Task<Message> {
+        if self.ask_pending() {
+            return Task::none();
+        }
+        let Some(instructions) = self
+            .macros
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(name, config)| config.instructions(name, ""))
+        else {
+            tracing::warn!(name, "Macro has no steps");
+            return Task::none();
+        };
+        self.ask(format!("/{name}"), instructions)
+    }
+
+    /// Send `message` in a conversation of its own, and open the panel for
+    /// the answer under `question`.
+    fn ask(&mut self, question: String, message: String) -> Task<Message> {
+        let Some(writer) = self.writer.clone() else {
+            tracing::warn!("Not connected to the agent; cannot ask");
+            return Task::none();
+        };
+        let conversation_id = Uuid::new_v4();
+        self.ask = Some(QuickAsk {
+            question,
+            conversation_id,
+            reply: None,
+        });
+        let request = IpcPayload::ChatRequest {
+            message,
+            conversation_id,
+            message_id: None,
+            attachments: Vec::new(),
+        };
+        Task::batch([
+            self.set_panel(Some(Panel::QuickAsk)),
+            Task::perform(ipc_client::send(writer, request), Message::AskSent),
+        ])
     }
 
-    "EN".to_owned()
+    /// Open or close a panel, growing the layer surface upwards to make room
+    /// for it. The exclusive zone stays at the bar height, so the panel
+    /// overlaps windows instead of pushing them up.
+    fn set_panel(&mut self, panel: Option<Panel>) -> Task<Message> {
+        self.panel = panel;
+        if panel != Some(Panel::Calendar) {
+            self.calendar = None;
+        }
+        let height = if panel.is_some() {
+            crate::DOCK_HEIGHT + PANEL_HEIGHT
+        } else {
+            crate::DOCK_HEIGHT
+        };
+        Task::done(Message::SizeChange((0, height)))
+    }
+
+    /// Send a request to the agent; it answers with a status or
+    /// notification update.
+    fn send(&self, payload: IpcPayload) -> Task<Message> {
+        let Some(writer) = self.writer.clone() else {
+            tracing::warn!("Not connected to the agent; dropping {payload:?}");
+            return Task::none();
+        };
+        Task::perform(ipc_client::send(writer, payload), Message::ControlSent)
+    }
+}
+
+/// The text of an agent reply; tool-use turns never reach the client.
+fn reply_text(message: &ChatMessage) -> String {
+    match &message.content {
+        MessageContent::Text { text } => text.clone(),
+        _ => t!("quick-ask-no-answer"),
+    }
+}
+
+/// The macros that asked for a dock button, by name.
+fn dock_macros() -> Vec<(String, MacroConfig)> {
+    MacroConfig::load_all()
+        .into_iter()
+        .filter(|(_, config)| config.dock)
+        .collect()
+}
+
+/// Layer-shell anchor for a dock spanning the edge at `position`.
+pub(crate) fn anchor(position: DockPosition) -> Anchor {
+    let edge = match position {
+        DockPosition::Top => Anchor::Top,
+        DockPosition::Bottom => Anchor::Bottom,
+    };
+    edge | Anchor::Left | Anchor::Right
+}
+
+/// The first day of `date`'s month.
+pub(crate) fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// The first day of the month `delta` months away from `month`.
+pub(crate) fn shift_month(month: NaiveDate, delta: i32) -> NaiveDate {
+    let months = Months::new(delta.unsigned_abs());
+    let shifted = if delta < 0 {
+        month.checked_sub_months(months)
+    } else {
+        month.checked_add_months(months)
+    };
+    shifted.map_or(month, first_of_month)
+}
+
+/// Returns the current local time formatted as `HH:MM`.
+fn current_time() -> String {
+    chrono::Local::now().format("%H:%M").to_string()
 }
 
 /// Convert a full layout name (e.g. "English (US)", "Russian") to a short label.
-fn layout_to_short(name: &str) -> String {
+pub(crate) fn layout_to_short(name: &str) -> String {
     let lower = name.to_lowercase();
     if lower.contains("russian") || lower.contains("ru") {
         "RU".to_owned()
@@ -141,68 +609,3 @@ fn layout_to_short(name: &str) -> String {
         name.chars().take(2).collect::<String>().to_uppercase()
     }
 }
-
-/// Use swaymsg IPC to position the dock at the bottom of the focused output.
-///
-/// Returns `true` if the move command succeeded.
-fn position_dock_via_sway() -> bool {
-    let output = std::process::Command::new("swaymsg")
-        .args(["-t", "get_outputs", "-r"])
-        .output()
-        .ok();
-
-    let Some(out) = output else {
-        tracing::warn!("swaymsg not available");
-        return false;
-    };
-
-    let (x, _y, w, h) = serde_json::from_slice::<Vec<serde_json::Value>>(&out.stdout)
-        .ok()
-        .and_then(|outputs| {
-            let focused = outputs.iter().find(|o| {
-                o.get("focused").and_then(|v| v.as_bool()).unwrap_or(false)
-            })?;
-            let rect = focused.get("rect")?;
-            let x = rect.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let y = rect.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let w = rect.get("width").and_then(|v| v.as_f64()).unwrap_or(1920.0);
-            let h = rect.get("height").and_then(|v| v.as_f64()).unwrap_or(1080.0);
-            Some((x, y, w, h))
-        })
-        .unwrap_or((0.0, 0.0, 1920.0, 1080.0));
-
-    let dock_x = x as i32;
-    let dock_y = (_y + h - 48.0) as i32;
-    let dock_w = w as i32;
-
-    // Use PID matching — 100% reliable since we know our own PID.
-    let pid = std::process::id();
-    let sel = format!("[pid={pid}]");
-
-    tracing::info!("Positioning dock via swaymsg {sel}: ({dock_x}, {dock_y}) width {dock_w}");
-
-    // Force floating (for_window rules may not have matched).
-    let cmds = [
-        format!("{sel} floating enable"),
-        format!("{sel} sticky enable"),
-        format!("{sel} resize set width {dock_w} height 48"),
-        format!("{sel} move absolute position {dock_x} {dock_y}"),
-    ];
-
-    let mut ok = true;
-    for cmd in &cmds {
-        match std::process::Command::new("swaymsg").arg(cmd).output() {
-            Ok(o) if o.status.success() => {}
-            Ok(o) => {
-                let err = String::from_utf8_lossy(&o.stderr);
-                tracing::warn!("swaymsg `{cmd}` failed: {err}");
-                ok = false;
-            }
-            Err(e) => {
-                tracing::warn!("swaymsg `{cmd}` error: {e}");
-                ok = false;
-            }
-        }
-    }
-    ok
-}
@@ -0,0 +1,196 @@
+//! Agent connection: receives the `SystemStatus` shown in the tray, agent
+//! health, tool activity and the pause state, the notification list,
+//! quick-ask replies and calendar events, and sends quick-control changes,
+//! pause requests and questions.
+
+use std::sync::Arc;
+
+use aios_common::ipc::IpcWriter;
+use aios_common::{
+    AgentHealth, AgentNotification, CalendarEvent, ChatMessage, ClientType, IpcClient, IpcMessage,
+    IpcPayload, Sensor, SystemStatus,
+};
+use chrono::NaiveDate;
+use futures::channel::mpsc;
+use futures::SinkExt;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Socket path resolution: `AIOS_SOCKET` env var or platform default.
+pub fn socket_path() -> String {
+    std::env::var("AIOS_SOCKET").unwrap_or_else(|_| {
+        if cfg!(target_os = "macos") {
+            "/tmp/aios-agent.sock".to_owned()
+        } else {
+            format!("/run/user/{}/aios-agent.sock", 1000)
+        }
+    })
+}
+
+/// Events produced by the IPC background worker and forwarded to the app.
+#[derive(Clone)]
+pub enum IpcEvent {
+    /// Connection established; carries a shared writer handle.
+    Connected(Arc<Mutex<IpcWriter>>),
+    /// The agent pushed a new system status.
+    Status(SystemStatus),
+    /// The agent's LLM backend health changed.
+    Health(AgentHealth),
+    /// The agent started handling a tool call: `(call_id, tool name,
+    /// sensors it uses)`.
+    ToolStarted(Uuid, String, Vec<Sensor>),
+    /// The tool call with this id is done.
+    ToolFinished(Uuid),
+    /// Tool execution was paused or resumed.
+    Paused(bool),
+    /// The agent pushed its notification list, newest first.
+    Notifications(Vec<AgentNotification>),
+    /// The answer to a quick-ask question.
+    Reply(ChatMessage),
+    /// The events on a day, for the calendar panel.
+    CalendarEvents(NaiveDate, Vec<CalendarEvent>),
+    /// Connection attempt failed or lost; carries a human-readable reason.
+    Disconnected(String),
+}
+
+impl std::fmt::Debug for IpcEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connected(_) => f.debug_tuple("Connected").field(&"<IpcWriter>").finish(),
+            Self::Status(status) => f.debug_tuple("Status").field(status).finish(),
+            Self::Health(health) => f.debug_tuple("Health").field(health).finish(),
+            Self::ToolStarted(id, tool, sensors) => f
+                .debug_tuple("ToolStarted")
+                .field(id)
+                .field(tool)
+                .field(sensors)
+                .finish(),
+            Self::ToolFinished(id) => f.debug_tuple("ToolFinished").field(id).finish(),
+            Self::Paused(paused) => f.debug_tuple("Paused").field(paused).finish(),
+            Self::Notifications(list) => f.debug_tuple("Notifications").field(list).finish(),
+            Self::Reply(message) => f.debug_tuple("Reply").field(message).finish(),
+            Self::CalendarEvents(date, events) => f
+                .debug_tuple("CalendarEvents")
+                .field(date)
+                .field(events)
+                .finish(),
+            Self::Disconnected(reason) => {
+                f.debug_tuple("Disconnected").field(reason).finish()
+            }
+        }
+    }
+}
+
+/// Creates a long-lived `Stream<Item = IpcEvent>` that registers with the
+/// agent as the Dock client and forwards incoming status, activity and
+/// notification updates.
+///
+/// On any error, emits `Disconnected`, waits 2 seconds, and retries.
+pub fn ipc_worker() -> impl futures::Stream<Item = IpcEvent> {
+    iced::stream::channel(16, async move |mut output: mpsc::Sender<IpcEvent>| {
+        loop {
+            if let Err(reason) = run_ipc_session(&mut output).await {
+                let _ = output
+                    .send(IpcEvent::Disconnected(reason.clone()))
+                    .await;
+                tracing::warn!("IPC session ended: {reason}. Reconnecting in 2 s...");
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        }
+    })
+}
+
+/// A single connect-register-read session. Returns `Err(reason)` when the
+/// session must be retried.
+async fn run_ipc_session(output: &mut mpsc::Sender<IpcEvent>) -> Result<(), String> {
+    let path = socket_path();
+    tracing::info!("Connecting to agent at {path}...");
+
+    let conn = IpcClient::connect(&path)
+        .await
+        .map_err(|e| format!("connect failed: {e}"))?;
+
+    let (mut reader, writer) = conn.into_split();
+
+    let register_msg = IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::Register {
+            client_type: ClientType::Dock,
+        },
+    };
+    let writer = Arc::new(Mutex::new(writer));
+    writer
+        .lock()
+        .await
+        .send(&register_msg)
+        .await
+        .map_err(|e| format!("register send failed: {e}"))?;
+
+    let ack = reader
+        .recv()
+        .await
+        .map_err(|e| format!("register ack recv failed: {e}"))?;
+
+    if !matches!(ack.payload, IpcPayload::RegisterAck { success: true }) {
+        return Err(format!("unexpected registration response: {:?}", ack.payload));
+    }
+
+    tracing::info!("Registered with agent as dock client");
+    let _ = output.send(IpcEvent::Connected(Arc::clone(&writer))).await;
+
+    loop {
+        let msg = reader
+            .recv()
+            .await
+            .map_err(|e| format!("read error: {e}"))?;
+
+        let event = match msg.payload {
+            IpcPayload::SystemStatus { status } => IpcEvent::Status(status),
+            IpcPayload::AgentHealth { health } => IpcEvent::Health(health),
+            IpcPayload::ToolStarted {
+                call_id,
+                tool,
+                sensors,
+            } => IpcEvent::ToolStarted(call_id, tool, sensors),
+            IpcPayload::ToolFinished { call_id, .. } => IpcEvent::ToolFinished(call_id),
+            IpcPayload::AgentPaused { paused } => IpcEvent::Paused(paused),
+            IpcPayload::Notifications { notifications } => IpcEvent::Notifications(notifications),
+            IpcPayload::ChatResponse { message, .. } => IpcEvent::Reply(message),
+            IpcPayload::CalendarEvents { date, events } => IpcEvent::CalendarEvents(date, events),
+            IpcPayload::Error { message, .. } => {
+                tracing::warn!("Agent error: {message}");
+                continue;
+            }
+            IpcPayload::Ping => {
+                let pong = IpcMessage {
+                    id: Uuid::new_v4(),
+                    payload: IpcPayload::Pong,
+                };
+                let _ = writer.lock().await.send(&pong).await;
+                continue;
+            }
+            other => {
+                tracing::debug!("Ignoring unexpected IPC payload: {other:?}");
+                continue;
+            }
+        };
+
+        if output.send(event).await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Send `payload` to the agent over the shared writer.
+pub async fn send(writer: Arc<Mutex<IpcWriter>>, payload: IpcPayload) -> Result<(), String> {
+    let msg = IpcMessage {
+        id: Uuid::new_v4(),
+        payload,
+    };
+    writer
+        .lock()
+        .await
+        .send(&msg)
+        .await
+        .map_err(|e| format!("{e}"))
+}
@@ -2,43 +2,32 @@
 
 use std::process::Command;
 
-/// Attempts to launch the `aios-chat` binary.
-///
-/// Logs an error if the binary cannot be found or started, but never panics.
-pub fn launch_chat() {
-    if let Err(e) = Command::new("aios-chat").spawn() {
-        tracing::error!("Failed to launch aios-chat: {e}");
-    }
-}
+use aios_common::desktop::DesktopEntry;
 
-/// Attempts to launch a web browser (Chromium with Wayland hints on Linux,
-/// or the default `open` command on macOS).
-///
-/// Logs an error if the browser cannot be started, but never panics.
-pub fn launch_browser() {
-    let result = if cfg!(target_os = "macos") {
-        Command::new("open").arg("https://google.com").spawn()
-    } else {
-        Command::new("chromium")
-            .arg("--ozone-platform-hint=auto")
-            .spawn()
-    };
+/// Terminal emulator for desktop entries with `Terminal=true`.
+const TERMINAL: &[&str] = &["foot", "-e"];
 
-    if let Err(e) = result {
-        tracing::error!("Failed to launch browser: {e}");
+/// Starts the application described by `entry`.
+///
+/// Logs an error if the program cannot be started, but never panics.
+pub fn launch(entry: &DesktopEntry) {
+    let mut command = entry.command();
+    if entry.terminal {
+        command.splice(0..0, TERMINAL.iter().map(|arg| (*arg).to_owned()));
     }
-}
-
-/// Attempts to launch the `foot` terminal emulator.
-pub fn launch_terminal() {
-    if let Err(e) = Command::new("foot").spawn() {
-        tracing::error!("Failed to launch foot: {e}");
+    let Some((program, args)) = command.split_first() else {
+        tracing::error!(id = %entry.id, "Desktop entry has an empty Exec line");
+        return;
+    };
+    if let Err(e) = Command::new(program).args(args).spawn() {
+        tracing::error!("Failed to launch {}: {e}", entry.id);
     }
 }
 
-/// Attempts to launch the `aios-settings` binary.
-pub fn launch_settings() {
-    if let Err(e) = Command::new("aios-settings").spawn() {
-        tracing::error!("Failed to launch aios-settings: {e}");
+/// Opens the chat window with `question` in the input, starting `aios-chat`
+/// if it is not running yet.
+pub fn ask_in_chat(question: &str) {
+    if let Err(e) = Command::new("aios-chat").arg("--ask").arg(question).spawn() {
+        tracing::error!("Failed to launch aios-chat: {e}");
     }
 }
@@ -1,14 +1,22 @@
 mod app;
+mod ipc_client;
 mod launcher;
+mod outputs;
+mod pinned;
+mod sway;
 mod theme;
 mod views;
 
 use app::DockApp;
+use iced_layershell::build_pattern::{application, MainSettings};
+use iced_layershell::reexport::{KeyboardInteractivity, Layer};
+use iced_layershell::settings::{LayerShellSettings, StartMode};
 
-/// Dock panel height in logical pixels.
-const DOCK_HEIGHT: f32 = 48.0;
+/// Dock panel height in logical pixels; also reserved as the exclusive zone
+/// so tiled windows end above the dock.
+const DOCK_HEIGHT: u32 = 48;
 
-fn main() -> iced::Result {
+fn main() -> Result<(), iced_layershell::Error> {
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -16,21 +24,65 @@ fn main() -> iced::Result {
         )
         .init();
 
-    tracing::info!("aios-dock starting...");
+    // Without an output the process only supervises one dock per output.
+    let Some(output) = output_arg() else {
+        tracing::info!("aios-dock supervising per-output docks...");
+        if let Err(e) = outputs::supervise() {
+            tracing::error!("Dock supervisor failed: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    };
 
-    // On Wayland, Position::Specific is ignored by the compositor.
-    // The dock positions itself via swaymsg after the window is created (see app.rs).
-    iced::application(DockApp::new, DockApp::update, DockApp::view)
-        .title("AIOS Dock")
-        .theme(iced::Theme::TokyoNight)
-        .window_size((1920.0, DOCK_HEIGHT))
-        .level(iced::window::Level::AlwaysOnTop)
-        .decorations(false)
-        .resizable(false)
-        .transparent(true)
+    tracing::info!(%output, "aios-dock starting...");
+    aios_common::i18n::init_from_config();
+    aios_theme::init_from_config();
+
+    // A wlr-layer-shell surface anchored to one edge of `output`; the
+    // compositor keeps it in place across resolution changes.
+    let position = aios_common::UiConfig::load().dock_position;
+    application(DockApp::namespace, DockApp::update, DockApp::view)
+        .theme(|_state: &DockApp| aios_theme::iced_theme())
         .antialiasing(true)
-        .subscription(|_state| {
-            iced::time::every(std::time::Duration::from_secs(5)).map(|_| app::Message::Tick)
+        .subscription(|state| {
+            // The spinner only animates while the agent is running tools.
+            let spinner = if state.running_tools.is_empty() {
+                iced::Subscription::none()
+            } else {
+                iced::time::every(std::time::Duration::from_millis(100))
+                    .map(|_| app::Message::SpinnerTick)
+            };
+            iced::Subscription::batch([
+                iced::time::every(std::time::Duration::from_secs(5)).map(|_| app::Message::Tick),
+                spinner,
+                iced::Subscription::run(ipc_client::ipc_worker).map(app::Message::Ipc),
+                iced::Subscription::run(sway::watch).map(app::Message::Sway),
+                aios_theme::subscription().map(|()| app::Message::AppearanceChanged),
+            ])
+        })
+        .settings(MainSettings {
+            layer_settings: LayerShellSettings {
+                size: Some((0, DOCK_HEIGHT)),
+                exclusive_zone: DOCK_HEIGHT as i32,
+                anchor: app::anchor(position),
+                layer: Layer::Top,
+                // Focus only when clicked, for the quick-ask field.
+                keyboard_interactivity: KeyboardInteractivity::OnDemand,
+                start_mode: StartMode::TargetScreen(output),
+                ..LayerShellSettings::default()
+            },
+            ..MainSettings::default()
         })
-        .run()
+        .run_with(DockApp::new)
+}
+
+/// The value of `--output <name>`, if given.
+fn output_arg() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--output" {
+            return args.next();
+        }
+    }
+    None
 }
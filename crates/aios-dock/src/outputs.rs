@@ -0,0 +1,142 @@
+//! One dock per output.
+//!
+//! Started without `--output`, `aios-dock` runs no UI itself: it starts a
+//! child `aios-dock --output <name>` for every active sway output (or only
+//! those listed in `ui.dock_outputs`) and starts or stops children as
+//! monitors are plugged in and out. Each child owns a single layer surface,
+//! so panels open only on the monitor where they were clicked.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::Duration;
+
+use aios_common::UiConfig;
+use tokio::io::AsyncBufReadExt;
+use tokio::process::Child;
+use tokio::signal::unix::{SignalKind, signal};
+
+/// Delay before re-subscribing when swaymsg exits (sway restarting).
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(2);
+
+/// How often crashed docks are noticed and restarted.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Supervise the per-output docks until the process is terminated.
+pub fn supervise() -> std::io::Result<()> {
+    tokio::runtime::Runtime::new()?.block_on(async {
+        let mut terminate = signal(SignalKind::terminate())?;
+        // Children are killed when this map is dropped.
+        let mut docks = HashMap::new();
+        loop {
+            tokio::select! {
+                result = watch(&mut docks) => {
+                    if let Err(e) = result {
+                        tracing::warn!("sway output subscription ended: {e}");
+                    }
+                }
+                _ = terminate.recv() => return Ok(()),
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    })
+}
+
+/// Reconcile the docks with the outputs after every output event, and
+/// periodically to restart docks that exited.
+async fn watch(docks: &mut HashMap<String, Child>) -> Result<(), String> {
+    let mut child = tokio::process::Command::new("swaymsg")
+        .args(["-t", "subscribe", "-m", r#"["output"]"#])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("cannot run swaymsg: {e}"))?;
+    let stdout = child.stdout.take().ok_or("swaymsg has no stdout")?;
+    let mut events = tokio::io::BufReader::new(stdout).lines();
+    let mut check = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        tokio::select! {
+            line = events.next_line() => match line {
+                Ok(Some(_)) => {}
+                Ok(None) => return Err("swaymsg exited".to_owned()),
+                Err(e) => return Err(format!("read error: {e}")),
+            },
+            _ = check.tick() => {}
+        }
+        reconcile(docks).await;
+    }
+}
+
+/// Stop docks whose output went away, and start one for each wanted output
+/// without a running dock.
+async fn reconcile(docks: &mut HashMap<String, Child>) {
+    // Keep the current docks if sway cannot be asked right now.
+    let Some(active) = active_outputs().await else {
+        return;
+    };
+    let configured = UiConfig::load().dock_outputs;
+    let wanted: Vec<String> = active
+        .into_iter()
+        .filter(|name| configured.is_empty() || configured.contains(name))
+        .collect();
+
+    docks.retain(|name, child| {
+        if !wanted.contains(name) {
+            tracing::info!(output = %name, "Output gone; stopping its dock");
+            let _ = child.start_kill();
+            return false;
+        }
+        match child.try_wait() {
+            Ok(None) => true,
+            Ok(Some(status)) => {
+                tracing::warn!(output = %name, %status, "Dock exited; restarting");
+                false
+            }
+            Err(e) => {
+                tracing::warn!(output = %name, "Cannot check dock: {e}");
+                true
+            }
+        }
+    });
+
+    for name in wanted {
+        if docks.contains_key(&name) {
+            continue;
+        }
+        match spawn(&name) {
+            Ok(child) => {
+                tracing::info!(output = %name, "Started dock");
+                docks.insert(name, child);
+            }
+            Err(e) => tracing::warn!(output = %name, "Cannot start dock: {e}"),
+        }
+    }
+}
+
+fn spawn(output: &str) -> std::io::Result<Child> {
+    tokio::process::Command::new(std::env::current_exe()?)
+        .args(["--output", output])
+        .kill_on_drop(true)
+        .spawn()
+}
+
+/// Names of the enabled outputs, or `None` if sway is unreachable.
+async fn active_outputs() -> Option<Vec<String>> {
+    let output = tokio::process::Command::new("swaymsg")
+        .args(["-t", "get_outputs", "-r"])
+        .output()
+        .await
+        .ok()
+        .filter(|o| o.status.success())?;
+    let outputs: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).ok()?;
+    Some(
+        outputs
+            .iter()
+            .filter(|o| o.get("active").and_then(serde_json::Value::as_bool) == Some(true))
+            .filter_map(|o| o.get("name")?.as_str())
+            .map(str::to_owned)
+            .collect(),
+    )
+}
@@ -0,0 +1,44 @@
+//! Pinned applications: the desktop entries listed in `ui.pinned_apps`,
+//! with their icons loaded.
+
+use aios_common::desktop::{self, DesktopEntry};
+use aios_common::UiConfig;
+use iced::widget::{image, svg};
+
+/// A decoded-on-demand icon handle.
+#[derive(Debug, Clone)]
+pub enum Icon {
+    Svg(svg::Handle),
+    Raster(image::Handle),
+}
+
+/// A launcher button in the dock.
+#[derive(Debug, Clone)]
+pub struct PinnedApp {
+    pub entry: DesktopEntry,
+    /// `None` if the icon could not be found; the name is shown instead.
+    pub icon: Option<Icon>,
+}
+
+/// Read the pinned list from the config. Ids without an installed desktop
+/// entry are skipped.
+pub fn load() -> Vec<PinnedApp> {
+    UiConfig::load()
+        .pinned_apps
+        .iter()
+        .filter_map(|id| {
+            let Some(entry) = desktop::find(id) else {
+                tracing::warn!(%id, "Pinned app has no desktop entry");
+                return None;
+            };
+            let icon = entry.icon.as_deref().and_then(desktop::icon_path).map(|path| {
+                if path.extension().is_some_and(|ext| ext == "svg") {
+                    Icon::Svg(svg::Handle::from_path(path))
+                } else {
+                    Icon::Raster(image::Handle::from_path(path))
+                }
+            });
+            Some(PinnedApp { entry, icon })
+        })
+        .collect()
+}
@@ -0,0 +1,246 @@
+//! Workspaces, open windows and keyboard layouts from sway, for the taskbar
+//! and the layout indicator of the dock.
+//!
+//! `swaymsg -t subscribe -m` reports workspace, window and input events;
+//! after each one the dock re-reads the workspace list, the tree and the
+//! inputs.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use futures::SinkExt;
+use tokio::io::AsyncBufReadExt;
+
+/// Delay before re-subscribing when swaymsg exits (sway restarting).
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(2);
+
+/// A sway workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub name: String,
+    pub focused: bool,
+    pub urgent: bool,
+}
+
+/// An open application window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Window {
+    /// sway container id, used to focus it.
+    pub id: i64,
+    /// Wayland `app_id`, or the X11 class for Xwayland windows.
+    pub app: String,
+    pub title: String,
+    pub focused: bool,
+}
+
+/// The xkb layouts configured for the keyboards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keyboard {
+    /// Layout names as sway reports them, e.g. `English (US)`.
+    pub layouts: Vec<String>,
+    /// Index of the active layout in `layouts`.
+    pub active: usize,
+}
+
+impl Keyboard {
+    /// Name of the active layout.
+    pub fn active_name(&self) -> Option<&str> {
+        self.layouts.get(self.active).map(String::as_str)
+    }
+}
+
+/// Workspaces, windows and keyboard layouts at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub workspaces: Vec<Workspace>,
+    pub windows: Vec<Window>,
+    /// `None` when sway knows no keyboard.
+    pub keyboard: Option<Keyboard>,
+}
+
+/// Stream a fresh [`Snapshot`] on startup and after every workspace, window
+/// or input event.
+pub fn watch() -> impl futures::Stream<Item = Snapshot> {
+    iced::stream::channel(16, async move |mut output: mpsc::Sender<Snapshot>| {
+        loop {
+            if let Err(e) = run_subscription(&mut output).await {
+                tracing::warn!("sway subscription ended: {e}");
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    })
+}
+
+async fn run_subscription(output: &mut mpsc::Sender<Snapshot>) -> Result<(), String> {
+    let mut child = tokio::process::Command::new("swaymsg")
+        .args(["-t", "subscribe", "-m", r#"["workspace","window","input"]"#])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("cannot run swaymsg: {e}"))?;
+    let stdout = child.stdout.take().ok_or("swaymsg has no stdout")?;
+    let mut events = tokio::io::BufReader::new(stdout).lines();
+
+    let mut last = None;
+    loop {
+        let snapshot = snapshot().await;
+        if last.as_ref() != Some(&snapshot) {
+            last = Some(snapshot.clone());
+            if output.send(snapshot).await.is_err() {
+                return Ok(());
+            }
+        }
+        match events.next_line().await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Err("swaymsg exited".to_owned()),
+            Err(e) => return Err(format!("read error: {e}")),
+        }
+    }
+}
+
+async fn snapshot() -> Snapshot {
+    let (workspaces, tree, inputs) = tokio::join!(
+        query("get_workspaces"),
+        query("get_tree"),
+        query("get_inputs")
+    );
+    let workspaces = workspaces
+        .as_ref()
+        .and_then(serde_json::Value::as_array)
+        .map(|list| list.iter().filter_map(parse_workspace).collect())
+        .unwrap_or_default();
+    let mut windows = Vec::new();
+    if let Some(tree) = &tree {
+        collect_windows(tree, &mut windows);
+    }
+    let keyboard = inputs
+        .as_ref()
+        .and_then(serde_json::Value::as_array)
+        .and_then(|list| list.iter().find_map(parse_keyboard));
+    Snapshot {
+        workspaces,
+        windows,
+        keyboard,
+    }
+}
+
+async fn query(kind: &str) -> Option<serde_json::Value> {
+    let output = tokio::process::Command::new("swaymsg")
+        .args(["-t", kind, "-r"])
+        .output()
+        .await
+        .ok()
+        .filter(|o| o.status.success())?;
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+fn parse_workspace(value: &serde_json::Value) -> Option<Workspace> {
+    Some(Workspace {
+        name: value.get("name")?.as_str()?.to_owned(),
+        focused: value.get("focused").and_then(serde_json::Value::as_bool) == Some(true),
+        urgent: value.get("urgent").and_then(serde_json::Value::as_bool) == Some(true),
+    })
+}
+
+/// Layouts of a keyboard input; other device types and keyboards without
+/// xkb layouts are skipped.
+fn parse_keyboard(input: &serde_json::Value) -> Option<Keyboard> {
+    if input.get("type")?.as_str()? != "keyboard" {
+        return None;
+    }
+    let layouts: Vec<String> = input
+        .get("xkb_layout_names")?
+        .as_array()?
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .map(str::to_owned)
+        .collect();
+    if layouts.is_empty() {
+        return None;
+    }
+    let active = input
+        .get("xkb_active_layout_index")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|index| usize::try_from(index).ok())
+        .filter(|index| *index < layouts.len())
+        .unwrap_or_default();
+    Some(Keyboard { layouts, active })
+}
+
+/// Walk the tree collecting application windows, skipping the dock itself
+/// and the scratchpad.
+fn collect_windows(node: &serde_json::Value, windows: &mut Vec<Window>) {
+    if node.get("name").and_then(serde_json::Value::as_str) == Some("__i3_scratch") {
+        return;
+    }
+    if let Some(window) = parse_window(node) {
+        windows.push(window);
+        return;
+    }
+    for key in ["nodes", "floating_nodes"] {
+        if let Some(children) = node.get(key).and_then(serde_json::Value::as_array) {
+            for child in children {
+                collect_windows(child, windows);
+            }
+        }
+    }
+}
+
+fn parse_window(node: &serde_json::Value) -> Option<Window> {
+    let pid = node.get("pid")?.as_u64()?;
+    if pid == u64::from(std::process::id()) {
+        return None;
+    }
+    let app = node
+        .get("app_id")
+        .and_then(serde_json::Value::as_str)
+        .or_else(|| node.get("window_properties")?.get("class")?.as_str())?;
+    Some(Window {
+        id: node.get("id")?.as_i64()?,
+        app: app.to_owned(),
+        title: node
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_owned(),
+        focused: node.get("focused").and_then(serde_json::Value::as_bool) == Some(true),
+    })
+}
+
+/// Switch to the workspace called `name`.
+pub async fn focus_workspace(name: String) {
+    let escaped = name.replace('\\', "\\\\").replace('"', "\\\"");
+    run_command(&format!("workspace \"{escaped}\"")).await;
+}
+
+/// Focus the window with sway container id `id`.
+pub async fn focus_window(id: i64) {
+    run_command(&format!("[con_id={id}] focus")).await;
+}
+
+/// Switch every keyboard to the next layout.
+pub async fn next_layout() {
+    run_command("input type:keyboard xkb_switch_layout next").await;
+}
+
+/// Switch every keyboard to the layout at `index`.
+pub async fn switch_layout(index: usize) {
+    run_command(&format!("input type:keyboard xkb_switch_layout {index}")).await;
+}
+
+async fn run_command(command: &str) {
+    match tokio::process::Command::new("swaymsg")
+        .arg(command)
+        .output()
+        .await
+    {
+        Ok(o) if o.status.success() => {}
+        Ok(o) => {
+            let err = String::from_utf8_lossy(&o.stderr);
+            tracing::warn!("swaymsg `{command}` failed: {err}");
+        }
+        Err(e) => tracing::warn!("swaymsg `{command}` error: {e}"),
+    }
+}
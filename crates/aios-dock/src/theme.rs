@@ -1,39 +1,67 @@
-//! Dark theme styles for the AIOS Dock panel.
+//! Styles for the AIOS Dock panel.
 
 use iced::widget::{button, container};
 use iced::{Background, Border, Color};
 
-/// Color palette for the dock panel.
+/// Color palette for the dock panel, derived from the shared
+/// [`aios_theme`] palette.
 pub struct DockColors;
 
 impl DockColors {
-    /// Semi-transparent dark background for the dock bar.
-    pub const DOCK_BG: Color = Color::from_rgba(0.10, 0.11, 0.15, 0.95);
+    /// Semi-transparent background for the dock bar.
+    pub fn dock_bg() -> Color {
+        aios_theme::with_alpha(aios_theme::palette().bg_primary, 0.95)
+    }
 
     /// Subtle background for app icon buttons (idle state).
-    pub const ICON_BG: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.08);
+    pub fn icon_bg() -> Color {
+        aios_theme::palette().overlay(0.08)
+    }
 
     /// Highlighted background for app icon buttons (hovered state).
-    pub const ICON_HOVER: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.15);
+    pub fn icon_hover() -> Color {
+        aios_theme::palette().overlay(0.15)
+    }
 
     /// Pressed state background for app icon buttons.
-    pub const ICON_PRESSED: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.22);
+    pub fn icon_pressed() -> Color {
+        aios_theme::palette().overlay(0.22)
+    }
 
-    /// Primary text color (light gray).
-    pub const TEXT: Color = Color::from_rgb(0.87, 0.89, 0.93);
+    /// Primary text color.
+    pub fn text() -> Color {
+        aios_theme::palette().text_primary
+    }
 
     /// Secondary/muted text color.
-    pub const TEXT_MUTED: Color = Color::from_rgb(0.55, 0.58, 0.65);
+    pub fn text_muted() -> Color {
+        aios_theme::palette().text_secondary
+    }
 
-    /// Accent color for active/connected indicators (reserved for future use).
-    #[allow(dead_code)]
-    pub const ACCENT: Color = Color::from_rgb(0.47, 0.56, 1.0);
+    /// Accent color for active indicators (focused workspace/window).
+    pub fn accent() -> Color {
+        aios_theme::palette().accent
+    }
 
     /// Green indicator (e.g. Wi-Fi connected).
-    pub const STATUS_OK: Color = Color::from_rgb(0.30, 0.78, 0.47);
+    pub fn status_ok() -> Color {
+        aios_theme::palette().success
+    }
+
+    /// Low battery.
+    pub fn status_warning() -> Color {
+        aios_theme::palette().warning
+    }
+
+    /// Critically low battery.
+    pub fn status_critical() -> Color {
+        aios_theme::palette().danger
+    }
 
     /// Gray indicator (e.g. Wi-Fi disconnected).
-    pub const STATUS_OFF: Color = Color::from_rgb(0.45, 0.47, 0.52);
+    pub fn status_off() -> Color {
+        aios_theme::with_alpha(aios_theme::palette().text_secondary, 0.7)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -43,8 +71,33 @@ impl DockColors {
 /// Style for the root dock bar container.
 pub fn dock_bar(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(DockColors::DOCK_BG)),
-        text_color: Some(DockColors::TEXT),
+        background: Some(Background::Color(DockColors::dock_bg())),
+        text_color: Some(DockColors::text()),
+        ..container::Style::default()
+    }
+}
+
+/// Style for the quick-control popovers in the tray.
+pub fn popover(_theme: &iced::Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(DockColors::icon_bg())),
+        text_color: Some(DockColors::text()),
+        border: Border {
+            radius: 8.0.into(),
+            ..Border::default()
+        },
+        ..container::Style::default()
+    }
+}
+
+/// Style for a privacy indicator: a pill filled with `color`.
+pub fn privacy_badge(color: Color) -> impl Fn(&iced::Theme) -> container::Style {
+    move |_theme| container::Style {
+        background: Some(Background::Color(color)),
+        border: Border {
+            radius: 8.0.into(),
+            ..Border::default()
+        },
         ..container::Style::default()
     }
 }
@@ -56,8 +109,8 @@ pub fn dock_bar(_theme: &iced::Theme) -> container::Style {
 /// Style for app icon buttons in the dock.
 pub fn app_icon_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(DockColors::ICON_BG)),
-        text_color: DockColors::TEXT,
+        background: Some(Background::Color(DockColors::icon_bg())),
+        text_color: DockColors::text(),
         border: Border {
             radius: 8.0.into(),
             ..Border::default()
@@ -68,16 +121,54 @@ pub fn app_icon_button(_theme: &iced::Theme, status: button::Status) -> button::
     match status {
         button::Status::Active => base,
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(DockColors::ICON_HOVER)),
+            background: Some(Background::Color(DockColors::icon_hover())),
             ..base
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(DockColors::ICON_PRESSED)),
+            background: Some(Background::Color(DockColors::icon_pressed())),
             ..base
         },
         button::Status::Disabled => button::Style {
-            background: Some(Background::Color(DockColors::ICON_BG)),
-            text_color: DockColors::TEXT_MUTED,
+            background: Some(Background::Color(DockColors::icon_bg())),
+            text_color: DockColors::text_muted(),
+            ..base
+        },
+    }
+}
+
+/// Style for highlighted dock buttons: the focused workspace or window.
+pub fn app_icon_button_active(theme: &iced::Theme, status: button::Status) -> button::Style {
+    let base = app_icon_button(theme, status);
+    button::Style {
+        border: Border {
+            color: DockColors::accent(),
+            width: 1.5,
+            radius: 8.0.into(),
+        },
+        ..base
+    }
+}
+
+/// Style for clickable tray items: flat until hovered.
+pub fn tray_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
+    let base = button::Style {
+        background: None,
+        text_color: DockColors::text(),
+        border: Border {
+            radius: 6.0.into(),
+            ..Border::default()
+        },
+        ..button::Style::default()
+    };
+
+    match status {
+        button::Status::Active | button::Status::Disabled => base,
+        button::Status::Hovered => button::Style {
+            background: Some(Background::Color(DockColors::icon_hover())),
+            ..base
+        },
+        button::Status::Pressed => button::Style {
+            background: Some(Background::Color(DockColors::icon_pressed())),
             ..base
         },
     }
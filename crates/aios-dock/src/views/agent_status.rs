@@ -0,0 +1,85 @@
+//! Agent indicator: a dot colored by LLM backend health, replaced by a
+//! spinner while the agent is running tools. Hovering explains the state.
+//! Next to it sits the kill switch that pauses all tool execution.
+
+use aios_common::{AgentHealth, t};
+use iced::widget::{button, container, text, tooltip};
+use iced::Element;
+
+use crate::app::{DockApp, Message};
+use crate::theme::{self, DockColors};
+
+/// Braille spinner frames, advanced by `Message::SpinnerTick`.
+const SPINNER: [&str; 8] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"];
+
+pub fn view(state: &DockApp) -> Element<'static, Message> {
+    let (health_label, color) = match &state.health {
+        None => (t!("agent-status-offline"), DockColors::status_off()),
+        Some(AgentHealth::Ready { provider }) => (
+            t!("agent-status-ready", provider = provider.as_str()),
+            DockColors::status_ok(),
+        ),
+        Some(AgentHealth::Echo) => (t!("agent-status-echo"), DockColors::status_warning()),
+        Some(AgentHealth::ProviderError { provider, message }) => (
+            t!(
+                "agent-status-error",
+                provider = provider.as_str(),
+                message = message.as_str()
+            ),
+            DockColors::status_critical(),
+        ),
+    };
+
+    let (glyph, label) = match state.running_tools.last() {
+        Some(running) => (
+            SPINNER[state.spinner_frame % SPINNER.len()],
+            format!(
+                "{health_label}\n{}",
+                t!("agent-status-running", tool = running.tool.as_str())
+            ),
+        ),
+        None => ("●", health_label),
+    };
+    let (label, color) = if state.paused {
+        (
+            format!("{label}\n{}", t!("agent-status-paused")),
+            DockColors::status_warning(),
+        )
+    } else {
+        (label, color)
+    };
+
+    tooltip(
+        text(glyph).size(14).color(color),
+        container(text(label).size(12))
+            .padding(6)
+            .style(theme::popover),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Pause or resume all tool execution; only shown while the agent is
+/// connected.
+pub fn pause_button(state: &DockApp) -> Option<Element<'static, Message>> {
+    state.health.as_ref()?;
+    let (glyph, label, color) = if state.paused {
+        ("▶", t!("agent-resume-tools"), DockColors::status_warning())
+    } else {
+        ("⏸", t!("agent-pause-tools"), DockColors::text_muted())
+    };
+    let pause = button(text(glyph).size(12).color(color))
+        .padding([2, 4])
+        .style(theme::tray_button)
+        .on_press(Message::TogglePause);
+    Some(
+        tooltip(
+            pause,
+            container(text(label).size(12))
+                .padding(6)
+                .style(theme::popover),
+            tooltip::Position::Top,
+        )
+        .into(),
+    )
+}
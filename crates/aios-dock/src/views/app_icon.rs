@@ -1,24 +1,50 @@
 //! A single application icon button for the dock bar.
 
-use iced::widget::{button, center, text};
+use iced::widget::{button, center, container, image, svg, text, tooltip};
 use iced::{Element, Length};
 
-use crate::app::{AppId, Message};
+use crate::app::Message;
+use crate::pinned::{Icon, PinnedApp};
 use crate::theme;
 
-/// Renders a single dock icon as a fixed-size button with a text label.
+/// Icon edge length in the 48x40 button.
+const ICON_SIZE: f32 = 28.0;
+
+/// Names stand in for missing icons, cut to this many characters.
+const LABEL_CHARS: usize = 6;
+
+/// Renders a pinned app as a fixed-size button with its icon, or its name
+/// if there is no icon. The full name shows on hover.
 ///
 /// The button sends `Message::LaunchApp(id)` when pressed.
-pub fn view(label: &str, id: AppId) -> Element<'static, Message> {
-    let content = center(text(label.to_owned()).size(13))
-        .width(Length::Fill)
-        .height(Length::Fill);
+pub fn view(app: &PinnedApp) -> Element<'_, Message> {
+    let content: Element<'_, Message> = match &app.icon {
+        Some(Icon::Svg(handle)) => svg(handle.clone())
+            .width(ICON_SIZE)
+            .height(ICON_SIZE)
+            .into(),
+        Some(Icon::Raster(handle)) => image(handle.clone())
+            .width(ICON_SIZE)
+            .height(ICON_SIZE)
+            .into(),
+        None => text(app.entry.name.chars().take(LABEL_CHARS).collect::<String>())
+            .size(13)
+            .into(),
+    };
 
-    button(content)
+    let icon = button(center(content).width(Length::Fill).height(Length::Fill))
         .width(48.0)
         .height(40.0)
         .padding(4)
         .style(theme::app_icon_button)
-        .on_press(Message::LaunchApp(id))
-        .into()
+        .on_press(Message::LaunchApp(app.entry.id.clone()));
+
+    tooltip(
+        icon,
+        container(text(app.entry.name.as_str()).size(12))
+            .padding(6)
+            .style(theme::popover),
+        tooltip::Position::Top,
+    )
+    .into()
 }
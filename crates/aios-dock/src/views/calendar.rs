@@ -0,0 +1,181 @@
+//! The clock in the tray and the calendar panel it opens: a month grid and
+//! the agenda of the selected day.
+
+use aios_common::{CalendarEvent, t};
+use chrono::{Datelike, Duration, NaiveDate};
+use iced::widget::{button, center, column, row, scrollable, text, Column, Row, Space};
+use iced::{Element, Length};
+
+use crate::app::{self, CalendarView, DockApp, Message};
+use crate::theme::{self, DockColors};
+
+/// Side of a day cell in the month grid.
+const DAY_SIZE: f32 = 28.0;
+
+/// Tray clock; toggles the calendar panel.
+pub fn clock(state: &DockApp) -> Element<'static, Message> {
+    button(text(state.clock.clone()).size(14).color(DockColors::text()))
+        .padding([2, 4])
+        .style(theme::tray_button)
+        .on_press(Message::ToggleCalendar)
+        .into()
+}
+
+/// Panel content: month grid on the left, agenda on the right.
+pub fn panel(state: &DockApp) -> Element<'_, Message> {
+    let Some(calendar) = &state.calendar else {
+        return Space::new().into();
+    };
+    row![month(calendar), agenda(state, calendar)]
+        .spacing(16)
+        .into()
+}
+
+fn month(calendar: &CalendarView) -> Element<'static, Message> {
+    let shown = calendar.month;
+    let months = t!("calendar-months");
+    let name = months
+        .split_whitespace()
+        .nth(shown.month0() as usize)
+        .unwrap_or_default();
+    let title = format!("{name} {}", shown.year());
+    let header = row![
+        nav_button("‹", app::shift_month(shown, -1)),
+        center(text(title).size(13).color(DockColors::text())).height(DAY_SIZE),
+        nav_button("›", app::shift_month(shown, 1)),
+    ]
+    .width(DAY_SIZE * 7.0)
+    .align_y(iced::Alignment::Center);
+
+    let weekdays = Row::with_children(t!("calendar-weekdays").split_whitespace().map(|day| {
+        center(
+            text(day.to_owned())
+                .size(11)
+                .color(DockColors::text_muted()),
+        )
+        .width(DAY_SIZE)
+        .height(20.0)
+        .into()
+    }));
+
+    // Monday-first weeks covering the whole month.
+    let offset = i64::from(shown.weekday().num_days_from_monday());
+    let first = shown - Duration::days(offset);
+    let next_month = app::shift_month(shown, 1);
+    let mut weeks = Column::new();
+    let mut week_start = first;
+    while week_start < next_month {
+        weeks = weeks.push(Row::with_children(
+            week_start
+                .iter_days()
+                .take(7)
+                .map(|date| day(calendar, date)),
+        ));
+        week_start += Duration::days(7);
+    }
+
+    column![header, weekdays, weeks].spacing(4).into()
+}
+
+fn nav_button(label: &'static str, month: NaiveDate) -> Element<'static, Message> {
+    button(center(text(label).size(14).color(DockColors::text())))
+        .width(DAY_SIZE)
+        .height(DAY_SIZE)
+        .padding(0)
+        .style(theme::tray_button)
+        .on_press(Message::ShowMonth(month))
+        .into()
+}
+
+/// A day cell: today in the accent color, the selected day highlighted and
+/// days of the neighbouring months muted.
+fn day(calendar: &CalendarView, date: NaiveDate) -> Element<'static, Message> {
+    let today = chrono::Local::now().date_naive();
+    let color = if date == today {
+        DockColors::accent()
+    } else if date.month() != calendar.month.month() {
+        DockColors::text_muted()
+    } else {
+        DockColors::text()
+    };
+    let style = if date == calendar.selected {
+        theme::app_icon_button_active
+    } else {
+        theme::tray_button
+    };
+
+    button(center(text(date.day().to_string()).size(12).color(color)))
+        .width(DAY_SIZE)
+        .height(DAY_SIZE)
+        .padding(0)
+        .style(style)
+        .on_press(Message::SelectDay(date))
+        .into()
+}
+
+fn agenda<'a>(state: &DockApp, calendar: &'a CalendarView) -> Element<'a, Message> {
+    let today = chrono::Local::now().date_naive();
+    let heading = if calendar.selected == today {
+        t!("calendar-today")
+    } else {
+        calendar.selected.format("%d.%m.%Y").to_string()
+    };
+    let heading = text(heading).size(14).color(DockColors::text());
+
+    let body: Element<'a, Message> = match &calendar.events {
+        None => {
+            let note = if state.connected() {
+                t!("calendar-loading")
+            } else {
+                t!("calendar-unavailable")
+            };
+            text(note).size(12).color(DockColors::text_muted()).into()
+        }
+        Some(events) if events.is_empty() => text(t!("calendar-no-events"))
+            .size(12)
+            .color(DockColors::text_muted())
+            .into(),
+        Some(events) => scrollable(
+            Column::with_children(events.iter().map(|e| event(e, calendar.selected))).spacing(8),
+        )
+        .height(Length::Fill)
+        .into(),
+    };
+
+    column![heading, body]
+        .spacing(10)
+        .width(Length::Fill)
+        .into()
+}
+
+/// One agenda line: start time (or "All day"), title and location.
+fn event(event: &CalendarEvent, date: NaiveDate) -> Element<'_, Message> {
+    let time = if event.all_day || event.start.date() != date {
+        t!("calendar-all-day")
+    } else {
+        event.start.format("%H:%M").to_string()
+    };
+    let title = if event.title.is_empty() {
+        t!("calendar-untitled")
+    } else {
+        event.title.clone()
+    };
+
+    let mut content = column![
+        row![
+            text(time).size(11).color(DockColors::accent()),
+            text(title).size(13).color(DockColors::text()),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(2);
+    if let Some(location) = &event.location {
+        content = content.push(
+            text(location.as_str())
+                .size(12)
+                .color(DockColors::text_muted()),
+        );
+    }
+    content.into()
+}
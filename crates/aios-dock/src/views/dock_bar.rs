@@ -1,26 +1,29 @@
 //! Main dock bar layout -- horizontal panel with app icons and system tray.
 
-use iced::widget::{container, row, Space};
+use aios_common::DockPosition;
+use iced::widget::{column, container, row, Row, Space};
 use iced::{Element, Length};
 
-use crate::app::{AppId, DockApp, Message};
+use crate::app::{DockApp, Message, Panel, PANEL_HEIGHT};
 use crate::theme;
-use crate::views::{app_icon, system_tray};
+use crate::views::{app_icon, calendar, notifications, quick_ask, system_tray, taskbar};
+
+/// Width of an open panel, kept at the right edge next to the tray.
+const PANEL_WIDTH: f32 = 420.0;
 
 /// Renders the full dock bar.
 ///
 /// ```text
-/// +------+------+------+------+------------+---+---+---+-------+
-/// | Chat | Web  | Term | Gear | (spacer)   |WiFi|Vol|Bat| 15:30 |
-/// +------+------+------+------+------------+---+---+---+-------+
+/// +-------+-------------+--------------+----------+--------+---------+---+---+-------+
+/// | 1 2 3 | pinned apps | running apps | (spacer) | macros | Ask ... |Vol|Bat| 15:30 |
+/// +-------+-------------+--------------+----------+--------+---------+---+---+-------+
 /// ```
+///
+/// While a panel (notifications, quick-ask answer, calendar) is open the
+/// surface is taller and the panel sits above the bar, or below it when the
+/// dock is at the top of the screen.
 pub fn view(state: &DockApp) -> Element<'_, Message> {
-    let chat_icon = app_icon::view("Chat", AppId::Chat);
-    let web_icon = app_icon::view("Web", AppId::Browser);
-    let term_icon = app_icon::view("Term", AppId::Terminal);
-    let settings_icon = app_icon::view("Gear", AppId::Settings);
-
-    let app_icons = row![chat_icon, web_icon, term_icon, settings_icon]
+    let app_icons = Row::with_children(state.pinned.iter().map(app_icon::view))
         .spacing(6)
         .align_y(iced::Alignment::Center);
 
@@ -28,15 +31,49 @@ pub fn view(state: &DockApp) -> Element<'_, Message> {
 
     let tray = system_tray::view(state);
 
-    let bar = row![app_icons, spacer, tray]
+    let workspaces = taskbar::workspaces(&state.sway);
+    let windows = taskbar::windows(&state.sway);
+
+    let macros = quick_ask::macro_buttons(state);
+    let ask = quick_ask::field(state);
+
+    let bar = row![workspaces, app_icons, windows, spacer, macros, ask, tray]
         .spacing(12)
         .padding([4, 12])
         .align_y(iced::Alignment::Center);
 
-    container(bar)
+    let bar = container(bar)
         .width(Length::Fill)
+        .height(crate::DOCK_HEIGHT as f32)
+        .center_y(crate::DOCK_HEIGHT as f32)
+        .style(theme::dock_bar);
+
+    let panel = match state.panel {
+        Some(Panel::Notifications) => notifications::panel(state),
+        Some(Panel::QuickAsk) => quick_ask::panel(state),
+        Some(Panel::Calendar) => calendar::panel(state),
+        None => return bar.into(),
+    };
+    match state.position {
+        DockPosition::Top => column![bar, panel_frame(panel)].into(),
+        DockPosition::Bottom => column![panel_frame(panel), bar].into(),
+    }
+}
+
+/// The area next to the bar while a panel is open, with the panel's card at
+/// the right edge.
+fn panel_frame(content: Element<'_, Message>) -> Element<'_, Message> {
+    let card = container(content)
+        .width(PANEL_WIDTH)
         .height(Length::Fill)
-        .center_y(Length::Fill)
+        .padding(12)
+        .style(theme::popover);
+
+    container(card)
+        .width(Length::Fill)
+        .height(PANEL_HEIGHT as f32)
+        .padding([8, 12])
+        .align_right(Length::Fill)
         .style(theme::dock_bar)
         .into()
 }
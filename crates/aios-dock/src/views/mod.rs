@@ -1,3 +1,9 @@
+pub mod agent_status;
 pub mod app_icon;
+pub mod calendar;
 pub mod dock_bar;
+pub mod notifications;
+pub mod privacy;
+pub mod quick_ask;
 pub mod system_tray;
+pub mod taskbar;
@@ -0,0 +1,99 @@
+//! Notification center: the bell in the tray and the panel it opens.
+
+use aios_common::{AgentNotification, NotificationKind, t};
+use iced::widget::{button, column, row, scrollable, text, Column, Space};
+use iced::{Element, Length};
+
+use crate::app::{DockApp, Message};
+use crate::theme::{self, DockColors};
+
+/// Tray button showing the unread count; toggles the panel.
+pub fn bell(state: &DockApp) -> Element<'static, Message> {
+    let unread = state.unread_notifications();
+    let (label, color) = if unread > 0 {
+        (
+            t!("tray-notifications-unread", count = unread),
+            DockColors::accent(),
+        )
+    } else {
+        (t!("tray-notifications"), DockColors::text_muted())
+    };
+    button(text(label).size(12).color(color))
+        .padding([2, 4])
+        .style(theme::tray_button)
+        .on_press(Message::ToggleNotifications)
+        .into()
+}
+
+/// Panel content: recent notifications, newest first.
+pub fn panel(state: &DockApp) -> Element<'_, Message> {
+    let header = row![
+        text(t!("notifications-title"))
+            .size(14)
+            .color(DockColors::text()),
+        Space::new().width(Length::Fill),
+        button(text(t!("notifications-clear")).size(12))
+            .padding([2, 6])
+            .style(theme::tray_button)
+            .on_press_maybe(
+                (!state.notifications.is_empty()).then_some(Message::ClearNotifications)
+            ),
+    ]
+    .align_y(iced::Alignment::Center);
+
+    let body: Element<'_, Message> = if state.notifications.is_empty() {
+        text(t!("notifications-empty"))
+            .size(12)
+            .color(DockColors::text_muted())
+            .into()
+    } else {
+        scrollable(
+            Column::with_children(state.notifications.iter().map(entry))
+                .spacing(8)
+                .padding([0, 8]),
+        )
+        .height(Length::Fill)
+        .into()
+    };
+
+    column![header, body].spacing(10).into()
+}
+
+fn entry(notification: &AgentNotification) -> Element<'_, Message> {
+    let heading = match notification.kind {
+        NotificationKind::TaskResult => t!("notification-task-result"),
+        NotificationKind::Download => t!("notification-download"),
+        NotificationKind::ConfirmMissed => t!("notification-confirm-missed"),
+        NotificationKind::Info => t!("notification-info"),
+    };
+    let time = notification
+        .timestamp
+        .with_timezone(&chrono::Local)
+        .format("%H:%M")
+        .to_string();
+    let heading_color = if notification.read {
+        DockColors::text_muted()
+    } else {
+        DockColors::accent()
+    };
+
+    let mut content = column![
+        row![
+            text(heading).size(11).color(heading_color),
+            Space::new().width(Length::Fill),
+            text(time).size(11).color(DockColors::text_muted()),
+        ],
+        text(notification.title.as_str())
+            .size(13)
+            .color(DockColors::text()),
+    ]
+    .spacing(2);
+    if !notification.body.is_empty() {
+        content = content.push(
+            text(notification.body.as_str())
+                .size(12)
+                .color(DockColors::text_muted()),
+        );
+    }
+    content.into()
+}
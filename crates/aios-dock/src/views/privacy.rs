@@ -0,0 +1,49 @@
+//! Privacy indicators: a colored badge for every camera, microphone,
+//! screen capture or location lookup in use by an agent tool, shown for as
+//! long as the tool runs. Hovering names the tools.
+
+use aios_common::{Sensor, t};
+use iced::widget::{container, text, tooltip, Row};
+use iced::Element;
+
+use crate::app::{DockApp, Message};
+use crate::theme::{self, DockColors};
+
+/// Badges for the sensors in use; `None` when none are.
+pub fn view(state: &DockApp) -> Option<Element<'static, Message>> {
+    let in_use = state.sensors_in_use();
+    if in_use.is_empty() {
+        return None;
+    }
+    let badges = in_use
+        .into_iter()
+        .map(|(sensor, tools)| badge(sensor, &tools.join(", ")));
+    Some(
+        Row::with_children(badges)
+            .spacing(4)
+            .align_y(iced::Alignment::Center)
+            .into(),
+    )
+}
+
+fn badge(sensor: Sensor, tools: &str) -> Element<'static, Message> {
+    let (label, color) = match sensor {
+        Sensor::Camera => (t!("privacy-camera"), DockColors::status_critical()),
+        Sensor::Microphone => (t!("privacy-microphone"), DockColors::status_warning()),
+        Sensor::Screen => (t!("privacy-screen"), DockColors::accent()),
+        Sensor::Location => (t!("privacy-location"), DockColors::status_warning()),
+    };
+
+    let pill = container(text(label).size(11).color(DockColors::dock_bg()))
+        .padding([1, 6])
+        .style(theme::privacy_badge(color));
+
+    tooltip(
+        pill,
+        container(text(t!("privacy-in-use", tools = tools.to_owned())).size(12))
+            .padding(6)
+            .style(theme::popover),
+        tooltip::Position::Top,
+    )
+    .into()
+}
@@ -0,0 +1,89 @@
+//! Quick-ask: a field in the bar for one-shot questions, buttons for the
+//! macros that asked for one, and the panel that shows the answer.
+
+use aios_common::t;
+use iced::widget::{
+    button, column, container, row, scrollable, text, text_input, tooltip, Row, Space,
+};
+use iced::{Element, Length};
+
+use crate::app::{DockApp, Message};
+use crate::theme::{self, DockColors};
+
+/// Width of the field in the bar.
+const FIELD_WIDTH: f32 = 220.0;
+
+/// The input in the bar. Submitting is disabled while the previous question
+/// is still being answered.
+pub fn field(state: &DockApp) -> Element<'_, Message> {
+    text_input(&t!("quick-ask-placeholder"), &state.ask_input)
+        .on_input(Message::AskInputChanged)
+        .on_submit_maybe((!state.ask_pending()).then_some(Message::AskSubmitted))
+        .size(12)
+        .padding([4, 8])
+        .width(FIELD_WIDTH)
+        .into()
+}
+
+/// A button per dock macro, run like a quick question. Disabled while a
+/// question is being answered.
+pub fn macro_buttons(state: &DockApp) -> Element<'_, Message> {
+    let enabled = state.connected() && !state.ask_pending();
+    Row::with_children(state.macros.iter().map(|(name, config)| {
+        let run = button(text(name.as_str()).size(12))
+            .padding([2, 8])
+            .style(theme::tray_button)
+            .on_press_maybe(enabled.then(|| Message::RunMacro(name.clone())));
+        if config.description.is_empty() {
+            run.into()
+        } else {
+            tooltip(
+                run,
+                container(text(config.description.as_str()).size(12))
+                    .padding(6)
+                    .style(theme::popover),
+                tooltip::Position::Top,
+            )
+            .into()
+        }
+    }))
+    .spacing(4)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Panel content: the question, then the answer or a waiting line.
+pub fn panel(state: &DockApp) -> Element<'_, Message> {
+    let Some(ask) = &state.ask else {
+        return Space::new().into();
+    };
+
+    let header = row![
+        text(ask.question.as_str())
+            .size(13)
+            .color(DockColors::text_muted())
+            .width(Length::Fill),
+        button(text(t!("quick-ask-open-in-chat")).size(12))
+            .padding([2, 6])
+            .style(theme::tray_button)
+            .on_press(Message::OpenAskInChat),
+        button(text(t!("quick-ask-close")).size(12))
+            .padding([2, 6])
+            .style(theme::tray_button)
+            .on_press(Message::ClosePanel),
+    ]
+    .spacing(6)
+    .align_y(iced::Alignment::Center);
+
+    let body: Element<'_, Message> = match &ask.reply {
+        Some(reply) => scrollable(text(reply.as_str()).size(13).color(DockColors::text()))
+            .height(Length::Fill)
+            .into(),
+        None => text(t!("quick-ask-waiting"))
+            .size(12)
+            .color(DockColors::text_muted())
+            .into(),
+    };
+
+    column![header, body].spacing(10).into()
+}
@@ -1,53 +1,241 @@
-//! System tray area: clock, Wi-Fi status, volume, battery.
+//! System tray area: agent indicator, clock, network status, volume,
+//! brightness, GPU load, keyboard layout, battery, notifications.
 
-use iced::widget::{row, text};
+use aios_common::{
+    BatteryStatus, ChargeState, GpuInfo, NetworkStatus, SystemStatus, VolumeStatus, t,
+};
+use iced::widget::{button, container, mouse_area, row, slider, text, Row};
 use iced::Element;
 
-use crate::app::{DockApp, Message};
-use crate::theme::DockColors;
+use crate::app::{self, DockApp, Message, QuickControl};
+use crate::sway::Keyboard;
+use crate::theme::{self, DockColors};
+use crate::views::{agent_status, calendar, notifications, privacy};
 
 /// Renders the system tray section of the dock (right side).
 ///
-/// Layout: `WiFi | Vol | Bat | HH:MM`
+/// Layout: `Privacy | Agent | Pause | Net | Vol | Bri | GPU | Kbd | Bat |
+/// Bell | HH:MM`. Privacy badges show while agent tools use a camera,
+/// microphone or the screen; the pause button stops all tool execution. The
+/// status items come from the agent and are left out while it is
+/// unreachable, the GPU meter also while `[ui] gpu_meter` is off; the
+/// keyboard layout comes from sway. Volume and brightness open a slider
+/// popover next to them when clicked, the layout switches on click and lists
+/// all layouts on right-click; the bell opens the notification panel and the
+/// clock the calendar.
 pub fn view(state: &DockApp) -> Element<'_, Message> {
-    let wifi_color = if state.wifi_connected {
-        DockColors::STATUS_OK
+    let mut items: Vec<_> = privacy::view(state).into_iter().collect();
+    items.push(agent_status::view(state));
+    items.extend(agent_status::pause_button(state));
+    if let Some(status) = &state.status {
+        items.extend(status_items(state, status));
+    }
+    if let Some(keyboard) = &state.sway.keyboard {
+        items.push(keyboard_layout(keyboard));
+        if state.layout_menu {
+            items.push(layout_menu(keyboard));
+        }
+    }
+    if let Some(battery_status) = state.status.as_ref().and_then(|s| s.battery) {
+        items.push(battery(battery_status));
+    }
+    let mut items = Row::with_children(items)
+        .spacing(12)
+        .align_y(iced::Alignment::Center);
+
+    items = items
+        .push(notifications::bell(state))
+        .push(calendar::clock(state));
+
+    items.into()
+}
+
+fn status_items(state: &DockApp, status: &SystemStatus) -> Vec<Element<'static, Message>> {
+    let mut items = vec![network(status.network)];
+
+    if let Some(volume_status) = status.volume {
+        items.push(volume(volume_status));
+        if state.popover == Some(QuickControl::Volume) {
+            items.push(popover(state, QuickControl::Volume, Some(volume_status.muted)));
+        }
+    }
+
+    if let Some(percent) = status.brightness {
+        items.push(tray_button(
+            t!("tray-brightness", percent = percent),
+            DockColors::text_muted(),
+            QuickControl::Brightness,
+        ));
+        if state.popover == Some(QuickControl::Brightness) {
+            items.push(popover(state, QuickControl::Brightness, None));
+        }
+    }
+
+    if let Some(info) = &status.gpu {
+        items.push(gpu(info));
+    }
+
+    items
+}
+
+/// Short name of the active layout; click for the next layout, right-click
+/// for the menu.
+fn keyboard_layout(keyboard: &Keyboard) -> Element<'static, Message> {
+    let label = keyboard
+        .active_name()
+        .map(app::layout_to_short)
+        .unwrap_or_default();
+    let indicator = button(text(label).size(12).color(DockColors::text()))
+        .padding([2, 4])
+        .style(theme::tray_button)
+        .on_press(Message::CycleLayout);
+    mouse_area(indicator)
+        .on_right_press(Message::ToggleLayoutMenu)
+        .into()
+}
+
+/// Inline menu of the configured layouts, the active one highlighted.
+fn layout_menu(keyboard: &Keyboard) -> Element<'static, Message> {
+    let entries = keyboard.layouts.iter().enumerate().map(|(index, name)| {
+        let color = if index == keyboard.active {
+            DockColors::accent()
+        } else {
+            DockColors::text()
+        };
+        button(text(name.clone()).size(12).color(color))
+            .padding([2, 6])
+            .style(theme::tray_button)
+            .on_press(Message::SelectLayout(index))
+            .into()
+    });
+
+    container(
+        Row::with_children(entries)
+            .spacing(4)
+            .align_y(iced::Alignment::Center),
+    )
+    .padding([4, 10])
+    .style(theme::popover)
+    .into()
+}
+
+fn network(network: NetworkStatus) -> Element<'static, Message> {
+    let (label, color) = if !network.connected {
+        (t!("tray-offline"), DockColors::status_off())
+    } else if network.wireless {
+        (t!("tray-wifi"), DockColors::status_ok())
     } else {
-        DockColors::STATUS_OFF
+        (t!("tray-ethernet"), DockColors::status_ok())
+    };
+    text(label).size(12).color(color).into()
+}
+
+/// Charge with an arrow while charging or the remaining runtime while
+/// discharging; warning colors when low.
+fn battery(battery: BatteryStatus) -> Element<'static, Message> {
+    let percent = battery.percent;
+    let label = match (battery.state, battery.minutes_to_empty) {
+        (ChargeState::Charging, _) => t!("tray-battery-charging", percent = percent),
+        (ChargeState::Discharging, Some(minutes)) => t!(
+            "tray-battery-remaining",
+            percent = percent,
+            time = format!("{}:{:02}", minutes / 60, minutes % 60)
+        ),
+        _ => t!("tray-battery", percent = percent),
     };
 
-    let wifi_label = if state.wifi_connected {
-        "WiFi"
+    let color = if battery.state == ChargeState::Charging {
+        DockColors::status_ok()
+    } else if battery.is_low() && percent <= 10 {
+        DockColors::status_critical()
+    } else if battery.is_low() {
+        DockColors::status_warning()
     } else {
-        "WiFi Off"
+        DockColors::text()
+    };
+
+    text(label).size(12).color(color).into()
+}
+
+/// Load of the busiest GPU, with video memory when the driver reports it;
+/// warning colors when either is nearly exhausted.
+fn gpu(info: &GpuInfo) -> Element<'static, Message> {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let percent = info.utilization.unwrap_or_default();
+    let label = match (info.memory_used, info.memory_total) {
+        (Some(used), Some(total)) if total > 0 => t!(
+            "tray-gpu-memory",
+            percent = percent,
+            used = format!("{:.1}", used as f64 / GIB),
+            total = format!("{:.0}", total as f64 / GIB)
+        ),
+        _ => t!("tray-gpu", percent = percent),
     };
 
-    let wifi = text(wifi_label).size(12).color(wifi_color);
+    let color = match info.load() {
+        97.. => DockColors::status_critical(),
+        90.. => DockColors::status_warning(),
+        _ => DockColors::text_muted(),
+    };
+
+    text(label).size(12).color(color).into()
+}
 
-    let volume = text(format!("Vol {}%", state.volume_percent))
-        .size(12)
-        .color(DockColors::TEXT_MUTED);
+fn volume(volume: VolumeStatus) -> Element<'static, Message> {
+    let label = if volume.muted {
+        t!("tray-volume-muted")
+    } else {
+        t!("tray-volume", percent = volume.percent)
+    };
+    tray_button(label, DockColors::text_muted(), QuickControl::Volume)
+}
 
-    let kbd = text(state.kbd_layout.as_str().to_owned())
-        .size(12)
-        .color(DockColors::TEXT);
+/// A tray label that toggles the popover of `control`.
+fn tray_button(
+    label: String,
+    color: iced::Color,
+    control: QuickControl,
+) -> Element<'static, Message> {
+    button(text(label).size(12).color(color))
+        .padding([2, 4])
+        .style(theme::tray_button)
+        .on_press(Message::TogglePopover(control))
+        .into()
+}
 
-    let mut items = row![wifi, volume, kbd].spacing(12).align_y(iced::Alignment::Center);
+/// Inline slider for `control`, with a mute toggle for the volume. The dock
+/// is a single bar, so the "popover" unfolds next to its tray item.
+fn popover(
+    state: &DockApp,
+    control: QuickControl,
+    muted: Option<bool>,
+) -> Element<'static, Message> {
+    let level = state.level(control).unwrap_or_default();
+    let mut content = row![
+        slider(0..=100, level, Message::LevelChanged)
+            .on_release(Message::LevelReleased)
+            .width(120),
+        text(format!("{level}%")).size(12).color(DockColors::text()),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
 
-    if let Some(bat) = state.battery_percent {
-        let bat_color = if bat > 20 {
-            DockColors::TEXT
+    if let Some(muted) = muted {
+        let label = if muted {
+            t!("tray-unmute")
         } else {
-            DockColors::STATUS_OFF
+            t!("tray-mute")
         };
-        items = items.push(text(format!("Bat {bat}%")).size(12).color(bat_color));
+        content = content.push(
+            button(text(label).size(12))
+                .padding([2, 6])
+                .style(theme::tray_button)
+                .on_press(Message::ToggleMute),
+        );
     }
 
-    let clock = text(state.clock.as_str().to_owned())
-        .size(14)
-        .color(DockColors::TEXT);
-
-    items = items.push(clock);
-
-    items.into()
+    container(content)
+        .padding([4, 10])
+        .style(theme::popover)
+        .into()
 }
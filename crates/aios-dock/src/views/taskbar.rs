@@ -0,0 +1,86 @@
+//! Taskbar: workspace switcher and running applications.
+
+use iced::widget::{button, center, container, text, tooltip, Row};
+use iced::{Element, Length};
+
+use crate::app::Message;
+use crate::sway::{Snapshot, Window, Workspace};
+use crate::theme::{self, DockColors};
+
+/// Labels of running apps are cut to this many characters.
+const APP_LABEL_CHARS: usize = 6;
+
+/// Workspace buttons, one per sway workspace.
+pub fn workspaces(snapshot: &Snapshot) -> Element<'_, Message> {
+    Row::with_children(snapshot.workspaces.iter().map(workspace_button))
+        .spacing(4)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+/// A button per open window; the focused one is highlighted and the title
+/// shows on hover.
+pub fn windows(snapshot: &Snapshot) -> Element<'_, Message> {
+    Row::with_children(snapshot.windows.iter().map(window_button))
+        .spacing(6)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+fn workspace_button(workspace: &Workspace) -> Element<'_, Message> {
+    let color = if workspace.urgent {
+        DockColors::status_warning()
+    } else {
+        DockColors::text()
+    };
+    let style = if workspace.focused {
+        theme::app_icon_button_active
+    } else {
+        theme::app_icon_button
+    };
+
+    button(center(text(workspace.name.as_str()).size(12).color(color)))
+        .width(28.0)
+        .height(28.0)
+        .padding(0)
+        .style(style)
+        .on_press(Message::FocusWorkspace(workspace.name.clone()))
+        .into()
+}
+
+fn window_button(window: &Window) -> Element<'_, Message> {
+    let style = if window.focused {
+        theme::app_icon_button_active
+    } else {
+        theme::app_icon_button
+    };
+
+    let icon = button(center(text(app_label(&window.app)).size(12)).width(Length::Fill))
+        .width(56.0)
+        .height(40.0)
+        .padding(4)
+        .style(style)
+        .on_press(Message::FocusWindow(window.id));
+
+    tooltip(
+        icon,
+        container(text(window.title.as_str()).size(12))
+            .padding(6)
+            .style(theme::popover),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Short label from an app id: `org.mozilla.firefox` -> `Firefo`.
+fn app_label(app: &str) -> String {
+    let name = app.rsplit('.').next().unwrap_or(app);
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.take(APP_LABEL_CHARS - 1))
+            .collect(),
+        None => String::new(),
+    }
+}
@@ -1,19 +1,186 @@
 //! Tool execution trait and context.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use aios_common::{Sensor, ToolDefinition, ToolErrorKind, ToolResult, TrustLevel, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::pty::ShellSessions;
+
+/// Variables of the agent's own environment that commands inherit. Anything
+/// else, such as provider API keys, stays out of their reach.
+pub const ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "LANG",
+    "LANGUAGE",
+    "LC_ALL",
+    "TERM",
+    "TZ",
+    "XDG_RUNTIME_DIR",
+    "WAYLAND_DISPLAY",
+    "DISPLAY",
+    "DBUS_SESSION_BUS_ADDRESS",
+];
+
+/// Variables of [`ENV_ALLOWLIST`] that lead into the user session. Sandboxed
+/// commands go without them: the session bus would run anything they ask
+/// outside the sandbox.
+pub const SESSION_VARS: &[&str] = &["XDG_RUNTIME_DIR", "DBUS_SESSION_BUS_ADDRESS"];
+
+/// Working directory and environment of one conversation, so commands run
+/// one after another behave like a shell session.
+#[derive(Debug, Clone, Default)]
+pub struct ExecContext {
+    /// Directory commands start in; the home directory until changed.
+    pub cwd: Option<PathBuf>,
+    /// Variables set for commands on top of [`ENV_ALLOWLIST`].
+    pub env: BTreeMap<String, String>,
+    /// Interactive shells opened with `shell_session`, killed along with
+    /// the conversation.
+    pub shells: ShellSessions,
+}
+
+impl ExecContext {
+    /// The directory commands start in.
+    pub fn cwd(&self) -> PathBuf {
+        self.cwd
+            .clone()
+            .or_else(|| std::env::var_os("HOME").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("/"))
+    }
+
+    /// `path` relative to the working directory, with `~` expanded.
+    pub fn resolve(&self, path: &str) -> PathBuf {
+        let home = || std::env::var_os("HOME").map_or_else(|| PathBuf::from("/"), PathBuf::from);
+        if path == "~" {
+            return home();
+        }
+        if let Some(rest) = path.strip_prefix("~/") {
+            return home().join(rest);
+        }
+        self.cwd().join(Path::new(path))
+    }
+
+    /// The environment commands run with: the allowlisted variables of the
+    /// agent's environment, then the conversation's own; none of
+    /// [`SESSION_VARS`] when `sandboxed`.
+    pub fn vars(&self, sandboxed: bool) -> Vec<(String, String)> {
+        let inherited = ENV_ALLOWLIST
+            .iter()
+            .filter_map(|name| Some(((*name).to_owned(), std::env::var(name).ok()?)));
+        inherited
+            .filter(|(name, _)| !self.env.contains_key(name))
+            .chain(self.env.clone())
+            .filter(|(name, _)| !sandboxed || !SESSION_VARS.contains(&name.as_str()))
+            .collect()
+    }
+}
+
+/// A tool failure with a known cause, for errors returned through the
+/// `Result` of [`Tool::execute`] rather than as an error [`ToolResult`].
+#[derive(Debug)]
+pub struct ToolError {
+    pub kind: ToolErrorKind,
+    pub message: String,
+}
+
+impl ToolError {
+    /// A missing or malformed argument.
+    pub fn invalid_arguments(message: impl Into<String>) -> anyhow::Error {
+        Self {
+            kind: ToolErrorKind::InvalidArguments,
+            message: message.into(),
+        }
+        .into()
+    }
+
+    /// The cause of a failure to run an external program: a missing
+    /// program makes the tool unavailable rather than its target not found.
+    pub fn command_kind(error: &std::io::Error) -> ToolErrorKind {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => ToolErrorKind::Unavailable,
+            _ => ToolErrorKind::from_io(error),
+        }
+    }
+
+    /// The cause of an error returned by [`Tool::execute`]: a [`ToolError`],
+    /// I/O or HTTP error anywhere in the chain, otherwise
+    /// [`ToolErrorKind::Failed`].
+    pub fn kind_of(error: &anyhow::Error) -> ToolErrorKind {
+        error
+            .chain()
+            .find_map(|cause| {
+                if let Some(e) = cause.downcast_ref::<ToolError>() {
+                    Some(e.kind)
+                } else if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+                    Some(ToolErrorKind::from_io(e))
+                } else {
+                    cause.downcast_ref::<reqwest::Error>().map(|e| {
+                        if e.is_timeout() {
+                            ToolErrorKind::Timeout
+                        } else {
+                            ToolErrorKind::Network
+                        }
+                    })
+                }
+            })
+            .unwrap_or(ToolErrorKind::Failed)
+    }
+}
+
+impl std::fmt::Display for ToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ToolError {}
+
+/// Where a running tool sends its output as it comes in, for live display.
+pub type ProgressSender = tokio::sync::mpsc::UnboundedSender<String>;
+
 /// Context passed to every tool invocation.
-///
-/// Carries the call identifier and will be extended in Step 3.3
-/// with an audit logger and confirmation channel.
 pub struct ToolContext {
     /// Unique identifier of the tool call this execution belongs to.
     pub call_id: Uuid,
-    // TODO: Step 3.3 - audit logger, confirm channel
+    /// Execution context of the conversation the call belongs to; tools
+    /// may change it for the calls that follow.
+    pub exec: Arc<Mutex<ExecContext>>,
+    /// Receives output while the call runs; `None` when nobody watches.
+    pub progress: Option<ProgressSender>,
+}
+
+impl ToolContext {
+    /// A copy of the conversation's current execution context.
+    pub fn exec(&self) -> ExecContext {
+        self.exec
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Change the conversation's execution context.
+    pub fn update_exec(&self, update: impl FnOnce(&mut ExecContext)) {
+        let mut exec = self.exec.lock().unwrap_or_else(PoisonError::into_inner);
+        update(&mut exec);
+    }
+
+    /// Report output of the call before it finishes. The final result
+    /// should still contain all of it.
+    pub fn progress(&self, output: impl Into<String>) {
+        if let Some(progress) = &self.progress {
+            let _ = progress.send(output.into());
+        }
+    }
 }
 
 /// Trait that all tools must implement.
@@ -29,10 +196,31 @@ pub trait Tool: Send + Sync {
     /// Returns the confirmation level required before this tool can execute.
     fn trust_requirement(&self) -> TrustRequirement;
 
+    /// A stricter level for one particular call, e.g. a permanent delete
+    /// by a tool that otherwise moves files to the trash. It only ever
+    /// raises the tool's level, including one the user lowered.
+    fn call_trust(&self, _args: &Value) -> Option<TrustRequirement> {
+        None
+    }
+
+    /// Camera, microphone, screen or location access the tool needs while
+    /// it runs.
+    fn sensors(&self) -> &'static [Sensor] {
+        &[]
+    }
+
+    /// Provenance of the tool's output: web pages and third-party servers
+    /// are untrusted, so the agent wraps their output and confirms actions
+    /// that follow from it more strictly.
+    fn output_trust(&self) -> TrustLevel {
+        TrustLevel::System
+    }
+
     /// Execute the tool with the given arguments.
     ///
     /// Implementations must **never panic**. All errors are returned as
-    /// [`ToolResult`] with `is_error: true` or via the `Result` wrapper
-    /// for truly unrecoverable situations.
+    /// [`ToolResult`] with `is_error: true` and an `error_kind`, or via the
+    /// `Result` wrapper for bad arguments ([`ToolError::invalid_arguments`])
+    /// and truly unrecoverable situations.
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult>;
 }
@@ -0,0 +1,319 @@
+//! Tools of external MCP servers, from `[mcp_servers]`.
+//!
+//! [`McpClient`] speaks the MCP JSON-RPC protocol with a server started as a
+//! child process (newline-delimited messages over stdin/stdout). Every tool
+//! the server lists is wrapped in an [`ExternalTool`] named
+//! `<server>__<tool>`, so it can sit in the [`ToolRegistry`] next to the
+//! built-in tools without clashing with them.
+//!
+//! [`ToolRegistry`]: crate::registry::ToolRegistry
+
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use aios_common::{
+    McpServerConfig, McpTransport, ToolDefinition, ToolErrorKind, ToolResult, TrustLevel,
+    TrustRequirement,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::Mutex;
+
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// MCP revision this client implements.
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Time a server gets to start and answer `initialize`.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Time a single tool call may take.
+const CALL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Separates the server name from the tool name in registry names.
+const NAME_SEPARATOR: &str = "__";
+
+/// Registry name of `tool` from `server`.
+pub fn tool_name(server: &str, tool: &str) -> String {
+    format!("{server}{NAME_SEPARATOR}{tool}")
+}
+
+/// A tool as listed by a server.
+#[derive(Debug, Clone)]
+pub struct RemoteTool {
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the arguments.
+    pub input_schema: Value,
+}
+
+struct Pipes {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+/// A running MCP server. The process is killed when the client is dropped.
+pub struct McpClient {
+    name: String,
+    pipes: Mutex<Pipes>,
+    next_id: AtomicU64,
+    _child: Child,
+}
+
+impl McpClient {
+    /// Start the server described by `config` and complete the MCP
+    /// handshake.
+    pub async fn connect(name: &str, config: &McpServerConfig) -> Result<Self> {
+        if config.transport == McpTransport::Sse {
+            bail!("the SSE transport is not supported yet");
+        }
+        if config.command.trim().is_empty() {
+            bail!("no command configured");
+        }
+
+        let mut child = tokio::process::Command::new(&config.command)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("failed to start {}", config.command))?;
+        let stdin = child.stdin.take().context("no stdin pipe")?;
+        let stdout = child.stdout.take().context("no stdout pipe")?;
+
+        let client = Self {
+            name: name.to_owned(),
+            pipes: Mutex::new(Pipes {
+                stdin,
+                stdout: BufReader::new(stdout),
+            }),
+            next_id: AtomicU64::new(1),
+            _child: child,
+        };
+
+        let params = json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "aios", "version": env!("CARGO_PKG_VERSION") },
+        });
+        tokio::time::timeout(CONNECT_TIMEOUT, client.request("initialize", params))
+            .await
+            .map_err(|_| anyhow!("no answer to initialize"))??;
+        client
+            .send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await?;
+
+        Ok(client)
+    }
+
+    /// Every tool the server offers, following pagination.
+    pub async fn list_tools(&self) -> Result<Vec<RemoteTool>> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let result = tokio::time::timeout(CONNECT_TIMEOUT, self.request("tools/list", params))
+                .await
+                .map_err(|_| anyhow!("no answer to tools/list"))??;
+
+            for tool in result["tools"].as_array().into_iter().flatten() {
+                let Some(name) = tool["name"].as_str() else {
+                    continue;
+                };
+                tools.push(RemoteTool {
+                    name: name.to_owned(),
+                    description: tool["description"].as_str().unwrap_or_default().to_owned(),
+                    input_schema: tool
+                        .get("inputSchema")
+                        .cloned()
+                        .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+                });
+            }
+
+            cursor = result["nextCursor"].as_str().map(str::to_owned);
+            if cursor.is_none() {
+                return Ok(tools);
+            }
+        }
+    }
+
+    /// Call `tool` and return its text output and whether the server
+    /// reported an error.
+    pub async fn call_tool(&self, tool: &str, args: Value) -> Result<(String, bool)> {
+        let params = json!({ "name": tool, "arguments": args });
+        let result = tokio::time::timeout(CALL_TIMEOUT, self.request("tools/call", params))
+            .await
+            .map_err(|_| ToolError {
+                kind: ToolErrorKind::Timeout,
+                message: format!("{tool} on {} timed out", self.name),
+            })??;
+
+        let output = result["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|item| match item["type"].as_str() {
+                Some("text") => item["text"].as_str().unwrap_or_default().to_owned(),
+                Some(kind) => format!("[{kind} content]"),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let is_error = result["isError"].as_bool().unwrap_or(false);
+        Ok((output, is_error))
+    }
+
+    /// Send a request and wait for its response. Requests from the server
+    /// that arrive meanwhile are answered; notifications are skipped.
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut pipes = self.pipes.lock().await;
+        write_message(
+            &mut pipes.stdin,
+            &json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }),
+        )
+        .await?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if pipes.stdout.read_line(&mut line).await? == 0 {
+                bail!("{} exited", self.name);
+            }
+            let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                // Servers sometimes log to stdout.
+                continue;
+            };
+
+            if let Some(server_method) = message["method"].as_str() {
+                if let Some(request_id) = message.get("id") {
+                    let reply = server_request_reply(request_id, server_method);
+                    write_message(&mut pipes.stdin, &reply).await?;
+                }
+                continue;
+            }
+            if message["id"].as_u64() != Some(id) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                let text = error["message"].as_str().unwrap_or("unknown error");
+                bail!("{method} failed: {text}");
+            }
+            return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+        }
+    }
+
+    /// Send a message that has no response.
+    async fn send(&self, message: &Value) -> Result<()> {
+        let mut pipes = self.pipes.lock().await;
+        write_message(&mut pipes.stdin, message).await
+    }
+}
+
+async fn write_message(stdin: &mut ChildStdin, message: &Value) -> Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    stdin.write_all(&line).await?;
+    stdin.flush().await?;
+    Ok(())
+}
+
+/// Answer to a request the server sent us: `ping` is acknowledged, anything
+/// else (sampling, roots) is not offered by this client.
+fn server_request_reply(id: &Value, method: &str) -> Value {
+    if method == "ping" {
+        json!({ "jsonrpc": "2.0", "id": id, "result": {} })
+    } else {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": format!("{method} is not supported") },
+        })
+    }
+}
+
+/// A server's tool in the registry.
+pub struct ExternalTool {
+    client: Arc<McpClient>,
+    remote: RemoteTool,
+    trust: TrustRequirement,
+}
+
+#[async_trait]
+impl Tool for ExternalTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: tool_name(&self.client.name, &self.remote.name),
+            description: format!("[{}] {}", self.client.name, self.remote.description),
+            parameters: self.remote.input_schema.clone(),
+            trust_requirement: self.trust,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        self.trust
+    }
+
+    /// Third-party servers may relay anything, web pages included.
+    fn output_trust(&self) -> TrustLevel {
+        TrustLevel::WebContent
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let (output, error_kind) = match self.client.call_tool(&self.remote.name, args).await {
+            Ok((output, is_error)) => (output, is_error.then_some(ToolErrorKind::Failed)),
+            Err(e) => (format!("{e:#}"), Some(ToolError::kind_of(&e))),
+        };
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: error_kind.is_some(),
+            error_kind,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+/// Connect to `name` and list its tools; used to test a server before
+/// enabling it.
+pub async fn probe(name: &str, config: &McpServerConfig) -> Result<Vec<RemoteTool>> {
+    McpClient::connect(name, config).await?.list_tools().await
+}
+
+/// Start every enabled server and wrap its tools. Servers that fail are
+/// logged and left out.
+pub async fn connect_all(servers: &BTreeMap<String, McpServerConfig>) -> Vec<Box<dyn Tool>> {
+    let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+    for (name, config) in servers.iter().filter(|(_, config)| config.enabled) {
+        let connected = match McpClient::connect(name, config).await {
+            Ok(client) => client.list_tools().await.map(|remote| (client, remote)),
+            Err(e) => Err(e),
+        };
+        match connected {
+            Ok((client, remote)) => {
+                tracing::info!(server = %name, tools = remote.len(), "MCP server connected");
+                let client = Arc::new(client);
+                for remote in remote {
+                    tools.push(Box::new(ExternalTool {
+                        client: Arc::clone(&client),
+                        remote,
+                        trust: config.trust,
+                    }));
+                }
+            }
+            Err(e) => tracing::warn!(server = %name, "MCP server unavailable: {e:#}"),
+        }
+    }
+    tools
+}
@@ -2,9 +2,17 @@
 //!
 //! Provides the [`Tool`](executor::Tool) trait, [`ToolRegistry`](registry::ToolRegistry),
 //! and a collection of built-in tools for file operations, system management,
-//! and device control.
+//! and device control, plus the tools of external MCP servers. Shell
+//! commands run in a [`sandbox`], interactive shells on a [`pty`]; file
+//! changes are recorded in the [`undo`] journal, and deleted files go to the
+//! [`trash`].
 
 pub mod chrome_mcp;
 pub mod executor;
+pub mod external;
+pub mod pty;
 pub mod registry;
+pub mod sandbox;
 pub mod tools;
+pub mod trash;
+pub mod undo;
@@ -0,0 +1,246 @@
+//! Interactive shells on a pseudo-terminal, kept alive between tool calls.
+//!
+//! A conversation's shells live in its [`ExecContext`](crate::executor::ExecContext)
+//! and are killed when the conversation goes away. Output is collected by a
+//! reader thread and handed out in pieces, with terminal escape sequences
+//! removed.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tokio::process::{Child, Command};
+
+/// Output kept unread before the oldest is dropped (1 MiB).
+const MAX_BUFFERED: usize = 1024 * 1024;
+
+/// Output is complete once the shell has been quiet this long.
+const QUIET: Duration = Duration::from_millis(300);
+
+/// Size the terminal reports to programs.
+const ROWS: u16 = 40;
+const COLS: u16 = 120;
+
+/// A shell running on a pseudo-terminal.
+pub struct PtyShell {
+    child: Child,
+    writer: File,
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+impl PtyShell {
+    /// Start `cmd` with a new pseudo-terminal as its controlling terminal
+    /// and stdin, stdout and stderr.
+    pub fn spawn(mut cmd: Command) -> io::Result<Self> {
+        let (mut master, mut slave) = (-1, -1);
+        let size = libc::winsize {
+            ws_row: ROWS,
+            ws_col: COLS,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        // SAFETY: the out-pointers are valid for writes, and on success
+        // both descriptors are fresh and owned by nobody else.
+        let (master, slave) = unsafe {
+            let null = std::ptr::null_mut();
+            if libc::openpty(&mut master, &mut slave, null, std::ptr::null(), &size) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            (OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave))
+        };
+        // Keep both ends out of other children the agent starts meanwhile:
+        // a stray copy of the slave would keep the reader from ever seeing
+        // EIO after the shell exits.
+        for fd in [&master, &slave] {
+            // SAFETY: plain syscall on a descriptor we own.
+            if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        cmd.stdin(slave.try_clone()?)
+            .stdout(slave.try_clone()?)
+            .stderr(slave)
+            .kill_on_drop(true);
+        // SAFETY: only async-signal-safe calls between fork and exec.
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let child = cmd.spawn()?;
+        // Drop our copies of the terminal's other end, so reads fail once
+        // the shell exits.
+        drop(cmd);
+
+        let writer = File::from(master.try_clone()?);
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let buffer = Arc::clone(&output);
+        let mut reader = File::from(master);
+        std::thread::spawn(move || {
+            let mut chunk = [0; 4096];
+            // Ends with EIO once the shell and its children are gone.
+            while let Ok(n @ 1..) = reader.read(&mut chunk) {
+                let mut buffer = buffer.lock().unwrap_or_else(PoisonError::into_inner);
+                buffer.extend_from_slice(&chunk[..n]);
+                let excess = buffer.len().saturating_sub(MAX_BUFFERED);
+                buffer.drain(..excess);
+            }
+        });
+
+        Ok(Self {
+            child,
+            writer,
+            output,
+        })
+    }
+
+    /// Type `input` into the terminal.
+    pub fn send(&mut self, input: &str) -> io::Result<()> {
+        self.writer.write_all(input.as_bytes())?;
+        self.writer.flush()
+    }
+
+    /// The shell is still running.
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// A handle on the unread output, for waiting without holding the
+    /// shell.
+    pub fn output(&self) -> PtyOutput {
+        PtyOutput(Arc::clone(&self.output))
+    }
+}
+
+impl Drop for PtyShell {
+    fn drop(&mut self) {
+        // The shell leads its own session: hang up on everything it started,
+        // not only on the shell itself.
+        if let Some(pid) = self.child.id().and_then(|pid| i32::try_from(pid).ok()) {
+            // SAFETY: plain syscall on a process group we created.
+            unsafe {
+                libc::kill(-pid, libc::SIGHUP);
+            }
+        }
+    }
+}
+
+/// Unread output of a [`PtyShell`].
+#[derive(Clone)]
+pub struct PtyOutput(Arc<Mutex<Vec<u8>>>);
+
+impl PtyOutput {
+    fn len(&self) -> usize {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Wait until output has come in and gone quiet, or `wait` is over, then
+    /// take everything unread as text.
+    pub async fn collect(&self, wait: Duration) -> String {
+        let deadline = Instant::now() + wait;
+        let mut last_len = self.len();
+        let mut last_change = Instant::now();
+        while Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let len = self.len();
+            if len != last_len {
+                (last_len, last_change) = (len, Instant::now());
+            } else if len > 0 && last_change.elapsed() >= QUIET {
+                break;
+            }
+        }
+        let bytes = std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner));
+        strip_escapes(&String::from_utf8_lossy(&bytes))
+    }
+}
+
+/// The interactive shells of one conversation, by name. Clones share them;
+/// the shells are killed when the last clone is dropped.
+#[derive(Clone, Default)]
+pub struct ShellSessions(Arc<Mutex<HashMap<String, PtyShell>>>);
+
+impl ShellSessions {
+    /// Run `f` on the shells.
+    pub fn with<T>(&self, f: impl FnOnce(&mut HashMap<String, PtyShell>) -> T) -> T {
+        f(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl std::fmt::Debug for ShellSessions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<String> = self.with(|shells| shells.keys().cloned().collect());
+        f.debug_tuple("ShellSessions").field(&names).finish()
+    }
+}
+
+/// Terminal output as plain text: escape sequences removed, line endings
+/// normalized, backspaces applied.
+pub fn strip_escapes(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.next() {
+                // CSI: parameters, then a final byte in @..~.
+                Some('[') => {
+                    chars.by_ref().find(|c| ('@'..='~').contains(c));
+                }
+                // OSC: up to BEL or ESC \.
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\u{7}' {
+                            break;
+                        }
+                        if c == '\u{1b}' {
+                            chars.next_if_eq(&'\\');
+                            break;
+                        }
+                    }
+                }
+                // Two-character sequences, e.g. ESC = or ESC (B.
+                Some('(' | ')') => {
+                    chars.next();
+                }
+                _ => {}
+            },
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    out.push('\n');
+                }
+            }
+            '\u{8}' => {
+                out.pop();
+            }
+            '\u{7}' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_csi_osc_and_charset_sequences() {
+        let text = "\u{1b}]0;user@host: ~\u{7}\u{1b}[01;32muser\u{1b}[0m:~$ ls\r\n\
+                    \u{1b}(Ba.txt\u{1b}]2;title\u{1b}\\ b.txt\r\n";
+        assert_eq!(strip_escapes(text), "user:~$ ls\na.txt b.txt\n");
+    }
+
+    #[test]
+    fn applies_backspaces_and_lone_carriage_returns() {
+        assert_eq!(strip_escapes("lsx\u{8} -l\u{7}\r\n"), "ls -l\n");
+        assert_eq!(strip_escapes("10%\r50%\r100%"), "10%\n50%\n100%");
+        // A sequence cut off at the end of the output is dropped.
+        assert_eq!(strip_escapes("done\u{1b}[1"), "done");
+    }
+}
@@ -1,8 +1,10 @@
 //! Central registry for discovering and dispatching tools.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use aios_common::ToolDefinition;
+use aios_common::{Sensor, ToolDefinition, ToolPolicy, TrustLevel, TrustRequirement};
 
 use crate::executor::Tool;
 
@@ -10,8 +12,26 @@ use crate::executor::Tool;
 ///
 /// Use [`ToolRegistry::with_defaults`] to get a registry pre-populated with
 /// every built-in tool, or [`ToolRegistry::new`] to build one selectively.
+/// User overrides from the `[tools]` config section are applied with
+/// [`ToolRegistry::set_policies`], tools of external MCP servers are added
+/// with [`ToolRegistry::set_external`]. Old names of renamed tools keep
+/// working through [`ToolRegistry::register_alias`].
+#[derive(Clone)]
 pub struct ToolRegistry {
-    tools: HashMap<String, Box<dyn Tool>>,
+    tools: HashMap<String, Arc<dyn Tool>>,
+    /// Deprecated names, keyed by the old name.
+    aliases: HashMap<String, Arc<Alias>>,
+    policies: BTreeMap<String, ToolPolicy>,
+    /// Names of the tools added by [`ToolRegistry::set_external`].
+    external: Vec<String>,
+}
+
+/// A deprecated name of a tool.
+struct Alias {
+    /// Current name of the tool.
+    target: String,
+    /// The deprecation warning was logged; once is enough.
+    warned: AtomicBool,
 }
 
 impl ToolRegistry {
@@ -20,6 +40,9 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            aliases: HashMap::new(),
+            policies: BTreeMap::new(),
+            external: Vec::new(),
         }
     }
 
@@ -27,19 +50,150 @@ impl ToolRegistry {
     /// replaced.
     pub fn register(&mut self, tool: Box<dyn Tool>) {
         let name = tool.definition().name.clone();
-        self.tools.insert(name, tool);
+        self.tools.insert(name, Arc::from(tool));
+    }
+
+    /// Remove the tool called `name`, along with its aliases; `false` when
+    /// there is no such tool. Calls already running keep the copy of the
+    /// registry they started with.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        if self.tools.remove(name).is_none() {
+            return false;
+        }
+        self.aliases.retain(|_, alias| alias.target != name);
+        self.external.retain(|external| external != name);
+        true
     }
 
-    /// Look up a tool by name.
+    /// Keep accepting `alias`, a former name of the tool now called
+    /// `target`. Calls by the alias run the tool and log a deprecation
+    /// warning; a tool registered under the alias itself takes precedence.
+    pub fn register_alias(&mut self, alias: impl Into<String>, target: impl Into<String>) {
+        let entry = Alias {
+            target: target.into(),
+            warned: AtomicBool::new(false),
+        };
+        self.aliases.insert(alias.into(), Arc::new(entry));
+    }
+
+    /// Look up a tool by name or by a deprecated alias.
     #[must_use]
     pub fn get(&self, name: &str) -> Option<&dyn Tool> {
-        self.tools.get(name).map(AsRef::as_ref)
+        if let Some(tool) = self.tools.get(name) {
+            return Some(tool.as_ref());
+        }
+        let alias = self.aliases.get(name)?;
+        if !alias.warned.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                alias = %name,
+                tool = %alias.target,
+                "Tool called by a deprecated name"
+            );
+        }
+        self.tools.get(&alias.target).map(AsRef::as_ref)
+    }
+
+    /// Current name of the tool called `name`, resolving aliases; `None`
+    /// for unknown tools.
+    #[must_use]
+    pub fn canonical_name<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        if self.tools.contains_key(name) {
+            return Some(name);
+        }
+        let target = &self.aliases.get(name)?.target;
+        self.tools.contains_key(target).then_some(target.as_str())
+    }
+
+    /// Replace the user overrides (enabled flag, trust level) per tool.
+    pub fn set_policies(&mut self, policies: BTreeMap<String, ToolPolicy>) {
+        self.policies = policies;
+    }
+
+    /// Replace the tools of external MCP servers, keeping the built-in
+    /// ones. A tool whose name is already taken is skipped.
+    pub fn set_external(&mut self, tools: Vec<Box<dyn Tool>>) {
+        for name in self.external.drain(..) {
+            self.tools.remove(&name);
+        }
+        for tool in tools {
+            let name = tool.definition().name;
+            if self.tools.contains_key(&name) || self.aliases.contains_key(&name) {
+                tracing::warn!(tool = %name, "Skipping external tool with a taken name");
+                continue;
+            }
+            self.external.push(name);
+            self.register(tool);
+        }
+    }
+
+    /// Whether the tool called `name` may be offered and run.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        let name = self.canonical_name(name).unwrap_or(name);
+        self.policies.get(name).is_none_or(|policy| policy.enabled)
+    }
+
+    /// Confirmation level of the tool called `name`, with the user's
+    /// override applied; `None` for unknown tools.
+    #[must_use]
+    pub fn trust_requirement(&self, name: &str) -> Option<TrustRequirement> {
+        let name = self.canonical_name(name)?;
+        let tool = self.get(name)?;
+        let policy = self.policies.get(name).and_then(|policy| policy.trust);
+        Some(policy.unwrap_or_else(|| tool.trust_requirement()))
+    }
+
+    /// Sensors used by the tool called `name`; empty for unknown tools.
+    #[must_use]
+    pub fn sensors(&self, name: &str) -> &'static [Sensor] {
+        self.get(name)
+            .map(|tool| tool.sensors())
+            .unwrap_or_default()
+    }
+
+    /// Provenance of the output of the tool called `name`; system data for
+    /// unknown tools.
+    #[must_use]
+    pub fn output_trust(&self, name: &str) -> TrustLevel {
+        self.get(name)
+            .map_or(TrustLevel::System, |tool| tool.output_trust())
+    }
+
+    /// Names of every enabled tool, sorted.
+    #[must_use]
+    pub fn enabled_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tools
+            .keys()
+            .filter(|name| self.is_enabled(name))
+            .cloned()
+            .collect();
+        names.sort();
+        names
     }
 
-    /// Return the definitions of every registered tool (unordered).
+    /// Return the definitions of every enabled tool (unordered), with
+    /// trust overrides and aliases applied.
     #[must_use]
     pub fn definitions(&self) -> Vec<ToolDefinition> {
-        self.tools.values().map(|t| t.definition()).collect()
+        self.tools
+            .iter()
+            .filter(|(name, _)| self.is_enabled(name))
+            .map(|(name, tool)| {
+                let mut definition = tool.definition();
+                if let Some(trust) = self.policies.get(name).and_then(|policy| policy.trust) {
+                    definition.trust_requirement = trust;
+                }
+                definition.aliases = self
+                    .aliases
+                    .iter()
+                    .filter(|(_, alias)| alias.target == *name)
+                    .map(|(alias, _)| alias.clone())
+                    .collect();
+                definition.aliases.sort();
+                definition
+            })
+            .collect()
     }
 
     /// Create a registry pre-populated with all built-in tools.
@@ -55,15 +209,29 @@ impl ToolRegistry {
         registry.register(Box::new(file_delete::FileDeleteTool));
         registry.register(Box::new(file_list::FileListTool));
         registry.register(Box::new(file_search::FileSearchTool));
+        registry.register(Box::new(trash_restore::TrashRestoreTool));
+        registry.register(Box::new(undo_last::UndoLastTool));
 
         // System tools
         registry.register(Box::new(shell_exec::ShellExecTool));
+        registry.register(Box::new(shell_session::ChangeDirectoryTool));
+        registry.register(Box::new(shell_session::SetEnvTool));
+        registry.register(Box::new(shell_session::ShellSessionTool));
         registry.register(Box::new(wifi_list::WifiListTool));
         registry.register(Box::new(wifi_connect::WifiConnectTool));
+        registry.register(Box::new(net_usage::NetUsageTool));
         registry.register(Box::new(brightness::BrightnessTool));
         registry.register(Box::new(volume::VolumeTool));
         registry.register(Box::new(system_info::SystemInfoTool));
+        registry.register(Box::new(gpu_info::GpuInfoTool));
         registry.register(Box::new(open_url::OpenUrlTool));
+        registry.register(Box::new(calendar::CalendarEventsTool));
+        registry.register(Box::new(contacts::ContactsSearchTool));
+        registry.register(Box::new(contacts::ContactsAddTool));
+        registry.register(Box::new(datetime::DateTimeTool));
+        registry.register(Box::new(location::LocationTool));
+        registry.register(Box::new(home_assistant::HomeAssistantTool));
+        registry.register(Box::new(audit_history::AuditHistoryTool));
 
         // Browser tools (Chrome MCP bridge)
         registry.register(Box::new(browser::BrowserNavigateTool));
@@ -0,0 +1,95 @@
+//! Bubblewrap sandbox for shell commands.
+//!
+//! Approved commands still run with a limited blast radius: no new
+//! privileges, no capabilities, their own PID/IPC/UTS namespaces, a
+//! read-only root with only the configured paths writable, secrets hidden,
+//! and optionally no network. The user's runtime directory is replaced by
+//! an empty one: the session bus and the systemd user manager there would
+//! run anything outside the sandbox.
+
+use std::path::{Path, PathBuf};
+
+use aios_common::SandboxProfile;
+use tokio::process::Command;
+
+/// The bubblewrap binary.
+pub const BWRAP: &str = "bwrap";
+
+/// A process running `command` with `sh -c` in `working_dir`, inside the
+/// sandbox described by `profile` when there is one.
+pub fn shell_command(
+    command: &str,
+    working_dir: &Path,
+    profile: Option<&SandboxProfile>,
+) -> Command {
+    shell_command_with("sh", command, working_dir, profile)
+}
+
+/// Like [`shell_command`], with another shell than `sh`, such as `bash`.
+/// The shell must take the command with `-c`.
+pub fn shell_command_with(
+    shell: &str,
+    command: &str,
+    working_dir: &Path,
+    profile: Option<&SandboxProfile>,
+) -> Command {
+    let Some(profile) = profile else {
+        let mut cmd = Command::new(shell);
+        cmd.arg("-c").arg(command).current_dir(working_dir);
+        return cmd;
+    };
+
+    let mut cmd = Command::new(BWRAP);
+    cmd.args(["--die-with-parent", "--new-session", "--unshare-all"]);
+    if profile.network {
+        cmd.arg("--share-net");
+    }
+    cmd.args(["--cap-drop", "ALL"]);
+    cmd.args(["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc"]);
+    // Shell commands need nothing from the session, so nothing is bound
+    // back into the empty runtime directory.
+    for dir in runtime_dirs() {
+        cmd.arg("--tmpfs").arg(dir);
+    }
+    for path in profile.writable.iter().filter(|path| path.exists()) {
+        cmd.arg("--bind").arg(path).arg(path);
+    }
+    for path in &profile.read_only {
+        if !path.exists() && path.as_os_str().to_string_lossy().ends_with('/') {
+            let _ = std::fs::create_dir_all(path);
+        }
+        if path.exists() {
+            cmd.arg("--ro-bind").arg(path).arg(path);
+        }
+    }
+    // Hidden paths go last so they also cover paths under writable ones.
+    for path in &profile.hidden {
+        hide(&mut cmd, path);
+    }
+    cmd.arg("--chdir").arg(working_dir);
+    cmd.args(["--", shell, "-c"]).arg(command);
+    cmd
+}
+
+/// `/run/user/$UID` and `$XDG_RUNTIME_DIR`, the ones that exist.
+fn runtime_dirs() -> Vec<PathBuf> {
+    // SAFETY: getuid has no preconditions and cannot fail.
+    let uid = unsafe { libc::getuid() };
+    let mut found = vec![PathBuf::from(format!("/run/user/{uid}"))];
+    if let Some(dir) = dirs::runtime_dir()
+        && !found.contains(&dir)
+    {
+        found.push(dir);
+    }
+    found.retain(|dir| dir.is_dir());
+    found
+}
+
+/// Cover `path` with an empty directory, or `/dev/null` for files.
+fn hide(cmd: &mut Command, path: &Path) {
+    if path.is_dir() {
+        cmd.arg("--tmpfs").arg(path);
+    } else if path.exists() {
+        cmd.arg("--ro-bind").arg("/dev/null").arg(path);
+    }
+}
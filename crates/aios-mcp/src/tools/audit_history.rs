@@ -0,0 +1,131 @@
+//! Look up what the agent did, from its audit log.
+
+use aios_common::audit::AuditIndex;
+use aios_common::{
+    AgentConfig, AuditFilter, AuditKind, AuditRange, ToolDefinition, ToolResult, TrustRequirement,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// Longest range the tool returns at once.
+const MAX_DAYS: u64 = 31;
+
+/// Most entries returned when the model does not ask for fewer.
+const DEFAULT_LIMIT: u64 = 50;
+
+/// Most entries returned at once.
+const MAX_LIMIT: u64 = 200;
+
+/// Lists the tool calls the agent made over a day or a range of days, so
+/// the user can ask "what did you do yesterday?".
+pub struct AuditHistoryTool;
+
+#[async_trait]
+impl Tool for AuditHistoryTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "audit_history".to_string(),
+            description: "List the actions (tool calls) the assistant performed on this computer \
+                          for a date or a range of days, newest first"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "date": {
+                        "type": "string",
+                        "description": "First day as YYYY-MM-DD (default: today)"
+                    },
+                    "days": {
+                        "type": "integer",
+                        "description": "Number of days to include (1-31, default: 1)"
+                    },
+                    "action": {
+                        "type": "string",
+                        "description": "Only calls of this tool, e.g. \"shell_exec\""
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of entries (1-200, default: 50)"
+                    }
+                },
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let from = match args.get("date").and_then(|v| v.as_str()) {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| ToolError::invalid_arguments(format!("invalid 'date' argument: {e}")))?,
+            None => Local::now().date_naive(),
+        };
+        let days = args
+            .get("days")
+            .and_then(Value::as_u64)
+            .unwrap_or(1)
+            .clamp(1, MAX_DAYS);
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT) as usize;
+        let filter = AuditFilter {
+            kind: Some(AuditKind::Tool),
+            action: args
+                .get("action")
+                .and_then(|v| v.as_str())
+                .map(str::to_owned),
+            ..AuditFilter::default()
+        };
+        let range = AuditRange {
+            from: local_midnight(from),
+            to: local_midnight(from + chrono::Days::new(days)),
+        };
+
+        let path = AgentConfig::load().audit_log;
+        let (entries, truncated) = tokio::task::spawn_blocking(move || {
+            let mut index = AuditIndex::new(path);
+            index.refresh()?;
+            index.query(&filter, range, limit)
+        })
+        .await??;
+
+        let output = if entries.is_empty() {
+            "No actions".to_string()
+        } else {
+            let mut output = serde_json::to_string_pretty(&entries)
+                .unwrap_or_else(|e| format!("Error serializing entries: {e}"));
+            if truncated {
+                output.push_str(&format!("\n(only the newest {limit} entries are shown)"));
+            }
+            output
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+/// Start of `date` in local time; `None` only for a midnight that does not
+/// exist there.
+fn local_midnight(date: NaiveDate) -> Option<chrono::DateTime<Utc>> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
@@ -1,25 +1,130 @@
 //! Control display brightness.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::path::{Path, PathBuf};
+
+use aios_common::ddcutil::{self, DdcError};
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
 use crate::executor::{Tool, ToolContext};
 
-/// Reads or sets screen brightness via `/sys/class/backlight`.
+/// Reads or sets screen brightness: built-in panels through
+/// `/sys/class/backlight`, external monitors over DDC/CI with `ddcutil`.
 pub struct BrightnessTool;
 
-/// Find the first backlight device directory under `/sys/class/backlight/`.
-async fn find_backlight_dir() -> std::io::Result<std::path::PathBuf> {
-    let mut entries = tokio::fs::read_dir("/sys/class/backlight").await?;
-    if let Some(entry) = entries.next_entry().await? {
-        Ok(entry.path())
-    } else {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "no backlight device found",
-        ))
+/// Prefix of the ids of DDC monitors, followed by the I2C bus.
+const DDC_PREFIX: &str = "ddc-";
+
+/// A display whose brightness can be controlled.
+#[derive(Debug, Clone)]
+struct Display {
+    /// `intel_backlight` for a backlight, `ddc-6` for a monitor on bus 6.
+    id: String,
+    /// `backlight` or `ddc`.
+    kind: &'static str,
+    /// Model of a monitor; the id again for a backlight.
+    name: String,
+    target: Target,
+}
+
+#[derive(Debug, Clone)]
+enum Target {
+    Backlight(PathBuf),
+    Ddc(u32),
+}
+
+/// Backlight devices under `/sys/class/backlight/`.
+async fn backlights() -> Vec<Display> {
+    let mut displays = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir("/sys/class/backlight").await else {
+        return displays;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let id = entry.file_name().to_string_lossy().into_owned();
+        displays.push(Display {
+            name: id.clone(),
+            id,
+            kind: "backlight",
+            target: Target::Backlight(entry.path()),
+        });
+    }
+    displays.sort_by(|a, b| a.id.cmp(&b.id));
+    displays
+}
+
+/// External monitors that answer DDC/CI.
+async fn monitors() -> Result<Vec<Display>, DdcError> {
+    let monitors = ddcutil::monitors().await?;
+    Ok(monitors
+        .into_iter()
+        .map(|monitor| Display {
+            id: format!("{DDC_PREFIX}{}", monitor.bus),
+            kind: "ddc",
+            name: if monitor.model.is_empty() {
+                format!("Monitor on bus {}", monitor.bus)
+            } else {
+                monitor.model
+            },
+            target: Target::Ddc(monitor.bus),
+        })
+        .collect())
+}
+
+async fn read_number(path: &Path) -> std::io::Result<u64> {
+    let raw = tokio::fs::read_to_string(path).await?;
+    raw.trim()
+        .parse()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Brightness of `display` in percent.
+async fn get(display: &Display) -> Result<u8, String> {
+    match &display.target {
+        Target::Backlight(dir) => {
+            let max = read_number(&dir.join("max_brightness"))
+                .await
+                .map_err(|e| format!("Error reading max_brightness: {e}"))?;
+            let current = read_number(&dir.join("brightness"))
+                .await
+                .map_err(|e| format!("Error reading brightness: {e}"))?;
+            Ok((current * 100)
+                .checked_div(max)
+                .map_or(0, |percent| u8::try_from(percent).unwrap_or(100)))
+        }
+        Target::Ddc(bus) => ddcutil::brightness(*bus)
+            .await
+            .map(|value| value.percent())
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Set `display` to `percent`.
+async fn set(display: &Display, percent: u8) -> Result<(), (String, ToolErrorKind)> {
+    match &display.target {
+        Target::Backlight(dir) => {
+            let max = read_number(&dir.join("max_brightness")).await.unwrap_or(100);
+            let raw = max * u64::from(percent) / 100;
+            tokio::fs::write(dir.join("brightness"), raw.to_string())
+                .await
+                .map_err(|e| {
+                    (format!("Error writing brightness: {e}"), ToolErrorKind::from_io(&e))
+                })
+        }
+        Target::Ddc(bus) => ddcutil::set_brightness(*bus, percent)
+            .await
+            .map_err(|e| (e.to_string(), e.tool_kind())),
+    }
+}
+
+fn error(ctx: &ToolContext, output: String, kind: ToolErrorKind) -> ToolResult {
+    ToolResult {
+        call_id: ctx.call_id,
+        output,
+        is_error: true,
+        error_kind: Some(kind),
+        attachments: Vec::new(),
     }
 }
 
@@ -28,18 +133,26 @@ impl Tool for BrightnessTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "brightness".to_string(),
-            description: "Get or set display brightness (0-100)".to_string(),
+            description: "Get or set display brightness (0-100) of the built-in screen or of \
+                          external monitors (over DDC/CI). Without a value, lists the displays \
+                          with their brightness"
+                .to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "value": {
                         "type": "integer",
                         "description": "Brightness value 0-100. Omit to read current brightness."
+                    },
+                    "device": {
+                        "type": "string",
+                        "description": "Display id from the listing (e.g. intel_backlight, ddc-6), a monitor model, or \"all\". Default: the built-in screen, or every external monitor when there is none"
                     }
                 },
                 "required": []
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -48,71 +161,115 @@ impl Tool for BrightnessTool {
     }
 
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
-        let bl_dir = match find_backlight_dir().await {
-            Ok(d) => d,
-            Err(e) => {
-                return Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output: format!("Error finding backlight device: {e}"),
-                    is_error: true,
-                });
-            }
-        };
+        let device = args.get("device").and_then(|v| v.as_str());
+        let value = args.get("value").and_then(|v| v.as_u64());
 
-        let max_brightness_path = bl_dir.join("max_brightness");
-        let brightness_path = bl_dir.join("brightness");
-
-        let max_raw = match tokio::fs::read_to_string(&max_brightness_path).await {
-            Ok(s) => s,
-            Err(e) => {
-                return Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output: format!("Error reading max_brightness: {e}"),
-                    is_error: true,
-                });
+        // Backlights answer instantly, while detecting monitors takes a
+        // second or two: only look for them when they may be meant.
+        let mut all = backlights().await;
+        let needs_ddc = value.is_none()
+            || all.is_empty()
+            || device.is_some_and(|d| !all.iter().any(|b| b.id == d));
+        let mut ddc_error = None;
+        if needs_ddc {
+            match monitors().await {
+                Ok(monitors) => all.extend(monitors),
+                Err(e) => ddc_error = Some(e),
             }
+        }
+
+        let targets: Vec<&Display> = match device {
+            Some("all") => all.iter().collect(),
+            Some(device) => all
+                .iter()
+                .filter(|d| d.id == device || d.name.eq_ignore_ascii_case(device))
+                .collect(),
+            None if value.is_none() => all.iter().collect(),
+            None => match all.iter().find(|d| d.kind == "backlight") {
+                Some(backlight) => vec![backlight],
+                None => all.iter().collect(),
+            },
         };
-        let max_val: u64 = max_raw.trim().parse().unwrap_or(100);
-
-        if let Some(value) = args.get("value").and_then(|v| v.as_u64()) {
-            // Set brightness.
-            let clamped = value.min(100);
-            let raw = max_val * clamped / 100;
-            match tokio::fs::write(&brightness_path, raw.to_string()).await {
-                Ok(()) => Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output: format!("Brightness set to {clamped}%"),
-                    is_error: false,
-                }),
-                Err(e) => Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output: format!("Error writing brightness: {e}"),
-                    is_error: true,
-                }),
-            }
-        } else {
-            // Read current brightness.
-            let cur_raw = match tokio::fs::read_to_string(&brightness_path).await {
-                Ok(s) => s,
-                Err(e) => {
-                    return Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: format!("Error reading brightness: {e}"),
-                        is_error: true,
-                    });
+
+        if targets.is_empty() {
+            let known: Vec<&str> = all.iter().map(|d| d.id.as_str()).collect();
+            let output = match (device, ddc_error) {
+                (Some(device), _) if !known.is_empty() => {
+                    format!("No display '{device}'; available: {}", known.join(", "))
                 }
+                (_, Some(e)) => format!(
+                    "No backlight device found, and external monitors cannot be \
+                     controlled: {e}"
+                ),
+                _ => "No backlight device found, and no external monitor answers DDC/CI"
+                    .to_owned(),
             };
-            let cur_val: u64 = cur_raw.trim().parse().unwrap_or(0);
-            let percent = if max_val > 0 {
-                cur_val * 100 / max_val
+            let kind = if device.is_some() && !known.is_empty() {
+                ToolErrorKind::NotFound
             } else {
-                0
+                ToolErrorKind::Unavailable
             };
-            Ok(ToolResult {
+            return Ok(error(ctx, output, kind));
+        }
+
+        let Some(value) = value else {
+            // Read current brightness.
+            let mut listing = Vec::new();
+            for display in targets {
+                let entry = match get(display).await {
+                    Ok(percent) => json!({
+                        "id": display.id,
+                        "kind": display.kind,
+                        "name": display.name,
+                        "brightness": percent,
+                    }),
+                    Err(e) => json!({
+                        "id": display.id,
+                        "kind": display.kind,
+                        "name": display.name,
+                        "error": e,
+                    }),
+                };
+                listing.push(entry);
+            }
+            let mut output = json!({ "displays": listing });
+            if let Some(e) = ddc_error {
+                output["ddc_error"] = json!(e.to_string());
+            }
+            return Ok(ToolResult {
                 call_id: ctx.call_id,
-                output: format!("Current brightness: {percent}%"),
+                output: output.to_string(),
                 is_error: false,
-            })
+                error_kind: None,
+                attachments: Vec::new(),
+            });
+        };
+
+        // Set brightness.
+        let clamped = u8::try_from(value.min(100)).unwrap_or(100);
+        let mut done = Vec::new();
+        let mut failed = Vec::new();
+        for display in targets {
+            match set(display, clamped).await {
+                Ok(()) => done.push(display.name.clone()),
+                Err((message, kind)) => failed.push((display.name.clone(), message, kind)),
+            }
+        }
+        if done.is_empty()
+            && let Some((name, message, kind)) = failed.pop()
+        {
+            return Ok(error(ctx, format!("{name}: {message}"), kind));
+        }
+        let mut output = format!("Brightness set to {clamped}% on {}", done.join(", "));
+        for (name, message, _) in &failed {
+            output.push_str(&format!("; {name} failed: {message}"));
         }
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
     }
 }
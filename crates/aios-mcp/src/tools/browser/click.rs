@@ -1,6 +1,6 @@
 //! Click an element in the browser.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -33,6 +33,7 @@ impl Tool for BrowserClickTool {
                 "required": ["selector"]
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -45,6 +46,8 @@ impl Tool for BrowserClickTool {
             call_id: ctx.call_id,
             output: MCP_STUB_MSG.into(),
             is_error: true,
+            error_kind: Some(ToolErrorKind::Unavailable),
+            attachments: Vec::new(),
         })
     }
 }
@@ -1,6 +1,6 @@
 //! Find an element on the current browser page.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustLevel, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -37,6 +37,7 @@ impl Tool for BrowserFindTool {
                 "required": ["selector"]
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -44,11 +45,17 @@ impl Tool for BrowserFindTool {
         TrustRequirement::None
     }
 
+    fn output_trust(&self) -> TrustLevel {
+        TrustLevel::WebContent
+    }
+
     async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
         Ok(ToolResult {
             call_id: ctx.call_id,
             output: MCP_STUB_MSG.into(),
             is_error: true,
+            error_kind: Some(ToolErrorKind::Unavailable),
+            attachments: Vec::new(),
         })
     }
 }
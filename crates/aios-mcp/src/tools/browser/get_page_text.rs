@@ -1,6 +1,6 @@
 //! Extract text content from the current browser page.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustLevel, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -38,6 +38,7 @@ impl Tool for BrowserGetPageTextTool {
                 "required": []
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -45,11 +46,17 @@ impl Tool for BrowserGetPageTextTool {
         TrustRequirement::None
     }
 
+    fn output_trust(&self) -> TrustLevel {
+        TrustLevel::WebContent
+    }
+
     async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
         Ok(ToolResult {
             call_id: ctx.call_id,
             output: MCP_STUB_MSG.into(),
             is_error: true,
+            error_kind: Some(ToolErrorKind::Unavailable),
+            attachments: Vec::new(),
         })
     }
 }
@@ -1,11 +1,12 @@
 //! Navigate the browser to a URL.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{BrowserConfig, ToolDefinition, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
+use crate::tools::open_url::check_url;
 
 /// Opens a URL in the Chromium browser.
 ///
@@ -30,6 +31,7 @@ impl Tool for BrowserNavigateTool {
                 "required": ["url"]
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -41,13 +43,27 @@ impl Tool for BrowserNavigateTool {
         let url = args
             .get("url")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required 'url' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing required 'url' argument"))?;
+
+        // Same schemes as open_url: no local files or scripts.
+        let url = match check_url(url, &BrowserConfig::load()) {
+            Ok(url) => url,
+            Err((output, kind)) => {
+                return Ok(ToolResult {
+                    call_id: ctx.call_id,
+                    output,
+                    is_error: true,
+                    error_kind: Some(kind),
+                    attachments: Vec::new(),
+                });
+            }
+        };
 
         // Spawn Chromium in the background -- we do not wait for it to exit
         // because a browser process stays alive until the user closes it.
         let spawn_result = tokio::process::Command::new("chromium")
             .arg("--ozone-platform-hint=auto")
-            .arg(url)
+            .arg(url.as_str())
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::piped())
@@ -58,11 +74,15 @@ impl Tool for BrowserNavigateTool {
                 call_id: ctx.call_id,
                 output: format!("Navigated to {url} in Chromium"),
                 is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: format!("Failed to launch Chromium: {e}"),
                 is_error: true,
+                error_kind: Some(ToolError::command_kind(&e)),
+                attachments: Vec::new(),
             }),
         }
     }
@@ -1,6 +1,6 @@
 //! Read the current page content from the browser.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustLevel, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -33,6 +33,7 @@ impl Tool for BrowserReadPageTool {
                 "required": []
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -40,11 +41,17 @@ impl Tool for BrowserReadPageTool {
         TrustRequirement::None
     }
 
+    fn output_trust(&self) -> TrustLevel {
+        TrustLevel::WebContent
+    }
+
     async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
         Ok(ToolResult {
             call_id: ctx.call_id,
             output: MCP_STUB_MSG.into(),
             is_error: true,
+            error_kind: Some(ToolErrorKind::Unavailable),
+            attachments: Vec::new(),
         })
     }
 }
@@ -1,6 +1,6 @@
 //! Take a screenshot of the current browser page.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{Sensor, ToolDefinition, ToolErrorKind, ToolResult, TrustLevel, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -42,6 +42,7 @@ impl Tool for BrowserScreenshotTool {
                 "required": []
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -49,11 +50,21 @@ impl Tool for BrowserScreenshotTool {
         TrustRequirement::None
     }
 
+    fn output_trust(&self) -> TrustLevel {
+        TrustLevel::WebContent
+    }
+
+    fn sensors(&self) -> &'static [Sensor] {
+        &[Sensor::Screen]
+    }
+
     async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
         Ok(ToolResult {
             call_id: ctx.call_id,
             output: MCP_STUB_MSG.into(),
             is_error: true,
+            error_kind: Some(ToolErrorKind::Unavailable),
+            attachments: Vec::new(),
         })
     }
 }
@@ -1,6 +1,6 @@
 //! Type text into a browser input element.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -41,6 +41,7 @@ impl Tool for BrowserTypeTool {
                 "required": ["selector", "text"]
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -53,6 +54,8 @@ impl Tool for BrowserTypeTool {
             call_id: ctx.call_id,
             output: MCP_STUB_MSG.into(),
             is_error: true,
+            error_kind: Some(ToolErrorKind::Unavailable),
+            attachments: Vec::new(),
         })
     }
 }
@@ -0,0 +1,78 @@
+//! Read events from the local calendar.
+
+use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// Longest range the tool returns at once.
+const MAX_DAYS: u64 = 31;
+
+/// Lists calendar events for a day or a range of days from the `.ics` files
+/// in the AIOS calendar directory.
+pub struct CalendarEventsTool;
+
+#[async_trait]
+impl Tool for CalendarEventsTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "calendar_events".to_string(),
+            description: "List the user's calendar events for a date or a range of days"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "date": {
+                        "type": "string",
+                        "description": "First day as YYYY-MM-DD (default: today)"
+                    },
+                    "days": {
+                        "type": "integer",
+                        "description": "Number of days to include (1-31, default: 1)"
+                    }
+                },
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let from = match args.get("date").and_then(|v| v.as_str()) {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| ToolError::invalid_arguments(format!("invalid 'date' argument: {e}")))?,
+            None => chrono::Local::now().date_naive(),
+        };
+        let days = args
+            .get("days")
+            .and_then(Value::as_u64)
+            .unwrap_or(1)
+            .clamp(1, MAX_DAYS) as u32;
+
+        let events =
+            tokio::task::spawn_blocking(move || aios_common::calendar::events_between(from, days))
+                .await?;
+        let output = if events.is_empty() {
+            "No events".to_string()
+        } else {
+            serde_json::to_string_pretty(&events)
+                .unwrap_or_else(|e| format!("Error serializing events: {e}"))
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
+    }
+}
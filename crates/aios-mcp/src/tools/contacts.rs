@@ -0,0 +1,179 @@
+//! Look up and add contacts in the local address book.
+
+use aios_common::{contacts, Contact, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// Contacts returned unless the caller asks for another number.
+const DEFAULT_LIMIT: usize = 10;
+
+/// Upper bound on `limit`.
+const MAX_LIMIT: usize = 50;
+
+/// Finds contacts by name, nickname, organization, email or phone number in
+/// the vCard directory of `[contacts]`.
+pub struct ContactsSearchTool;
+
+/// Saves a new contact as a vCard in the directory of `[contacts]`.
+pub struct ContactsAddTool;
+
+#[async_trait]
+impl Tool for ContactsSearchTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "contacts_search".to_string(),
+            description: "Search the user's contacts for a person's email address or phone \
+                number. Returns JSON [{name, emails, phones, nickname, organization, note}], by \
+                name. Use it whenever the user names someone to email, call or invite instead \
+                of asking for the address."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Words of the name, nickname, organization or email, \
+                            or part of a phone number; empty lists everyone"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": format!(
+                            "Number of contacts to return (default: {DEFAULT_LIMIT}, max: \
+                             {MAX_LIMIT})"
+                        )
+                    }
+                },
+                "required": ["query"]
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |n| (n as usize).clamp(1, MAX_LIMIT));
+
+        let mut found = tokio::task::spawn_blocking(move || contacts::search(&query)).await?;
+        found.truncate(limit);
+        let output = if found.is_empty() {
+            "No contacts found".to_string()
+        } else {
+            serde_json::to_string_pretty(&found)?
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for ContactsAddTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "contacts_add".to_string(),
+            description: "Add a person to the user's contacts. Fails if a contact already has \
+                one of the email addresses; search first to avoid duplicates."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Full name" },
+                    "emails": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Email addresses"
+                    },
+                    "phones": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Phone numbers, international format if known"
+                    },
+                    "nickname": { "type": "string" },
+                    "organization": { "type": "string" },
+                    "note": { "type": "string" }
+                },
+                "required": ["name"]
+            }),
+            trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::Confirm
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let text = |key: &str| {
+            args.get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_owned)
+        };
+        let list = |key: &str| -> Vec<String> {
+            let values = match args.get(key) {
+                // Models sometimes pass a single address as a string.
+                Some(Value::String(value)) => vec![value.as_str()],
+                Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            values
+                .into_iter()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_owned)
+                .collect()
+        };
+        let contact = Contact {
+            name: text("name").ok_or_else(|| ToolError::invalid_arguments("missing 'name'"))?,
+            emails: list("emails"),
+            phones: list("phones"),
+            nickname: text("nickname"),
+            organization: text("organization"),
+            note: text("note"),
+        };
+        if let Some(email) = contact.emails.iter().find(|email| !email.contains('@')) {
+            let message = format!("'{email}' is not an email address");
+            return Err(ToolError::invalid_arguments(message));
+        }
+
+        let name = contact.name.clone();
+        Ok(match tokio::task::spawn_blocking(move || contacts::add(&contact)).await? {
+            Ok(path) => ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Added {name} to the contacts ({})", path.display()),
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            },
+            Err(e) => ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Failed to add {name}: {e}"),
+                is_error: true,
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
+            },
+        })
+    }
+}
@@ -0,0 +1,125 @@
+//! Current local date and time, and moments relative to it.
+
+use aios_common::{locale, ToolDefinition, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// Tells the time in the user's timezone, and resolves a day and time such
+/// as "tomorrow at 9:00" to an exact moment.
+pub struct DateTimeTool;
+
+/// A moment as the LLM gets it.
+fn describe(moment: DateTime<Local>, now: DateTime<Local>) -> Value {
+    let mut value = json!({
+        "local": moment.to_rfc3339(),
+        "date": moment.format("%Y-%m-%d").to_string(),
+        "time": moment.format("%H:%M").to_string(),
+        "weekday": moment.format("%A").to_string(),
+        "utc_offset": moment.format("%:z").to_string(),
+        "unix": moment.timestamp(),
+    });
+    if let Some(timezone) = locale::timezone() {
+        value["timezone"] = json!(timezone);
+    }
+    if moment != now {
+        value["from_now"] = json!(from_now(moment - now));
+    }
+    value
+}
+
+/// `in 2d 3h 5m` or `4h 10m ago`.
+fn from_now(delta: Duration) -> String {
+    let minutes = delta.num_minutes().abs();
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{days}d"));
+    }
+    if hours > 0 {
+        parts.push(format!("{hours}h"));
+    }
+    if minutes > 0 || parts.is_empty() {
+        parts.push(format!("{minutes}m"));
+    }
+    if delta < Duration::zero() {
+        format!("{} ago", parts.join(" "))
+    } else {
+        format!("in {}", parts.join(" "))
+    }
+}
+
+#[async_trait]
+impl Tool for DateTimeTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "datetime".to_string(),
+            description: "Get the current local date, time, weekday and timezone of the user. \
+                          With date, days or time, resolves that moment instead (e.g. tomorrow \
+                          at 9am: days=1, time=09:00)"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "date": {
+                        "type": "string",
+                        "description": "Day as YYYY-MM-DD (default: today)"
+                    },
+                    "days": {
+                        "type": "integer",
+                        "description": "Days to add to the date, negative for the past (e.g. 1 for tomorrow)"
+                    },
+                    "time": {
+                        "type": "string",
+                        "description": "Local time of day as HH:MM, 24-hour (default: the current time)"
+                    }
+                },
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let now = Local::now();
+        let date = match args.get("date").and_then(|v| v.as_str()) {
+            Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| ToolError::invalid_arguments(format!("invalid 'date' argument: {e}")))?,
+            None => now.date_naive(),
+        };
+        let days = args.get("days").and_then(Value::as_i64).unwrap_or(0);
+        let date = Duration::try_days(days)
+            .and_then(|days| date.checked_add_signed(days))
+            .ok_or_else(|| ToolError::invalid_arguments("'days' is out of range"))?;
+        let time = match args.get("time").and_then(|v| v.as_str()) {
+            Some(time) => NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|e| ToolError::invalid_arguments(format!("invalid 'time' argument: {e}")))?,
+            None => now.time(),
+        };
+
+        // A time skipped by a DST change has no moment; one repeated by it
+        // resolves to its first occurrence.
+        let Some(moment) = Local.from_local_datetime(&date.and_time(time)).earliest() else {
+            return Err(ToolError::invalid_arguments(format!(
+                "{date} {} does not exist in the local timezone (daylight saving change)",
+                time.format("%H:%M")
+            )));
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output: describe(moment, now).to_string(),
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
+    }
+}
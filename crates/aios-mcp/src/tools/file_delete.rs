@@ -1,57 +1,119 @@
 //! Delete a file from the filesystem.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::path::{Path, PathBuf};
+
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
+use crate::trash;
+use crate::undo::{self, UndoAction};
 
-/// Deletes a single file. This is a destructive operation requiring double
-/// confirmation.
+/// Moves a file or directory to the trash, from where `trash_restore` or
+/// `undo_last` bring it back. Deleting a file permanently instead is a
+/// destructive operation requiring double confirmation.
 pub struct FileDeleteTool;
 
+fn is_permanent(args: &Value) -> bool {
+    args.get("permanent").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Trash `path` and record it in the undo journal. Blocking.
+fn trash(path: &Path) -> std::io::Result<trash::Trashed> {
+    let trashed = trash::move_to_trash(path)?;
+    let action = UndoAction::Untrash {
+        path: trashed.original.clone(),
+        trashed: trashed.trashed.clone(),
+    };
+    if let Err(e) = undo::record("file_delete", action) {
+        tracing::warn!(error = %e, "Failed to record file_delete in the undo journal");
+    }
+    Ok(trashed)
+}
+
 #[async_trait]
 impl Tool for FileDeleteTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "file_delete".to_string(),
-            description: "Delete a file (destructive, requires double confirmation)".to_string(),
+            description: "Move a file or directory to the trash, where it can be restored. \
+                With permanent: true a file is deleted for good instead (destructive, requires \
+                double confirmation)."
+                .to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "path": {
                         "type": "string",
                         "description": "Absolute path to the file to delete"
+                    },
+                    "permanent": {
+                        "type": "boolean",
+                        "description": "Delete the file for good instead of trashing it \
+                            (default: false; only for files)"
                     }
                 },
                 "required": ["path"]
             }),
-            trust_requirement: TrustRequirement::DoubleConfirm,
+            trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
     fn trust_requirement(&self) -> TrustRequirement {
-        TrustRequirement::DoubleConfirm
+        TrustRequirement::Confirm
+    }
+
+    fn call_trust(&self, args: &Value) -> Option<TrustRequirement> {
+        is_permanent(args).then_some(TrustRequirement::DoubleConfirm)
     }
 
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'path' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'path' argument"))?;
 
-        match tokio::fs::remove_file(path).await {
-            Ok(()) => Ok(ToolResult {
+        if is_permanent(&args) {
+            return Ok(match tokio::fs::remove_file(path).await {
+                Ok(()) => ToolResult {
+                    call_id: ctx.call_id,
+                    output: format!("Permanently deleted {path}"),
+                    is_error: false,
+                    error_kind: None,
+                    attachments: Vec::new(),
+                },
+                Err(e) => ToolResult {
+                    call_id: ctx.call_id,
+                    output: format!("Error deleting file: {e}"),
+                    is_error: true,
+                    error_kind: Some(ToolErrorKind::from_io(&e)),
+                    attachments: Vec::new(),
+                },
+            });
+        }
+
+        let target = PathBuf::from(path);
+        Ok(match tokio::task::spawn_blocking(move || trash(&target)).await? {
+            Ok(trashed) => ToolResult {
                 call_id: ctx.call_id,
-                output: format!("Successfully deleted {path}"),
+                output: format!(
+                    "Moved {path} to the trash ({}). Restore it with trash_restore or undo_last.",
+                    trashed.trashed.display()
+                ),
                 is_error: false,
-            }),
-            Err(e) => Ok(ToolResult {
+                error_kind: None,
+                attachments: Vec::new(),
+            },
+            Err(e) => ToolResult {
                 call_id: ctx.call_id,
-                output: format!("Error deleting file: {e}"),
+                output: format!("Error moving to the trash: {e}"),
                 is_error: true,
-            }),
-        }
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
+            },
+        })
     }
 }
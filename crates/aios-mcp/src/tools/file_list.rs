@@ -1,32 +1,198 @@
 //! List entries in a directory.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::fs::Metadata;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use serde::Serialize;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use super::file_search::matches_pattern;
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// Entries returned per call unless the caller asks for another number.
+const DEFAULT_LIMIT: usize = 200;
+
+/// Upper bound on `limit`.
+const MAX_LIMIT: usize = 1000;
+
+/// Deepest recursion allowed.
+const MAX_DEPTH: usize = 10;
+
+/// Entries examined before a recursive listing stops, so a listing of `/`
+/// finishes.
+const MAX_SCANNED: usize = 50_000;
 
-/// Lists files and directories inside a given directory path.
+/// Lists files and directories inside a given directory path, with size,
+/// modification time, type and permissions, sorted and paged.
 pub struct FileListTool;
 
+/// One listed entry.
+#[derive(Debug, Serialize)]
+struct Entry {
+    /// Path relative to the listed directory.
+    name: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    size: u64,
+    /// Local time, RFC 3339.
+    modified: Option<String>,
+    /// `ls`-style mode, e.g. `rwxr-xr-x`.
+    permissions: String,
+    #[serde(skip)]
+    mtime: Option<std::time::SystemTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Name,
+    Size,
+    Modified,
+    Type,
+}
+
+impl SortKey {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "name" => Some(Self::Name),
+            "size" => Some(Self::Size),
+            "modified" => Some(Self::Modified),
+            "type" => Some(Self::Type),
+            _ => None,
+        }
+    }
+}
+
+/// What to walk and what to keep.
+struct Walk<'a> {
+    depth: usize,
+    pattern: Option<&'a str>,
+    include_hidden: bool,
+    scanned: usize,
+}
+
+impl Walk<'_> {
+    /// Collect entries of `dir` (and below, up to `depth` levels) into
+    /// `entries`, naming them relative to the listed root via `prefix`.
+    fn run(&mut self, dir: &Path, prefix: &str, level: usize, entries: &mut Vec<Entry>) {
+        let Ok(read) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in read.flatten() {
+            if self.scanned >= MAX_SCANNED {
+                return;
+            }
+            self.scanned += 1;
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if !self.include_hidden && file_name.starts_with('.') {
+                continue;
+            }
+            let Ok(metadata) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            let name = format!("{prefix}{file_name}");
+            if self.pattern.is_none_or(|pattern| matches_pattern(&file_name, pattern)) {
+                entries.push(describe(name.clone(), &metadata));
+            }
+            if metadata.is_dir() && level + 1 < self.depth {
+                self.run(&entry.path(), &format!("{name}/"), level + 1, entries);
+            }
+        }
+    }
+}
+
+fn describe(name: String, metadata: &Metadata) -> Entry {
+    let kind = if metadata.is_dir() {
+        "dir"
+    } else if metadata.is_symlink() {
+        "symlink"
+    } else {
+        "file"
+    };
+    let mtime = metadata.modified().ok();
+    Entry {
+        name,
+        kind,
+        size: metadata.len(),
+        modified: mtime.map(|t| DateTime::<Local>::from(t).to_rfc3339()),
+        permissions: mode_string(metadata.permissions().mode()),
+        mtime,
+    }
+}
+
+/// `rwxr-xr-x` from the permission bits of `mode`.
+fn mode_string(mode: u32) -> String {
+    (0..9)
+        .map(|bit| {
+            if mode & (0o400 >> bit) == 0 {
+                '-'
+            } else {
+                ['r', 'w', 'x'][bit % 3]
+            }
+        })
+        .collect()
+}
+
 #[async_trait]
 impl Tool for FileListTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "file_list".to_string(),
-            description: "List files and directories in a given path".to_string(),
+            description: "List files and directories in a given path with size, modification \
+                time, type and permissions. Large listings are paged: use offset and limit, \
+                and next_offset from the result to continue."
+                .to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "path": {
                         "type": "string",
                         "description": "Absolute path to the directory to list"
+                    },
+                    "depth": {
+                        "type": "integer",
+                        "description": format!(
+                            "Levels to descend; 1 lists only the directory itself \
+                             (default: 1, max: {MAX_DEPTH})"
+                        )
+                    },
+                    "pattern": {
+                        "type": "string",
+                        "description": "Only entries whose name matches this pattern with \
+                            * wildcards (e.g. '*.pdf')"
+                    },
+                    "include_hidden": {
+                        "type": "boolean",
+                        "description": "Include entries starting with a dot (default: false)"
+                    },
+                    "sort": {
+                        "type": "string",
+                        "enum": ["name", "size", "modified", "type"],
+                        "description": "Sort key (default: name)"
+                    },
+                    "descending": {
+                        "type": "boolean",
+                        "description": "Reverse the order, e.g. largest or newest first"
+                    },
+                    "offset": {
+                        "type": "integer",
+                        "description": "Entries to skip (default: 0)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": format!(
+                            "Entries to return (default: {DEFAULT_LIMIT}, max: {MAX_LIMIT})"
+                        )
                     }
                 },
                 "required": ["path"]
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -38,36 +204,83 @@ impl Tool for FileListTool {
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'path' argument"))?;
-
-        match tokio::fs::read_dir(path).await {
-            Ok(mut entries) => {
-                let mut items = Vec::new();
-                while let Ok(Some(entry)) = entries.next_entry().await {
-                    let file_type = entry.file_type().await;
-                    let kind = match file_type {
-                        Ok(ft) if ft.is_dir() => "dir",
-                        Ok(ft) if ft.is_symlink() => "symlink",
-                        _ => "file",
-                    };
-                    items.push(json!({
-                        "name": entry.file_name().to_string_lossy().to_string(),
-                        "type": kind,
-                    }));
-                }
-                let output = serde_json::to_string_pretty(&items)
-                    .unwrap_or_else(|e| format!("Error serializing entries: {e}"));
-                Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output,
-                    is_error: false,
-                })
-            }
-            Err(e) => Ok(ToolResult {
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'path' argument"))?
+            .to_owned();
+
+        let sort = match args.get("sort").and_then(|v| v.as_str()) {
+            None => SortKey::Name,
+            Some(name) => SortKey::parse(name).ok_or_else(|| {
+                ToolError::invalid_arguments(format!("unknown sort key '{name}'"))
+            })?,
+        };
+        let descending = args.get("descending").and_then(|v| v.as_bool()).unwrap_or(false);
+        let depth = args
+            .get("depth")
+            .and_then(|v| v.as_u64())
+            .map_or(1, |d| (d as usize).clamp(1, MAX_DEPTH));
+        let pattern = args.get("pattern").and_then(|v| v.as_str()).map(str::to_owned);
+        let include_hidden = args.get("include_hidden").and_then(|v| v.as_bool()).unwrap_or(false);
+        let offset = args.get("offset").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+        let limit = args
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_LIMIT, |l| (l as usize).clamp(1, MAX_LIMIT));
+
+        if let Err(e) = tokio::fs::read_dir(&path).await {
+            return Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: format!("Error listing directory: {e}"),
                 is_error: true,
-            }),
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
+            });
         }
+
+        // Walking can touch many directories; keep it off the runtime.
+        let (mut entries, scanned) = tokio::task::spawn_blocking(move || {
+            let mut walk = Walk {
+                depth,
+                pattern: pattern.as_deref(),
+                include_hidden,
+                scanned: 0,
+            };
+            let mut entries = Vec::new();
+            walk.run(Path::new(&path), "", 0, &mut entries);
+            (entries, walk.scanned)
+        })
+        .await?;
+
+        entries.sort_by(|a, b| {
+            let order = match sort {
+                SortKey::Name => a.name.cmp(&b.name),
+                SortKey::Size => a.size.cmp(&b.size),
+                SortKey::Modified => a.mtime.cmp(&b.mtime),
+                SortKey::Type => a.kind.cmp(b.kind),
+            };
+            order.then_with(|| a.name.cmp(&b.name))
+        });
+        if descending {
+            entries.reverse();
+        }
+
+        let total = entries.len();
+        let page: Vec<Entry> = entries.into_iter().skip(offset).take(limit).collect();
+        let next_offset = (offset + page.len() < total).then_some(offset + page.len());
+        let output = json!({
+            "total": total,
+            "offset": offset,
+            "next_offset": next_offset,
+            // The walk stopped early; the listing is incomplete.
+            "incomplete": scanned >= MAX_SCANNED,
+            "entries": page,
+        });
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output: serde_json::to_string_pretty(&output)?,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
     }
 }
@@ -1,32 +1,331 @@
 //! Read the contents of a file.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use aios_common::{
+    Attachment, AttachmentContent, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement,
+};
 use anyhow::Result;
 use async_trait::async_trait;
+use base64::Engine as _;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
 
-/// Reads a file and returns its contents as a UTF-8 string.
+/// Reads a file, or a range of its lines or bytes, and returns it as text.
+///
+/// Images are returned as an attachment instead, so the chat can show them.
+/// Binary files get a hexdump of their start.
 pub struct FileReadTool;
 
+/// Most text returned by one call (256 KiB); longer ranges are cut and the
+/// result says where to continue.
+const MAX_TEXT_BYTES: u64 = 256 * 1024;
+
+/// Lines returned when the caller gives no limit.
+const DEFAULT_LINES: u64 = 2000;
+
+/// Start of the file inspected for a byte order mark or binary content.
+const SNIFF_BYTES: u64 = 8192;
+
+/// Bytes shown in the hexdump of a binary file.
+const HEXDUMP_BYTES: usize = 256;
+
+/// Whether `offset` and `limit` count lines or bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Lines,
+    Bytes,
+}
+
+/// Text encoding found at the start of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    /// UTF-8 with a byte order mark, which is skipped.
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl Encoding {
+    /// Length of the byte order mark.
+    fn bom_len(self) -> u64 {
+        match self {
+            Self::Utf8 => 0,
+            Self::Utf8Bom => 3,
+            Self::Utf16Le | Self::Utf16Be => 2,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Utf8 | Self::Utf8Bom => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+        }
+    }
+}
+
+/// The encoding of a file starting with `head`, or `None` if it looks
+/// binary (a NUL byte without a UTF-16 byte order mark).
+fn sniff(head: &[u8]) -> Option<Encoding> {
+    match head {
+        [0xEF, 0xBB, 0xBF, ..] => Some(Encoding::Utf8Bom),
+        [0xFF, 0xFE, ..] => Some(Encoding::Utf16Le),
+        [0xFE, 0xFF, ..] => Some(Encoding::Utf16Be),
+        _ if head.contains(&0) => None,
+        _ => Some(Encoding::Utf8),
+    }
+}
+
+/// Decode `bytes`; the flag is set when some bytes were not valid and got
+/// replaced with U+FFFD.
+fn decode(bytes: &[u8], encoding: Encoding) -> (String, bool) {
+    let units = |from: fn([u8; 2]) -> u16| -> Vec<u16> {
+        bytes.chunks_exact(2).map(|pair| from([pair[0], pair[1]])).collect()
+    };
+    match encoding {
+        Encoding::Utf8 | Encoding::Utf8Bom => match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => (text, false),
+            Err(e) => (String::from_utf8_lossy(e.as_bytes()).into_owned(), true),
+        },
+        Encoding::Utf16Le | Encoding::Utf16Be => {
+            let units = units(if encoding == Encoding::Utf16Le {
+                u16::from_le_bytes
+            } else {
+                u16::from_be_bytes
+            });
+            match String::from_utf16(&units) {
+                Ok(text) => (text, !bytes.len().is_multiple_of(2)),
+                Err(_) => (String::from_utf16_lossy(&units), true),
+            }
+        }
+    }
+}
+
+/// `xxd`-style dump of `bytes`: offset, 16 hex bytes, printable ASCII.
+fn hexdump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect();
+        out.push_str(&format!("{:08x}  {:<47}  {ascii}\n", row * 16, hex.join(" ")));
+    }
+    out
+}
+
+/// Read the requested range of a text file, or describe a binary one.
+/// Blocking; run it off the runtime.
+fn read_range(path: &Path, unit: Unit, offset: u64, limit: Option<u64>) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let mut head = Vec::new();
+    (&mut file).take(SNIFF_BYTES).read_to_end(&mut head)?;
+
+    let Some(encoding) = sniff(&head) else {
+        head.truncate(HEXDUMP_BYTES);
+        return Ok(format!(
+            "[Binary file, {size} bytes; not shown as text. First {} bytes:]\n{}",
+            head.len(),
+            hexdump(&head)
+        ));
+    };
+
+    let mut notes = Vec::new();
+    let text = match (unit, encoding) {
+        (Unit::Bytes, _) => {
+            let start = offset.max(encoding.bom_len()).min(size);
+            let want = limit.unwrap_or(u64::MAX).min(MAX_TEXT_BYTES);
+            file.seek(SeekFrom::Start(start))?;
+            let mut bytes = Vec::new();
+            (&mut file).take(want).read_to_end(&mut bytes)?;
+            let end = start + bytes.len() as u64;
+            if start > 0 || end < size {
+                let mut note = format!("[Bytes {start}-{end} of {size}");
+                if end < size {
+                    note.push_str(&format!("; continue with offset={end}"));
+                }
+                notes.push(note + "]");
+            }
+            decode_noting(&bytes, encoding, &mut notes)
+        }
+        // Lines of UTF-16 cannot be split on the raw newline byte; decode
+        // the start of the file and split the text instead.
+        (Unit::Lines, Encoding::Utf16Le | Encoding::Utf16Be) => {
+            file.seek(SeekFrom::Start(encoding.bom_len()))?;
+            let mut bytes = Vec::new();
+            (&mut file).take(MAX_TEXT_BYTES).read_to_end(&mut bytes)?;
+            let text = decode_noting(&bytes, encoding, &mut notes);
+            let want = limit.unwrap_or(DEFAULT_LINES) as usize;
+            let lines: Vec<&str> = text.split_inclusive('\n').collect();
+            let shown = lines.iter().skip(offset as usize).take(want).copied().collect();
+            let end = (offset as usize + want).min(lines.len());
+            if end < lines.len() {
+                notes.push(format!("[Lines {}-{end}; continue with offset={end}]", offset + 1));
+            } else if bytes.len() as u64 + encoding.bom_len() < size {
+                notes.push(format!(
+                    "[Only the first {MAX_TEXT_BYTES} bytes of this UTF-16 file can be read by \
+                     lines; use unit=bytes for the rest]"
+                ));
+            }
+            shown
+        }
+        (Unit::Lines, _) => {
+            file.seek(SeekFrom::Start(encoding.bom_len()))?;
+            let mut reader = BufReader::new(file);
+            let mut line = Vec::new();
+            for _ in 0..offset {
+                line.clear();
+                if reader.read_until(b'\n', &mut line)? == 0 {
+                    break;
+                }
+            }
+            let want = limit.unwrap_or(DEFAULT_LINES);
+            let mut bytes = Vec::new();
+            let mut count = 0;
+            while count < want && (bytes.len() as u64) < MAX_TEXT_BYTES {
+                if reader.read_until(b'\n', &mut bytes)? == 0 {
+                    break;
+                }
+                count += 1;
+            }
+            let capped = bytes.len() as u64 > MAX_TEXT_BYTES;
+            bytes.truncate(MAX_TEXT_BYTES as usize);
+            let more = capped || !reader.fill_buf()?.is_empty();
+            if more {
+                let end = offset + count;
+                let mut note = format!("[Lines {}-{end}", offset + 1);
+                if capped {
+                    note.push_str(&format!(", the last one cut at {MAX_TEXT_BYTES} bytes"));
+                }
+                notes.push(note + &format!("; continue with offset={end}]"));
+            }
+            decode_noting(&bytes, encoding, &mut notes)
+        }
+    };
+
+    if notes.is_empty() {
+        return Ok(text);
+    }
+    let mut out = text;
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&notes.join("\n"));
+    Ok(out)
+}
+
+/// [`decode`], adding a note for anything but clean UTF-8.
+fn decode_noting(bytes: &[u8], encoding: Encoding, notes: &mut Vec<String>) -> String {
+    let (text, lossy) = decode(bytes, encoding);
+    if lossy {
+        notes.push(format!(
+            "[Not valid {}; undecodable bytes were replaced with {}]",
+            encoding.name(),
+            char::REPLACEMENT_CHARACTER
+        ));
+    } else if matches!(encoding, Encoding::Utf16Le | Encoding::Utf16Be) {
+        notes.push(format!("[Decoded from {}]", encoding.name()));
+    }
+    text
+}
+
+/// Largest image returned as an attachment (8 MiB), so the base64-encoded
+/// result stays under the 16 MiB IPC frame limit.
+const MAX_IMAGE_BYTES: usize = 8 * 1024 * 1024;
+
+/// MIME type for image files the chat can display, by extension.
+fn image_media_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Read an image file into an attachment result.
+async fn read_image(path: &Path, media_type: &str, ctx: &ToolContext) -> ToolResult {
+    match tokio::fs::read(path).await {
+        Ok(bytes) if bytes.len() > MAX_IMAGE_BYTES => ToolResult {
+            call_id: ctx.call_id,
+            output: format!("Error reading file: image is larger than 8 MiB ({} bytes)", bytes.len()),
+            is_error: true,
+            error_kind: Some(ToolErrorKind::InvalidArguments),
+            attachments: Vec::new(),
+        },
+        Ok(bytes) => {
+            let name = path
+                .file_name()
+                .map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+            ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Image {} ({media_type}, {} bytes)", path.display(), bytes.len()),
+                is_error: false,
+                error_kind: None,
+                attachments: vec![Attachment {
+                    name,
+                    media_type: media_type.to_owned(),
+                    content: AttachmentContent::Image {
+                        data: base64::engine::general_purpose::STANDARD.encode(&bytes),
+                    },
+                }],
+            }
+        }
+        Err(e) => ToolResult {
+            call_id: ctx.call_id,
+            output: format!("Error reading file: {e}"),
+            is_error: true,
+            error_kind: Some(ToolErrorKind::from_io(&e)),
+            attachments: Vec::new(),
+        },
+    }
+}
+
 #[async_trait]
 impl Tool for FileReadTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "file_read".to_string(),
-            description: "Read the contents of a file".to_string(),
+            description: format!(
+                "Read the contents of a file. Returns up to {DEFAULT_LINES} lines unless a \
+                 range is given, and never more than {} KiB; a note at the end says how to \
+                 continue. Binary files are shown as a short hexdump.",
+                MAX_TEXT_BYTES / 1024
+            ),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "path": {
                         "type": "string",
                         "description": "Absolute path to the file to read"
+                    },
+                    "offset": {
+                        "type": "integer",
+                        "description": "Lines (or bytes) to skip from the start (default: 0)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Lines (or bytes) to return"
+                    },
+                    "unit": {
+                        "type": "string",
+                        "enum": ["lines", "bytes"],
+                        "description": "What offset and limit count (default: lines)"
                     }
                 },
                 "required": ["path"]
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -38,19 +337,116 @@ impl Tool for FileReadTool {
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'path' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'path' argument"))?;
+
+        let unit = match args.get("unit").and_then(|v| v.as_str()) {
+            None | Some("lines") => Unit::Lines,
+            Some("bytes") => Unit::Bytes,
+            Some(other) => {
+                return Err(ToolError::invalid_arguments(format!("unknown unit '{other}'")));
+            }
+        };
+        let offset = args.get("offset").and_then(|v| v.as_u64()).unwrap_or(0);
+        let limit = args.get("limit").and_then(|v| v.as_u64());
+
+        if let Some(media_type) = image_media_type(Path::new(path)) {
+            return Ok(read_image(Path::new(path), media_type, ctx).await);
+        }
 
-        match tokio::fs::read_to_string(path).await {
+        let owned = Path::new(path).to_path_buf();
+        match tokio::task::spawn_blocking(move || read_range(&owned, unit, offset, limit)).await? {
             Ok(content) => Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: content,
                 is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: format!("Error reading file: {e}"),
                 is_error: true,
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
             }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write `content` to a new temporary file, read `unit` range
+    /// `offset`..`limit` of it and remove it again.
+    fn read(content: &[u8], unit: Unit, offset: u64, limit: Option<u64>) -> String {
+        let path = std::env::temp_dir().join(format!("aios-file-read-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, content).unwrap();
+        let text = read_range(&path, unit, offset, limit);
+        std::fs::remove_file(&path).unwrap();
+        text.unwrap()
+    }
+
+    #[test]
+    fn sniffs_byte_order_marks_and_binary_content() {
+        assert_eq!(sniff(b"plain text"), Some(Encoding::Utf8));
+        assert_eq!(sniff(b"\xEF\xBB\xBFtext"), Some(Encoding::Utf8Bom));
+        assert_eq!(sniff(b"\xFF\xFEt\0"), Some(Encoding::Utf16Le));
+        assert_eq!(sniff(b"\xFE\xFF\0t"), Some(Encoding::Utf16Be));
+        assert_eq!(sniff(b"\x7FELF\x02\x01\x01\0"), None);
+    }
+
+    #[test]
+    fn decodes_and_flags_invalid_bytes() {
+        assert_eq!(decode(b"caf\xC3\xA9", Encoding::Utf8), ("café".to_owned(), false));
+        assert_eq!(decode(b"caf\xE9", Encoding::Utf8), ("caf\u{FFFD}".to_owned(), true));
+        assert_eq!(decode(b"h\0i\0", Encoding::Utf16Le), ("hi".to_owned(), false));
+        assert_eq!(decode(b"\0h\0i", Encoding::Utf16Be), ("hi".to_owned(), false));
+        // A stray last byte of UTF-16 is dropped and flagged.
+        assert_eq!(decode(b"h\0i\0!", Encoding::Utf16Le), ("hi".to_owned(), true));
+    }
+
+    #[test]
+    fn reads_line_ranges_with_a_note_on_where_to_continue() {
+        let content = b"one\ntwo\nthree\nfour\n";
+        assert_eq!(read(content, Unit::Lines, 0, None), "one\ntwo\nthree\nfour\n");
+        assert_eq!(
+            read(content, Unit::Lines, 1, Some(2)),
+            "two\nthree\n[Lines 2-3; continue with offset=3]"
+        );
+        assert_eq!(read(content, Unit::Lines, 3, Some(5)), "four\n");
+        assert_eq!(read(content, Unit::Lines, 10, None), "");
+    }
+
+    #[test]
+    fn reads_byte_ranges_past_a_byte_order_mark() {
+        let content = b"\xEF\xBB\xBFabcdef";
+        assert_eq!(
+            read(content, Unit::Bytes, 0, Some(2)),
+            "ab\n[Bytes 3-5 of 9; continue with offset=5]"
+        );
+        assert_eq!(read(content, Unit::Bytes, 7, None), "ef\n[Bytes 7-9 of 9]");
+    }
+
+    #[test]
+    fn reads_utf16_by_lines() {
+        let content: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("a\nb\nc\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert_eq!(
+            read(&content, Unit::Lines, 1, Some(1)),
+            "b\n[Decoded from UTF-16LE]\n[Lines 2-2; continue with offset=2]"
+        );
+    }
+
+    #[test]
+    fn binary_files_get_a_hexdump() {
+        let text = read(b"\x7FELF\0\x01", Unit::Lines, 0, None);
+        assert_eq!(
+            text,
+            "[Binary file, 6 bytes; not shown as text. First 6 bytes:]\n\
+             00000000  7f 45 4c 46 00 01                                .ELF..\n"
+        );
+    }
+}
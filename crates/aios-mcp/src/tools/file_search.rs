@@ -1,20 +1,37 @@
 //! Search for files by name pattern in a directory tree.
 
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
 
 use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
+use ignore::{WalkBuilder, WalkState};
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// Directories never searched: version control data and dependency trees
+/// that are rarely what the user means and can be huge.
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules"];
+
+/// Levels searched below the root unless the caller asks otherwise.
+const DEFAULT_DEPTH: usize = 12;
+
+/// Matches collected before sorting; a search this broad is cut short.
+const MAX_COLLECTED: usize = 20_000;
 
 /// Recursively searches a directory tree for files whose names match a glob-like
 /// pattern (simple `*` wildcard only).
+///
+/// The walk runs on several threads, skips what `.gitignore` and `.ignore`
+/// files exclude, stays on the root's filesystem so network mounts are not
+/// crawled, and survives symlink loops.
 pub struct FileSearchTool;
 
 /// Check whether `name` matches `pattern` with simple `*` wildcard support.
-fn matches_pattern(name: &str, pattern: &str) -> bool {
+pub(crate) fn matches_pattern(name: &str, pattern: &str) -> bool {
     if pattern == "*" {
         return true;
     }
@@ -49,29 +66,85 @@ fn matches_pattern(name: &str, pattern: &str) -> bool {
     true
 }
 
-/// Recursively walk `dir` collecting paths whose file name matches `pattern`.
-fn walk_dir(dir: &Path, pattern: &str, results: &mut Vec<String>, max: usize) {
-    if results.len() >= max {
-        return;
-    }
-    let entries = match std::fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-    for entry in entries.flatten() {
-        if results.len() >= max {
-            return;
-        }
-        let path = entry.path();
-        if let Some(name) = path.file_name().and_then(|n| n.to_str())
-            && matches_pattern(name, pattern)
-        {
-            results.push(path.to_string_lossy().to_string());
-        }
-        if path.is_dir() {
-            walk_dir(&path, pattern, results, max);
-        }
+/// Options of one search.
+#[derive(Debug, Clone)]
+struct Search {
+    pattern: String,
+    max_results: usize,
+    max_depth: usize,
+    include_hidden: bool,
+    respect_ignore: bool,
+    same_filesystem: bool,
+    follow_symlinks: bool,
+    /// Stop at the first `max_results` matches found instead of returning
+    /// the first ones in path order.
+    stream: bool,
+}
+
+/// Matches found, and whether more were left out.
+struct Found {
+    paths: Vec<String>,
+    truncated: bool,
+}
+
+/// Walk `root` in parallel and collect the paths whose file name matches.
+/// `.git` and `node_modules` are never entered. Blocking.
+fn search(root: &Path, options: &Search) -> Found {
+    let walker = WalkBuilder::new(root)
+        .max_depth(Some(options.max_depth))
+        .hidden(!options.include_hidden)
+        .ignore(options.respect_ignore)
+        .git_ignore(options.respect_ignore)
+        .git_global(options.respect_ignore)
+        .git_exclude(options.respect_ignore)
+        .parents(options.respect_ignore)
+        // .gitignore files count outside repositories too.
+        .require_git(false)
+        .same_file_system(options.same_filesystem)
+        // Loops through symlinks are detected and skipped.
+        .follow_links(options.follow_symlinks)
+        .filter_entry(|entry| {
+            !(entry.file_type().is_some_and(|t| t.is_dir())
+                && SKIPPED_DIRS.iter().any(|dir| entry.file_name() == *dir))
+        })
+        .build_parallel();
+
+    let limit = if options.stream { options.max_results } else { MAX_COLLECTED };
+    let found = Mutex::new(Vec::new());
+    let full = AtomicBool::new(false);
+    let (found_ref, full_ref) = (&found, &full);
+    walker.run(move || {
+        Box::new(move |entry| {
+            if full_ref.load(Ordering::Relaxed) {
+                return WalkState::Quit;
+            }
+            // Unreadable directories and symlink loops are skipped.
+            let Ok(entry) = entry else {
+                return WalkState::Continue;
+            };
+            if entry.depth() > 0
+                && let Some(name) = entry.file_name().to_str()
+                && matches_pattern(name, &options.pattern)
+            {
+                let mut found = found_ref.lock().unwrap_or_else(PoisonError::into_inner);
+                found.push(entry.path().to_string_lossy().into_owned());
+                if found.len() >= limit {
+                    full_ref.store(true, Ordering::Relaxed);
+                    return WalkState::Quit;
+                }
+            }
+            WalkState::Continue
+        })
+    });
+
+    let mut paths = found.into_inner().unwrap_or_else(PoisonError::into_inner);
+    let mut truncated = full.into_inner();
+    if !options.stream {
+        paths.sort();
+        truncated |= paths.len() > options.max_results;
+        paths.truncate(options.max_results);
     }
+    Found { paths, truncated }
 }
 
 #[async_trait]
@@ -94,11 +167,42 @@ impl Tool for FileSearchTool {
                     "max_results": {
                         "type": "integer",
                         "description": "Maximum number of results to return (default: 100)"
+                    },
+                    "max_depth": {
+                        "type": "integer",
+                        "description": format!(
+                            "Levels to descend below path (default: {DEFAULT_DEPTH})"
+                        )
+                    },
+                    "include_hidden": {
+                        "type": "boolean",
+                        "description": "Search hidden files and directories (default: false)"
+                    },
+                    "respect_ignore": {
+                        "type": "boolean",
+                        "description": "Skip what .gitignore and .ignore files exclude \
+                            (default: true)"
+                    },
+                    "same_filesystem": {
+                        "type": "boolean",
+                        "description": "Stay on the filesystem of path, skipping mounts such \
+                            as network shares (default: true)"
+                    },
+                    "follow_symlinks": {
+                        "type": "boolean",
+                        "description": "Descend into symlinked directories (default: false)"
+                    },
+                    "stream": {
+                        "type": "boolean",
+                        "description": "Return the first matches found as soon as there are \
+                            max_results, instead of the first ones in path order; faster on \
+                            big trees (default: false)"
                     }
                 },
                 "required": ["path", "pattern"]
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -110,38 +214,51 @@ impl Tool for FileSearchTool {
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'path' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'path' argument"))?;
 
         let pattern = args
             .get("pattern")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'pattern' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'pattern' argument"))?;
 
-        let max_results = args
-            .get("max_results")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(100) as usize;
+        let flag = |key: &str, default: bool| {
+            args.get(key).and_then(|v| v.as_bool()).unwrap_or(default)
+        };
+        let options = Search {
+            pattern: pattern.to_string(),
+            max_results: args
+                .get("max_results")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(100) as usize,
+            max_depth: args
+                .get("max_depth")
+                .and_then(|v| v.as_u64())
+                .map_or(DEFAULT_DEPTH, |d| d as usize),
+            include_hidden: flag("include_hidden", false),
+            respect_ignore: flag("respect_ignore", true),
+            same_filesystem: flag("same_filesystem", true),
+            follow_symlinks: flag("follow_symlinks", false),
+            stream: flag("stream", false),
+        };
 
         let root = Path::new(path).to_path_buf();
-        let pattern_owned = pattern.to_string();
 
-        // Run blocking walk on a dedicated thread to avoid blocking the runtime.
-        let results =
-            tokio::task::spawn_blocking(move || {
-                let mut results = Vec::new();
-                walk_dir(&root, &pattern_owned, &mut results, max_results);
-                results
-            })
-            .await
-            .unwrap_or_default();
+        // Run blocking walk on dedicated threads to avoid blocking the runtime.
+        let found = tokio::task::spawn_blocking(move || search(&root, &options)).await?;
 
-        let output = serde_json::to_string_pretty(&results)
-            .unwrap_or_else(|e| format!("Error serializing results: {e}"));
+        let output = serde_json::to_string_pretty(&json!({
+            "matches": found.paths,
+            // More files matched than were returned.
+            "truncated": found.truncated,
+        }))
+        .unwrap_or_else(|e| format!("Error serializing results: {e}"));
 
         Ok(ToolResult {
             call_id: ctx.call_id,
             output,
             is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
         })
     }
 }
@@ -1,22 +1,154 @@
 //! Write content to a file.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
+use crate::undo::{self, UndoAction};
 
-/// Writes the given content to a file, creating it if it does not exist and
-/// overwriting it if it does.
+/// Writes the given content to a file: replacing it, appending to it, or
+/// only creating a new one.
+///
+/// Replacing goes through a temporary file renamed over the target, so a
+/// crash leaves the old or the new content, never half of it; a symlink is
+/// followed, so the file it points to gets the content. Changes are
+/// recorded in the [`undo`] journal; replacing an existing file can keep
+/// the old content in a `.bak` file next to it for that.
 pub struct FileWriteTool;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Overwrite,
+    Append,
+    /// Fail if the file exists.
+    Create,
+}
+
+/// `path` with `suffix` added to its file name.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// `<path>.bak`, or `<path>.bak.1`, `<path>.bak.2` and so on when the
+/// user has such a file already.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = with_suffix(path, ".bak");
+    let mut n = 0;
+    while backup.symlink_metadata().is_ok() {
+        n += 1;
+        backup = with_suffix(path, &format!(".bak.{n}"));
+    }
+    backup
+}
+
+/// Replace `path` with `content` through a temporary file next to the
+/// file it resolves to, keeping the permissions of the file it replaces.
+/// The temporary file has them from the start, so a private file is never
+/// readable by others in between.
+fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    // Renaming over a symlink would replace the link, not its target.
+    let path = &fs::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+    let permissions = fs::metadata(path).ok().map(|metadata| metadata.permissions());
+    let temp = with_suffix(path, &format!(".aios-tmp-{}", uuid::Uuid::new_v4()));
+    let result = (|| {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        if let Some(permissions) = &permissions {
+            options.mode(permissions.mode());
+        }
+        let mut file = options.open(&temp)?;
+        if let Some(permissions) = permissions {
+            // The umask may have taken bits off the mode asked for.
+            file.set_permissions(permissions)?;
+        }
+        file.write_all(content)?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Write `content` to `path` in `mode` and record how to undo it. Returns
+/// the backup made, if any. Blocking.
+fn write(path: &Path, content: &[u8], mode: Mode, backup: bool) -> io::Result<Option<PathBuf>> {
+    let (action, backup_path) = write_file(path, content, mode, backup)?;
+    if let Some(action) = action
+        && let Err(e) = undo::record("file_write", action)
+    {
+        tracing::warn!(error = %e, "Failed to record file_write in the undo journal");
+    }
+    Ok(backup_path)
+}
+
+/// Write `content` to `path` in `mode`. Returns how to undo it and the
+/// backup made, if any.
+fn write_file(
+    path: &Path,
+    content: &[u8],
+    mode: Mode,
+    backup: bool,
+) -> io::Result<(Option<UndoAction>, Option<PathBuf>)> {
+    let existed = path.exists();
+    let (action, backup_path) = match mode {
+        Mode::Create => {
+            let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+            file.write_all(content)?;
+            file.sync_all()?;
+            (Some(UndoAction::Remove { path: path.to_owned() }), None)
+        }
+        Mode::Append => {
+            let len = if existed { fs::metadata(path)?.len() } else { 0 };
+            let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+            file.write_all(content)?;
+            file.sync_all()?;
+            let action = if existed {
+                UndoAction::Truncate { path: path.to_owned(), len }
+            } else {
+                UndoAction::Remove { path: path.to_owned() }
+            };
+            (Some(action), None)
+        }
+        Mode::Overwrite => {
+            let backup_path = (existed && backup).then(|| backup_path(path));
+            if let Some(backup_path) = &backup_path {
+                fs::copy(path, backup_path)?;
+            }
+            write_atomic(path, content)?;
+            let action = match &backup_path {
+                Some(backup) => Some(UndoAction::Restore {
+                    path: path.to_owned(),
+                    backup: backup.clone(),
+                }),
+                None if !existed => Some(UndoAction::Remove { path: path.to_owned() }),
+                // Overwritten without a backup: nothing to go back to.
+                None => None,
+            };
+            (action, backup_path)
+        }
+    };
+    Ok((action, backup_path))
+}
+
 #[async_trait]
 impl Tool for FileWriteTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "file_write".to_string(),
-            description: "Write content to a file (creates or overwrites)".to_string(),
+            description: "Write content to a file: overwrite (default), append, or create a \
+                new file only"
+                .to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
@@ -27,11 +159,23 @@ impl Tool for FileWriteTool {
                     "content": {
                         "type": "string",
                         "description": "Content to write to the file"
+                    },
+                    "mode": {
+                        "type": "string",
+                        "enum": ["overwrite", "append", "create"],
+                        "description": "overwrite replaces the file, append adds to its end, \
+                            create fails if the file exists (default: overwrite)"
+                    },
+                    "backup": {
+                        "type": "boolean",
+                        "description": "When overwriting, keep the old content as <path>.bak \
+                            (default: true)"
                     }
                 },
                 "required": ["path", "content"]
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -43,24 +187,138 @@ impl Tool for FileWriteTool {
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'path' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'path' argument"))?;
 
         let content = args
             .get("content")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'content' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'content' argument"))?;
 
-        match tokio::fs::write(path, content).await {
-            Ok(()) => Ok(ToolResult {
-                call_id: ctx.call_id,
-                output: format!("Successfully wrote {} bytes to {path}", content.len()),
-                is_error: false,
-            }),
+        let mode = match args.get("mode").and_then(|v| v.as_str()) {
+            None | Some("overwrite") => Mode::Overwrite,
+            Some("append") => Mode::Append,
+            Some("create") => Mode::Create,
+            Some(other) => {
+                return Err(ToolError::invalid_arguments(format!("unknown mode '{other}'")));
+            }
+        };
+        let backup = args.get("backup").and_then(|v| v.as_bool()).unwrap_or(true);
+
+        let target = PathBuf::from(path);
+        let bytes = content.as_bytes().to_vec();
+        let written =
+            tokio::task::spawn_blocking(move || write(&target, &bytes, mode, backup)).await?;
+
+        match written {
+            Ok(backup_path) => {
+                let verb = if mode == Mode::Append { "appended" } else { "wrote" };
+                let mut output = format!("Successfully {verb} {} bytes to {path}", content.len());
+                if let Some(backup_path) = backup_path {
+                    let backup_path = backup_path.display();
+                    output.push_str(&format!("; previous content saved to {backup_path}"));
+                }
+                Ok(ToolResult {
+                    call_id: ctx.call_id,
+                    output,
+                    is_error: false,
+                    error_kind: None,
+                    attachments: Vec::new(),
+                })
+            }
             Err(e) => Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: format!("Error writing file: {e}"),
                 is_error: true,
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
             }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aios-file-write-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn create_fails_on_existing_files_and_undoes_by_removing() {
+        let dir = temp_dir();
+        let path = dir.join("new.txt");
+        let (action, backup) = write_file(&path, b"hello", Mode::Create, true).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"hello");
+        assert_eq!(backup, None);
+        let error = write_file(&path, b"again", Mode::Create, true).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+
+        action.unwrap().apply().unwrap();
+        assert!(!path.exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn append_undoes_by_truncating() {
+        let dir = temp_dir();
+        let path = dir.join("log.txt");
+        fs::write(&path, "one\n").unwrap();
+        let (action, _) = write_file(&path, b"two\n", Mode::Append, true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+
+        action.unwrap().apply().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn overwrite_keeps_user_backups_and_undoes_from_its_own() {
+        let dir = temp_dir();
+        let path = dir.join("notes.txt");
+        fs::write(&path, "old").unwrap();
+        fs::write(dir.join("notes.txt.bak"), "user's backup").unwrap();
+
+        let (action, backup) = write_file(&path, b"new", Mode::Overwrite, true).unwrap();
+        assert_eq!(backup.as_deref(), Some(dir.join("notes.txt.bak.1").as_path()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_to_string(dir.join("notes.txt.bak")).unwrap(), "user's backup");
+
+        action.unwrap().apply().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn overwrite_without_backup_cannot_be_undone() {
+        let dir = temp_dir();
+        let path = dir.join("notes.txt");
+        fs::write(&path, "old").unwrap();
+        let (action, backup) = write_file(&path, b"new", Mode::Overwrite, false).unwrap();
+        assert_eq!((action, backup), (None, None));
+
+        let created = dir.join("created.txt");
+        let (action, _) = write_file(&created, b"new", Mode::Overwrite, true).unwrap();
+        assert_eq!(action, Some(UndoAction::Remove { path: created }));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn overwrite_follows_symlinks_and_keeps_the_mode() {
+        let dir = temp_dir();
+        let target = dir.join("config");
+        let link = dir.join("link");
+        fs::write(&target, "old").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o600)).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_file(&link, b"new", Mode::Overwrite, false).unwrap();
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(fs::metadata(&target).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
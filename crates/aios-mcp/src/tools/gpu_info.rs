@@ -0,0 +1,60 @@
+//! Report GPU load and video memory.
+
+use aios_common::{gpu, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext};
+
+/// Lists NVIDIA (`nvidia-smi`) and AMD (amdgpu sysfs) GPUs with their
+/// utilization, video memory, temperature and power draw, as JSON.
+pub struct GpuInfoTool;
+
+#[async_trait]
+impl Tool for GpuInfoTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "gpu_info".to_string(),
+            description: "Get GPU load as JSON: [{name, utilization, memory_used, memory_total, \
+                celsius, watts}], one entry per NVIDIA or AMD GPU. utilization is the percent \
+                of recent time the GPU was busy; memory sizes are in bytes. Fields the driver \
+                does not report are left out. Use it to answer whether the GPU is busy or a \
+                local model fits in video memory."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let gpus = gpu::gpus().await;
+        if gpus.is_empty() {
+            return Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: "No NVIDIA or AMD GPU found: nvidia-smi is missing or no driver \
+                    reports GPU load"
+                    .to_owned(),
+                is_error: true,
+                error_kind: Some(ToolErrorKind::Unavailable),
+                attachments: Vec::new(),
+            });
+        }
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output: serde_json::to_string_pretty(&gpus)?,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
+    }
+}
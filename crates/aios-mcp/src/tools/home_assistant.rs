@@ -0,0 +1,222 @@
+//! Control the smart home through the Home Assistant REST API.
+
+use std::time::Duration;
+
+use aios_common::{
+    HomeAssistantConfig, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::executor::{Tool, ToolContext, ToolError};
+
+/// Time a Home Assistant request may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Most entities listed at once.
+const MAX_ENTITIES: usize = 200;
+
+/// Lists entities, reads their state and calls services (turn on a light,
+/// set the thermostat) on the Home Assistant instance from
+/// `[home_assistant]`.
+pub struct HomeAssistantTool;
+
+#[async_trait]
+impl Tool for HomeAssistantTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "home_assistant".to_string(),
+            description: "Control the smart home through Home Assistant: list entities \
+                          (lights, switches, climate, sensors), read one entity's state, or \
+                          call a service such as light.turn_on or climate.set_temperature"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["list_entities", "get_state", "call_service"],
+                        "description": "What to do"
+                    },
+                    "domain": {
+                        "type": "string",
+                        "description": "Entity domain, e.g. light or climate: filters list_entities, \
+                                        and is the service domain for call_service"
+                    },
+                    "entity_id": {
+                        "type": "string",
+                        "description": "Entity, e.g. light.kitchen (get_state, call_service)"
+                    },
+                    "service": {
+                        "type": "string",
+                        "description": "Service name, e.g. turn_on (call_service)"
+                    },
+                    "data": {
+                        "type": "object",
+                        "description": "Service data, e.g. {\"brightness_pct\": 50} (call_service)"
+                    }
+                },
+                "required": ["action"]
+            }),
+            trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::Confirm
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let config = HomeAssistantConfig::load();
+        let (output, error_kind) = if !config.is_configured() {
+            (
+                "Home Assistant is not configured; set url and token in [home_assistant]"
+                    .to_owned(),
+                Some(ToolErrorKind::Unavailable),
+            )
+        } else {
+            match run(&config, &args).await {
+                Ok(output) => (output, None),
+                Err(e) => (
+                    format!("Home Assistant request failed: {e:#}"),
+                    Some(ToolError::kind_of(&e)),
+                ),
+            }
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: error_kind.is_some(),
+            error_kind,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+async fn run(config: &HomeAssistantConfig, args: &Value) -> Result<String> {
+    let str_arg = |key: &str| {
+        args.get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+    };
+    let client = HomeAssistant::new(config)?;
+
+    match str_arg("action").unwrap_or_default() {
+        "list_entities" => {
+            let states = client.get("states").await?;
+            Ok(list_entities(&states, str_arg("domain")).to_string())
+        }
+        "get_state" => {
+            let entity_id = str_arg("entity_id")
+                .ok_or_else(|| ToolError::invalid_arguments("missing 'entity_id' argument"))?;
+            let state = client.get(&format!("states/{entity_id}")).await?;
+            Ok(state.to_string())
+        }
+        "call_service" => {
+            let entity_id = str_arg("entity_id");
+            let domain = str_arg("domain")
+                .or_else(|| {
+                    entity_id
+                        .and_then(|id| id.split_once('.'))
+                        .map(|(domain, _)| domain)
+                })
+                .ok_or_else(|| ToolError::invalid_arguments("missing 'domain' argument"))?;
+            let service = str_arg("service")
+                .ok_or_else(|| ToolError::invalid_arguments("missing 'service' argument"))?;
+            let mut data = match args.get("data") {
+                Some(Value::Object(data)) => data.clone(),
+                Some(Value::Null) | None => serde_json::Map::new(),
+                Some(_) => return Err(ToolError::invalid_arguments("'data' must be an object")),
+            };
+            if let Some(entity_id) = entity_id {
+                data.insert("entity_id".to_owned(), json!(entity_id));
+            }
+            let changed = client
+                .post(
+                    &format!("services/{domain}/{service}"),
+                    &Value::Object(data),
+                )
+                .await?;
+            Ok(json!({ "called": format!("{domain}.{service}"), "changed": changed }).to_string())
+        }
+        other => Err(ToolError::invalid_arguments(format!("unknown action '{other}'"))),
+    }
+}
+
+/// Entity ids, states and names from `GET /api/states`, without the other
+/// attributes, optionally only those of `domain`.
+fn list_entities(states: &Value, domain: Option<&str>) -> Value {
+    let entities: Vec<Value> = states
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entity| {
+            let id = entity["entity_id"].as_str().unwrap_or_default();
+            domain.is_none_or(|domain| id.split_once('.').is_some_and(|(d, _)| d == domain))
+        })
+        .take(MAX_ENTITIES)
+        .map(|entity| {
+            json!({
+                "entity_id": entity["entity_id"],
+                "state": entity["state"],
+                "name": entity["attributes"]["friendly_name"],
+            })
+        })
+        .collect();
+    Value::Array(entities)
+}
+
+/// Authenticated client for `<url>/api`.
+struct HomeAssistant {
+    client: reqwest::Client,
+    base: String,
+    token: String,
+}
+
+impl HomeAssistant {
+    fn new(config: &HomeAssistantConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            base: format!("{}/api", config.url.trim().trim_end_matches('/')),
+            token: config.token.trim().to_owned(),
+        })
+    }
+
+    async fn get(&self, path: &str) -> Result<Value> {
+        let request = self.client.get(format!("{}/{path}", self.base));
+        self.send(request).await
+    }
+
+    async fn post(&self, path: &str, body: &Value) -> Result<Value> {
+        let request = self.client.post(format!("{}/{path}", self.base)).json(body);
+        self.send(request).await
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = request.bearer_auth(&self.token).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let kind = match status {
+                reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                    ToolErrorKind::PermissionDenied
+                }
+                reqwest::StatusCode::NOT_FOUND => ToolErrorKind::NotFound,
+                reqwest::StatusCode::BAD_REQUEST => ToolErrorKind::InvalidArguments,
+                _ => ToolErrorKind::Failed,
+            };
+            return Err(ToolError {
+                kind,
+                message: format!("{status}: {}", body.trim()),
+            }
+            .into());
+        }
+        Ok(response.json().await?)
+    }
+}
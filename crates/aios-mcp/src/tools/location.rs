@@ -0,0 +1,61 @@
+//! Look up the approximate location of the machine.
+
+use aios_common::{geoclue, Sensor, ToolDefinition, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext};
+
+/// Asks GeoClue where the machine is, at city accuracy and rounded to about
+/// a kilometer. Every call is confirmed and shows the dock's location badge.
+pub struct LocationTool;
+
+#[async_trait]
+impl Tool for LocationTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "location".to_string(),
+            description: "Get the approximate location of this computer as JSON: {latitude, \
+                longitude, accuracy, description}, rounded to about a kilometer; accuracy is in \
+                meters. Use it for the weather, sunrise and sunset, local opening hours or the \
+                place of a calendar event when the user does not say where they are. The user \
+                confirms every call."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::Confirm
+    }
+
+    fn sensors(&self) -> &'static [Sensor] {
+        &[Sensor::Location]
+    }
+
+    async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        Ok(match geoclue::locate().await {
+            Ok(location) => ToolResult {
+                call_id: ctx.call_id,
+                output: serde_json::to_string_pretty(&location)?,
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            },
+            Err(e) => ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Failed to get the location: {e}"),
+                is_error: true,
+                error_kind: Some(e.tool_kind()),
+                attachments: Vec::new(),
+            },
+        })
+    }
+}
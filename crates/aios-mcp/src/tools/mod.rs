@@ -1,15 +1,26 @@
 //! Built-in tool implementations.
 
+pub mod audit_history;
 pub mod brightness;
 pub mod browser;
+pub mod calendar;
+pub mod contacts;
+pub mod datetime;
 pub mod file_delete;
 pub mod file_list;
 pub mod file_read;
 pub mod file_search;
 pub mod file_write;
+pub mod gpu_info;
+pub mod home_assistant;
+pub mod location;
+pub mod net_usage;
 pub mod open_url;
 pub mod shell_exec;
+pub mod shell_session;
 pub mod system_info;
+pub mod trash_restore;
+pub mod undo_last;
 pub mod volume;
 pub mod wifi_connect;
 pub mod wifi_list;
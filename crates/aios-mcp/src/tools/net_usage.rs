@@ -0,0 +1,264 @@
+//! Report network bandwidth per interface and per process.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::process::Command;
+use tokio::time::Instant;
+
+use crate::executor::{Tool, ToolContext};
+
+/// Sampling window unless the caller asks for another one, in seconds.
+const DEFAULT_SECONDS: u64 = 2;
+
+/// Upper bound on `seconds`; the user waits for the whole window.
+const MAX_SECONDS: u64 = 10;
+
+/// Processes listed unless the caller asks for another number.
+const DEFAULT_TOP: usize = 5;
+
+/// Upper bound on `top`, keeping the result small.
+const MAX_TOP: usize = 25;
+
+/// Time `nethogs` gets beyond the window before it is killed.
+const NETHOGS_GRACE: Duration = Duration::from_secs(5);
+
+/// Samples `/proc/net/dev` twice for the traffic of each interface and, if
+/// `nethogs` is installed and may capture packets, lists the processes
+/// moving the most data in the same window.
+pub struct NetUsageTool;
+
+#[derive(Debug, Serialize)]
+struct Usage {
+    seconds: u64,
+    /// Busiest first.
+    interfaces: Vec<InterfaceUsage>,
+    /// Busiest first; left out when `nethogs` could not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processes: Option<Vec<ProcessUsage>>,
+    /// Why `processes` is left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processes_unavailable: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct InterfaceUsage {
+    name: String,
+    /// Bytes per second received during the window.
+    rx_rate: u64,
+    /// Bytes per second sent during the window.
+    tx_rate: u64,
+    /// Bytes received since the interface came up.
+    rx_total: u64,
+    /// Bytes sent since the interface came up.
+    tx_total: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ProcessUsage {
+    /// `0` for traffic nethogs cannot tie to a process.
+    pid: u32,
+    name: String,
+    rx_rate: u64,
+    tx_rate: u64,
+}
+
+#[async_trait]
+impl Tool for NetUsageTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "net_usage".to_string(),
+            description: "Measure network bandwidth over a few seconds, as JSON: interfaces \
+                [{name, rx_rate, tx_rate, rx_total, tx_total}] and processes [{pid, name, \
+                rx_rate, tx_rate}], busiest first. Rates are bytes per second, totals bytes \
+                since the interface came up. Processes need nethogs with permission to capture \
+                packets; without it processes_unavailable says why. Use it to answer what is \
+                using the bandwidth."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "seconds": {
+                        "type": "integer",
+                        "description": format!(
+                            "Length of the measurement (default: {DEFAULT_SECONDS}, max: \
+                             {MAX_SECONDS})"
+                        )
+                    },
+                    "top": {
+                        "type": "integer",
+                        "description": format!(
+                            "Number of processes to list (default: {DEFAULT_TOP}, max: {MAX_TOP})"
+                        )
+                    }
+                },
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let seconds = args
+            .get("seconds")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_SECONDS, |n| n.clamp(1, MAX_SECONDS));
+        let top = args
+            .get("top")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_TOP, |n| (n as usize).min(MAX_TOP));
+
+        let (interfaces, processes) = tokio::join!(interfaces(seconds), nethogs(seconds));
+        let (processes, processes_unavailable) = match processes {
+            Ok(mut processes) => {
+                processes.truncate(top);
+                (Some(processes), None)
+            }
+            Err(reason) => (None, Some(reason)),
+        };
+        let usage = Usage {
+            seconds,
+            interfaces,
+            processes,
+            processes_unavailable,
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output: serde_json::to_string_pretty(&usage)?,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+/// Traffic of every interface but loopback over `seconds`.
+async fn interfaces(seconds: u64) -> Vec<InterfaceUsage> {
+    let started = Instant::now();
+    let before = counters().await;
+    tokio::time::sleep(Duration::from_secs(seconds)).await;
+    let after = counters().await;
+    let elapsed = started.elapsed().as_secs_f64().max(0.001);
+
+    let rate = |from: u64, to: u64| (to.saturating_sub(from) as f64 / elapsed) as u64;
+    let mut usage: Vec<_> = after
+        .into_iter()
+        .filter(|(name, ..)| name != "lo")
+        .map(|(name, rx_total, tx_total)| {
+            // An interface that came up during the window counts from zero.
+            let (rx_before, tx_before) = before
+                .iter()
+                .find(|(n, ..)| *n == name)
+                .map_or((0, 0), |(_, rx, tx)| (*rx, *tx));
+            InterfaceUsage {
+                rx_rate: rate(rx_before, rx_total),
+                tx_rate: rate(tx_before, tx_total),
+                name,
+                rx_total,
+                tx_total,
+            }
+        })
+        .collect();
+    usage.sort_by_key(|i| std::cmp::Reverse(i.rx_rate + i.tx_rate));
+    usage
+}
+
+/// `/proc/net/dev`: two header lines, then
+/// `  eth0: 1234 56 0 0 0 0 0 0 5678 90 0 0 0 0 0 0` with the received
+/// bytes first and the sent bytes ninth. Returns `(name, received, sent)`.
+async fn counters() -> Vec<(String, u64, u64)> {
+    let dev = tokio::fs::read_to_string("/proc/net/dev")
+        .await
+        .unwrap_or_default();
+    dev.lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (name, fields) = line.split_once(':')?;
+            let fields: Vec<&str> = fields.split_whitespace().collect();
+            Some((
+                name.trim().to_owned(),
+                fields.first()?.parse().ok()?,
+                fields.get(8)?.parse().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Per-process traffic from `nethogs` in trace mode, one refresh a second
+/// for `seconds`; the reason on failure.
+async fn nethogs(seconds: u64) -> Result<Vec<ProcessUsage>, String> {
+    let cycles = seconds.to_string();
+    let output = Command::new("nethogs")
+        .args(["-t", "-d", "1", "-c", &cycles])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let limit = Duration::from_secs(seconds) + NETHOGS_GRACE;
+    let output = match tokio::time::timeout(limit, output).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err("nethogs is not installed".to_owned());
+        }
+        Ok(Err(e)) => return Err(format!("cannot run nethogs: {e}")),
+        Err(_) => return Err("nethogs did not finish in time".to_owned()),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().find(|l| !l.trim().is_empty()).unwrap_or_default();
+        return Err(format!(
+            "nethogs failed ({reason}); it needs root or the cap_net_admin and cap_net_raw \
+             capabilities"
+        ));
+    }
+    Ok(parse_nethogs(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// The last refresh of `nethogs -t`:
+///
+/// ```text
+/// Refreshing:
+/// /usr/lib/firefox/firefox/4242/1000    1.25    310.5
+/// unknown TCP/0/0    0    0
+/// ```
+///
+/// Each line is `program/pid/uid`, then sent and received KB/s, separated
+/// by tabs. Idle processes are left out.
+fn parse_nethogs(output: &str) -> Vec<ProcessUsage> {
+    let last = output.rsplit("Refreshing:").next().unwrap_or_default();
+    let kilobytes = |value: &str| value.trim().parse::<f64>().map(|kb| (kb * 1024.0) as u64);
+    let mut processes: Vec<_> = last
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let process = fields.next()?;
+            let tx_rate = kilobytes(fields.next()?).ok()?;
+            let rx_rate = kilobytes(fields.next()?).ok()?;
+            // The program path has slashes of its own.
+            let mut parts = process.rsplitn(3, '/');
+            let _uid = parts.next()?;
+            let pid = parts.next()?.parse().ok()?;
+            let program = parts.next()?;
+            let name = program.rsplit('/').next().unwrap_or(program).to_owned();
+            Some(ProcessUsage {
+                pid,
+                name,
+                rx_rate,
+                tx_rate,
+            })
+        })
+        .filter(|p| p.rx_rate + p.tx_rate > 0)
+        .collect();
+    processes.sort_by_key(|p| std::cmp::Reverse(p.rx_rate + p.tx_rate));
+    processes
+}
@@ -1,32 +1,118 @@
-//! Open a URL in the default browser.
+//! Open a URL in the browser.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::process::Stdio;
+
+use aios_common::{
+    BrowserConfig, BrowserProfile, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement,
+};
 use anyhow::Result;
 use async_trait::async_trait;
+use reqwest::Url;
 use serde_json::{json, Value};
+use tokio::process::Command;
+
+use crate::executor::{Tool, ToolContext, ToolError};
 
-use crate::executor::{Tool, ToolContext};
+/// Browser of the agent's profile when `[browser]` names none.
+const DEFAULT_AGENT_BROWSER: &str = "chromium";
 
-/// Opens a URL in Chromium (or another configured browser).
+/// Opens a URL in the browser configured under `[browser]`: the user's own
+/// profile, or one kept apart for the agent. Only the allowed schemes are
+/// opened, never `file:` or `javascript:`.
 pub struct OpenUrlTool;
 
+/// `url` normalized, or why it may not be opened.
+pub(crate) fn check_url(
+    url: &str,
+    config: &BrowserConfig,
+) -> Result<Url, (String, ToolErrorKind)> {
+    let parsed = Url::parse(url.trim()).map_err(|e| {
+        (
+            format!("Not a valid URL ({e}); include the scheme, e.g. https://"),
+            ToolErrorKind::InvalidArguments,
+        )
+    })?;
+    let scheme = parsed.scheme();
+    if !config.allows_scheme(scheme) {
+        return Err((
+            format!("Opening {scheme}: URLs is not allowed"),
+            ToolErrorKind::PermissionDenied,
+        ));
+    }
+    if matches!(scheme, "http" | "https") && parsed.host_str().is_none_or(str::is_empty) {
+        return Err((format!("No host in {url}"), ToolErrorKind::InvalidArguments));
+    }
+    Ok(parsed)
+}
+
+/// The command opening `url` in `profile`.
+fn browser_command(config: &BrowserConfig, profile: BrowserProfile, url: &Url) -> Command {
+    let mut cmd = match (profile, config.command.as_str()) {
+        (BrowserProfile::User, "") => {
+            let mut cmd = Command::new("xdg-open");
+            cmd.arg(url.as_str());
+            return cmd;
+        }
+        (BrowserProfile::User, program) => Command::new(program),
+        (BrowserProfile::Agent, program) => {
+            let program = if program.is_empty() { DEFAULT_AGENT_BROWSER } else { program };
+            let dir = config.agent_profile_path();
+            let mut cmd = Command::new(program);
+            // Firefox and its forks take a profile directory; Chromium and
+            // the browsers built on it a user data directory.
+            if ["firefox", "librewolf", "waterfox"].iter().any(|name| program.contains(name)) {
+                cmd.arg("--no-remote").arg("--profile").arg(dir);
+            } else {
+                cmd.arg(format!("--user-data-dir={}", dir.display()));
+            }
+            cmd
+        }
+    };
+    cmd.args(&config.args).arg(url.as_str());
+    cmd
+}
+
+/// Start the browser on `url` without waiting for it to exit.
+pub(crate) fn spawn_browser(
+    config: &BrowserConfig,
+    profile: BrowserProfile,
+    url: &Url,
+) -> std::io::Result<()> {
+    // The browser keeps running until the user closes it; do not wait.
+    browser_command(config, profile, url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
 #[async_trait]
 impl Tool for OpenUrlTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "open_url".to_string(),
-            description: "Open a URL in the browser".to_string(),
+            description: "Open a web page (http, https or mailto URL) in the browser, in the \
+                          user's profile or in a separate one of the agent's that shares no \
+                          logins or history with it"
+                .to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "url": {
                         "type": "string",
-                        "description": "The URL to open"
+                        "description": "The URL to open, including the scheme"
+                    },
+                    "profile": {
+                        "type": "string",
+                        "enum": ["user", "agent"],
+                        "description": "user opens the page in the user's browser, agent in the agent's isolated profile (default: from the config, usually user)"
                     }
                 },
                 "required": ["url"]
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -38,31 +124,63 @@ impl Tool for OpenUrlTool {
         let url = args
             .get("url")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'url' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'url' argument"))?;
 
-        let output = tokio::process::Command::new("chromium")
-            .arg(url)
-            .output()
-            .await;
+        // Read on every call so config changes apply without a restart.
+        let config = BrowserConfig::load();
+        let profile = match args.get("profile").and_then(|v| v.as_str()) {
+            None => config.profile,
+            Some("user") => BrowserProfile::User,
+            Some("agent") => BrowserProfile::Agent,
+            Some(other) => {
+                return Err(ToolError::invalid_arguments(format!("unknown profile '{other}'")));
+            }
+        };
 
-        match output {
-            Ok(out) if out.status.success() => Ok(ToolResult {
-                call_id: ctx.call_id,
-                output: format!("Opened {url} in browser"),
-                is_error: false,
-            }),
-            Ok(out) => {
-                let stderr = String::from_utf8_lossy(&out.stderr);
-                Ok(ToolResult {
+        let url = match check_url(url, &config) {
+            Ok(url) => url,
+            Err((output, kind)) => {
+                return Ok(ToolResult {
                     call_id: ctx.call_id,
-                    output: format!("Browser failed: {stderr}"),
+                    output,
                     is_error: true,
-                })
+                    error_kind: Some(kind),
+                    attachments: Vec::new(),
+                });
             }
+        };
+
+        if profile == BrowserProfile::Agent
+            && let Err(e) = tokio::fs::create_dir_all(config.agent_profile_path()).await
+        {
+            return Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Cannot create the agent's browser profile: {e}"),
+                is_error: true,
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
+            });
+        }
+
+        match spawn_browser(&config, profile, &url) {
+            Ok(()) => Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: match profile {
+                    BrowserProfile::User => format!("Opened {url} in the browser"),
+                    BrowserProfile::Agent => {
+                        format!("Opened {url} in the agent's browser profile")
+                    }
+                },
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            }),
             Err(e) => Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: format!("Error launching browser: {e}"),
                 is_error: true,
+                error_kind: Some(ToolError::command_kind(&e)),
+                attachments: Vec::new(),
             }),
         }
     }
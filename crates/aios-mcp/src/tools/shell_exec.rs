@@ -1,22 +1,51 @@
 //! Execute a shell command.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use aios_common::{
+    AgentConfig, SandboxConfig, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement,
+};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+
+use crate::executor::{Tool, ToolContext, ToolError, SESSION_VARS};
+use crate::sandbox;
+use crate::tools::shell_session::{is_settable, is_var_name, not_settable};
 
-use crate::executor::{Tool, ToolContext};
+/// Shells a command can run in; each takes the command with `-c`.
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "fish"];
 
-/// Executes an arbitrary shell command via `sh -c`. This is a destructive
-/// operation requiring double confirmation.
+/// Executes an arbitrary shell command via `sh -c` or another shell, inside
+/// the `[sandbox]` unless it is turned off for the command. This is a
+/// destructive operation requiring double confirmation.
+///
+/// Output is reported line by line while the command runs, so a long build
+/// shows up in the chat as it goes.
 pub struct ShellExecTool;
 
+/// Read `pipe` to the end, reporting every line as progress.
+async fn pump(pipe: impl AsyncRead + Unpin, ctx: &ToolContext) -> std::io::Result<Vec<u8>> {
+    let mut reader = BufReader::new(pipe);
+    let mut collected = Vec::new();
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line).await? > 0 {
+        ctx.progress(String::from_utf8_lossy(&line));
+        collected.append(&mut line);
+    }
+    Ok(collected)
+}
+
 #[async_trait]
 impl Tool for ShellExecTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "shell_exec".to_string(),
-            description: "Execute a shell command (destructive, requires double confirmation)"
+            description: "Execute a shell command (destructive, requires double confirmation). \
+                          Commands run in a sandbox: writes outside the home directory and \
+                          /tmp fail, and the network may be off"
                 .to_string(),
             parameters: json!({
                 "type": "object",
@@ -27,16 +56,31 @@ impl Tool for ShellExecTool {
                     },
                     "working_dir": {
                         "type": "string",
-                        "description": "Optional working directory for the command"
+                        "description": "Optional working directory for this command only; by default commands run in the conversation's directory (see change_directory)"
                     },
                     "timeout_ms": {
                         "type": "integer",
                         "description": "Timeout in milliseconds (default: 30000)"
+                    },
+                    "env": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" },
+                        "description": "Environment variables for this command only, on top of the conversation's; the same names as set_env accepts"
+                    },
+                    "stdin": {
+                        "type": "string",
+                        "description": "Text fed to the command's standard input"
+                    },
+                    "shell": {
+                        "type": "string",
+                        "enum": SHELLS,
+                        "description": "Shell that runs the command (default: sh)"
                     }
                 },
                 "required": ["command"]
             }),
             trust_requirement: TrustRequirement::DoubleConfirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -48,58 +92,153 @@ impl Tool for ShellExecTool {
         let command = args
             .get("command")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'command' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'command' argument"))?;
 
-        let working_dir = args.get("working_dir").and_then(|v| v.as_str());
+        let exec = ctx.exec();
+        let working_dir = match args.get("working_dir").and_then(|v| v.as_str()) {
+            Some(dir) => exec.resolve(dir),
+            None => exec.cwd(),
+        };
 
         let timeout_ms = args
             .get("timeout_ms")
             .and_then(|v| v.as_u64())
             .unwrap_or(30_000);
 
-        let mut cmd = tokio::process::Command::new("sh");
-        cmd.arg("-c").arg(command);
+        let shell = args.get("shell").and_then(|v| v.as_str()).unwrap_or("sh");
+        if !SHELLS.contains(&shell) {
+            return Err(ToolError::invalid_arguments(format!("unsupported shell '{shell}'")));
+        }
 
-        if let Some(dir) = working_dir {
-            cmd.current_dir(dir);
+        let mut env = Vec::new();
+        if let Some(vars) = args.get("env") {
+            let vars = vars
+                .as_object()
+                .ok_or_else(|| ToolError::invalid_arguments("'env' must be an object"))?;
+            for (name, value) in vars {
+                let Some(value) = value.as_str() else {
+                    return Err(ToolError::invalid_arguments(format!(
+                        "value of {name} must be a string"
+                    )));
+                };
+                if !is_var_name(name) {
+                    return Err(ToolError::invalid_arguments(format!(
+                        "invalid variable name: {name}"
+                    )));
+                }
+                if !is_settable(name) {
+                    return Err(ToolError::invalid_arguments(not_settable(name)));
+                }
+                env.push((name.clone(), value.to_owned()));
+            }
         }
+        let stdin = args.get("stdin").and_then(|v| v.as_str());
 
-        cmd.stdout(std::process::Stdio::piped());
-        cmd.stderr(std::process::Stdio::piped());
+        // Read on every call so config changes apply without a restart.
+        let mut profile = SandboxConfig::load().profile(command);
+        if let Some(profile) = &mut profile {
+            // Keep sandboxed commands from talking to the agent, e.g. to
+            // approve their own confirmations.
+            profile
+                .hidden
+                .push(PathBuf::from(AgentConfig::load().socket_path));
+        }
+        let sandboxed = profile.is_some();
+        let mut cmd = sandbox::shell_command_with(shell, command, &working_dir, profile.as_ref());
+        cmd.env_clear();
+        cmd.envs(exec.vars(sandboxed));
+        cmd.envs(
+            env.into_iter()
+                .filter(|(name, _)| !sandboxed || !SESSION_VARS.contains(&name.as_str())),
+        );
+
+        cmd.stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() });
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        // A command that times out is killed with the future waiting on it.
+        cmd.kill_on_drop(true);
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) if sandboxed && e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(ToolResult {
+                    call_id: ctx.call_id,
+                    output: format!(
+                        "Sandbox unavailable: {} is not installed. Install bubblewrap or set \
+                         enabled = false under [sandbox] in the config.",
+                        sandbox::BWRAP
+                    ),
+                    is_error: true,
+                    error_kind: Some(ToolErrorKind::Unavailable),
+                    attachments: Vec::new(),
+                });
+            }
+            Err(e) => {
+                return Ok(ToolResult {
+                    call_id: ctx.call_id,
+                    output: format!("Error executing command: {e}"),
+                    is_error: true,
+                    error_kind: Some(ToolError::command_kind(&e)),
+                    attachments: Vec::new(),
+                });
+            }
+        };
+
+        let input = child.stdin.take();
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+        let feed = async {
+            if let (Some(mut pipe), Some(text)) = (input, stdin) {
+                // A command that stops reading early is not an error.
+                if let Err(e) = pipe.write_all(text.as_bytes()).await
+                    && e.kind() != std::io::ErrorKind::BrokenPipe
+                {
+                    return Err(e);
+                }
+            }
+            Ok(())
+        };
+        let run = async {
+            tokio::try_join!(feed, pump(stdout, ctx), pump(stderr, ctx), child.wait())
+        };
 
-        let result = tokio::time::timeout(
-            std::time::Duration::from_millis(timeout_ms),
-            cmd.output(),
-        )
-        .await;
+        let result =
+            tokio::time::timeout(std::time::Duration::from_millis(timeout_ms), run).await;
 
         match result {
-            Ok(Ok(output)) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                let exit_code = output.status.code().unwrap_or(-1);
+            Ok(Ok(((), stdout, stderr, status))) => {
+                let stdout = String::from_utf8_lossy(&stdout);
+                let stderr = String::from_utf8_lossy(&stderr);
+                let exit_code = status.code().unwrap_or(-1);
 
                 let combined = json!({
                     "exit_code": exit_code,
                     "stdout": stdout.as_ref(),
                     "stderr": stderr.as_ref(),
+                    "sandboxed": sandboxed,
                 });
 
                 Ok(ToolResult {
                     call_id: ctx.call_id,
                     output: combined.to_string(),
-                    is_error: !output.status.success(),
+                    is_error: !status.success(),
+                    error_kind: (!status.success()).then_some(ToolErrorKind::Failed),
+                    attachments: Vec::new(),
                 })
             }
             Ok(Err(e)) => Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: format!("Error executing command: {e}"),
                 is_error: true,
+                error_kind: Some(ToolError::command_kind(&e)),
+                attachments: Vec::new(),
             }),
             Err(_) => Ok(ToolResult {
                 call_id: ctx.call_id,
                 output: format!("Command timed out after {timeout_ms}ms"),
                 is_error: true,
+                error_kind: Some(ToolErrorKind::Timeout),
+                attachments: Vec::new(),
             }),
         }
     }
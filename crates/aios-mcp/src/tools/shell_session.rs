@@ -0,0 +1,471 @@
+//! Change the working directory and environment that `shell_exec` commands
+//! of the conversation run with, and keep interactive shells open across
+//! calls.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aios_common::{
+    AgentConfig, SandboxConfig, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::executor::{ENV_ALLOWLIST, Tool, ToolContext, ToolError};
+use crate::pty::PtyShell;
+use crate::sandbox;
+
+/// Variables commands may be given, besides the locale's `LC_*`: ones that
+/// only change language, formatting or logging. Too many others make some
+/// later program run code (`PROMPT_COMMAND`, `ZDOTDIR`, `NODE_OPTIONS`,
+/// `GIT_SSH_COMMAND`, ...), which would slip it past the confirmation of
+/// the commands that follow, so nothing else can be set.
+pub(crate) const SETTABLE_VARS: &[&str] = &[
+    "LANG",
+    "LANGUAGE",
+    "TZ",
+    "TERM",
+    "COLORTERM",
+    "NO_COLOR",
+    "COLUMNS",
+    "LINES",
+    "RUST_LOG",
+    "RUST_BACKTRACE",
+    "NODE_ENV",
+];
+
+/// `name` may be set for commands: one of [`SETTABLE_VARS`] or `LC_*`.
+pub(crate) fn is_settable(name: &str) -> bool {
+    SETTABLE_VARS.contains(&name) || (name.starts_with("LC_") && is_var_name(name))
+}
+
+/// Why `name` cannot be set.
+pub(crate) fn not_settable(name: &str) -> String {
+    format!(
+        "{name} cannot be set; only LC_* and {} can",
+        SETTABLE_VARS.join(", ")
+    )
+}
+
+/// `name` can be an environment variable: letters, digits and `_`, not
+/// starting with a digit.
+pub(crate) fn is_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Shells one conversation may keep open at a time.
+const MAX_SHELLS: usize = 4;
+
+/// Longest a call waits for output.
+const MAX_WAIT_MS: u64 = 60_000;
+
+/// Output returned by one call; anything before is cut (64 KiB).
+const MAX_OUTPUT: usize = 64 * 1024;
+
+/// What a session runs: bash where there is one.
+const INTERACTIVE_SHELL: &str =
+    "if command -v bash >/dev/null 2>&1; then exec bash -i; else exec sh -i; fi";
+
+/// Key of the `[sandbox]` override for sessions,
+/// `[sandbox.commands.shell_session]`. Sessions run arbitrary commands, so
+/// they do not take the settings of any one program, such as `bash`.
+const SANDBOX_KEY: &str = "shell_session";
+
+/// `cd` for the conversation: later commands start in the new directory.
+pub struct ChangeDirectoryTool;
+
+#[async_trait]
+impl Tool for ChangeDirectoryTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "change_directory".to_string(),
+            description: "Change the working directory for the shell commands of this \
+                          conversation, like cd; prints the current one without a path"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Directory, absolute or relative to the current one; ~ is the home directory"
+                    }
+                },
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let Some(path) = args.get("path").and_then(|v| v.as_str()) else {
+            return Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: ctx.exec().cwd().display().to_string(),
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            });
+        };
+
+        let (output, error_kind) = match tokio::fs::canonicalize(ctx.exec().resolve(path)).await {
+            Ok(dir) if dir.is_dir() => {
+                let output = format!("Working directory: {}", dir.display());
+                ctx.update_exec(|exec| exec.cwd = Some(dir));
+                (output, None)
+            }
+            Ok(dir) => (
+                format!("Not a directory: {}", dir.display()),
+                Some(ToolErrorKind::InvalidArguments),
+            ),
+            Err(e) => (
+                format!("Cannot change to {path}: {e}"),
+                Some(ToolErrorKind::from_io(&e)),
+            ),
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: error_kind.is_some(),
+            error_kind,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+/// `export` for the conversation: later commands see the variable.
+pub struct SetEnvTool;
+
+#[async_trait]
+impl Tool for SetEnvTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "set_env".to_string(),
+            description: "Set or unset an environment variable for the shell commands of this \
+                          conversation, like export; lists the variables without a name. Only \
+                          language, terminal and logging variables can be set: LANG, LC_*, TZ, \
+                          TERM, RUST_LOG and the like"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Variable name, e.g. RUST_LOG"
+                    },
+                    "value": {
+                        "type": "string",
+                        "description": "New value; omit to unset the variable"
+                    }
+                },
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::Confirm
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let Some(name) = args.get("name").and_then(|v| v.as_str()) else {
+            let exec = ctx.exec();
+            let output = json!({
+                "inherited": ENV_ALLOWLIST,
+                "set": exec.env,
+            });
+            return Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: output.to_string(),
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            });
+        };
+
+        let (output, error_kind) = if !is_var_name(name) {
+            (
+                format!("Invalid variable name: {name}"),
+                Some(ToolErrorKind::InvalidArguments),
+            )
+        } else if !is_settable(name) {
+            (not_settable(name), Some(ToolErrorKind::PermissionDenied))
+        } else {
+            match args.get("value").and_then(|v| v.as_str()) {
+                Some(value) => {
+                    let value = value.to_owned();
+                    ctx.update_exec(|exec| {
+                        exec.env.insert(name.to_owned(), value);
+                    });
+                    (format!("Set {name}"), None)
+                }
+                None => {
+                    ctx.update_exec(|exec| {
+                        exec.env.remove(name);
+                    });
+                    (format!("Unset {name}"), None)
+                }
+            }
+        };
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: error_kind.is_some(),
+            error_kind,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+/// A shell on a pseudo-terminal that stays open between calls, so a venv
+/// activated or a variable exported in one call is still there in the
+/// next. Sessions belong to the conversation and end with it.
+///
+/// Opening a shell and typing into it run arbitrary commands and require
+/// double confirmation, like `shell_exec`; reading and closing do not.
+/// Everything typed and read is audited with the calls.
+pub struct ShellSessionTool;
+
+fn action(args: &Value) -> &str {
+    args.get("action").and_then(|v| v.as_str()).unwrap_or_default()
+}
+
+fn result(call_id: Uuid, output: String, error_kind: Option<ToolErrorKind>) -> ToolResult {
+    ToolResult {
+        call_id,
+        output,
+        is_error: error_kind.is_some(),
+        error_kind,
+        attachments: Vec::new(),
+    }
+}
+
+/// The last [`MAX_OUTPUT`] bytes of `output`.
+fn tail(output: &str) -> (&str, bool) {
+    if output.len() <= MAX_OUTPUT {
+        return (output, false);
+    }
+    let mut start = output.len() - MAX_OUTPUT;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    (&output[start..], true)
+}
+
+#[async_trait]
+impl Tool for ShellSessionTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "shell_session".to_string(),
+            description: "Keep an interactive shell open across calls, for multi-step work \
+                          such as activating a virtualenv and then running tests. open starts \
+                          a shell, send types input and returns the output, read returns \
+                          output that came in since, close ends the shell. open and send \
+                          are destructive and require double confirmation. Send \"\\u0003\" \
+                          with enter: false to interrupt a running program"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["open", "send", "read", "close"]
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Session name, to keep several shells apart (default: main)"
+                    },
+                    "input": {
+                        "type": "string",
+                        "description": "For send: text to type, e.g. a command"
+                    },
+                    "enter": {
+                        "type": "boolean",
+                        "description": "For send: press Enter after the input (default: true)"
+                    },
+                    "wait_ms": {
+                        "type": "integer",
+                        "description": "How long to wait for output to settle (default: 2000 \
+                            for send, 1000 otherwise; at most 60000)"
+                    }
+                },
+                "required": ["action"]
+            }),
+            trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::None
+    }
+
+    fn call_trust(&self, args: &Value) -> Option<TrustRequirement> {
+        matches!(action(args), "open" | "send").then_some(TrustRequirement::DoubleConfirm)
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let name = args.get("name").and_then(|v| v.as_str()).unwrap_or("main");
+        let action = action(&args);
+        let default_wait = if action == "send" { 2000 } else { 1000 };
+        let wait_ms = args
+            .get("wait_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(default_wait)
+            .min(MAX_WAIT_MS);
+        let exec = ctx.exec();
+        let shells = exec.shells.clone();
+
+        let output = match action {
+            "open" => {
+                let (exists, open) = shells.with(|s| (s.contains_key(name), s.len()));
+                if exists {
+                    let output = format!("Shell session '{name}' is already open");
+                    return Ok(result(ctx.call_id, output, Some(ToolErrorKind::AlreadyExists)));
+                }
+                if open >= MAX_SHELLS {
+                    let output = format!(
+                        "At most {MAX_SHELLS} shell sessions can be open; close one first"
+                    );
+                    return Ok(result(ctx.call_id, output, Some(ToolErrorKind::RateLimited)));
+                }
+
+                let mut profile = SandboxConfig::load().profile(SANDBOX_KEY);
+                if let Some(profile) = &mut profile {
+                    // Same as shell_exec: no talking to the agent from inside.
+                    profile
+                        .hidden
+                        .push(PathBuf::from(AgentConfig::load().socket_path));
+                }
+                let sandboxed = profile.is_some();
+                let mut cmd =
+                    sandbox::shell_command(INTERACTIVE_SHELL, &exec.cwd(), profile.as_ref());
+                cmd.env_clear();
+                cmd.envs(exec.vars(sandboxed));
+                // No colors or fancy line editing to strip from the output.
+                cmd.env("TERM", "dumb");
+
+                let shell = match PtyShell::spawn(cmd) {
+                    Ok(shell) => shell,
+                    Err(e) if sandboxed && e.kind() == std::io::ErrorKind::NotFound => {
+                        let output = format!(
+                            "Sandbox unavailable: {} is not installed. Install bubblewrap or \
+                             set enabled = false under [sandbox] in the config.",
+                            sandbox::BWRAP
+                        );
+                        return Ok(result(ctx.call_id, output, Some(ToolErrorKind::Unavailable)));
+                    }
+                    Err(e) => {
+                        let output = format!("Error starting shell: {e}");
+                        return Ok(result(ctx.call_id, output, Some(ToolError::command_kind(&e))));
+                    }
+                };
+                tracing::info!(session = name, sandboxed, "Opened shell session");
+                let pending = shell.output();
+                shells.with(|s| s.insert(name.to_owned(), shell));
+                pending
+            }
+            "send" | "read" => {
+                let sent = shells.with(|s| {
+                    let shell = s.get_mut(name)?;
+                    if action == "send" {
+                        let input = args.get("input").and_then(|v| v.as_str()).unwrap_or_default();
+                        let enter = args.get("enter").and_then(|v| v.as_bool()).unwrap_or(true);
+                        let text = if enter { format!("{input}\n") } else { input.to_owned() };
+                        if let Err(e) = shell.send(&text) {
+                            return Some(Err(e));
+                        }
+                    }
+                    Some(Ok(shell.output()))
+                });
+                match sent {
+                    Some(Ok(pending)) => pending,
+                    Some(Err(e)) => {
+                        let output = format!("Error typing into shell session '{name}': {e}");
+                        return Ok(result(ctx.call_id, output, Some(ToolErrorKind::from_io(&e))));
+                    }
+                    None => {
+                        let output = format!("No shell session named '{name}'; open one first");
+                        return Ok(result(ctx.call_id, output, Some(ToolErrorKind::NotFound)));
+                    }
+                }
+            }
+            "close" => {
+                // Dropping the shell hangs up on it.
+                return Ok(match shells.with(|s| s.remove(name)) {
+                    Some(_) => {
+                        tracing::info!(session = name, "Closed shell session");
+                        result(ctx.call_id, format!("Closed shell session '{name}'"), None)
+                    }
+                    None => result(
+                        ctx.call_id,
+                        format!("No shell session named '{name}'"),
+                        Some(ToolErrorKind::NotFound),
+                    ),
+                });
+            }
+            other => {
+                return Err(ToolError::invalid_arguments(format!("unknown action '{other}'")));
+            }
+        };
+
+        let text = output.collect(Duration::from_millis(wait_ms)).await;
+        let running = shells.with(|s| match s.get_mut(name).map(PtyShell::is_running) {
+            Some(false) => {
+                s.remove(name);
+                false
+            }
+            running => running.unwrap_or(false),
+        });
+        if !running {
+            tracing::info!(session = name, "Shell session exited");
+        }
+        let (text, truncated) = tail(&text);
+        let output = json!({
+            "session": name,
+            "output": text,
+            "truncated": truncated,
+            "running": running,
+        });
+        Ok(result(ctx.call_id, output.to_string(), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settable_vars_exclude_ones_that_run_code() {
+        for name in [
+            "PATH",
+            "LD_PRELOAD",
+            "BASH_ENV",
+            "PROMPT_COMMAND",
+            "ZDOTDIR",
+            "PYTHONSTARTUP",
+            "NODE_OPTIONS",
+            "PERL5OPT",
+            "GIT_SSH_COMMAND",
+            "LESSOPEN",
+            "LC_ALL=C",
+        ] {
+            assert!(!is_settable(name), "{name}");
+        }
+        for name in ["LANG", "LC_ALL", "LC_TIME", "TZ", "TERM", "RUST_LOG"] {
+            assert!(is_settable(name), "{name}");
+        }
+    }
+}
@@ -1,14 +1,28 @@
 //! Gather system information.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::{
+    BatteryStatus, ChargeState, DiskInfo, HostInfo, LoadInfo, MemoryInfo, ProcessInfo,
+    SystemReport, SystemSection, ThermalZone, ToolDefinition, ToolResult, TrustRequirement,
+};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
 
-/// Collects system information: CPU, memory, disk, and battery status.
-/// Returns data as a JSON object.
+/// Processes listed unless the caller asks for another number.
+const DEFAULT_TOP: usize = 5;
+
+/// Upper bound on `top`, keeping the result small.
+const MAX_TOP: usize = 25;
+
+/// Filesystems that are not disks.
+const PSEUDO_FILESYSTEMS: &[&str] =
+    &["tmpfs", "devtmpfs", "overlay", "squashfs", "efivarfs", "ramfs"];
+
+/// Collects a [`SystemReport`]: host, load, memory, disks, battery, busiest
+/// processes and temperatures. Returns it as JSON, limited to the requested
+/// sections.
 pub struct SystemInfoTool;
 
 /// Read a file and return its contents, or an empty string on error.
@@ -33,15 +47,35 @@ async fn run_or_empty(program: &str, args: &[&str]) -> String {
 #[async_trait]
 impl Tool for SystemInfoTool {
     fn definition(&self) -> ToolDefinition {
+        let sections: Vec<_> = SystemSection::ALL.iter().map(|s| s.as_str()).collect();
         ToolDefinition {
             name: "system_info".to_string(),
-            description: "Get system information (CPU, memory, disk, battery)".to_string(),
+            description: "Get system information as JSON. Sections: host {hostname, kernel, os, \
+                cpu_model, cpu_count, uptime_secs}; load {one, five, fifteen, running, tasks}; \
+                memory {total, available, swap_total, swap_free}; disks [{mount, total, used, \
+                available}]; battery {percent, state}; processes [{pid, name, cpu_percent, \
+                memory}], busiest first; thermal [{name, celsius}]. Sizes are in bytes. \
+                Sections that cannot be read are left out."
+                .to_string(),
             parameters: json!({
                 "type": "object",
-                "properties": {},
+                "properties": {
+                    "sections": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": sections },
+                        "description": "Sections to include (default: all)"
+                    },
+                    "top": {
+                        "type": "integer",
+                        "description": format!(
+                            "Number of processes to list (default: {DEFAULT_TOP}, max: {MAX_TOP})"
+                        )
+                    }
+                },
                 "required": []
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -49,62 +83,189 @@ impl Tool for SystemInfoTool {
         TrustRequirement::None
     }
 
-    async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
-        let cpuinfo = read_or_empty("/proc/cpuinfo").await;
-        let meminfo = read_or_empty("/proc/meminfo").await;
-        let df_output = run_or_empty("df", &["-h"]).await;
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let sections = match args.get("sections").and_then(Value::as_array) {
+            Some(names) if !names.is_empty() => names
+                .iter()
+                .map(|name| {
+                    name.as_str().and_then(SystemSection::parse).ok_or_else(|| {
+                        ToolError::invalid_arguments(format!("unknown section {name}"))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => SystemSection::ALL.to_vec(),
+        };
+        let top = args
+            .get("top")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_TOP, |n| (n as usize).min(MAX_TOP));
 
-        // Try to read battery status from common sysfs paths.
-        let battery_status = read_or_empty(
-            "/sys/class/power_supply/BAT0/status",
-        )
-        .await;
-        let battery_capacity = read_or_empty(
-            "/sys/class/power_supply/BAT0/capacity",
-        )
-        .await;
+        let mut report = SystemReport::default();
+        for section in sections {
+            match section {
+                SystemSection::Host => report.host = Some(host().await),
+                SystemSection::Load => report.load = load().await,
+                SystemSection::Memory => report.memory = memory().await,
+                SystemSection::Disks => report.disks = Some(disks().await),
+                SystemSection::Battery => report.battery = battery().await,
+                SystemSection::Processes => report.processes = Some(processes(top).await),
+                SystemSection::Thermal => report.thermal = Some(thermal().await),
+            }
+        }
 
-        // Extract CPU model name (first occurrence).
-        let cpu_model = cpuinfo
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output: serde_json::to_string_pretty(&report)?,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+async fn host() -> HostInfo {
+    let cpuinfo = read_or_empty("/proc/cpuinfo").await;
+    let os_release = read_or_empty("/etc/os-release").await;
+    let uptime = read_or_empty("/proc/uptime").await;
+
+    HostInfo {
+        hostname: read_or_empty("/proc/sys/kernel/hostname").await.trim().to_owned(),
+        kernel: read_or_empty("/proc/sys/kernel/osrelease").await.trim().to_owned(),
+        os: os_release
+            .lines()
+            .find_map(|l| l.strip_prefix("PRETTY_NAME="))
+            .map(|v| v.trim_matches('"').to_owned())
+            .unwrap_or_default(),
+        cpu_model: cpuinfo
             .lines()
             .find(|l| l.starts_with("model name"))
             .and_then(|l| l.split(':').nth(1))
-            .map(str::trim)
-            .unwrap_or("unknown")
-            .to_string();
-
-        // Extract total and available memory.
-        let extract_mem = |key: &str| -> String {
-            meminfo
-                .lines()
-                .find(|l| l.starts_with(key))
-                .and_then(|l| l.split(':').nth(1))
-                .map(str::trim)
-                .unwrap_or("unknown")
-                .to_string()
-        };
+            .map_or_else(|| "unknown".to_owned(), |v| v.trim().to_owned()),
+        cpu_count: std::thread::available_parallelism().map_or(1, |n| n.get() as u32),
+        uptime_secs: uptime
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<f64>().ok())
+            .map_or(0, |secs| secs as u64),
+    }
+}
 
-        let mem_total = extract_mem("MemTotal");
-        let mem_available = extract_mem("MemAvailable");
-
-        let info = json!({
-            "cpu_model": cpu_model,
-            "memory": {
-                "total": mem_total,
-                "available": mem_available,
-            },
-            "disk": df_output.trim(),
-            "battery": {
-                "status": battery_status.trim(),
-                "capacity": battery_capacity.trim(),
-            },
+/// `/proc/loadavg`: `0.52 0.58 0.59 2/1234 5678`.
+async fn load() -> Option<LoadInfo> {
+    let loadavg = read_or_empty("/proc/loadavg").await;
+    let fields: Vec<&str> = loadavg.split_whitespace().collect();
+    let (running, tasks) = fields.get(3)?.split_once('/')?;
+    Some(LoadInfo {
+        one: fields[0].parse().ok()?,
+        five: fields[1].parse().ok()?,
+        fifteen: fields[2].parse().ok()?,
+        running: running.parse().ok()?,
+        tasks: tasks.parse().ok()?,
+    })
+}
+
+async fn memory() -> Option<MemoryInfo> {
+    let meminfo = read_or_empty("/proc/meminfo").await;
+    // Values are in kB: `MemTotal:       16308124 kB`.
+    let field = |key: &str| -> Option<u64> {
+        meminfo
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|v| v.split_whitespace().next()?.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+    };
+    Some(MemoryInfo {
+        total: field("MemTotal")?,
+        available: field("MemAvailable")?,
+        swap_total: field("SwapTotal").unwrap_or(0),
+        swap_free: field("SwapFree").unwrap_or(0),
+    })
+}
+
+async fn disks() -> Vec<DiskInfo> {
+    let df = run_or_empty("df", &["-B1", "--output=fstype,target,size,used,avail"]).await;
+    df.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [fstype, mount, total, used, available] = fields[..] else {
+                return None;
+            };
+            if PSEUDO_FILESYSTEMS.contains(&fstype) {
+                return None;
+            }
+            Some(DiskInfo {
+                mount: mount.to_owned(),
+                total: total.parse().ok()?,
+                used: used.parse().ok()?,
+                available: available.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// The first battery in `/sys/class/power_supply`.
+async fn battery() -> Option<BatteryStatus> {
+    let mut entries = tokio::fs::read_dir("/sys/class/power_supply").await.ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        let kind = tokio::fs::read_to_string(path.join("type")).await.unwrap_or_default();
+        if kind.trim() != "Battery" {
+            continue;
+        }
+        let capacity = tokio::fs::read_to_string(path.join("capacity")).await.ok()?;
+        let status = tokio::fs::read_to_string(path.join("status")).await.unwrap_or_default();
+        return Some(BatteryStatus {
+            percent: capacity.trim().parse().ok()?,
+            state: ChargeState::from_sysfs(status.trim()),
+            minutes_to_empty: None,
         });
+    }
+    None
+}
 
-        Ok(ToolResult {
-            call_id: ctx.call_id,
-            output: serde_json::to_string_pretty(&info)
-                .unwrap_or_else(|e| format!("Error serializing system info: {e}")),
-            is_error: false,
+async fn processes(top: usize) -> Vec<ProcessInfo> {
+    let ps = run_or_empty("ps", &["-eo", "pid,%cpu,rss,comm", "--sort=-%cpu", "--no-headers"])
+        .await;
+    ps.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let cpu_percent = fields.next()?.parse().ok()?;
+            let rss_kb: u64 = fields.next()?.parse().ok()?;
+            // The command name may contain spaces.
+            let name = fields.collect::<Vec<_>>().join(" ");
+            Some(ProcessInfo {
+                pid,
+                name,
+                cpu_percent,
+                memory: rss_kb * 1024,
+            })
         })
+        .take(top)
+        .collect()
+}
+
+/// Temperatures from `/sys/class/thermal`, in millidegrees there.
+async fn thermal() -> Vec<ThermalZone> {
+    let mut zones = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir("/sys/class/thermal").await else {
+        return zones;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if !entry.file_name().to_string_lossy().starts_with("thermal_zone") {
+            continue;
+        }
+        let path = entry.path();
+        let name = tokio::fs::read_to_string(path.join("type")).await.unwrap_or_default();
+        let temp = tokio::fs::read_to_string(path.join("temp")).await.unwrap_or_default();
+        if let Ok(millidegrees) = temp.trim().parse::<i64>() {
+            zones.push(ThermalZone {
+                name: name.trim().to_owned(),
+                celsius: millidegrees as f32 / 1000.0,
+            });
+        }
     }
+    zones.sort_by(|a, b| a.name.cmp(&b.name));
+    zones
 }
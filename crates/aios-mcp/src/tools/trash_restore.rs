@@ -0,0 +1,113 @@
+//! List the trash and restore files from it.
+
+use std::path::PathBuf;
+
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext};
+use crate::trash;
+use crate::undo::{self, UndoAction};
+
+/// Trashed items listed when no path is given.
+const MAX_LISTED: usize = 50;
+
+/// Restores a trashed file or directory to where it came from, or lists
+/// the trash when no path is given.
+pub struct TrashRestoreTool;
+
+#[async_trait]
+impl Tool for TrashRestoreTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "trash_restore".to_string(),
+            description: "Restore a file from the trash to its original path. Without a path, \
+                list what is in the trash, most recently deleted first."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Original absolute path of the file to restore"
+                    }
+                },
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::Confirm
+    }
+
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let wanted = args.get("path").and_then(|v| v.as_str()).map(PathBuf::from);
+        let items = tokio::task::spawn_blocking(trash::list).await?;
+
+        let Some(wanted) = wanted else {
+            let listed: Vec<Value> = items
+                .iter()
+                .take(MAX_LISTED)
+                .map(|item| {
+                    json!({
+                        "path": item.original.display().to_string(),
+                        "deleted_at": item.deleted_at.map(|d| d.to_string()),
+                    })
+                })
+                .collect();
+            return Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: serde_json::to_string_pretty(&listed)?,
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            });
+        };
+
+        // The newest one, if the same path was trashed more than once.
+        let Some(item) = items.into_iter().find(|item| item.original == wanted) else {
+            return Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: format!("{} is not in the trash", wanted.display()),
+                is_error: true,
+                error_kind: Some(ToolErrorKind::NotFound),
+                attachments: Vec::new(),
+            });
+        };
+
+        let restored = tokio::task::spawn_blocking(move || {
+            trash::restore(&item.original, &item.trashed)?;
+            let action = UndoAction::Untrash {
+                path: item.original,
+                trashed: item.trashed,
+            };
+            if let Err(e) = undo::discard(&action) {
+                tracing::warn!(error = %e, "Failed to update the undo journal");
+            }
+            Ok::<_, std::io::Error>(())
+        })
+        .await?;
+
+        Ok(match restored {
+            Ok(()) => ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Restored {} from the trash", wanted.display()),
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            },
+            Err(e) => ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Error restoring from the trash: {e}"),
+                is_error: true,
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
+            },
+        })
+    }
+}
@@ -0,0 +1,69 @@
+//! Undo the most recent file change made by a tool.
+
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::executor::{Tool, ToolContext};
+use crate::undo;
+
+/// Reverses the latest change in the [`undo`] journal: an overwrite,
+/// append, newly created file or trashed file.
+pub struct UndoLastTool;
+
+#[async_trait]
+impl Tool for UndoLastTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "undo_last".to_string(),
+            description: "Undo the most recent file change made by file_write or file_delete: \
+                restore an overwritten file from its backup, remove a newly created file, cut \
+                off appended content, or take a trashed file back out of the trash. Call it \
+                again to undo earlier changes."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+            trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn trust_requirement(&self) -> TrustRequirement {
+        TrustRequirement::Confirm
+    }
+
+    async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        Ok(match tokio::task::spawn_blocking(undo::undo_last).await? {
+            Some(Ok(entry)) => ToolResult {
+                call_id: ctx.call_id,
+                output: format!(
+                    "Undid a {} change from {}: {}",
+                    entry.tool,
+                    entry.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                    entry.action.describe()
+                ),
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            },
+            Some(Err(e)) => ToolResult {
+                call_id: ctx.call_id,
+                output: format!("Error undoing the last change: {e}"),
+                is_error: true,
+                error_kind: Some(ToolErrorKind::from_io(&e)),
+                attachments: Vec::new(),
+            },
+            None => ToolResult {
+                call_id: ctx.call_id,
+                output: "There is no file change to undo".to_owned(),
+                is_error: true,
+                error_kind: Some(ToolErrorKind::NotFound),
+                attachments: Vec::new(),
+            },
+        })
+    }
+}
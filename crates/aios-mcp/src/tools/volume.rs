@@ -1,32 +1,123 @@
 //! Control audio volume.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use aios_common::wpctl::{self, AudioDevice, VolumeChange, WpctlError};
+use aios_common::{ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
 
-/// Gets or sets the default audio sink volume via `wpctl`.
+/// Lists, sets and mutes audio outputs (sinks) and inputs (sources) via
+/// `wpctl`. Without a `device`, the default output or input is meant.
 pub struct VolumeTool;
 
+/// Whether a device plays or records sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Output,
+    Input,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Output => "output",
+            Self::Input => "input",
+        }
+    }
+}
+
+/// The device `query` names: its id, its name, or a unique part of it.
+fn find<'a>(
+    devices: &'a [(Kind, AudioDevice)],
+    query: &str,
+) -> Result<&'a (Kind, AudioDevice), String> {
+    if let Ok(id) = query.parse::<u32>()
+        && let Some(device) = devices.iter().find(|(_, d)| d.id == id)
+    {
+        return Ok(device);
+    }
+    if let Some(device) = devices.iter().find(|(_, d)| d.name.eq_ignore_ascii_case(query)) {
+        return Ok(device);
+    }
+    let needle = query.to_lowercase();
+    let matches: Vec<_> = devices
+        .iter()
+        .filter(|(_, d)| d.name.to_lowercase().contains(&needle))
+        .collect();
+    match matches.as_slice() {
+        [device] => Ok(device),
+        [] => {
+            let names: Vec<&str> = devices.iter().map(|(_, d)| d.name.as_str()).collect();
+            Err(format!("No audio device '{query}'; available: {}", names.join(", ")))
+        }
+        _ => {
+            let names: Vec<&str> = matches.iter().map(|(_, d)| d.name.as_str()).collect();
+            Err(format!("'{query}' matches several devices: {}", names.join(", ")))
+        }
+    }
+}
+
+fn listing(kind: Kind, device: &AudioDevice) -> Value {
+    json!({
+        "id": device.id,
+        "kind": kind.as_str(),
+        "name": device.name,
+        "volume": device.volume,
+        "muted": device.muted,
+        "default": device.default,
+    })
+}
+
+fn error(ctx: &ToolContext, output: String, kind: ToolErrorKind) -> ToolResult {
+    ToolResult {
+        call_id: ctx.call_id,
+        output,
+        is_error: true,
+        error_kind: Some(kind),
+        attachments: Vec::new(),
+    }
+}
+
+fn wpctl_error(ctx: &ToolContext, e: &WpctlError) -> ToolResult {
+    error(ctx, format!("wpctl failed: {e}"), e.tool_kind())
+}
+
 #[async_trait]
 impl Tool for VolumeTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "volume".to_string(),
-            description: "Get or set audio volume (0-100)".to_string(),
+            description: "Get or set audio volume (0-100) and mute of speakers, headphones and \
+                          microphones. Without a value or mute, lists the audio devices"
+                .to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
                     "value": {
-                        "type": "integer",
-                        "description": "Volume percentage 0-100. Omit to read current volume."
+                        "type": "string",
+                        "description": "Volume percentage: \"50\" sets it, \"+10\" or \"-10\" changes it. Omit to read current volume."
+                    },
+                    "mute": {
+                        "type": "string",
+                        "enum": ["on", "off", "toggle"],
+                        "description": "Mute, unmute, or toggle mute of the device"
+                    },
+                    "device": {
+                        "type": "string",
+                        "description": "Device id or name (or part of it) from the listing. Default: the default output, or the default input when kind is input"
+                    },
+                    "kind": {
+                        "type": "string",
+                        "enum": ["output", "input"],
+                        "description": "output for speakers and headphones, input for microphones (default: output)"
                     }
                 },
                 "required": []
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -35,65 +126,112 @@ impl Tool for VolumeTool {
     }
 
     async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
-        if let Some(value) = args.get("value").and_then(|v| v.as_u64()) {
-            let clamped = value.min(100);
-            let fraction = format!("{:.2}", f64::from(clamped as u32) / 100.0);
-
-            let output = tokio::process::Command::new("wpctl")
-                .args(["set-volume", "@DEFAULT_AUDIO_SINK@", &fraction])
-                .output()
-                .await;
-
-            match output {
-                Ok(out) if out.status.success() => Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output: format!("Volume set to {clamped}%"),
-                    is_error: false,
-                }),
-                Ok(out) => {
-                    let stderr = String::from_utf8_lossy(&out.stderr);
-                    Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: format!("wpctl failed: {stderr}"),
-                        is_error: true,
-                    })
-                }
-                Err(e) => Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output: format!("Error running wpctl: {e}"),
-                    is_error: true,
-                }),
+        let change = match args.get("value") {
+            None | Some(Value::Null) => None,
+            Some(value) => {
+                let parsed = match value {
+                    Value::Number(n) => n
+                        .as_u64()
+                        .map(|v| VolumeChange::Set(u8::try_from(v.min(100)).unwrap_or(100))),
+                    Value::String(s) => VolumeChange::parse(s),
+                    _ => None,
+                };
+                let Some(change) = parsed else {
+                    return Err(ToolError::invalid_arguments(
+                        "'value' must be a percentage like 50, +10 or -10",
+                    ));
+                };
+                // Boosting past 100% is left to the settings app.
+                Some(match change {
+                    VolumeChange::Set(percent) => VolumeChange::Set(percent.min(100)),
+                    other => other,
+                })
             }
-        } else {
-            // Read current volume.
-            let output = tokio::process::Command::new("wpctl")
-                .args(["get-volume", "@DEFAULT_AUDIO_SINK@"])
-                .output()
-                .await;
-
-            match output {
-                Ok(out) if out.status.success() => {
-                    let stdout = String::from_utf8_lossy(&out.stdout);
-                    Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: stdout.trim().to_string(),
-                        is_error: false,
-                    })
-                }
-                Ok(out) => {
-                    let stderr = String::from_utf8_lossy(&out.stderr);
-                    Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: format!("wpctl failed: {stderr}"),
-                        is_error: true,
-                    })
-                }
-                Err(e) => Ok(ToolResult {
-                    call_id: ctx.call_id,
-                    output: format!("Error running wpctl: {e}"),
-                    is_error: true,
-                }),
+        };
+        let mute = match args.get("mute").and_then(|v| v.as_str()) {
+            None => None,
+            Some("on") => Some(Some(true)),
+            Some("off") => Some(Some(false)),
+            Some("toggle") => Some(None),
+            Some(other) => {
+                return Err(ToolError::invalid_arguments(format!("unknown mute '{other}'")));
+            }
+        };
+        let kind = match args.get("kind").and_then(|v| v.as_str()) {
+            None => None,
+            Some("output") => Some(Kind::Output),
+            Some("input") => Some(Kind::Input),
+            Some(other) => {
+                return Err(ToolError::invalid_arguments(format!("unknown kind '{other}'")));
+            }
+        };
+        let device = args.get("device").and_then(|v| v.as_str()).filter(|d| !d.is_empty());
+
+        // Only list the devices when one is named or the listing is asked for.
+        let mut devices = Vec::new();
+        if device.is_some() || (change.is_none() && mute.is_none()) {
+            let (sinks, sources) = match wpctl::devices().await {
+                Ok(devices) => devices,
+                Err(e) => return Ok(wpctl_error(ctx, &e)),
+            };
+            let outputs = sinks.into_iter().map(|d| (Kind::Output, d));
+            let inputs = sources.into_iter().map(|d| (Kind::Input, d));
+            devices = outputs
+                .chain(inputs)
+                .filter(|(k, _)| kind.is_none_or(|kind| *k == kind))
+                .collect();
+        }
+
+        let (target, name) = match device {
+            Some(query) => match find(&devices, query) {
+                Ok((_, device)) => (device.id.to_string(), device.name.clone()),
+                Err(message) => return Ok(error(ctx, message, ToolErrorKind::NotFound)),
+            },
+            None if kind == Some(Kind::Input) => {
+                (wpctl::DEFAULT_SOURCE.to_owned(), "Default input".to_owned())
             }
+            None => (wpctl::DEFAULT_SINK.to_owned(), "Default output".to_owned()),
+        };
+
+        if change.is_none() && mute.is_none() {
+            // Read current volume.
+            let listed: Vec<Value> = devices
+                .iter()
+                .filter(|(_, d)| device.is_none() || d.id.to_string() == target)
+                .map(|(kind, d)| listing(*kind, d))
+                .collect();
+            return Ok(ToolResult {
+                call_id: ctx.call_id,
+                output: json!({ "devices": listed }).to_string(),
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            });
         }
+
+        if let Some(change) = change
+            && let Err(e) = wpctl::set_volume(&target, change).await
+        {
+            return Ok(wpctl_error(ctx, &e));
+        }
+        if let Some(muted) = mute
+            && let Err(e) = wpctl::set_muted(&target, muted).await
+        {
+            return Ok(wpctl_error(ctx, &e));
+        }
+
+        let output = match wpctl::volume(&target).await {
+            Ok(status) if status.muted => format!("{name}: volume {}%, muted", status.percent),
+            Ok(status) => format!("{name}: volume {}%", status.percent),
+            // The change went through; only reading it back failed.
+            Err(_) => format!("{name}: done"),
+        };
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
     }
 }
@@ -1,21 +1,92 @@
 //! Connect to a Wi-Fi network.
 
-use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
+use std::time::Duration;
+
+use aios_common::nmcli::{self, ConnectFailure, ConnectRequest, Enterprise, NmcliError};
+use aios_common::{
+    BrowserConfig, BrowserProfile, ToolDefinition, ToolErrorKind, ToolResult, TrustRequirement,
+};
 use anyhow::Result;
 use async_trait::async_trait;
+use reqwest::Url;
 use serde_json::{json, Value};
 
-use crate::executor::{Tool, ToolContext};
+use crate::executor::{Tool, ToolContext, ToolError};
+use crate::tools::open_url::{check_url, spawn_browser};
+
+/// Page NetworkManager itself checks connectivity against; anything but its
+/// exact body means a portal is in the way.
+const PORTAL_PROBE: &str = "http://nmcheck.gnome.org/check_network_status.txt";
+
+/// Body of [`PORTAL_PROBE`] when the network is open.
+const PORTAL_PROBE_BODY: &str = "NetworkManager is online";
 
-/// Connects to a Wi-Fi network by SSID, optionally with a password.
+/// Time the probe gets; a network without internet access just times out.
+const PORTAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connects to a Wi-Fi network by SSID, optionally with a password,
+/// including hidden and WPA-Enterprise (802.1X) networks. A saved profile
+/// is reused when no password is given, and a captive portal found after
+/// joining is opened in the browser.
 pub struct WifiConnectTool;
 
+/// The sign-in page of a captive portal on the joined network, if any.
+async fn captive_portal() -> Option<Url> {
+    let client = reqwest::Client::builder()
+        .timeout(PORTAL_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .ok()?;
+    let response = client.get(PORTAL_PROBE).send().await.ok()?;
+    if response.status().is_redirection() {
+        // Portals redirect to their page, often with a relative location.
+        let location = response.headers().get(reqwest::header::LOCATION)?.to_str().ok()?;
+        return response.url().join(location).ok();
+    }
+    let probe = response.url().clone();
+    let body = response.text().await.ok()?;
+    // Some portals answer the probe with their page instead of redirecting.
+    (body.trim() != PORTAL_PROBE_BODY).then_some(probe)
+}
+
+/// Describe a failed attempt to join `ssid`.
+fn failure(ssid: &str, hidden: bool, error: &NmcliError) -> (String, ToolErrorKind) {
+    let NmcliError::Failed(message) = error else {
+        return (format!("Failed to connect: {error}"), error.tool_kind());
+    };
+    match ConnectFailure::from_message(message) {
+        ConnectFailure::WrongPassword => (
+            format!("Wrong or missing password for '{ssid}' ({message})"),
+            ToolErrorKind::PermissionDenied,
+        ),
+        ConnectFailure::NotFound if hidden => (
+            format!("No access point for '{ssid}' is in range ({message})"),
+            ToolErrorKind::NotFound,
+        ),
+        ConnectFailure::NotFound => (
+            format!(
+                "No access point for '{ssid}' is in range; if the network is hidden, retry \
+                 with hidden set ({message})"
+            ),
+            ToolErrorKind::NotFound,
+        ),
+        ConnectFailure::NoAddress => (
+            format!("Joined '{ssid}' but got no IP address from it ({message})"),
+            ToolErrorKind::Network,
+        ),
+        ConnectFailure::Other => (format!("Failed to connect: {message}"), ToolErrorKind::Failed),
+    }
+}
+
 #[async_trait]
 impl Tool for WifiConnectTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "wifi_connect".to_string(),
-            description: "Connect to a Wi-Fi network by SSID".to_string(),
+            description: "Connect to a Wi-Fi network by SSID. Known networks connect with \
+                          their saved password; a captive portal (hotel or cafe sign-in page) \
+                          is detected and opened in the browser"
+                .to_string(),
             parameters: json!({
                 "type": "object",
                 "properties": {
@@ -25,12 +96,30 @@ impl Tool for WifiConnectTool {
                     },
                     "password": {
                         "type": "string",
-                        "description": "Password for the network (optional for open networks)"
+                        "description": "Password for the network (optional for open networks and for networks with a saved profile)"
+                    },
+                    "hidden": {
+                        "type": "boolean",
+                        "description": "The network does not broadcast its SSID (default: false)"
+                    },
+                    "identity": {
+                        "type": "string",
+                        "description": "Username for a WPA-Enterprise (802.1X) network"
+                    },
+                    "eap": {
+                        "type": "string",
+                        "enum": ["peap", "ttls"],
+                        "description": "EAP method of an 802.1X network (default: peap)"
+                    },
+                    "open_portal": {
+                        "type": "boolean",
+                        "description": "Open the sign-in page of a captive portal in the browser (default: true)"
                     }
                 },
                 "required": ["ssid"]
             }),
             trust_requirement: TrustRequirement::Confirm,
+            aliases: Vec::new(),
         }
     }
 
@@ -42,43 +131,80 @@ impl Tool for WifiConnectTool {
         let ssid = args
             .get("ssid")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing 'ssid' argument"))?;
+            .ok_or_else(|| ToolError::invalid_arguments("missing 'ssid' argument"))?;
 
-        let password = args.get("password").and_then(|v| v.as_str());
+        let enterprise = args
+            .get("identity")
+            .and_then(|v| v.as_str())
+            .filter(|identity| !identity.is_empty())
+            .map(|identity| {
+                let mut enterprise = Enterprise::peap(identity);
+                if let Some(eap) = args.get("eap").and_then(|v| v.as_str()) {
+                    enterprise.eap = eap.to_owned();
+                }
+                enterprise
+            });
 
-        let mut cmd = tokio::process::Command::new("nmcli");
-        cmd.args(["dev", "wifi", "connect", ssid]);
+        let request = ConnectRequest {
+            ssid: ssid.to_owned(),
+            password: args
+                .get("password")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_owned(),
+            hidden: args.get("hidden").and_then(|v| v.as_bool()).unwrap_or(false),
+            enterprise,
+        };
+        let open_portal = args.get("open_portal").and_then(|v| v.as_bool()).unwrap_or(true);
 
-        if let Some(pw) = password {
-            cmd.args(["password", pw]);
-        }
+        // Without new credentials, a saved profile knows the password (and
+        // whether the network is hidden) already.
+        let saved = if request.password.is_empty() && request.enterprise.is_none() {
+            nmcli::saved_wifi_profile(ssid).await.ok().flatten()
+        } else {
+            None
+        };
+        let connected = match &saved {
+            Some(profile) => nmcli::connect_saved(&profile.uuid)
+                .await
+                .map(|output| format!("{output} (saved profile '{}')", profile.name)),
+            None => nmcli::connect(&request).await,
+        };
+        let mut output = match connected {
+            Ok(output) => output,
+            Err(e) => {
+                let (mut output, kind) = failure(ssid, request.hidden, &e);
+                if saved.is_some() && kind == ToolErrorKind::PermissionDenied {
+                    output.push_str("; the saved password no longer works, ask for the new one");
+                }
+                return Ok(ToolResult {
+                    call_id: ctx.call_id,
+                    output,
+                    is_error: true,
+                    error_kind: Some(kind),
+                    attachments: Vec::new(),
+                });
+            }
+        };
 
-        let output = cmd.output().await;
-
-        match output {
-            Ok(out) => {
-                let stdout = String::from_utf8_lossy(&out.stdout);
-                let stderr = String::from_utf8_lossy(&out.stderr);
-
-                if out.status.success() {
-                    Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: stdout.to_string(),
-                        is_error: false,
-                    })
-                } else {
-                    Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: format!("Failed to connect: {stderr}"),
-                        is_error: true,
-                    })
+        if let Some(portal) = captive_portal().await {
+            output.push_str(&format!(". The network has a captive portal; sign in at {portal}"));
+            // Read on every call so config changes apply without a restart.
+            let config = BrowserConfig::load();
+            if open_portal && check_url(portal.as_str(), &config).is_ok() {
+                match spawn_browser(&config, BrowserProfile::User, &portal) {
+                    Ok(()) => output.push_str(" (opened in the browser)"),
+                    Err(e) => output.push_str(&format!(" (the browser failed to start: {e})")),
                 }
             }
-            Err(e) => Ok(ToolResult {
-                call_id: ctx.call_id,
-                output: format!("Error running nmcli: {e}"),
-                is_error: true,
-            }),
         }
+
+        Ok(ToolResult {
+            call_id: ctx.call_id,
+            output,
+            is_error: false,
+            error_kind: None,
+            attachments: Vec::new(),
+        })
     }
 }
@@ -1,5 +1,6 @@
 //! List available Wi-Fi networks.
 
+use aios_common::nmcli::{self, Rescan};
 use aios_common::{ToolDefinition, ToolResult, TrustRequirement};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -7,7 +8,7 @@ use serde_json::{json, Value};
 
 use crate::executor::{Tool, ToolContext};
 
-/// Lists available Wi-Fi networks using `nmcli`.
+/// Lists available Wi-Fi networks using `nmcli`, as JSON.
 pub struct WifiListTool;
 
 #[async_trait]
@@ -15,13 +16,22 @@ impl Tool for WifiListTool {
     fn definition(&self) -> ToolDefinition {
         ToolDefinition {
             name: "wifi_list".to_string(),
-            description: "List available Wi-Fi networks".to_string(),
+            description: "List available Wi-Fi networks, strongest first, with signal in percent, \
+                security and whether this machine is connected. Networks whose security \
+                includes 802.1X need an identity to connect."
+                .to_string(),
             parameters: json!({
                 "type": "object",
-                "properties": {},
+                "properties": {
+                    "rescan": {
+                        "type": "boolean",
+                        "description": "Wait for a fresh scan (default: false)"
+                    }
+                },
                 "required": []
             }),
             trust_requirement: TrustRequirement::None,
+            aliases: Vec::new(),
         }
     }
 
@@ -29,36 +39,28 @@ impl Tool for WifiListTool {
         TrustRequirement::None
     }
 
-    async fn execute(&self, _args: Value, ctx: &ToolContext) -> Result<ToolResult> {
-        let output = tokio::process::Command::new("nmcli")
-            .args(["dev", "wifi", "list"])
-            .output()
-            .await;
+    async fn execute(&self, args: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        let rescan = if args.get("rescan").and_then(Value::as_bool).unwrap_or(false) {
+            Rescan::Yes
+        } else {
+            Rescan::Auto
+        };
 
-        match output {
-            Ok(out) => {
-                let stdout = String::from_utf8_lossy(&out.stdout);
-                let stderr = String::from_utf8_lossy(&out.stderr);
-
-                if out.status.success() {
-                    Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: stdout.to_string(),
-                        is_error: false,
-                    })
-                } else {
-                    Ok(ToolResult {
-                        call_id: ctx.call_id,
-                        output: format!("nmcli failed: {stderr}"),
-                        is_error: true,
-                    })
-                }
-            }
-            Err(e) => Ok(ToolResult {
+        Ok(match nmcli::wifi_list(rescan).await {
+            Ok(networks) => ToolResult {
+                call_id: ctx.call_id,
+                output: serde_json::to_string(&networks)?,
+                is_error: false,
+                error_kind: None,
+                attachments: Vec::new(),
+            },
+            Err(e) => ToolResult {
                 call_id: ctx.call_id,
-                output: format!("Error running nmcli: {e}"),
+                output: format!("Failed to list networks: {e}"),
                 is_error: true,
-            }),
-        }
+                error_kind: Some(e.tool_kind()),
+                attachments: Vec::new(),
+            },
+        })
     }
 }
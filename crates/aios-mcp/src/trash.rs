@@ -0,0 +1,265 @@
+//! The desktop trash, as the XDG trash specification lays it out.
+//!
+//! Trashed files move to `~/.local/share/Trash/files`, each with a
+//! `.trashinfo` file in `Trash/info` recording where it came from and when,
+//! so file managers can show and restore them too. Only the home trash is
+//! used: files on another filesystem cannot be moved there.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+
+/// Format of `DeletionDate`, local time without a zone.
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// A file or directory in the trash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trashed {
+    /// Where it was before it was trashed.
+    pub original: PathBuf,
+    /// Where it is now, under `Trash/files`.
+    pub trashed: PathBuf,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// The home trash directory.
+pub fn trash_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("Trash")
+}
+
+/// Move `path` to the trash. `path` must be absolute.
+pub fn move_to_trash(path: &Path) -> io::Result<Trashed> {
+    move_to(&trash_dir(), path)
+}
+
+/// [`move_to_trash`] with the trash at `dir`.
+fn move_to(dir: &Path, path: &Path) -> io::Result<Trashed> {
+    fs::symlink_metadata(path)?;
+    fs::create_dir_all(dir.join("files"))?;
+    fs::create_dir_all(dir.join("info"))?;
+
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "cannot trash a root"))?
+        .to_string_lossy()
+        .into_owned();
+    let deleted_at = Local::now().naive_local();
+
+    // Claim a free name by creating its info file; `name 2`, `name 3`...
+    // on collisions, like file managers do.
+    for n in 1.. {
+        let candidate = if n == 1 { name.clone() } else { format!("{name} {n}") };
+        let info = dir.join("info").join(format!("{candidate}.trashinfo"));
+        let mut file = match OpenOptions::new().write(true).create_new(true).open(&info) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        };
+        let trashed = dir.join("files").join(&candidate);
+        if trashed.exists() {
+            // A file without an info file; leave both alone.
+            drop(file);
+            let _ = fs::remove_file(&info);
+            continue;
+        }
+        write!(
+            file,
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            encode(path),
+            deleted_at.format(DATE_FORMAT)
+        )?;
+        if let Err(e) = fs::rename(path, &trashed) {
+            let _ = fs::remove_file(&info);
+            if e.kind() == io::ErrorKind::CrossesDevices {
+                return Err(io::Error::new(
+                    e.kind(),
+                    "the file is on another filesystem than the trash",
+                ));
+            }
+            return Err(e);
+        }
+        return Ok(Trashed {
+            original: path.to_owned(),
+            trashed,
+            deleted_at: Some(deleted_at),
+        });
+    }
+    unreachable!("1.. never ends")
+}
+
+/// Everything in the trash, most recently trashed first.
+pub fn list() -> Vec<Trashed> {
+    list_in(&trash_dir())
+}
+
+/// [`list`] for the trash at `dir`.
+fn list_in(dir: &Path) -> Vec<Trashed> {
+    let Ok(entries) = fs::read_dir(dir.join("info")) else {
+        return Vec::new();
+    };
+    let mut items: Vec<Trashed> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let name = file_name.strip_suffix(".trashinfo")?;
+            let (original, deleted_at) = parse_info(&fs::read_to_string(entry.path()).ok()?)?;
+            Some(Trashed {
+                original,
+                trashed: dir.join("files").join(name),
+                deleted_at,
+            })
+        })
+        .collect();
+    items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+    items
+}
+
+/// The original path and deletion date of a `.trashinfo` file; `None`
+/// without a `Path=`.
+fn parse_info(info: &str) -> Option<(PathBuf, Option<NaiveDateTime>)> {
+    let original = info.lines().find_map(|l| l.strip_prefix("Path="))?;
+    let deleted_at = info
+        .lines()
+        .find_map(|l| l.strip_prefix("DeletionDate="))
+        .and_then(|d| NaiveDateTime::parse_from_str(d, DATE_FORMAT).ok());
+    Some((PathBuf::from(decode(original)), deleted_at))
+}
+
+/// Move a trashed file back to where it came from, unless something else
+/// took its place meanwhile.
+pub fn restore(original: &Path, trashed: &Path) -> io::Result<()> {
+    restore_from(&trash_dir(), original, trashed)
+}
+
+/// [`restore`] from the trash at `dir`.
+fn restore_from(dir: &Path, original: &Path, trashed: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(original).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists again; move it away first", original.display()),
+        ));
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(trashed, original)?;
+    if let Some(name) = trashed.file_name() {
+        let mut info = name.to_os_string();
+        info.push(".trashinfo");
+        let _ = fs::remove_file(dir.join("info").join(info));
+    }
+    Ok(())
+}
+
+/// Percent-encode a path for the `Path=` key, keeping `/` and unreserved
+/// characters.
+fn encode(path: &Path) -> String {
+    let mut out = String::new();
+    for &b in path.as_os_str().as_encoded_bytes() {
+        if b.is_ascii_alphanumeric() || b"/-_.~".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+fn decode(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory holding a `home` and a `Trash`.
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aios-trash-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("home")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn colliding_names_get_numbered() {
+        let dir = temp_dir();
+        let trash = dir.join("Trash");
+        let path = dir.join("home").join("notes.txt");
+        let mut trashed = Vec::new();
+        for content in ["first", "second", "third"] {
+            fs::write(&path, content).unwrap();
+            trashed.push(move_to(&trash, &path).unwrap().trashed);
+        }
+        let names: Vec<_> = trashed.iter().map(|path| path.file_name().unwrap()).collect();
+        assert_eq!(names, ["notes.txt", "notes.txt 2", "notes.txt 3"]);
+        assert_eq!(fs::read_to_string(&trashed[1]).unwrap(), "second");
+        assert!(trash.join("info").join("notes.txt 3.trashinfo").exists());
+        assert!(!path.exists());
+
+        let listed = list_in(&trash);
+        assert_eq!(listed.len(), 3);
+        assert!(listed.iter().all(|item| item.original == path));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn parses_trashinfo_files() {
+        let info = "[Trash Info]\nPath=/home/u/My%20Notes/caf%C3%A9.txt\n\
+                    DeletionDate=2026-03-01T09:30:00\n";
+        let (original, deleted_at) = parse_info(info).unwrap();
+        assert_eq!(original, Path::new("/home/u/My Notes/café.txt"));
+        assert_eq!(
+            deleted_at,
+            NaiveDateTime::parse_from_str("2026-03-01T09:30:00", DATE_FORMAT).ok()
+        );
+        assert_eq!(parse_info("[Trash Info]\nPath=/a\nDeletionDate=soon\n").unwrap().1, None);
+        assert_eq!(parse_info("[Trash Info]\nDeletionDate=2026-03-01T09:30:00\n"), None);
+
+        let path = Path::new("/home/u/100% done (1).txt");
+        assert_eq!(decode(&encode(path)), path.to_str().unwrap());
+    }
+
+    #[test]
+    fn restores_unless_the_original_path_is_taken() {
+        let dir = temp_dir();
+        let trash = dir.join("Trash");
+        let path = dir.join("home").join("report.txt");
+        fs::write(&path, "report").unwrap();
+        let item = move_to(&trash, &path).unwrap();
+
+        fs::write(&path, "newer report").unwrap();
+        let error = restore_from(&trash, &path, &item.trashed).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "newer report");
+        assert!(item.trashed.exists());
+
+        fs::remove_file(&path).unwrap();
+        restore_from(&trash, &path, &item.trashed).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "report");
+        assert!(list_in(&trash).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
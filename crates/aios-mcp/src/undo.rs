@@ -0,0 +1,140 @@
+//! Journal of file changes made by tools, so the last one can be undone.
+//!
+//! Entries are JSON lines in `~/.local/share/aios/undo.jsonl`, newest last.
+//! Each says how to reverse one change: restore a backup, remove a file
+//! that did not exist, cut an append off again, or take a file back out of
+//! the [`trash`](crate::trash). Only the latest
+//! [`MAX_ENTRIES`] are kept.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::trash;
+
+/// Entries kept in the journal.
+const MAX_ENTRIES: usize = 200;
+
+/// A change and how to reverse it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum UndoAction {
+    /// `path` was overwritten; its previous content is in `backup`.
+    Restore { path: PathBuf, backup: PathBuf },
+    /// `path` was created and did not exist before.
+    Remove { path: PathBuf },
+    /// `path` was appended to; it was `len` bytes long before.
+    Truncate { path: PathBuf, len: u64 },
+    /// `path` was moved to the trash, where it is `trashed`.
+    Untrash { path: PathBuf, trashed: PathBuf },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub at: DateTime<Utc>,
+    /// Tool that made the change.
+    pub tool: String,
+    #[serde(flatten)]
+    pub action: UndoAction,
+}
+
+impl UndoAction {
+    /// Reverse the change.
+    pub fn apply(&self) -> io::Result<()> {
+        match self {
+            Self::Restore { path, backup } => fs::copy(backup, path).map(drop),
+            Self::Remove { path } => fs::remove_file(path),
+            Self::Truncate { path, len } => {
+                OpenOptions::new().write(true).open(path)?.set_len(*len)
+            }
+            Self::Untrash { path, trashed } => trash::restore(path, trashed),
+        }
+    }
+
+    /// What undoing it does, e.g. "restored /home/u/notes.txt from its backup".
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Restore { path, backup } => {
+                format!("restored {} from {}", path.display(), backup.display())
+            }
+            Self::Remove { path } => {
+                format!("removed {}, which the change created", path.display())
+            }
+            Self::Truncate { path, len } => {
+                format!("cut {} back to its previous {len} bytes", path.display())
+            }
+            Self::Untrash { path, .. } => format!("restored {} from the trash", path.display()),
+        }
+    }
+}
+
+/// Where the journal lives.
+pub fn journal_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("aios")
+        .join("undo.jsonl")
+}
+
+/// Add a change made by `tool` to the journal.
+pub fn record(tool: &str, action: UndoAction) -> io::Result<()> {
+    let mut entries = load(&journal_path());
+    entries.push(UndoEntry {
+        at: Utc::now(),
+        tool: tool.to_owned(),
+        action,
+    });
+    let excess = entries.len().saturating_sub(MAX_ENTRIES);
+    entries.drain(..excess);
+    save(&journal_path(), &entries)
+}
+
+/// Undo the most recent change and drop it from the journal; `None` if
+/// there is nothing to undo. An entry that fails to apply stays.
+pub fn undo_last() -> Option<io::Result<UndoEntry>> {
+    let path = journal_path();
+    let mut entries = load(&path);
+    let entry = entries.pop()?;
+    if let Err(e) = entry.action.apply() {
+        return Some(Err(e));
+    }
+    Some(save(&path, &entries).map(|()| entry))
+}
+
+/// Drop entries made moot by a change undone some other way, e.g. a file
+/// restored from the trash by hand.
+pub fn discard(action: &UndoAction) -> io::Result<()> {
+    let path = journal_path();
+    let mut entries = load(&path);
+    let before = entries.len();
+    entries.retain(|entry| &entry.action != action);
+    if entries.len() == before {
+        return Ok(());
+    }
+    save(&path, &entries)
+}
+
+/// Entries of the journal at `path`, oldest first; unreadable lines are
+/// skipped.
+fn load(path: &Path) -> Vec<UndoEntry> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn save(path: &Path, entries: &[UndoEntry]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = fs::File::create(path)?;
+    for entry in entries {
+        let line = serde_json::to_string(entry).map_err(io::Error::other)?;
+        writeln!(file, "{line}")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! `aios-session`: starts the AIOS components of a session in order and
+//! keeps them running.
+//!
+//! The agent starts first; the dock and the confirmation dialog follow once
+//! it accepts connections. A component that exits on its own is started
+//! again after a pause that grows while it keeps failing. Settings asks for
+//! their status and starts or stops them over the session socket
+//! (`[session] socket_path`).
+
+mod server;
+mod supervisor;
+
+use std::sync::Arc;
+
+use aios_common::{AgentConfig, IpcServer, SessionConfig};
+use anyhow::Result;
+
+use crate::supervisor::Supervisor;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "aios_session=info".into()),
+        )
+        .init();
+
+    let config = SessionConfig::load();
+    let agent_socket =
+        std::env::var("AIOS_SOCKET").unwrap_or_else(|_| AgentConfig::load().socket_path);
+    tracing::info!(components = ?config.components, "aios-session starting...");
+
+    let supervisor = Arc::new(Supervisor::new(&config.components, agent_socket));
+    let ipc_server = IpcServer::bind(&config.socket_path)?;
+    tracing::info!(path = %config.socket_path, "IPC server bound");
+    tokio::spawn(server::run_server(ipc_server, Arc::clone(&supervisor)));
+
+    tokio::select! {
+        () = supervisor.start_all() => {}
+        () = shutdown_signal() => {
+            tracing::info!("aios-session shutting down during startup");
+            supervisor.stop_all().await;
+            return Ok(());
+        }
+    }
+    shutdown_signal().await;
+    tracing::info!("aios-session shutting down");
+    supervisor.stop_all().await;
+
+    Ok(())
+}
+
+/// Resolves on SIGTERM (systemd stopping the service) or Ctrl+C.
+async fn shutdown_signal() {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Cannot listen for SIGTERM: {e}");
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
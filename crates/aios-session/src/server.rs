@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use aios_common::{AiosError, IpcConnection, IpcMessage, IpcPayload, IpcServer};
+
+use crate::supervisor::Supervisor;
+
+/// Accept connections and answer their requests. Unlike the agent's, this
+/// socket takes requests without a `Register` first.
+pub async fn run_server(server: IpcServer, supervisor: Arc<Supervisor>) {
+    loop {
+        match server.accept().await {
+            Ok(connection) => {
+                let supervisor = Arc::clone(&supervisor);
+                tokio::spawn(async move {
+                    if let Err(e) = handle_client(connection, &supervisor).await {
+                        tracing::warn!("Client handler error: {e}");
+                    }
+                });
+            }
+            Err(e) => tracing::error!("Accept error: {e}"),
+        }
+    }
+}
+
+async fn handle_client(
+    mut connection: IpcConnection,
+    supervisor: &Supervisor,
+) -> Result<(), AiosError> {
+    loop {
+        let request = match connection.recv().await {
+            Ok(request) => request,
+            Err(AiosError::ConnectionClosed) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let payload = match request.payload {
+            IpcPayload::SessionStatusRequest => IpcPayload::SessionStatus {
+                components: supervisor.status(),
+            },
+            IpcPayload::SetComponentRunning { name, running } => {
+                match supervisor.set_running(&name, running) {
+                    Ok(()) => IpcPayload::CommandResult {
+                        success: true,
+                        message: if running {
+                            format!("{name} started")
+                        } else {
+                            format!("{name} stopped")
+                        },
+                    },
+                    Err(message) => IpcPayload::CommandResult {
+                        success: false,
+                        message,
+                    },
+                }
+            }
+            IpcPayload::Ping => IpcPayload::Pong,
+            other => IpcPayload::Error {
+                message: format!("aios-session does not handle {other:?}"),
+                code: Some("unsupported".to_owned()),
+            },
+        };
+        connection
+            .send(&IpcMessage {
+                id: request.id,
+                payload,
+            })
+            .await?;
+    }
+}
@@ -0,0 +1,327 @@
+//! One task per component that starts it, waits for it to exit and starts
+//! it again.
+//!
+//! Each component has a `run` flag its task follows: raised, the task keeps
+//! the process running, restarting it after [`backoff`]; lowered, it stops
+//! the process with SIGTERM and waits. Status changes go to a watch channel
+//! that [`Supervisor::status`] reads.
+
+use std::process::{ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
+
+use aios_common::{ClientType, ComponentState, ComponentStatus, IpcClient, IpcMessage, IpcPayload};
+use chrono::Utc;
+use tokio::process::{Child, Command};
+use tokio::sync::watch;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+/// The component the others connect to. It counts as running once its
+/// socket takes a registration, and the others start after that.
+const AGENT: &str = "aios-agent";
+
+/// Pause before the first restart; doubles with each further one.
+const BACKOFF_MIN: Duration = Duration::from_secs(1);
+
+/// Longest pause before a restart.
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// A run this long counts as healthy, so the restart after it pauses for
+/// [`BACKOFF_MIN`] again.
+const STABLE_RUN: Duration = Duration::from_secs(30);
+
+/// Longest wait for the agent before the other components start anyway.
+const AGENT_READY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How often the agent's socket is tried while it starts.
+const READY_POLL: Duration = Duration::from_millis(200);
+
+/// Time a component has to exit after SIGTERM before it is killed.
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The components of the session, in start order.
+pub struct Supervisor {
+    components: Vec<Arc<Component>>,
+}
+
+struct Component {
+    name: String,
+    /// Whether the component should run.
+    run: watch::Sender<bool>,
+    status: watch::Sender<ComponentStatus>,
+}
+
+impl Supervisor {
+    /// Supervise the programs `names`. None starts before
+    /// [`Self::start_all`].
+    pub fn new(names: &[String], agent_socket: String) -> Self {
+        let components = names
+            .iter()
+            .map(|name| {
+                let (run, _) = watch::channel(false);
+                let (status, _) = watch::channel(ComponentStatus {
+                    name: name.clone(),
+                    state: ComponentState::Stopped,
+                    pid: None,
+                    restarts: 0,
+                    last_exit: None,
+                    since: Utc::now(),
+                });
+                let component = Arc::new(Component {
+                    name: name.clone(),
+                    run,
+                    status,
+                });
+                let ready_socket = (name == AGENT).then(|| agent_socket.clone());
+                tokio::spawn(supervise(Arc::clone(&component), ready_socket));
+                component
+            })
+            .collect();
+        Self { components }
+    }
+
+    /// Start every component in order, the ones after the agent once it
+    /// is ready.
+    pub async fn start_all(&self) {
+        for component in &self.components {
+            component.run.send_replace(true);
+            if component.name != AGENT {
+                continue;
+            }
+            let mut status = component.status.subscribe();
+            let ready = tokio::time::timeout(
+                AGENT_READY_TIMEOUT,
+                status.wait_for(|s| s.state == ComponentState::Running),
+            )
+            .await
+            .is_ok_and(|ready| ready.is_ok());
+            if !ready {
+                tracing::warn!(
+                    "{AGENT} is not ready after {AGENT_READY_TIMEOUT:?}; starting the rest anyway"
+                );
+            }
+        }
+    }
+
+    /// Stop every component, the last started first.
+    pub async fn stop_all(&self) {
+        for component in self.components.iter().rev() {
+            component.run.send_replace(false);
+            let mut status = component.status.subscribe();
+            let stopped = status.wait_for(|s| s.state == ComponentState::Stopped);
+            if tokio::time::timeout(STOP_TIMEOUT * 2, stopped).await.is_err() {
+                tracing::warn!(component = %component.name, "Did not stop in time");
+            }
+        }
+    }
+
+    /// Start or stop the component `name`.
+    pub fn set_running(&self, name: &str, running: bool) -> Result<(), String> {
+        let component = self
+            .components
+            .iter()
+            .find(|c| c.name == name)
+            .ok_or_else(|| format!("No component named {name}"))?;
+        tracing::info!(component = name, running, "Run state changed over IPC");
+        component.run.send_replace(running);
+        Ok(())
+    }
+
+    /// Status of every component, in start order.
+    pub fn status(&self) -> Vec<ComponentStatus> {
+        self.components
+            .iter()
+            .map(|c| c.status.borrow().clone())
+            .collect()
+    }
+}
+
+impl Component {
+    fn set(&self, state: ComponentState, pid: Option<u32>) {
+        self.status.send_modify(|status| {
+            status.state = state;
+            status.pid = pid;
+            status.since = Utc::now();
+        });
+    }
+}
+
+/// Keep `component` running while its `run` flag is raised. With
+/// `ready_socket`, it counts as running once that socket takes a
+/// registration.
+async fn supervise(component: Arc<Component>, ready_socket: Option<String>) {
+    let mut run = component.run.subscribe();
+    let mut pause = None;
+    loop {
+        if run.wait_for(|run| *run).await.is_err() {
+            return;
+        }
+        let started = Instant::now();
+        let spawned = Command::new(&component.name)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .spawn();
+        let exit = match spawned {
+            Ok(child) => run_child(&component, child, &mut run, ready_socket.as_deref()).await,
+            Err(e) => Some(format!("cannot start: {e}")),
+        };
+        let Some(exit) = exit else {
+            component.set(ComponentState::Stopped, None);
+            pause = None;
+            continue;
+        };
+
+        let next = backoff(pause, started.elapsed());
+        pause = Some(next);
+        tracing::warn!(
+            component = %component.name,
+            %exit,
+            "Component exited; restarting in {next:?}"
+        );
+        component.status.send_modify(|status| {
+            status.state = ComponentState::Restarting;
+            status.pid = None;
+            status.restarts += 1;
+            status.last_exit = Some(exit);
+            status.since = Utc::now();
+        });
+        tokio::select! {
+            () = tokio::time::sleep(next) => {}
+            () = lowered(&mut run) => {
+                component.set(ComponentState::Stopped, None);
+                pause = None;
+            }
+        }
+    }
+}
+
+/// Wait for `child` to exit, or stop it once `run` is lowered. Returns how
+/// it exited on its own; `None` when it was stopped.
+async fn run_child(
+    component: &Component,
+    mut child: Child,
+    run: &mut watch::Receiver<bool>,
+    ready_socket: Option<&str>,
+) -> Option<String> {
+    let pid = child.id();
+    let mut ready = ready_socket.is_none();
+    component.set(
+        if ready {
+            ComponentState::Running
+        } else {
+            ComponentState::Starting
+        },
+        pid,
+    );
+    tracing::info!(component = %component.name, ?pid, "Component started");
+
+    let readiness = async {
+        match ready_socket {
+            Some(path) => wait_ready(path).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(readiness);
+    loop {
+        tokio::select! {
+            status = child.wait() => return Some(describe(status)),
+            () = &mut readiness, if !ready => {
+                ready = true;
+                component.set(ComponentState::Running, pid);
+                tracing::info!(component = %component.name, "Component ready");
+            }
+            () = lowered(run) => {
+                stop(&mut child, &component.name).await;
+                return None;
+            }
+        }
+    }
+}
+
+/// Resolves once `run` is lowered or its sender is gone. The watch borrow
+/// is released before returning, so callers can await while stopping.
+async fn lowered(run: &mut watch::Receiver<bool>) {
+    let _ = run.wait_for(|run| !*run).await;
+}
+
+/// Resolves once the agent at `path` answers a registration.
+async fn wait_ready(path: &str) {
+    while !registers(path).await {
+        tokio::time::sleep(READY_POLL).await;
+    }
+}
+
+async fn registers(path: &str) -> bool {
+    let Ok(mut conn) = IpcClient::connect(path).await else {
+        return false;
+    };
+    let register = IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::Register {
+            client_type: ClientType::Session,
+        },
+    };
+    if conn.send(&register).await.is_err() {
+        return false;
+    }
+    matches!(
+        conn.recv().await.map(|reply| reply.payload),
+        Ok(IpcPayload::RegisterAck { success: true })
+    )
+}
+
+/// SIGTERM, then SIGKILL if it is still running after [`STOP_TIMEOUT`].
+async fn stop(child: &mut Child, name: &str) {
+    if let Some(pid) = child.id().and_then(|pid| i32::try_from(pid).ok()) {
+        // SAFETY: plain syscall on a child we have not reaped yet.
+        unsafe {
+            libc::kill(pid, libc::SIGTERM);
+        }
+    }
+    if tokio::time::timeout(STOP_TIMEOUT, child.wait()).await.is_err() {
+        tracing::warn!(
+            component = name,
+            "Still running {STOP_TIMEOUT:?} after SIGTERM; killing it"
+        );
+        let _ = child.kill().await;
+    }
+    tracing::info!(component = name, "Component stopped");
+}
+
+fn describe(status: std::io::Result<ExitStatus>) -> String {
+    match status {
+        Ok(status) => status.to_string(),
+        Err(e) => format!("cannot wait for it: {e}"),
+    }
+}
+
+/// Pause before a restart, after `previous` before the last one (`None`
+/// for the first) and a run of `ran`. A quick exit doubles the pause, a
+/// stable run resets it.
+fn backoff(previous: Option<Duration>, ran: Duration) -> Duration {
+    match previous {
+        Some(previous) if ran < STABLE_RUN => (previous * 2).min(BACKOFF_MAX),
+        _ => BACKOFF_MIN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_exits_back_off_until_a_stable_run() {
+        let quick = Duration::from_secs(2);
+        let mut pause = None;
+        let mut pauses = Vec::new();
+        for _ in 0..8 {
+            let next = backoff(pause, quick);
+            pauses.push(next.as_secs());
+            pause = Some(next);
+        }
+        assert_eq!(pauses, [1, 2, 4, 8, 16, 32, 60, 60]);
+
+        assert_eq!(backoff(pause, STABLE_RUN), BACKOFF_MIN);
+    }
+}
@@ -0,0 +1,184 @@
+//! What this system runs: the AIOS build it was installed from, the
+//! versions of its components, and pending package updates.
+//!
+//! The AIOS binaries ship with the ISO and are described by
+//! `/etc/aios-release` (written by `make install-binaries`); everything else
+//! is a Debian package and updates through apt.
+
+use std::path::Path;
+
+use aios_common::IpcPayload;
+
+use crate::agent_ipc;
+use crate::commands;
+
+const RELEASE_FILE: &str = "/etc/aios-release";
+
+/// Where the ISO installs the AIOS binaries.
+const BIN_DIR: &str = "/usr/local/bin";
+
+/// Components listed when the release file does not name them.
+const DEFAULT_COMPONENTS: [&str; 5] = [
+    "aios-agent",
+    "aios-chat",
+    "aios-dock",
+    "aios-confirm",
+    "aios-settings",
+];
+
+/// The build this system was installed from.
+#[derive(Debug, Clone, Default)]
+pub struct BuildInfo {
+    pub version: String,
+    /// Commit the binaries were built from.
+    pub build_id: String,
+    /// `YYYY-MM-DD`.
+    pub build_date: String,
+    pub components: Vec<String>,
+}
+
+impl BuildInfo {
+    /// Read the release file; `None` outside an AIOS install (e.g. a
+    /// development build).
+    pub fn load() -> Option<Self> {
+        std::fs::read_to_string(RELEASE_FILE)
+            .ok()
+            .map(|content| Self::parse(&content))
+    }
+
+    /// `KEY="value"` lines, as in os-release.
+    fn parse(content: &str) -> Self {
+        let mut info = Self::default();
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"').to_owned();
+            match key.trim() {
+                "VERSION" => info.version = value,
+                "BUILD_ID" => info.build_id = value,
+                "BUILD_DATE" => info.build_date = value,
+                "COMPONENTS" => {
+                    info.components = value.split_whitespace().map(str::to_owned).collect();
+                }
+                _ => {}
+            }
+        }
+        info
+    }
+}
+
+/// One AIOS binary.
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub name: String,
+    /// `None` when the binary is not installed.
+    pub version: Option<String>,
+}
+
+/// The AIOS components and their versions. The binaries are built together,
+/// so each has the build's version; this window knows its own.
+pub fn components(build: Option<&BuildInfo>) -> Vec<Component> {
+    let names = match build {
+        Some(build) if !build.components.is_empty() => build.components.clone(),
+        _ => DEFAULT_COMPONENTS.map(str::to_owned).to_vec(),
+    };
+    names
+        .into_iter()
+        .map(|name| {
+            let version = if name == env!("CARGO_PKG_NAME") {
+                Some(env!("CARGO_PKG_VERSION").to_owned())
+            } else if Path::new(BIN_DIR).join(&name).exists() {
+                build.map(|build| build.version.clone())
+            } else {
+                None
+            };
+            Component { name, version }
+        })
+        .collect()
+}
+
+/// The running agent, as it reports itself.
+#[derive(Debug, Clone)]
+pub struct AgentInfo {
+    pub version: String,
+    /// `None` in echo mode.
+    pub provider: Option<String>,
+    pub model: Option<String>,
+}
+
+/// Ask the agent about itself; `Ok(None)` when it is not running.
+pub async fn agent_info() -> Result<Option<AgentInfo>, String> {
+    let socket_path = agent_ipc::socket_path();
+    let Some(reply) = agent_ipc::request_if_running(&socket_path, IpcPayload::AboutRequest).await
+    else {
+        return Ok(None);
+    };
+    match reply? {
+        IpcPayload::AgentAbout {
+            version,
+            provider,
+            model,
+        } => Ok(Some(AgentInfo {
+            version,
+            provider,
+            model,
+        })),
+        IpcPayload::Error { message, .. } => Err(message),
+        _ => Err("Unexpected response from agent".to_owned()),
+    }
+}
+
+/// A Debian package with a newer version available.
+#[derive(Debug, Clone)]
+pub struct PackageUpdate {
+    pub name: String,
+    /// Empty for packages the upgrade newly installs.
+    pub installed: String,
+    pub available: String,
+}
+
+/// Refresh the package lists, then list what an upgrade would install.
+pub async fn check_updates() -> Result<Vec<PackageUpdate>, String> {
+    let refreshed = commands::apt_update().await;
+    if !refreshed.success {
+        return Err(refreshed.output);
+    }
+    let simulated = commands::apt_upgrade_simulate().await;
+    if !simulated.success {
+        return Err(simulated.output);
+    }
+    Ok(parse_simulation(&simulated.output))
+}
+
+/// `Inst <name> [<installed>] (<available> <origin> [<arch>])` lines of
+/// `apt-get --simulate upgrade`.
+fn parse_simulation(output: &str) -> Vec<PackageUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("Inst ")?;
+            let (name, rest) = rest.split_once(' ')?;
+            let (installed, rest) = match rest.strip_prefix('[') {
+                Some(rest) => rest.split_once("] ")?,
+                None => ("", rest),
+            };
+            let available = rest.strip_prefix('(')?.split_whitespace().next()?;
+            Some(PackageUpdate {
+                name: name.to_owned(),
+                installed: installed.to_owned(),
+                available: available.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Install all pending updates.
+pub async fn install_updates() -> Result<(), String> {
+    let upgraded = commands::apt_upgrade().await;
+    if upgraded.success {
+        Ok(())
+    } else {
+        Err(upgraded.output)
+    }
+}
@@ -0,0 +1,63 @@
+//! The agent's action history. The agent answers queries from an index of
+//! its audit log, so the file is never read here.
+
+use aios_common::{AuditEntry, AuditFilter, AuditRange, IpcPayload};
+use chrono::{Days, Local, NaiveDate, TimeZone, Utc};
+
+use crate::agent_ipc;
+
+/// Entries the Activity tab lists at most.
+const LIMIT: usize = 200;
+
+/// Time span the Activity tab shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Period {
+    #[default]
+    Today,
+    Yesterday,
+    Week,
+    All,
+}
+
+impl Period {
+    pub const ALL: [Self; 4] = [Self::Today, Self::Yesterday, Self::Week, Self::All];
+
+    /// The span in local days, ending now.
+    fn range(self) -> AuditRange {
+        let today = Local::now().date_naive();
+        let (from, to) = match self {
+            Self::Today => (Some(today), None),
+            Self::Yesterday => (today.checked_sub_days(Days::new(1)), Some(today)),
+            Self::Week => (today.checked_sub_days(Days::new(6)), None),
+            Self::All => (None, None),
+        };
+        AuditRange {
+            from: from.and_then(local_midnight),
+            to: to.and_then(local_midnight),
+        }
+    }
+}
+
+/// Start of `date` in local time.
+fn local_midnight(date: NaiveDate) -> Option<chrono::DateTime<Utc>> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Entries of `period` matching `filter`, newest first. The flag tells
+/// whether older matches were left out.
+pub async fn query(period: Period, filter: AuditFilter) -> Result<(Vec<AuditEntry>, bool), String> {
+    let socket_path = agent_ipc::socket_path();
+    let payload = IpcPayload::AuditQuery {
+        filter,
+        range: period.range(),
+        limit: LIMIT,
+    };
+    match agent_ipc::request(&socket_path, payload).await? {
+        IpcPayload::AuditEntries { entries, truncated } => Ok((entries, truncated)),
+        IpcPayload::Error { message, .. } => Err(message),
+        _ => Err("Unexpected response from agent".to_owned()),
+    }
+}
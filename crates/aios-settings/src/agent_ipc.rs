@@ -0,0 +1,137 @@
+//! Requests to the running agent over its IPC socket, as a Settings client.
+
+use std::time::Duration;
+
+use aios_common::{
+    AgentConfig, ClientType, IpcClient, IpcConnection, IpcMessage, IpcPayload, LogLine,
+};
+use futures::channel::mpsc;
+use futures::SinkExt;
+use uuid::Uuid;
+
+/// How often the log tail asks for new lines.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delay before the log tail reconnects to a stopped agent.
+const RETRY_DELAY: Duration = Duration::from_secs(3);
+
+/// Events of the [`log_tail`] stream.
+#[derive(Debug, Clone)]
+pub enum LogEvent {
+    /// (Re)connected; the agent's numbering starts over after a restart, so
+    /// lines shown so far should be dropped.
+    Connected,
+    /// New lines, oldest first.
+    Lines(Vec<LogLine>),
+    /// The connection failed or was lost.
+    Disconnected(String),
+}
+
+/// Socket the agent binds at start, from `[agent]`.
+pub fn socket_path() -> String {
+    AgentConfig::load().socket_path
+}
+
+/// Connect to `socket_path` and register as Settings.
+async fn connect(socket_path: &str) -> Result<IpcConnection, String> {
+    let mut conn = IpcClient::connect(socket_path)
+        .await
+        .map_err(|e| format!("Cannot connect to agent: {e}"))?;
+
+    let register = IpcMessage {
+        id: Uuid::new_v4(),
+        payload: IpcPayload::Register {
+            client_type: ClientType::Settings,
+        },
+    };
+    conn.send(&register)
+        .await
+        .map_err(|e| format!("Failed to register: {e}"))?;
+
+    match conn.recv().await {
+        Ok(msg) => match msg.payload {
+            IpcPayload::RegisterAck { success: true } => Ok(conn),
+            _ => Err("Unexpected registration response".to_owned()),
+        },
+        Err(e) => Err(format!("Registration failed: {e}")),
+    }
+}
+
+/// Send `payload` on a fresh connection and return the agent's reply.
+pub async fn request(socket_path: &str, payload: IpcPayload) -> Result<IpcPayload, String> {
+    let conn = connect(socket_path).await?;
+    exchange(conn, payload).await
+}
+
+/// Like [`request`], but `None` when no agent is listening, so the caller
+/// can do without it.
+pub async fn request_if_running(
+    socket_path: &str,
+    payload: IpcPayload,
+) -> Option<Result<IpcPayload, String>> {
+    let conn = connect(socket_path).await.ok()?;
+    Some(exchange(conn, payload).await)
+}
+
+async fn exchange(mut conn: IpcConnection, payload: IpcPayload) -> Result<IpcPayload, String> {
+    let msg = IpcMessage {
+        id: Uuid::new_v4(),
+        payload,
+    };
+    conn.send(&msg)
+        .await
+        .map_err(|e| format!("Failed to send request: {e}"))?;
+    conn.recv()
+        .await
+        .map(|reply| reply.payload)
+        .map_err(|e| format!("No response from agent: {e}"))
+}
+
+/// Follow the agent's log over one connection, reconnecting when it drops
+/// (e.g. across a restart).
+///
+/// Designed for `Subscription::run_with(socket_path, log_tail)`: the Agent
+/// tab subscribes only while it is shown, so the agent is not polled
+/// otherwise.
+pub fn log_tail(socket_path: &String) -> impl futures::Stream<Item = LogEvent> {
+    let socket_path = socket_path.clone();
+    iced::stream::channel(16, async move |mut output: mpsc::Sender<LogEvent>| {
+        loop {
+            if let Err(reason) = tail_session(&socket_path, &mut output).await {
+                let _ = output.send(LogEvent::Disconnected(reason)).await;
+            }
+            tokio::time::sleep(RETRY_DELAY).await;
+        }
+    })
+}
+
+/// Poll for new lines until the connection fails.
+async fn tail_session(
+    socket_path: &str,
+    output: &mut mpsc::Sender<LogEvent>,
+) -> Result<(), String> {
+    let mut conn = connect(socket_path).await?;
+    let _ = output.send(LogEvent::Connected).await;
+
+    let mut after = 0;
+    loop {
+        let request = IpcMessage {
+            id: Uuid::new_v4(),
+            payload: IpcPayload::LogsRequest { after },
+        };
+        conn.send(&request)
+            .await
+            .map_err(|e| format!("Connection lost: {e}"))?;
+        let reply = conn
+            .recv()
+            .await
+            .map_err(|e| format!("Connection lost: {e}"))?;
+        if let IpcPayload::LogLines { lines } = reply.payload
+            && let Some(last) = lines.last()
+        {
+            after = last.seq;
+            let _ = output.send(LogEvent::Lines(lines)).await;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -1,27 +1,85 @@
-use aios_common::{ClientType, IpcClient, IpcMessage, IpcPayload};
-use iced::{Element, Task};
-use uuid::Uuid;
-
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use aios_common::audit::AuditVerifyError;
+use aios_common::desktop::{self, DesktopEntry};
+use aios_common::hardware::Memory;
+use aios_common::nmcli::{
+    self, ConnectRequest, Enterprise, NmcliError, Rescan, SavedConnection, WifiNetwork,
+};
+use aios_common::wpctl::{self, AudioDevice};
+use aios_common::{
+    AgentConfig, AuditEntry, AuditFilter, AuditOutcome, ColorScheme, ComponentStatus, Density,
+    DockPosition, InputConfig, IpcPayload, LayoutSwitch, LidAction, LogLine, McpServerConfig,
+    McpTransport, PowerConfig, ToolDefinition, ToolPolicy, TrustRequirement, UiConfig, i18n, t,
+};
+use aios_mcp::external::{self, RemoteTool};
+use aios_mcp::registry::ToolRegistry;
+use iced::widget::text_editor;
+use iced::{Element, Subscription, Task};
+
+use crate::about::{AgentInfo, BuildInfo, Component, PackageUpdate};
+use crate::activity::Period;
+use crate::agent_ipc::{self, LogEvent};
 use crate::commands;
+use crate::ollama::{LocalModel, PullEvent};
+use crate::startup::{AutostartEntry, Service};
 use crate::theme;
-use crate::views::{ai, display, network, ollama, sidebar};
+use crate::views::{
+    about, activity, agent, ai, appearance, audio, display, dock, input, language, mcp, network,
+    ollama, permissions, power, sidebar, startup,
+};
+use crate::wallpaper;
 
 /// Active settings tab.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
     Network,
     Display,
+    Audio,
+    Power,
+    Input,
     Ollama,
     Ai,
+    Agent,
+    Activity,
+    Language,
+    Appearance,
+    Dock,
+    Startup,
+    Permissions,
+    Mcp,
+    About,
 }
 
-/// Wi-Fi network entry parsed from nmcli output.
-#[derive(Debug, Clone)]
-pub struct WifiNetwork {
-    pub ssid: String,
-    pub signal: u8,
-    pub security: String,
-    pub connected: bool,
+impl Tab {
+    /// Sidebar order.
+    pub const ALL: [Self; 16] = [
+        Self::Network,
+        Self::Display,
+        Self::Audio,
+        Self::Power,
+        Self::Input,
+        Self::Ollama,
+        Self::Ai,
+        Self::Agent,
+        Self::Activity,
+        Self::Language,
+        Self::Appearance,
+        Self::Dock,
+        Self::Startup,
+        Self::Permissions,
+        Self::Mcp,
+        Self::About,
+    ];
+
+    /// The tab `step` places after this one in the sidebar, wrapping
+    /// around; negative steps go back.
+    pub fn cycle(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|&tab| tab == self).unwrap_or(0);
+        let len = Self::ALL.len() as isize;
+        Self::ALL[(index as isize + step).rem_euclid(len) as usize]
+    }
 }
 
 /// Display output info parsed from swaymsg.
@@ -48,11 +106,23 @@ pub struct NetworkState {
     pub networks: Vec<WifiNetwork>,
     pub selected_ssid: Option<String>,
     pub password_input: String,
+    /// 802.1X username, asked for by WPA-Enterprise networks.
+    pub identity_input: String,
+    pub saved: Vec<SavedConnection>,
+    pub hidden_ssid: String,
+    pub hidden_password: String,
+    /// Username of a hidden WPA-Enterprise network; empty for WPA-Personal.
+    pub hidden_identity: String,
     pub status: String,
     pub loading: bool,
+    /// Frame of the scan spinner, advanced while `loading`.
+    pub spinner: usize,
     pub error: Option<String>,
 }
 
+/// Frame interval of the Network tab's scan spinner.
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
 /// State for Display tab.
 #[derive(Debug, Default)]
 pub struct DisplayState {
@@ -61,15 +131,81 @@ pub struct DisplayState {
     pub error: Option<String>,
 }
 
+/// State for Audio tab.
+#[derive(Debug, Default)]
+pub struct AudioState {
+    pub sinks: Vec<AudioDevice>,
+    pub sources: Vec<AudioDevice>,
+    /// Device whose slider is being dragged, with its new volume.
+    pub pending_volume: Option<(u32, u8)>,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl AudioState {
+    /// Volume shown for a device: the slider position while dragging.
+    pub fn volume(&self, device: &AudioDevice) -> u8 {
+        match self.pending_volume {
+            Some((id, volume)) if id == device.id => volume,
+            _ => device.volume,
+        }
+    }
+
+    fn device(&self, id: u32) -> Option<&AudioDevice> {
+        self.sinks.iter().chain(&self.sources).find(|d| d.id == id)
+    }
+}
+
+/// State for Power tab.
+#[derive(Debug, Default)]
+pub struct PowerState {
+    pub config: PowerConfig,
+    /// Profiles offered by power-profiles-daemon; empty if it is not running.
+    pub profiles: Vec<String>,
+    pub active_profile: Option<String>,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
+/// State for Input tab.
+#[derive(Debug, Default)]
+pub struct InputState {
+    pub config: InputConfig,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
 /// State for Ollama tab.
 #[derive(Debug, Default)]
 pub struct OllamaState {
     pub running: bool,
-    pub models: Vec<String>,
+    pub models: Vec<LocalModel>,
     /// Models available to pull (fetched from Ollama library API).
     pub available_models: Vec<String>,
     pub progress: Option<String>,
+    pub pull: Option<PullProgress>,
     pub error: Option<String>,
+    /// RAM and VRAM, for the recommended model and size warnings.
+    pub memory: Option<Memory>,
+}
+
+/// A model download on the Ollama tab.
+#[derive(Debug)]
+pub struct PullProgress {
+    pub model: String,
+    /// Latest step reported by the server.
+    pub status: String,
+    /// Layers in the order the server started them.
+    pub layers: Vec<PullLayer>,
+    /// Aborts the download.
+    handle: iced::task::Handle,
+}
+
+#[derive(Debug)]
+pub struct PullLayer {
+    pub digest: String,
+    pub completed: u64,
+    pub total: u64,
 }
 
 /// State for AI Provider tab.
@@ -81,8 +217,10 @@ pub struct AiState {
     pub base_url: String,
     pub saved: bool,
     pub error: Option<String>,
-    /// Locally installed Ollama models (for model picker).
-    pub installed_models: Vec<String>,
+    /// Models the selected provider offers (for model picker).
+    pub models: Vec<String>,
+    /// RAM and VRAM, to warn about models too large for this machine.
+    pub memory: Option<Memory>,
 }
 
 impl Default for AiState {
@@ -94,24 +232,260 @@ impl Default for AiState {
             base_url: String::new(),
             saved: false,
             error: None,
-            installed_models: Vec::new(),
+            models: Vec::new(),
+            memory: None,
+        }
+    }
+}
+
+/// Log lines kept by the Agent tab; matches what the agent keeps.
+const AGENT_LOG_LINES: usize = 500;
+
+/// State for Agent tab.
+#[derive(Debug, Default)]
+pub struct AgentState {
+    pub socket_path: String,
+    /// Socket the running agent listens on; differs from `socket_path`
+    /// after a change until the agent is restarted.
+    pub running_socket: String,
+    pub audit_log: String,
+    /// Destructive actions per minute, as typed; saved only if it parses.
+    pub max_destructive: String,
+    /// Tool calls running at once, as typed; saved only if it parses.
+    pub max_parallel_tools: String,
+    /// Audit LLM requests too.
+    pub audit_llm: bool,
+    /// Show the agent's plans in the chat.
+    pub show_plans: bool,
+    /// Kept as loaded; edited in the config file only.
+    pub confirm_command: Vec<String>,
+    /// Custom system prompt; empty uses the built-in one.
+    pub prompt: text_editor::Content,
+    /// Tail of the agent log, oldest first.
+    pub logs: Vec<LogLine>,
+    /// Text the shown log lines must contain, e.g. a request id; empty
+    /// shows all.
+    pub log_filter: String,
+    /// Why the log tail cannot reach the agent.
+    pub disconnected: Option<String>,
+    /// Outcome of the last reload or restart.
+    pub status: Option<(bool, String)>,
+    /// Outcome of the last audit log check.
+    pub audit_check: Option<(bool, String)>,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
+/// State for Activity tab.
+#[derive(Debug, Default)]
+pub struct ActivityState {
+    pub period: Period,
+    /// Tool name to list; empty lists all.
+    pub action: String,
+    /// Text to look for in arguments and details.
+    pub search: String,
+    pub errors_only: bool,
+    /// Newest first.
+    pub entries: Vec<AuditEntry>,
+    /// Older matches were left out.
+    pub truncated: bool,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+/// State for Language tab.
+#[derive(Debug, Clone)]
+pub struct LanguageState {
+    /// `auto` or a language code from [`i18n::LANGUAGES`].
+    pub selected: String,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
+impl Default for LanguageState {
+    fn default() -> Self {
+        Self {
+            selected: "auto".to_owned(),
+            saved: false,
+            error: None,
+        }
+    }
+}
+
+/// State for Appearance tab.
+#[derive(Debug, Clone)]
+pub struct AppearanceState {
+    pub color_scheme: ColorScheme,
+    /// `#rrggbb` accent override; `None` uses the scheme default.
+    pub accent: Option<String>,
+    /// Contents of the custom accent field.
+    pub accent_input: String,
+    pub font_scale: f32,
+    pub density: Density,
+    /// Image path; `None` for a plain background.
+    pub wallpaper: Option<String>,
+    /// Contents of the custom wallpaper field.
+    pub wallpaper_input: String,
+    /// Wallpapers found on disk.
+    pub wallpapers: Vec<String>,
+    pub dock_position: DockPosition,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
+impl Default for AppearanceState {
+    fn default() -> Self {
+        Self {
+            color_scheme: ColorScheme::Dark,
+            accent: None,
+            accent_input: String::new(),
+            font_scale: 1.0,
+            density: Density::Comfortable,
+            wallpaper: None,
+            wallpaper_input: String::new(),
+            wallpapers: Vec::new(),
+            dock_position: DockPosition::Bottom,
+            saved: false,
+            error: None,
+        }
+    }
+}
+
+/// State for Dock tab.
+#[derive(Debug, Default)]
+pub struct DockState {
+    /// Desktop entry ids pinned to the dock, in order.
+    pub pinned: Vec<String>,
+    /// Installed applications, sorted by name.
+    pub available: Vec<DesktopEntry>,
+    /// Filter for the list of installed applications.
+    pub filter: String,
+    /// Show GPU load in the tray.
+    pub gpu_meter: bool,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
+impl DockState {
+    /// Display name of a pinned id; the id itself if the app is not
+    /// installed.
+    pub fn app_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.available
+            .iter()
+            .find(|entry| entry.id == id)
+            .map_or(id, |entry| entry.name.as_str())
+    }
+}
+
+/// State for Permissions tab.
+#[derive(Debug, Default)]
+pub struct PermissionsState {
+    /// Every built-in tool with its default trust level, sorted by name.
+    pub tools: Vec<ToolDefinition>,
+    /// The `[tools]` config section.
+    pub policies: BTreeMap<String, ToolPolicy>,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
+impl PermissionsState {
+    /// Override for `name`, or the defaults if it has none.
+    pub fn policy(&self, name: &str) -> ToolPolicy {
+        self.policies.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Result of testing an MCP server.
+#[derive(Debug, Clone)]
+pub enum McpTest {
+    Running,
+    Tools(Vec<RemoteTool>),
+    Failed(String),
+}
+
+/// State for MCP servers tab.
+#[derive(Debug, Default)]
+pub struct McpState {
+    /// The `[mcp_servers]` config section.
+    pub servers: BTreeMap<String, McpServerConfig>,
+    /// The server being added; its arguments are kept as typed in
+    /// `new_args`.
+    pub new_name: String,
+    pub new_server: McpServerConfig,
+    pub new_args: String,
+    /// Last test per server.
+    pub tests: BTreeMap<String, McpTest>,
+    pub saved: bool,
+    pub error: Option<String>,
+}
+
+/// State for Startup tab.
+#[derive(Debug)]
+pub struct StartupState {
+    pub services: Vec<Service>,
+    /// Components of `aios-session`; `Err` when it does not answer.
+    pub components: Result<Vec<ComponentStatus>, String>,
+    pub entries: Vec<AutostartEntry>,
+    pub error: Option<String>,
+}
+
+impl Default for StartupState {
+    fn default() -> Self {
+        Self {
+            services: Vec::new(),
+            components: Ok(Vec::new()),
+            entries: Vec::new(),
+            error: None,
         }
     }
 }
 
+/// State for About tab.
+#[derive(Debug, Default)]
+pub struct AboutState {
+    /// `None` outside an AIOS install.
+    pub build: Option<BuildInfo>,
+    pub components: Vec<Component>,
+    /// `None` while the agent is not running.
+    pub agent: Option<AgentInfo>,
+    pub agent_error: Option<String>,
+    /// Pending package updates; `None` until checked.
+    pub updates: Option<Vec<PackageUpdate>>,
+    pub checking: bool,
+    pub installing: bool,
+    /// Updates were installed in this session.
+    pub installed: bool,
+    pub error: Option<String>,
+}
+
 /// All messages the settings UI can produce.
 #[derive(Debug, Clone)]
 pub enum Message {
     SwitchTab(Tab),
+    /// Ctrl+Tab and Ctrl+PageDown (1), with Shift or PageUp (-1): switch to
+    /// a neighbouring tab.
+    CycleTab(isize),
     CloseWindow,
+    /// Tab or Shift+Tab outside a field that used it: move the focus.
+    FocusNext,
+    FocusPrevious,
 
     // Network
     WifiScan,
+    WifiListed(Vec<WifiNetwork>, String),
     WifiScanDone(Vec<WifiNetwork>, String),
+    WifiSpinnerTick,
     SelectNetwork(String),
     PasswordChanged(String),
+    IdentityChanged(String),
     WifiConnect,
     WifiDisconnect,
+    HiddenSsidChanged(String),
+    HiddenPasswordChanged(String),
+    HiddenIdentityChanged(String),
+    WifiJoinHidden,
+    SavedConnectionsLoaded(Vec<SavedConnection>),
+    WifiForget(String),
     WifiActionDone(bool, String),
 
     // Display
@@ -120,12 +494,52 @@ pub enum Message {
     DisplaySetMode { output: String, width: u32, height: u32, refresh: f32 },
     DisplayActionDone(bool, String),
 
+    // Audio
+    AudioRefresh,
+    AudioRefreshDone(Vec<AudioDevice>, Vec<AudioDevice>),
+    /// Make this sink or source the default.
+    AudioSetDefault(u32),
+    /// A volume slider moved; applied on release.
+    AudioVolumeChanged(u32, u8),
+    AudioVolumeReleased,
+    AudioToggleMute(u32),
+    AudioActionDone(bool, String),
+
+    // Power
+    /// Screen-off timeout in minutes; `0` never.
+    PowerScreenOff(u32),
+    /// Suspend timeout in minutes; `0` never.
+    PowerSuspend(u32),
+    PowerLidClose(LidAction),
+    PowerSaveDone(bool, String),
+    /// Available profiles and the active one.
+    PowerProfilesLoaded(Vec<String>, Option<String>),
+    PowerSelectProfile(String),
+    PowerProfileDone(bool, String),
+
+    // Input
+    InputAddLayout(String),
+    /// Remove the layout at this index.
+    InputRemoveLayout(usize),
+    /// Swap the layout at this index with the one before it.
+    InputMoveLayoutUp(usize),
+    InputSelectSwitch(LayoutSwitch),
+    /// A repeat slider moved; saved on release.
+    InputRepeatDelay(u32),
+    InputRepeatRate(u32),
+    InputRepeatReleased,
+    InputTapToClick(bool),
+    InputNaturalScroll(bool),
+    InputSaveDone(bool, String),
+
     // Ollama
     OllamaRefresh,
-    OllamaRefreshDone { running: bool, models: Vec<String>, available: Vec<String> },
+    OllamaRefreshDone { running: bool, models: Vec<LocalModel>, available: Vec<String> },
     OllamaStart,
     OllamaStop,
     OllamaPull(String),
+    OllamaPullEvent(PullEvent),
+    OllamaCancelPull,
     OllamaRemove(String),
     OllamaActionDone(bool, String),
 
@@ -137,46 +551,211 @@ pub enum Message {
     AiBaseUrlChanged(String),
     AiSave,
     AiSaveDone(bool, String),
-    AiReloadDone(bool, String),
     /// Installed Ollama models loaded.
-    AiInstalledModels(Vec<String>),
+    AiModelsListed(Vec<String>),
+    /// RAM and VRAM probed, for model recommendations.
+    HardwareProbed(Option<Memory>),
     /// User picked a model from installed list.
     AiPickModel(String),
+    /// Open the chat app's setup wizard (`aios-chat --setup`).
+    AiRunSetupWizard,
+
+    // Agent
+    AgentSocketChanged(String),
+    AgentAuditLogChanged(String),
+    AgentMaxDestructiveChanged(String),
+    AgentMaxParallelToolsChanged(String),
+    AgentAuditLlm(bool),
+    AgentShowPlans(bool),
+    AgentPromptEdit(text_editor::Action),
+    AgentSave,
+    AgentSaveDone(bool, String),
+    AgentReload,
+    AgentReloadDone(bool, String),
+    AgentRestart,
+    AgentRestartDone(bool, String),
+    AgentLog(LogEvent),
+    AgentLogFilterChanged(String),
+    /// Check the hash chain of the audit log.
+    AgentVerifyAudit,
+    AgentAuditVerified(bool, String),
+
+    // Activity
+    ActivityRefresh,
+    ActivityPeriod(Period),
+    ActivityActionChanged(String),
+    ActivitySearchChanged(String),
+    ActivityErrorsOnly(bool),
+    ActivityLoaded(Result<(Vec<AuditEntry>, bool), String>),
+
+    // Language
+    LanguageSelect(String),
+    LanguageSaveDone(bool, String),
+
+    // Appearance
+    AppearanceSelectScheme(ColorScheme),
+    /// Preset picked, or `None` for the scheme default.
+    AppearanceSelectAccent(Option<String>),
+    AppearanceAccentInput(String),
+    AppearanceAccentSubmit,
+    AppearanceSelectFontScale(f32),
+    AppearanceSelectDensity(Density),
+    AppearanceWallpapersLoaded(Vec<String>),
+    /// Wallpaper picked, or `None` for a plain background.
+    AppearanceSelectWallpaper(Option<String>),
+    AppearanceWallpaperInput(String),
+    AppearanceWallpaperSubmit,
+    AppearanceSelectDockPosition(DockPosition),
+    AppearanceSaveDone(bool, String),
+    /// The config file changed on disk (possibly from this window).
+    AppearanceChanged,
+
+    // Dock
+    DockAppsLoaded(Vec<DesktopEntry>),
+    DockPin(String),
+    /// Remove the pinned app at this index.
+    DockUnpin(usize),
+    /// Swap the pinned app at this index with the one before it.
+    DockMoveUp(usize),
+    DockFilterChanged(String),
+    DockGpuMeter(bool),
+    DockSaveDone(bool, String),
+
+    // Permissions
+    PermissionsToggle(String, bool),
+    /// Trust level override for a tool; `None` restores its default.
+    PermissionsTrust(String, Option<TrustRequirement>),
+    PermissionsSaveDone(bool, String),
+
+    // MCP servers
+    McpNameChanged(String),
+    McpTransportSelected(McpTransport),
+    McpCommandChanged(String),
+    McpArgsChanged(String),
+    McpUrlChanged(String),
+    McpAdd,
+    McpRemove(String),
+    McpToggle(String, bool),
+    McpTrust(String, TrustRequirement),
+    /// Start the server and list its tools.
+    McpTest(String),
+    McpTestDone(String, Result<Vec<RemoteTool>, String>),
+    McpSaveDone(bool, String),
+
+    // Startup
+    StartupRefresh,
+    StartupServicesLoaded(Result<Vec<Service>, String>),
+    StartupEntriesLoaded(Vec<AutostartEntry>),
+    /// Start the service at login, or not.
+    StartupServiceEnabled(String, bool),
+    /// Start or stop the service now.
+    StartupServiceRun(String, bool),
+    StartupServiceDone(bool, String),
+    StartupComponentsLoaded(Result<Vec<ComponentStatus>, String>),
+    /// Start (`true`) or stop a component of `aios-session`.
+    StartupComponentRun(String, bool),
+    StartupEntryEnabled(String, bool),
+    /// Start the installed app with this desktop id at login.
+    StartupAddEntry(String),
+    StartupRemoveEntry(String),
+    StartupEntryDone(Result<(), String>),
+
+    // About
+    AboutAgentLoaded(Result<Option<AgentInfo>, String>),
+    /// Refresh the package lists and list pending updates.
+    AboutCheckUpdates,
+    AboutUpdatesChecked(Result<Vec<PackageUpdate>, String>),
+    AboutInstallUpdates,
+    AboutUpdatesInstalled(Result<(), String>),
 }
 
 pub struct SettingsApp {
     pub active_tab: Tab,
     pub network: NetworkState,
     pub display: DisplayState,
+    pub audio: AudioState,
+    pub power: PowerState,
+    pub input: InputState,
     pub ollama: OllamaState,
     pub ai: AiState,
+    pub agent: AgentState,
+    pub activity: ActivityState,
+    pub language: LanguageState,
+    pub appearance: AppearanceState,
+    pub dock: DockState,
+    pub permissions: PermissionsState,
+    pub mcp: McpState,
+    pub startup: StartupState,
+    pub about: AboutState,
 }
 
 impl SettingsApp {
     pub fn new() -> (Self, Task<Message>) {
-        let state = Self {
+        let mut state = Self {
             active_tab: Tab::Network,
             network: NetworkState::default(),
             display: DisplayState::default(),
+            audio: AudioState::default(),
+            power: PowerState::default(),
+            input: InputState::default(),
             ollama: OllamaState::default(),
             ai: AiState::default(),
+            agent: AgentState::default(),
+            activity: ActivityState::default(),
+            language: LanguageState::default(),
+            appearance: AppearanceState::default(),
+            dock: DockState::default(),
+            permissions: PermissionsState::default(),
+            mcp: McpState::default(),
+            startup: StartupState::default(),
+            about: AboutState::default(),
         };
+        state.load_permissions();
+        state.power.config = PowerConfig::load();
+        state.input.config = InputConfig::load();
+        state.load_agent_config(&AgentConfig::load());
+        state.mcp.servers = McpServerConfig::load_all();
+        state.load_ui_config(&UiConfig::load());
+        state.about.build = BuildInfo::load();
+        state.about.components = crate::about::components(state.about.build.as_ref());
         // Auto-refresh on start
         let tasks = Task::batch([
-            Task::perform(async { do_wifi_scan() }, |(nets, status)| Message::WifiScanDone(nets, status)),
-            Task::perform(async { do_display_refresh() }, Message::DisplayRefreshDone),
-            Task::perform(async { do_ollama_refresh() }, |(running, models, available)| {
+            network_refresh_task(),
+            Task::perform(do_display_refresh(), Message::DisplayRefreshDone),
+            audio_refresh_task(),
+            power_profiles_task(),
+            Task::perform(do_ollama_refresh(), |(running, models, available)| {
                 Message::OllamaRefreshDone { running, models, available }
             }),
             Task::perform(async { load_ai_config() }, |(p, k, m, u)| Message::AiConfigLoaded(p, k, m, u)),
+            Task::perform(probe_memory(), Message::HardwareProbed),
+            Task::perform(async { desktop::all() }, Message::DockAppsLoaded),
+            Task::perform(async { wallpaper::available() }, Message::AppearanceWallpapersLoaded),
         ]);
         (state, tasks)
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
+            Message::CycleTab(step) => {
+                return self.update(Message::SwitchTab(self.active_tab.cycle(step)));
+            }
+            Message::FocusNext => return iced::widget::operation::focus_next(),
+            Message::FocusPrevious => return iced::widget::operation::focus_previous(),
             Message::SwitchTab(tab) => {
                 self.active_tab = tab;
+                match tab {
+                    Tab::Activity => return self.activity_query(),
+                    Tab::Startup => return startup_refresh_task(),
+                    // The agent may have been restarted or reconfigured since.
+                    Tab::About => {
+                        return Task::perform(
+                            crate::about::agent_info(),
+                            Message::AboutAgentLoaded,
+                        );
+                    }
+                    _ => {}
+                }
             }
             Message::CloseWindow => {
                 return iced::exit();
@@ -184,53 +763,96 @@ impl SettingsApp {
 
             // -- Network --
             Message::WifiScan => {
-                self.network.loading = true;
-                self.network.error = None;
-                return Task::perform(async { do_wifi_scan() }, |(nets, status)| {
-                    Message::WifiScanDone(nets, status)
-                });
+                if !self.network.loading {
+                    self.network.loading = true;
+                    self.network.error = None;
+                    return wifi_scan_task();
+                }
+            }
+            Message::WifiListed(networks, status) => {
+                // Known networks while the rescan is still running
+                self.network.networks = networks;
+                self.network.status = status;
             }
             Message::WifiScanDone(networks, status) => {
                 self.network.loading = false;
                 self.network.networks = networks;
                 self.network.status = status;
             }
+            Message::WifiSpinnerTick => {
+                self.network.spinner = self.network.spinner.wrapping_add(1);
+            }
             Message::SelectNetwork(ssid) => {
                 self.network.selected_ssid = Some(ssid);
                 self.network.password_input.clear();
+                self.network.identity_input.clear();
             }
             Message::PasswordChanged(val) => {
                 self.network.password_input = val;
             }
+            Message::IdentityChanged(val) => {
+                self.network.identity_input = val;
+            }
             Message::WifiConnect => {
                 if let Some(ssid) = self.network.selected_ssid.clone() {
-                    let password = self.network.password_input.clone();
-                    return Task::perform(
-                        async move {
-                            let r = commands::wifi_connect(&ssid, &password);
-                            (r.success, r.output)
-                        },
-                        |(ok, msg)| Message::WifiActionDone(ok, msg),
-                    );
+                    let enterprise = self
+                        .network
+                        .networks
+                        .iter()
+                        .any(|n| n.ssid == ssid && n.is_enterprise());
+                    let request = ConnectRequest {
+                        ssid,
+                        password: self.network.password_input.clone(),
+                        hidden: false,
+                        enterprise: enterprise
+                            .then(|| Enterprise::peap(self.network.identity_input.trim())),
+                    };
+                    return nmcli_action(async move { nmcli::connect(&request).await });
                 }
             }
             Message::WifiDisconnect => {
-                return Task::perform(
-                    async {
-                        let r = commands::wifi_disconnect();
-                        (r.success, r.output)
-                    },
-                    |(ok, msg)| Message::WifiActionDone(ok, msg),
-                );
+                return nmcli_action(async {
+                    let device = nmcli::wifi_device().await.unwrap_or_else(|| "wlan0".to_owned());
+                    nmcli::disconnect(&device).await
+                });
+            }
+            Message::HiddenSsidChanged(val) => {
+                self.network.hidden_ssid = val;
+            }
+            Message::HiddenPasswordChanged(val) => {
+                self.network.hidden_password = val;
+            }
+            Message::HiddenIdentityChanged(val) => {
+                self.network.hidden_identity = val;
+            }
+            Message::WifiJoinHidden => {
+                let ssid = self.network.hidden_ssid.trim().to_owned();
+                if !ssid.is_empty() {
+                    let identity = self.network.hidden_identity.trim();
+                    let request = ConnectRequest {
+                        ssid,
+                        password: self.network.hidden_password.clone(),
+                        hidden: true,
+                        enterprise: (!identity.is_empty()).then(|| Enterprise::peap(identity)),
+                    };
+                    return nmcli_action(async move { nmcli::connect(&request).await });
+                }
+            }
+            Message::SavedConnectionsLoaded(saved) => {
+                self.network.saved = saved;
+            }
+            Message::WifiForget(uuid) => {
+                return nmcli_action(async move { nmcli::forget(&uuid).await });
             }
             Message::WifiActionDone(success, msg) => {
                 if success {
                     self.network.error = None;
                     self.network.status = msg;
+                    self.network.hidden_ssid.clear();
+                    self.network.hidden_password.clear();
+                    self.network.hidden_identity.clear();
                     // Refresh list after action
-                    return Task::perform(async { do_wifi_scan() }, |(nets, status)| {
-                        Message::WifiScanDone(nets, status)
-                    });
+                    return network_refresh_task();
                 } else {
                     self.network.error = Some(msg);
                 }
@@ -240,7 +862,7 @@ impl SettingsApp {
             Message::DisplayRefresh => {
                 self.display.loading = true;
                 self.display.error = None;
-                return Task::perform(async { do_display_refresh() }, Message::DisplayRefreshDone);
+                return Task::perform(do_display_refresh(), Message::DisplayRefreshDone);
             }
             Message::DisplayRefreshDone(outputs) => {
                 self.display.loading = false;
@@ -249,7 +871,7 @@ impl SettingsApp {
             Message::DisplaySetMode { output, width, height, refresh } => {
                 return Task::perform(
                     async move {
-                        let r = commands::display_set_mode(&output, width, height, refresh);
+                        let r = commands::display_set_mode(&output, width, height, refresh).await;
                         (r.success, r.output)
                     },
                     |(ok, msg)| Message::DisplayActionDone(ok, msg),
@@ -258,15 +880,145 @@ impl SettingsApp {
             Message::DisplayActionDone(success, msg) => {
                 if success {
                     self.display.error = None;
-                    return Task::perform(async { do_display_refresh() }, Message::DisplayRefreshDone);
+                    return Task::perform(do_display_refresh(), Message::DisplayRefreshDone);
                 } else {
                     self.display.error = Some(msg);
                 }
             }
 
+            // -- Audio --
+            Message::AudioRefresh => {
+                self.audio.loading = true;
+                self.audio.error = None;
+                return audio_refresh_task();
+            }
+            Message::AudioRefreshDone(sinks, sources) => {
+                self.audio.loading = false;
+                self.audio.sinks = sinks;
+                self.audio.sources = sources;
+                self.audio.pending_volume = None;
+            }
+            Message::AudioSetDefault(id) => {
+                return audio_action(commands::audio_set_default(id));
+            }
+            Message::AudioVolumeChanged(id, volume) => {
+                self.audio.pending_volume = Some((id, volume));
+            }
+            Message::AudioVolumeReleased => {
+                let Some((id, volume)) = self.audio.pending_volume else {
+                    return Task::none();
+                };
+                return audio_action(commands::audio_set_volume(id, volume));
+            }
+            Message::AudioToggleMute(id) => {
+                let Some(device) = self.audio.device(id) else {
+                    return Task::none();
+                };
+                let muted = !device.muted;
+                return audio_action(commands::audio_set_mute(id, muted));
+            }
+            Message::AudioActionDone(success, msg) => {
+                if success {
+                    self.audio.error = None;
+                } else {
+                    // Keep the refreshed state, but show why the change failed.
+                    self.audio.error = Some(msg);
+                }
+                return audio_refresh_task();
+            }
+
+            // -- Power --
+            Message::PowerScreenOff(minutes) => {
+                self.power.config.screen_off_minutes = minutes;
+                return self.save_power();
+            }
+            Message::PowerSuspend(minutes) => {
+                self.power.config.suspend_minutes = minutes;
+                return self.save_power();
+            }
+            Message::PowerLidClose(action) => {
+                self.power.config.lid_close = action;
+                return self.save_power();
+            }
+            Message::PowerSaveDone(success, msg) => {
+                if success {
+                    self.power.saved = true;
+                    self.power.error = None;
+                } else {
+                    self.power.error = Some(msg);
+                }
+            }
+            Message::PowerProfilesLoaded(profiles, active) => {
+                self.power.profiles = profiles;
+                self.power.active_profile = active;
+            }
+            Message::PowerSelectProfile(profile) => {
+                return Task::perform(
+                    async move {
+                        let r = commands::power_profile_set(&profile).await;
+                        (r.success, r.output)
+                    },
+                    |(ok, msg)| Message::PowerProfileDone(ok, msg),
+                );
+            }
+            Message::PowerProfileDone(success, msg) => {
+                self.power.error = (!success).then_some(msg);
+                return power_profiles_task();
+            }
+
+            // -- Input --
+            Message::InputAddLayout(layout) => {
+                if !self.input.config.layouts.contains(&layout) {
+                    self.input.config.layouts.push(layout);
+                    return self.save_input();
+                }
+            }
+            Message::InputRemoveLayout(index) => {
+                // sway needs at least one layout.
+                if index < self.input.config.layouts.len() && self.input.config.layouts.len() > 1 {
+                    self.input.config.layouts.remove(index);
+                    return self.save_input();
+                }
+            }
+            Message::InputMoveLayoutUp(index) => {
+                if index > 0 && index < self.input.config.layouts.len() {
+                    self.input.config.layouts.swap(index - 1, index);
+                    return self.save_input();
+                }
+            }
+            Message::InputSelectSwitch(switch) => {
+                self.input.config.layout_switch = switch;
+                return self.save_input();
+            }
+            Message::InputRepeatDelay(delay) => {
+                self.input.config.repeat_delay = delay;
+            }
+            Message::InputRepeatRate(rate) => {
+                self.input.config.repeat_rate = rate;
+            }
+            Message::InputRepeatReleased => {
+                return self.save_input();
+            }
+            Message::InputTapToClick(enabled) => {
+                self.input.config.tap_to_click = enabled;
+                return self.save_input();
+            }
+            Message::InputNaturalScroll(enabled) => {
+                self.input.config.natural_scroll = enabled;
+                return self.save_input();
+            }
+            Message::InputSaveDone(success, msg) => {
+                if success {
+                    self.input.saved = true;
+                    self.input.error = None;
+                } else {
+                    self.input.error = Some(msg);
+                }
+            }
+
             // -- Ollama --
             Message::OllamaRefresh => {
-                return Task::perform(async { do_ollama_refresh() }, |(running, models, available)| {
+                return Task::perform(do_ollama_refresh(), |(running, models, available)| {
                     Message::OllamaRefreshDone { running, models, available }
                 });
             }
@@ -277,41 +1029,85 @@ impl SettingsApp {
                 self.ollama.progress = None;
             }
             Message::OllamaStart => {
-                self.ollama.progress = Some("Starting Ollama...".to_owned());
+                self.ollama.progress = Some(t!("settings-ollama-starting"));
                 return Task::perform(
                     async {
-                        let r = commands::ollama_start();
+                        let r = commands::ollama_start().await;
                         (r.success, r.output)
                     },
                     |(ok, msg)| Message::OllamaActionDone(ok, msg),
                 );
             }
             Message::OllamaStop => {
-                self.ollama.progress = Some("Stopping Ollama...".to_owned());
+                self.ollama.progress = Some(t!("settings-ollama-stopping"));
                 return Task::perform(
                     async {
-                        let r = commands::ollama_stop();
+                        let r = commands::ollama_stop().await;
                         (r.success, r.output)
                     },
                     |(ok, msg)| Message::OllamaActionDone(ok, msg),
                 );
             }
             Message::OllamaPull(model) => {
-                self.ollama.progress = Some(format!("Pulling {model}..."));
+                if self.ollama.pull.is_some() {
+                    return Task::none();
+                }
                 self.ollama.error = None;
-                return Task::perform(
-                    async move {
-                        let r = commands::ollama_pull(&model);
-                        (r.success, r.output)
+                let (task, handle) =
+                    Task::run(crate::ollama::pull(model.clone()), Message::OllamaPullEvent)
+                        .abortable();
+                self.ollama.pull = Some(PullProgress {
+                    model,
+                    status: String::new(),
+                    layers: Vec::new(),
+                    handle,
+                });
+                return task;
+            }
+            Message::OllamaPullEvent(event) => {
+                let Some(pull) = &mut self.ollama.pull else {
+                    return Task::none();
+                };
+                match event {
+                    PullEvent::Status(status) => pull.status = status,
+                    PullEvent::Layer {
+                        digest,
+                        completed,
+                        total,
+                    } => match pull.layers.iter_mut().find(|layer| layer.digest == digest) {
+                        Some(layer) => {
+                            layer.completed = completed;
+                            layer.total = total;
+                        }
+                        None => pull.layers.push(PullLayer {
+                            digest,
+                            completed,
+                            total,
+                        }),
                     },
-                    |(ok, msg)| Message::OllamaActionDone(ok, msg),
-                );
+                    PullEvent::Done => {
+                        self.ollama.pull = None;
+                        return Task::perform(do_ollama_refresh(), |(running, models, available)| {
+                            Message::OllamaRefreshDone { running, models, available }
+                        });
+                    }
+                    PullEvent::Failed(e) => {
+                        self.ollama.pull = None;
+                        self.ollama.error = Some(e);
+                    }
+                }
+            }
+            Message::OllamaCancelPull => {
+                // Dropping the request makes the server stop the download.
+                if let Some(pull) = self.ollama.pull.take() {
+                    pull.handle.abort();
+                }
             }
             Message::OllamaRemove(model) => {
-                self.ollama.progress = Some(format!("Removing {model}..."));
+                self.ollama.progress = Some(t!("settings-ollama-removing", model = model.clone()));
                 return Task::perform(
                     async move {
-                        let r = commands::ollama_remove(&model);
+                        let r = commands::ollama_remove(&model).await;
                         (r.success, r.output)
                     },
                     |(ok, msg)| Message::OllamaActionDone(ok, msg),
@@ -320,7 +1116,7 @@ impl SettingsApp {
             Message::OllamaActionDone(success, msg) => {
                 if success {
                     self.ollama.error = None;
-                    return Task::perform(async { do_ollama_refresh() }, |(running, models, available)| {
+                    return Task::perform(do_ollama_refresh(), |(running, models, available)| {
                         Message::OllamaRefreshDone { running, models, available }
                     });
                 } else {
@@ -331,29 +1127,18 @@ impl SettingsApp {
 
             // -- AI Provider --
             Message::AiConfigLoaded(provider, api_key, model, base_url) => {
-                let is_ollama = provider == "ollama";
-                self.ai.provider = provider;
+                self.ai.provider = provider.clone();
                 self.ai.api_key = api_key;
                 self.ai.model = model;
                 self.ai.base_url = base_url;
                 self.ai.saved = false;
-                if is_ollama {
-                    return Task::perform(
-                        async { fetch_installed_ollama_models() },
-                        Message::AiInstalledModels,
-                    );
-                }
+                return Task::perform(fetch_models(provider), Message::AiModelsListed);
             }
             Message::AiSelectProvider(p) => {
                 self.ai.provider = p.clone();
                 self.ai.saved = false;
-                // Load installed models when switching to Ollama
-                if p == "ollama" {
-                    return Task::perform(
-                        async { fetch_installed_ollama_models() },
-                        Message::AiInstalledModels,
-                    );
-                }
+                self.ai.models.clear();
+                return Task::perform(fetch_models(p), Message::AiModelsListed);
             }
             Message::AiApiKeyChanged(v) => {
                 self.ai.api_key = v;
@@ -373,7 +1158,7 @@ impl SettingsApp {
                 let model = self.ai.model.clone();
                 let base_url = self.ai.base_url.clone();
                 return Task::perform(
-                    async move { save_ai_config(&provider, &api_key, &model, &base_url) },
+                    async move { save_ai_config(&provider, &api_key, &model, &base_url).await },
                     |(ok, msg)| Message::AiSaveDone(ok, msg),
                 );
             }
@@ -381,61 +1166,742 @@ impl SettingsApp {
                 if success {
                     self.ai.saved = true;
                     self.ai.error = None;
-                    // Notify agent to hot-reload config via IPC
-                    return Task::perform(
-                        async { notify_agent_reload().await },
-                        |(ok, msg)| Message::AiReloadDone(ok, msg),
-                    );
                 } else {
                     self.ai.error = Some(msg);
                 }
             }
-            Message::AiReloadDone(success, msg) => {
-                if success {
-                    tracing::info!("Agent config reloaded: {msg}");
-                } else {
-                    tracing::warn!("Agent reload failed: {msg}");
-                    // Not a fatal error — config is saved, agent will pick it up on next restart
-                }
+            Message::HardwareProbed(memory) => {
+                self.ai.memory = memory;
+                self.ollama.memory = memory;
             }
-            Message::AiInstalledModels(models) => {
-                self.ai.installed_models = models;
+            Message::AiModelsListed(models) => {
+                self.ai.models = models;
             }
             Message::AiPickModel(model) => {
                 self.ai.model = model;
                 self.ai.saved = false;
             }
-        }
-        Task::none()
-    }
-
-    pub fn view(&self) -> Element<'_, Message> {
-        use iced::widget::{button, column, container, row, text, Space};
-        use iced::Length;
+            Message::AiRunSetupWizard => {
+                if let Err(e) = std::process::Command::new("aios-chat").arg("--setup").spawn() {
+                    tracing::error!("Failed to launch setup wizard: {e}");
+                    self.ai.error = Some(t!("settings-ai-setup-failed", error = e.to_string()));
+                }
+            }
 
-        // Title bar with close button
-        let title_bar = {
-            let title = text("AIOS Settings").size(18).color(theme::SettingsColors::TEXT_PRIMARY);
-            let close_btn = button(text("X").size(14).color(theme::SettingsColors::TEXT_SECONDARY))
-                .on_press(Message::CloseWindow)
-                .padding([4, 10])
-                .style(theme::close_button);
-            container(
-                row![title, Space::new().width(Length::Fill), close_btn]
-                    .align_y(iced::Alignment::Center),
-            )
-            .width(Length::Fill)
-            .padding(12)
-            .style(theme::container_secondary)
-        };
+            // -- Agent --
+            Message::AgentSocketChanged(path) => {
+                self.agent.socket_path = path;
+                self.agent.saved = false;
+            }
+            Message::AgentAuditLogChanged(path) => {
+                self.agent.audit_log = path;
+                self.agent.audit_check = None;
+                self.agent.saved = false;
+            }
+            Message::AgentMaxDestructiveChanged(value) => {
+                self.agent.max_destructive = value;
+                self.agent.saved = false;
+            }
+            Message::AgentMaxParallelToolsChanged(value) => {
+                self.agent.max_parallel_tools = value;
+                self.agent.saved = false;
+            }
+            Message::AgentAuditLlm(enabled) => {
+                self.agent.audit_llm = enabled;
+                self.agent.saved = false;
+            }
+            Message::AgentShowPlans(enabled) => {
+                self.agent.show_plans = enabled;
+                self.agent.saved = false;
+            }
+            Message::AgentPromptEdit(action) => {
+                if action.is_edit() {
+                    self.agent.saved = false;
+                }
+                self.agent.prompt.perform(action);
+            }
+            Message::AgentSave => {
+                let Ok(max_destructive_per_minute) = self.agent.max_destructive.trim().parse()
+                else {
+                    self.agent.error = Some(t!("settings-agent-invalid-limit"));
+                    return Task::none();
+                };
+                let Ok(max_parallel_tools) = self.agent.max_parallel_tools.trim().parse() else {
+                    self.agent.error = Some(t!("settings-agent-invalid-limit"));
+                    return Task::none();
+                };
+                let prompt = self.agent.prompt.text();
+                let config = AgentConfig {
+                    socket_path: self.agent.socket_path.trim().to_owned(),
+                    audit_log: self.agent.audit_log.trim().to_owned(),
+                    max_destructive_per_minute,
+                    audit_llm: self.agent.audit_llm,
+                    max_parallel_tools,
+                    show_plans: self.agent.show_plans,
+                    confirm_command: self.agent.confirm_command.clone(),
+                    system_prompt: (!prompt.trim().is_empty())
+                        .then(|| prompt.trim_end().to_owned()),
+                };
+                return Task::perform(
+                    async move { save_agent_config(&config).await },
+                    |(ok, msg)| Message::AgentSaveDone(ok, msg),
+                );
+            }
+            Message::AgentSaveDone(success, msg) => {
+                if success {
+                    self.agent.saved = true;
+                    self.agent.error = None;
+                } else {
+                    self.agent.error = Some(msg);
+                }
+            }
+            Message::AgentReload => {
+                self.agent.status = None;
+                let socket = self.agent.running_socket.clone();
+                return Task::perform(
+                    async move { reload_agent(&socket).await },
+                    |(ok, msg)| Message::AgentReloadDone(ok, msg),
+                );
+            }
+            Message::AgentReloadDone(success, msg) => {
+                self.agent.status = Some((success, msg));
+            }
+            Message::AgentRestart => {
+                self.agent.status = None;
+                let socket = self.agent.running_socket.clone();
+                return Task::perform(
+                    async move { restart_agent(&socket).await },
+                    |(ok, msg)| Message::AgentRestartDone(ok, msg),
+                );
+            }
+            Message::AgentRestartDone(success, msg) => {
+                if success {
+                    // The new process binds whatever the file says now.
+                    self.agent.running_socket = agent_ipc::socket_path();
+                }
+                self.agent.status = Some((success, msg));
+            }
+            Message::AgentVerifyAudit => {
+                let path = self.agent.audit_log.trim().to_owned();
+                return Task::perform(verify_audit_log(path), |(ok, msg)| {
+                    Message::AgentAuditVerified(ok, msg)
+                });
+            }
+            Message::AgentAuditVerified(success, msg) => {
+                self.agent.audit_check = Some((success, msg));
+            }
+            Message::AgentLog(event) => match event {
+                LogEvent::Connected => {
+                    self.agent.logs.clear();
+                    self.agent.disconnected = None;
+                }
+                LogEvent::Lines(lines) => {
+                    self.agent.logs.extend(lines);
+                    let excess = self.agent.logs.len().saturating_sub(AGENT_LOG_LINES);
+                    self.agent.logs.drain(..excess);
+                }
+                LogEvent::Disconnected(reason) => {
+                    self.agent.disconnected = Some(reason);
+                }
+            },
+            Message::AgentLogFilterChanged(filter) => {
+                self.agent.log_filter = filter;
+            }
+
+            // -- Language --
+            Message::LanguageSelect(code) => {
+                // Applied to this window right away; other apps pick it up
+                // from the config watcher.
+                i18n::set_language(&code);
+                self.language.selected = code.clone();
+                self.language.saved = false;
+                return Task::perform(
+                    async move { save_ui_language(&code).await },
+                    |(ok, msg)| Message::LanguageSaveDone(ok, msg),
+                );
+            }
+            Message::LanguageSaveDone(success, msg) => {
+                if success {
+                    self.language.saved = true;
+                    self.language.error = None;
+                } else {
+                    self.language.error = Some(msg);
+                }
+            }
+
+            // -- Appearance --
+            Message::AppearanceSelectScheme(scheme) => {
+                self.appearance.color_scheme = scheme;
+                return self.save_appearance();
+            }
+            Message::AppearanceSelectAccent(accent) => {
+                self.appearance.accent_input = accent.clone().unwrap_or_default();
+                self.appearance.accent = accent;
+                return self.save_appearance();
+            }
+            Message::AppearanceAccentInput(value) => {
+                self.appearance.accent_input = value;
+            }
+            Message::AppearanceAccentSubmit => {
+                let input = self.appearance.accent_input.trim();
+                if input.is_empty() {
+                    self.appearance.accent = None;
+                } else if let Some(color) = aios_theme::parse_hex(input) {
+                    self.appearance.accent = Some(aios_theme::to_hex(color));
+                } else {
+                    self.appearance.saved = false;
+                    self.appearance.error = Some(t!("settings-appearance-invalid-accent"));
+                    return Task::none();
+                }
+                return self.save_appearance();
+            }
+            Message::AppearanceSelectFontScale(scale) => {
+                self.appearance.font_scale = scale;
+                return self.save_appearance();
+            }
+            Message::AppearanceSelectDensity(density) => {
+                self.appearance.density = density;
+                return self.save_appearance();
+            }
+            Message::AppearanceWallpapersLoaded(wallpapers) => {
+                self.appearance.wallpapers = wallpapers;
+            }
+            Message::AppearanceSelectWallpaper(wallpaper) => {
+                self.appearance.wallpaper_input = wallpaper.clone().unwrap_or_default();
+                self.appearance.wallpaper = wallpaper;
+                return self.save_appearance();
+            }
+            Message::AppearanceWallpaperInput(value) => {
+                self.appearance.wallpaper_input = value;
+            }
+            Message::AppearanceWallpaperSubmit => {
+                let input = self.appearance.wallpaper_input.trim();
+                if input.is_empty() {
+                    self.appearance.wallpaper = None;
+                } else if std::path::Path::new(input).is_file() {
+                    self.appearance.wallpaper = Some(input.to_owned());
+                } else {
+                    self.appearance.saved = false;
+                    self.appearance.error = Some(t!("settings-appearance-wallpaper-missing"));
+                    return Task::none();
+                }
+                return self.save_appearance();
+            }
+            Message::AppearanceSelectDockPosition(position) => {
+                self.appearance.dock_position = position;
+                return self.save_appearance();
+            }
+            Message::AppearanceSaveDone(success, msg) => {
+                if success {
+                    self.appearance.saved = true;
+                    self.appearance.error = None;
+                } else {
+                    self.appearance.error = Some(msg);
+                }
+            }
+            Message::AppearanceChanged => {
+                // The watcher already applied palette and language; keep the
+                // pickers in sync with edits made outside this window.
+                self.load_ui_config(&UiConfig::load());
+            }
+
+            // -- Dock --
+            Message::DockAppsLoaded(apps) => {
+                self.dock.available = apps;
+            }
+            Message::DockPin(id) => {
+                if !self.dock.pinned.contains(&id) {
+                    self.dock.pinned.push(id);
+                    return self.save_dock();
+                }
+            }
+            Message::DockUnpin(index) => {
+                if index < self.dock.pinned.len() {
+                    self.dock.pinned.remove(index);
+                    return self.save_dock();
+                }
+            }
+            Message::DockMoveUp(index) => {
+                if index > 0 && index < self.dock.pinned.len() {
+                    self.dock.pinned.swap(index - 1, index);
+                    return self.save_dock();
+                }
+            }
+            Message::DockFilterChanged(value) => {
+                self.dock.filter = value;
+            }
+            Message::DockGpuMeter(enabled) => {
+                self.dock.gpu_meter = enabled;
+                self.dock.saved = false;
+                return Task::perform(
+                    async move { save_ui_gpu_meter(enabled).await },
+                    |(ok, msg)| Message::DockSaveDone(ok, msg),
+                );
+            }
+            Message::DockSaveDone(success, msg) => {
+                if success {
+                    self.dock.saved = true;
+                    self.dock.error = None;
+                } else {
+                    self.dock.error = Some(msg);
+                }
+            }
+
+            // -- Permissions --
+            Message::PermissionsToggle(name, enabled) => {
+                let policy = self.permissions.policies.entry(name).or_default();
+                policy.enabled = enabled;
+                return self.save_permissions();
+            }
+            Message::PermissionsTrust(name, trust) => {
+                let policy = self.permissions.policies.entry(name).or_default();
+                policy.trust = trust;
+                return self.save_permissions();
+            }
+            Message::PermissionsSaveDone(success, msg) => {
+                if success {
+                    self.permissions.saved = true;
+                    self.permissions.error = None;
+                } else {
+                    self.permissions.error = Some(msg);
+                }
+            }
+
+            // -- MCP servers --
+            Message::McpNameChanged(name) => {
+                self.mcp.new_name = name;
+            }
+            Message::McpTransportSelected(transport) => {
+                self.mcp.new_server.transport = transport;
+            }
+            Message::McpCommandChanged(command) => {
+                self.mcp.new_server.command = command;
+            }
+            Message::McpArgsChanged(args) => {
+                self.mcp.new_args = args;
+            }
+            Message::McpUrlChanged(url) => {
+                self.mcp.new_server.url = (!url.is_empty()).then_some(url);
+            }
+            Message::McpAdd => {
+                let name = self.mcp.new_name.trim().to_owned();
+                let mut server = self.mcp.new_server.clone();
+                server.command = server.command.trim().to_owned();
+                server.args = self
+                    .mcp
+                    .new_args
+                    .split_whitespace()
+                    .map(str::to_owned)
+                    .collect();
+                if let Some(error) = check_new_mcp_server(&name, &server, &self.mcp.servers) {
+                    self.mcp.error = Some(error);
+                    return Task::none();
+                }
+                self.mcp.servers.insert(name.clone(), server);
+                self.mcp.new_name.clear();
+                self.mcp.new_server = McpServerConfig::default();
+                self.mcp.new_args.clear();
+                self.mcp.error = None;
+                return Task::batch([self.save_mcp(), self.update(Message::McpTest(name))]);
+            }
+            Message::McpRemove(name) => {
+                self.mcp.servers.remove(&name);
+                self.mcp.tests.remove(&name);
+                return self.save_mcp();
+            }
+            Message::McpToggle(name, enabled) => {
+                if let Some(server) = self.mcp.servers.get_mut(&name) {
+                    server.enabled = enabled;
+                    return self.save_mcp();
+                }
+            }
+            Message::McpTrust(name, trust) => {
+                if let Some(server) = self.mcp.servers.get_mut(&name) {
+                    server.trust = trust;
+                    return self.save_mcp();
+                }
+            }
+            Message::McpTest(name) => {
+                if let Some(server) = self.mcp.servers.get(&name).cloned() {
+                    self.mcp.tests.insert(name.clone(), McpTest::Running);
+                    let probed = name.clone();
+                    return Task::perform(
+                        async move {
+                            external::probe(&probed, &server)
+                                .await
+                                .map_err(|e| format!("{e:#}"))
+                        },
+                        move |result| Message::McpTestDone(name.clone(), result),
+                    );
+                }
+            }
+            Message::McpTestDone(name, result) => {
+                let test = match result {
+                    Ok(tools) => McpTest::Tools(tools),
+                    Err(e) => McpTest::Failed(e),
+                };
+                self.mcp.tests.insert(name, test);
+            }
+            Message::McpSaveDone(success, msg) => {
+                if success {
+                    self.mcp.saved = true;
+                    self.mcp.error = None;
+                } else {
+                    self.mcp.error = Some(msg);
+                }
+            }
+
+            // -- Activity --
+            Message::ActivityRefresh => return self.activity_query(),
+            Message::ActivityPeriod(period) => {
+                self.activity.period = period;
+                return self.activity_query();
+            }
+            Message::ActivityActionChanged(action) => {
+                self.activity.action = action;
+            }
+            Message::ActivitySearchChanged(search) => {
+                self.activity.search = search;
+            }
+            Message::ActivityErrorsOnly(errors_only) => {
+                self.activity.errors_only = errors_only;
+                return self.activity_query();
+            }
+            Message::ActivityLoaded(result) => {
+                self.activity.loading = false;
+                match result {
+                    Ok((entries, truncated)) => {
+                        self.activity.entries = entries;
+                        self.activity.truncated = truncated;
+                        self.activity.error = None;
+                    }
+                    Err(e) => self.activity.error = Some(e),
+                }
+            }
+
+            // -- Startup --
+            Message::StartupRefresh => {
+                self.startup.error = None;
+                return startup_refresh_task();
+            }
+            Message::StartupServicesLoaded(result) => match result {
+                Ok(services) => self.startup.services = services,
+                Err(e) => self.startup.error = Some(e),
+            },
+            Message::StartupEntriesLoaded(entries) => {
+                self.startup.entries = entries;
+            }
+            Message::StartupServiceEnabled(name, enabled) => {
+                return Task::perform(
+                    async move {
+                        let r = crate::startup::set_enabled(&name, enabled).await;
+                        (r.success, r.output)
+                    },
+                    |(ok, msg)| Message::StartupServiceDone(ok, msg),
+                );
+            }
+            Message::StartupServiceRun(name, running) => {
+                return Task::perform(
+                    async move {
+                        let r = crate::startup::set_running(&name, running).await;
+                        (r.success, r.output)
+                    },
+                    |(ok, msg)| Message::StartupServiceDone(ok, msg),
+                );
+            }
+            Message::StartupServiceDone(success, msg) => {
+                self.startup.error = (!success).then_some(msg);
+                return Task::batch([
+                    Task::perform(crate::startup::services(), Message::StartupServicesLoaded),
+                    Task::perform(
+                        crate::session_ipc::components(),
+                        Message::StartupComponentsLoaded,
+                    ),
+                ]);
+            }
+            Message::StartupComponentsLoaded(components) => {
+                self.startup.components = components;
+            }
+            Message::StartupComponentRun(name, running) => {
+                return Task::perform(
+                    crate::session_ipc::set_running(name, running),
+                    |(ok, msg)| Message::StartupServiceDone(ok, msg),
+                );
+            }
+            Message::StartupEntryEnabled(id, enabled) => {
+                return startup_entry_action(move || {
+                    crate::startup::set_autostart_enabled(&id, enabled)
+                });
+            }
+            Message::StartupAddEntry(id) => {
+                return startup_entry_action(move || crate::startup::add_autostart(&id));
+            }
+            Message::StartupRemoveEntry(id) => {
+                return startup_entry_action(move || crate::startup::remove_autostart(&id));
+            }
+            Message::StartupEntryDone(result) => {
+                self.startup.error = result.err();
+                return Task::perform(
+                    async { crate::startup::autostart_entries() },
+                    Message::StartupEntriesLoaded,
+                );
+            }
+
+            // -- About --
+            Message::AboutAgentLoaded(result) => match result {
+                Ok(agent) => {
+                    self.about.agent = agent;
+                    self.about.agent_error = None;
+                }
+                Err(e) => {
+                    self.about.agent = None;
+                    self.about.agent_error = Some(e);
+                }
+            },
+            Message::AboutCheckUpdates => {
+                if !self.about.checking && !self.about.installing {
+                    self.about.checking = true;
+                    self.about.error = None;
+                    return Task::perform(
+                        crate::about::check_updates(),
+                        Message::AboutUpdatesChecked,
+                    );
+                }
+            }
+            Message::AboutUpdatesChecked(result) => {
+                self.about.checking = false;
+                match result {
+                    Ok(updates) => {
+                        self.about.updates = Some(updates);
+                        self.about.installed = false;
+                    }
+                    Err(e) => self.about.error = Some(e),
+                }
+            }
+            Message::AboutInstallUpdates => {
+                if !self.about.checking && !self.about.installing {
+                    self.about.installing = true;
+                    self.about.error = None;
+                    return Task::perform(
+                        crate::about::install_updates(),
+                        Message::AboutUpdatesInstalled,
+                    );
+                }
+            }
+            Message::AboutUpdatesInstalled(result) => {
+                self.about.installing = false;
+                match result {
+                    Ok(()) => {
+                        self.about.updates = Some(Vec::new());
+                        self.about.installed = true;
+                    }
+                    Err(e) => self.about.error = Some(e),
+                }
+            }
+        }
+        Task::none()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let mut subscriptions = vec![
+            aios_theme::subscription().map(|()| Message::AppearanceChanged),
+            iced::event::listen_with(key_event),
+        ];
+        if self.active_tab == Tab::Agent {
+            let socket = self.agent.running_socket.clone();
+            subscriptions
+                .push(Subscription::run_with(socket, agent_ipc::log_tail).map(Message::AgentLog));
+        }
+        if self.network.loading {
+            subscriptions
+                .push(iced::time::every(SPINNER_INTERVAL).map(|_| Message::WifiSpinnerTick));
+        }
+        Subscription::batch(subscriptions)
+    }
+
+    /// Fill the Agent tab from the `[agent]` config section.
+    fn load_agent_config(&mut self, config: &AgentConfig) {
+        self.agent.socket_path.clone_from(&config.socket_path);
+        self.agent.running_socket.clone_from(&config.socket_path);
+        self.agent.audit_log.clone_from(&config.audit_log);
+        self.agent.max_destructive = config.max_destructive_per_minute.to_string();
+        self.agent.max_parallel_tools = config.max_parallel_tools.to_string();
+        self.agent.audit_llm = config.audit_llm;
+        self.agent.show_plans = config.show_plans;
+        self.agent.confirm_command.clone_from(&config.confirm_command);
+        self.agent.prompt =
+            text_editor::Content::with_text(config.system_prompt.as_deref().unwrap_or_default());
+    }
+
+    /// Mirror the `[ui]` config section into the Language, Appearance and
+    /// Dock tabs.
+    fn load_ui_config(&mut self, ui: &UiConfig) {
+        self.dock.pinned.clone_from(&ui.pinned_apps);
+        self.dock.gpu_meter = ui.gpu_meter;
+        self.language.selected.clone_from(&ui.language);
+        self.appearance.color_scheme = ui.color_scheme;
+        self.appearance.accent.clone_from(&ui.accent);
+        self.appearance.accent_input = ui.accent.clone().unwrap_or_default();
+        self.appearance.font_scale = ui.font_scale;
+        self.appearance.density = ui.density;
+        self.appearance.wallpaper.clone_from(&ui.wallpaper);
+        self.appearance.wallpaper_input = ui.wallpaper.clone().unwrap_or_default();
+        self.appearance.dock_position = ui.dock_position;
+    }
+
+    /// Apply the selected appearance to this window and the desktop
+    /// background, then persist it; the other apps follow the file.
+    fn save_appearance(&mut self) -> Task<Message> {
+        let ui = UiConfig {
+            language: self.language.selected.clone(),
+            color_scheme: self.appearance.color_scheme,
+            accent: self.appearance.accent.clone(),
+            font_scale: self.appearance.font_scale,
+            density: self.appearance.density,
+            dock_position: self.appearance.dock_position,
+            wallpaper: self.appearance.wallpaper.clone(),
+            ..UiConfig::default()
+        };
+        aios_theme::apply(&ui);
+        // Without a wallpaper the desktop takes the scheme's background.
+        let background = aios_theme::to_hex(aios_theme::palette().bg_primary);
+        self.appearance.saved = false;
+        Task::perform(
+            async move {
+                let (saved, msg) = save_ui_appearance(&ui).await;
+                if !saved {
+                    return (false, msg);
+                }
+                let applied = wallpaper::apply(ui.wallpaper.as_deref(), &background).await;
+                (applied.success, applied.output)
+            },
+            |(ok, msg)| Message::AppearanceSaveDone(ok, msg),
+        )
+    }
+
+    /// Persist the power settings and apply them to the session.
+    fn save_power(&mut self) -> Task<Message> {
+        let config = self.power.config;
+        self.power.saved = false;
+        Task::perform(
+            async move { save_power_config(&config).await },
+            |(ok, msg)| Message::PowerSaveDone(ok, msg),
+        )
+    }
+
+    /// Persist the input settings and reload sway with them.
+    fn save_input(&mut self) -> Task<Message> {
+        let config = self.input.config.clone();
+        self.input.saved = false;
+        Task::perform(
+            async move { save_input_config(&config).await },
+            |(ok, msg)| Message::InputSaveDone(ok, msg),
+        )
+    }
+
+    /// List the built-in tools and read their overrides.
+    fn load_permissions(&mut self) {
+        let mut tools = ToolRegistry::with_defaults().definitions();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        self.permissions.tools = tools;
+        self.permissions.policies = ToolPolicy::load_all();
+    }
+
+    /// Persist the tool overrides, then have the agent reload them.
+    fn save_permissions(&mut self) -> Task<Message> {
+        // Entries equal to the defaults are dropped to keep the file tidy.
+        self.permissions
+            .policies
+            .retain(|_, policy| *policy != ToolPolicy::default());
+        let policies = self.permissions.policies.clone();
+        self.permissions.saved = false;
+        Task::perform(
+            async move { save_tool_policies(&policies).await },
+            |(ok, msg)| Message::PermissionsSaveDone(ok, msg),
+        )
+    }
+
+    /// Persist the MCP servers, then have the agent reconnect them.
+    fn save_mcp(&mut self) -> Task<Message> {
+        let servers = self.mcp.servers.clone();
+        self.mcp.saved = false;
+        Task::perform(
+            async move { save_mcp_servers(&servers).await },
+            |(ok, msg)| Message::McpSaveDone(ok, msg),
+        )
+    }
+
+    /// Persist the pinned apps; the dock picks them up from the file.
+    fn save_dock(&mut self) -> Task<Message> {
+        let pinned = self.dock.pinned.clone();
+        self.dock.saved = false;
+        Task::perform(
+            async move { save_ui_pinned_apps(&pinned).await },
+            |(ok, msg)| Message::DockSaveDone(ok, msg),
+        )
+    }
+
+    /// Ask the agent for the history matching the Activity tab's filters.
+    fn activity_query(&mut self) -> Task<Message> {
+        let non_empty = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_owned())
+        };
+        let filter = AuditFilter {
+            action: non_empty(&self.activity.action),
+            kind: None,
+            outcome: self.activity.errors_only.then_some(AuditOutcome::Error),
+            text: non_empty(&self.activity.search),
+        };
+        self.activity.loading = true;
+        Task::perform(
+            crate::activity::query(self.activity.period, filter),
+            Message::ActivityLoaded,
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        use iced::widget::{button, column, container, row, text, Space};
+        use iced::Length;
+
+        // Title bar with close button
+        let title_bar = {
+            let title = text(t!("settings-title")).size(18).color(theme::SettingsColors::text_primary());
+            let close_btn = crate::views::named(
+                button(text("X").size(14).color(theme::SettingsColors::text_secondary()))
+                    .on_press(Message::CloseWindow)
+                    .padding([4, 10])
+                    .style(theme::close_button),
+                t!("settings-close"),
+            );
+            container(
+                row![title, Space::new().width(Length::Fill), close_btn]
+                    .align_y(iced::Alignment::Center),
+            )
+            .width(Length::Fill)
+            .padding(12)
+            .style(theme::container_secondary)
+        };
 
         let sidebar_view = sidebar::view(self.active_tab);
 
         let tab_content: Element<'_, Message> = match self.active_tab {
             Tab::Network => network::view(&self.network),
             Tab::Display => display::view(&self.display),
+            Tab::Audio => audio::view(&self.audio),
+            Tab::Power => power::view(&self.power),
+            Tab::Input => input::view(&self.input),
             Tab::Ollama => ollama::view(&self.ollama),
             Tab::Ai => ai::view(&self.ai),
+            Tab::Agent => agent::view(&self.agent),
+            Tab::Activity => activity::view(&self.activity),
+            Tab::Language => language::view(&self.language),
+            Tab::Appearance => appearance::view(&self.appearance),
+            Tab::Dock => dock::view(&self.dock),
+            Tab::Startup => startup::view(&self.startup, &self.dock.available),
+            Tab::Permissions => permissions::view(&self.permissions),
+            Tab::Mcp => mcp::view(&self.mcp),
+            Tab::About => about::view(&self.about),
         };
 
         let body = row![sidebar_view, tab_content];
@@ -448,49 +1914,105 @@ impl SettingsApp {
     }
 }
 
+/// Keyboard navigation: switching tabs with Ctrl, moving the focus with
+/// Tab when the focused widget did not use it.
+fn key_event(
+    event: iced::Event,
+    status: iced::event::Status,
+    _window: iced::window::Id,
+) -> Option<Message> {
+    use iced::keyboard::{self, key::Named};
+
+    let iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = event else {
+        return None;
+    };
+    match key {
+        keyboard::Key::Named(Named::Tab) if modifiers.command() => {
+            Some(Message::CycleTab(if modifiers.shift() { -1 } else { 1 }))
+        }
+        keyboard::Key::Named(Named::PageDown) if modifiers.command() => Some(Message::CycleTab(1)),
+        keyboard::Key::Named(Named::PageUp) if modifiers.command() => Some(Message::CycleTab(-1)),
+        keyboard::Key::Named(Named::Tab) if status == iced::event::Status::Ignored => {
+            Some(if modifiers.shift() {
+                Message::FocusPrevious
+            } else {
+                Message::FocusNext
+            })
+        }
+        _ => None,
+    }
+}
+
 // -- Async helpers --
 
-fn do_wifi_scan() -> (Vec<WifiNetwork>, String) {
-    let status_result = commands::network_status();
-    let scan_result = commands::wifi_scan();
+/// Reload service and component status and autostart entries.
+fn startup_refresh_task() -> Task<Message> {
+    Task::batch([
+        Task::perform(crate::startup::services(), Message::StartupServicesLoaded),
+        Task::perform(crate::session_ipc::components(), Message::StartupComponentsLoaded),
+        Task::perform(
+            async { crate::startup::autostart_entries() },
+            Message::StartupEntriesLoaded,
+        ),
+    ])
+}
 
-    let networks = if scan_result.success {
-        parse_wifi_list(&scan_result.output)
-    } else {
-        Vec::new()
-    };
+/// Change an autostart entry, then reload the list.
+fn startup_entry_action(
+    change: impl FnOnce() -> Result<(), String> + Send + 'static,
+) -> Task<Message> {
+    Task::perform(async move { change() }, Message::StartupEntryDone)
+}
 
-    (networks, status_result.output.trim().to_owned())
+/// Rescan Wi-Fi, showing the networks NetworkManager already knows first.
+fn wifi_scan_task() -> Task<Message> {
+    Task::perform(do_wifi_scan(false), |(nets, status)| {
+        Message::WifiListed(nets, status)
+    })
+    .chain(Task::perform(do_wifi_scan(true), |(nets, status)| {
+        Message::WifiScanDone(nets, status)
+    }))
 }
 
-fn parse_wifi_list(output: &str) -> Vec<WifiNetwork> {
-    let mut networks = Vec::new();
-    for line in output.lines() {
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() >= 4 {
-            let ssid = parts[0].trim().to_owned();
-            if ssid.is_empty() {
-                continue;
-            }
-            let signal = parts[1].trim().parse::<u8>().unwrap_or(0);
-            let security = parts[2].trim().to_owned();
-            let connected = parts[3].trim() == "*";
-            networks.push(WifiNetwork {
-                ssid,
-                signal,
-                security,
-                connected,
-            });
-        }
-    }
-    // Deduplicate by SSID, keep highest signal
-    networks.sort_by(|a, b| b.signal.cmp(&a.signal));
-    networks.dedup_by(|a, b| a.ssid == b.ssid);
-    networks
+/// Rescan Wi-Fi and reload the saved connections.
+fn network_refresh_task() -> Task<Message> {
+    Task::batch([
+        Task::done(Message::WifiScan),
+        Task::perform(do_saved_connections(), Message::SavedConnectionsLoaded),
+    ])
+}
+
+async fn do_wifi_scan(rescan: bool) -> (Vec<WifiNetwork>, String) {
+    let rescan = if rescan { Rescan::Yes } else { Rescan::No };
+    let (status, networks) = tokio::join!(nmcli::device_status(), nmcli::wifi_list(rescan));
+    let status = status
+        .unwrap_or_default()
+        .iter()
+        .map(|d| match &d.connection {
+            Some(connection) => format!("{}: {} ({connection})", d.device, d.state),
+            None => format!("{}: {}", d.device, d.state),
+        })
+        .collect::<Vec<_>>()
+        .join(" · ");
+    (networks.unwrap_or_default(), status)
+}
+
+async fn do_saved_connections() -> Vec<SavedConnection> {
+    nmcli::saved_connections().await.unwrap_or_default()
+}
+
+/// Run an nmcli change and report it back as [`Message::WifiActionDone`].
+fn nmcli_action(
+    run: impl Future<Output = Result<String, NmcliError>> + Send + 'static,
+) -> Task<Message> {
+    Task::perform(run, |result| match result {
+        Ok(msg) => Message::WifiActionDone(true, msg),
+        Err(e) => Message::WifiActionDone(false, e.to_string()),
+    })
 }
 
-fn do_display_refresh() -> Vec<DisplayOutput> {
-    let result = commands::display_list();
+async fn do_display_refresh() -> Vec<DisplayOutput> {
+    let result = commands::display_list().await;
     if !result.success {
         return Vec::new();
     }
@@ -539,88 +2061,125 @@ fn parse_sway_outputs(json_str: &str) -> Vec<DisplayOutput> {
         .collect()
 }
 
-fn do_ollama_refresh() -> (bool, Vec<String>, Vec<String>) {
-    let status = commands::ollama_status();
+fn audio_refresh_task() -> Task<Message> {
+    Task::perform(do_audio_refresh(), |(sinks, sources)| {
+        Message::AudioRefreshDone(sinks, sources)
+    })
+}
+
+/// Run a `wpctl` change off the UI thread; the devices are re-read afterwards.
+fn audio_action(run: impl Future<Output = commands::CmdResult> + Send + 'static) -> Task<Message> {
+    Task::perform(
+        async move {
+            let r = run.await;
+            (r.success, r.output)
+        },
+        |(ok, msg)| Message::AudioActionDone(ok, msg),
+    )
+}
+
+async fn do_audio_refresh() -> (Vec<AudioDevice>, Vec<AudioDevice>) {
+    let result = commands::audio_status().await;
+    if !result.success {
+        return (Vec::new(), Vec::new());
+    }
+    wpctl::parse_status(&result.output)
+}
+
+fn power_profiles_task() -> Task<Message> {
+    Task::perform(do_power_profiles(), |(profiles, active)| {
+        Message::PowerProfilesLoaded(profiles, active)
+    })
+}
+
+async fn do_power_profiles() -> (Vec<String>, Option<String>) {
+    let result = commands::power_profiles_list().await;
+    if !result.success {
+        return (Vec::new(), None);
+    }
+    parse_power_profiles(&result.output)
+}
+
+/// Profiles from `powerprofilesctl list`, where each profile is a heading
+/// followed by indented details and the active one is starred:
+///
+/// ```text
+///   performance:
+///     Driver:     platform_profile
+///
+/// * balanced:
+///     Driver:     platform_profile
+/// ```
+fn parse_power_profiles(output: &str) -> (Vec<String>, Option<String>) {
+    let mut profiles = Vec::new();
+    let mut active = None;
+    for line in output.lines() {
+        let (starred, rest) = match line.strip_prefix("* ") {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix("  ").unwrap_or(line)),
+        };
+        if rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let Some(name) = rest.trim_end().strip_suffix(':') else {
+            continue;
+        };
+        if starred {
+            active = Some(name.to_owned());
+        }
+        profiles.push(name.to_owned());
+    }
+    (profiles, active)
+}
+
+async fn do_ollama_refresh() -> (bool, Vec<LocalModel>, Vec<String>) {
+    let status = commands::ollama_status().await;
     let running = status.success && status.output.trim() == "active";
 
-    let models_result = commands::ollama_list_models();
-    let models: Vec<String> = if models_result.success {
-        models_result
-            .output
-            .lines()
-            .skip(1) // skip header
-            .filter_map(|line| {
-                let name = line.split_whitespace().next()?;
-                if name.is_empty() {
-                    None
-                } else {
-                    Some(name.to_owned())
-                }
-            })
-            .collect()
-    } else {
-        Vec::new()
-    };
+    let models = crate::ollama::installed().await.unwrap_or_default();
+    let names: Vec<String> = models.iter().map(|model| model.name.clone()).collect();
 
     // Fetch available models from Ollama library API (offline-only: size > 0)
-    let available = fetch_available_models(&models);
+    let available = fetch_available_models(&names).await;
 
     (running, models, available)
 }
 
 /// Fetch popular offline models from the Ollama library API.
 /// Falls back to a curated list if the API is unreachable.
-fn fetch_available_models(installed: &[String]) -> Vec<String> {
-    let fallback = vec![
-        "llama3.2", "llama3.1", "mistral", "qwen2.5",
-        "gemma2", "phi4-mini", "deepseek-r1", "codellama",
-    ];
+async fn fetch_available_models(installed: &[String]) -> Vec<String> {
+    let fallback = || -> Vec<String> {
+        [
+            "llama3.2", "llama3.1", "mistral", "qwen2.5",
+            "gemma2", "phi4-mini", "deepseek-r1", "codellama",
+        ]
+        .into_iter()
+        .filter(|m| !installed.iter().any(|i| i.starts_with(m)))
+        .map(|s| s.to_owned())
+        .collect()
+    };
 
-    let result = std::process::Command::new("curl")
-        .args(["-sf", "--connect-timeout", "5", "https://ollama.com/api/tags"])
-        .output();
-
-    let output = match result {
-        Ok(o) if o.status.success() => o.stdout,
-        _ => {
-            return fallback
-                .into_iter()
-                .filter(|m| !installed.iter().any(|i| i.starts_with(m)))
-                .map(|s| s.to_owned())
-                .collect();
-        }
+    let Ok(json) = crate::ollama::library().await else {
+        return fallback();
     };
 
-    let parsed: Result<serde_json::Value, _> = serde_json::from_slice(&output);
-    match parsed {
-        Ok(json) => {
-            // API returns {"models": [...]} — unwrap the wrapper object
-            let mut models: Vec<String> = json
-                .get("models")
-                .and_then(|v| v.as_array())
-                .into_iter()
-                .flatten()
-                .filter(|m| {
-                    m.get("size").and_then(|s| s.as_u64()).unwrap_or(0) > 0
-                })
-                .filter_map(|m| m.get("name").and_then(|n| n.as_str()).map(|s| s.to_owned()))
-                .filter(|name| !installed.iter().any(|i| i.starts_with(name.as_str())))
-                .take(20)
-                .collect();
-            if models.is_empty() {
-                models = fallback
-                    .into_iter()
-                    .filter(|m| !installed.iter().any(|i| i.starts_with(m)))
-                    .map(|s| s.to_owned())
-                    .collect();
-            }
-            models
-        }
-        Err(_) => fallback
-            .into_iter()
-            .filter(|m| !installed.iter().any(|i| i.starts_with(m)))
-            .map(|s| s.to_owned())
-            .collect(),
+    // API returns {"models": [...]} — unwrap the wrapper object
+    let models: Vec<String> = json
+        .get("models")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|m| {
+            m.get("size").and_then(|s| s.as_u64()).unwrap_or(0) > 0
+        })
+        .filter_map(|m| m.get("name").and_then(|n| n.as_str()).map(|s| s.to_owned()))
+        .filter(|name| !installed.iter().any(|i| i.starts_with(name.as_str())))
+        .take(20)
+        .collect();
+    if models.is_empty() {
+        fallback()
+    } else {
+        models
     }
 }
 
@@ -664,19 +2223,12 @@ fn load_ai_config() -> (String, String, String, String) {
     (provider, api_key, model, base_url)
 }
 
-fn save_ai_config(provider: &str, api_key: &str, model: &str, base_url: &str) -> (bool, String) {
-    let path = ai_config_path();
-
-    // Read existing config to preserve agent section
-    let mut config: toml::Value = if path.exists() {
-        let content = std::fs::read_to_string(&path).unwrap_or_default();
-        toml::from_str(&content).unwrap_or_else(|_| toml::Value::Table(toml::map::Map::new()))
-    } else {
-        toml::Value::Table(toml::map::Map::new())
-    };
-
-    // Update provider section
-    let table = config.as_table_mut().unwrap();
+async fn save_ai_config(
+    provider: &str,
+    api_key: &str,
+    model: &str,
+    base_url: &str,
+) -> (bool, String) {
     let mut prov = toml::map::Map::new();
     prov.insert("type".to_owned(), toml::Value::String(provider.to_owned()));
     prov.insert("api_key".to_owned(), toml::Value::String(api_key.to_owned()));
@@ -684,19 +2236,33 @@ fn save_ai_config(provider: &str, api_key: &str, model: &str, base_url: &str) ->
     if !base_url.is_empty() {
         prov.insert("base_url".to_owned(), toml::Value::String(base_url.to_owned()));
     }
-    table.insert("provider".to_owned(), toml::Value::Table(prov));
 
-    // Ensure agent section exists with defaults
-    if !table.contains_key("agent") {
-        let uid = std::env::var("UID")
-            .or_else(|_| std::env::var("EUID"))
-            .unwrap_or_else(|_| "1000".to_owned());
-        let mut agent = toml::map::Map::new();
-        agent.insert("socket_path".to_owned(), toml::Value::String(format!("/run/user/{uid}/aios-agent.sock")));
-        agent.insert("audit_log".to_owned(), toml::Value::String("/var/log/aios/actions.log".to_owned()));
-        agent.insert("max_destructive_per_minute".to_owned(), toml::Value::Integer(3));
-        table.insert("agent".to_owned(), toml::Value::Table(agent));
+    if ai_config_path().exists() {
+        return save_section("provider", |table| *table = prov).await;
     }
+    // First setup: create the file, then have a running agent pick it up.
+    let saved = create_ai_config(prov);
+    if saved.0 {
+        let _ = notify_agent_reload().await;
+    }
+    saved
+}
+
+/// Write a new config file with `provider` and a default `[agent]`.
+fn create_ai_config(provider: toml::map::Map<String, toml::Value>) -> (bool, String) {
+    let path = ai_config_path();
+    let uid = std::env::var("UID")
+        .or_else(|_| std::env::var("EUID"))
+        .unwrap_or_else(|_| "1000".to_owned());
+    let mut agent = toml::map::Map::new();
+    agent.insert("socket_path".to_owned(), toml::Value::String(format!("/run/user/{uid}/aios-agent.sock")));
+    agent.insert("audit_log".to_owned(), toml::Value::String("/var/log/aios/actions.log".to_owned()));
+    agent.insert("max_destructive_per_minute".to_owned(), toml::Value::Integer(3));
+
+    let mut table = toml::map::Map::new();
+    table.insert("provider".to_owned(), toml::Value::Table(provider));
+    table.insert("agent".to_owned(), toml::Value::Table(agent));
+    let config = toml::Value::Table(table);
 
     if let Some(parent) = path.parent() {
         let _ = std::fs::create_dir_all(parent);
@@ -711,75 +2277,339 @@ fn save_ai_config(provider: &str, api_key: &str, model: &str, base_url: &str) ->
     }
 }
 
-/// Fetch locally installed Ollama models via `ollama list`.
-fn fetch_installed_ollama_models() -> Vec<String> {
-    let output = std::process::Command::new("ollama")
-        .arg("list")
-        .output();
+/// Persist `ui.language` in the agent config, keeping all other sections.
+async fn save_ui_language(language: &str) -> (bool, String) {
+    save_ui_section(|ui| {
+        ui.insert("language".to_owned(), toml::Value::String(language.to_owned()));
+    })
+    .await
+}
 
-    match output {
-        Ok(o) if o.status.success() => {
-            let stdout = String::from_utf8_lossy(&o.stdout);
-            stdout
-                .lines()
-                .skip(1) // skip header
-                .filter_map(|line| {
-                    let name = line.split_whitespace().next()?;
-                    if name.is_empty() { None } else { Some(name.to_owned()) }
-                })
-                .collect()
+/// Persist the appearance fields of `config` into `[ui]`.
+async fn save_ui_appearance(config: &UiConfig) -> (bool, String) {
+    save_ui_section(|ui| {
+        let scheme = match config.color_scheme {
+            ColorScheme::Dark => "dark",
+            ColorScheme::Light => "light",
+        };
+        ui.insert("color_scheme".to_owned(), toml::Value::String(scheme.to_owned()));
+        match &config.accent {
+            Some(accent) => {
+                ui.insert("accent".to_owned(), toml::Value::String(accent.clone()));
+            }
+            None => {
+                ui.remove("accent");
+            }
+        }
+        ui.insert(
+            "font_scale".to_owned(),
+            toml::Value::Float(f64::from(config.font_scale)),
+        );
+        let density = match config.density {
+            Density::Comfortable => "comfortable",
+            Density::Compact => "compact",
+        };
+        ui.insert("density".to_owned(), toml::Value::String(density.to_owned()));
+        let dock_position = match config.dock_position {
+            DockPosition::Top => "top",
+            DockPosition::Bottom => "bottom",
+        };
+        ui.insert(
+            "dock_position".to_owned(),
+            toml::Value::String(dock_position.to_owned()),
+        );
+        match &config.wallpaper {
+            Some(wallpaper) => {
+                ui.insert("wallpaper".to_owned(), toml::Value::String(wallpaper.clone()));
+            }
+            None => {
+                ui.remove("wallpaper");
+            }
         }
-        _ => Vec::new(),
+    })
+    .await
+}
+
+/// Persist `ui.pinned_apps`.
+async fn save_ui_pinned_apps(pinned: &[String]) -> (bool, String) {
+    save_ui_section(|ui| {
+        let ids = pinned.iter().cloned().map(toml::Value::String).collect();
+        ui.insert("pinned_apps".to_owned(), toml::Value::Array(ids));
+    })
+    .await
+}
+
+/// Persist `ui.gpu_meter`.
+async fn save_ui_gpu_meter(enabled: bool) -> (bool, String) {
+    save_ui_section(|ui| {
+        ui.insert("gpu_meter".to_owned(), toml::Value::Boolean(enabled));
+    })
+    .await
+}
+
+/// Replace the `[tools]` table with `policies`.
+async fn save_tool_policies(policies: &BTreeMap<String, ToolPolicy>) -> (bool, String) {
+    let tools = match toml::Value::try_from(policies) {
+        Ok(toml::Value::Table(tools)) => tools,
+        Ok(_) => return (false, "Tool policies are not a TOML table".to_owned()),
+        Err(e) => return (false, format!("Serialize error: {e}")),
+    };
+    save_section("tools", |table| *table = tools).await
+}
+
+/// Replace the `[mcp_servers]` table with `servers`.
+async fn save_mcp_servers(servers: &BTreeMap<String, McpServerConfig>) -> (bool, String) {
+    let table = match toml::Value::try_from(servers) {
+        Ok(toml::Value::Table(table)) => table,
+        Ok(_) => return (false, "MCP servers are not a TOML table".to_owned()),
+        Err(e) => return (false, format!("Serialize error: {e}")),
+    };
+    save_section("mcp_servers", |mcp_servers| *mcp_servers = table).await
+}
+
+/// Why `server` cannot be added as `name`, if anything. Names end up in
+/// tool names, which LLM APIs restrict to letters, digits, `_` and `-`.
+fn check_new_mcp_server(
+    name: &str,
+    server: &McpServerConfig,
+    servers: &BTreeMap<String, McpServerConfig>,
+) -> Option<String> {
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_name {
+        return Some(t!("settings-mcp-invalid-name"));
+    }
+    if servers.contains_key(name) {
+        return Some(t!("settings-mcp-duplicate-name"));
     }
+    let missing = match server.transport {
+        McpTransport::Stdio => server.command.is_empty(),
+        McpTransport::Sse => server.url.is_none(),
+    };
+    missing.then(|| t!("settings-mcp-missing-target"))
 }
 
-/// Connect to the agent via IPC and send a ReloadConfig command.
-async fn notify_agent_reload() -> (bool, String) {
-    let uid = std::env::var("UID")
-        .or_else(|_| std::env::var("EUID"))
-        .unwrap_or_else(|_| "1000".to_owned());
-    let socket_path = format!("/run/user/{uid}/aios-agent.sock");
+/// Replace the `[agent]` table with `config`.
+async fn save_agent_config(config: &AgentConfig) -> (bool, String) {
+    let table = match toml::Value::try_from(config) {
+        Ok(toml::Value::Table(table)) => table,
+        Ok(_) => return (false, "Agent settings are not a TOML table".to_owned()),
+        Err(e) => return (false, format!("Serialize error: {e}")),
+    };
+    save_section("agent", |agent| *agent = table).await
+}
 
-    let mut conn = match IpcClient::connect(&socket_path).await {
-        Ok(c) => c,
-        Err(e) => return (false, format!("Cannot connect to agent: {e}")),
+/// Replace the `[power]` table with `config`, then apply it to the session.
+async fn save_power_config(config: &PowerConfig) -> (bool, String) {
+    let table = match toml::Value::try_from(config) {
+        Ok(toml::Value::Table(table)) => table,
+        Ok(_) => return (false, "Power settings are not a TOML table".to_owned()),
+        Err(e) => return (false, format!("Serialize error: {e}")),
     };
+    let (saved, msg) = save_section("power", |power| *power = table).await;
+    if !saved {
+        return (false, msg);
+    }
+    let applied = crate::power::apply(config).await;
+    (applied.success, applied.output)
+}
 
-    // Register as Settings client
-    let register = IpcMessage {
-        id: Uuid::new_v4(),
-        payload: IpcPayload::Register {
-            client_type: ClientType::Settings,
-        },
+/// Replace the `[input]` table with `config`, then reload sway with it.
+async fn save_input_config(config: &InputConfig) -> (bool, String) {
+    let table = match toml::Value::try_from(config) {
+        Ok(toml::Value::Table(table)) => table,
+        Ok(_) => return (false, "Input settings are not a TOML table".to_owned()),
+        Err(e) => return (false, format!("Serialize error: {e}")),
     };
-    if let Err(e) = conn.send(&register).await {
-        return (false, format!("Failed to register: {e}"));
+    let (saved, msg) = save_section("input", |input| *input = table).await;
+    if !saved {
+        return (false, msg);
     }
+    let applied = crate::input::apply(config).await;
+    (applied.success, applied.output)
+}
 
-    // Wait for RegisterAck
-    match conn.recv().await {
-        Ok(msg) => match msg.payload {
-            IpcPayload::RegisterAck { success: true } => {}
-            _ => return (false, "Unexpected registration response".to_owned()),
-        },
-        Err(e) => return (false, format!("Registration failed: {e}")),
+/// Edit the `[ui]` table of the config file, preserving everything else.
+async fn save_ui_section(
+    edit: impl FnOnce(&mut toml::map::Map<String, toml::Value>),
+) -> (bool, String) {
+    save_section("ui", edit).await
+}
+
+/// Edit the `[section]` table through the running agent, which validates
+/// the whole config, writes it and applies it live. Without a running
+/// agent the file is edited directly and read on the agent's next start.
+async fn save_section(
+    section: &str,
+    edit: impl FnOnce(&mut toml::map::Map<String, toml::Value>),
+) -> (bool, String) {
+    let socket = agent_ipc::socket_path();
+    let get = IpcPayload::GetConfig {
+        section: section.to_owned(),
+    };
+    let current = match agent_ipc::request_if_running(&socket, get).await {
+        None => return save_section_to_file(section, edit),
+        Some(Ok(IpcPayload::ConfigSection { value, .. })) => value,
+        Some(Ok(IpcPayload::Error { message, .. })) => return (false, message),
+        Some(Ok(_)) => return (false, "Unexpected response".to_owned()),
+        Some(Err(e)) => return (false, e),
+    };
+
+    let mut table = match toml::Value::try_from(current) {
+        Ok(toml::Value::Table(table)) => table,
+        Ok(_) => toml::map::Map::new(),
+        Err(e) => return (false, format!("Parse error: {e}")),
+    };
+    edit(&mut table);
+    let value = match serde_json::to_value(&table) {
+        Ok(value) => value,
+        Err(e) => return (false, format!("Serialize error: {e}")),
+    };
+
+    let set = IpcPayload::SetConfig {
+        section: section.to_owned(),
+        value,
+    };
+    match agent_ipc::request(&socket, set).await {
+        Ok(IpcPayload::ConfigReloaded { success, message }) => (success, message),
+        Ok(_) => (false, "Unexpected response".to_owned()),
+        Err(e) => (false, e),
     }
+}
 
-    // Send ReloadConfig
-    let reload = IpcMessage {
-        id: Uuid::new_v4(),
-        payload: IpcPayload::ReloadConfig,
+/// Edit the `[section]` table of the config file, preserving everything
+/// else.
+fn save_section_to_file(
+    section: &str,
+    edit: impl FnOnce(&mut toml::map::Map<String, toml::Value>),
+) -> (bool, String) {
+    let path = ai_config_path();
+
+    // A config holding only this section would not parse in the agent and
+    // would skip the chat's first-boot setup, so never create one from here.
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return (false, t!("settings-ui-no-config"));
+    };
+    let mut config: toml::Value = match toml::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => return (false, format!("Parse error: {e}")),
     };
-    if let Err(e) = conn.send(&reload).await {
-        return (false, format!("Failed to send reload: {e}"));
+
+    let Some(table) = config.as_table_mut() else {
+        return (false, "Config is not a TOML table".to_owned());
+    };
+    let entry = table
+        .entry(section)
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+    if let Some(entry) = entry.as_table_mut() {
+        edit(entry);
     }
 
-    // Wait for ConfigReloaded response
-    match conn.recv().await {
-        Ok(msg) => match msg.payload {
-            IpcPayload::ConfigReloaded { success, message } => (success, message),
-            _ => (false, "Unexpected response".to_owned()),
+    match toml::to_string_pretty(&config) {
+        Ok(content) => match std::fs::write(&path, &content) {
+            Ok(()) => (true, "Saved!".to_owned()),
+            Err(e) => (false, format!("Write error: {e}")),
         },
-        Err(e) => (false, format!("Reload response failed: {e}")),
+        Err(e) => (false, format!("Serialize error: {e}")),
+    }
+}
+
+/// RAM and VRAM of this machine; probing runs `nvidia-smi`.
+async fn probe_memory() -> Option<Memory> {
+    tokio::task::spawn_blocking(Memory::probe)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Models `provider` offers, from the agent's catalog when it runs with
+/// that provider; otherwise only local Ollama models can be listed.
+async fn fetch_models(provider: String) -> Vec<String> {
+    let reply =
+        agent_ipc::request_if_running(&agent_ipc::socket_path(), IpcPayload::ListModels).await;
+    if let Some(Ok(IpcPayload::Models {
+        provider: active,
+        models,
+        ..
+    })) = reply
+        && active == provider.replace('_', "")
+    {
+        return models.into_iter().map(|model| model.id).collect();
+    }
+    if provider == "ollama" {
+        fetch_installed_ollama_models().await
+    } else {
+        Vec::new()
+    }
+}
+
+/// Names of the locally installed Ollama models.
+async fn fetch_installed_ollama_models() -> Vec<String> {
+    crate::ollama::installed()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|model| model.name)
+        .collect()
+}
+
+/// Ask the agent to reload its configuration from disk.
+async fn notify_agent_reload() -> (bool, String) {
+    reload_agent(&agent_ipc::socket_path()).await
+}
+
+/// Ask the agent listening on `socket_path` to reload its configuration.
+async fn reload_agent(socket_path: &str) -> (bool, String) {
+    match agent_ipc::request(socket_path, IpcPayload::ReloadConfig).await {
+        Ok(IpcPayload::ConfigReloaded { success, message }) => (success, message),
+        Ok(_) => (false, "Unexpected response".to_owned()),
+        Err(e) => (false, e),
+    }
+}
+
+/// Ask the agent to restart itself; it rereads the whole config, including
+/// the socket and audit log paths.
+async fn restart_agent(socket_path: &str) -> (bool, String) {
+    match agent_ipc::request(socket_path, IpcPayload::RestartAgent).await {
+        Ok(IpcPayload::CommandResult { success, message }) => (success, message),
+        Ok(_) => (false, "Unexpected response".to_owned()),
+        Err(e) => (false, e),
+    }
+}
+
+/// Check the hash chain of the audit log at `path`, offline.
+async fn verify_audit_log(path: String) -> (bool, String) {
+    let log = match tokio::fs::read_to_string(&path).await {
+        Ok(log) => log,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return (true, t!("settings-agent-audit-empty"));
+        }
+        Err(e) => return (false, format!("{path}: {e}")),
+    };
+    match aios_common::audit::verify(&log) {
+        Ok(verification) if verification.unchained > 0 => (
+            true,
+            t!(
+                "settings-agent-audit-intact-partly",
+                count = verification.chained,
+                unchained = verification.unchained
+            ),
+        ),
+        Ok(verification) => (
+            true,
+            t!("settings-agent-audit-intact", count = verification.chained),
+        ),
+        Err(e) => {
+            let line = e.line();
+            let message = match e {
+                AuditVerifyError::Malformed(_) => t!("settings-agent-audit-malformed", line = line),
+                AuditVerifyError::Unchained(_) => t!("settings-agent-audit-unchained", line = line),
+                AuditVerifyError::Modified(_) => t!("settings-agent-audit-modified", line = line),
+                AuditVerifyError::Broken(_) => t!("settings-agent-audit-broken", line = line),
+            };
+            (false, message)
+        }
     }
 }
@@ -1,7 +1,23 @@
 //! System command execution for settings tabs.
 //! All commands run directly without AI dependency.
+//!
+//! Commands run as async processes, so the UI thread never waits on them.
+//! Each has a timeout, and its process is killed when the future is dropped
+//! (an aborted task or an expired timeout), so nothing outlives its request.
 
-use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::process::Command;
+
+/// Time a command gets unless it needs longer.
+const CMD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Downloading package lists; slow mirrors take a while.
+const APT_UPDATE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Installing updates, which can mean hundreds of megabytes.
+const APT_UPGRADE_TIMEOUT: Duration = Duration::from_secs(3600);
 
 /// Result of a system command execution.
 #[derive(Debug, Clone)]
@@ -10,9 +26,18 @@ pub struct CmdResult {
     pub output: String,
 }
 
-fn run_cmd(program: &str, args: &[&str]) -> CmdResult {
-    match Command::new(program).args(args).output() {
-        Ok(output) => {
+async fn run_cmd(program: &str, args: &[&str]) -> CmdResult {
+    run_cmd_timeout(program, args, CMD_TIMEOUT).await
+}
+
+async fn run_cmd_timeout(program: &str, args: &[&str], timeout: Duration) -> CmdResult {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(timeout, output).await {
+        Ok(Ok(output)) => {
             let stdout = String::from_utf8_lossy(&output.stdout).to_string();
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
             CmdResult {
@@ -20,68 +45,123 @@ fn run_cmd(program: &str, args: &[&str]) -> CmdResult {
                 output: if stdout.is_empty() { stderr } else { stdout },
             }
         }
-        Err(e) => CmdResult {
+        Ok(Err(e)) => CmdResult {
             success: false,
             output: format!("Failed to run {program}: {e}"),
         },
+        Err(_) => CmdResult {
+            success: false,
+            output: format!("{program} timed out after {}s", timeout.as_secs()),
+        },
     }
 }
 
-// -- Network commands (nmcli) --
+// -- Display commands (swaymsg) --
+
+pub async fn display_list() -> CmdResult {
+    run_cmd("swaymsg", &["-t", "get_outputs", "-r"]).await
+}
+
+pub async fn display_set_mode(output_name: &str, width: u32, height: u32, hz: f32) -> CmdResult {
+    let mode = format!("{width}x{height}@{hz:.3}Hz");
+    run_cmd("swaymsg", &["output", output_name, "mode", &mode]).await
+}
 
-pub fn wifi_scan() -> CmdResult {
-    run_cmd("nmcli", &["-t", "-f", "SSID,SIGNAL,SECURITY,IN-USE", "dev", "wifi", "list", "--rescan", "yes"])
+// -- Audio commands (wpctl) --
+
+pub async fn audio_status() -> CmdResult {
+    run_cmd("wpctl", &["status"]).await
 }
 
-pub fn wifi_connect(ssid: &str, password: &str) -> CmdResult {
-    if password.is_empty() {
-        run_cmd("nmcli", &["dev", "wifi", "connect", ssid])
-    } else {
-        run_cmd("nmcli", &["dev", "wifi", "connect", ssid, "password", password])
-    }
+pub async fn audio_set_default(id: u32) -> CmdResult {
+    run_cmd("wpctl", &["set-default", &id.to_string()]).await
 }
 
-pub fn wifi_disconnect() -> CmdResult {
-    run_cmd("nmcli", &["dev", "disconnect", "wlan0"])
+pub async fn audio_set_volume(id: u32, percent: u8) -> CmdResult {
+    run_cmd("wpctl", &["set-volume", &id.to_string(), &format!("{percent}%")]).await
 }
 
-pub fn network_status() -> CmdResult {
-    run_cmd("nmcli", &["-t", "-f", "DEVICE,TYPE,STATE,CONNECTION", "dev", "status"])
+pub async fn audio_set_mute(id: u32, muted: bool) -> CmdResult {
+    run_cmd("wpctl", &["set-mute", &id.to_string(), if muted { "1" } else { "0" }]).await
 }
 
-// -- Display commands (swaymsg) --
+// -- Power commands (sway, swayidle, powerprofilesctl) --
 
-pub fn display_list() -> CmdResult {
-    run_cmd("swaymsg", &["-t", "get_outputs", "-r"])
+pub async fn sway_command(command: &str) -> CmdResult {
+    run_cmd("swaymsg", &[command]).await
 }
 
-pub fn display_set_mode(output_name: &str, width: u32, height: u32, hz: f32) -> CmdResult {
-    let mode = format!("{width}x{height}@{hz:.3}Hz");
-    run_cmd("swaymsg", &["output", output_name, "mode", &mode])
+pub async fn swayidle_restart() -> CmdResult {
+    // Not running yet on the first change, so pkill may fail.
+    let _ = run_cmd("pkill", &["-x", "swayidle"]).await;
+    // Started through sway so it outlives this window.
+    run_cmd("swaymsg", &["exec", "swayidle -w"]).await
+}
+
+pub async fn power_profiles_list() -> CmdResult {
+    run_cmd("powerprofilesctl", &["list"]).await
+}
+
+pub async fn power_profile_set(profile: &str) -> CmdResult {
+    run_cmd("powerprofilesctl", &["set", profile]).await
 }
 
 // -- Ollama commands --
 
-pub fn ollama_status() -> CmdResult {
-    run_cmd("systemctl", &["is-active", "ollama"])
+pub async fn ollama_status() -> CmdResult {
+    run_cmd("systemctl", &["is-active", "ollama"]).await
+}
+
+pub async fn ollama_start() -> CmdResult {
+    run_cmd("systemctl", &["start", "ollama"]).await
+}
+
+pub async fn ollama_stop() -> CmdResult {
+    run_cmd("systemctl", &["stop", "ollama"]).await
+}
+
+pub async fn ollama_remove(model: &str) -> CmdResult {
+    run_cmd("ollama", &["rm", model]).await
 }
 
-pub fn ollama_start() -> CmdResult {
-    run_cmd("systemctl", &["start", "ollama"])
+// -- Service commands (systemd user units) --
+
+/// `Id`, `ActiveState` and `UnitFileState` of each unit.
+pub async fn user_units_show(units: &[&str]) -> CmdResult {
+    let mut args = vec!["--user", "show", "--property=Id,ActiveState,UnitFileState", "--"];
+    args.extend_from_slice(units);
+    run_cmd("systemctl", &args).await
 }
 
-pub fn ollama_stop() -> CmdResult {
-    run_cmd("systemctl", &["stop", "ollama"])
+pub async fn user_unit_enable(unit: &str) -> CmdResult {
+    run_cmd("systemctl", &["--user", "enable", unit]).await
 }
 
-pub fn ollama_list_models() -> CmdResult {
-    run_cmd("ollama", &["list"])
+pub async fn user_unit_disable(unit: &str) -> CmdResult {
+    run_cmd("systemctl", &["--user", "disable", unit]).await
+}
+
+pub async fn user_unit_start(unit: &str) -> CmdResult {
+    run_cmd("systemctl", &["--user", "start", unit]).await
+}
+
+pub async fn user_unit_stop(unit: &str) -> CmdResult {
+    run_cmd("systemctl", &["--user", "stop", unit]).await
+}
+
+// -- Update commands (apt through pkexec) --
+
+/// Download fresh package lists; asks for the password.
+pub async fn apt_update() -> CmdResult {
+    run_cmd_timeout("pkexec", &["apt-get", "update"], APT_UPDATE_TIMEOUT).await
 }
 
-pub fn ollama_pull(model: &str) -> CmdResult {
-    run_cmd("ollama", &["pull", model])
+/// What an upgrade would install, without installing anything.
+pub async fn apt_upgrade_simulate() -> CmdResult {
+    run_cmd("apt-get", &["--simulate", "upgrade"]).await
 }
 
-pub fn ollama_remove(model: &str) -> CmdResult {
-    run_cmd("ollama", &["rm", model])
+/// Install all pending updates; asks for the password.
+pub async fn apt_upgrade() -> CmdResult {
+    run_cmd_timeout("pkexec", &["env", "DEBIAN_FRONTEND=noninteractive", "apt-get", "-y", "upgrade"], APT_UPGRADE_TIMEOUT).await
 }
@@ -0,0 +1,34 @@
+//! Config files written for session programs that do not read
+//! `agent.toml` (sway, swayidle). They are regenerated on every change, so
+//! hand edits do not last.
+
+use std::path::{Path, PathBuf};
+
+/// First line of every generated file.
+const HEADER: &str = "# Generated by AIOS Settings; edits here are overwritten.\n";
+
+/// `~/.config/<relative>`.
+pub fn config_file(relative: &str) -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from(".config"))
+        .join(relative)
+}
+
+/// A snippet in `~/.config/sway/config.d`, which the sway config includes
+/// at login.
+pub fn sway_snippet(name: &str) -> PathBuf {
+    config_file("sway/config.d").join(name)
+}
+
+/// Write `lines` below the header, creating missing directories.
+pub fn write(path: &Path, lines: &[String]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut content = HEADER.to_owned();
+    for line in lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    std::fs::write(path, content)
+}
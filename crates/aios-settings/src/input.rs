@@ -0,0 +1,38 @@
+//! Keyboard and touchpad settings for sway.
+//!
+//! [`InputConfig`] is written as a sway snippet whose `input` blocks
+//! override the defaults of the main sway config; sway is reloaded to pick
+//! it up.
+
+use aios_common::InputConfig;
+
+use crate::commands::{self, CmdResult};
+use crate::generated;
+
+/// sway config lines for `input`.
+fn sway_config(input: &InputConfig) -> Vec<String> {
+    let on_off = |enabled: bool| if enabled { "enabled" } else { "disabled" };
+    vec![
+        "input type:keyboard {".to_owned(),
+        format!("    xkb_layout {}", input.layouts.join(",")),
+        format!("    xkb_options {}", input.layout_switch.xkb_option()),
+        format!("    repeat_delay {}", input.repeat_delay),
+        format!("    repeat_rate {}", input.repeat_rate),
+        "}".to_owned(),
+        "input type:touchpad {".to_owned(),
+        format!("    tap {}", on_off(input.tap_to_click)),
+        format!("    natural_scroll {}", on_off(input.natural_scroll)),
+        "}".to_owned(),
+    ]
+}
+
+/// Write the input snippet and reload sway.
+pub async fn apply(input: &InputConfig) -> CmdResult {
+    if let Err(e) = generated::write(&generated::sway_snippet("aios-input"), &sway_config(input)) {
+        return CmdResult {
+            success: false,
+            output: format!("Write error: {e}"),
+        };
+    }
+    commands::sway_command("reload").await
+}
@@ -1,7 +1,17 @@
+mod about;
+mod activity;
+mod agent_ipc;
 mod app;
 mod commands;
+mod generated;
+mod input;
+mod ollama;
+mod power;
+mod session_ipc;
+mod startup;
 mod theme;
 mod views;
+mod wallpaper;
 
 use app::SettingsApp;
 
@@ -14,11 +24,15 @@ fn main() -> iced::Result {
         .init();
 
     tracing::info!("aios-settings starting...");
+    aios_common::i18n::init_from_config();
+    aios_theme::init_from_config();
 
     iced::application(SettingsApp::new, SettingsApp::update, SettingsApp::view)
+        .subscription(SettingsApp::subscription)
         .title("AIOS Settings")
-        .theme(iced::Theme::TokyoNight)
+        .theme(|_state: &SettingsApp| aios_theme::iced_theme())
         .window_size((700.0, 500.0))
+        .scale_factor(|_state: &SettingsApp| aios_theme::scale_factor())
         .centered()
         .antialiasing(true)
         .run()
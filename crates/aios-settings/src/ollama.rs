@@ -0,0 +1,195 @@
+//! The local Ollama server's HTTP API: installed models with their details,
+//! and model downloads that report progress as they go.
+
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use futures::SinkExt;
+use serde_json::Value;
+
+/// Where `ollama serve` listens.
+const API: &str = "http://localhost:11434/api";
+
+/// Time the local server gets to answer a listing.
+const LIST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time the Ollama library API gets to answer.
+const LIBRARY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An installed model.
+#[derive(Debug, Clone)]
+pub struct LocalModel {
+    pub name: String,
+    /// Bytes on disk.
+    pub size: u64,
+    /// e.g. "8.0B".
+    pub parameter_size: String,
+    /// e.g. "Q4_K_M".
+    pub quantization: String,
+    /// Date the model was last pulled or changed, `YYYY-MM-DD`.
+    pub modified: String,
+    /// Loaded in memory right now.
+    pub loaded: bool,
+}
+
+/// Events of a [`pull`] stream.
+#[derive(Debug, Clone)]
+pub enum PullEvent {
+    /// A step without progress ("pulling manifest", "verifying sha256
+    /// digest", ...).
+    Status(String),
+    /// Download progress of one layer.
+    Layer {
+        digest: String,
+        completed: u64,
+        total: u64,
+    },
+    /// The model is installed.
+    Done,
+    Failed(String),
+}
+
+fn client(timeout: Option<Duration>) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder().connect_timeout(Duration::from_secs(5));
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Result<Value, String> {
+    client
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Installed models, largest first.
+pub async fn installed() -> Result<Vec<LocalModel>, String> {
+    let client = client(Some(LIST_TIMEOUT))?;
+    let tags = get_json(&client, &format!("{API}/tags")).await?;
+    // Only tells which models are loaded; not worth failing the list over.
+    let running = get_json(&client, &format!("{API}/ps"))
+        .await
+        .unwrap_or_default();
+    let loaded: Vec<&str> = running["models"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|model| model["name"].as_str())
+        .collect();
+
+    let mut models: Vec<LocalModel> = tags["models"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            let name = model["name"].as_str()?.to_owned();
+            let details = &model["details"];
+            Some(LocalModel {
+                loaded: loaded.contains(&name.as_str()),
+                size: model["size"].as_u64().unwrap_or(0),
+                parameter_size: details["parameter_size"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_owned(),
+                quantization: details["quantization_level"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_owned(),
+                modified: model["modified_at"]
+                    .as_str()
+                    .and_then(|date| date.get(..10))
+                    .unwrap_or_default()
+                    .to_owned(),
+                name,
+            })
+        })
+        .collect();
+    models.sort_by(|a, b| b.size.cmp(&a.size));
+    Ok(models)
+}
+
+/// Model list of the Ollama library (`{"models": [...]}`).
+pub async fn library() -> Result<Value, String> {
+    get_json(
+        &client(Some(LIBRARY_TIMEOUT))?,
+        "https://ollama.com/api/tags",
+    )
+    .await
+}
+
+/// Download `model`, reporting progress.
+///
+/// Designed for `Task::run(pull(model), ..).abortable()`: aborting the task
+/// drops the request, and the server stops the download.
+pub fn pull(model: String) -> impl futures::Stream<Item = PullEvent> {
+    iced::stream::channel(16, async move |mut output: mpsc::Sender<PullEvent>| {
+        let event = match pull_session(&model, &mut output).await {
+            Ok(()) => PullEvent::Done,
+            Err(e) => PullEvent::Failed(e),
+        };
+        let _ = output.send(event).await;
+    })
+}
+
+/// Stream the `/api/pull` response: one JSON object per line.
+async fn pull_session(model: &str, output: &mut mpsc::Sender<PullEvent>) -> Result<(), String> {
+    // No overall timeout: large models take a while on slow links.
+    let mut response = client(None)?
+        .post(format!("{API}/pull"))
+        .json(&serde_json::json!({ "model": model, "stream": true }))
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| e.to_string())?;
+
+    let mut pending = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        pending.extend_from_slice(&chunk);
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let Ok(message) = serde_json::from_slice::<Value>(&line) else {
+                continue;
+            };
+            if let Some(error) = message["error"].as_str() {
+                return Err(error.to_owned());
+            }
+            let status = message["status"].as_str().unwrap_or_default();
+            if status == "success" {
+                return Ok(());
+            }
+            let event = match (message["digest"].as_str(), message["total"].as_u64()) {
+                (Some(digest), Some(total)) => PullEvent::Layer {
+                    digest: digest.to_owned(),
+                    completed: message["completed"].as_u64().unwrap_or(0),
+                    total,
+                },
+                _ => PullEvent::Status(status.to_owned()),
+            };
+            let _ = output.send(event).await;
+        }
+    }
+    Err("Ollama closed the connection before the download finished".to_owned())
+}
+
+/// `bytes` for people: "4.7 GB", "310 MB".
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit >= 3 {
+        format!("{size:.1} {}", UNITS[unit])
+    } else {
+        format!("{size:.0} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,84 @@
+//! Power settings for the sway session.
+//!
+//! [`PowerConfig`] is turned into `~/.config/swayidle/config` (read by the
+//! `swayidle -w` that sway starts at login) and a sway snippet with the lid
+//! binding, so the settings survive a re-login. [`apply`] also pushes them
+//! into the running session.
+
+use aios_common::{LidAction, PowerConfig};
+
+use crate::commands::{self, CmdResult};
+use crate::generated;
+
+const SCREENS_OFF: &str = r#"swaymsg "output * power off""#;
+const SCREENS_ON: &str = r#"swaymsg "output * power on""#;
+
+/// swayidle config: a `timeout` line per enabled timeout.
+fn swayidle_config(power: &PowerConfig) -> Vec<String> {
+    let mut config = Vec::new();
+    if power.screen_off_minutes > 0 {
+        let seconds = power.screen_off_minutes * 60;
+        config.push(format!(
+            "timeout {seconds} '{SCREENS_OFF}' resume '{SCREENS_ON}'"
+        ));
+    }
+    if power.suspend_minutes > 0 {
+        let seconds = power.suspend_minutes * 60;
+        config.push(format!("timeout {seconds} 'systemctl suspend'"));
+    }
+    // Screens turned off before suspending stay off after waking otherwise.
+    config.push(format!("after-resume '{SCREENS_ON}'"));
+    config
+}
+
+/// sway commands binding the lid switch; none leaves the lid unhandled
+/// (logind ignores it, see the ISO's logind.conf.d).
+fn lid_bindings(action: LidAction) -> Vec<String> {
+    match action {
+        LidAction::Suspend => {
+            vec!["bindswitch --reload --locked lid:on exec systemctl suspend".to_owned()]
+        }
+        LidAction::ScreenOff => vec![
+            "bindswitch --reload --locked lid:on output * power off".to_owned(),
+            "bindswitch --reload --locked lid:off output * power on".to_owned(),
+        ],
+        LidAction::Nothing => Vec::new(),
+    }
+}
+
+/// Write the swayidle config and lid snippet, then restart swayidle and
+/// rebind the lid in the running sway.
+pub async fn apply(power: &PowerConfig) -> CmdResult {
+    let bindings = lid_bindings(power.lid_close);
+    let written = generated::write(
+        &generated::config_file("swayidle/config"),
+        &swayidle_config(power),
+    )
+    .and_then(|()| generated::write(&generated::sway_snippet("aios-power"), &bindings));
+    if let Err(e) = written {
+        return CmdResult {
+            success: false,
+            output: format!("Write error: {e}"),
+        };
+    }
+
+    let idle = commands::swayidle_restart().await;
+    if !idle.success {
+        return idle;
+    }
+
+    // Sway only unbinds with the flags the switch was bound with. Fails for
+    // switches that are not bound, which is fine.
+    let _ =
+        commands::sway_command("unbindswitch --locked lid:on; unbindswitch --locked lid:off").await;
+    for binding in &bindings {
+        let result = commands::sway_command(binding).await;
+        if !result.success {
+            return result;
+        }
+    }
+    CmdResult {
+        success: true,
+        output: String::new(),
+    }
+}
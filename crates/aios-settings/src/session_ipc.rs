@@ -0,0 +1,40 @@
+//! Requests to `aios-session`, which runs the agent, dock and confirmation
+//! dialog. Its socket takes requests without registering first.
+
+use aios_common::{ComponentStatus, IpcClient, IpcMessage, IpcPayload, SessionConfig};
+use uuid::Uuid;
+
+/// Status of the session's components, in start order.
+pub async fn components() -> Result<Vec<ComponentStatus>, String> {
+    match request(IpcPayload::SessionStatusRequest).await? {
+        IpcPayload::SessionStatus { components } => Ok(components),
+        other => Err(format!("Unexpected response: {other:?}")),
+    }
+}
+
+/// Start or stop the component `name`; the message says what happened.
+pub async fn set_running(name: String, running: bool) -> (bool, String) {
+    match request(IpcPayload::SetComponentRunning { name, running }).await {
+        Ok(IpcPayload::CommandResult { success, message }) => (success, message),
+        Ok(other) => (false, format!("Unexpected response: {other:?}")),
+        Err(e) => (false, e),
+    }
+}
+
+async fn request(payload: IpcPayload) -> Result<IpcPayload, String> {
+    let socket_path = SessionConfig::load().socket_path;
+    let mut conn = IpcClient::connect(&socket_path)
+        .await
+        .map_err(|e| format!("Cannot connect to aios-session: {e}"))?;
+    let msg = IpcMessage {
+        id: Uuid::new_v4(),
+        payload,
+    };
+    conn.send(&msg)
+        .await
+        .map_err(|e| format!("Failed to send request: {e}"))?;
+    conn.recv()
+        .await
+        .map(|reply| reply.payload)
+        .map_err(|e| format!("No response from aios-session: {e}"))
+}
@@ -0,0 +1,254 @@
+//! What starts at login: the aios-* systemd user services, which sway
+//! starts through `aios.target`, and XDG autostart entries, which it starts
+//! through `xdg-desktop-autostart.target`. The agent, dock and confirmation
+//! dialog are components of `aios-session`, see [`crate::session_ipc`].
+//!
+//! Services are turned on and off with `systemctl --user enable/disable`.
+//! Autostart entries are turned off by writing a copy with `Hidden=true` to
+//! `~/.config/autostart`, which shadows a system entry of the same name.
+
+use std::path::PathBuf;
+
+use crate::commands::{self, CmdResult};
+
+/// The user services, in the order they start.
+pub const SERVICES: [&str; 2] = ["aios-session", "aios-chat"];
+
+/// A systemd user service.
+#[derive(Debug, Clone)]
+pub struct Service {
+    /// Unit name without `.service`.
+    pub name: String,
+    /// systemd `ActiveState`: `active`, `inactive`, `failed`, ...
+    pub state: String,
+    /// Starts at login.
+    pub enabled: bool,
+}
+
+impl Service {
+    pub fn running(&self) -> bool {
+        matches!(self.state.as_str(), "active" | "activating" | "reloading")
+    }
+}
+
+/// Status of all [`SERVICES`].
+pub async fn services() -> Result<Vec<Service>, String> {
+    let units: Vec<String> = SERVICES.iter().map(|name| unit(name)).collect();
+    let units: Vec<&str> = units.iter().map(String::as_str).collect();
+    let result = commands::user_units_show(&units).await;
+    if !result.success {
+        return Err(result.output);
+    }
+    Ok(parse_show(&result.output))
+}
+
+/// `systemctl show` prints a block of `Key=value` lines per unit, separated
+/// by blank lines.
+fn parse_show(output: &str) -> Vec<Service> {
+    output
+        .split("\n\n")
+        .filter_map(|block| {
+            let mut name = None;
+            let mut state = String::new();
+            let mut enabled = false;
+            for line in block.lines() {
+                match line.split_once('=') {
+                    Some(("Id", id)) => name = id.strip_suffix(".service").map(str::to_owned),
+                    Some(("ActiveState", value)) => state = value.to_owned(),
+                    Some(("UnitFileState", value)) => enabled = value == "enabled",
+                    _ => {}
+                }
+            }
+            Some(Service {
+                name: name?,
+                state,
+                enabled,
+            })
+        })
+        .collect()
+}
+
+pub async fn set_enabled(name: &str, enabled: bool) -> CmdResult {
+    if enabled {
+        commands::user_unit_enable(&unit(name)).await
+    } else {
+        commands::user_unit_disable(&unit(name)).await
+    }
+}
+
+pub async fn set_running(name: &str, running: bool) -> CmdResult {
+    if running {
+        commands::user_unit_start(&unit(name)).await
+    } else {
+        commands::user_unit_stop(&unit(name)).await
+    }
+}
+
+fn unit(name: &str) -> String {
+    format!("{name}.service")
+}
+
+/// An XDG autostart entry.
+#[derive(Debug, Clone)]
+pub struct AutostartEntry {
+    /// File name without `.desktop`.
+    pub id: String,
+    pub name: String,
+    pub exec: String,
+    pub enabled: bool,
+    /// Exists only in `~/.config/autostart`, so removing it is possible
+    /// (a system entry can only be turned off).
+    pub user_only: bool,
+}
+
+/// `~/.config/autostart`.
+fn user_dir() -> PathBuf {
+    crate::generated::config_file("autostart")
+}
+
+/// `autostart` directories of `$XDG_CONFIG_DIRS`.
+fn system_dirs() -> Vec<PathBuf> {
+    std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_owned())
+        .split(':')
+        .map(|dir| PathBuf::from(dir).join("autostart"))
+        .collect()
+}
+
+/// The file of entry `id` that applies: the user's, else the first system
+/// one.
+fn effective_file(id: &str) -> Option<PathBuf> {
+    std::iter::once(user_dir())
+        .chain(system_dirs())
+        .map(|dir| dir.join(format!("{id}.desktop")))
+        .find(|path| path.exists())
+}
+
+/// All autostart entries, sorted by name. A user entry shadows a system one
+/// with the same id.
+pub fn autostart_entries() -> Vec<AutostartEntry> {
+    let system = system_dirs();
+    let mut ids: Vec<String> = Vec::new();
+    for dir in std::iter::once(user_dir()).chain(system.iter().cloned()) {
+        let Ok(files) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            if let Some(id) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".desktop"))
+                && !ids.iter().any(|known| known == id)
+            {
+                ids.push(id.to_owned());
+            }
+        }
+    }
+
+    let mut entries: Vec<AutostartEntry> = ids
+        .into_iter()
+        .filter_map(|id| {
+            let file = format!("{id}.desktop");
+            let user_only = !system.iter().any(|dir| dir.join(&file).exists());
+            let content = std::fs::read_to_string(effective_file(&id)?).ok()?;
+            Some(parse_entry(id, &content, user_only))
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+    entries
+}
+
+/// The `[Desktop Entry]` group of an autostart file. Unlike menu entries,
+/// hidden ones are kept: they are the ones turned off.
+fn parse_entry(id: String, content: &str, user_only: bool) -> AutostartEntry {
+    let mut in_group = false;
+    let mut name = None;
+    let mut exec = String::new();
+    let mut enabled = true;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_group {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Name" => name = Some(value.to_owned()),
+            "Exec" => exec = value.to_owned(),
+            "Hidden" if value == "true" => enabled = false,
+            "X-GNOME-Autostart-enabled" if value == "false" => enabled = false,
+            _ => {}
+        }
+    }
+    AutostartEntry {
+        name: name.unwrap_or_else(|| id.clone()),
+        id,
+        exec,
+        enabled,
+        user_only,
+    }
+}
+
+/// Turn entry `id` on or off with a user copy of its file.
+pub fn set_autostart_enabled(id: &str, enabled: bool) -> Result<(), String> {
+    let path = effective_file(id).ok_or_else(|| format!("{id}.desktop not found"))?;
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    write_user_entry(id, &with_hidden(&content, !enabled))
+}
+
+/// Start the installed application `id` at login.
+pub fn add_autostart(id: &str) -> Result<(), String> {
+    let path = aios_common::desktop::application_dirs()
+        .into_iter()
+        .map(|dir| dir.join(format!("{id}.desktop")))
+        .find(|path| path.exists())
+        .ok_or_else(|| format!("{id}.desktop not found"))?;
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    write_user_entry(id, &with_hidden(&content, false))
+}
+
+/// Delete the user's entry `id`.
+pub fn remove_autostart(id: &str) -> Result<(), String> {
+    std::fs::remove_file(user_dir().join(format!("{id}.desktop"))).map_err(|e| e.to_string())
+}
+
+fn write_user_entry(id: &str, content: &str) -> Result<(), String> {
+    let dir = user_dir();
+    std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(dir.join(format!("{id}.desktop")), content))
+        .map_err(|e| e.to_string())
+}
+
+/// `content` with the enable keys of `[Desktop Entry]` replaced by a single
+/// `Hidden=true` when `hidden`.
+fn with_hidden(content: &str, hidden: bool) -> String {
+    let mut in_group = false;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_group = trimmed == "[Desktop Entry]";
+            lines.push(line);
+            if in_group && hidden {
+                lines.push("Hidden=true");
+            }
+            continue;
+        }
+        let key = trimmed.split_once('=').map(|(key, _)| key.trim());
+        if in_group && matches!(key, Some("Hidden" | "X-GNOME-Autostart-enabled")) {
+            continue;
+        }
+        lines.push(line);
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
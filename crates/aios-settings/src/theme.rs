@@ -1,52 +1,89 @@
-//! Dark theme styles for AIOS Settings -- matches dock/chat Tokyo Night palette.
+//! Styles for AIOS Settings -- shares the dock/chat palette via `aios_theme`.
 
 use iced::widget::{button, container, text_input};
 use iced::{Background, Border, Color};
 
+/// Colors for the settings window, read from the shared [`aios_theme`] palette.
 pub struct SettingsColors;
 
 impl SettingsColors {
-    pub const BG_PRIMARY: Color = Color::from_rgb(0.10, 0.11, 0.15);
-    pub const BG_SECONDARY: Color = Color::from_rgb(0.13, 0.14, 0.18);
-    pub const BG_SIDEBAR: Color = Color::from_rgb(0.08, 0.09, 0.12);
-    pub const BG_INPUT: Color = Color::from_rgb(0.16, 0.17, 0.22);
-    pub const TEXT_PRIMARY: Color = Color::from_rgb(0.87, 0.89, 0.93);
-    pub const TEXT_SECONDARY: Color = Color::from_rgb(0.55, 0.58, 0.65);
-    pub const ACCENT: Color = Color::from_rgb(0.47, 0.56, 1.0);
-    pub const SUCCESS: Color = Color::from_rgb(0.30, 0.78, 0.47);
-    pub const DANGER: Color = Color::from_rgb(0.85, 0.30, 0.30);
-    pub const SIDEBAR_ACTIVE: Color = Color::from_rgba(0.47, 0.56, 1.0, 0.15);
-    pub const SIDEBAR_HOVER: Color = Color::from_rgba(1.0, 1.0, 1.0, 0.08);
+    pub fn bg_primary() -> Color {
+        aios_theme::palette().bg_primary
+    }
+    pub fn bg_secondary() -> Color {
+        aios_theme::palette().bg_secondary
+    }
+    pub fn bg_sidebar() -> Color {
+        aios_theme::palette().bg_sidebar
+    }
+    pub fn bg_input() -> Color {
+        aios_theme::palette().bg_input
+    }
+    pub fn text_primary() -> Color {
+        aios_theme::palette().text_primary
+    }
+    pub fn text_secondary() -> Color {
+        aios_theme::palette().text_secondary
+    }
+    pub fn accent() -> Color {
+        aios_theme::palette().accent
+    }
+    pub fn success() -> Color {
+        aios_theme::palette().success
+    }
+    pub fn danger() -> Color {
+        aios_theme::palette().danger
+    }
+    pub fn sidebar_active() -> Color {
+        aios_theme::with_alpha(aios_theme::palette().accent, 0.15)
+    }
+    pub fn sidebar_hover() -> Color {
+        aios_theme::palette().overlay(0.08)
+    }
 }
 
 pub fn container_primary(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(SettingsColors::BG_PRIMARY)),
-        text_color: Some(SettingsColors::TEXT_PRIMARY),
+        background: Some(Background::Color(SettingsColors::bg_primary())),
+        text_color: Some(SettingsColors::text_primary()),
         ..container::Style::default()
     }
 }
 
 pub fn container_secondary(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(SettingsColors::BG_SECONDARY)),
-        text_color: Some(SettingsColors::TEXT_PRIMARY),
+        background: Some(Background::Color(SettingsColors::bg_secondary())),
+        text_color: Some(SettingsColors::text_primary()),
         ..container::Style::default()
     }
 }
 
 pub fn container_sidebar(_theme: &iced::Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(SettingsColors::BG_SIDEBAR)),
-        text_color: Some(SettingsColors::TEXT_PRIMARY),
+        background: Some(Background::Color(SettingsColors::bg_sidebar())),
+        text_color: Some(SettingsColors::text_primary()),
+        ..container::Style::default()
+    }
+}
+
+/// Tooltip naming a button that only shows a symbol.
+pub fn container_tooltip(_theme: &iced::Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(SettingsColors::bg_input())),
+        text_color: Some(SettingsColors::text_primary()),
+        border: Border {
+            color: aios_theme::palette().border,
+            width: 1.0,
+            radius: 6.0.into(),
+        },
         ..container::Style::default()
     }
 }
 
 pub fn sidebar_tab_active(_theme: &iced::Theme, _status: button::Status) -> button::Style {
     button::Style {
-        background: Some(Background::Color(SettingsColors::SIDEBAR_ACTIVE)),
-        text_color: SettingsColors::ACCENT,
+        background: Some(Background::Color(SettingsColors::sidebar_active())),
+        text_color: SettingsColors::accent(),
         border: Border {
             radius: 6.0.into(),
             ..Border::default()
@@ -58,7 +95,7 @@ pub fn sidebar_tab_active(_theme: &iced::Theme, _status: button::Status) -> butt
 pub fn sidebar_tab_inactive(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
         background: Some(Background::Color(Color::TRANSPARENT)),
-        text_color: SettingsColors::TEXT_SECONDARY,
+        text_color: SettingsColors::text_secondary(),
         border: Border {
             radius: 6.0.into(),
             ..Border::default()
@@ -67,8 +104,8 @@ pub fn sidebar_tab_inactive(_theme: &iced::Theme, status: button::Status) -> but
     };
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(SettingsColors::SIDEBAR_HOVER)),
-            text_color: SettingsColors::TEXT_PRIMARY,
+            background: Some(Background::Color(SettingsColors::sidebar_hover())),
+            text_color: SettingsColors::text_primary(),
             ..base
         },
         _ => base,
@@ -77,7 +114,7 @@ pub fn sidebar_tab_inactive(_theme: &iced::Theme, status: button::Status) -> but
 
 pub fn action_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(SettingsColors::ACCENT)),
+        background: Some(Background::Color(SettingsColors::accent())),
         text_color: Color::WHITE,
         border: Border {
             radius: 8.0.into(),
@@ -87,11 +124,11 @@ pub fn action_button(_theme: &iced::Theme, status: button::Status) -> button::St
     };
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.55, 0.64, 1.0))),
+            background: Some(Background::Color(aios_theme::palette().accent_hover())),
             ..base
         },
         button::Status::Disabled => button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.47, 0.56, 1.0, 0.4))),
+            background: Some(Background::Color(aios_theme::with_alpha(SettingsColors::accent(), 0.4))),
             text_color: Color::from_rgba(1.0, 1.0, 1.0, 0.4),
             ..base
         },
@@ -101,7 +138,7 @@ pub fn action_button(_theme: &iced::Theme, status: button::Status) -> button::St
 
 pub fn danger_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
-        background: Some(Background::Color(SettingsColors::DANGER)),
+        background: Some(Background::Color(SettingsColors::danger())),
         text_color: Color::WHITE,
         border: Border {
             radius: 8.0.into(),
@@ -111,7 +148,7 @@ pub fn danger_button(_theme: &iced::Theme, status: button::Status) -> button::St
     };
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.95, 0.40, 0.40))),
+            background: Some(Background::Color(aios_theme::mix(SettingsColors::danger(), Color::WHITE, 0.15))),
             ..base
         },
         _ => base,
@@ -121,7 +158,7 @@ pub fn danger_button(_theme: &iced::Theme, status: button::Status) -> button::St
 pub fn close_button(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let base = button::Style {
         background: Some(Background::Color(Color::TRANSPARENT)),
-        text_color: SettingsColors::TEXT_SECONDARY,
+        text_color: SettingsColors::text_secondary(),
         border: Border {
             radius: 4.0.into(),
             ..Border::default()
@@ -130,8 +167,8 @@ pub fn close_button(_theme: &iced::Theme, status: button::Status) -> button::Sty
     };
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.85, 0.30, 0.30, 0.3))),
-            text_color: Color::from_rgb(0.95, 0.40, 0.40),
+            background: Some(Background::Color(aios_theme::with_alpha(SettingsColors::danger(), 0.3))),
+            text_color: SettingsColors::danger(),
             ..base
         },
         _ => base,
@@ -140,21 +177,21 @@ pub fn close_button(_theme: &iced::Theme, status: button::Status) -> button::Sty
 
 pub fn input_style(_theme: &iced::Theme, status: text_input::Status) -> text_input::Style {
     let base = text_input::Style {
-        background: Background::Color(SettingsColors::BG_INPUT),
+        background: Background::Color(SettingsColors::bg_input()),
         border: Border {
             radius: 8.0.into(),
             width: 1.0,
-            color: Color::from_rgba(1.0, 1.0, 1.0, 0.08),
+            color: aios_theme::palette().overlay(0.08),
         },
-        icon: SettingsColors::TEXT_SECONDARY,
-        placeholder: SettingsColors::TEXT_SECONDARY,
-        value: SettingsColors::TEXT_PRIMARY,
-        selection: SettingsColors::ACCENT,
+        icon: SettingsColors::text_secondary(),
+        placeholder: SettingsColors::text_secondary(),
+        value: SettingsColors::text_primary(),
+        selection: SettingsColors::accent(),
     };
     match status {
         text_input::Status::Focused { .. } => text_input::Style {
             border: Border {
-                color: SettingsColors::ACCENT,
+                color: SettingsColors::accent(),
                 width: 1.5,
                 ..base.border
             },
@@ -163,3 +200,29 @@ pub fn input_style(_theme: &iced::Theme, status: text_input::Status) -> text_inp
         _ => base,
     }
 }
+
+/// Round accent preset swatch; `selected` draws a ring in the text color.
+pub fn accent_swatch(
+    color: Color,
+    selected: bool,
+) -> impl Fn(&iced::Theme, button::Status) -> button::Style {
+    move |_theme, status| {
+        let background = match status {
+            button::Status::Hovered => aios_theme::mix(color, Color::WHITE, 0.15),
+            _ => color,
+        };
+        button::Style {
+            background: Some(Background::Color(background)),
+            border: Border {
+                color: if selected {
+                    SettingsColors::text_primary()
+                } else {
+                    Color::TRANSPARENT
+                },
+                width: 2.0,
+                radius: 14.0.into(),
+            },
+            ..button::Style::default()
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use aios_common::t;
+use iced::widget::{button, column, container, row, scrollable, text, Space};
+use iced::{Element, Length};
+
+use crate::app::{AboutState, Message};
+use crate::theme;
+
+/// Package updates listed by name; the rest are only counted.
+const MAX_LISTED_UPDATES: usize = 50;
+
+pub fn view(state: &AboutState) -> Element<'_, Message> {
+    let title = text(t!("settings-about-title"))
+        .size(20)
+        .color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    // Build
+    match &state.build {
+        Some(build) => {
+            content = content.push(
+                text(t!(
+                    "settings-about-version",
+                    version = build.version.as_str()
+                ))
+                .size(16)
+                .color(theme::SettingsColors::text_primary()),
+            );
+            content = content.push(secondary(t!(
+                "settings-about-build",
+                id = build.build_id.as_str(),
+                date = build.build_date.as_str()
+            )));
+        }
+        None => content = content.push(secondary(t!("settings-about-dev-build"))),
+    }
+
+    // Components
+    content = content.push(heading(t!("settings-about-components")));
+    let mut components = column![].spacing(4);
+    for component in &state.components {
+        let version = match &component.version {
+            Some(version) => text(version.as_str())
+                .size(13)
+                .color(theme::SettingsColors::text_primary()),
+            None => text(t!("settings-about-not-installed"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        };
+        components = components.push(row![
+            text(component.name.as_str())
+                .size(13)
+                .color(theme::SettingsColors::text_primary())
+                .width(160),
+            version,
+        ]);
+    }
+    content = content.push(components);
+
+    // Running agent
+    content = content.push(heading(t!("settings-about-agent")));
+    match (&state.agent, &state.agent_error) {
+        (Some(agent), _) => {
+            content = content.push(
+                text(t!(
+                    "settings-about-agent-running",
+                    version = agent.version.as_str()
+                ))
+                .size(13)
+                .color(theme::SettingsColors::success()),
+            );
+            let backend = match (&agent.provider, &agent.model) {
+                (Some(provider), Some(model)) => t!(
+                    "settings-about-agent-provider",
+                    provider = provider.as_str(),
+                    model = model.as_str()
+                ),
+                _ => t!("settings-about-agent-echo"),
+            };
+            content = content.push(secondary(backend));
+        }
+        (None, Some(err)) => {
+            content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+        }
+        (None, None) => {
+            content = content.push(
+                text(t!("settings-about-agent-stopped"))
+                    .size(13)
+                    .color(theme::SettingsColors::danger()),
+            );
+        }
+    }
+
+    // Updates
+    let busy = state.checking || state.installing;
+    let check_label = if state.checking {
+        t!("settings-about-checking")
+    } else {
+        t!("settings-about-check")
+    };
+    content = content.push(
+        row![
+            heading(t!("settings-about-updates")),
+            Space::new().width(Length::Fill),
+            button(text(check_label).size(13))
+                .on_press_maybe((!busy).then_some(Message::AboutCheckUpdates))
+                .padding([6, 14])
+                .style(theme::action_button),
+        ]
+        .align_y(iced::Alignment::Center),
+    );
+    content = content.push(secondary(t!("settings-about-updates-hint")));
+
+    if state.installed {
+        content = content.push(
+            text(t!("settings-about-installed"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    } else if let Some(updates) = &state.updates {
+        if updates.is_empty() {
+            content = content.push(
+                text(t!("settings-about-up-to-date"))
+                    .size(12)
+                    .color(theme::SettingsColors::success()),
+            );
+        } else {
+            let install_label = if state.installing {
+                t!("settings-about-installing")
+            } else {
+                t!("settings-about-install")
+            };
+            content = content.push(
+                row![
+                    text(t!(
+                        "settings-about-updates-available",
+                        count = updates.len()
+                    ))
+                    .size(13)
+                    .color(theme::SettingsColors::accent()),
+                    Space::new().width(Length::Fill),
+                    button(text(install_label).size(13))
+                        .on_press_maybe((!busy).then_some(Message::AboutInstallUpdates))
+                        .padding([6, 14])
+                        .style(theme::action_button),
+                ]
+                .align_y(iced::Alignment::Center),
+            );
+
+            let mut list = column![].spacing(2);
+            for update in updates.iter().take(MAX_LISTED_UPDATES) {
+                let versions = if update.installed.is_empty() {
+                    update.available.clone()
+                } else {
+                    format!("{} → {}", update.installed, update.available)
+                };
+                list = list.push(row![
+                    text(update.name.as_str())
+                        .size(12)
+                        .color(theme::SettingsColors::text_primary())
+                        .width(220),
+                    text(versions)
+                        .size(12)
+                        .color(theme::SettingsColors::text_secondary()),
+                ]);
+            }
+            if updates.len() > MAX_LISTED_UPDATES {
+                list = list.push(secondary(format!(
+                    "… +{}",
+                    updates.len() - MAX_LISTED_UPDATES
+                )));
+            }
+            content = content.push(list);
+        }
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+fn heading(title: String) -> Element<'static, Message> {
+    text(title)
+        .size(16)
+        .color(theme::SettingsColors::text_primary())
+        .into()
+}
+
+fn secondary(line: String) -> Element<'static, Message> {
+    text(line)
+        .size(12)
+        .color(theme::SettingsColors::text_secondary())
+        .into()
+}
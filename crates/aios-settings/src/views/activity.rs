@@ -0,0 +1,175 @@
+use aios_common::{AuditEntry, AuditKind, AuditResult, t};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, toggler, Space};
+use iced::{Element, Length};
+
+use crate::activity::Period;
+use crate::app::{ActivityState, Message};
+use crate::theme;
+
+/// Characters of a call's arguments shown in the list.
+const MAX_ARGUMENTS_LEN: usize = 160;
+
+pub fn view(state: &ActivityState) -> Element<'_, Message> {
+    let refresh_label = if state.loading {
+        t!("settings-activity-loading")
+    } else {
+        t!("settings-refresh")
+    };
+    let title = row![
+        text(t!("settings-tab-activity"))
+            .size(20)
+            .color(theme::SettingsColors::text_primary()),
+        Space::new().width(Length::Fill),
+        button(text(refresh_label).size(13))
+            .on_press_maybe((!state.loading).then_some(Message::ActivityRefresh))
+            .padding([6, 14])
+            .style(theme::action_button),
+    ]
+    .align_y(iced::Alignment::Center);
+
+    let mut content = column![title].spacing(12).padding(16);
+    content = content.push(
+        text(t!("settings-activity-hint"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+
+    // Filters
+    let mut periods = row![].spacing(8);
+    for period in Period::ALL {
+        let label = match period {
+            Period::Today => t!("settings-activity-today"),
+            Period::Yesterday => t!("settings-activity-yesterday"),
+            Period::Week => t!("settings-activity-week"),
+            Period::All => t!("settings-activity-all"),
+        };
+        periods = periods.push(
+            button(text(label).size(13))
+                .on_press(Message::ActivityPeriod(period))
+                .padding([6, 12])
+                .style(if state.period == period {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::sidebar_tab_inactive
+                }),
+        );
+    }
+    content = content.push(periods);
+    content = content.push(
+        row![
+            text_input(&t!("settings-activity-action"), &state.action)
+                .on_input(Message::ActivityActionChanged)
+                .on_submit(Message::ActivityRefresh)
+                .padding(8)
+                .size(13)
+                .width(200)
+                .style(theme::input_style),
+            text_input(&t!("settings-activity-search"), &state.search)
+                .on_input(Message::ActivitySearchChanged)
+                .on_submit(Message::ActivityRefresh)
+                .padding(8)
+                .size(13)
+                .style(theme::input_style),
+            toggler(state.errors_only)
+                .label(t!("settings-activity-errors-only"))
+                .on_toggle(Message::ActivityErrorsOnly),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+    );
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    } else if state.entries.is_empty() && !state.loading {
+        content = content.push(
+            text(t!("settings-activity-empty"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    }
+
+    let mut entries = column![].spacing(8);
+    for entry in &state.entries {
+        entries = entries.push(entry_row(entry));
+    }
+    content = content.push(entries);
+
+    if state.truncated {
+        content = content.push(
+            text(t!(
+                "settings-activity-truncated",
+                count = state.entries.len()
+            ))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+        );
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+/// Time, tool and outcome of a call, with its arguments below.
+fn entry_row(entry: &AuditEntry) -> Element<'_, Message> {
+    let (outcome, color) = match &entry.result {
+        AuditResult::Ok => (t!("settings-activity-ok"), theme::SettingsColors::success()),
+        AuditResult::Error(_) => (
+            t!("settings-activity-error"),
+            theme::SettingsColors::danger(),
+        ),
+        AuditResult::Rejected => (
+            t!("settings-activity-rejected"),
+            theme::SettingsColors::danger(),
+        ),
+        AuditResult::Timeout => (
+            t!("settings-activity-timeout"),
+            theme::SettingsColors::danger(),
+        ),
+    };
+    let time = entry
+        .timestamp
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+
+    let action = match entry.kind {
+        AuditKind::Tool => entry.action.clone(),
+        AuditKind::Llm => t!("settings-activity-llm", provider = entry.action.as_str()),
+    };
+    let mut arguments = entry.arguments.to_string();
+    if let Some((end, _)) = arguments.char_indices().nth(MAX_ARGUMENTS_LEN) {
+        arguments.truncate(end);
+        arguments.push('…');
+    }
+
+    let mut details = column![
+        row![
+            text(time)
+                .size(12)
+                .color(theme::SettingsColors::text_secondary())
+                .width(150),
+            text(action)
+                .size(13)
+                .color(theme::SettingsColors::text_primary()),
+            Space::new().width(Length::Fill),
+            text(outcome).size(12).color(color),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+        text(arguments)
+            .size(11)
+            .color(theme::SettingsColors::text_secondary()),
+    ]
+    .spacing(2);
+    let message = match &entry.result {
+        AuditResult::Error(message) => Some(message.as_str()),
+        _ => entry.details.as_deref(),
+    };
+    if let Some(message) = message {
+        details = details.push(text(message).size(11).color(color));
+    }
+    details.into()
+}
@@ -0,0 +1,209 @@
+use aios_common::t;
+use iced::widget::{
+    button, column, container, row, scrollable, text, text_editor, text_input, toggler, Space,
+};
+use iced::{Element, Font, Length};
+
+use crate::app::{AgentState, Message};
+use crate::theme;
+
+pub fn view(state: &AgentState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-agent"))
+        .size(20)
+        .color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    content = content.push(heading(t!("settings-agent-socket")));
+    content = content.push(
+        text_input("/run/user/1000/aios-agent.sock", &state.socket_path)
+            .on_input(Message::AgentSocketChanged)
+            .padding(8)
+            .size(13),
+    );
+    content = content.push(heading(t!("settings-agent-audit-log")));
+    content = content.push(
+        row![
+            text_input("/var/log/aios/actions.log", &state.audit_log)
+                .on_input(Message::AgentAuditLogChanged)
+                .padding(8)
+                .size(13),
+            button(text(t!("settings-agent-audit-verify")).size(13))
+                .on_press(Message::AgentVerifyAudit)
+                .padding([8, 16])
+                .style(theme::action_button),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+    );
+    if let Some((intact, msg)) = &state.audit_check {
+        let color = if *intact {
+            theme::SettingsColors::success()
+        } else {
+            theme::SettingsColors::danger()
+        };
+        content = content.push(text(msg).size(12).color(color));
+    }
+    content = content.push(
+        toggler(state.audit_llm)
+            .label(t!("settings-agent-audit-llm"))
+            .on_toggle(Message::AgentAuditLlm),
+    );
+    content = content.push(
+        text(t!("settings-agent-audit-llm-hint"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+    content = content.push(
+        toggler(state.show_plans)
+            .label(t!("settings-agent-show-plans"))
+            .on_toggle(Message::AgentShowPlans),
+    );
+    content = content.push(
+        text(t!("settings-agent-show-plans-hint"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+    content = content.push(
+        row![
+            text(t!("settings-agent-max-destructive"))
+                .size(13)
+                .color(theme::SettingsColors::text_primary()),
+            Space::new().width(Length::Fill),
+            text_input("3", &state.max_destructive)
+                .on_input(Message::AgentMaxDestructiveChanged)
+                .padding(8)
+                .size(13)
+                .width(80),
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center),
+    );
+    content = content.push(
+        row![
+            text(t!("settings-agent-max-parallel-tools"))
+                .size(13)
+                .color(theme::SettingsColors::text_primary()),
+            Space::new().width(Length::Fill),
+            text_input("4", &state.max_parallel_tools)
+                .on_input(Message::AgentMaxParallelToolsChanged)
+                .padding(8)
+                .size(13)
+                .width(80),
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center),
+    );
+    content = content.push(
+        text(t!("settings-agent-restart-hint"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+
+    content = content.push(heading(t!("settings-agent-prompt")));
+    content = content.push(
+        text_editor(&state.prompt)
+            .placeholder(t!("settings-agent-prompt-placeholder"))
+            .on_action(Message::AgentPromptEdit)
+            .height(160)
+            .padding(8)
+            .size(13),
+    );
+
+    content = content.push(
+        row![
+            button(text(t!("settings-agent-save")).size(13))
+                .on_press(Message::AgentSave)
+                .padding([8, 16])
+                .style(theme::action_button),
+            button(text(t!("settings-agent-reload")).size(13))
+                .on_press(Message::AgentReload)
+                .padding([8, 16])
+                .style(theme::action_button),
+            button(text(t!("settings-agent-restart")).size(13))
+                .on_press(Message::AgentRestart)
+                .padding([8, 16])
+                .style(theme::danger_button),
+        ]
+        .spacing(8),
+    );
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-agent-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+    if let Some((success, msg)) = &state.status {
+        let color = if *success {
+            theme::SettingsColors::success()
+        } else {
+            theme::SettingsColors::danger()
+        };
+        content = content.push(text(msg).size(12).color(color));
+    }
+
+    // Live log tail, newest at the bottom
+    content = content.push(heading(t!("settings-agent-logs")));
+    if let Some(reason) = &state.disconnected {
+        content = content.push(
+            text(t!("settings-agent-disconnected", reason = reason.as_str()))
+                .size(12)
+                .color(theme::SettingsColors::danger()),
+        );
+    }
+    // Lines logged while handling a request carry its `request_id`, so
+    // filtering on one shows that request across chat turn, tools and
+    // confirmation.
+    content = content.push(
+        text_input(&t!("settings-agent-logs-filter"), &state.log_filter)
+            .on_input(Message::AgentLogFilterChanged)
+            .padding(8)
+            .size(13),
+    );
+    let filter = state.log_filter.trim();
+    let shown: Vec<_> = state
+        .logs
+        .iter()
+        .filter(|line| line.text.contains(filter))
+        .collect();
+    let mut lines = column![].spacing(2);
+    if shown.is_empty() {
+        lines = lines.push(
+            text(t!("settings-agent-logs-empty"))
+                .size(12)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    }
+    for line in shown {
+        lines = lines.push(
+            text(&line.text)
+                .size(11)
+                .font(Font::MONOSPACE)
+                .color(theme::SettingsColors::text_primary()),
+        );
+    }
+    content = content.push(
+        container(scrollable(lines).anchor_bottom().width(Length::Fill))
+            .height(220)
+            .padding(8)
+            .style(theme::container_secondary),
+    );
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+fn heading(label: String) -> Element<'static, Message> {
+    text(label)
+        .size(14)
+        .color(theme::SettingsColors::text_secondary())
+        .into()
+}
@@ -1,11 +1,12 @@
-use iced::widget::{button, column, container, row, text, text_input, Space};
+use aios_common::t;
+use iced::widget::{button, column, container, pick_list, row, text, text_input, Space};
 use iced::{Element, Length};
 
 use crate::app::{AiState, Message};
 use crate::theme;
 
 pub fn view(state: &AiState) -> Element<'_, Message> {
-    let title = text("AI Provider").size(20).color(theme::SettingsColors::TEXT_PRIMARY);
+    let title = text(t!("settings-tab-ai")).size(20).color(theme::SettingsColors::text_primary());
 
     let header = row![title].align_y(iced::Alignment::Center);
 
@@ -13,7 +14,7 @@ pub fn view(state: &AiState) -> Element<'_, Message> {
 
     // Provider selection buttons
     content = content.push(
-        text("Provider").size(14).color(theme::SettingsColors::TEXT_SECONDARY),
+        text(t!("settings-ai-provider")).size(14).color(theme::SettingsColors::text_secondary()),
     );
 
     let providers = [("ollama", "Ollama"), ("open_ai", "OpenAI"), ("claude", "Claude")];
@@ -39,7 +40,7 @@ pub fn view(state: &AiState) -> Element<'_, Message> {
     // API Key (hidden for Ollama, shown for OpenAI/Claude)
     if state.provider != "ollama" {
         content = content.push(
-            text("API Key").size(14).color(theme::SettingsColors::TEXT_SECONDARY),
+            text(t!("settings-ai-api-key")).size(14).color(theme::SettingsColors::text_secondary()),
         );
         content = content.push(
             text_input("sk-...", &state.api_key)
@@ -52,60 +53,48 @@ pub fn view(state: &AiState) -> Element<'_, Message> {
 
     // Model
     content = content.push(
-        text("Model").size(14).color(theme::SettingsColors::TEXT_SECONDARY),
+        text(t!("settings-ai-model")).size(14).color(theme::SettingsColors::text_secondary()),
     );
 
-    // Show installed Ollama models as clickable cards
-    if state.provider == "ollama" && !state.installed_models.is_empty() {
-        let mut model_row = row![].spacing(6);
-        let mut count = 0;
-        let mut model_col = column![].spacing(6);
-
-        for model_name in &state.installed_models {
-            let is_selected = state.model == *model_name;
-            let style = if is_selected {
-                theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
-            } else {
-                theme::action_button
-            };
-            let btn = button(text(model_name.clone()).size(12))
-                .padding([6, 12])
-                .style(style)
-                .on_press(Message::AiPickModel(model_name.clone()));
-            model_row = model_row.push(btn);
-            count += 1;
-            if count % 3 == 0 {
-                model_col = model_col.push(model_row);
-                model_row = row![].spacing(6);
-            }
-        }
-        if count % 3 != 0 {
-            model_col = model_col.push(model_row);
-        }
-        content = content.push(model_col);
-
+    // Models from the provider's catalog, for picking instead of typing
+    if !state.models.is_empty() {
+        let selected = state.models.iter().find(|m| **m == state.model).cloned();
         content = content.push(
-            text("Или введи вручную:").size(12).color(theme::SettingsColors::TEXT_SECONDARY),
+            pick_list(state.models.as_slice(), selected, Message::AiPickModel)
+                .placeholder(t!("settings-ai-model-pick"))
+                .text_size(13)
+                .padding(10)
+                .width(Length::Fill),
+        );
+        content = content.push(
+            text(t!("settings-ai-model-manual")).size(12).color(theme::SettingsColors::text_secondary()),
         );
     }
 
     let model_placeholder = match state.provider.as_str() {
-        "ollama" => "llama3.2:3b",
-        "open_ai" => "gpt-4o",
-        "claude" => "claude-sonnet-4-20250514",
-        _ => "model name",
+        "ollama" => "llama3.2:3b".to_owned(),
+        "open_ai" => "gpt-4o".to_owned(),
+        "claude" => "claude-sonnet-4-20250514".to_owned(),
+        _ => t!("settings-ai-model-placeholder"),
     };
 
     content = content.push(
-        text_input(model_placeholder, &state.model)
+        text_input(&model_placeholder, &state.model)
             .on_input(Message::AiModelChanged)
             .padding(10)
             .size(13),
     );
+    if state.provider == "ollama"
+        && let Some(warning) = state
+            .memory
+            .and_then(|memory| memory.warning(state.model.trim(), None))
+    {
+        content = content.push(text(warning).size(12).color(theme::SettingsColors::danger()));
+    }
 
     // Base URL (optional, mainly for Ollama custom host or OpenAI-compatible)
     content = content.push(
-        text("Base URL (optional)").size(14).color(theme::SettingsColors::TEXT_SECONDARY),
+        text(t!("settings-ai-base-url")).size(14).color(theme::SettingsColors::text_secondary()),
     );
 
     let url_placeholder = match state.provider.as_str() {
@@ -125,7 +114,7 @@ pub fn view(state: &AiState) -> Element<'_, Message> {
     // Save button
     content = content.push(Space::new().height(8));
 
-    let save_btn = button(text("Save").size(14))
+    let save_btn = button(text(t!("settings-save")).size(14))
         .padding([10, 24])
         .style(theme::action_button)
         .on_press(Message::AiSave);
@@ -134,9 +123,9 @@ pub fn view(state: &AiState) -> Element<'_, Message> {
 
     if state.saved {
         save_row = save_row.push(
-            text("Saved & applied!")
+            text(t!("settings-ai-saved"))
                 .size(12)
-                .color(theme::SettingsColors::SUCCESS),
+                .color(theme::SettingsColors::success()),
         );
     }
 
@@ -144,10 +133,24 @@ pub fn view(state: &AiState) -> Element<'_, Message> {
 
     if let Some(err) = &state.error {
         content = content.push(
-            text(err).size(12).color(theme::SettingsColors::DANGER),
+            text(err).size(12).color(theme::SettingsColors::danger()),
         );
     }
 
+    // Setup wizard
+    content = content.push(Space::new().height(8));
+    content = content.push(
+        row![
+            text(t!("settings-ai-setup-hint")).size(12).color(theme::SettingsColors::text_secondary()),
+            Space::new().width(Length::Fill),
+            button(text(t!("settings-ai-rerun-setup")).size(13))
+                .padding([8, 16])
+                .style(theme::action_button)
+                .on_press(Message::AiRunSetupWizard),
+        ]
+        .align_y(iced::Alignment::Center),
+    );
+
     container(content)
         .width(Length::Fill)
         .height(Length::Fill)
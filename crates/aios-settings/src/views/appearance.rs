@@ -0,0 +1,228 @@
+use aios_common::{ColorScheme, Density, DockPosition, t};
+use iced::widget::{
+    button, column, container, image, row, scrollable, text, text_input, Row, Space,
+};
+use iced::{Element, Length};
+
+use crate::app::{AppearanceState, Message};
+use crate::theme;
+
+/// Size of a wallpaper preview.
+const THUMBNAIL_WIDTH: f32 = 112.0;
+const THUMBNAIL_HEIGHT: f32 = 63.0;
+
+pub fn view(state: &AppearanceState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-appearance")).size(20).color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    // Color scheme
+    content = content.push(
+        text(t!("settings-appearance-scheme")).size(14).color(theme::SettingsColors::text_secondary()),
+    );
+
+    let schemes = [
+        (ColorScheme::Dark, t!("settings-appearance-dark")),
+        (ColorScheme::Light, t!("settings-appearance-light")),
+    ];
+    let mut scheme_row = row![].spacing(8);
+    for (scheme, label) in schemes {
+        let is_active = state.color_scheme == scheme;
+        scheme_row = scheme_row.push(
+            button(text(label).size(13))
+                .on_press(Message::AppearanceSelectScheme(scheme))
+                .padding([8, 16])
+                .style(if is_active {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::action_button
+                }),
+        );
+    }
+    content = content.push(scheme_row);
+
+    // Accent color
+    content = content.push(Space::new().height(4));
+    content = content.push(
+        text(t!("settings-appearance-accent")).size(14).color(theme::SettingsColors::text_secondary()),
+    );
+
+    let default_btn = button(text(t!("settings-appearance-accent-default")).size(13))
+        .on_press(Message::AppearanceSelectAccent(None))
+        .padding([6, 12])
+        .style(if state.accent.is_none() {
+            theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+        } else {
+            theme::sidebar_tab_inactive
+        });
+
+    let mut accent_row = row![default_btn].spacing(8).align_y(iced::Alignment::Center);
+    for (_, hex) in aios_theme::ACCENT_PRESETS {
+        let Some(color) = aios_theme::parse_hex(hex) else {
+            continue;
+        };
+        let selected = state.accent.as_deref() == Some(*hex);
+        accent_row = accent_row.push(
+            button(Space::new().width(0).height(0))
+                .on_press(Message::AppearanceSelectAccent(Some((*hex).to_owned())))
+                .width(28)
+                .height(28)
+                .style(theme::accent_swatch(color, selected)),
+        );
+    }
+    content = content.push(accent_row);
+
+    content = content.push(
+        text(t!("settings-appearance-accent-custom")).size(12).color(theme::SettingsColors::text_secondary()),
+    );
+    content = content.push(
+        text_input("#7890ff", &state.accent_input)
+            .on_input(Message::AppearanceAccentInput)
+            .on_submit(Message::AppearanceAccentSubmit)
+            .padding(10)
+            .size(13)
+            .width(160)
+            .style(theme::input_style),
+    );
+
+    // Text size
+    content = content.push(Space::new().height(4));
+    content = content.push(
+        text(t!("settings-appearance-font-scale")).size(14).color(theme::SettingsColors::text_secondary()),
+    );
+
+    let mut scale_row = row![].spacing(8);
+    for &scale in aios_theme::FONT_SCALES {
+        let is_active = (state.font_scale - scale).abs() < 0.01;
+        scale_row = scale_row.push(
+            button(text(format!("{:.0}%", scale * 100.0)).size(13))
+                .on_press(Message::AppearanceSelectFontScale(scale))
+                .padding([6, 12])
+                .style(if is_active {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::sidebar_tab_inactive
+                }),
+        );
+    }
+    content = content.push(scale_row);
+
+    // Density
+    content = content.push(
+        text(t!("settings-appearance-density")).size(14).color(theme::SettingsColors::text_secondary()),
+    );
+
+    let densities = [
+        (Density::Comfortable, t!("settings-appearance-comfortable")),
+        (Density::Compact, t!("settings-appearance-compact")),
+    ];
+    let mut density_row = row![].spacing(8);
+    for (density, label) in densities {
+        density_row = density_row.push(
+            button(text(label).size(13))
+                .on_press(Message::AppearanceSelectDensity(density))
+                .padding([6, 12])
+                .style(if state.density == density {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::sidebar_tab_inactive
+                }),
+        );
+    }
+    content = content.push(density_row);
+
+    // Wallpaper
+    content = content.push(Space::new().height(4));
+    content = content.push(
+        text(t!("settings-appearance-wallpaper")).size(14).color(theme::SettingsColors::text_secondary()),
+    );
+
+    let none_btn = button(text(t!("settings-appearance-wallpaper-none")).size(13))
+        .on_press(Message::AppearanceSelectWallpaper(None))
+        .height(THUMBNAIL_HEIGHT)
+        .padding([6, 12])
+        .style(if state.wallpaper.is_none() {
+            theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+        } else {
+            theme::sidebar_tab_inactive
+        });
+    let thumbnails = state.wallpapers.iter().map(|path| {
+        let selected = state.wallpaper.as_ref() == Some(path);
+        button(
+            image(path.as_str())
+                .width(THUMBNAIL_WIDTH)
+                .height(THUMBNAIL_HEIGHT)
+                .content_fit(iced::ContentFit::Cover),
+        )
+        .on_press(Message::AppearanceSelectWallpaper(Some(path.clone())))
+        .padding(2)
+        .style(if selected {
+            theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+        } else {
+            theme::sidebar_tab_inactive
+        })
+        .into()
+    });
+    content = content.push(
+        Row::with_children(std::iter::once(Element::from(none_btn)).chain(thumbnails))
+            .spacing(8)
+            .wrap(),
+    );
+
+    content = content.push(
+        text(t!("settings-appearance-wallpaper-custom")).size(12).color(theme::SettingsColors::text_secondary()),
+    );
+    content = content.push(
+        text_input("/usr/share/backgrounds/...", &state.wallpaper_input)
+            .on_input(Message::AppearanceWallpaperInput)
+            .on_submit(Message::AppearanceWallpaperSubmit)
+            .padding(10)
+            .size(13)
+            .style(theme::input_style),
+    );
+
+    // Dock
+    content = content.push(Space::new().height(4));
+    content = content.push(
+        text(t!("settings-appearance-dock-position")).size(14).color(theme::SettingsColors::text_secondary()),
+    );
+
+    let positions = [
+        (DockPosition::Bottom, t!("settings-appearance-dock-bottom")),
+        (DockPosition::Top, t!("settings-appearance-dock-top")),
+    ];
+    let mut position_row = row![].spacing(8);
+    for (position, label) in positions {
+        position_row = position_row.push(
+            button(text(label).size(13))
+                .on_press(Message::AppearanceSelectDockPosition(position))
+                .padding([6, 12])
+                .style(if state.dock_position == position {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::sidebar_tab_inactive
+                }),
+        );
+    }
+    content = content.push(position_row);
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-appearance-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(
+            text(err).size(12).color(theme::SettingsColors::danger()),
+        );
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
@@ -0,0 +1,139 @@
+use aios_common::t;
+use aios_common::wpctl::AudioDevice;
+use iced::widget::{button, column, container, row, scrollable, slider, text, Space};
+use iced::{Element, Length};
+
+use crate::app::{AudioState, Message};
+use crate::theme;
+
+pub fn view(state: &AudioState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-audio"))
+        .size(20)
+        .color(theme::SettingsColors::text_primary());
+
+    let refresh_btn = button(text(t!("settings-refresh")).size(13))
+        .on_press(Message::AudioRefresh)
+        .padding([6, 14])
+        .style(theme::action_button);
+
+    let header =
+        row![title, Space::new().width(Length::Fill), refresh_btn].align_y(iced::Alignment::Center);
+
+    let mut content = column![header].spacing(12).padding(16);
+
+    if state.loading {
+        content = content.push(
+            text(t!("settings-audio-loading"))
+                .size(13)
+                .color(theme::SettingsColors::accent()),
+        );
+    } else if state.sinks.is_empty() && state.sources.is_empty() {
+        content = content.push(
+            text(t!("settings-audio-empty"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    } else {
+        let devices = column![
+            section(state, t!("settings-audio-outputs"), &state.sinks),
+            section(state, t!("settings-audio-inputs"), &state.sources),
+        ]
+        .spacing(16);
+        content = content.push(scrollable(devices).height(Length::Fill));
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+fn section<'a>(
+    state: &AudioState,
+    heading: String,
+    devices: &'a [AudioDevice],
+) -> Element<'a, Message> {
+    let mut col = column![
+        text(heading)
+            .size(16)
+            .color(theme::SettingsColors::text_primary())
+    ]
+    .spacing(8);
+    if devices.is_empty() {
+        col = col.push(
+            text(t!("settings-audio-none"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    }
+    for device in devices {
+        col = col.push(device_row(state, device));
+    }
+    col.into()
+}
+
+/// Name and default button on top, volume slider and mute toggle below.
+fn device_row<'a>(state: &AudioState, device: &'a AudioDevice) -> Element<'a, Message> {
+    let id = device.id;
+    let volume = state.volume(device);
+
+    let default_btn = if device.default {
+        button(text(t!("settings-audio-default")).size(11))
+            .padding([4, 8])
+            .style(theme::sidebar_tab_active)
+    } else {
+        button(text(t!("settings-audio-make-default")).size(11))
+            .on_press(Message::AudioSetDefault(id))
+            .padding([4, 8])
+            .style(theme::action_button)
+    };
+
+    let mute_label = if device.muted {
+        t!("settings-audio-unmute")
+    } else {
+        t!("settings-audio-mute")
+    };
+    let mute_btn = button(text(mute_label).size(11))
+        .on_press(Message::AudioToggleMute(id))
+        .padding([4, 8])
+        .style(if device.muted {
+            theme::danger_button as fn(&iced::Theme, _) -> _
+        } else {
+            theme::action_button
+        });
+
+    let name_color = if device.default {
+        theme::SettingsColors::text_primary()
+    } else {
+        theme::SettingsColors::text_secondary()
+    };
+
+    column![
+        row![
+            text(device.name.as_str()).size(13).color(name_color),
+            Space::new().width(Length::Fill),
+            default_btn,
+        ]
+        .align_y(iced::Alignment::Center),
+        row![
+            slider(0..=100, volume.min(100), move |v| {
+                Message::AudioVolumeChanged(id, v)
+            })
+            .on_release(Message::AudioVolumeReleased),
+            text(format!("{volume}%"))
+                .size(12)
+                .width(40)
+                .color(theme::SettingsColors::text_secondary()),
+            mute_btn,
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+    ]
+    .spacing(4)
+    .into()
+}
@@ -1,3 +1,4 @@
+use aios_common::t;
 use iced::widget::{button, column, container, row, text, Space};
 use iced::{Element, Length};
 
@@ -5,9 +6,9 @@ use crate::app::{DisplayState, Message};
 use crate::theme;
 
 pub fn view(state: &DisplayState) -> Element<'_, Message> {
-    let title = text("Display").size(20).color(theme::SettingsColors::TEXT_PRIMARY);
+    let title = text(t!("settings-tab-display")).size(20).color(theme::SettingsColors::text_primary());
 
-    let refresh_btn = button(text("Refresh").size(13))
+    let refresh_btn = button(text(t!("settings-refresh")).size(13))
         .on_press(Message::DisplayRefresh)
         .padding([6, 14])
         .style(theme::action_button);
@@ -19,22 +20,24 @@ pub fn view(state: &DisplayState) -> Element<'_, Message> {
 
     if state.loading {
         content = content.push(
-            text("Loading displays...").size(13).color(theme::SettingsColors::ACCENT),
+            text(t!("settings-display-loading")).size(13).color(theme::SettingsColors::accent()),
         );
     } else if state.outputs.is_empty() {
         content = content.push(
-            text("No displays found. Click Refresh.")
+            text(t!("settings-display-empty"))
                 .size(13)
-                .color(theme::SettingsColors::TEXT_SECONDARY),
+                .color(theme::SettingsColors::text_secondary()),
         );
     } else {
         for output in &state.outputs {
-            let info = format!(
-                "{}: {}x{} @ {:.0}Hz (scale {})",
-                output.name, output.width, output.height, output.refresh, output.scale
+            let info = t!(
+                "settings-display-output",
+                name = output.name.as_str(),
+                mode = format!("{}x{} @ {:.0}Hz", output.width, output.height, output.refresh),
+                scale = output.scale.to_string()
             );
             content = content.push(
-                text(info).size(14).color(theme::SettingsColors::TEXT_PRIMARY),
+                text(info).size(14).color(theme::SettingsColors::text_primary()),
             );
 
             // Mode selection buttons
@@ -68,7 +71,7 @@ pub fn view(state: &DisplayState) -> Element<'_, Message> {
 
     if let Some(err) = &state.error {
         content = content.push(
-            text(err).size(12).color(theme::SettingsColors::DANGER),
+            text(err).size(12).color(theme::SettingsColors::danger()),
         );
     }
 
@@ -0,0 +1,124 @@
+use aios_common::t;
+use iced::widget::{
+    button, column, container, row, scrollable, text, text_input, toggler, Space,
+};
+use iced::{Element, Length};
+
+use crate::app::{DockState, Message};
+use crate::theme;
+use crate::views::named;
+
+pub fn view(state: &DockState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-dock")).size(20).color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    // Pinned apps, in dock order
+    content = content.push(
+        text(t!("settings-dock-pinned")).size(16).color(theme::SettingsColors::text_primary()),
+    );
+
+    if state.pinned.is_empty() {
+        content = content.push(
+            text(t!("settings-dock-none-pinned"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    } else {
+        let mut list = column![].spacing(4);
+        for (index, id) in state.pinned.iter().enumerate() {
+            let last = index + 1 == state.pinned.len();
+            let pinned_row = row![
+                text(state.app_name(id)).size(13).color(theme::SettingsColors::text_primary()),
+                Space::new().width(Length::Fill),
+                named(
+                    button(text("↑").size(11))
+                        .on_press_maybe((index > 0).then_some(Message::DockMoveUp(index)))
+                        .padding([4, 8])
+                        .style(theme::action_button),
+                    t!("settings-move-up"),
+                ),
+                named(
+                    button(text("↓").size(11))
+                        .on_press_maybe((!last).then_some(Message::DockMoveUp(index + 1)))
+                        .padding([4, 8])
+                        .style(theme::action_button),
+                    t!("settings-move-down"),
+                ),
+                button(text(t!("settings-dock-unpin")).size(11))
+                    .on_press(Message::DockUnpin(index))
+                    .padding([4, 8])
+                    .style(theme::danger_button),
+            ]
+            .spacing(4)
+            .align_y(iced::Alignment::Center);
+            list = list.push(pinned_row);
+        }
+        content = content.push(list);
+    }
+
+    content = content.push(
+        toggler(state.gpu_meter)
+            .label(t!("settings-dock-gpu-meter"))
+            .on_toggle(Message::DockGpuMeter),
+    );
+    content = content.push(
+        text(t!("settings-dock-gpu-meter-hint"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+
+    // Installed apps that can be pinned
+    content = content.push(
+        text(t!("settings-dock-add")).size(16).color(theme::SettingsColors::text_primary()),
+    );
+    content = content.push(
+        text_input(&t!("settings-dock-filter"), &state.filter)
+            .on_input(Message::DockFilterChanged)
+            .padding(10)
+            .size(13)
+            .width(280)
+            .style(theme::input_style),
+    );
+
+    let filter = state.filter.trim().to_lowercase();
+    let mut available = column![].spacing(4);
+    for entry in state
+        .available
+        .iter()
+        .filter(|entry| !state.pinned.contains(&entry.id))
+        .filter(|entry| filter.is_empty() || entry.name.to_lowercase().contains(&filter))
+    {
+        let app_row = row![
+            text(entry.name.as_str()).size(13).color(theme::SettingsColors::text_primary()),
+            Space::new().width(Length::Fill),
+            button(text(t!("settings-dock-pin")).size(11))
+                .on_press(Message::DockPin(entry.id.clone()))
+                .padding([4, 8])
+                .style(theme::action_button),
+        ]
+        .align_y(iced::Alignment::Center);
+        available = available.push(app_row);
+    }
+    content = content.push(scrollable(available).height(Length::Fill));
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-dock-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(
+            text(err).size(12).color(theme::SettingsColors::danger()),
+        );
+    }
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
@@ -0,0 +1,197 @@
+use aios_common::{LayoutSwitch, t};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, slider, text, toggler, Space,
+};
+use iced::{Element, Length};
+
+use crate::app::{InputState, Message};
+use crate::theme;
+use crate::views::named;
+
+/// xkb layouts offered for adding, with their native names.
+const LAYOUTS: &[(&str, &str)] = &[
+    ("us", "English (US)"),
+    ("gb", "English (UK)"),
+    ("ru", "Русский"),
+    ("ua", "Українська"),
+    ("by", "Беларуская"),
+    ("kz", "Қазақша"),
+    ("de", "Deutsch"),
+    ("fr", "Français"),
+    ("es", "Español"),
+    ("it", "Italiano"),
+    ("pt", "Português"),
+    ("pl", "Polski"),
+    ("cz", "Čeština"),
+    ("tr", "Türkçe"),
+];
+
+/// An entry of the "add layout" list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LayoutChoice {
+    code: &'static str,
+    name: &'static str,
+}
+
+impl std::fmt::Display for LayoutChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.code)
+    }
+}
+
+fn layout_name(code: &str) -> &str {
+    LAYOUTS
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map_or(code, |(_, name)| *name)
+}
+
+pub fn view(state: &InputState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-input"))
+        .size(20)
+        .color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+    let config = &state.config;
+
+    // Layouts, in switching order
+    content = content.push(heading(t!("settings-input-layouts")));
+    let mut layouts = column![].spacing(4);
+    for (index, code) in config.layouts.iter().enumerate() {
+        let last = index + 1 == config.layouts.len();
+        layouts = layouts.push(
+            row![
+                text(format!("{} ({code})", layout_name(code)))
+                    .size(13)
+                    .color(theme::SettingsColors::text_primary()),
+                Space::new().width(Length::Fill),
+                named(
+                    button(text("↑").size(11))
+                        .on_press_maybe((index > 0).then_some(Message::InputMoveLayoutUp(index)))
+                        .padding([4, 8])
+                        .style(theme::action_button),
+                    t!("settings-move-up"),
+                ),
+                named(
+                    button(text("↓").size(11))
+                        .on_press_maybe((!last).then_some(Message::InputMoveLayoutUp(index + 1)))
+                        .padding([4, 8])
+                        .style(theme::action_button),
+                    t!("settings-move-down"),
+                ),
+                button(text(t!("settings-input-remove")).size(11))
+                    .on_press_maybe(
+                        (config.layouts.len() > 1).then_some(Message::InputRemoveLayout(index)),
+                    )
+                    .padding([4, 8])
+                    .style(theme::danger_button),
+            ]
+            .spacing(6)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    content = content.push(layouts);
+
+    let addable: Vec<LayoutChoice> = LAYOUTS
+        .iter()
+        .filter(|(code, _)| !config.layouts.iter().any(|layout| layout == code))
+        .map(|&(code, name)| LayoutChoice { code, name })
+        .collect();
+    content = content.push(
+        pick_list(addable, None::<LayoutChoice>, |choice| {
+            Message::InputAddLayout(choice.code.to_owned())
+        })
+        .placeholder(t!("settings-input-add-layout"))
+        .text_size(13)
+        .width(240),
+    );
+
+    // Layout switch shortcut
+    content = content.push(heading(t!("settings-input-switch")));
+    let switches = [
+        (LayoutSwitch::AltShift, "Alt+Shift".to_owned()),
+        (LayoutSwitch::CtrlShift, "Ctrl+Shift".to_owned()),
+        (LayoutSwitch::SuperSpace, t!("settings-input-super-space")),
+        (LayoutSwitch::CapsLock, "Caps Lock".to_owned()),
+    ];
+    let mut switch_row = row![].spacing(8);
+    for (switch, label) in switches {
+        switch_row = switch_row.push(
+            button(text(label).size(13))
+                .on_press(Message::InputSelectSwitch(switch))
+                .padding([6, 12])
+                .style(if config.layout_switch == switch {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::sidebar_tab_inactive
+                }),
+        );
+    }
+    content = content.push(switch_row);
+
+    // Key repeat
+    content = content.push(heading(t!("settings-input-repeat")));
+    content = content.push(
+        row![
+            text(t!("settings-input-repeat-delay", ms = config.repeat_delay))
+                .size(13)
+                .width(200)
+                .color(theme::SettingsColors::text_primary()),
+            slider(150..=1000, config.repeat_delay, Message::InputRepeatDelay)
+                .step(50u32)
+                .on_release(Message::InputRepeatReleased),
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center),
+    );
+    content = content.push(
+        row![
+            text(t!("settings-input-repeat-rate", rate = config.repeat_rate))
+                .size(13)
+                .width(200)
+                .color(theme::SettingsColors::text_primary()),
+            slider(5..=60, config.repeat_rate, Message::InputRepeatRate)
+                .on_release(Message::InputRepeatReleased),
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center),
+    );
+
+    // Touchpad
+    content = content.push(heading(t!("settings-input-touchpad")));
+    content = content.push(
+        toggler(config.tap_to_click)
+            .label(t!("settings-input-tap-to-click"))
+            .on_toggle(Message::InputTapToClick),
+    );
+    content = content.push(
+        toggler(config.natural_scroll)
+            .label(t!("settings-input-natural-scroll"))
+            .on_toggle(Message::InputNaturalScroll),
+    );
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-input-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+fn heading(label: String) -> Element<'static, Message> {
+    text(label)
+        .size(14)
+        .color(theme::SettingsColors::text_secondary())
+        .into()
+}
@@ -0,0 +1,73 @@
+use aios_common::{i18n, t};
+use iced::widget::{button, column, container, text};
+use iced::{Element, Length};
+
+use crate::app::{LanguageState, Message};
+use crate::theme;
+
+pub fn view(state: &LanguageState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-language")).size(20).color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    content = content.push(
+        text(t!("settings-language-hint"))
+            .size(13)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+
+    let auto_label = t!(
+        "settings-language-auto",
+        detected = language_name(i18n::resolve(&i18n::detect_locale()))
+    );
+    let options = std::iter::once(("auto".to_owned(), auto_label)).chain(
+        i18n::LANGUAGES
+            .iter()
+            .map(|(code, name)| ((*code).to_owned(), (*name).to_owned())),
+    );
+
+    let mut list = column![].spacing(6);
+    for (code, label) in options {
+        let is_selected = state.selected == code;
+        list = list.push(
+            button(text(label).size(14))
+                .on_press(Message::LanguageSelect(code))
+                .width(Length::Fill)
+                .padding([8, 12])
+                .style(if is_selected {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::sidebar_tab_inactive
+                }),
+        );
+    }
+    content = content.push(list);
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-language-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(
+            text(err).size(12).color(theme::SettingsColors::danger()),
+        );
+    }
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+/// Native name of a bundled language, or the code itself.
+fn language_name(code: &str) -> String {
+    i18n::LANGUAGES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map_or_else(|| code.to_owned(), |(_, name)| (*name).to_owned())
+}
@@ -0,0 +1,216 @@
+use aios_common::{McpServerConfig, McpTransport, TrustRequirement, t};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, text, text_input, toggler, Space,
+};
+use iced::{Element, Length};
+
+use crate::app::{McpState, McpTest, Message};
+use crate::theme;
+use crate::views::permissions::trust_label;
+
+/// A choice in the trust level dropdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TrustChoice(TrustRequirement);
+
+impl std::fmt::Display for TrustChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&trust_label(self.0))
+    }
+}
+
+const TRUST_CHOICES: [TrustChoice; 3] = [
+    TrustChoice(TrustRequirement::None),
+    TrustChoice(TrustRequirement::Confirm),
+    TrustChoice(TrustRequirement::DoubleConfirm),
+];
+
+/// What the server runs or connects to.
+fn target(server: &McpServerConfig) -> String {
+    match server.transport {
+        McpTransport::Stdio if server.args.is_empty() => server.command.clone(),
+        McpTransport::Stdio => format!("{} {}", server.command, server.args.join(" ")),
+        McpTransport::Sse => format!("SSE {}", server.url.as_deref().unwrap_or_default()),
+    }
+}
+
+pub fn view(state: &McpState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-mcp"))
+        .size(20)
+        .color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    content = content.push(
+        text(t!("settings-mcp-hint"))
+            .size(13)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+
+    if state.servers.is_empty() {
+        content = content.push(
+            text(t!("settings-mcp-none"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    }
+
+    for (name, server) in &state.servers {
+        let toggle = toggler(server.enabled)
+            .on_toggle(move |enabled| Message::McpToggle(name.clone(), enabled));
+        let trust = pick_list(
+            TRUST_CHOICES,
+            Some(TrustChoice(server.trust)),
+            move |choice| Message::McpTrust(name.clone(), choice.0),
+        )
+        .text_size(12)
+        .width(150);
+        let testing = matches!(state.tests.get(name), Some(McpTest::Running));
+
+        let header = row![
+            toggle,
+            column![
+                text(name)
+                    .size(14)
+                    .color(theme::SettingsColors::text_primary()),
+                text(target(server))
+                    .size(11)
+                    .color(theme::SettingsColors::text_secondary()),
+            ]
+            .spacing(2),
+            Space::new().width(Length::Fill),
+            trust,
+            button(text(t!("settings-mcp-test")).size(12))
+                .on_press_maybe((!testing).then(|| Message::McpTest(name.clone())))
+                .padding([6, 10])
+                .style(theme::action_button),
+            button(text(t!("settings-mcp-remove")).size(12))
+                .on_press(Message::McpRemove(name.clone()))
+                .padding([6, 10])
+                .style(theme::danger_button),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let mut entry = column![header].spacing(6);
+        match state.tests.get(name) {
+            Some(McpTest::Running) => {
+                entry = entry.push(
+                    text(t!("settings-mcp-testing"))
+                        .size(12)
+                        .color(theme::SettingsColors::text_secondary()),
+                );
+            }
+            Some(McpTest::Tools(tools)) => {
+                entry = entry.push(
+                    text(t!("settings-mcp-tools", count = tools.len()))
+                        .size(12)
+                        .color(theme::SettingsColors::success()),
+                );
+                for tool in tools {
+                    entry = entry.push(
+                        text(format!("{} — {}", tool.name, tool.description))
+                            .size(11)
+                            .color(theme::SettingsColors::text_secondary()),
+                    );
+                }
+            }
+            Some(McpTest::Failed(error)) => {
+                entry = entry.push(text(error).size(12).color(theme::SettingsColors::danger()));
+            }
+            None => {}
+        }
+        content = content.push(
+            container(entry)
+                .width(Length::Fill)
+                .padding(10)
+                .style(theme::container_secondary),
+        );
+    }
+
+    // New server
+    content = content.push(
+        text(t!("settings-mcp-add"))
+            .size(14)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+    content = content.push(
+        text_input(&t!("settings-mcp-name"), &state.new_name)
+            .on_input(Message::McpNameChanged)
+            .padding(8)
+            .size(13),
+    );
+
+    let transports = [(McpTransport::Stdio, "stdio"), (McpTransport::Sse, "SSE")];
+    let mut transport_row = row![].spacing(8);
+    for (transport, label) in transports {
+        transport_row = transport_row.push(
+            button(text(label).size(13))
+                .on_press(Message::McpTransportSelected(transport))
+                .padding([6, 12])
+                .style(if state.new_server.transport == transport {
+                    theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::sidebar_tab_inactive
+                }),
+        );
+    }
+    content = content.push(transport_row);
+
+    match state.new_server.transport {
+        McpTransport::Stdio => {
+            content = content.push(
+                text_input(&t!("settings-mcp-command"), &state.new_server.command)
+                    .on_input(Message::McpCommandChanged)
+                    .padding(8)
+                    .size(13),
+            );
+            content = content.push(
+                text_input(&t!("settings-mcp-args"), &state.new_args)
+                    .on_input(Message::McpArgsChanged)
+                    .padding(8)
+                    .size(13),
+            );
+        }
+        McpTransport::Sse => {
+            content = content.push(
+                text_input(
+                    "http://localhost:8080/sse",
+                    state.new_server.url.as_deref().unwrap_or_default(),
+                )
+                .on_input(Message::McpUrlChanged)
+                .padding(8)
+                .size(13),
+            );
+            content = content.push(
+                text(t!("settings-mcp-sse-unsupported"))
+                    .size(12)
+                    .color(theme::SettingsColors::text_secondary()),
+            );
+        }
+    }
+
+    content = content.push(
+        button(text(t!("settings-mcp-add-button")).size(13))
+            .on_press(Message::McpAdd)
+            .padding([8, 16])
+            .style(theme::action_button),
+    );
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-mcp-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
@@ -1,5 +1,36 @@
+pub mod about;
+pub mod activity;
+pub mod agent;
 pub mod ai;
+pub mod appearance;
+pub mod audio;
+pub mod dock;
+pub mod input;
 pub mod sidebar;
 pub mod network;
 pub mod display;
+pub mod language;
+pub mod mcp;
 pub mod ollama;
+pub mod permissions;
+pub mod power;
+pub mod startup;
+
+use iced::widget::{container, text, tooltip};
+use iced::Element;
+
+use crate::app::Message;
+use crate::theme;
+
+/// `button` with a tooltip saying what it does, for buttons that only show
+/// a symbol.
+pub fn named<'a>(button: impl Into<Element<'a, Message>>, name: String) -> Element<'a, Message> {
+    tooltip(
+        button,
+        container(text(name).size(12))
+            .padding(6)
+            .style(theme::container_tooltip),
+        tooltip::Position::Top,
+    )
+    .into()
+}
@@ -1,14 +1,29 @@
+use aios_common::nmcli::SavedConnection;
+use aios_common::t;
 use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
 use iced::{Element, Length};
 
 use crate::app::{Message, NetworkState};
 use crate::theme;
 
+/// Frames of the scan spinner.
+const SPINNER: [&str; 8] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"];
+
+/// Readable name of an nmcli connection type.
+fn kind_label(connection: &SavedConnection) -> String {
+    match connection.kind.as_str() {
+        "802-11-wireless" => t!("settings-network-kind-wifi"),
+        "802-3-ethernet" => t!("settings-network-kind-ethernet"),
+        "vpn" | "wireguard" => t!("settings-network-kind-vpn"),
+        other => other.to_owned(),
+    }
+}
+
 pub fn view(state: &NetworkState) -> Element<'_, Message> {
-    let title = text("Network").size(20).color(theme::SettingsColors::TEXT_PRIMARY);
+    let title = text(t!("settings-tab-network")).size(20).color(theme::SettingsColors::text_primary());
 
-    let scan_btn = button(text("Scan").size(13))
-        .on_press(Message::WifiScan)
+    let scan_btn = button(text(t!("settings-network-scan")).size(13))
+        .on_press_maybe((!state.loading).then_some(Message::WifiScan))
         .padding([6, 14])
         .style(theme::action_button);
 
@@ -20,21 +35,29 @@ pub fn view(state: &NetworkState) -> Element<'_, Message> {
     // Status line
     if !state.status.is_empty() {
         content = content.push(
-            text(&state.status).size(12).color(theme::SettingsColors::TEXT_SECONDARY)
+            text(&state.status).size(12).color(theme::SettingsColors::text_secondary())
         );
     }
 
-    // Network list
-    if state.networks.is_empty() && !state.loading {
+    // Scan progress; networks already known stay listed meanwhile
+    if state.loading {
+        let frame = SPINNER[state.spinner % SPINNER.len()];
         content = content.push(
-            text("No networks found. Click Scan to search.")
+            text(format!("{frame} {}", t!("settings-network-scanning")))
                 .size(13)
-                .color(theme::SettingsColors::TEXT_SECONDARY),
-        );
-    } else if state.loading {
-        content = content.push(
-            text("Scanning...").size(13).color(theme::SettingsColors::ACCENT),
+                .color(theme::SettingsColors::accent()),
         );
+    }
+
+    // Network list
+    if state.networks.is_empty() {
+        if !state.loading {
+            content = content.push(
+                text(t!("settings-network-empty"))
+                    .size(13)
+                    .color(theme::SettingsColors::text_secondary()),
+            );
+        }
     } else {
         let mut list = column![].spacing(6);
         for net in &state.networks {
@@ -51,7 +74,7 @@ pub fn view(state: &NetworkState) -> Element<'_, Message> {
                 });
             list = list.push(net_row);
         }
-        content = content.push(scrollable(list).height(Length::Fill));
+        content = content.push(list);
     }
 
     // Password input + connect/disconnect buttons
@@ -60,11 +83,23 @@ pub fn view(state: &NetworkState) -> Element<'_, Message> {
 
         // Only show password input for secured networks
         let selected_net = state.networks.iter().find(|n| &n.ssid == ssid);
-        let is_secured = selected_net.map_or(false, |n| n.security != "--" && !n.security.is_empty());
-        let is_connected = selected_net.map_or(false, |n| n.connected);
+        let is_secured = selected_net.is_some_and(|n| n.is_secured());
+        let is_enterprise = selected_net.is_some_and(|n| n.is_enterprise());
+        let is_connected = selected_net.is_some_and(|n| n.connected);
+
+        if is_enterprise && !is_connected {
+            action_row = action_row.push(
+                text_input(&t!("settings-network-identity"), &state.identity_input)
+                    .on_input(Message::IdentityChanged)
+                    .padding(8)
+                    .size(13)
+                    .width(200)
+                    .style(theme::input_style),
+            );
+        }
 
         if is_secured && !is_connected {
-            let pwd_input = text_input("Password...", &state.password_input)
+            let pwd_input = text_input(&t!("settings-network-password"), &state.password_input)
                 .on_input(Message::PasswordChanged)
                 .on_submit(Message::WifiConnect)
                 .secure(true)
@@ -77,14 +112,14 @@ pub fn view(state: &NetworkState) -> Element<'_, Message> {
 
         if is_connected {
             action_row = action_row.push(
-                button(text("Disconnect").size(13))
+                button(text(t!("settings-network-disconnect")).size(13))
                     .on_press(Message::WifiDisconnect)
                     .padding([6, 14])
                     .style(theme::danger_button),
             );
         } else {
             action_row = action_row.push(
-                button(text("Connect").size(13))
+                button(text(t!("settings-network-connect")).size(13))
                     .on_press(Message::WifiConnect)
                     .padding([6, 14])
                     .style(theme::action_button),
@@ -97,11 +132,94 @@ pub fn view(state: &NetworkState) -> Element<'_, Message> {
     // Error display
     if let Some(err) = &state.error {
         content = content.push(
-            text(err).size(12).color(theme::SettingsColors::DANGER),
+            text(err).size(12).color(theme::SettingsColors::danger()),
+        );
+    }
+
+    // Hidden network
+    content = content.push(
+        text(t!("settings-network-hidden"))
+            .size(14)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+    let join = (!state.hidden_ssid.trim().is_empty()).then_some(Message::WifiJoinHidden);
+    content = content.push(
+        row![
+            text_input(&t!("settings-network-hidden-ssid"), &state.hidden_ssid)
+                .on_input(Message::HiddenSsidChanged)
+                .padding(8)
+                .size(13)
+                .width(200)
+                .style(theme::input_style),
+            text_input(&t!("settings-network-identity-optional"), &state.hidden_identity)
+                .on_input(Message::HiddenIdentityChanged)
+                .padding(8)
+                .size(13)
+                .width(160)
+                .style(theme::input_style),
+            text_input(&t!("settings-network-password"), &state.hidden_password)
+                .on_input(Message::HiddenPasswordChanged)
+                .on_submit_maybe(join.clone())
+                .secure(true)
+                .padding(8)
+                .size(13)
+                .width(200)
+                .style(theme::input_style),
+            button(text(t!("settings-network-join")).size(13))
+                .on_press_maybe(join)
+                .padding([6, 14])
+                .style(theme::action_button),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center),
+    );
+
+    // Saved connection profiles
+    content = content.push(
+        text(t!("settings-network-saved"))
+            .size(14)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+    if state.saved.is_empty() {
+        content = content.push(
+            text(t!("settings-network-saved-empty"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    }
+    for connection in &state.saved {
+        let mut details = kind_label(connection);
+        if connection.active {
+            details = format!("{details} · {}", t!("settings-network-active"));
+        }
+        content = content.push(
+            container(
+                row![
+                    column![
+                        text(&connection.name)
+                            .size(13)
+                            .color(theme::SettingsColors::text_primary()),
+                        text(details)
+                            .size(11)
+                            .color(theme::SettingsColors::text_secondary()),
+                    ]
+                    .spacing(2),
+                    Space::new().width(Length::Fill),
+                    button(text(t!("settings-network-forget")).size(12))
+                        .on_press(Message::WifiForget(connection.uuid.clone()))
+                        .padding([6, 10])
+                        .style(theme::danger_button),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+            )
+            .width(Length::Fill)
+            .padding(10)
+            .style(theme::container_secondary),
         );
     }
 
-    container(content)
+    container(scrollable(content))
         .width(Length::Fill)
         .height(Length::Fill)
         .style(theme::container_primary)
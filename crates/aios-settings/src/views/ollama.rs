@@ -1,30 +1,108 @@
-use iced::widget::{button, column, container, row, scrollable, text, Space};
+use aios_common::t;
+use iced::widget::{button, column, container, progress_bar, row, scrollable, text, Space};
 use iced::{Element, Length};
 
-use crate::app::{Message, OllamaState};
+use crate::app::{Message, OllamaState, PullProgress};
+use crate::ollama::{format_size, LocalModel};
 use crate::theme;
 
+/// Parameters, quantization, size and date of an installed model.
+fn model_details(model: &LocalModel) -> String {
+    let mut details: Vec<String> = [&model.parameter_size, &model.quantization]
+        .into_iter()
+        .filter(|detail| !detail.is_empty())
+        .cloned()
+        .collect();
+    details.push(format_size(model.size));
+    if !model.modified.is_empty() {
+        details.push(t!(
+            "settings-ollama-modified",
+            date = model.modified.as_str()
+        ));
+    }
+    if model.loaded {
+        details.push(t!("settings-ollama-loaded"));
+    }
+    details.join(" · ")
+}
+
+/// Status and per-layer progress of a download.
+fn pull_view(pull: &PullProgress) -> Element<'_, Message> {
+    let mut status = t!("settings-ollama-pulling", model = pull.model.as_str());
+    if !pull.status.is_empty() {
+        status = format!("{status} {}", pull.status);
+    }
+    let mut content = column![
+        row![
+            text(status).size(12).color(theme::SettingsColors::accent()),
+            Space::new().width(Length::Fill),
+            button(text(t!("settings-ollama-cancel")).size(11))
+                .on_press(Message::OllamaCancelPull)
+                .padding([4, 8])
+                .style(theme::danger_button),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
+    ]
+    .spacing(6);
+
+    for layer in &pull.layers {
+        // "sha256:" and the rest of the digest add nothing for people.
+        let digest = layer.digest.trim_start_matches("sha256:");
+        let short = digest.get(..12).unwrap_or(digest);
+        content = content.push(
+            row![
+                text(short)
+                    .size(11)
+                    .color(theme::SettingsColors::text_secondary())
+                    .width(100),
+                container(progress_bar(
+                    0.0..=layer.total as f32,
+                    layer.completed as f32
+                ))
+                .height(6)
+                .width(Length::Fill),
+                text(format!(
+                    "{} / {}",
+                    format_size(layer.completed),
+                    format_size(layer.total)
+                ))
+                .size(11)
+                .color(theme::SettingsColors::text_secondary())
+                .width(140),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    content.into()
+}
+
 pub fn view(state: &OllamaState) -> Element<'_, Message> {
-    let title = text("Ollama").size(20).color(theme::SettingsColors::TEXT_PRIMARY);
+    let title = text("Ollama").size(20).color(theme::SettingsColors::text_primary());
 
     let status_color = if state.running {
-        theme::SettingsColors::SUCCESS
+        theme::SettingsColors::success()
     } else {
-        theme::SettingsColors::DANGER
+        theme::SettingsColors::danger()
+    };
+    let status_text = if state.running {
+        t!("settings-ollama-running")
+    } else {
+        t!("settings-ollama-stopped")
     };
-    let status_text = if state.running { "Running" } else { "Stopped" };
 
     let header = row![
         title,
         Space::new().width(8),
         text(status_text).size(12).color(status_color),
         Space::new().width(Length::Fill),
-        button(text("Refresh").size(13))
+        button(text(t!("settings-refresh")).size(13))
             .on_press(Message::OllamaRefresh)
             .padding([6, 14])
             .style(theme::action_button),
         Space::new().width(4),
-        button(text(if state.running { "Stop" } else { "Start" }).size(13))
+        button(text(if state.running { t!("settings-ollama-stop") } else { t!("settings-ollama-start") }).size(13))
             .on_press(if state.running { Message::OllamaStop } else { Message::OllamaStart })
             .padding([6, 14])
             .style(if state.running { theme::danger_button as fn(&iced::Theme, _) -> _ } else { theme::action_button }),
@@ -34,52 +112,114 @@ pub fn view(state: &OllamaState) -> Element<'_, Message> {
     let mut content = column![header].spacing(12).padding(16);
 
     // Installed models
+    let disk_total: u64 = state.models.iter().map(|model| model.size).sum();
     content = content.push(
-        text("Installed Models").size(16).color(theme::SettingsColors::TEXT_PRIMARY),
+        row![
+            text(t!("settings-ollama-installed"))
+                .size(16)
+                .color(theme::SettingsColors::text_primary()),
+            Space::new().width(Length::Fill),
+            text(t!(
+                "settings-ollama-disk-total",
+                size = format_size(disk_total)
+            ))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+        ]
+        .align_y(iced::Alignment::Center),
     );
 
     if state.models.is_empty() {
         content = content.push(
-            text("No models installed.")
+            text(t!("settings-ollama-none-installed"))
                 .size(13)
-                .color(theme::SettingsColors::TEXT_SECONDARY),
+                .color(theme::SettingsColors::text_secondary()),
         );
     } else {
         let mut list = column![].spacing(4);
         for model in &state.models {
             let model_row = row![
-                text(model).size(13).color(theme::SettingsColors::TEXT_PRIMARY),
+                column![
+                    text(&model.name)
+                        .size(13)
+                        .color(theme::SettingsColors::text_primary()),
+                    text(model_details(model))
+                        .size(11)
+                        .color(theme::SettingsColors::text_secondary()),
+                ]
+                .spacing(2),
                 Space::new().width(Length::Fill),
-                button(text("Remove").size(11))
-                    .on_press(Message::OllamaRemove(model.clone()))
+                button(text(t!("settings-ollama-remove")).size(11))
+                    .on_press(Message::OllamaRemove(model.name.clone()))
                     .padding([4, 8])
                     .style(theme::danger_button),
             ]
             .align_y(iced::Alignment::Center);
             list = list.push(model_row);
         }
-        content = content.push(scrollable(list).height(Length::Fill));
+        content = content.push(list);
     }
 
     // Pull popular models
     content = content.push(
-        text("Pull Model").size(16).color(theme::SettingsColors::TEXT_PRIMARY),
+        text(t!("settings-ollama-pull")).size(16).color(theme::SettingsColors::text_primary()),
     );
 
+    // The model sized for this machine, unless it is installed already.
+    if let Some(memory) = &state.memory {
+        let recommended = memory.recommended_model();
+        let mut recommendation = row![
+            text(t!(
+                "hardware-recommended",
+                memory = memory.describe(),
+                model = recommended
+            ))
+            .size(13)
+            .color(theme::SettingsColors::text_secondary()),
+            Space::new().width(Length::Fill),
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center);
+        if !state.models.iter().any(|model| model.name == recommended) {
+            let pull = state
+                .pull
+                .is_none()
+                .then(|| Message::OllamaPull(recommended.to_owned()));
+            recommendation = recommendation.push(
+                button(text(recommended).size(12))
+                    .padding([6, 12])
+                    .style(theme::action_button)
+                    .on_press_maybe(pull),
+            );
+        }
+        content = content.push(recommendation);
+    }
+
     if state.available_models.is_empty() {
         content = content.push(
-            text("No models available. Press Refresh.")
+            text(t!("settings-ollama-none-available"))
                 .size(13)
-                .color(theme::SettingsColors::TEXT_SECONDARY),
+                .color(theme::SettingsColors::text_secondary()),
         );
     } else {
         let mut pull_grid = column![].spacing(6);
         let mut current_row = row![].spacing(6);
+        let mut oversized = false;
         for (i, model) in state.available_models.iter().enumerate() {
+            let pull = state.pull.is_none().then(|| Message::OllamaPull(model.clone()));
+            // Models likely too large for this machine are marked.
+            let fits = state
+                .memory
+                .is_none_or(|memory| memory.warning(model, None).is_none());
+            oversized |= !fits;
             let btn = button(text(model.as_str()).size(12))
                 .padding([6, 12])
-                .style(theme::action_button)
-                .on_press(Message::OllamaPull(model.clone()));
+                .style(if fits {
+                    theme::action_button as fn(&iced::Theme, _) -> _
+                } else {
+                    theme::danger_button
+                })
+                .on_press_maybe(pull);
 
             current_row = current_row.push(btn);
 
@@ -92,22 +232,33 @@ pub fn view(state: &OllamaState) -> Element<'_, Message> {
             pull_grid = pull_grid.push(current_row);
         }
         content = content.push(pull_grid);
+        if oversized {
+            content = content.push(
+                text(t!("settings-ollama-oversized"))
+                    .size(12)
+                    .color(theme::SettingsColors::text_secondary()),
+            );
+        }
+    }
+
+    if let Some(pull) = &state.pull {
+        content = content.push(pull_view(pull));
     }
 
     // Status/progress message
     if let Some(msg) = &state.progress {
         content = content.push(
-            text(msg).size(12).color(theme::SettingsColors::ACCENT),
+            text(msg).size(12).color(theme::SettingsColors::accent()),
         );
     }
 
     if let Some(err) = &state.error {
         content = content.push(
-            text(err).size(12).color(theme::SettingsColors::DANGER),
+            text(err).size(12).color(theme::SettingsColors::danger()),
         );
     }
 
-    container(content)
+    container(scrollable(content))
         .width(Length::Fill)
         .height(Length::Fill)
         .style(theme::container_primary)
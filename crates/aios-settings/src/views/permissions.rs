@@ -0,0 +1,118 @@
+use aios_common::{TrustRequirement, t};
+use iced::widget::{column, container, pick_list, row, scrollable, text, toggler, Space};
+use iced::{Element, Length};
+
+use crate::app::{Message, PermissionsState};
+use crate::theme;
+
+/// A choice in the trust level dropdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TrustChoice {
+    /// `None` follows the tool's own level.
+    trust: Option<TrustRequirement>,
+    default: TrustRequirement,
+}
+
+impl std::fmt::Display for TrustChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.trust {
+            Some(trust) => f.write_str(&trust_label(trust)),
+            None => f.write_str(&t!(
+                "settings-permissions-default",
+                level = trust_label(self.default)
+            )),
+        }
+    }
+}
+
+pub(crate) fn trust_label(trust: TrustRequirement) -> String {
+    match trust {
+        TrustRequirement::None => t!("settings-permissions-trust-none"),
+        TrustRequirement::Confirm => t!("settings-permissions-trust-confirm"),
+        TrustRequirement::DoubleConfirm => t!("settings-permissions-trust-double"),
+    }
+}
+
+pub fn view(state: &PermissionsState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-permissions"))
+        .size(20)
+        .color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    content = content.push(
+        text(t!("settings-permissions-hint"))
+            .size(13)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+
+    let mut list = column![].spacing(10);
+    for tool in &state.tools {
+        let policy = state.policy(&tool.name);
+        let default = tool.trust_requirement;
+        let choices: Vec<TrustChoice> = [
+            None,
+            Some(TrustRequirement::None),
+            Some(TrustRequirement::Confirm),
+            Some(TrustRequirement::DoubleConfirm),
+        ]
+        .into_iter()
+        .map(|trust| TrustChoice { trust, default })
+        .collect();
+        let selected = TrustChoice {
+            trust: policy.trust,
+            default,
+        };
+
+        let name = tool.name.clone();
+        let toggle = toggler(policy.enabled)
+            .on_toggle(move |enabled| Message::PermissionsToggle(name.clone(), enabled));
+
+        let name = tool.name.clone();
+        let trust = pick_list(choices, Some(selected), move |choice: TrustChoice| {
+            Message::PermissionsTrust(name.clone(), choice.trust)
+        })
+        .text_size(12)
+        .width(200);
+
+        let label_color = if policy.enabled {
+            theme::SettingsColors::text_primary()
+        } else {
+            theme::SettingsColors::text_secondary()
+        };
+        let tool_row = row![
+            toggle,
+            column![
+                text(tool.name.as_str()).size(13).color(label_color),
+                text(tool.description.as_str())
+                    .size(11)
+                    .color(theme::SettingsColors::text_secondary()),
+            ]
+            .spacing(2),
+            Space::new().width(Length::Fill),
+            trust,
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center);
+        list = list.push(tool_row);
+    }
+    content = content.push(scrollable(list).height(Length::Fill));
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-permissions-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
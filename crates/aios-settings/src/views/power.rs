@@ -0,0 +1,129 @@
+use aios_common::{LidAction, t};
+use iced::widget::{button, column, container, row, scrollable, text, Row, Space};
+use iced::{Element, Length};
+
+use crate::app::{Message, PowerState};
+use crate::theme;
+
+/// Timeout choices in minutes; `0` is "Never".
+const TIMEOUTS: [u32; 7] = [1, 5, 10, 15, 30, 60, 0];
+
+pub fn view(state: &PowerState) -> Element<'_, Message> {
+    let title = text(t!("settings-tab-power"))
+        .size(20)
+        .color(theme::SettingsColors::text_primary());
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    content = content.push(label(t!("settings-power-screen-off")));
+    content = content.push(timeouts(
+        state.config.screen_off_minutes,
+        Message::PowerScreenOff,
+    ));
+
+    content = content.push(label(t!("settings-power-suspend")));
+    content = content.push(timeouts(
+        state.config.suspend_minutes,
+        Message::PowerSuspend,
+    ));
+
+    content = content.push(label(t!("settings-power-lid")));
+    let lid_actions = [
+        (LidAction::Suspend, t!("settings-power-lid-suspend")),
+        (LidAction::ScreenOff, t!("settings-power-lid-screen-off")),
+        (LidAction::Nothing, t!("settings-power-lid-nothing")),
+    ];
+    content = content.push(
+        Row::with_children(lid_actions.into_iter().map(|(action, name)| {
+            choice(
+                name,
+                state.config.lid_close == action,
+                Message::PowerLidClose(action),
+            )
+        }))
+        .spacing(8),
+    );
+
+    content = content.push(label(t!("settings-power-profile")));
+    if state.profiles.is_empty() {
+        content = content.push(
+            text(t!("settings-power-no-profiles"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    } else {
+        content = content.push(
+            Row::with_children(state.profiles.iter().map(|profile| {
+                choice(
+                    profile_name(profile),
+                    state.active_profile.as_ref() == Some(profile),
+                    Message::PowerSelectProfile(profile.clone()),
+                )
+            }))
+            .spacing(8),
+        );
+    }
+
+    if state.saved {
+        content = content.push(
+            text(t!("settings-power-saved"))
+                .size(12)
+                .color(theme::SettingsColors::success()),
+        );
+    }
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+fn label(title: String) -> Element<'static, Message> {
+    column![
+        Space::new().height(4),
+        text(title)
+            .size(14)
+            .color(theme::SettingsColors::text_secondary()),
+    ]
+    .into()
+}
+
+fn timeouts(selected: u32, on_press: fn(u32) -> Message) -> Element<'static, Message> {
+    Row::with_children(TIMEOUTS.into_iter().map(|minutes| {
+        let name = if minutes == 0 {
+            t!("settings-power-never")
+        } else {
+            t!("settings-power-minutes", minutes = minutes)
+        };
+        choice(name, selected == minutes, on_press(minutes))
+    }))
+    .spacing(8)
+    .into()
+}
+
+fn choice(name: String, active: bool, on_press: Message) -> Element<'static, Message> {
+    button(text(name).size(13))
+        .on_press(on_press)
+        .padding([6, 12])
+        .style(if active {
+            theme::sidebar_tab_active as fn(&iced::Theme, _) -> _
+        } else {
+            theme::action_button
+        })
+        .into()
+}
+
+/// Localized name of a power-profiles-daemon profile.
+fn profile_name(profile: &str) -> String {
+    match profile {
+        "performance" => t!("settings-power-performance"),
+        "balanced" => t!("settings-power-balanced"),
+        "power-saver" => t!("settings-power-saver"),
+        other => other.to_owned(),
+    }
+}
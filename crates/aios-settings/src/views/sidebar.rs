@@ -1,20 +1,15 @@
-use iced::widget::{button, column, container, text};
+use aios_common::t;
+use iced::widget::{button, column, container, scrollable, text};
 use iced::{Element, Length};
 
 use crate::app::{Message, Tab};
 use crate::theme;
 
+/// The tab list. Ctrl+Tab and Ctrl+Shift+Tab go through it in this order.
 pub fn view(active_tab: Tab) -> Element<'static, Message> {
-    let tabs = [
-        (Tab::Network, "Network"),
-        (Tab::Display, "Display"),
-        (Tab::Ollama, "Ollama"),
-        (Tab::Ai, "AI Provider"),
-    ];
-
     let mut col = column![].spacing(4).padding(8);
 
-    for (tab, label) in tabs {
+    for tab in Tab::ALL {
         let style = if tab == active_tab {
             theme::sidebar_tab_active as fn(&iced::Theme, button::Status) -> button::Style
         } else {
@@ -22,7 +17,7 @@ pub fn view(active_tab: Tab) -> Element<'static, Message> {
         };
 
         col = col.push(
-            button(text(label).size(14))
+            button(text(label(tab)).size(14))
                 .on_press(Message::SwitchTab(tab))
                 .width(Length::Fill)
                 .padding([8, 12])
@@ -30,9 +25,31 @@ pub fn view(active_tab: Tab) -> Element<'static, Message> {
         );
     }
 
-    container(col)
+    // More tabs than fit the default window height.
+    container(scrollable(col))
         .width(140)
         .height(Length::Fill)
         .style(theme::container_sidebar)
         .into()
 }
+
+fn label(tab: Tab) -> String {
+    match tab {
+        Tab::Network => t!("settings-tab-network"),
+        Tab::Display => t!("settings-tab-display"),
+        Tab::Audio => t!("settings-tab-audio"),
+        Tab::Power => t!("settings-tab-power"),
+        Tab::Input => t!("settings-tab-input"),
+        Tab::Ollama => "Ollama".to_owned(),
+        Tab::Ai => t!("settings-tab-ai"),
+        Tab::Agent => t!("settings-tab-agent"),
+        Tab::Activity => t!("settings-tab-activity"),
+        Tab::Language => t!("settings-tab-language"),
+        Tab::Appearance => t!("settings-tab-appearance"),
+        Tab::Dock => t!("settings-tab-dock"),
+        Tab::Startup => t!("settings-tab-startup"),
+        Tab::Permissions => t!("settings-tab-permissions"),
+        Tab::Mcp => t!("settings-tab-mcp"),
+        Tab::About => t!("settings-tab-about"),
+    }
+}
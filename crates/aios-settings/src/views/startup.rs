@@ -0,0 +1,261 @@
+use aios_common::desktop::DesktopEntry;
+use aios_common::{t, ComponentState, ComponentStatus};
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, toggler, Space};
+use iced::{Element, Length};
+
+use crate::app::{Message, StartupState};
+use crate::startup::Service;
+use crate::theme;
+
+/// An entry of the "add application" list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AppChoice {
+    id: String,
+    name: String,
+}
+
+impl std::fmt::Display for AppChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+pub fn view<'a>(state: &'a StartupState, apps: &'a [DesktopEntry]) -> Element<'a, Message> {
+    let title = row![
+        text(t!("settings-tab-startup"))
+            .size(20)
+            .color(theme::SettingsColors::text_primary()),
+        Space::new().width(Length::Fill),
+        button(text(t!("settings-refresh")).size(13))
+            .on_press(Message::StartupRefresh)
+            .padding([6, 14])
+            .style(theme::action_button),
+    ]
+    .align_y(iced::Alignment::Center);
+
+    let mut content = column![title].spacing(12).padding(16);
+
+    // AIOS services
+    content = content.push(heading(t!("settings-startup-services")));
+    content = content.push(
+        text(t!("settings-startup-services-hint"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+    let mut services = column![].spacing(6);
+    for service in &state.services {
+        services = services.push(service_row(service));
+    }
+    content = content.push(services);
+
+    // Components run by aios-session
+    content = content.push(heading(t!("settings-startup-components")));
+    content = content.push(
+        text(t!("settings-startup-components-hint"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+    );
+    match &state.components {
+        Ok(components) => {
+            let mut rows = column![].spacing(6);
+            for component in components {
+                rows = rows.push(component_row(component));
+            }
+            content = content.push(rows);
+        }
+        Err(_) => {
+            content = content.push(
+                text(t!("settings-startup-session-down"))
+                    .size(13)
+                    .color(theme::SettingsColors::text_secondary()),
+            );
+        }
+    }
+
+    // Autostart applications
+    content = content.push(heading(t!("settings-startup-apps")));
+    if state.entries.is_empty() {
+        content = content.push(
+            text(t!("settings-startup-apps-none"))
+                .size(13)
+                .color(theme::SettingsColors::text_secondary()),
+        );
+    } else {
+        let mut entries = column![].spacing(4);
+        for entry in &state.entries {
+            let id = entry.id.clone();
+            let mut entry_row = row![
+                column![
+                    text(entry.name.as_str())
+                        .size(13)
+                        .color(theme::SettingsColors::text_primary()),
+                    text(entry.exec.as_str())
+                        .size(11)
+                        .color(theme::SettingsColors::text_secondary()),
+                ]
+                .spacing(2),
+                Space::new().width(Length::Fill),
+                toggler(entry.enabled)
+                    .on_toggle(move |enabled| Message::StartupEntryEnabled(id.clone(), enabled)),
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center);
+            if entry.user_only {
+                entry_row = entry_row.push(
+                    button(text(t!("settings-startup-remove")).size(11))
+                        .on_press(Message::StartupRemoveEntry(entry.id.clone()))
+                        .padding([4, 8])
+                        .style(theme::danger_button),
+                );
+            }
+            entries = entries.push(entry_row);
+        }
+        content = content.push(entries);
+    }
+
+    let addable: Vec<AppChoice> = apps
+        .iter()
+        .filter(|app| !state.entries.iter().any(|entry| entry.id == app.id))
+        .map(|app| AppChoice {
+            id: app.id.clone(),
+            name: app.name.clone(),
+        })
+        .collect();
+    content = content.push(
+        pick_list(addable, None::<AppChoice>, |choice| {
+            Message::StartupAddEntry(choice.id)
+        })
+        .placeholder(t!("settings-startup-add"))
+        .text_size(13)
+        .width(280),
+    );
+
+    if let Some(err) = &state.error {
+        content = content.push(text(err).size(12).color(theme::SettingsColors::danger()));
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::container_primary)
+        .into()
+}
+
+/// Name, status, "at login" switch and start/stop of a service.
+fn service_row(service: &Service) -> Element<'_, Message> {
+    let (status, color) = match service.state.as_str() {
+        "failed" => (
+            t!("settings-startup-state-failed"),
+            theme::SettingsColors::danger(),
+        ),
+        _ if service.running() => (
+            t!("settings-startup-state-active"),
+            theme::SettingsColors::success(),
+        ),
+        _ => (
+            t!("settings-startup-state-inactive"),
+            theme::SettingsColors::text_secondary(),
+        ),
+    };
+    let running = service.running();
+    let name = service.name.clone();
+    row![
+        text(service.name.as_str())
+            .size(13)
+            .color(theme::SettingsColors::text_primary())
+            .width(120),
+        text(status).size(12).color(color),
+        Space::new().width(Length::Fill),
+        text(t!("settings-startup-at-login"))
+            .size(12)
+            .color(theme::SettingsColors::text_secondary()),
+        toggler(service.enabled)
+            .on_toggle(move |enabled| Message::StartupServiceEnabled(name.clone(), enabled)),
+        button(
+            text(if running {
+                t!("settings-startup-stop")
+            } else {
+                t!("settings-startup-start")
+            })
+            .size(11)
+        )
+        .on_press(Message::StartupServiceRun(service.name.clone(), !running))
+        .padding([4, 8])
+        .style(if running {
+            theme::danger_button as fn(&iced::Theme, _) -> _
+        } else {
+            theme::action_button
+        }),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Name, state, restarts and start/stop of a component of aios-session.
+fn component_row(component: &ComponentStatus) -> Element<'_, Message> {
+    let (status, color) = match component.state {
+        ComponentState::Starting => (
+            t!("settings-startup-state-starting"),
+            theme::SettingsColors::text_secondary(),
+        ),
+        ComponentState::Running => (
+            t!("settings-startup-state-active"),
+            theme::SettingsColors::success(),
+        ),
+        ComponentState::Restarting => (
+            t!("settings-startup-state-restarting"),
+            theme::SettingsColors::danger(),
+        ),
+        ComponentState::Stopped => (
+            t!("settings-startup-state-inactive"),
+            theme::SettingsColors::text_secondary(),
+        ),
+    };
+    let running = component.state != ComponentState::Stopped;
+    let mut details = column![text(status).size(12).color(color)].spacing(2);
+    if let Some(exit) = &component.last_exit {
+        details = details.push(
+            text(t!(
+                "settings-startup-restarts",
+                count = component.restarts,
+                exit = exit.as_str()
+            ))
+            .size(11)
+            .color(theme::SettingsColors::text_secondary()),
+        );
+    }
+    row![
+        text(component.name.as_str())
+            .size(13)
+            .color(theme::SettingsColors::text_primary())
+            .width(120),
+        details,
+        Space::new().width(Length::Fill),
+        button(
+            text(if running {
+                t!("settings-startup-stop")
+            } else {
+                t!("settings-startup-start")
+            })
+            .size(11)
+        )
+        .on_press(Message::StartupComponentRun(component.name.clone(), !running))
+        .padding([4, 8])
+        .style(if running {
+            theme::danger_button as fn(&iced::Theme, _) -> _
+        } else {
+            theme::action_button
+        }),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+fn heading(title: String) -> Element<'static, Message> {
+    text(title)
+        .size(16)
+        .color(theme::SettingsColors::text_primary())
+        .into()
+}
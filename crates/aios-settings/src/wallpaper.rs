@@ -0,0 +1,61 @@
+//! Desktop wallpaper, drawn by sway on every output. The choice is also
+//! written to a sway snippet so it is back after the next login.
+
+use std::path::Path;
+
+use crate::commands::{self, CmdResult};
+use crate::generated;
+
+/// Images sway's background helper can draw.
+const EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+
+/// Wallpapers shipped with the system and the user's own in
+/// `~/Pictures/Wallpapers`, sorted by path.
+pub fn available() -> Vec<String> {
+    let mut found = Vec::new();
+    collect(Path::new("/usr/share/backgrounds"), 2, &mut found);
+    if let Some(pictures) = dirs::picture_dir() {
+        collect(&pictures.join("Wallpapers"), 1, &mut found);
+    }
+    found.sort();
+    found
+}
+
+/// Images in `dir`, looking `depth` levels deep.
+fn collect(dir: &Path, depth: usize, found: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            if depth > 1 {
+                collect(&path, depth - 1, found);
+            }
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            found.push(path.to_string_lossy().into_owned());
+        }
+    }
+}
+
+/// Show `wallpaper` on every output, or fill them with `color` (`#rrggbb`)
+/// when there is none, and keep it for the next login.
+pub async fn apply(wallpaper: Option<&str>, color: &str) -> CmdResult {
+    let command = match wallpaper {
+        Some(path) => format!("output * bg \"{}\" fill", path.replace('"', "\\\"")),
+        None => format!("output * bg {color} solid_color"),
+    };
+    if let Err(e) = generated::write(
+        &generated::sway_snippet("aios-wallpaper"),
+        std::slice::from_ref(&command),
+    ) {
+        return CmdResult {
+            success: false,
+            output: format!("Write error: {e}"),
+        };
+    }
+    commands::sway_command(&command).await
+}
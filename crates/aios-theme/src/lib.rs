@@ -0,0 +1,336 @@
+//! Shared color palette and sizing for the AIOS desktop apps.
+//!
+//! Chat, dock, confirm and settings style their widgets from [`palette()`]
+//! and [`metrics()`], which follow the `[ui]` section of
+//! `~/.config/aios/agent.toml`:
+//!
+//! ```toml
+//! [ui]
+//! color_scheme = "light"   # or "dark"
+//! accent = "#7aa2f7"       # optional
+//! font_scale = 1.15
+//! density = "compact"      # or "comfortable"
+//! ```
+//!
+//! Each app calls [`init_from_config`] at startup, returns [`iced_theme()`]
+//! from its theme function and listens to [`subscription()`], which re-reads
+//! the config when it changes so edits made in settings apply live.
+
+use std::ops::RangeInclusive;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+use aios_common::{ColorScheme, Density, UiConfig};
+use futures::SinkExt;
+use futures::channel::mpsc;
+use iced::{Color, Subscription};
+
+/// How often [`subscription()`] checks the config file for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Accent presets offered by the settings app: `(name, hex)`.
+pub const ACCENT_PRESETS: &[(&str, &str)] = &[
+    ("blue", "#7890ff"),
+    ("purple", "#bb9af7"),
+    ("teal", "#2ac3de"),
+    ("green", "#4dc778"),
+    ("orange", "#ff9e64"),
+    ("pink", "#f7768e"),
+];
+
+/// Allowed `font_scale` values; anything outside is clamped.
+pub const FONT_SCALE_RANGE: RangeInclusive<f32> = 0.8..=1.6;
+
+/// Font scale presets offered by the settings app.
+pub const FONT_SCALES: &[f32] = &[0.9, 1.0, 1.15, 1.3, 1.5];
+
+static PALETTE: RwLock<Palette> = RwLock::new(Palette::dark());
+static METRICS: RwLock<Metrics> = RwLock::new(Metrics::standard());
+
+/// Colors shared by every AIOS window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub bg_primary: Color,
+    pub bg_secondary: Color,
+    pub bg_sidebar: Color,
+    pub bg_input: Color,
+    /// Background for code blocks and command previews.
+    pub bg_code: Color,
+    pub text_primary: Color,
+    pub text_secondary: Color,
+    pub accent: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub user_bubble: Color,
+    pub assistant_bubble: Color,
+    pub border: Color,
+    pub is_dark: bool,
+}
+
+impl Palette {
+    /// Tokyo Night inspired dark palette (the default).
+    pub const fn dark() -> Self {
+        Self {
+            bg_primary: Color::from_rgb(0.10, 0.11, 0.15),
+            bg_secondary: Color::from_rgb(0.13, 0.14, 0.18),
+            bg_sidebar: Color::from_rgb(0.08, 0.09, 0.12),
+            bg_input: Color::from_rgb(0.16, 0.17, 0.22),
+            bg_code: Color::from_rgb(0.08, 0.08, 0.12),
+            text_primary: Color::from_rgb(0.87, 0.89, 0.93),
+            text_secondary: Color::from_rgb(0.55, 0.58, 0.65),
+            accent: Color::from_rgb(0.47, 0.56, 1.0),
+            success: Color::from_rgb(0.30, 0.78, 0.47),
+            warning: Color::from_rgb(1.0, 0.76, 0.03),
+            danger: Color::from_rgb(0.85, 0.30, 0.30),
+            user_bubble: Color::from_rgb(0.20, 0.25, 0.45),
+            assistant_bubble: Color::from_rgb(0.15, 0.16, 0.20),
+            border: Color::from_rgb(0.25, 0.27, 0.35),
+            is_dark: true,
+        }
+    }
+
+    /// Light counterpart of [`Palette::dark`].
+    pub const fn light() -> Self {
+        Self {
+            bg_primary: Color::from_rgb(0.97, 0.97, 0.98),
+            bg_secondary: Color::from_rgb(0.93, 0.94, 0.96),
+            bg_sidebar: Color::from_rgb(0.90, 0.91, 0.94),
+            bg_input: Color::from_rgb(1.0, 1.0, 1.0),
+            bg_code: Color::from_rgb(0.91, 0.92, 0.95),
+            text_primary: Color::from_rgb(0.13, 0.14, 0.19),
+            text_secondary: Color::from_rgb(0.40, 0.43, 0.50),
+            accent: Color::from_rgb(0.20, 0.35, 0.90),
+            success: Color::from_rgb(0.16, 0.58, 0.32),
+            warning: Color::from_rgb(0.80, 0.55, 0.0),
+            danger: Color::from_rgb(0.80, 0.20, 0.20),
+            user_bubble: Color::from_rgb(0.84, 0.88, 1.0),
+            assistant_bubble: Color::from_rgb(0.91, 0.92, 0.95),
+            border: Color::from_rgb(0.80, 0.82, 0.87),
+            is_dark: false,
+        }
+    }
+
+    /// Palette for a scheme, with an optional accent override.
+    pub fn from_config(config: &UiConfig) -> Self {
+        let base = match config.color_scheme {
+            ColorScheme::Dark => Self::dark(),
+            ColorScheme::Light => Self::light(),
+        };
+        match config.accent.as_deref() {
+            Some(hex) => match parse_hex(hex) {
+                Some(accent) => base.with_accent(accent),
+                None => {
+                    tracing::warn!(accent = hex, "Ignoring invalid accent color");
+                    base
+                }
+            },
+            None => base,
+        }
+    }
+
+    /// Replace the accent and re-derive the user bubble from it.
+    pub fn with_accent(self, accent: Color) -> Self {
+        let weight = if self.is_dark { 0.3 } else { 0.2 };
+        Self {
+            accent,
+            user_bubble: mix(self.bg_primary, accent, weight),
+            ..self
+        }
+    }
+
+    /// Accent for hovered buttons.
+    pub fn accent_hover(&self) -> Color {
+        mix(self.accent, Color::WHITE, 0.15)
+    }
+
+    /// Accent for pressed buttons.
+    pub fn accent_pressed(&self) -> Color {
+        mix(self.accent, Color::BLACK, 0.10)
+    }
+
+    /// Translucent foreground wash for hover highlights and subtle borders:
+    /// white on dark palettes, black on light ones.
+    pub fn overlay(&self, alpha: f32) -> Color {
+        let base = if self.is_dark { Color::WHITE } else { Color::BLACK };
+        with_alpha(base, alpha)
+    }
+
+    /// Background tinted towards `color`, used for status cards.
+    pub fn tint(&self, color: Color) -> Color {
+        mix(self.bg_primary, color, 0.15)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Text scale and spacing density.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    /// UI zoom factor, within [`FONT_SCALE_RANGE`].
+    pub font_scale: f32,
+    pub density: Density,
+}
+
+impl Metrics {
+    /// 100% scale, comfortable density.
+    pub const fn standard() -> Self {
+        Self {
+            font_scale: 1.0,
+            density: Density::Comfortable,
+        }
+    }
+
+    /// Metrics for `config`, with the scale clamped to a usable range.
+    pub fn from_config(config: &UiConfig) -> Self {
+        let font_scale = if config.font_scale.is_finite() {
+            config
+                .font_scale
+                .clamp(*FONT_SCALE_RANGE.start(), *FONT_SCALE_RANGE.end())
+        } else {
+            1.0
+        };
+        Self {
+            font_scale,
+            density: config.density,
+        }
+    }
+
+    /// Spacing or padding for the current density, given the comfortable
+    /// value.
+    pub fn space(&self, comfortable: f32) -> f32 {
+        match self.density {
+            Density::Comfortable => comfortable,
+            Density::Compact => (comfortable * 0.5).round(),
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// The palette currently in use.
+pub fn palette() -> Palette {
+    *PALETTE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The text scale and density currently in use.
+pub fn metrics() -> Metrics {
+    *METRICS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Window scale factor for apps that follow `font_scale`.
+pub fn scale_factor() -> f32 {
+    metrics().font_scale
+}
+
+/// Switch palette and metrics to match `config`.
+pub fn apply(config: &UiConfig) {
+    let palette = Palette::from_config(config);
+    *PALETTE.write().unwrap_or_else(|e| e.into_inner()) = palette;
+    let metrics = Metrics::from_config(config);
+    *METRICS.write().unwrap_or_else(|e| e.into_inner()) = metrics;
+    tracing::debug!(
+        scheme = ?config.color_scheme,
+        accent = ?config.accent,
+        font_scale = metrics.font_scale,
+        density = ?metrics.density,
+        "Appearance applied"
+    );
+}
+
+/// Read `[ui]` from the config file and apply it.
+pub fn init_from_config() {
+    apply(&UiConfig::load());
+}
+
+/// iced theme matching the current palette, for widgets without a custom
+/// style function (markdown, pick lists, scrollbars, ...).
+pub fn iced_theme() -> iced::Theme {
+    let palette = palette();
+    iced::Theme::custom(
+        if palette.is_dark { "AIOS Dark" } else { "AIOS Light" }.to_owned(),
+        iced::theme::Palette {
+            background: palette.bg_primary,
+            text: palette.text_primary,
+            primary: palette.accent,
+            success: palette.success,
+            warning: palette.warning,
+            danger: palette.danger,
+        },
+    )
+}
+
+/// Fires whenever the `[ui]` section of the config file changes.
+///
+/// The palette, metrics and UI language are already updated when the
+/// message arrives; the app only needs to redraw.
+pub fn subscription() -> Subscription<()> {
+    Subscription::run(watch)
+}
+
+fn watch() -> impl futures::Stream<Item = ()> {
+    iced::stream::channel(1, async move |mut output: mpsc::Sender<()>| {
+        let mut last_modified = modified();
+        let mut interval = tokio::time::interval(WATCH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let current = modified();
+            if current == last_modified {
+                continue;
+            }
+            last_modified = current;
+
+            let config = UiConfig::load();
+            apply(&config);
+            aios_common::i18n::set_language(&config.language);
+            if output.send(()).await.is_err() {
+                return;
+            }
+        }
+    })
+}
+
+fn modified() -> Option<SystemTime> {
+    std::fs::metadata(aios_common::config_path())
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// Parse `#rrggbb` (the `#` is optional).
+pub fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Format a color as `#rrggbb`.
+pub fn to_hex(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Linear blend from `a` (`t = 0`) to `b` (`t = 1`).
+pub fn mix(a: Color, b: Color, t: f32) -> Color {
+    Color {
+        r: a.r + (b.r - a.r) * t,
+        g: a.g + (b.g - a.g) * t,
+        b: a.b + (b.b - a.b) * t,
+        a: a.a + (b.a - a.a) * t,
+    }
+}
+
+/// `color` with its alpha replaced.
+pub fn with_alpha(color: Color, alpha: f32) -> Color {
+    Color { a: alpha, ..color }
+}
@@ -1,3 +1,5 @@
 //! Speech-to-text and text-to-speech for AIOS.
 
-// TODO: Phase 2 - whisper-rs + piper-rs
+pub mod stt;
+
+// TODO: Phase 2 - piper-rs text-to-speech
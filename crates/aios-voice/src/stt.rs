@@ -0,0 +1,173 @@
+//! Speech-to-text via the whisper.cpp command-line tool.
+//!
+//! Audio arrives from clients as 16 kHz mono signed 16-bit PCM. A
+//! [`TranscriptionSession`] accumulates it, and [`WhisperStt`] transcribes
+//! snapshots of the buffer by writing a temporary WAV file and running
+//! `whisper-cli` on it. Partial transcripts cover only the last
+//! [`PARTIAL_WINDOW_SAMPLES`] of audio, so each costs the same however long
+//! the session runs; the final one covers all of it.
+
+use std::path::PathBuf;
+
+use aios_common::VoiceConfig;
+use anyhow::{Context, Result};
+
+/// Sample rate expected by Whisper and sent by clients.
+pub const SAMPLE_RATE: u32 = 16_000;
+
+/// Minimum amount of new audio (in samples) between partial transcripts.
+const PARTIAL_INTERVAL_SAMPLES: usize = SAMPLE_RATE as usize;
+
+/// Audio transcribed for a partial transcript (30 seconds, the window
+/// Whisper works in anyway).
+const PARTIAL_WINDOW_SAMPLES: usize = SAMPLE_RATE as usize * 30;
+
+/// Upper bound on buffered audio per session (5 minutes).
+const MAX_SESSION_SAMPLES: usize = SAMPLE_RATE as usize * 300;
+
+/// Runs whisper.cpp on buffered PCM audio.
+#[derive(Debug, Clone)]
+pub struct WhisperStt {
+    binary: String,
+    model: PathBuf,
+    language: String,
+}
+
+impl WhisperStt {
+    /// Create a transcriber from the voice configuration.
+    pub fn new(config: &VoiceConfig) -> Self {
+        Self {
+            binary: config.whisper_binary.clone(),
+            model: PathBuf::from(&config.whisper_model),
+            language: config.language.clone(),
+        }
+    }
+
+    /// Transcribe `samples` and return the recognised text.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the temporary WAV cannot be written, the binary cannot be
+    /// started, or whisper.cpp exits unsuccessfully.
+    pub async fn transcribe(&self, samples: &[i16]) -> Result<String> {
+        if samples.is_empty() {
+            return Ok(String::new());
+        }
+
+        let wav_path =
+            std::env::temp_dir().join(format!("aios-stt-{}.wav", uuid::Uuid::new_v4()));
+        tokio::fs::write(&wav_path, encode_wav(samples))
+            .await
+            .with_context(|| format!("failed to write {}", wav_path.display()))?;
+
+        let output = tokio::process::Command::new(&self.binary)
+            .arg("-m")
+            .arg(&self.model)
+            .arg("-f")
+            .arg(&wav_path)
+            .args(["-l", &self.language, "--no-timestamps", "--no-prints"])
+            .output()
+            .await;
+
+        let _ = tokio::fs::remove_file(&wav_path).await;
+
+        let output =
+            output.with_context(|| format!("failed to run {} -- is whisper.cpp installed?", self.binary))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} exited with {}: {}",
+                self.binary,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(text)
+    }
+}
+
+/// Audio accumulated for one voice input session.
+#[derive(Debug, Default)]
+pub struct TranscriptionSession {
+    samples: Vec<i16>,
+    /// Buffer length at the time of the last partial transcript.
+    transcribed_len: usize,
+    /// A partial transcript is being made; no other starts until it is done.
+    partial_running: bool,
+}
+
+impl TranscriptionSession {
+    /// Create an empty session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append little-endian 16-bit PCM bytes. A trailing odd byte is dropped,
+    /// as is audio beyond the per-session cap.
+    pub fn push_pcm(&mut self, bytes: &[u8]) {
+        let room = MAX_SESSION_SAMPLES.saturating_sub(self.samples.len());
+        self.samples.extend(
+            bytes
+                .chunks_exact(2)
+                .take(room)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]])),
+        );
+    }
+
+    /// Whether enough new audio has arrived to warrant a partial transcript,
+    /// and none is running.
+    pub fn partial_due(&self) -> bool {
+        !self.partial_running
+            && self.samples.len() - self.transcribed_len >= PARTIAL_INTERVAL_SAMPLES
+    }
+
+    /// Copy the end of the buffer for a partial transcript and mark it as
+    /// transcribed. Call [`Self::partial_done`] once the transcript is made.
+    pub fn snapshot(&mut self) -> Vec<i16> {
+        self.transcribed_len = self.samples.len();
+        self.partial_running = true;
+        let start = self.samples.len().saturating_sub(PARTIAL_WINDOW_SAMPLES);
+        self.samples[start..].to_vec()
+    }
+
+    /// The partial transcript of the last [`Self::snapshot`] is done or
+    /// failed; the next one may start.
+    pub fn partial_done(&mut self) {
+        self.partial_running = false;
+    }
+
+    /// Consume the session, returning all buffered audio.
+    pub fn into_samples(self) -> Vec<i16> {
+        self.samples
+    }
+}
+
+/// Wrap mono 16-bit PCM in a minimal RIFF/WAVE container.
+fn encode_wav(samples: &[i16]) -> Vec<u8> {
+    let data_len = u32::try_from(samples.len() * 2).unwrap_or(u32::MAX);
+    let byte_rate = SAMPLE_RATE * 2;
+
+    let mut buf = Vec::with_capacity(44 + samples.len() * 2);
+    buf.extend_from_slice(b"RIFF");
+    buf.extend_from_slice(&data_len.saturating_add(36).to_le_bytes());
+    buf.extend_from_slice(b"WAVEfmt ");
+    buf.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    buf.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    buf.extend_from_slice(&1u16.to_le_bytes()); // mono
+    buf.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    buf.extend_from_slice(&byte_rate.to_le_bytes());
+    buf.extend_from_slice(&2u16.to_le_bytes()); // block align
+    buf.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    buf.extend_from_slice(b"data");
+    buf.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        buf.extend_from_slice(&sample.to_le_bytes());
+    }
+    buf
+}
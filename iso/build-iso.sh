@@ -33,7 +33,7 @@ fi
 
 # Verify that AIOS binaries are present in the chroot overlay.
 BINDIR="config/includes.chroot/usr/local/bin"
-EXPECTED_BINS="aios-agent aios-chat aios-dock aios-confirm"
+EXPECTED_BINS="aios-agent aios-chat aios-dock aios-confirm aios-session"
 MISSING=0
 for bin in $EXPECTED_BINS; do
     if [ ! -f "${BINDIR}/${bin}" ]; then